@@ -0,0 +1,495 @@
+//! Index administration for reindexing while serving traffic.
+//!
+//! The backend queries `ManticoreConfig::table_name`, which is managed as
+//! an alias: a distributed table with a single local member pointing at a
+//! versioned physical table (e.g. `trademarks_20260115`). Reindexing
+//! builds into a new physical table, validates it, then re-points the
+//! alias, so searches never see a partially built index. The previous
+//! target is remembered so the swap can be rolled back.
+//!
+//! Manticore cannot change a distributed table's members in place, so the
+//! swap drops and recreates the alias in back-to-back statements; a search
+//! landing in that window is retried once by the backend. The alias is
+//! only dropped once `SHOW CREATE TABLE` confirms it is missing or already
+//! distributed, so a deployment still serving from a physical table of
+//! that name is never dropped; migrate it to a versioned table first.
+
+use crate::{BackendError, ManticoreBackend};
+use std::sync::Mutex;
+
+/// Delay before retrying a search that hit a missing alias table.
+pub const SWAP_RETRY_DELAY_MS: u64 = 50;
+
+/// Check whether a Manticore error means the queried table is missing.
+pub(crate) fn is_unknown_table_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("unknown table")
+        || message.contains("unknown local table")
+        || message.contains("no such table")
+}
+
+/// Thresholds a new table must meet before it can serve traffic.
+#[derive(Debug, Clone)]
+pub struct ValidationPolicy {
+    /// Minimum number of documents in the new table
+    pub min_documents: u64,
+    /// Minimum size relative to the currently active table (0.0 - 1.0)
+    pub min_ratio_of_active: f64,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            min_documents: 1,
+            min_ratio_of_active: 0.9,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// Check document counts against the policy.
+    pub fn check(&self, documents: u64, active_documents: Option<u64>) -> Result<(), String> {
+        if documents < self.min_documents {
+            return Err(format!(
+                "{} documents, expected at least {}",
+                documents, self.min_documents
+            ));
+        }
+
+        if let Some(active) = active_documents {
+            let required = (active as f64 * self.min_ratio_of_active).ceil() as u64;
+            if documents < required {
+                return Err(format!(
+                    "{} documents is below {:.0}% of the active table ({})",
+                    documents,
+                    self.min_ratio_of_active * 100.0,
+                    active
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Result of validating a candidate table.
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    pub table: String,
+    pub documents: u64,
+    pub active_documents: Option<u64>,
+}
+
+/// Result of re-pointing the alias.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapOutcome {
+    pub alias: String,
+    /// Target before the swap, if the alias existed
+    pub previous: Option<String>,
+    /// Target after the swap
+    pub current: String,
+}
+
+/// Check that a table name is a plain identifier.
+pub fn validate_table_name(name: &str) -> Result<(), BackendError> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(BackendError::Alias(format!("Invalid table name '{}'", name)))
+    }
+}
+
+/// Statements that point `alias` at `target`.
+pub fn alias_statements(alias: &str, target: &str) -> [String; 2] {
+    [
+        format!("DROP TABLE IF EXISTS {}", alias),
+        format!("CREATE TABLE {} type='distributed' local='{}'", alias, target),
+    ]
+}
+
+/// What the alias name currently refers to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AliasState {
+    /// No table of that name
+    Missing,
+    /// A distributed table, with its local target if it has one
+    Alias(Option<String>),
+    /// A physical table, e.g. a deployment that predates aliasing
+    Physical,
+}
+
+impl AliasState {
+    /// The alias target, if the name is an alias with one.
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            Self::Alias(target) => target.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Refuse to replace `alias` unless it is missing or already an alias.
+fn check_replaceable(alias: &str, state: &AliasState) -> Result<(), BackendError> {
+    match state {
+        AliasState::Physical => Err(BackendError::Alias(format!(
+            "Refusing to replace '{}': it is a physical table, not an alias",
+            alias
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Refuse to drop the alias itself or the table it points at.
+fn check_droppable(table: &str, alias: &str, state: &AliasState) -> Result<(), BackendError> {
+    if table == alias {
+        return Err(BackendError::Alias(format!(
+            "Refusing to drop '{}': it is the alias",
+            table
+        )));
+    }
+    if state.target() == Some(table) {
+        return Err(BackendError::Alias(format!(
+            "Refusing to drop '{}': it is the active target",
+            table
+        )));
+    }
+    Ok(())
+}
+
+/// Classify a `SHOW CREATE TABLE` response.
+fn parse_alias_state(response: &serde_json::Value) -> AliasState {
+    match find_string(response, &|s| s.to_uppercase().starts_with("CREATE TABLE")) {
+        Some(stmt) if stmt.contains("type='distributed'") => {
+            AliasState::Alias(parse_alias_target(response))
+        }
+        Some(_) => AliasState::Physical,
+        None => AliasState::Missing,
+    }
+}
+
+/// Extract the local target from a `SHOW CREATE TABLE` response.
+fn parse_alias_target(response: &serde_json::Value) -> Option<String> {
+    find_string(response, &|s| s.contains("local='")).and_then(|stmt| {
+        let start = stmt.find("local='")? + "local='".len();
+        let end = stmt[start..].find('\'')? + start;
+        Some(stmt[start..end].to_string())
+    })
+}
+
+/// Extract a document count from a `SELECT COUNT(*)` response.
+///
+/// Handles both the raw-mode result-set array and the `hits.total` shape.
+fn parse_count(response: &serde_json::Value) -> Option<u64> {
+    if let Some(total) = response
+        .get("hits")
+        .and_then(|h| h.get("total"))
+        .and_then(|t| t.as_u64())
+    {
+        return Some(total);
+    }
+
+    let result_set = response.as_array().and_then(|a| a.first()).unwrap_or(response);
+    result_set
+        .get("data")?
+        .as_array()?
+        .first()?
+        .as_object()?
+        .values()
+        .find_map(|v| v.as_u64())
+}
+
+/// Extract `(id, mark_text)` rows from a raw-mode `SELECT` response.
+fn parse_mark_texts(response: &serde_json::Value) -> Vec<(u64, String)> {
+    let result_set = response.as_array().and_then(|a| a.first()).unwrap_or(response);
+    let Some(rows) = result_set.get("data").and_then(|d| d.as_array()) else {
+        return Vec::new();
+    };
+    rows.iter()
+        .filter_map(|row| {
+            // Ids may come back as numbers or strings
+            let id = row.get("id").and_then(|v| {
+                v.as_u64()
+                    .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+            })?;
+            let mark = row.get("mark_text").and_then(|v| v.as_str()).unwrap_or("");
+            Some((id, mark.to_string()))
+        })
+        .collect()
+}
+
+fn find_string<'v>(
+    value: &'v serde_json::Value,
+    pred: &dyn Fn(&str) -> bool,
+) -> Option<&'v str> {
+    match value {
+        serde_json::Value::String(s) if pred(s) => Some(s),
+        serde_json::Value::Array(items) => items.iter().find_map(|v| find_string(v, pred)),
+        serde_json::Value::Object(map) => map.values().find_map(|v| find_string(v, pred)),
+        _ => None,
+    }
+}
+
+/// Administers versioned tables behind the backend's alias.
+pub struct IndexAdmin<'a> {
+    backend: &'a ManticoreBackend,
+    history: Mutex<Vec<String>>,
+}
+
+impl<'a> IndexAdmin<'a> {
+    pub fn new(backend: &'a ManticoreBackend) -> Self {
+        Self {
+            backend,
+            history: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The alias the backend queries.
+    pub fn alias(&self) -> &str {
+        &self.backend.config().table_name
+    }
+
+    /// Physical table name for a build version (e.g. `trademarks_20260115`).
+    pub fn versioned_name(&self, version: &str) -> String {
+        format!("{}_{}", self.alias(), version)
+    }
+
+    /// Get what the alias name currently refers to.
+    pub async fn alias_state(&self) -> Result<AliasState, BackendError> {
+        match self
+            .backend
+            .execute(&format!("SHOW CREATE TABLE {}", self.alias()))
+            .await
+        {
+            Ok(response) => Ok(parse_alias_state(&response)),
+            Err(BackendError::QueryFailed(msg)) if is_unknown_table_error(&msg) => {
+                Ok(AliasState::Missing)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the table the alias currently points at.
+    pub async fn current_target(&self) -> Result<Option<String>, BackendError> {
+        Ok(self.alias_state().await?.target().map(str::to_string))
+    }
+
+    /// Create an empty table with the active table's schema, ready to load.
+    pub async fn create_table_like_active(&self, table: &str) -> Result<(), BackendError> {
+        validate_table_name(table)?;
+        let active = self
+            .current_target()
+            .await?
+            .ok_or_else(|| BackendError::Alias(format!("Alias '{}' has no target", self.alias())))?;
+
+        self.backend
+            .execute(&format!("CREATE TABLE {} LIKE {}", table, active))
+            .await?;
+        Ok(())
+    }
+
+    /// Count documents in a table.
+    pub async fn count(&self, table: &str) -> Result<u64, BackendError> {
+        validate_table_name(table)?;
+        let response = self
+            .backend
+            .execute(&format!("SELECT COUNT(*) FROM {}", table))
+            .await?;
+        parse_count(&response)
+            .ok_or_else(|| BackendError::ParseError("Missing count in response".to_string()))
+    }
+
+    /// Validate a candidate table against the policy.
+    pub async fn validate(
+        &self,
+        table: &str,
+        policy: &ValidationPolicy,
+    ) -> Result<ValidationReport, BackendError> {
+        let documents = self.count(table).await?;
+        let active_documents = match self.current_target().await? {
+            Some(active) if active != table => Some(self.count(&active).await?),
+            _ => None,
+        };
+
+        policy
+            .check(documents, active_documents)
+            .map_err(|reason| BackendError::ValidationFailed(format!("{}: {}", table, reason)))?;
+
+        Ok(ValidationReport {
+            table: table.to_string(),
+            documents,
+            active_documents,
+        })
+    }
+
+    /// Validate a table and point the alias at it.
+    pub async fn promote(
+        &self,
+        table: &str,
+        policy: &ValidationPolicy,
+    ) -> Result<SwapOutcome, BackendError> {
+        self.validate(table, policy).await?;
+        let outcome = self.swap(table).await?;
+
+        if let Some(previous) = &outcome.previous {
+            if let Ok(mut history) = self.history.lock() {
+                history.push(previous.clone());
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Point the alias back at a previous target.
+    ///
+    /// Without an explicit target, the target replaced by the last
+    /// `promote` on this admin is used.
+    pub async fn rollback(&self, to: Option<&str>) -> Result<SwapOutcome, BackendError> {
+        let target = match to {
+            Some(table) => table.to_string(),
+            None => self
+                .history
+                .lock()
+                .ok()
+                .and_then(|mut h| h.pop())
+                .ok_or_else(|| BackendError::Alias("No previous target to roll back to".to_string()))?,
+        };
+
+        // The old table must still be intact
+        self.validate(&target, &ValidationPolicy { min_ratio_of_active: 0.0, ..Default::default() })
+            .await?;
+        self.swap(&target).await
+    }
+
+    /// Read a page of mark texts from the alias, in id order, starting
+    /// after `after_id`; pass the last id returned to read the next page.
+    pub async fn mark_texts(
+        &self,
+        after_id: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, String)>, BackendError> {
+        let response = self
+            .backend
+            .execute(&format!(
+                "SELECT id, mark_text FROM {} WHERE id > {} ORDER BY id ASC LIMIT {} \
+                 OPTION max_matches={}",
+                self.alias(),
+                after_id,
+                limit,
+                limit
+            ))
+            .await?;
+        Ok(parse_mark_texts(&response))
+    }
+
+    /// Drop a physical table that is neither the alias nor its target.
+    pub async fn drop_table(&self, table: &str) -> Result<(), BackendError> {
+        validate_table_name(table)?;
+        check_droppable(table, self.alias(), &self.alias_state().await?)?;
+
+        self.backend
+            .execute(&format!("DROP TABLE {}", table))
+            .await?;
+        Ok(())
+    }
+
+    async fn swap(&self, target: &str) -> Result<SwapOutcome, BackendError> {
+        validate_table_name(target)?;
+        let state = self.alias_state().await?;
+        check_replaceable(self.alias(), &state)?;
+        let previous = state.target().map(str::to_string);
+
+        tracing::info!(alias = %self.alias(), from = ?previous, to = %target, "Swapping index alias");
+
+        for statement in alias_statements(self.alias(), target) {
+            self.backend.execute(&statement).await?;
+        }
+
+        Ok(SwapOutcome {
+            alias: self.alias().to_string(),
+            previous,
+            current: target.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validation_policy() {
+        let policy = ValidationPolicy::default();
+        assert!(policy.check(0, None).is_err());
+        assert!(policy.check(100, None).is_ok());
+        assert!(policy.check(950, Some(1000)).is_ok());
+        assert!(policy.check(500, Some(1000)).is_err());
+    }
+
+    #[test]
+    fn test_alias_statements() {
+        let [drop, create] = alias_statements("trademarks", "trademarks_v2");
+        assert_eq!(drop, "DROP TABLE IF EXISTS trademarks");
+        assert_eq!(
+            create,
+            "CREATE TABLE trademarks type='distributed' local='trademarks_v2'"
+        );
+        assert!(validate_table_name("trademarks_v2").is_ok());
+        assert!(validate_table_name("x; DROP TABLE y").is_err());
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let show = json!([{
+            "columns": [{"Table": {}}, {"Create Table": {}}],
+            "data": [{
+                "Table": "trademarks",
+                "Create Table": "CREATE TABLE trademarks type='distributed' local='trademarks_v2'"
+            }]
+        }]);
+        assert_eq!(parse_alias_target(&show).as_deref(), Some("trademarks_v2"));
+
+        let count = json!([{"columns": [{"count(*)": {}}], "data": [{"count(*)": 10234}]}]);
+        assert_eq!(parse_count(&count), Some(10234));
+        assert_eq!(parse_count(&json!({"hits": {"total": 7}})), Some(7));
+
+        let page = json!([{
+            "columns": [{"id": {}}, {"mark_text": {}}],
+            "data": [{"id": 11, "mark_text": "NIKE"}, {"id": "12", "mark_text": "ACME"}]
+        }]);
+        assert_eq!(
+            parse_mark_texts(&page),
+            vec![(11, "NIKE".to_string()), (12, "ACME".to_string())]
+        );
+
+        assert!(is_unknown_table_error("HTTP 500: unknown table 'trademarks'"));
+    }
+
+    #[test]
+    fn test_refuses_physical_alias() {
+        let show = |stmt: &str| json!([{"data": [{"Table": "trademarks", "Create Table": stmt}]}]);
+        let physical = parse_alias_state(&show("CREATE TABLE trademarks (mark_text text)"));
+        assert_eq!(physical, AliasState::Physical);
+        assert!(check_replaceable("trademarks", &physical).is_err());
+
+        let alias = parse_alias_state(&show(
+            "CREATE TABLE trademarks type='distributed' local='trademarks_v1'",
+        ));
+        assert_eq!(alias, AliasState::Alias(Some("trademarks_v1".to_string())));
+        assert!(check_replaceable("trademarks", &alias).is_ok());
+        assert!(check_replaceable("trademarks", &AliasState::Missing).is_ok());
+    }
+
+    #[test]
+    fn test_refuses_dropping_alias_or_target() {
+        let state = AliasState::Alias(Some("trademarks_v2".to_string()));
+        assert!(check_droppable("trademarks", "trademarks", &state).is_err());
+        assert!(check_droppable("trademarks_v2", "trademarks", &state).is_err());
+        assert!(check_droppable("trademarks_v1", "trademarks", &state).is_ok());
+        assert!(check_droppable("trademarks", "trademarks", &AliasState::Missing).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Raw response capture for debugging.
+//!
+//! When enabled, the backend keeps the raw bodies of its most recent
+//! Manticore responses, keyed by a hash of the SQL that produced them, so
+//! a payload behind a parse bug can be retrieved after the fact. Capture
+//! is bounded by entry count, per-body size, and total size; the oldest
+//! entries are evicted first.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Limits on captured responses.
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    /// Responses kept
+    pub max_entries: usize,
+    /// Bytes kept per body; longer bodies are truncated
+    pub max_body_bytes: usize,
+    /// Bytes kept across all bodies
+    pub max_total_bytes: usize,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 16,
+            max_body_bytes: 1024 * 1024,
+            max_total_bytes: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// A captured raw response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedResponse {
+    /// Hash of the SQL statement (see `query_hash`)
+    pub query_hash: String,
+    /// The SQL statement
+    pub sql: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Raw response body, possibly truncated
+    pub body: String,
+    /// True if the body was cut at `max_body_bytes`
+    pub truncated: bool,
+    /// Capture time, milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+}
+
+/// Stable hash of a SQL statement (FNV-1a, 16 hex digits), the same
+/// across runs and builds.
+pub fn query_hash(sql: &str) -> String {
+    let hash = sql.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Bounded store of recent raw responses.
+#[derive(Debug)]
+pub struct ResponseCapture {
+    config: CaptureConfig,
+    entries: Mutex<VecDeque<CapturedResponse>>,
+}
+
+impl ResponseCapture {
+    /// Create an empty capture store.
+    pub fn new(config: CaptureConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a response, replacing any earlier capture of the same query.
+    pub fn record(&self, sql: &str, status: u16, body: &str) {
+        let mut end = body.len().min(self.config.max_body_bytes);
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let entry = CapturedResponse {
+            query_hash: query_hash(sql),
+            sql: sql.to_string(),
+            status,
+            body: body[..end].to_string(),
+            truncated: end < body.len(),
+            timestamp_ms,
+        };
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|e| e.query_hash != entry.query_hash);
+        entries.push_back(entry);
+
+        let mut total: usize = entries.iter().map(|e| e.body.len()).sum();
+        while entries.len() > self.config.max_entries.max(1)
+            || (total > self.config.max_total_bytes && entries.len() > 1)
+        {
+            if let Some(evicted) = entries.pop_front() {
+                total -= evicted.body.len();
+            }
+        }
+    }
+
+    /// Captured responses, newest first.
+    pub fn entries(&self) -> Vec<CapturedResponse> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().rev().cloned().collect()
+    }
+
+    /// The captured response for a query hash.
+    pub fn get(&self, query_hash: &str) -> Option<CapturedResponse> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().find(|e| e.query_hash == query_hash).cloned()
+    }
+
+    /// Drop all captured responses.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_hash_is_stable() {
+        assert_eq!(query_hash(""), "cbf29ce484222325");
+        assert_eq!(query_hash("SELECT 1"), query_hash("SELECT 1"));
+        assert_ne!(query_hash("SELECT 1"), query_hash("SELECT 2"));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let capture = ResponseCapture::new(CaptureConfig {
+            max_entries: 2,
+            max_body_bytes: 4,
+            max_total_bytes: 6,
+        });
+        capture.record("A", 200, "aé");
+        capture.record("B", 200, "bbbbbb");
+        let b = capture.get(&query_hash("B")).unwrap();
+        assert_eq!((b.body.as_str(), b.truncated), ("bbbb", true));
+        // 3 + 4 bytes exceeds the total, so A is evicted
+        assert_eq!(capture.get(&query_hash("A")), None);
+
+        capture.record("C", 500, "cc");
+        capture.record("B", 200, "b");
+        let sqls: Vec<String> = capture.entries().into_iter().map(|e| e.sql).collect();
+        assert_eq!(sqls, vec!["B", "C"]);
+
+        capture.clear();
+        assert!(capture.entries().is_empty());
+    }
+
+    #[test]
+    fn test_truncates_on_char_boundary() {
+        let capture = ResponseCapture::new(CaptureConfig {
+            max_body_bytes: 2,
+            ..Default::default()
+        });
+        capture.record("A", 200, "aé");
+        assert_eq!(capture.entries()[0].body, "a");
+    }
+}
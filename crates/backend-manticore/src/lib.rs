@@ -3,8 +3,20 @@
 //! Provides the `SearchBackend` trait and its Manticore implementation.
 //! This allows retrieval from Manticore while keeping the core logic
 //! backend-agnostic for future Tantivy migration.
-
-use ilegalflow_model::{SearchQuery, TrademarkRecord, TrademarkStatus};
+//!
+//! The `admin` module manages versioned tables behind the queried alias;
+//! `capture` keeps recent raw responses for debugging.
+
+pub mod admin;
+pub mod capture;
+
+use capture::{CaptureConfig, CapturedResponse, ResponseCapture};
+use ilegalflow_model::design::normalize_design_code;
+use ilegalflow_model::{
+    ClassTaxonomy, Date, NiceClass, Owner, RecordId, ScoreNormalization, SearchQuery,
+    TrademarkRecord, TrademarkStatus,
+};
+use ilegalflow_query::{ManticoreDialect, QueryError};
 use std::future::Future;
 use thiserror::Error;
 
@@ -20,10 +32,29 @@ pub enum BackendError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("Invalid query: {0}")]
+    InvalidQuery(#[from] QueryError),
+
+    #[error("Index validation failed: {0}")]
+    ValidationFailed(String),
+
+    #[error("Alias error: {0}")]
+    Alias(String),
+
     #[error("Backend not available")]
     Unavailable,
 }
 
+/// Candidates retrieved for a query.
+#[derive(Debug, Clone, Default)]
+pub struct Retrieval {
+    /// Candidates with their retrieval scores
+    pub candidates: Vec<(TrademarkRecord, f32)>,
+    /// Records matching the query in all, beyond the limit, when the
+    /// backend reports it
+    pub total: Option<usize>,
+}
+
 /// Trait for search backends (Manticore, Tantivy, etc.)
 ///
 /// This abstraction allows swapping backends without changing scoring logic.
@@ -34,11 +65,39 @@ pub trait SearchBackend {
         query: &SearchQuery,
     ) -> impl Future<Output = Result<Vec<(TrademarkRecord, f32)>, BackendError>> + Send;
 
+    /// Search for candidates, with the total count of matching records
+    /// if the backend reports one. Defaults to `search` without a total.
+    fn retrieve(
+        &self,
+        query: &SearchQuery,
+    ) -> impl Future<Output = Result<Retrieval, BackendError>> + Send {
+        let search = self.search(query);
+        async move {
+            Ok(Retrieval {
+                candidates: search.await?,
+                total: None,
+            })
+        }
+    }
+
+    /// Load a single record by serial or registration number.
+    fn fetch_record(
+        &self,
+        id: &RecordId,
+    ) -> impl Future<Output = Result<Option<TrademarkRecord>, BackendError>> + Send;
+
     /// Check if the backend is healthy.
     fn health_check(&self) -> impl Future<Output = Result<(), BackendError>> + Send;
 
     /// Get the backend name for logging.
     fn name(&self) -> &'static str;
+
+    /// How this backend's retrieval scores map to 0.0 - 1.0 before they
+    /// are blended into risk scores. Relative to the best candidate
+    /// unless the backend knows its scale.
+    fn score_normalization(&self) -> ScoreNormalization {
+        ScoreNormalization::MaxScaled
+    }
 }
 
 /// Manticore Search backend configuration.
@@ -46,10 +105,12 @@ pub trait SearchBackend {
 pub struct ManticoreConfig {
     /// Base URL for Manticore HTTP API
     pub base_url: String,
-    /// Table/index name
+    /// Table/index name (an alias table when using `admin::IndexAdmin`)
     pub table_name: String,
     /// Request timeout in seconds
     pub timeout_secs: u64,
+    /// Class clusters used when the query asks for cluster expansion
+    pub class_taxonomy: ClassTaxonomy,
 }
 
 impl Default for ManticoreConfig {
@@ -58,6 +119,7 @@ impl Default for ManticoreConfig {
             base_url: "http://127.0.0.1:9308".to_string(),
             table_name: "trademarks".to_string(),
             timeout_secs: 30,
+            class_taxonomy: ClassTaxonomy::default(),
         }
     }
 }
@@ -66,6 +128,8 @@ impl Default for ManticoreConfig {
 pub struct ManticoreBackend {
     config: ManticoreConfig,
     client: reqwest::Client,
+    dialect: ManticoreDialect,
+    capture: Option<ResponseCapture>,
 }
 
 impl ManticoreBackend {
@@ -76,25 +140,115 @@ impl ManticoreBackend {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { config, client }
+        let dialect = ManticoreDialect {
+            taxonomy: config.class_taxonomy.clone(),
+        };
+
+        Self {
+            config,
+            client,
+            dialect,
+            capture: None,
+        }
+    }
+
+    /// Keep recent raw responses for debugging.
+    pub fn with_capture(mut self, config: CaptureConfig) -> Self {
+        self.capture = Some(ResponseCapture::new(config));
+        self
+    }
+
+    /// Captured raw responses, newest first; empty unless capture is on.
+    pub fn captured_responses(&self) -> Vec<CapturedResponse> {
+        self.capture
+            .as_ref()
+            .map(ResponseCapture::entries)
+            .unwrap_or_default()
+    }
+
+    /// The captured raw response for a query hash (see
+    /// `capture::query_hash`).
+    pub fn captured_response(&self, query_hash: &str) -> Option<CapturedResponse> {
+        self.capture.as_ref()?.get(query_hash)
     }
 
     /// Build SQL query for Manticore.
-    fn build_query(&self, query: &SearchQuery) -> String {
-        let escaped = query.mark_text.replace('\'', "''");
+    fn build_query(&self, query: &SearchQuery) -> Result<String, BackendError> {
+        let where_clause = self.dialect.where_clause(query)?;
 
-        let mut sql = format!(
-            "SELECT *, WEIGHT() as _score FROM {} WHERE MATCH('{}')",
-            self.config.table_name, escaped
-        );
+        Ok(format!(
+            "SELECT *, WEIGHT() as _score FROM {} WHERE {} LIMIT {}",
+            self.config.table_name, where_clause, query.limit
+        ))
+    }
+
+    /// Build SQL to load a record by number.
+    fn build_fetch(&self, id: &RecordId) -> String {
+        let column = match id {
+            RecordId::Serial(_) => "serial_number",
+            RecordId::Registration(_) => "registration_number",
+        };
+        // Canonical numbers are digits only, so need no escaping
+        format!(
+            "SELECT * FROM {} WHERE {} = '{}' LIMIT 1",
+            self.config.table_name,
+            column,
+            id.number()
+        )
+    }
+
+    /// Execute a query, retrying once if it landed in an alias swap.
+    async fn execute_with_retry(&self, sql: &str) -> Result<serde_json::Value, BackendError> {
+        match self.execute(sql).await {
+            // An alias swap drops and recreates the alias table; retry once
+            // if we landed in that window.
+            Err(BackendError::QueryFailed(msg)) if admin::is_unknown_table_error(&msg) => {
+                tracing::debug!("Alias table missing, retrying after swap window");
+                tokio::time::sleep(std::time::Duration::from_millis(admin::SWAP_RETRY_DELAY_MS)).await;
+                self.execute(sql).await
+            }
+            result => result,
+        }
+    }
 
-        if let Some(status) = &query.status_filter {
-            sql.push_str(&format!(" AND status = '{:?}'", status));
+    /// Execute a SQL statement via the `/sql` endpoint in raw mode.
+    pub(crate) async fn execute(&self, sql: &str) -> Result<serde_json::Value, BackendError> {
+        let response = self
+            .client
+            .post(format!("{}/sql", self.config.base_url))
+            .query(&[("mode", "raw")])
+            .body(format!("query={}", sql))
+            .send()
+            .await
+            .map_err(|e| BackendError::Connection(e.to_string()))?;
+
+        let status = response.status();
+        let body = if status.is_success() {
+            response
+                .text()
+                .await
+                .map_err(|e| BackendError::Connection(e.to_string()))?
+        } else {
+            response.text().await.unwrap_or_default()
+        };
+
+        if let Some(capture) = &self.capture {
+            capture.record(sql, status.as_u16(), &body);
+        }
+
+        if !status.is_success() {
+            return Err(BackendError::QueryFailed(format!(
+                "HTTP {}: {}",
+                status, body
+            )));
         }
 
-        sql.push_str(&format!(" LIMIT {}", query.limit));
+        serde_json::from_str(&body).map_err(|e| BackendError::ParseError(e.to_string()))
+    }
 
-        sql
+    /// Get the backend configuration.
+    pub fn config(&self) -> &ManticoreConfig {
+        &self.config
     }
 
     /// Parse Manticore response into records.
@@ -123,7 +277,7 @@ impl ManticoreBackend {
                 .and_then(|s| s.as_f64())
                 .unwrap_or(0.0) as f32;
 
-            let record = TrademarkRecord {
+            let mut record = TrademarkRecord {
                 serial_number: source
                     .get("serial_number")
                     .and_then(|v| v.as_str())
@@ -139,6 +293,11 @@ impl ManticoreBackend {
                     .unwrap_or("")
                     .to_string(),
                 mark_text_normalized: None,
+                transliteration: source
+                    .get("transliteration")
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
                 status: source
                     .get("status")
                     .and_then(|v| v.as_str())
@@ -148,22 +307,42 @@ impl ManticoreBackend {
                     .get("status_code")
                     .and_then(|v| v.as_str())
                     .map(|s| s.to_string()),
-                classes: Vec::new(), // TODO: Parse from response
+                classes: source.get("classes").map(parse_classes).unwrap_or_default(),
+                // Manticore returns bool attributes as 0/1
+                classes_inferred: source
+                    .get("classes_inferred")
+                    .and_then(|v| v.as_bool().or_else(|| v.as_u64().map(|n| n != 0)))
+                    .unwrap_or(false),
                 goods_services: source
                     .get("goods_services")
                     .and_then(|v| v.as_str())
                     .unwrap_or("")
                     .to_string(),
-                owner_name: source
+                owner: source
                     .get("owner_name")
                     .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
+                    .map(Owner::from)
+                    .unwrap_or_default(),
                 filing_date: None,
                 registration_date: None,
-                status_date: None,
-                is_design_mark: false,
+                status_date: source.get("status_date").and_then(parse_date),
+                drawing_type: source
+                    .get("mark_drawing_code")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok()),
+                design_codes: source
+                    .get("design_codes")
+                    .map(parse_design_codes)
+                    .unwrap_or_default(),
+                // Prosecution history is not indexed
+                prosecution_events: Vec::new(),
+                provenance: None,
             };
+            // Indexes without a coarse status still carry the code
+            if record.status == TrademarkStatus::Unknown {
+                if let Some(detail) = record.status_detail() {
+                    record.status = detail.status();
+                }
+            }
 
             results.push((record, score));
         }
@@ -172,40 +351,87 @@ impl ManticoreBackend {
     }
 }
 
+/// Total matches in a response (`hits.total`), when present.
+fn parse_total(response: &serde_json::Value) -> Option<usize> {
+    let total = response.get("hits")?.get("total")?;
+    // Newer versions report {"value": N, "relation": "eq"}
+    let total = total.get("value").unwrap_or(total);
+    total.as_u64().map(|n| n as usize)
+}
+
+/// Parse a date from a Manticore timestamp (seconds; 0 when unset) or an
+/// ISO or `YYYYMMDD` string.
+fn parse_date(value: &serde_json::Value) -> Option<Date> {
+    match value {
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .filter(|&seconds| seconds > 0)
+            .map(|seconds| Date::from_days(seconds.div_euclid(86_400))),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Parse Nice classes from a Manticore MVA array or a comma-separated
+/// string, dropping any that are not valid classes.
+fn parse_classes(value: &serde_json::Value) -> Vec<NiceClass> {
+    let number = |n: u64| NiceClass::new(u16::try_from(n).ok()?);
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_u64().and_then(number).or_else(|| v.as_str()?.trim().parse().ok()))
+            .collect(),
+        serde_json::Value::String(s) => s
+            .split(',')
+            .filter_map(|c| c.trim().parse().ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse design codes from an array or the whitespace-separated
+/// full-text field (`260103 260104`).
+fn parse_design_codes(value: &serde_json::Value) -> Vec<String> {
+    let codes: Vec<&str> = match value {
+        serde_json::Value::Array(items) => items.iter().filter_map(|v| v.as_str()).collect(),
+        serde_json::Value::String(s) => s.split([' ', ',']).collect(),
+        _ => Vec::new(),
+    };
+    codes.into_iter().filter_map(normalize_design_code).collect()
+}
+
 impl SearchBackend for ManticoreBackend {
     async fn search(
         &self,
         query: &SearchQuery,
     ) -> Result<Vec<(TrademarkRecord, f32)>, BackendError> {
-        let sql = self.build_query(query);
+        Ok(self.retrieve(query).await?.candidates)
+    }
+
+    async fn retrieve(&self, query: &SearchQuery) -> Result<Retrieval, BackendError> {
+        let sql = self.build_query(query)?;
 
         tracing::debug!(sql = %sql, "Executing Manticore query");
 
-        // Use /sql endpoint with mode=raw for JSON response
-        let response = self
-            .client
-            .post(format!("{}/sql", self.config.base_url))
-            .query(&[("mode", "raw")])
-            .body(format!("query={}", sql))
-            .send()
-            .await
-            .map_err(|e| BackendError::Connection(e.to_string()))?;
+        let json = self.execute_with_retry(&sql).await?;
+        let total = parse_total(&json);
+        Ok(Retrieval {
+            candidates: self.parse_response(json)?,
+            total,
+        })
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(BackendError::QueryFailed(format!(
-                "HTTP {}: {}",
-                status, body
-            )));
-        }
+    async fn fetch_record(&self, id: &RecordId) -> Result<Option<TrademarkRecord>, BackendError> {
+        let sql = self.build_fetch(id);
 
-        let json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| BackendError::ParseError(e.to_string()))?;
+        tracing::debug!(sql = %sql, "Fetching Manticore record");
 
-        self.parse_response(json)
+        let json = self.execute_with_retry(&sql).await?;
+        Ok(self
+            .parse_response(json)?
+            .into_iter()
+            .next()
+            .map(|(record, _)| record))
     }
 
     async fn health_check(&self) -> Result<(), BackendError> {
@@ -237,19 +463,57 @@ mod tests {
     fn test_build_query() {
         let backend = ManticoreBackend::new(ManticoreConfig::default());
         let query = SearchQuery::new("NIKE").with_limit(50);
-        let sql = backend.build_query(&query);
+        let sql = backend.build_query(&query).unwrap();
 
         assert!(sql.contains("MATCH('NIKE')"));
         assert!(sql.contains("LIMIT 50"));
         assert!(sql.contains("trademarks"));
     }
 
+    #[test]
+    fn test_class_filter_and_parsing() {
+        let backend = ManticoreBackend::new(ManticoreConfig::default());
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[9, 25]));
+        let sql = backend.build_query(&query).unwrap();
+        assert!(sql.contains("AND ANY(classes) IN (9, 25)"));
+
+        assert_eq!(parse_classes(&serde_json::json!([9, 25])), vec![9, 25]);
+        assert_eq!(parse_classes(&serde_json::json!("009, 025")), vec![9, 25]);
+        assert_eq!(
+            parse_design_codes(&serde_json::json!("260103 030101")),
+            vec!["26.01.03", "03.01.01"]
+        );
+        let june = Date::from_ymd(2024, 6, 1);
+        assert_eq!(parse_date(&serde_json::json!(1717200000)), june);
+        assert_eq!(parse_date(&serde_json::json!(0)), None);
+        assert_eq!(parse_date(&serde_json::json!("2024-06-01")), june);
+        assert_eq!(parse_date(&serde_json::json!("20240601")), june);
+
+        let total = |hits| parse_total(&serde_json::json!({ "hits": hits }));
+        assert_eq!(total(serde_json::json!({"total": 240, "hits": []})), Some(240));
+        assert_eq!(total(serde_json::json!({"total": {"value": 7}})), Some(7));
+        assert_eq!(total(serde_json::json!({"hits": []})), None);
+    }
+
     #[test]
     fn test_query_escaping() {
         let backend = ManticoreBackend::new(ManticoreConfig::default());
         let query = SearchQuery::new("O'REILLY");
-        let sql = backend.build_query(&query);
+        let sql = backend.build_query(&query).unwrap();
 
         assert!(sql.contains("O''REILLY"));
     }
+
+    #[test]
+    fn test_build_fetch() {
+        let backend = ManticoreBackend::new(ManticoreConfig::default());
+        assert_eq!(
+            backend.build_fetch(&RecordId::parse("97123456").unwrap()),
+            "SELECT * FROM trademarks WHERE serial_number = '97123456' LIMIT 1"
+        );
+        assert_eq!(
+            backend.build_fetch(&RecordId::parse("5,123,456").unwrap()),
+            "SELECT * FROM trademarks WHERE registration_number = '5123456' LIMIT 1"
+        );
+    }
 }
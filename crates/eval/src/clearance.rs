@@ -0,0 +1,287 @@
+//! `eval clearance`: search, verify, explain, and write a report.
+//!
+//! Runs a full clearance search for a mark, re-fetches the top hits from
+//! the backend to confirm they still exist with the status the search
+//! saw, explains every reported hit, and writes a Markdown report with
+//! recommendations, plus the PDF report with `--pdf`. `--redact` masks
+//! owners, serial numbers and goods text in both, for reports shared
+//! outside privilege. The exit code is 2 when any hit is in the HIGH
+//! tier.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use ilegalflow_backend_manticore::{ManticoreBackend, SearchBackend};
+use ilegalflow_explain::{render, ExplainOptions, Redaction, RedactionPolicy, RiskTier};
+use ilegalflow_model::{CandidateHit, RecordId, SearchQuery, TrademarkStatus};
+use ilegalflow_pipeline::{HitExplanation, Pipeline, PipelineOutput};
+use ilegalflow_report::ClearanceReport;
+use std::fmt::Write as _;
+
+/// Exit code when HIGH tier risks are found.
+pub const HIGH_RISK_EXIT_CODE: i32 = 2;
+
+#[derive(Args)]
+pub struct ClearanceArgs {
+    /// Mark to clear
+    mark: String,
+
+    /// Nice classes of the intended use (comma-separated)
+    #[arg(short, long)]
+    classes: Option<String>,
+
+    /// Goods/services of the intended use
+    #[arg(long)]
+    goods: Option<String>,
+
+    /// Maximum candidates to retrieve
+    #[arg(short, long, default_value = "50")]
+    limit: usize,
+
+    /// Hits reported in detail
+    #[arg(long, default_value = "10")]
+    top: usize,
+
+    /// Top hits re-fetched to confirm their current status
+    #[arg(long, default_value = "5")]
+    verify: usize,
+
+    /// Report file (Markdown); printed to stdout if not set
+    #[arg(long, value_name = "FILE")]
+    out: Option<String>,
+
+    /// Also write the client-ready PDF report to this file
+    #[arg(long, value_name = "FILE")]
+    pdf: Option<String>,
+
+    /// Mask owners, serial numbers and goods text (partial, full)
+    #[arg(long)]
+    redact: Option<Redaction>,
+}
+
+/// Result of re-fetching a hit from the backend.
+enum Verification {
+    /// Found with the status the search saw
+    Current,
+    /// Found with a different status
+    Changed(TrademarkStatus),
+    /// No longer found
+    Missing,
+    /// Not checked (beyond `--verify`, or the lookup failed)
+    Unchecked,
+}
+
+/// Run a clearance search; returns the overall risk tier.
+pub async fn run_clearance(
+    pipeline: &Pipeline<ManticoreBackend>,
+    tenant: Option<&str>,
+    args: &ClearanceArgs,
+) -> Result<RiskTier> {
+    let mut query = SearchQuery::new(args.mark.clone())
+        .with_classes(crate::parse_classes(args.classes.as_deref())?)
+        .with_limit(args.limit);
+    query.goods_services_query = args.goods.clone();
+
+    eprintln!("[1/4] Searching for '{}'...", args.mark);
+    let output = match tenant {
+        Some(tenant) => pipeline.run_for_tenant(tenant, &query).await,
+        None => pipeline.run(&query).await,
+    }?;
+    eprintln!(
+        "      {} candidates retrieved, {} hits",
+        output.retrieved,
+        output.result.hits.len()
+    );
+
+    let top: Vec<&CandidateHit> = output.result.hits.iter().take(args.top).collect();
+
+    eprintln!(
+        "[2/4] Verifying the top {} hits...",
+        args.verify.min(top.len())
+    );
+    let mut verifications = Vec::with_capacity(top.len());
+    for (i, hit) in top.iter().enumerate() {
+        let verification = if i < args.verify {
+            verify(pipeline.backend(), hit).await
+        } else {
+            Verification::Unchecked
+        };
+        verifications.push(verification);
+    }
+
+    eprintln!("[3/4] Explaining {} hits...", top.len());
+    let redaction = args.redact.map(RedactionPolicy::uniform).unwrap_or_default();
+    let explanations: Vec<Option<HitExplanation>> = top
+        .iter()
+        .map(|hit| {
+            let mut explanation = output
+                .explanations
+                .iter()
+                .find(|e| e.serial_number == hit.record.serial_number)
+                .cloned()
+                .or_else(|| pipeline.explain(&output, &hit.record.serial_number))?;
+            explanation.explanations = std::mem::take(&mut explanation.explanations)
+                .into_iter()
+                .map(|e| redaction.explanation(e, hit))
+                .collect();
+            // Narratives were written from the unredacted hit
+            if args.redact.is_some() {
+                explanation.narrative = None;
+            }
+            Some(explanation)
+        })
+        .collect();
+
+    let options = &pipeline.config().explain;
+    let tier = output
+        .result
+        .hits
+        .iter()
+        .map(|hit| RiskTier::of_with(hit, &options.severities))
+        .max()
+        .unwrap_or(RiskTier::Low);
+    let report = render_report(
+        &output,
+        tier,
+        &top,
+        &verifications,
+        &explanations,
+        options,
+        &redaction,
+    );
+
+    eprintln!("[4/4] Writing report...");
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, report).with_context(|| format!("Failed to write {}", path))?;
+            eprintln!("      Wrote {}", path);
+        }
+        None => print!("{}", report),
+    }
+    if let Some(path) = &args.pdf {
+        let mut pdf = ClearanceReport::new(&output.result.query, &output.result.hits[..top.len()])
+            .with_date(ilegalflow_query::iso_date_days_ago(0))
+            .with_redaction(redaction)
+            .with_severities(options.severities);
+        for explanation in explanations.iter().flatten() {
+            pdf = pdf.with_explanations(&explanation.serial_number, &explanation.explanations);
+            if let Some(narrative) = &explanation.narrative {
+                pdf = pdf.with_narrative(&explanation.serial_number, narrative);
+            }
+        }
+        std::fs::write(path, pdf.to_pdf()).with_context(|| format!("Failed to write {}", path))?;
+        eprintln!("      Wrote {}", path);
+    }
+    eprintln!("Overall: {}", tier.label());
+
+    Ok(tier)
+}
+
+async fn verify(backend: &ManticoreBackend, hit: &CandidateHit) -> Verification {
+    let Some(id) = RecordId::serial(&hit.record.serial_number) else {
+        return Verification::Unchecked;
+    };
+    match backend.fetch_record(&id).await {
+        Ok(Some(record)) if record.status == hit.record.status => Verification::Current,
+        Ok(Some(record)) => Verification::Changed(record.status),
+        Ok(None) => Verification::Missing,
+        Err(e) => {
+            tracing::warn!(serial = %hit.record.serial_number, "Verification failed: {}", e);
+            Verification::Unchecked
+        }
+    }
+}
+
+fn recommendations(tier: RiskTier) -> &'static [&'static str] {
+    match tier {
+        RiskTier::High => &[
+            "Do not adopt the mark without review by trademark counsel.",
+            "Consider alternative marks; the conflicts below are likely to block registration.",
+        ],
+        RiskTier::Moderate => &[
+            "Have trademark counsel review the conflicts below before filing.",
+            "Consider narrowing the goods/services to avoid overlapping classes.",
+        ],
+        RiskTier::Low => &[
+            "No significant conflicts were found in the indexed records.",
+            "Run a full search, including common-law use, before filing.",
+        ],
+    }
+}
+
+fn render_report(
+    output: &PipelineOutput,
+    tier: RiskTier,
+    top: &[&CandidateHit],
+    verifications: &[Verification],
+    explanations: &[Option<HitExplanation>],
+    options: &ExplainOptions,
+    redaction: &RedactionPolicy,
+) -> String {
+    let query = &output.result.query;
+    let mut md = String::new();
+
+    let _ = writeln!(md, "# Clearance report: {}\n", query.mark_text);
+    if !query.classes.is_empty() {
+        let classes: Vec<String> = query.classes.iter().map(|c| c.to_string()).collect();
+        let _ = writeln!(md, "- Classes: {}", classes.join(", "));
+    }
+    if let Some(goods) = &query.goods_services_query {
+        let _ = writeln!(md, "- Goods/services: {}", goods);
+    }
+    let _ = writeln!(md, "- Candidates retrieved: {}", output.retrieved);
+    let _ = writeln!(md, "- Hits: {}", output.result.hits.len());
+    let _ = writeln!(md, "- Overall risk: **{}**\n", tier.label());
+
+    let _ = writeln!(md, "## Recommendations\n");
+    for recommendation in recommendations(tier) {
+        let _ = writeln!(md, "- {}", recommendation);
+    }
+    for warning in &output.warnings {
+        let _ = writeln!(md, "- Note: {}", warning);
+    }
+
+    let _ = writeln!(md, "\n## Top conflicts\n");
+    if top.is_empty() {
+        let _ = writeln!(md, "No conflicting marks found.");
+    }
+    for (i, hit) in top.iter().enumerate() {
+        let record = redaction.record(&hit.record);
+        let _ = writeln!(
+            md,
+            "### {}. {} ({})\n",
+            i + 1,
+            record.mark_text,
+            RiskTier::of_with(hit, &options.severities).label()
+        );
+        let _ = writeln!(md, "- Serial: {}", record.serial_number);
+        if !record.owner.is_empty() {
+            let _ = writeln!(md, "- Owner: {}", record.owner);
+        }
+        let status = match &verifications[i] {
+            Verification::Current => format!("{:?} (verified)", record.status),
+            Verification::Changed(now) => {
+                format!("{:?} (now {:?} on re-fetch)", record.status, now)
+            }
+            Verification::Missing => format!("{:?} (no longer found)", record.status),
+            Verification::Unchecked => format!("{:?}", record.status),
+        };
+        let _ = writeln!(md, "- Status: {}", status);
+        let _ = writeln!(md, "- Risk score: {:.2}", hit.risk_score);
+        // TSDR links carry the serial number
+        let tsdr = hit.record.tsdr_url().filter(|_| redaction.serial_numbers == Redaction::Keep);
+        if let Some(tsdr) = tsdr {
+            let _ = writeln!(md, "- TSDR: <{}>", tsdr);
+        }
+
+        if let Some(explanation) = &explanations[i] {
+            let _ = writeln!(md, "\n{}\n", explanation.summary);
+            if let Some(narrative) = &explanation.narrative {
+                let _ = writeln!(md, "{}\n", narrative);
+            }
+            md.push_str(&render::markdown(&explanation.explanations, options.locale));
+        }
+        let _ = writeln!(md);
+    }
+
+    md
+}
@@ -2,15 +2,47 @@
 //!
 //! Usage:
 //!     eval search "NIKE" --limit 20
+//!     eval --rerank-config rerank.toml --rerank-profile knockout search "NIKE"
+//!     eval clearance "NIKE" --classes 25 --goods "t-shirts" --out report.md
 //!     eval benchmark --test-file tests.yaml
 //!     eval health
+//!     eval index terms --out terms.tsv
+//!     eval audit 5,123,456
+//!     eval stats audit/ --since 2026-07-01 --epsilon 1.0
+//!     eval watch run watchlist.json --daemon
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+mod clearance;
+mod watch;
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use ilegalflow_backend_manticore::admin::{IndexAdmin, ValidationPolicy};
+use ilegalflow_backend_manticore::capture::CaptureConfig;
 use ilegalflow_backend_manticore::{ManticoreBackend, ManticoreConfig, SearchBackend};
-use ilegalflow_explain::summarize_risk;
-use ilegalflow_model::SearchQuery;
-use ilegalflow_rerank::{rerank, RerankConfig};
+use clearance::ClearanceArgs;
+use ilegalflow_explain::{
+    explain_dupont, summarize_risk_with, DetailLevel, ExplainOptions, Locale, RiskTier, Verbosity,
+};
+use ilegalflow_famous::FamousMarks;
+use ilegalflow_features::{PhoneticAlgorithm, PhoneticRegistry, TermFrequencies};
+use ilegalflow_model::{
+    NiceClass, RecordId, SearchIntent, SearchQuery, SeverityTable, TrademarkStatus,
+};
+use ilegalflow_query::{iso_date_days_ago, parse_iso_date};
+use ilegalflow_rerank::calibration::Calibration;
+use ilegalflow_rerank::common_law::UseListing;
+use ilegalflow_rerank::presets::PRESETS;
+use ilegalflow_rerank::profiles::{ProfileError, RerankProfiles};
+use ilegalflow_rerank::learned::RiskModel;
+use ilegalflow_rerank::RerankConfig;
+use ilegalflow_pipeline::audit::DirectoryAuditSink;
+use ilegalflow_pipeline::overrides::OverrideStore;
+use ilegalflow_pipeline::stats::{AggregationConfig, Noise, TermAggregator};
+use ilegalflow_pipeline::{
+    ExplainPolicy, Pipeline, PipelineConfig, PipelineOutput, SearchResponse,
+};
+use std::sync::Arc;
+use watch::WatchCommand;
 
 #[derive(Parser)]
 #[command(name = "eval")]
@@ -22,31 +54,144 @@ struct Cli {
     /// Manticore URL
     #[arg(long, default_value = "http://127.0.0.1:9308")]
     manticore_url: String,
+
+    /// Directory for QA audit samples (disabled if not set)
+    #[arg(long)]
+    audit_dir: Option<String>,
+
+    /// Fraction of searches to sample into the audit directory
+    #[arg(long, default_value = "0.01")]
+    audit_rate: f64,
+
+    /// Reviewer overrides file (JSON: tenant -> serial -> note)
+    #[arg(long)]
+    overrides: Option<String>,
+
+    /// Extra foreign-equivalent mappings (tab-separated: language,
+    /// foreign word, English meaning)
+    #[arg(long, value_name = "FILE")]
+    translations: Option<String>,
+
+    /// Corpus term frequencies (from `eval index terms`) for ranking
+    /// dominant terms by rarity
+    #[arg(long, value_name = "FILE")]
+    term_frequencies: Option<String>,
+
+    /// Tenant whose reviewer overrides apply
+    #[arg(long)]
+    tenant: Option<String>,
+
+    /// Explanation verbosity (terse, standard, detailed)
+    #[arg(long, default_value = "detailed")]
+    verbosity: Verbosity,
+
+    /// Explanation language (en, es, pt)
+    #[arg(long, default_value = "en")]
+    locale: Locale,
+
+    /// Explanation audience (plain, professional)
+    #[arg(long, default_value = "professional")]
+    detail_level: DetailLevel,
+
+    /// Which hits to explain: all, on-demand (none), or a minimum risk
+    /// score such as 0.5
+    #[arg(long, default_value = "all")]
+    explain: ExplainPolicy,
+
+    /// Re-ranker settings and named profiles (TOML or YAML)
+    #[arg(long, value_name = "FILE")]
+    rerank_config: Option<String>,
+
+    /// Profile to use from the re-ranker config (default: the file's
+    /// default_profile)
+    #[arg(long, value_name = "NAME", requires = "rerank_config")]
+    rerank_profile: Option<String>,
+
+    /// Re-ranker preset (knockout, balanced, comprehensive)
+    #[arg(long, value_name = "NAME", conflicts_with = "rerank_config")]
+    rerank_preset: Option<String>,
+
+    /// Extra famous marks, protected across classes (tab-separated:
+    /// name, owner, comma-separated classes)
+    #[arg(long, value_name = "FILE")]
+    famous_marks: Option<String>,
+
+    /// Related class grades added to or overriding the bundled ones
+    /// (tab-separated: class, class, grade 0-1)
+    #[arg(long, value_name = "FILE")]
+    class_relatedness: Option<String>,
+
+    /// Sightings of marks in use outside the register (tab-separated:
+    /// mark, source, evidence, optional confidence)
+    #[arg(long, value_name = "FILE")]
+    common_law: Option<String>,
+
+    /// Learned risk model (JSON, linear or GBDT) scoring candidates after
+    /// the heuristic re-ranker
+    #[arg(long, value_name = "FILE")]
+    risk_model: Option<String>,
+
+    /// Reviewed outcomes to calibrate risk scores into probabilities of
+    /// conflict (tab-separated: score, conflict as 1/0)
+    #[arg(long, value_name = "FILE")]
+    calibration: Option<String>,
+
+    /// Phonetic encoders used for matching (soundex, metaphone,
+    /// double_metaphone, nysiis, caverphone2, cologne); overrides the
+    /// re-ranker config [default: soundex,metaphone,double_metaphone]
+    #[arg(long, value_delimiter = ',')]
+    phonetic: Vec<PhoneticAlgorithm>,
+
+    /// Drop hits scoring below this risk (0.0 - 1.0); overrides the
+    /// re-ranker config
+    #[arg(long, value_name = "SCORE")]
+    min_risk_score: Option<f32>,
+
+    /// Return at most this many hits, riskiest first; overrides the
+    /// re-ranker config
+    #[arg(long, value_name = "N")]
+    max_results: Option<usize>,
+
+    /// Add this much of each hit's normalized retrieval score to its risk
+    /// (0.0 - 1.0); overrides the re-ranker config
+    #[arg(long, value_name = "WEIGHT")]
+    retrieval_weight: Option<f32>,
+
+    /// Weigh each hit on the du Pont likelihood-of-confusion factors
+    #[arg(long)]
+    dupont: bool,
+
+    /// Include TSDR/TESS links in each hit
+    #[arg(long)]
+    links: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Search for a trademark
-    Search {
-        /// Mark text to search
-        query: String,
+    Search(Box<SearchArgs>),
 
-        /// Maximum results
-        #[arg(short, long, default_value = "20")]
-        limit: usize,
-
-        /// Nice classes to filter (comma-separated)
-        #[arg(short, long)]
-        classes: Option<String>,
-
-        /// Output format (text, json)
-        #[arg(short, long, default_value = "text")]
-        format: String,
-    },
+    /// Search, verify top hits, explain, and write a clearance report
+    /// (exit code 2 if HIGH tier risks are found)
+    Clearance(ClearanceArgs),
 
     /// Check backend health
     Health,
 
+    /// Manage versioned tables behind the search alias
+    #[command(subcommand)]
+    Index(IndexCommand),
+
+    /// Run saved watchlist searches
+    #[command(subcommand)]
+    Watch(WatchCommand),
+
+    /// Find marks that may conflict with an existing record
+    Audit(AuditArgs),
+
+    /// Export privacy-safe term statistics from an audit directory
+    Stats(StatsArgs),
+
     /// Run benchmark against test file
     Benchmark {
         /// Path to test YAML file
@@ -55,10 +200,235 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum IndexCommand {
+    /// Show the alias target and document count
+    Status,
+
+    /// Validate a table and point the alias at it
+    Promote {
+        /// Physical table to promote
+        table: String,
+
+        /// Minimum size relative to the active table
+        #[arg(long, default_value = "0.9")]
+        min_ratio: f64,
+    },
+
+    /// Point the alias back at a previous table
+    Rollback {
+        /// Table to restore
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Drop a table that is no longer the alias target
+    Drop {
+        table: String,
+    },
+
+    /// Count how many indexed marks use each word, for dominant-term
+    /// ranking
+    Terms {
+        /// Output file (tab-separated term, count)
+        #[arg(long, value_name = "FILE")]
+        out: String,
+
+        /// Marks read per request
+        #[arg(long, default_value = "10000")]
+        batch: usize,
+    },
+}
+
+#[derive(Args)]
+struct AuditArgs {
+    /// Serial (8 digits) or registration number (e.g. 5,123,456)
+    number: String,
+
+    /// Treat the number as a serial number
+    #[arg(long, conflicts_with = "registration")]
+    serial: bool,
+
+    /// Treat the number as a registration number
+    #[arg(long)]
+    registration: bool,
+
+    /// Output format (text, json)
+    #[arg(long, default_value = "text")]
+    format: String,
+}
+
+#[derive(Args)]
+struct StatsArgs {
+    /// Audit directory to aggregate
+    audit_dir: String,
+
+    /// Only include searches on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only include searches before this date (YYYY-MM-DD)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Minimum distinct tenants for a term to be exported
+    #[arg(long, default_value = "5")]
+    min_tenants: usize,
+
+    /// Most any one tenant adds to a term's counts
+    #[arg(long, default_value = "10")]
+    max_per_tenant: u64,
+
+    /// Add Laplace noise with this privacy budget per count
+    #[arg(long)]
+    epsilon: Option<f64>,
+
+    /// Number of terms to print
+    #[arg(long, default_value = "20")]
+    top: usize,
+
+    /// Output format (text, json)
+    #[arg(long, default_value = "text")]
+    format: String,
+}
+
+#[derive(Args)]
+struct SearchArgs {
+    /// Mark text to search (may be empty for owner/goods-only searches)
+    #[arg(default_value = "")]
+    query: String,
+
+    /// Owner name to match
+    #[arg(long)]
+    owner: Option<String>,
+
+    /// Goods/services text to match
+    #[arg(long)]
+    goods: Option<String>,
+
+    /// Exclude marks owned by this owner (repeatable)
+    #[arg(long = "exclude-owner")]
+    exclude_owners: Vec<String>,
+
+    /// Exclude these serial numbers (comma-separated)
+    #[arg(long)]
+    exclude_serials: Option<String>,
+
+    /// Status filter, comma-separated (e.g. live,pending)
+    #[arg(long)]
+    status: Option<String>,
+
+    /// Only marks filed on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    filed_after: Option<String>,
+
+    /// Only marks filed on or before this date (YYYY-MM-DD)
+    #[arg(long)]
+    filed_before: Option<String>,
+
+    /// Only marks filed in the last N days (overrides --filed-after)
+    #[arg(long)]
+    filed_within_days: Option<i64>,
+
+    /// Only marks whose status changed on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    status_changed_after: Option<String>,
+
+    /// Maximum results
+    #[arg(short, long, default_value = "20")]
+    limit: usize,
+
+    /// Nice classes to filter (comma-separated)
+    #[arg(short, long)]
+    classes: Option<String>,
+
+    /// Expand the class filter to related-industry clusters
+    #[arg(long)]
+    expand_clusters: bool,
+
+    /// Expand the class filter to coordinated classes (e.g. 25 -> 35)
+    #[arg(long)]
+    expand_related: bool,
+
+    /// Append truncation (`*`) to short single-word marks
+    #[arg(long)]
+    auto_truncate: bool,
+
+    /// Search intent (clearance, knockout); knockout runs a fast screen
+    /// for exact and near-exact conflicts
+    #[arg(long, default_value = "clearance")]
+    intent: SearchIntent,
+
+    /// Output format (text, json)
+    #[arg(short, long, default_value = "text")]
+    format: String,
+
+    /// With --format json, print a bare hits array instead of the
+    /// SearchResponse envelope (deprecated)
+    #[arg(long)]
+    legacy_json: bool,
+
+    /// Write the raw Manticore responses to this file as JSON, even if
+    /// the search fails
+    #[arg(long, value_name = "FILE")]
+    capture_raw: Option<String>,
+}
+
+impl SearchArgs {
+    /// Build the search query from CLI arguments.
+    fn to_query(&self) -> Result<SearchQuery> {
+        Ok(SearchQuery {
+            mark_text: self.query.clone(),
+            auto_truncate: self.auto_truncate,
+            owner_query: self.owner.clone(),
+            goods_services_query: self.goods.clone(),
+            status_filter: self
+                .status
+                .as_deref()
+                .map(|s| s.split(',').map(str::parse::<TrademarkStatus>).collect::<Result<_, _>>())
+                .transpose()
+                .map_err(anyhow::Error::msg)?
+                .unwrap_or_default(),
+            exclude_owners: self.exclude_owners.clone(),
+            exclude_serials: self
+                .exclude_serials
+                .as_deref()
+                .map(|s| s.split(',').map(|serial| serial.trim().to_string()).collect())
+                .unwrap_or_default(),
+            filed_after: self
+                .filed_within_days
+                .map(iso_date_days_ago)
+                .or_else(|| self.filed_after.clone()),
+            filed_before: self.filed_before.clone(),
+            status_changed_after: self.status_changed_after.clone(),
+            classes: parse_classes(self.classes.as_deref())?,
+            expand_class_clusters: self.expand_clusters,
+            expand_related_classes: self.expand_related,
+            limit: self.limit,
+            intent: self.intent,
+            ..Default::default()
+        })
+    }
+}
+
+/// Parse a comma-separated class list.
+fn parse_classes(classes: Option<&str>) -> Result<Vec<NiceClass>> {
+    let Some(classes) = classes else {
+        return Ok(Vec::new());
+    };
+    classes
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(|c| c.parse().map_err(anyhow::Error::msg))
+        .collect()
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
     tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
         .with_env_filter(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive("ilegalflow=debug".parse()?),
@@ -71,22 +441,125 @@ async fn main() -> Result<()> {
         base_url: cli.manticore_url,
         ..Default::default()
     };
-    let backend = ManticoreBackend::new(config);
+    let mut backend = ManticoreBackend::new(config);
+    if matches!(&cli.command, Commands::Search(args) if args.capture_raw.is_some()) {
+        backend = backend.with_capture(CaptureConfig::default());
+    }
+
+    let mut pipeline_config = PipelineConfig {
+        audit_sample_rate: cli.audit_rate,
+        explain: ExplainOptions::new(cli.verbosity)
+            .with_locale(cli.locale)
+            .with_detail_level(cli.detail_level),
+        explain_policy: cli.explain,
+        include_links: cli.links,
+        ..Default::default()
+    };
+    if let Some(path) = &cli.rerank_config {
+        let load = || -> Result<_, ProfileError> {
+            let profiles = RerankProfiles::from_file(path)?;
+            let profile = cli.rerank_profile.as_deref().or(profiles.default_profile());
+            Ok((profiles.config(profile)?, profile.map(str::to_string)))
+        };
+        (pipeline_config.rerank, pipeline_config.profile) =
+            load().with_context(|| format!("Failed to load re-ranker config from {}", path))?;
+    }
+    if let Some(name) = &cli.rerank_preset {
+        pipeline_config.rerank = RerankConfig::preset(name).with_context(|| {
+            format!("Unknown re-ranker preset '{}' ({})", name, PRESETS.join(", "))
+        })?;
+        pipeline_config.profile = Some(name.clone());
+    }
+    if let Some(path) = &cli.famous_marks {
+        let mut marks = FamousMarks::default();
+        marks
+            .load(path)
+            .with_context(|| format!("Failed to load famous marks from {}", path))?;
+        pipeline_config.rerank.famous_marks = Arc::new(marks);
+    }
+    if let Some(path) = &cli.class_relatedness {
+        pipeline_config
+            .rerank
+            .class_relatedness
+            .load(path)
+            .with_context(|| format!("Failed to load class relatedness from {}", path))?;
+    }
+    if let Some(path) = &cli.common_law {
+        let mut listing = UseListing::default();
+        listing
+            .load(path)
+            .with_context(|| format!("Failed to load common-law use from {}", path))?;
+        pipeline_config.rerank.common_law_sources.push(Arc::new(listing));
+    }
+    if let Some(path) = &cli.risk_model {
+        let model = RiskModel::load(path)
+            .with_context(|| format!("Failed to load risk model from {}", path))?;
+        pipeline_config.rerank.risk_model = Some(Arc::new(model));
+    }
+    if let Some(path) = &cli.calibration {
+        let calibration = Calibration::fit_file(path)
+            .with_context(|| format!("Failed to fit calibration from {}", path))?;
+        pipeline_config.rerank.calibration = Some(calibration);
+    }
+    if !cli.phonetic.is_empty() {
+        pipeline_config.rerank.phonetic = PhoneticRegistry::from_algorithms(&cli.phonetic);
+    }
+    if let Some(min_risk_score) = cli.min_risk_score {
+        pipeline_config.rerank.min_risk_score = min_risk_score;
+    }
+    if cli.max_results.is_some() {
+        pipeline_config.rerank.max_results = cli.max_results;
+    }
+    if let Some(retrieval_weight) = cli.retrieval_weight {
+        pipeline_config.rerank.retrieval_weight = retrieval_weight;
+    }
+    if cli.dupont {
+        pipeline_config.rerank.dupont_assessment = true;
+    }
+    pipeline_config.rerank.validate()?;
+    if let Some(path) = &cli.translations {
+        pipeline_config.rerank.translations.load(path)?;
+    }
+    if let Some(path) = &cli.term_frequencies {
+        let frequencies = TermFrequencies::load(path)
+            .with_context(|| format!("Failed to load term frequencies from {}", path))?;
+        pipeline_config.rerank.term_frequencies = Some(Arc::new(frequencies));
+    }
+    let mut pipeline = Pipeline::new(backend, pipeline_config);
+    if let Some(dir) = &cli.audit_dir {
+        pipeline = pipeline.with_audit_sink(DirectoryAuditSink::open(dir)?);
+    }
+    if let Some(path) = &cli.overrides {
+        pipeline = pipeline.with_overrides(OverrideStore::load(path)?);
+    }
 
     match cli.command {
-        Commands::Search {
-            query,
-            limit,
-            classes,
-            format,
-        } => {
-            run_search(&backend, &query, limit, classes, &format).await?;
+        Commands::Search(args) => {
+            run_search(&pipeline, cli.tenant.as_deref(), &args).await?;
+        }
+        Commands::Clearance(args) => {
+            let tier = clearance::run_clearance(&pipeline, cli.tenant.as_deref(), &args).await?;
+            if tier == RiskTier::High {
+                std::process::exit(clearance::HIGH_RISK_EXIT_CODE);
+            }
         }
         Commands::Health => {
-            run_health(&backend).await?;
+            run_health(pipeline.backend()).await?;
+        }
+        Commands::Index(command) => {
+            run_index(pipeline.backend(), command).await?;
+        }
+        Commands::Watch(command) => {
+            watch::run_watch(&pipeline, command).await?;
+        }
+        Commands::Audit(args) => {
+            run_audit(&pipeline, cli.tenant.as_deref(), &args).await?;
+        }
+        Commands::Stats(args) => {
+            run_stats(&args)?;
         }
         Commands::Benchmark { test_file } => {
-            run_benchmark(&backend, &test_file).await?;
+            run_benchmark(pipeline.backend(), &test_file).await?;
         }
     }
 
@@ -94,68 +567,127 @@ async fn main() -> Result<()> {
 }
 
 async fn run_search(
-    backend: &ManticoreBackend,
-    query_text: &str,
-    limit: usize,
-    classes: Option<String>,
-    format: &str,
+    pipeline: &Pipeline<ManticoreBackend>,
+    tenant: Option<&str>,
+    args: &SearchArgs,
 ) -> Result<()> {
-    let classes: Vec<u16> = classes
-        .map(|s| {
-            s.split(',')
-                .filter_map(|c| c.trim().parse().ok())
-                .collect()
-        })
-        .unwrap_or_default();
+    let query = args.to_query()?;
 
-    let query = SearchQuery {
-        mark_text: query_text.to_string(),
-        classes: classes.clone(),
-        limit,
-        ..Default::default()
+    // Retrieve, re-rank, and explain
+    let result = match tenant {
+        Some(tenant) => pipeline.run_for_tenant(tenant, &query).await,
+        None => pipeline.run(&query).await,
     };
 
-    println!("Searching for: {}", query_text);
-    if !classes.is_empty() {
-        println!("Classes: {:?}", classes);
+    if let Some(path) = &args.capture_raw {
+        let captured = pipeline.backend().captured_responses();
+        std::fs::write(path, serde_json::to_vec_pretty(&captured)?)?;
+        eprintln!("Wrote {} raw response(s) to {}", captured.len(), path);
+    }
+    let output = result?;
+
+    match args.format.as_str() {
+        "json" if args.legacy_json => {
+            println!("{}", serde_json::to_string_pretty(&output.result.hits)?);
+        }
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(&SearchResponse::new(output))?);
+        }
+        _ => print_search_text(&output, &pipeline.config().explain.severities),
+    }
+
+    Ok(())
+}
+
+fn print_search_text(output: &PipelineOutput, severities: &SeverityTable) {
+    let query = &output.result.query;
+
+    println!("Searching for: {}", query.mark_text);
+    if let Some(owner) = &query.owner_query {
+        println!("Owner: {}", owner);
+    }
+    if let Some(goods) = &query.goods_services_query {
+        println!("Goods/services: {}", goods);
+    }
+    if !query.classes.is_empty() {
+        println!("Classes: {:?}", query.classes);
     }
     println!("---");
 
-    // Retrieve from backend
-    let candidates = backend.search(&query).await?;
-    println!("Retrieved {} candidates from Manticore", candidates.len());
+    let result = &output.result;
+    print!(
+        "Retrieved {} candidates from {} in {} ms",
+        output.retrieved, result.backend, result.retrieval_latency_ms
+    );
+    match result.total_available {
+        Some(total) => println!(" ({} matching)", total),
+        None => println!(),
+    }
+    if let Some(profile) = &result.profile {
+        println!("Profile: {}", profile);
+    }
+    if !output.class_filter.expansions.is_empty() {
+        println!("Expanded classes: {:?}", output.class_filter.expanded_classes());
+    }
+    println!("{}", output.assessment.summary());
+    println!("Next step: {}", output.assessment.next_step.description());
 
-    // Re-rank with our scoring logic
-    let config = RerankConfig::default();
-    let hits = rerank(&query, candidates, &config);
+    for (i, hit) in output.result.hits.iter().enumerate() {
+        println!(
+            "\n{}. {} (Serial: {})",
+            i + 1,
+            hit.record.mark_text,
+            hit.record.serial_number
+        );
+        println!("   Status: {:?}", hit.record.status);
+        println!(
+            "   Risk Score: {:.2} | Retrieval Score: {:.2}",
+            hit.risk_score, hit.retrieval_score
+        );
+        println!("   {}", summarize_risk_with(hit, severities));
 
-    if format == "json" {
-        println!("{}", serde_json::to_string_pretty(&hits)?);
-    } else {
-        for (i, hit) in hits.iter().enumerate() {
-            println!(
-                "\n{}. {} (Serial: {})",
-                i + 1,
-                hit.record.mark_text,
-                hit.record.serial_number
-            );
-            println!("   Status: {:?}", hit.record.status);
-            println!(
-                "   Risk Score: {:.2} | Retrieval Score: {:.2}",
-                hit.risk_score, hit.retrieval_score
-            );
-            println!("   {}", summarize_risk(hit));
+        if let Some(review) = &hit.review {
+            println!("   Reviewer note: {}", review.note);
+        }
+
+        if !hit.family.is_empty() {
+            let serials: Vec<_> = hit.family.iter().map(|r| r.serial_number.as_str()).collect();
+            println!("   Same owner's other filings: {}", serials.join(", "));
+        }
+
+        if !hit.flags.is_empty() {
+            println!("   Flags: {:?}", hit.flags.iter().map(|f| f.label()).collect::<Vec<_>>());
+        }
 
-            if !hit.flags.is_empty() {
-                println!("   Flags: {:?}", hit.flags.iter().map(|f| f.label()).collect::<Vec<_>>());
+        if let Some(tsdr) = hit.links.as_ref().and_then(|links| links.tsdr.as_ref()) {
+            println!("   TSDR: {}", tsdr);
+        }
+
+        if let Some(assessment) = &hit.dupont {
+            for explanation in explain_dupont(assessment) {
+                println!("   {}", explanation.summary);
+                println!("      {}", explanation.detail);
             }
         }
     }
 
     println!("\n---");
-    println!("Total: {} results", hits.len());
-
-    Ok(())
+    println!("Total: {} results", output.result.hits.len());
+    if !output.suppressed.is_empty() {
+        println!("Suppressed by reviewer: {}", output.suppressed.len());
+    }
+    if output.stopped_early {
+        println!("Knockout stopped at the first high-risk hit");
+    }
+    if output.pruned > 0 {
+        println!("Pruned as low risk or beyond the result limit: {}", output.pruned);
+    }
+    for warning in &output.warnings {
+        println!("Warning: {}", warning);
+    }
+    for facet in &output.class_facets {
+        println!("  {}: {}", facet.label, facet.count);
+    }
 }
 
 async fn run_health(backend: &ManticoreBackend) -> Result<()> {
@@ -173,6 +705,144 @@ async fn run_health(backend: &ManticoreBackend) -> Result<()> {
     }
 }
 
+async fn run_index(backend: &ManticoreBackend, command: IndexCommand) -> Result<()> {
+    let admin = IndexAdmin::new(backend);
+
+    match command {
+        IndexCommand::Status => {
+            let target = admin.current_target().await?;
+            println!("Alias: {}", admin.alias());
+            match target {
+                Some(table) => {
+                    println!("Target: {}", table);
+                    println!("Documents: {}", admin.count(&table).await?);
+                }
+                None => println!("Target: (none)"),
+            }
+        }
+        IndexCommand::Promote { table, min_ratio } => {
+            let policy = ValidationPolicy {
+                min_ratio_of_active: min_ratio,
+                ..Default::default()
+            };
+            let outcome = admin.promote(&table, &policy).await?;
+            println!(
+                "{} -> {} (previous: {})",
+                outcome.alias,
+                outcome.current,
+                outcome.previous.as_deref().unwrap_or("none")
+            );
+        }
+        IndexCommand::Rollback { to } => {
+            let outcome = admin.rollback(Some(&to)).await?;
+            println!("{} -> {} (rolled back)", outcome.alias, outcome.current);
+        }
+        IndexCommand::Drop { table } => {
+            admin.drop_table(&table).await?;
+            println!("Dropped {}", table);
+        }
+        IndexCommand::Terms { out, batch } => {
+            let mut frequencies = TermFrequencies::default();
+            let mut after_id = 0;
+            loop {
+                let page = admin.mark_texts(after_id, batch).await?;
+                let Some((last_id, _)) = page.last() else {
+                    break;
+                };
+                after_id = *last_id;
+                for (_, mark) in &page {
+                    frequencies.add_mark(mark);
+                }
+                eprintln!("  {} marks read", frequencies.marks());
+                if page.len() < batch {
+                    break;
+                }
+            }
+            frequencies
+                .save(&out)
+                .with_context(|| format!("Failed to write {}", out))?;
+            println!("Counted {} marks into {}", frequencies.marks(), out);
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_audit(
+    pipeline: &Pipeline<ManticoreBackend>,
+    tenant: Option<&str>,
+    args: &AuditArgs,
+) -> Result<()> {
+    let id = if args.serial {
+        RecordId::serial(&args.number)
+    } else if args.registration {
+        RecordId::registration(&args.number)
+    } else {
+        RecordId::parse(&args.number)
+    }
+    .ok_or_else(|| anyhow::anyhow!("Invalid serial or registration number: {}", args.number))?;
+
+    let (record, output) = pipeline.run_for_record(tenant, &id).await?;
+
+    match args.format.as_str() {
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(&SearchResponse::new(output))?);
+        }
+        _ => {
+            println!(
+                "Auditing {} (Serial: {}, Owner: {})",
+                record.mark_text, record.serial_number, record.owner
+            );
+            print_search_text(&output, &pipeline.config().explain.severities);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_stats(args: &StatsArgs) -> Result<()> {
+    let day_ms = |date: &Option<String>| -> Result<Option<u64>> {
+        Ok(match date {
+            Some(date) => Some(parse_iso_date(date)?.max(0) as u64 * 86_400_000),
+            None => None,
+        })
+    };
+
+    let config = AggregationConfig {
+        min_contributors: args.min_tenants,
+        max_per_contributor: args.max_per_tenant,
+        noise: match args.epsilon {
+            Some(epsilon) => Noise::laplace(epsilon).map_err(anyhow::Error::msg)?,
+            None => Noise::None,
+        },
+        ..Default::default()
+    };
+
+    let mut aggregator = TermAggregator::new();
+    aggregator.add_dir(
+        &args.audit_dir,
+        (day_ms(&args.since)?, day_ms(&args.until)?),
+        &config,
+    )?;
+    let mut report = aggregator.export(&config);
+    report.terms.truncate(args.top);
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => {
+            println!("Aggregated {} sampled searches", aggregator.records());
+            println!("---");
+            for stat in &report.terms {
+                println!("{:<24} {:>6} conflicted / {:>6} searches", stat.term, stat.conflicted, stat.searches);
+            }
+            println!("---");
+            println!("Withheld {} terms with fewer than {} tenants", report.suppressed, args.min_tenants);
+        }
+    }
+
+    Ok(())
+}
+
 async fn run_benchmark(_backend: &ManticoreBackend, test_file: &str) -> Result<()> {
     // TODO: Implement benchmark loading and execution
     println!("Benchmark not yet implemented");
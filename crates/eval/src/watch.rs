@@ -0,0 +1,209 @@
+//! `eval watch`: run watchlists once or as a long-running daemon.
+//!
+//! In daemon mode the watchlist is scheduled in-process (see
+//! `ilegalflow_pipeline::watch`), a minimal HTTP health endpoint reports
+//! the schedule, and SIGHUP reloads the watchlist file without dropping
+//! the schedule of unchanged watches.
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use ilegalflow_backend_manticore::ManticoreBackend;
+use ilegalflow_pipeline::watch::{Watch, WatchScheduler, Watchlist};
+use ilegalflow_pipeline::{Pipeline, SearchResponse};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Longest sleep between schedule checks, so reloads that add earlier
+/// watches are picked up promptly even without a signal.
+const MAX_IDLE_MS: u64 = 60_000;
+
+#[derive(Subcommand)]
+pub enum WatchCommand {
+    /// Run the watches in a watchlist file
+    Run {
+        /// Watchlist JSON file
+        watchlist: PathBuf,
+
+        /// Keep running, re-running each watch on its cadence
+        #[arg(long)]
+        daemon: bool,
+
+        /// Address for the daemon health endpoint
+        #[arg(long, default_value = "127.0.0.1:9465")]
+        health_addr: String,
+
+        /// Directory for result files (<watch>-<timestamp>.json)
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+pub async fn run_watch(pipeline: &Pipeline<ManticoreBackend>, command: WatchCommand) -> Result<()> {
+    match command {
+        WatchCommand::Run {
+            watchlist,
+            daemon,
+            health_addr,
+            out_dir,
+        } => {
+            let list = Watchlist::load(&watchlist)
+                .with_context(|| format!("Failed to load watchlist {}", watchlist.display()))?;
+
+            if let Some(dir) = &out_dir {
+                std::fs::create_dir_all(dir)?;
+            }
+
+            if daemon {
+                run_daemon(pipeline, &watchlist, list, &health_addr, out_dir.as_deref()).await
+            } else {
+                let mut failed = 0;
+                for watch in &list.watches {
+                    if let Err(e) = run_one(pipeline, watch, now_ms(), out_dir.as_deref()).await {
+                        eprintln!("{}: {}", watch.name, e);
+                        failed += 1;
+                    }
+                }
+                if failed > 0 {
+                    anyhow::bail!("{} of {} watches failed", failed, list.watches.len());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn run_daemon(
+    pipeline: &Pipeline<ManticoreBackend>,
+    path: &Path,
+    list: Watchlist,
+    health_addr: &str,
+    out_dir: Option<&Path>,
+) -> Result<()> {
+    let scheduler = Arc::new(Mutex::new(WatchScheduler::new(list, now_ms())));
+
+    let listener = TcpListener::bind(health_addr)
+        .await
+        .with_context(|| format!("Failed to bind health endpoint {}", health_addr))?;
+    tokio::spawn(serve_health(listener, scheduler.clone()));
+
+    #[cfg(unix)]
+    let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+
+    tracing::info!(
+        watches = scheduler.lock().map(|s| s.len()).unwrap_or(0),
+        health = %health_addr,
+        "Watch daemon started"
+    );
+
+    loop {
+        let sleep_ms = scheduler
+            .lock()
+            .ok()
+            .and_then(|s| s.next_due_ms())
+            .map(|due| due.saturating_sub(now_ms()))
+            .unwrap_or(MAX_IDLE_MS)
+            .min(MAX_IDLE_MS);
+
+        #[cfg(unix)]
+        let reload = hangup.recv();
+        #[cfg(not(unix))]
+        let reload = std::future::pending::<Option<()>>();
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(sleep_ms)) => {}
+            _ = reload => {
+                match Watchlist::load(path) {
+                    Ok(list) => {
+                        if let Ok(mut s) = scheduler.lock() {
+                            s.reload(list, now_ms());
+                            tracing::info!(watches = s.len(), "Reloaded watchlist");
+                        }
+                    }
+                    // Keep the current schedule if the new file is bad
+                    Err(e) => tracing::warn!(error = %e, "Failed to reload watchlist"),
+                }
+                continue;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("Watch daemon stopping");
+                return Ok(());
+            }
+        }
+
+        let now = now_ms();
+        let due = scheduler.lock().map(|mut s| s.take_due(now)).unwrap_or_default();
+
+        for watch in due {
+            let error = run_one(pipeline, &watch, now, out_dir)
+                .await
+                .err()
+                .map(|e| e.to_string());
+            if let Some(e) = &error {
+                tracing::warn!(watch = %watch.name, error = %e, "Watch run failed");
+            }
+            if let Ok(mut s) = scheduler.lock() {
+                s.record_result(&watch.name, error);
+            }
+        }
+    }
+}
+
+/// Run a single watch, writing or printing its results.
+async fn run_one(
+    pipeline: &Pipeline<ManticoreBackend>,
+    watch: &Watch,
+    now: u64,
+    out_dir: Option<&Path>,
+) -> Result<()> {
+    let output = match &watch.tenant {
+        Some(tenant) => pipeline.run_for_tenant(tenant, &watch.query).await?,
+        None => pipeline.run(&watch.query).await?,
+    };
+
+    tracing::info!(watch = %watch.name, hits = output.result.hits.len(), "Watch run complete");
+
+    let response = SearchResponse::new(output);
+    match out_dir {
+        Some(dir) => {
+            let file = std::fs::File::create(dir.join(format!("{}-{}.json", watch.name, now)))?;
+            serde_json::to_writer_pretty(file, &response)?;
+        }
+        None => println!("{}", serde_json::to_string(&response)?),
+    }
+
+    Ok(())
+}
+
+/// Answer every connection with the schedule as JSON.
+async fn serve_health(listener: TcpListener, scheduler: Arc<Mutex<WatchScheduler>>) {
+    loop {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            continue;
+        };
+
+        let status = scheduler.lock().map(|s| s.status()).unwrap_or_default();
+        let body = serde_json::json!({ "status": "ok", "watches": status }).to_string();
+
+        tokio::spawn(async move {
+            // Drain the request line; any path gets the same answer
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
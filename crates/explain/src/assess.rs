@@ -0,0 +1,247 @@
+//! Overall assessment of a search.
+//!
+//! `summarize_risk` speaks to one hit; `assess_search` speaks to the
+//! whole result list, for display above it: how risky the mark looks
+//! overall, which candidates could block it, what the hits have in
+//! common, and what to do next.
+
+use crate::RiskTier;
+use ilegalflow_model::{
+    compare_hits, CandidateHit, NiceClass, SearchQuery, SeverityTable, TrademarkStatus,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Most blocking candidates listed in an assessment.
+pub const MAX_BLOCKING: usize = 5;
+
+/// Overall assessment of one search's hits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchAssessment {
+    /// The mark searched for
+    pub mark_text: String,
+    /// Number of hits assessed
+    pub hits: usize,
+    /// Highest tier among the hits
+    pub tier: RiskTier,
+    /// Live or pending HIGH tier hits, highest risk first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocking: Vec<BlockingCandidate>,
+    /// Number of hits carrying each flag, by flag label
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub flag_counts: BTreeMap<String, usize>,
+    /// Number of hits in each Nice class
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub class_counts: BTreeMap<NiceClass, usize>,
+    /// Recommended next step
+    pub next_step: NextStep,
+}
+
+/// A hit that could block registration of the searched mark.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockingCandidate {
+    pub serial_number: String,
+    pub mark_text: String,
+    pub status: TrademarkStatus,
+    pub risk_score: f32,
+    /// Labels of the HIGH severity flags that make it blocking
+    pub reasons: Vec<String>,
+}
+
+/// What to do after a search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NextStep {
+    /// Nothing on the register stands in the way
+    #[default]
+    Proceed,
+    /// Flagged candidates need a closer look
+    Investigate,
+    /// Live marks are likely to block this one
+    Reconsider,
+}
+
+impl NextStep {
+    /// The recommendation, in a sentence.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Proceed => {
+                "No blocking conflicts were found on the register. Run a full search, \
+                 including common-law use, before filing."
+            }
+            Self::Investigate => {
+                "Review the flagged candidates, including their goods and actual use, \
+                 before adopting the mark."
+            }
+            Self::Reconsider => {
+                "Live or pending marks are likely to block registration. Consider another \
+                 mark, or obtain an attorney's opinion before filing."
+            }
+        }
+    }
+}
+
+impl SearchAssessment {
+    /// One-line summary for the top of the results.
+    pub fn summary(&self) -> String {
+        if self.hits == 0 {
+            return format!(
+                "{}: no conflicting marks found for '{}'",
+                self.tier.label(),
+                self.mark_text
+            );
+        }
+        match self.blocking.len() {
+            0 => format!(
+                "{}: no blocking candidates among {} hit(s) for '{}'",
+                self.tier.label(),
+                self.hits,
+                self.mark_text
+            ),
+            n => format!(
+                "{}: {} candidate(s) may block '{}'",
+                self.tier.label(),
+                n,
+                self.mark_text
+            ),
+        }
+    }
+}
+
+/// Assess the hits of a search as a whole.
+///
+/// Dead marks raise the overall tier like any other hit but never count
+/// as blocking, so a search whose only HIGH tier hits are dead calls for
+/// investigation rather than a new mark.
+pub fn assess_search(query: &SearchQuery, hits: &[CandidateHit]) -> SearchAssessment {
+    assess_search_with(query, hits, &SeverityTable::DEFAULT)
+}
+
+/// Assess the hits of a search as a whole, tiering them under a
+/// severity table.
+pub fn assess_search_with(
+    query: &SearchQuery,
+    hits: &[CandidateHit],
+    severities: &SeverityTable,
+) -> SearchAssessment {
+    let tier_of = |hit: &CandidateHit| RiskTier::of_with(hit, severities);
+    let tier = hits.iter().map(tier_of).max().unwrap_or(RiskTier::Low);
+
+    let mut blocking: Vec<&CandidateHit> = hits
+        .iter()
+        .filter(|hit| tier_of(hit) == RiskTier::High)
+        .filter(|hit| hit.record.status != TrademarkStatus::Dead)
+        .collect();
+    blocking.sort_by(|a, b| compare_hits(a, b));
+    let blocking: Vec<BlockingCandidate> = blocking
+        .into_iter()
+        .take(MAX_BLOCKING)
+        .map(|hit| BlockingCandidate {
+            serial_number: hit.record.serial_number.clone(),
+            mark_text: hit.record.mark_text.clone(),
+            status: hit.record.status,
+            risk_score: hit.risk_score,
+            reasons: hit
+                .flags
+                .iter()
+                .filter(|f| RiskTier::from_severity(severities.severity(f)) == RiskTier::High)
+                .map(|f| f.label().to_string())
+                .collect(),
+        })
+        .collect();
+
+    let mut flag_counts = BTreeMap::new();
+    let mut class_counts = BTreeMap::new();
+    for hit in hits {
+        let mut labels: Vec<&str> = hit.flags.iter().map(|f| f.label()).collect();
+        labels.sort_unstable();
+        labels.dedup();
+        for label in labels {
+            *flag_counts.entry(label.to_string()).or_insert(0) += 1;
+        }
+        for class in &hit.record.classes {
+            *class_counts.entry(*class).or_insert(0) += 1;
+        }
+    }
+
+    let next_step = if !blocking.is_empty() {
+        NextStep::Reconsider
+    } else if tier > RiskTier::Low {
+        NextStep::Investigate
+    } else {
+        NextStep::Proceed
+    };
+
+    SearchAssessment {
+        mark_text: query.mark_text.clone(),
+        hits: hits.len(),
+        tier,
+        blocking,
+        flag_counts,
+        class_counts,
+        next_step,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::{RiskFlag, TrademarkRecord};
+
+    fn hit(
+        serial: &str,
+        status: TrademarkStatus,
+        score: f32,
+        flags: Vec<RiskFlag>,
+    ) -> CandidateHit {
+        let mut record = TrademarkRecord::new(serial, "NIKE");
+        record.status = status;
+        record.classes = NiceClass::of_all(&[25]);
+        CandidateHit {
+            record,
+            retrieval_score: 1.0,
+            risk_score: score,
+            risk_probability: None,
+            flags,
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_assess_search() {
+        let query = SearchQuery::new("NIKE");
+        let fuzzy = RiskFlag::FuzzyMatch { distance: 1 };
+        let hits = vec![
+            hit("001", TrademarkStatus::Live, 0.7, vec![RiskFlag::ExactMatch, fuzzy.clone()]),
+            hit("002", TrademarkStatus::Dead, 0.95, vec![RiskFlag::ExactMatch]),
+            hit("003", TrademarkStatus::Pending, 0.9, vec![RiskFlag::ExactMatch]),
+            hit("004", TrademarkStatus::Live, 0.3, vec![fuzzy]),
+        ];
+
+        let assessment = assess_search(&query, &hits);
+        assert_eq!(assessment.tier, RiskTier::High);
+        let serials: Vec<_> =
+            assessment.blocking.iter().map(|b| b.serial_number.as_str()).collect();
+        assert_eq!(serials, ["003", "001"]);
+        assert_eq!(assessment.blocking[1].reasons, ["Exact Match"]);
+        assert_eq!(assessment.flag_counts["Exact Match"], 3);
+        assert_eq!(assessment.class_counts[&NiceClass::of(25)], 4);
+        assert_eq!(assessment.next_step, NextStep::Reconsider);
+        assert_eq!(assessment.summary(), "HIGH RISK: 2 candidate(s) may block 'NIKE'");
+
+        // Dead marks alone call for investigation
+        let assessment = assess_search(&query, &hits[1..2]);
+        assert_eq!(assessment.tier, RiskTier::High);
+        assert!(assessment.blocking.is_empty());
+        assert_eq!(assessment.next_step, NextStep::Investigate);
+
+        let assessment = assess_search(&query, &[]);
+        assert_eq!(assessment.next_step, NextStep::Proceed);
+        assert_eq!(assessment.summary(), "LOW RISK: no conflicting marks found for 'NIKE'");
+    }
+}
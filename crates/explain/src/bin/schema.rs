@@ -0,0 +1,29 @@
+//! Writes JSON Schemas for the types API consumers exchange.
+//!
+//! Usage: `ilegalflow-schema [OUT_DIR]` (default `schemas`). Each type
+//! gets `<OUT_DIR>/<Type>.schema.json`, for generating client types.
+
+use ilegalflow_explain::Explanation;
+use ilegalflow_model::{CandidateHit, RiskFlag, SearchQuery, TrademarkRecord};
+use schemars::{schema_for, Schema};
+use std::path::Path;
+use std::{env, fs, io};
+
+fn main() -> io::Result<()> {
+    let out_dir = env::args().nth(1).unwrap_or_else(|| "schemas".to_string());
+    let schemas: [(&str, Schema); 5] = [
+        ("TrademarkRecord", schema_for!(TrademarkRecord)),
+        ("SearchQuery", schema_for!(SearchQuery)),
+        ("CandidateHit", schema_for!(CandidateHit)),
+        ("RiskFlag", schema_for!(RiskFlag)),
+        ("Explanation", schema_for!(Explanation)),
+    ];
+
+    fs::create_dir_all(&out_dir)?;
+    for (name, schema) in schemas {
+        let path = Path::new(&out_dir).join(format!("{}.schema.json", name));
+        fs::write(&path, serde_json::to_string_pretty(&schema)? + "\n")?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
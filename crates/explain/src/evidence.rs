@@ -0,0 +1,220 @@
+//! Kinds of evidence behind explanations.
+//!
+//! Each kind serializes to the snake_case name the `kind` field always
+//! carried, so existing JSON keeps parsing. Kinds built from open-ended
+//! names (custom phonetic encoders, new similarity metrics) that have no
+//! variant of their own are kept as `Other`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a piece of evidence is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EvidenceKind {
+    /// The mark text that matched exactly
+    ExactMatch,
+    /// A character substitution undone to find the match
+    Substitution,
+    /// A number spelled out, or a word written as a number
+    NumberWord,
+    /// A house mark stripped before comparing
+    HouseMark,
+    /// An entity designator (INC, LLC) stripped before comparing
+    Designator,
+    /// A split of a compound word
+    Segmentation,
+    /// An acronym and the words it abbreviates
+    Acronym,
+    /// Language of a foreign equivalent
+    Language,
+    /// Translation of a foreign equivalent
+    Translation,
+    /// Shared Soundex code
+    PhoneticSoundex,
+    /// Shared Metaphone code
+    PhoneticMetaphone,
+    /// Shared Double Metaphone code
+    PhoneticDoubleMetaphone,
+    /// Shared NYSIIS code
+    PhoneticNysiis,
+    /// Phoneme strings of a near phonetic match
+    Phonemes,
+    /// Edit distance between the marks
+    EditDistance,
+    /// Jaro-Winkler similarity
+    JaroWinkler,
+    /// Similarity with the words sorted
+    TokenSortRatio,
+    /// Similarity of the stemmed marks
+    StemMatch,
+    /// Similarity weighted toward the start of the marks
+    PrefixWeighted,
+    /// Character n-gram cosine similarity
+    NgramCosine,
+    /// Syllables aligned between the marks
+    AlignedSyllables,
+    /// A shared Nice class
+    NiceClass,
+    /// A pair of related Nice classes
+    RelatedClasses,
+    /// Similarity of the goods and services
+    GoodsSimilarity,
+    /// A design search code
+    DesignCode,
+    /// The dominant term the marks share
+    DominantTerm,
+    /// A normalization applied to the dominant term
+    DominantTransformation,
+    /// The famous mark matched
+    FamousMark,
+    /// Kind of dilution
+    Dilution,
+    /// Another similar mark held by the same owner
+    OwnerMark,
+    /// Unregistered use found
+    Use,
+    /// How long ago the mark died
+    StatusAge,
+    /// Registration status behind a risk adjustment
+    Status,
+    /// An event in the mark's prosecution history
+    ProsecutionEvent,
+    /// A reviewer's note
+    ReviewerNote,
+    /// A risk flag behind a du Pont factor finding
+    RiskFlag,
+    /// Any other kind, such as a custom phonetic encoder's
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl EvidenceKind {
+    /// Every kind with a variant of its own.
+    pub const KNOWN: [EvidenceKind; 36] = [
+        Self::ExactMatch,
+        Self::Substitution,
+        Self::NumberWord,
+        Self::HouseMark,
+        Self::Designator,
+        Self::Segmentation,
+        Self::Acronym,
+        Self::Language,
+        Self::Translation,
+        Self::PhoneticSoundex,
+        Self::PhoneticMetaphone,
+        Self::PhoneticDoubleMetaphone,
+        Self::PhoneticNysiis,
+        Self::Phonemes,
+        Self::EditDistance,
+        Self::JaroWinkler,
+        Self::TokenSortRatio,
+        Self::StemMatch,
+        Self::PrefixWeighted,
+        Self::NgramCosine,
+        Self::AlignedSyllables,
+        Self::NiceClass,
+        Self::RelatedClasses,
+        Self::GoodsSimilarity,
+        Self::DesignCode,
+        Self::DominantTerm,
+        Self::DominantTransformation,
+        Self::FamousMark,
+        Self::Dilution,
+        Self::OwnerMark,
+        Self::Use,
+        Self::StatusAge,
+        Self::Status,
+        Self::ProsecutionEvent,
+        Self::ReviewerNote,
+        Self::RiskFlag,
+    ];
+
+    /// The kind of a shared phonetic code (e.g. "soundex").
+    pub fn phonetic(algorithm: &str) -> Self {
+        Self::from(format!("phonetic_{}", algorithm).as_str())
+    }
+
+    /// Wire name, as serialized.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::ExactMatch => "exact_match",
+            Self::Substitution => "substitution",
+            Self::NumberWord => "number_word",
+            Self::HouseMark => "house_mark",
+            Self::Designator => "designator",
+            Self::Segmentation => "segmentation",
+            Self::Acronym => "acronym",
+            Self::Language => "language",
+            Self::Translation => "translation",
+            Self::PhoneticSoundex => "phonetic_soundex",
+            Self::PhoneticMetaphone => "phonetic_metaphone",
+            Self::PhoneticDoubleMetaphone => "phonetic_double_metaphone",
+            Self::PhoneticNysiis => "phonetic_nysiis",
+            Self::Phonemes => "phonemes",
+            Self::EditDistance => "edit_distance",
+            Self::JaroWinkler => "jaro_winkler",
+            Self::TokenSortRatio => "token_sort_ratio",
+            Self::StemMatch => "stem_match",
+            Self::PrefixWeighted => "prefix_weighted",
+            Self::NgramCosine => "ngram_cosine",
+            Self::AlignedSyllables => "aligned_syllables",
+            Self::NiceClass => "nice_class",
+            Self::RelatedClasses => "related_classes",
+            Self::GoodsSimilarity => "goods_similarity",
+            Self::DesignCode => "design_code",
+            Self::DominantTerm => "dominant_term",
+            Self::DominantTransformation => "dominant_transformation",
+            Self::FamousMark => "famous_mark",
+            Self::Dilution => "dilution",
+            Self::OwnerMark => "owner_mark",
+            Self::Use => "use",
+            Self::StatusAge => "status_age",
+            Self::Status => "status",
+            Self::ProsecutionEvent => "prosecution_event",
+            Self::ReviewerNote => "reviewer_note",
+            Self::RiskFlag => "risk_flag",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for EvidenceKind {
+    /// The kind with this wire name, or `Other`.
+    fn from(name: &str) -> Self {
+        Self::KNOWN
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+            .unwrap_or_else(|| Self::Other(name.to_string()))
+    }
+}
+
+impl fmt::Display for EvidenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_names() {
+        for kind in EvidenceKind::KNOWN {
+            let json = serde_json::to_value(&kind).unwrap();
+            assert_eq!(json, kind.as_str(), "{:?}", kind);
+            assert_eq!(EvidenceKind::from(kind.as_str()), kind);
+            assert_eq!(serde_json::from_value::<EvidenceKind>(json).unwrap(), kind);
+        }
+
+        assert_eq!(EvidenceKind::phonetic("nysiis"), EvidenceKind::PhoneticNysiis);
+        let cologne = EvidenceKind::phonetic("cologne");
+        assert_eq!(cologne, EvidenceKind::Other("phonetic_cologne".to_string()));
+        let json = serde_json::to_string(&cologne).unwrap();
+        assert_eq!(json, "\"phonetic_cologne\"");
+        assert_eq!(serde_json::from_str::<EvidenceKind>(&json).unwrap(), cologne);
+    }
+}
@@ -0,0 +1,965 @@
+//! Localized explanation text.
+//!
+//! Summaries and details are rendered from an embedded message catalog
+//! keyed by message id, with `{name}` placeholders filled per call, so
+//! every language shares the same formatting code. A message missing
+//! from a catalog falls back to English. Evidence kinds and values are
+//! data and stay untranslated.
+//!
+//! Messages with legal terminology have a `.plain` variant for
+//! `DetailLevel::Plain`, so both audiences share the same code too.
+
+use crate::DetailLevel;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Language explanations are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// Spanish
+    Es,
+    /// Portuguese
+    Pt,
+}
+
+impl Locale {
+    /// Every supported locale.
+    pub const ALL: [Self; 3] = [Self::En, Self::Es, Self::Pt];
+
+    /// ISO 639-1 code ("en").
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+            Self::Pt => "pt",
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => EN,
+            Self::Es => ES,
+            Self::Pt => PT,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Parse a language tag; the region is ignored ("pt-BR" is `Pt`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or_default().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+            .ok_or_else(|| format!("Unknown locale '{}' (en, es, pt)", s))
+    }
+}
+
+/// Render a message in a locale, filling its `{name}` placeholders from
+/// `args`. Placeholders without an argument are left as written.
+pub fn message(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = lookup(locale.catalog(), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key);
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match args.iter().find(|(arg, _)| *arg == name) {
+                    Some((_, value)) => out.push_str(&value.to_string()),
+                    None => out.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Render a message for an audience: at `DetailLevel::Plain`, the
+/// message's `.plain` variant is used where the locale has one.
+pub fn message_at(
+    locale: Locale,
+    level: DetailLevel,
+    key: &str,
+    args: &[(&str, &dyn Display)],
+) -> String {
+    if level == DetailLevel::Plain {
+        let plain = format!("{}.plain", key);
+        if lookup(locale.catalog(), &plain).is_some() {
+            return message(locale, &plain, args);
+        }
+    }
+    message(locale, key, args)
+}
+
+fn lookup(catalog: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog.iter().find(|(k, _)| *k == key).map(|(_, text)| *text)
+}
+
+const EN: &[(&str, &str)] = &[
+    ("exact.summary", "Exact match found"),
+    ("exact.detail",
+     "The mark '{mark}' is an exact match for your query '{query}'. This represents the \
+      highest level of potential conflict."),
+    ("obfuscated.summary", "Same mark with disguised characters"),
+    ("obfuscated.detail",
+     "The mark '{mark}' reads the same as your query '{query}' once character substitutions \
+      ({substitutions}) are undone. Leetspeak and look-alike characters do not distinguish \
+      a mark."),
+    ("number_word.summary.same", "Same mark with numbers spelled differently"),
+    ("number_word.summary.near", "Spelled similarly once numbers are swapped"),
+    ("number_word.detail.same",
+     "The mark '{mark}' reads the same as your query '{query}' once numerals and number \
+      words are swapped ({transformations}). A numeral reads the same as the word it \
+      stands for."),
+    ("number_word.detail.near",
+     "The mark '{mark}' differs by {distance} character(s) from your query '{query}' once \
+      numerals and number words are swapped ({transformations}). A numeral reads the same \
+      as the word it stands for."),
+    ("house_mark.summary", "Same mark apart from house mark"),
+    ("house_mark.detail.one",
+     "The mark '{mark}' matches your query '{query}' once {words} is removed. A house mark \
+      or designator added to a mark does not distinguish the product mark."),
+    ("house_mark.detail.many",
+     "The mark '{mark}' matches your query '{query}' once {words} are removed. A house mark \
+      or designator added to a mark does not distinguish the product mark."),
+    ("compound.summary", "Same words run together"),
+    ("compound.detail",
+     "The mark '{mark}' uses the same words as your query '{query}' once run-together words \
+      are split ({splits}). Joining or spacing words does not distinguish a mark."),
+    ("foreign.summary", "{language} equivalent of '{translation}'"),
+    ("foreign.detail",
+     "The marks '{mark}' and '{query}' both mean '{translation}' once {language} is \
+      translated. Under the doctrine of foreign equivalents, a mark in a common foreign \
+      language is compared by its English meaning."),
+    ("phonetic.summary", "Sounds similar"),
+    ("phonetic.detail",
+     "The mark '{mark}' sounds phonetically similar to '{query}'. Consumers may confuse the \
+      two when spoken aloud."),
+    ("near_phonetic.summary", "Sounds nearly alike ({percent}%)"),
+    ("near_phonetic.detail",
+     "The mark '{mark}' is pronounced within {distance} sound(s) of '{query}'. Marks this \
+      close are easily misheard for one another."),
+    ("fuzzy.summary", "Spelled similarly"),
+    ("fuzzy.detail",
+     "The mark '{mark}' differs from '{query}' by only {distance} character(s). This minor \
+      spelling difference may not prevent consumer confusion."),
+    ("reordered.summary", "Same words reordered ({percent}%)"),
+    ("reordered.detail",
+     "The mark '{mark}' uses largely the same words as '{query}' in a different order. \
+      Rearranging the words of a mark does not avoid confusion."),
+    ("stem.summary", "Same words apart from their endings"),
+    ("stem.detail",
+     "The mark '{mark}' uses the same words as '{query}' once plurals and endings such as \
+      -ING and -ER are set aside. Consumers see such marks as variants of one another."),
+    ("prefix.summary", "Same beginning ({percent}%)"),
+    ("prefix.detail",
+     "The mark '{mark}' begins like '{query}'. Consumers notice the start of a mark first, \
+      so differences toward the end do little to avoid confusion."),
+    ("ngram.summary", "Shares letter sequences ({percent}%)"),
+    ("ngram.detail",
+     "The mark '{mark}' shares most of its letter sequences with '{query}', regardless of \
+      spacing. Splitting or joining words does not avoid confusion."),
+    ("spelling.summary", "Spelled similarly ({percent}%)"),
+    ("spelling.detail",
+     "The mark '{mark}' is {percent}% similar in spelling to '{query}'. Marks sharing most \
+      of their letters, especially at the start, look alike to consumers."),
+    ("syllable.summary", "Similar syllables ({percent}%)"),
+    ("syllable.detail",
+     "The mark '{mark}' follows '{query}' syllable by syllable. Respelling a syllable keeps \
+      the sound and rhythm of a mark."),
+    ("class", "Class {class}"),
+    ("class_overlap.summary", "Same class ({classes})"),
+    ("class_overlap.detail",
+     "Both marks are registered in the same Nice classification(s): {classes}. This \
+      increases the likelihood of confusion in the marketplace."),
+    ("class_overlap.detail.inferred",
+     "Both marks appear to cover the same Nice classification(s): {classes}. At least one \
+      mark's classes were inferred from its goods/services description, so verify them \
+      against the filing."),
+    ("related_classes.summary", "Related classes ({pairs})"),
+    ("related_classes.detail",
+     "The marks are in different but related Nice classifications ({percent}% related). \
+      Buyers often expect goods and services in related classes to come from the same \
+      source."),
+    ("goods.summary", "Similar goods/services"),
+    ("goods.detail",
+     "The goods and services descriptions are {percent}% similar. Even with different \
+      marks, similar goods increase confusion risk."),
+    ("design_code.summary", "Same design elements ({codes})"),
+    ("design_code.detail",
+     "Both marks carry the design code(s) {codes}. Similar figurative elements can cause \
+      confusion even when the words differ."),
+    ("dominant.summary", "Dominant term '{term}' matches"),
+    ("dominant.detail",
+     "The dominant/distinctive element '{term}' appears in both marks. Courts often focus \
+      on dominant terms when assessing confusion."),
+    ("dominant.detail.normalized",
+     "The dominant/distinctive element '{term}' appears in both marks once spelling and \
+      spacing are normalized. Stylized or run-together spellings do not avoid confusion."),
+    ("famous.summary", "Famous mark ({famous})"),
+    ("famous.detail",
+     "Both '{query}' and '{mark}' contain {famous}, a famous mark owned by {owner}. Famous \
+      marks receive broader protection against confusion and dilution."),
+    ("famous.detail.cross_class",
+     "Both '{query}' and '{mark}' contain {famous}, a famous mark owned by {owner}. Famous \
+      marks receive broader protection against confusion and dilution, even in unrelated \
+      classes."),
+    ("blurring.summary", "Could blur famous mark {famous}"),
+    ("blurring.detail",
+     "Using '{query}' could weaken the link between {famous} and its owner, so the famous \
+      mark no longer points to a single source. Dilution by blurring does not require \
+      confusion or related goods."),
+    ("tarnishment.summary", "Could tarnish famous mark {famous}"),
+    ("tarnishment.detail",
+     "Using '{query}' for these goods or services could harm the reputation of {famous} by \
+      association. Dilution by tarnishment does not require confusion or related goods."),
+    ("common_law.summary", "Common law usage"),
+    ("common_law.detail",
+     "'{mark}' appears to be in use outside the register ({source}), so its owner may hold \
+      unregistered common law rights beyond its registration. Consider conducting a \
+      comprehensive common law search."),
+    ("design_element.summary", "Design mark: words compared only"),
+    ("design_element.summary.shared", "Design mark with shared elements ({shared})"),
+    ("design_element.detail",
+     "'{mark}' is a design mark whose words may be incidental to its design ({codes}). \
+      Only the words were compared, so review the design itself."),
+    ("design_element.detail.shared",
+     "'{mark}' is a design mark sharing the design code(s) {shared} with your mark. Its \
+      figurative elements weigh more than its words."),
+    ("recently_dead.summary.zero", "Died this month"),
+    ("recently_dead.summary.one", "Died 1 month ago"),
+    ("recently_dead.summary.many", "Died {months} months ago"),
+    ("recently_dead.detail",
+     "'{mark}' was abandoned, cancelled, or expired recently. A recently dead mark may be \
+      revived or may still carry goodwill with buyers, so it is weighed more heavily than \
+      a long-dead one."),
+    ("acronym.summary", "Acronym of the other mark"),
+    ("acronym.detail",
+     "'{acronym}' is formed from the initials of '{expansion}'. Buyers who know a company \
+      by its initials may take the acronym and the full name for the same mark."),
+    ("owner_conflict.summary", "Owner holds similar marks"),
+    ("owner_conflict.detail",
+     "{owner}, the owner of '{mark}', also holds other marks similar to '{query}' \
+      ({marks}). Marks sharing a common element can form a family of marks, which \
+      protects that element in new marks too."),
+    ("other.detail", "'{mark}' was flagged as a possible conflict with '{query}'."),
+    ("review.pin.summary", "Flagged by reviewer"),
+    ("review.pin.detail", "A reviewer pinned this mark for attention."),
+    ("review.suppress.summary", "Cleared by reviewer"),
+    ("review.suppress.detail", "A reviewer marked this mark as not a concern."),
+    ("review.note", "{detail} Note: {note}"),
+    ("status.live.summary", "Live registration"),
+    ("status.live.reason", "The mark is registered and in force."),
+    ("status.pending.summary", "Pending application"),
+    ("status.pending.reason",
+     "The application has not registered yet; if it does, it takes priority from its \
+      filing date."),
+    ("status.dead.summary", "Dead mark"),
+    ("status.dead.reason",
+     "The registration or application is cancelled, expired, or abandoned, so it is \
+      unlikely to block a new mark, though the mark may still be in use."),
+    ("status.unknown.summary", "Status unknown"),
+    ("status.unknown.reason", "The record's status was not reported."),
+    ("status.summary.reduced", "{status}: risk reduced"),
+    ("status.summary.raised", "{status}: risk raised"),
+    ("status.detail.reduced",
+     "{reason} The risk score was reduced to {percent}% for this status."),
+    ("status.detail.raised", "{reason} The risk score was raised to {percent}% for this status."),
+    ("prosecution.refusal.summary", "Previously refused for likelihood of confusion"),
+    ("prosecution.refusal.detail",
+     "The examiner refused '{mark}' under Section 2(d) during its prosecution, finding it \
+      likely to be confused with an earlier mark."),
+    ("tier.low", "Low"),
+    ("tier.moderate", "Moderate"),
+    ("tier.high", "High"),
+    ("references", "See {citations}"),
+    // Plain-language variants, used at DetailLevel::Plain
+    ("exact.detail.plain",
+     "'{mark}' is exactly the same as '{query}'. This is the strongest kind of conflict."),
+    ("obfuscated.detail.plain",
+     "'{mark}' reads the same as '{query}' once look-alike characters ({substitutions}) are \
+      swapped back."),
+    ("number_word.detail.same.plain",
+     "'{mark}' reads the same as '{query}' once numbers and number words are swapped \
+      ({transformations})."),
+    ("number_word.detail.near.plain",
+     "'{mark}' is only {distance} letter(s) away from '{query}' once numbers and number \
+      words are swapped ({transformations})."),
+    ("house_mark.detail.one.plain",
+     "'{mark}' is the same as '{query}' apart from the company name {words}. Adding a \
+      company name does not make a mark different enough."),
+    ("house_mark.detail.many.plain",
+     "'{mark}' is the same as '{query}' apart from the company names {words}. Adding a \
+      company name does not make a mark different enough."),
+    ("compound.detail.plain",
+     "'{mark}' uses the same words as '{query}', only joined or spaced differently \
+      ({splits})."),
+    ("foreign.detail.plain",
+     "'{mark}' and '{query}' both mean '{translation}', one of them in {language}. A \
+      translation counts as the same name."),
+    ("phonetic.detail.plain", "'{mark}' sounds like '{query}' when said out loud."),
+    ("near_phonetic.detail.plain", "'{mark}' sounds almost like '{query}' when said out loud."),
+    ("fuzzy.detail.plain", "'{mark}' is only {distance} letter(s) away from '{query}'."),
+    ("class_overlap.summary.plain", "Same kind of products ({classes})"),
+    ("class_overlap.detail.plain",
+     "Both marks cover the same kind of products or services ({classes})."),
+    ("class_overlap.detail.inferred.plain",
+     "Both marks seem to cover the same kind of products or services ({classes}). Check the \
+      filings to be sure."),
+    ("related_classes.summary.plain", "Related products ({pairs})"),
+    ("related_classes.detail.plain",
+     "The marks cover different but related products or services. Customers could think \
+      they come from the same business."),
+    ("goods.detail.plain",
+     "The products or services listed for the two marks are {percent}% alike."),
+    ("design_code.detail.plain", "Both marks use similar pictures or symbols ({codes})."),
+    ("dominant.summary.plain", "Shares the key word '{term}'"),
+    ("dominant.detail.plain",
+     "Both marks share '{term}', the part of a name people remember most."),
+    ("dominant.detail.normalized.plain",
+     "Both marks share '{term}' once spelling and spacing are ignored. It is the part of a \
+      name people remember most."),
+    ("famous.detail.plain",
+     "Both '{query}' and '{mark}' contain {famous}, a famous brand owned by {owner}. Famous \
+      brands are protected more widely than others."),
+    ("famous.detail.cross_class.plain",
+     "Both '{query}' and '{mark}' contain {famous}, a famous brand owned by {owner}. Famous \
+      brands are protected even for unrelated products."),
+    ("blurring.summary.plain", "Could weaken famous brand {famous}"),
+    ("blurring.detail.plain",
+     "Using '{query}' could make {famous} less special as a famous brand, even if no one is \
+      confused."),
+    ("tarnishment.summary.plain", "Could harm famous brand {famous}"),
+    ("tarnishment.detail.plain",
+     "Using '{query}' for these products could hurt the reputation of {famous}, even if no \
+      one is confused."),
+    ("common_law.summary.plain", "In use without registration"),
+    ("common_law.detail.plain",
+     "'{mark}' seems to be in use ({source}), and its owner may have rights from that use \
+      alone."),
+    ("design_element.summary.plain", "Logo: only the words compared"),
+    ("design_element.detail.plain",
+     "'{mark}' is a logo, and only its words were compared. Look at the logo itself too."),
+    ("design_element.detail.shared.plain",
+     "'{mark}' is a logo that shares picture elements ({shared}) with yours."),
+    ("recently_dead.detail.plain",
+     "'{mark}' was dropped recently. Its owner could bring it back, or customers may still \
+      remember it."),
+    ("acronym.detail.plain",
+     "'{acronym}' is spelled with the first letters of '{expansion}', so people may think \
+      they are the same brand."),
+    ("owner_conflict.detail.plain",
+     "{owner} already has several marks like '{query}' ({marks}), and customers may think \
+      yours is one more of them."),
+    ("prosecution.refusal.detail.plain",
+     "The trademark office once turned '{mark}' down because it was too close to an earlier \
+      mark."),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("exact.summary", "Coincidencia exacta"),
+    ("exact.detail",
+     "La marca '{mark}' coincide exactamente con su consulta '{query}'. Es el mayor grado \
+      de conflicto posible."),
+    ("obfuscated.summary", "La misma marca con caracteres disfrazados"),
+    ("obfuscated.detail",
+     "La marca '{mark}' se lee igual que su consulta '{query}' una vez deshechas las \
+      sustituciones de caracteres ({substitutions}). El leetspeak y los caracteres de \
+      aspecto similar no distinguen una marca."),
+    ("number_word.summary.same", "La misma marca con los números escritos de otra forma"),
+    ("number_word.summary.near", "Ortografía similar al intercambiar los números"),
+    ("number_word.detail.same",
+     "La marca '{mark}' se lee igual que su consulta '{query}' al intercambiar cifras y \
+      números escritos en letra ({transformations}). Una cifra se lee igual que la palabra \
+      que representa."),
+    ("number_word.detail.near",
+     "La marca '{mark}' difiere en {distance} carácter(es) de su consulta '{query}' al \
+      intercambiar cifras y números escritos en letra ({transformations}). Una cifra se lee \
+      igual que la palabra que representa."),
+    ("house_mark.summary", "La misma marca salvo la marca de la casa"),
+    ("house_mark.detail.one",
+     "La marca '{mark}' coincide con su consulta '{query}' al eliminar {words}. Añadir una \
+      marca de la casa o un designador a una marca no distingue la marca del producto."),
+    ("house_mark.detail.many",
+     "La marca '{mark}' coincide con su consulta '{query}' al eliminar {words}. Añadir una \
+      marca de la casa o un designador a una marca no distingue la marca del producto."),
+    ("compound.summary", "Las mismas palabras unidas"),
+    ("compound.detail",
+     "La marca '{mark}' usa las mismas palabras que su consulta '{query}' al separar las \
+      palabras unidas ({splits}). Unir o separar palabras no distingue una marca."),
+    ("foreign.summary", "Equivalente en {language} de '{translation}'"),
+    ("foreign.detail",
+     "Las marcas '{mark}' y '{query}' significan '{translation}' al traducir del \
+      {language}. Según la doctrina de los equivalentes extranjeros, una marca en un idioma \
+      extranjero común se compara por su significado en inglés."),
+    ("phonetic.summary", "Suena similar"),
+    ("phonetic.detail",
+     "La marca '{mark}' suena de forma similar a '{query}'. Los consumidores pueden \
+      confundirlas al pronunciarlas."),
+    ("near_phonetic.summary", "Suena casi igual ({percent}%)"),
+    ("near_phonetic.detail",
+     "La marca '{mark}' se pronuncia a {distance} sonido(s) de '{query}'. Marcas tan \
+      cercanas se confunden fácilmente al oído."),
+    ("fuzzy.summary", "Ortografía similar"),
+    ("fuzzy.detail",
+     "La marca '{mark}' difiere de '{query}' en solo {distance} carácter(es). Esta pequeña \
+      diferencia ortográfica puede no evitar la confusión del consumidor."),
+    ("reordered.summary", "Las mismas palabras en otro orden ({percent}%)"),
+    ("reordered.detail",
+     "La marca '{mark}' usa en gran parte las mismas palabras que '{query}' en distinto \
+      orden. Reordenar las palabras de una marca no evita la confusión."),
+    ("stem.summary", "Las mismas palabras salvo sus terminaciones"),
+    ("stem.detail",
+     "La marca '{mark}' usa las mismas palabras que '{query}' si se dejan de lado los \
+      plurales y terminaciones como -ING y -ER. Los consumidores ven estas marcas como \
+      variantes una de otra."),
+    ("prefix.summary", "El mismo comienzo ({percent}%)"),
+    ("prefix.detail",
+     "La marca '{mark}' empieza como '{query}'. Los consumidores se fijan primero en el \
+      comienzo de una marca, por lo que las diferencias hacia el final apenas evitan la \
+      confusión."),
+    ("ngram.summary", "Comparte secuencias de letras ({percent}%)"),
+    ("ngram.detail",
+     "La marca '{mark}' comparte la mayoría de sus secuencias de letras con '{query}', sin \
+      importar los espacios. Separar o unir palabras no evita la confusión."),
+    ("spelling.summary", "Ortografía similar ({percent}%)"),
+    ("spelling.detail",
+     "La marca '{mark}' tiene una ortografía un {percent}% similar a '{query}'. Las marcas \
+      que comparten la mayoría de sus letras, sobre todo al comienzo, resultan parecidas \
+      para los consumidores."),
+    ("syllable.summary", "Sílabas similares ({percent}%)"),
+    ("syllable.detail",
+     "La marca '{mark}' sigue a '{query}' sílaba por sílaba. Cambiar la ortografía de una \
+      sílaba conserva el sonido y el ritmo de una marca."),
+    ("class", "Clase {class}"),
+    ("class_overlap.summary", "Misma clase ({classes})"),
+    ("class_overlap.detail",
+     "Ambas marcas están registradas en la(s) misma(s) clase(s) de Niza: {classes}. Esto \
+      aumenta la probabilidad de confusión en el mercado."),
+    ("class_overlap.detail.inferred",
+     "Ambas marcas parecen cubrir la(s) misma(s) clase(s) de Niza: {classes}. Las clases de \
+      al menos una marca se dedujeron de su descripción de productos/servicios, así que \
+      verifíquelas con la solicitud."),
+    ("related_classes.summary", "Clases relacionadas ({pairs})"),
+    ("related_classes.detail",
+     "Las marcas están en clases de Niza distintas pero relacionadas ({percent}% de \
+      relación). Los compradores suelen esperar que los productos y servicios de clases \
+      relacionadas provengan del mismo origen."),
+    ("goods.summary", "Productos/servicios similares"),
+    ("goods.detail",
+     "Las descripciones de productos y servicios son un {percent}% similares. Incluso con \
+      marcas distintas, los productos similares aumentan el riesgo de confusión."),
+    ("design_code.summary", "Los mismos elementos figurativos ({codes})"),
+    ("design_code.detail",
+     "Ambas marcas llevan el/los código(s) de diseño {codes}. Elementos figurativos \
+      similares pueden causar confusión aunque las palabras difieran."),
+    ("dominant.summary", "Coincide el término dominante '{term}'"),
+    ("dominant.detail",
+     "El elemento dominante/distintivo '{term}' aparece en ambas marcas. Los tribunales \
+      suelen centrarse en los términos dominantes al evaluar la confusión."),
+    ("dominant.detail.normalized",
+     "El elemento dominante/distintivo '{term}' aparece en ambas marcas una vez \
+      normalizados la ortografía y los espacios. Las grafías estilizadas o unidas no \
+      evitan la confusión."),
+    ("famous.summary", "Marca notoria ({famous})"),
+    ("famous.detail",
+     "Tanto '{query}' como '{mark}' contienen {famous}, una marca notoria de {owner}. Las \
+      marcas notorias gozan de una protección más amplia frente a la confusión y la \
+      dilución."),
+    ("famous.detail.cross_class",
+     "Tanto '{query}' como '{mark}' contienen {famous}, una marca notoria de {owner}. Las \
+      marcas notorias gozan de una protección más amplia frente a la confusión y la \
+      dilución, incluso en clases no relacionadas."),
+    ("blurring.summary", "Podría difuminar la marca notoria {famous}"),
+    ("blurring.detail",
+     "Usar '{query}' podría debilitar el vínculo entre {famous} y su titular, de modo que \
+      la marca notoria deje de indicar un único origen. La dilución por difuminación no \
+      requiere confusión ni productos relacionados."),
+    ("tarnishment.summary", "Podría empañar la marca notoria {famous}"),
+    ("tarnishment.detail",
+     "Usar '{query}' para estos productos o servicios podría dañar la reputación de \
+      {famous} por asociación. La dilución por empañamiento no requiere confusión ni \
+      productos relacionados."),
+    ("common_law.summary", "Uso no registrado"),
+    ("common_law.detail",
+     "'{mark}' parece usarse fuera del registro ({source}), por lo que su titular podría \
+      tener derechos no registrados más allá de su registro. Considere realizar una \
+      búsqueda exhaustiva de uso no registrado."),
+    ("design_element.summary", "Marca figurativa: solo se compararon las palabras"),
+    ("design_element.summary.shared", "Marca figurativa con elementos compartidos ({shared})"),
+    ("design_element.detail",
+     "'{mark}' es una marca figurativa cuyas palabras pueden ser accesorias a su diseño \
+      ({codes}). Solo se compararon las palabras, así que revise el diseño en sí."),
+    ("design_element.detail.shared",
+     "'{mark}' es una marca figurativa que comparte el/los código(s) de diseño {shared} con \
+      su marca. Sus elementos figurativos pesan más que sus palabras."),
+    ("recently_dead.summary.zero", "Caducó este mes"),
+    ("recently_dead.summary.one", "Caducó hace 1 mes"),
+    ("recently_dead.summary.many", "Caducó hace {months} meses"),
+    ("recently_dead.detail",
+     "'{mark}' fue abandonada, cancelada o caducó recientemente. Una marca caducada hace \
+      poco puede restablecerse o conservar prestigio entre los compradores, por lo que \
+      pesa más que una caducada hace tiempo."),
+    ("acronym.summary", "Acrónimo de la otra marca"),
+    ("acronym.detail",
+     "'{acronym}' se forma con las iniciales de '{expansion}'. Los compradores que \
+      conocen una empresa por sus iniciales pueden tomar el acrónimo y el nombre completo \
+      por la misma marca."),
+    ("owner_conflict.summary", "El titular tiene marcas similares"),
+    ("owner_conflict.detail",
+     "{owner}, titular de '{mark}', tiene también otras marcas similares a '{query}' \
+      ({marks}). Las marcas que comparten un elemento común pueden formar una familia de \
+      marcas, que protege ese elemento también en marcas nuevas."),
+    ("other.detail", "'{mark}' se señaló como posible conflicto con '{query}'."),
+    ("review.pin.summary", "Señalada por un revisor"),
+    ("review.pin.detail", "Un revisor destacó esta marca para su atención."),
+    ("review.suppress.summary", "Descartada por un revisor"),
+    ("review.suppress.detail", "Un revisor indicó que esta marca no es motivo de preocupación."),
+    ("review.note", "{detail} Nota: {note}"),
+    ("status.live.summary", "Registro vigente"),
+    ("status.live.reason", "La marca está registrada y en vigor."),
+    ("status.pending.summary", "Solicitud en trámite"),
+    ("status.pending.reason",
+     "La solicitud aún no se ha registrado; si se registra, tendrá prioridad desde su fecha \
+      de presentación."),
+    ("status.dead.summary", "Marca caducada"),
+    ("status.dead.reason",
+     "El registro o la solicitud está cancelado, caducado o abandonado, por lo que es poco \
+      probable que bloquee una nueva marca, aunque la marca aún podría estar en uso."),
+    ("status.unknown.summary", "Estado desconocido"),
+    ("status.unknown.reason", "No se informó el estado del registro."),
+    ("status.summary.reduced", "{status}: riesgo reducido"),
+    ("status.summary.raised", "{status}: riesgo aumentado"),
+    ("status.detail.reduced",
+     "{reason} La puntuación de riesgo se redujo al {percent}% por este estado."),
+    ("status.detail.raised",
+     "{reason} La puntuación de riesgo se aumentó al {percent}% por este estado."),
+    ("prosecution.refusal.summary", "Denegada antes por riesgo de confusión"),
+    ("prosecution.refusal.detail",
+     "El examinador denegó '{mark}' conforme a la Sección 2(d) durante su tramitación, al \
+      considerar probable la confusión con una marca anterior."),
+    ("tier.low", "Bajo"),
+    ("tier.moderate", "Moderado"),
+    ("tier.high", "Alto"),
+    ("references", "Véase {citations}"),
+    // Plain-language variants, used at DetailLevel::Plain
+    ("exact.detail.plain",
+     "'{mark}' es exactamente igual que '{query}'. Es el tipo de conflicto más fuerte."),
+    ("obfuscated.detail.plain",
+     "'{mark}' se lee igual que '{query}' al deshacer los caracteres parecidos \
+      ({substitutions})."),
+    ("number_word.detail.same.plain",
+     "'{mark}' se lee igual que '{query}' al intercambiar números y números escritos en \
+      letra ({transformations})."),
+    ("number_word.detail.near.plain",
+     "'{mark}' está a solo {distance} letra(s) de '{query}' al intercambiar números y \
+      números escritos en letra ({transformations})."),
+    ("house_mark.detail.one.plain",
+     "'{mark}' es igual que '{query}' salvo por el nombre de empresa {words}. Añadir el \
+      nombre de una empresa no hace que una marca sea lo bastante distinta."),
+    ("house_mark.detail.many.plain",
+     "'{mark}' es igual que '{query}' salvo por los nombres de empresa {words}. Añadir el \
+      nombre de una empresa no hace que una marca sea lo bastante distinta."),
+    ("compound.detail.plain",
+     "'{mark}' usa las mismas palabras que '{query}', solo que unidas o separadas de otra \
+      forma ({splits})."),
+    ("foreign.detail.plain",
+     "'{mark}' y '{query}' significan '{translation}', una de ellas en {language}. Una \
+      traducción cuenta como el mismo nombre."),
+    ("phonetic.detail.plain", "'{mark}' suena como '{query}' al decirlo en voz alta."),
+    ("near_phonetic.detail.plain", "'{mark}' suena casi como '{query}' al decirlo en voz alta."),
+    ("fuzzy.detail.plain", "'{mark}' está a solo {distance} letra(s) de '{query}'."),
+    ("class_overlap.summary.plain", "El mismo tipo de productos ({classes})"),
+    ("class_overlap.detail.plain",
+     "Ambas marcas cubren el mismo tipo de productos o servicios ({classes})."),
+    ("class_overlap.detail.inferred.plain",
+     "Ambas marcas parecen cubrir el mismo tipo de productos o servicios ({classes}). \
+      Compruebe las solicitudes para asegurarse."),
+    ("related_classes.summary.plain", "Productos relacionados ({pairs})"),
+    ("related_classes.detail.plain",
+     "Las marcas cubren productos o servicios distintos pero relacionados. Los clientes \
+      podrían pensar que vienen de la misma empresa."),
+    ("goods.detail.plain",
+     "Los productos o servicios de las dos marcas se parecen en un {percent}%."),
+    ("design_code.detail.plain", "Ambas marcas usan imágenes o símbolos parecidos ({codes})."),
+    ("dominant.summary.plain", "Comparte la palabra clave '{term}'"),
+    ("dominant.detail.plain",
+     "Ambas marcas comparten '{term}', la parte del nombre que la gente más recuerda."),
+    ("dominant.detail.normalized.plain",
+     "Ambas marcas comparten '{term}' si se ignoran la ortografía y los espacios. Es la \
+      parte del nombre que la gente más recuerda."),
+    ("famous.detail.plain",
+     "Tanto '{query}' como '{mark}' contienen {famous}, una marca famosa de {owner}. Las \
+      marcas famosas tienen una protección más amplia que las demás."),
+    ("famous.detail.cross_class.plain",
+     "Tanto '{query}' como '{mark}' contienen {famous}, una marca famosa de {owner}. Las \
+      marcas famosas están protegidas incluso para productos no relacionados."),
+    ("blurring.summary.plain", "Podría debilitar la marca famosa {famous}"),
+    ("blurring.detail.plain",
+     "Usar '{query}' podría hacer que {famous} deje de ser única como marca famosa, aunque \
+      nadie se confunda."),
+    ("tarnishment.summary.plain", "Podría perjudicar la marca famosa {famous}"),
+    ("tarnishment.detail.plain",
+     "Usar '{query}' para estos productos podría dañar la reputación de {famous}, aunque \
+      nadie se confunda."),
+    ("common_law.summary.plain", "En uso sin registro"),
+    ("common_law.detail.plain",
+     "'{mark}' parece estar en uso ({source}), y su titular puede tener derechos solo por \
+      ese uso."),
+    ("design_element.summary.plain", "Logotipo: solo se compararon las palabras"),
+    ("design_element.detail.plain",
+     "'{mark}' es un logotipo y solo se compararon sus palabras. Revise también el logotipo."),
+    ("design_element.detail.shared.plain",
+     "'{mark}' es un logotipo que comparte elementos gráficos ({shared}) con el suyo."),
+    ("recently_dead.detail.plain",
+     "'{mark}' se abandonó hace poco. Su titular podría recuperarla, o los clientes aún \
+      pueden recordarla."),
+    ("acronym.detail.plain",
+     "'{acronym}' se escribe con las primeras letras de '{expansion}', así que la gente \
+      puede pensar que son la misma marca."),
+    ("owner_conflict.detail.plain",
+     "{owner} ya tiene varias marcas como '{query}' ({marks}), y los clientes pueden pensar \
+      que la suya es una más de ellas."),
+    ("prosecution.refusal.detail.plain",
+     "La oficina de marcas rechazó una vez '{mark}' por ser demasiado parecida a una marca \
+      anterior."),
+];
+
+const PT: &[(&str, &str)] = &[
+    ("exact.summary", "Correspondência exata"),
+    ("exact.detail",
+     "A marca '{mark}' corresponde exatamente à sua consulta '{query}'. Este é o maior grau \
+      possível de conflito."),
+    ("obfuscated.summary", "A mesma marca com caracteres disfarçados"),
+    ("obfuscated.detail",
+     "A marca '{mark}' é lida da mesma forma que sua consulta '{query}' depois de desfeitas \
+      as substituições de caracteres ({substitutions}). Leetspeak e caracteres parecidos \
+      não distinguem uma marca."),
+    ("number_word.summary.same", "A mesma marca com os números escritos de outra forma"),
+    ("number_word.summary.near", "Grafia semelhante ao trocar os números"),
+    ("number_word.detail.same",
+     "A marca '{mark}' é lida da mesma forma que sua consulta '{query}' ao trocar \
+      algarismos por números por extenso ({transformations}). Um algarismo é lido da mesma \
+      forma que a palavra que representa."),
+    ("number_word.detail.near",
+     "A marca '{mark}' difere em {distance} caractere(s) da sua consulta '{query}' ao \
+      trocar algarismos por números por extenso ({transformations}). Um algarismo é lido da \
+      mesma forma que a palavra que representa."),
+    ("house_mark.summary", "A mesma marca exceto pela marca da casa"),
+    ("house_mark.detail.one",
+     "A marca '{mark}' corresponde à sua consulta '{query}' ao remover {words}. Acrescentar \
+      uma marca da casa ou um designador a uma marca não distingue a marca do produto."),
+    ("house_mark.detail.many",
+     "A marca '{mark}' corresponde à sua consulta '{query}' ao remover {words}. Acrescentar \
+      uma marca da casa ou um designador a uma marca não distingue a marca do produto."),
+    ("compound.summary", "As mesmas palavras juntas"),
+    ("compound.detail",
+     "A marca '{mark}' usa as mesmas palavras que sua consulta '{query}' ao separar as \
+      palavras juntas ({splits}). Juntar ou espaçar palavras não distingue uma marca."),
+    ("foreign.summary", "Equivalente em {language} de '{translation}'"),
+    ("foreign.detail",
+     "As marcas '{mark}' e '{query}' significam '{translation}' ao traduzir do {language}. \
+      Pela doutrina dos equivalentes estrangeiros, uma marca em um idioma estrangeiro comum \
+      é comparada pelo seu significado em inglês."),
+    ("phonetic.summary", "Som semelhante"),
+    ("phonetic.detail",
+     "A marca '{mark}' soa foneticamente semelhante a '{query}'. Os consumidores podem \
+      confundi-las quando pronunciadas."),
+    ("near_phonetic.summary", "Som quase igual ({percent}%)"),
+    ("near_phonetic.detail",
+     "A marca '{mark}' é pronunciada a {distance} som(ns) de '{query}'. Marcas tão próximas \
+      são facilmente confundidas ao ouvido."),
+    ("fuzzy.summary", "Grafia semelhante"),
+    ("fuzzy.detail",
+     "A marca '{mark}' difere de '{query}' em apenas {distance} caractere(s). Essa pequena \
+      diferença de grafia pode não evitar a confusão do consumidor."),
+    ("reordered.summary", "As mesmas palavras em outra ordem ({percent}%)"),
+    ("reordered.detail",
+     "A marca '{mark}' usa em grande parte as mesmas palavras que '{query}' em outra ordem. \
+      Reordenar as palavras de uma marca não evita a confusão."),
+    ("stem.summary", "As mesmas palavras exceto pelas terminações"),
+    ("stem.detail",
+     "A marca '{mark}' usa as mesmas palavras que '{query}' deixando de lado plurais e \
+      terminações como -ING e -ER. Os consumidores veem essas marcas como variantes uma da \
+      outra."),
+    ("prefix.summary", "O mesmo início ({percent}%)"),
+    ("prefix.detail",
+     "A marca '{mark}' começa como '{query}'. Os consumidores notam primeiro o início de uma \
+      marca, então diferenças no final pouco ajudam a evitar a confusão."),
+    ("ngram.summary", "Compartilha sequências de letras ({percent}%)"),
+    ("ngram.detail",
+     "A marca '{mark}' compartilha a maioria de suas sequências de letras com '{query}', \
+      independentemente dos espaços. Separar ou juntar palavras não evita a confusão."),
+    ("spelling.summary", "Grafia semelhante ({percent}%)"),
+    ("spelling.detail",
+     "A marca '{mark}' tem grafia {percent}% semelhante a '{query}'. Marcas que \
+      compartilham a maioria de suas letras, sobretudo no início, parecem iguais aos \
+      consumidores."),
+    ("syllable.summary", "Sílabas semelhantes ({percent}%)"),
+    ("syllable.detail",
+     "A marca '{mark}' acompanha '{query}' sílaba por sílaba. Alterar a grafia de uma \
+      sílaba mantém o som e o ritmo de uma marca."),
+    ("class", "Classe {class}"),
+    ("class_overlap.summary", "Mesma classe ({classes})"),
+    ("class_overlap.detail",
+     "Ambas as marcas estão registradas na(s) mesma(s) classe(s) de Nice: {classes}. Isso \
+      aumenta a probabilidade de confusão no mercado."),
+    ("class_overlap.detail.inferred",
+     "Ambas as marcas parecem abranger a(s) mesma(s) classe(s) de Nice: {classes}. As \
+      classes de pelo menos uma marca foram inferidas da descrição de produtos/serviços, \
+      então confira-as com o pedido."),
+    ("related_classes.summary", "Classes relacionadas ({pairs})"),
+    ("related_classes.detail",
+     "As marcas estão em classes de Nice diferentes, mas relacionadas ({percent}% de \
+      relação). Os compradores costumam esperar que produtos e serviços de classes \
+      relacionadas venham da mesma origem."),
+    ("goods.summary", "Produtos/serviços semelhantes"),
+    ("goods.detail",
+     "As descrições de produtos e serviços são {percent}% semelhantes. Mesmo com marcas \
+      diferentes, produtos semelhantes aumentam o risco de confusão."),
+    ("design_code.summary", "Os mesmos elementos figurativos ({codes})"),
+    ("design_code.detail",
+     "Ambas as marcas têm o(s) código(s) de design {codes}. Elementos figurativos \
+      semelhantes podem causar confusão mesmo quando as palavras diferem."),
+    ("dominant.summary", "O termo dominante '{term}' coincide"),
+    ("dominant.detail",
+     "O elemento dominante/distintivo '{term}' aparece em ambas as marcas. Os tribunais \
+      costumam se concentrar nos termos dominantes ao avaliar a confusão."),
+    ("dominant.detail.normalized",
+     "O elemento dominante/distintivo '{term}' aparece em ambas as marcas depois de \
+      normalizadas a grafia e os espaços. Grafias estilizadas ou juntas não evitam a \
+      confusão."),
+    ("famous.summary", "Marca notória ({famous})"),
+    ("famous.detail",
+     "Tanto '{query}' quanto '{mark}' contêm {famous}, uma marca notória de {owner}. Marcas \
+      notórias recebem proteção mais ampla contra confusão e diluição."),
+    ("famous.detail.cross_class",
+     "Tanto '{query}' quanto '{mark}' contêm {famous}, uma marca notória de {owner}. Marcas \
+      notórias recebem proteção mais ampla contra confusão e diluição, mesmo em classes não \
+      relacionadas."),
+    ("blurring.summary", "Pode ofuscar a marca notória {famous}"),
+    ("blurring.detail",
+     "Usar '{query}' pode enfraquecer o vínculo entre {famous} e seu titular, de modo que a \
+      marca notória deixe de indicar uma única origem. A diluição por ofuscação não exige \
+      confusão nem produtos relacionados."),
+    ("tarnishment.summary", "Pode denegrir a marca notória {famous}"),
+    ("tarnishment.detail",
+     "Usar '{query}' para esses produtos ou serviços pode prejudicar a reputação de \
+      {famous} por associação. A diluição por denegrimento não exige confusão nem produtos \
+      relacionados."),
+    ("common_law.summary", "Uso não registrado"),
+    ("common_law.detail",
+     "'{mark}' parece estar em uso fora do registro ({source}), então seu titular pode ter \
+      direitos não registrados além do registro. Considere realizar uma busca abrangente \
+      de uso não registrado."),
+    ("design_element.summary", "Marca figurativa: apenas as palavras foram comparadas"),
+    ("design_element.summary.shared", "Marca figurativa com elementos compartilhados ({shared})"),
+    ("design_element.detail",
+     "'{mark}' é uma marca figurativa cujas palavras podem ser acessórias ao seu design \
+      ({codes}). Apenas as palavras foram comparadas, então revise o próprio design."),
+    ("design_element.detail.shared",
+     "'{mark}' é uma marca figurativa que compartilha o(s) código(s) de design {shared} com \
+      a sua marca. Seus elementos figurativos pesam mais que suas palavras."),
+    ("recently_dead.summary.zero", "Extinta este mês"),
+    ("recently_dead.summary.one", "Extinta há 1 mês"),
+    ("recently_dead.summary.many", "Extinta há {months} meses"),
+    ("recently_dead.detail",
+     "'{mark}' foi abandonada, cancelada ou expirou recentemente. Uma marca extinta há pouco \
+      pode ser restaurada ou ainda ter prestígio junto aos compradores, por isso pesa mais \
+      que uma extinta há muito tempo."),
+    ("acronym.summary", "Acrônimo da outra marca"),
+    ("acronym.detail",
+     "'{acronym}' é formado pelas iniciais de '{expansion}'. Compradores que conhecem uma \
+      empresa pelas iniciais podem tomar o acrônimo e o nome completo pela mesma marca."),
+    ("owner_conflict.summary", "O titular tem marcas semelhantes"),
+    ("owner_conflict.detail",
+     "{owner}, titular de '{mark}', tem também outras marcas semelhantes a '{query}' \
+      ({marks}). Marcas que compartilham um elemento comum podem formar uma família de \
+      marcas, que protege esse elemento também em marcas novas."),
+    ("other.detail", "'{mark}' foi sinalizada como possível conflito com '{query}'."),
+    ("review.pin.summary", "Sinalizada por um revisor"),
+    ("review.pin.detail", "Um revisor destacou esta marca para atenção."),
+    ("review.suppress.summary", "Liberada por um revisor"),
+    ("review.suppress.detail", "Um revisor indicou que esta marca não é motivo de preocupação."),
+    ("review.note", "{detail} Nota: {note}"),
+    ("status.live.summary", "Registro em vigor"),
+    ("status.live.reason", "A marca está registrada e em vigor."),
+    ("status.pending.summary", "Pedido pendente"),
+    ("status.pending.reason",
+     "O pedido ainda não foi registrado; se for, terá prioridade a partir da data de \
+      depósito."),
+    ("status.dead.summary", "Marca extinta"),
+    ("status.dead.reason",
+     "O registro ou pedido foi cancelado, expirou ou foi abandonado, então é improvável que \
+      bloqueie uma nova marca, embora a marca ainda possa estar em uso."),
+    ("status.unknown.summary", "Situação desconhecida"),
+    ("status.unknown.reason", "A situação do registro não foi informada."),
+    ("status.summary.reduced", "{status}: risco reduzido"),
+    ("status.summary.raised", "{status}: risco aumentado"),
+    ("status.detail.reduced",
+     "{reason} A pontuação de risco foi reduzida para {percent}% por esta situação."),
+    ("status.detail.raised",
+     "{reason} A pontuação de risco foi aumentada para {percent}% por esta situação."),
+    ("prosecution.refusal.summary", "Recusada antes por risco de confusão"),
+    ("prosecution.refusal.detail",
+     "O examinador recusou '{mark}' com base na Seção 2(d) durante o seu processamento, por \
+      considerar provável a confusão com uma marca anterior."),
+    ("tier.low", "Baixo"),
+    ("tier.moderate", "Moderado"),
+    ("tier.high", "Alto"),
+    ("references", "Ver {citations}"),
+    // Plain-language variants, used at DetailLevel::Plain
+    ("exact.detail.plain",
+     "'{mark}' é exatamente igual a '{query}'. Este é o tipo de conflito mais forte."),
+    ("obfuscated.detail.plain",
+     "'{mark}' é lida como '{query}' ao desfazer os caracteres parecidos ({substitutions})."),
+    ("number_word.detail.same.plain",
+     "'{mark}' é lida como '{query}' ao trocar algarismos por números por extenso \
+      ({transformations})."),
+    ("number_word.detail.near.plain",
+     "'{mark}' está a apenas {distance} letra(s) de '{query}' ao trocar algarismos por \
+      números por extenso ({transformations})."),
+    ("house_mark.detail.one.plain",
+     "'{mark}' é igual a '{query}' exceto pelo nome de empresa {words}. Acrescentar o nome \
+      de uma empresa não torna uma marca diferente o bastante."),
+    ("house_mark.detail.many.plain",
+     "'{mark}' é igual a '{query}' exceto pelos nomes de empresa {words}. Acrescentar o \
+      nome de uma empresa não torna uma marca diferente o bastante."),
+    ("compound.detail.plain",
+     "'{mark}' usa as mesmas palavras que '{query}', apenas juntas ou separadas de outra \
+      forma ({splits})."),
+    ("foreign.detail.plain",
+     "'{mark}' e '{query}' significam '{translation}', uma delas em {language}. Uma \
+      tradução conta como o mesmo nome."),
+    ("phonetic.detail.plain", "'{mark}' soa como '{query}' quando dita em voz alta."),
+    ("near_phonetic.detail.plain", "'{mark}' soa quase como '{query}' quando dita em voz alta."),
+    ("fuzzy.detail.plain", "'{mark}' está a apenas {distance} letra(s) de '{query}'."),
+    ("class_overlap.summary.plain", "O mesmo tipo de produto ({classes})"),
+    ("class_overlap.detail.plain",
+     "As duas marcas cobrem o mesmo tipo de produtos ou serviços ({classes})."),
+    ("class_overlap.detail.inferred.plain",
+     "As duas marcas parecem cobrir o mesmo tipo de produtos ou serviços ({classes}). \
+      Confira os pedidos para ter certeza."),
+    ("related_classes.summary.plain", "Produtos relacionados ({pairs})"),
+    ("related_classes.detail.plain",
+     "As marcas cobrem produtos ou serviços diferentes, mas relacionados. Os clientes \
+      podem achar que vêm da mesma empresa."),
+    ("goods.detail.plain",
+     "Os produtos ou serviços das duas marcas são {percent}% parecidos."),
+    ("design_code.detail.plain", "As duas marcas usam imagens ou símbolos parecidos ({codes})."),
+    ("dominant.summary.plain", "Compartilha a palavra-chave '{term}'"),
+    ("dominant.detail.plain",
+     "As duas marcas compartilham '{term}', a parte do nome que as pessoas mais lembram."),
+    ("dominant.detail.normalized.plain",
+     "As duas marcas compartilham '{term}' ignorando grafia e espaços. É a parte do nome \
+      que as pessoas mais lembram."),
+    ("famous.detail.plain",
+     "Tanto '{query}' quanto '{mark}' contêm {famous}, uma marca famosa de {owner}. Marcas \
+      famosas têm proteção mais ampla que as demais."),
+    ("famous.detail.cross_class.plain",
+     "Tanto '{query}' quanto '{mark}' contêm {famous}, uma marca famosa de {owner}. Marcas \
+      famosas são protegidas até para produtos não relacionados."),
+    ("blurring.summary.plain", "Pode enfraquecer a marca famosa {famous}"),
+    ("blurring.detail.plain",
+     "Usar '{query}' pode fazer {famous} deixar de ser única como marca famosa, mesmo que \
+      ninguém se confunda."),
+    ("tarnishment.summary.plain", "Pode prejudicar a marca famosa {famous}"),
+    ("tarnishment.detail.plain",
+     "Usar '{query}' para estes produtos pode prejudicar a reputação de {famous}, mesmo que \
+      ninguém se confunda."),
+    ("common_law.summary.plain", "Em uso sem registro"),
+    ("common_law.detail.plain",
+     "'{mark}' parece estar em uso ({source}), e seu titular pode ter direitos só por esse \
+      uso."),
+    ("design_element.summary.plain", "Logotipo: só as palavras foram comparadas"),
+    ("design_element.detail.plain",
+     "'{mark}' é um logotipo e só suas palavras foram comparadas. Confira também o \
+      logotipo."),
+    ("design_element.detail.shared.plain",
+     "'{mark}' é um logotipo que compartilha elementos gráficos ({shared}) com o seu."),
+    ("recently_dead.detail.plain",
+     "'{mark}' foi abandonada recentemente. O titular pode recuperá-la, ou os clientes \
+      ainda podem se lembrar dela."),
+    ("acronym.detail.plain",
+     "'{acronym}' é escrito com as primeiras letras de '{expansion}', então as pessoas podem \
+      achar que são a mesma marca."),
+    ("owner_conflict.detail.plain",
+     "{owner} já tem várias marcas como '{query}' ({marks}), e os clientes podem achar que a \
+      sua é mais uma delas."),
+    ("prosecution.refusal.detail.plain",
+     "O escritório de marcas já rejeitou '{mark}' por ser parecida demais com uma marca \
+      anterior."),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<_> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_catalogs_match_english() {
+        for locale in [Locale::Es, Locale::Pt] {
+            for (key, template) in EN {
+                let translated = lookup(locale.catalog(), key)
+                    .unwrap_or_else(|| panic!("{} is missing '{}'", locale, key));
+                assert_eq!(placeholders(translated), placeholders(template), "{}", key);
+            }
+            assert_eq!(locale.catalog().len(), EN.len(), "{} has extra keys", locale);
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        let args: [(&str, &dyn Display); 2] = [("mark", &"NYKE"), ("distance", &2)];
+        assert_eq!(
+            message(Locale::Es, "fuzzy.detail", &args),
+            "La marca 'NYKE' difiere de '{query}' en solo 2 carácter(es). Esta pequeña \
+             diferencia ortográfica puede no evitar la confusión del consumidor."
+        );
+        assert_eq!(message(Locale::Pt, "unknown.key", &args), "unknown.key");
+
+        let plain = message_at(Locale::En, DetailLevel::Plain, "fuzzy.detail", &args);
+        assert_eq!(plain, "'NYKE' is only 2 letter(s) away from '{query}'.");
+        // Messages without a plain variant are shared
+        let summary = message_at(Locale::En, DetailLevel::Plain, "fuzzy.summary", &args);
+        assert_eq!(summary, "Spelled similarly");
+
+        assert_eq!("pt-BR".parse::<Locale>(), Ok(Locale::Pt));
+        assert_eq!("ES".parse::<Locale>(), Ok(Locale::Es));
+        assert!("fr".parse::<Locale>().is_err());
+    }
+}
@@ -2,31 +2,184 @@
 //!
 //! Converts risk flags into human-readable explanations suitable for
 //! display in the extension and web interface.
+//!
+//! `ExplainOptions` controls how much of each explanation is produced,
+//! from a one-line summary for the extension to full evidence for
+//! reports. `Locale` selects the language summaries and details are
+//! written in. Flag explanations cite their TMEP sections, statutes and
+//! cases from the `references` table. An `ExplanationNarrator` can turn
+//! explanations into prose paragraphs.
+
+pub mod assess;
+pub mod evidence;
+pub mod i18n;
+pub mod narrate;
+pub mod redact;
+pub mod references;
+pub mod render;
 
-use ilegalflow_model::{CandidateHit, RiskFlag};
+pub use assess::{assess_search, assess_search_with, BlockingCandidate, NextStep, SearchAssessment};
+pub use evidence::EvidenceKind;
+pub use i18n::Locale;
+pub use narrate::{Comparison, ExplanationNarrator, NarrationError, NarrationFuture, NoNarrator};
+pub use redact::{Redaction, RedactionPolicy};
+pub use references::{Reference, ReferenceKind};
+
+use i18n::{message, message_at};
+use ilegalflow_model::{
+    CandidateHit, CodeVariant, DilutionKind, DuPontAssessment, FactorWeight, ProsecutionEvent,
+    ReviewNote, ReviewVerdict, RiskFlag, SeverityTable, StatusAdjustment, TrademarkRecord,
+    TrademarkStatus,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// How much of each explanation to generate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verbosity {
+    /// Summary line only
+    Terse,
+    /// Summary and detail paragraph
+    Standard,
+    /// Summary, detail, and evidence
+    #[default]
+    Detailed,
+}
+
+impl FromStr for Verbosity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "terse" => Ok(Self::Terse),
+            "standard" => Ok(Self::Standard),
+            "detailed" => Ok(Self::Detailed),
+            _ => Err(format!("Unknown verbosity '{}' (terse, standard, detailed)", s)),
+        }
+    }
+}
+
+/// Who explanations are written for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailLevel {
+    /// Short, jargon-free wording for small-business users
+    Plain,
+    /// Legal terminology, du Pont factor analysis, and references
+    #[default]
+    Professional,
+}
+
+impl FromStr for DetailLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "professional" => Ok(Self::Professional),
+            _ => Err(format!("Unknown detail level '{}' (plain, professional)", s)),
+        }
+    }
+}
+
+/// Options controlling explanation output.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExplainOptions {
+    #[serde(default)]
+    pub verbosity: Verbosity,
+    /// Language of summaries and details
+    #[serde(default)]
+    pub locale: Locale,
+    /// Audience the wording is for
+    #[serde(default)]
+    pub detail_level: DetailLevel,
+    /// What to mask before results are shared
+    #[serde(default)]
+    pub redaction: RedactionPolicy,
+    /// Severity of each flag
+    #[serde(default)]
+    pub severities: SeverityTable,
+}
+
+impl ExplainOptions {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self {
+            verbosity,
+            locale: Locale::default(),
+            detail_level: DetailLevel::default(),
+            redaction: RedactionPolicy::default(),
+            severities: SeverityTable::default(),
+        }
+    }
+
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn with_detail_level(mut self, detail_level: DetailLevel) -> Self {
+        self.detail_level = detail_level;
+        self
+    }
+
+    pub fn with_redaction(mut self, redaction: RedactionPolicy) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    pub fn with_severities(mut self, severities: SeverityTable) -> Self {
+        self.severities = severities;
+        self
+    }
+
+    /// Trim an explanation to the configured verbosity.
+    ///
+    /// Every explanation goes through here, so payload size depends only
+    /// on the verbosity, never on which flag produced it.
+    pub fn apply(&self, mut explanation: Explanation) -> Explanation {
+        if self.verbosity == Verbosity::Terse {
+            explanation.detail.clear();
+        }
+        if self.verbosity != Verbosity::Detailed {
+            explanation.evidence.clear();
+            explanation.references.clear();
+        }
+        explanation
+    }
+}
 
 /// A structured explanation for a trademark risk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Explanation {
     /// Short summary (1 line)
     pub summary: String,
 
-    /// Detailed explanation (2-3 sentences)
+    /// Detailed explanation (2-3 sentences; empty when terse)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub detail: String,
 
     /// Severity level (0.0 - 1.0)
     pub severity: f32,
 
-    /// Evidence items supporting this explanation
+    /// Evidence items supporting this explanation (detailed only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub evidence: Vec<EvidenceItem>,
+
+    /// TMEP sections, statutes and cases behind it (detailed only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<Reference>,
 }
 
 /// A piece of evidence supporting a risk flag.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EvidenceItem {
     /// Type of evidence
-    pub kind: String,
+    pub kind: EvidenceKind,
 
     /// The specific value or match
     pub value: String,
@@ -34,155 +187,815 @@ pub struct EvidenceItem {
     /// Optional context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<String>,
+
+    /// Char ranges of the query behind the flag, for highlighting
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranges_in_query: Vec<Range<usize>>,
+
+    /// Char ranges of the mark behind the flag, for highlighting
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranges_in_mark: Vec<Range<usize>>,
+}
+
+impl EvidenceItem {
+    /// Evidence of a kind, without context or ranges.
+    pub fn new(kind: EvidenceKind, value: impl Into<String>) -> Self {
+        Self {
+            kind,
+            value: value.into(),
+            context: None,
+            ranges_in_query: Vec::new(),
+            ranges_in_mark: Vec::new(),
+        }
+    }
+
+    /// Set the context.
+    pub fn with_context(mut self, context: impl Into<Option<String>>) -> Self {
+        self.context = context.into();
+        self
+    }
 }
 
 /// Generate explanations for a candidate hit.
+///
+/// A reviewer note, if present, comes first.
 pub fn explain_hit(hit: &CandidateHit, query_text: &str) -> Vec<Explanation> {
-    hit.flags.iter().map(|flag| explain_flag(flag, query_text, &hit.record.mark_text)).collect()
+    explain_hit_with(hit, query_text, &ExplainOptions::default())
+}
+
+/// Generate explanations for a candidate hit at a given verbosity and
+/// locale.
+///
+/// A status adjustment, if any, comes after the flags it scaled, then a
+/// note of any earlier likelihood-of-confusion refusal of the mark. At
+/// the professional level the du Pont factor analysis, if any, comes
+/// last. The hit's highlight spans go on the first evidence item of
+/// their flag, and flag severities come from the options' table. The
+/// redaction policy masks the hit's owner, numbers and goods text.
+pub fn explain_hit_with(
+    hit: &CandidateHit,
+    query_text: &str,
+    options: &ExplainOptions,
+) -> Vec<Explanation> {
+    let (locale, level) = (options.locale, options.detail_level);
+    let mark_text = &hit.record.mark_text;
+    let dupont = hit.dupont.as_ref().filter(|_| level == DetailLevel::Professional);
+    hit.review
+        .iter()
+        .map(|review| explain_review_in(review, locale))
+        .chain(hit.flags.iter().map(|flag| {
+            let mut explanation = explain_flag_at(flag, query_text, mark_text, locale, level);
+            explanation.severity = options.severities.severity(flag);
+            let spans = hit.highlights.iter().find(|spans| spans.flag == flag.label());
+            if let (Some(spans), Some(item)) = (spans, explanation.evidence.first_mut()) {
+                item.ranges_in_query = spans.ranges_in_query.clone();
+                item.ranges_in_mark = spans.ranges_in_mark.clone();
+            }
+            explanation
+        }))
+        .chain(hit.status_adjustment.iter().map(|a| explain_status_in(a, locale)))
+        .chain(explain_prosecution_at(&hit.record, locale, level))
+        .chain(dupont.into_iter().flat_map(explain_dupont))
+        .map(|explanation| options.redaction.explanation(explanation, hit))
+        .map(|explanation| options.apply(explanation))
+        .collect()
+}
+
+/// Generate explanation for a reviewer override.
+pub fn explain_review(review: &ReviewNote) -> Explanation {
+    explain_review_in(review, Locale::En)
+}
+
+/// Generate explanation for a reviewer override in a locale.
+pub fn explain_review_in(review: &ReviewNote, locale: Locale) -> Explanation {
+    let key = match review.verdict {
+        ReviewVerdict::Pin => "review.pin",
+        ReviewVerdict::Suppress => "review.suppress",
+    };
+
+    let detail = message(locale, &format!("{}.detail", key), &[]);
+    let detail = if review.note.is_empty() {
+        detail
+    } else {
+        message(locale, "review.note", &[("detail", &detail), ("note", &review.note)])
+    };
+
+    Explanation {
+        summary: message(locale, &format!("{}.summary", key), &[]),
+        detail,
+        severity: 0.0,
+        evidence: vec![EvidenceItem::new(EvidenceKind::ReviewerNote, &review.note)
+            .with_context(review.reviewer.as_ref().map(|r| format!("Reviewed by {}", r)))],
+        references: Vec::new(),
+    }
+}
+
+/// Generate explanation for a risk score scaled by the record's status.
+pub fn explain_status(adjustment: &StatusAdjustment) -> Explanation {
+    explain_status_in(adjustment, Locale::En)
+}
+
+/// Generate explanation for a status adjustment in a locale.
+pub fn explain_status_in(adjustment: &StatusAdjustment, locale: Locale) -> Explanation {
+    let key = match adjustment.status {
+        TrademarkStatus::Live => "status.live",
+        TrademarkStatus::Pending => "status.pending",
+        TrademarkStatus::Dead => "status.dead",
+        _ => "status.unknown",
+    };
+    let status = message(locale, &format!("{}.summary", key), &[]);
+    let reason = message(locale, &format!("{}.reason", key), &[]);
+    let direction = if adjustment.multiplier < 1.0 { "reduced" } else { "raised" };
+    let percent = format!("{:.0}", adjustment.multiplier * 100.0);
+
+    Explanation {
+        summary: message(locale, &format!("status.summary.{}", direction), &[("status", &status)]),
+        detail: message(
+            locale,
+            &format!("status.detail.{}", direction),
+            &[("reason", &reason), ("percent", &percent)],
+        ),
+        severity: 0.0,
+        evidence: vec![EvidenceItem::new(
+            EvidenceKind::Status,
+            format!("{:?}", adjustment.status).to_uppercase(),
+        )
+        .with_context(format!("risk x{:.2}", adjustment.multiplier))],
+        references: Vec::new(),
+    }
+}
+
+/// Generate a note that the record's mark was refused under Section
+/// 2(d) during its prosecution, if it was.
+pub fn explain_prosecution(record: &TrademarkRecord) -> Option<Explanation> {
+    explain_prosecution_at(record, Locale::En, DetailLevel::Professional)
+}
+
+/// Generate a prior refusal note in a locale, worded for an audience.
+/// Plain notes carry no references.
+///
+/// The note is context rather than risk, so its severity is 0.0.
+pub fn explain_prosecution_at(
+    record: &TrademarkRecord,
+    locale: Locale,
+    level: DetailLevel,
+) -> Option<Explanation> {
+    let refusals: Vec<&ProsecutionEvent> =
+        record.prosecution_events.iter().filter(|e| e.is_2d_refusal()).collect();
+    if refusals.is_empty() {
+        return None;
+    }
+    Some(Explanation {
+        summary: message_at(locale, level, "prosecution.refusal.summary", &[]),
+        detail: message_at(
+            locale,
+            level,
+            "prosecution.refusal.detail",
+            &[("mark", &record.mark_text)],
+        ),
+        severity: 0.0,
+        evidence: refusals
+            .iter()
+            .map(|event| {
+                let context = match event.date {
+                    Some(date) => format!("{}, {}", event.code, date),
+                    None => event.code.clone(),
+                };
+                EvidenceItem::new(EvidenceKind::ProsecutionEvent, &event.description)
+                    .with_context(context)
+            })
+            .collect(),
+        references: if level == DetailLevel::Plain {
+            Vec::new()
+        } else {
+            references::prior_refusal()
+        },
+    })
+}
+
+/// Generate one explanation per du Pont factor, in factor order, for a
+/// likelihood-of-confusion write-up. Factors favoring confusion carry
+/// severity 1.0, neutral ones 0.5, and the rest 0.0.
+pub fn explain_dupont(assessment: &DuPontAssessment) -> Vec<Explanation> {
+    assessment
+        .findings
+        .iter()
+        .map(|finding| Explanation {
+            summary: format!(
+                "du Pont factor {} - {}: {}",
+                finding.factor.number(),
+                finding.factor.title(),
+                finding.weight.label()
+            ),
+            detail: finding.finding.clone(),
+            severity: match finding.weight {
+                FactorWeight::FavorsConfusion => 1.0,
+                FactorWeight::Neutral => 0.5,
+                FactorWeight::AgainstConfusion | FactorWeight::NotAssessed => 0.0,
+            },
+            evidence: finding
+                .signals
+                .iter()
+                .map(|signal| EvidenceItem::new(EvidenceKind::RiskFlag, signal))
+                .collect(),
+            references: references::dupont(),
+        })
+        .collect()
 }
 
 /// Generate explanation for a single risk flag.
 pub fn explain_flag(flag: &RiskFlag, query_text: &str, mark_text: &str) -> Explanation {
-    match flag {
+    explain_flag_in(flag, query_text, mark_text, Locale::En)
+}
+
+/// Generate explanation for a single risk flag in a locale.
+pub fn explain_flag_in(
+    flag: &RiskFlag,
+    query_text: &str,
+    mark_text: &str,
+    locale: Locale,
+) -> Explanation {
+    explain_flag_at(flag, query_text, mark_text, locale, DetailLevel::Professional)
+}
+
+/// Generate explanation for a single risk flag in a locale, worded for
+/// an audience. Plain explanations carry no references.
+///
+/// The severity is the flag's own under the default severity table;
+/// `explain_hit_with` uses the options' table instead.
+pub fn explain_flag_at(
+    flag: &RiskFlag,
+    query_text: &str,
+    mark_text: &str,
+    locale: Locale,
+    level: DetailLevel,
+) -> Explanation {
+    let message = |key: &str, args: &[(&str, &dyn Display)]| message_at(locale, level, key, args);
+    let mark: (&str, &dyn Display) = ("mark", &mark_text);
+    let query: (&str, &dyn Display) = ("query", &query_text);
+    let percent = |value: f32| format!("{:.0}", value * 100.0);
+    let severity = flag.severity();
+
+    let mut explanation = match flag {
         RiskFlag::ExactMatch => Explanation {
-            summary: "Exact match found".to_string(),
-            detail: format!(
-                "The mark '{}' is an exact match for your query '{}'. \
-                 This represents the highest level of potential conflict.",
-                mark_text, query_text
+            summary: message("exact.summary", &[]),
+            detail: message("exact.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::ExactMatch, mark_text.to_string())],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::ObfuscatedMatch { substitutions } => Explanation {
+            summary: message("obfuscated.summary", &[]),
+            detail: message(
+                "obfuscated.detail",
+                &[mark, query, ("substitutions", &substitutions.join(", "))],
             ),
-            severity: 1.0,
-            evidence: vec![EvidenceItem {
-                kind: "exact_match".to_string(),
-                value: mark_text.to_string(),
-                context: None,
-            }],
+            severity,
+            evidence: substitutions
+                .iter()
+                .map(|s| EvidenceItem::new(EvidenceKind::Substitution, s))
+                .collect(),
+            references: references::for_flag(flag),
         },
 
-        RiskFlag::PhoneticMatch { algorithm, code } => Explanation {
-            summary: "Sounds similar".to_string(),
-            detail: format!(
-                "The mark '{}' sounds phonetically similar to '{}'. \
-                 Consumers may confuse the two when spoken aloud.",
-                mark_text, query_text
+        RiskFlag::NumberWordMatch {
+            distance,
+            transformations,
+        } => Explanation {
+            summary: if *distance == 0 {
+                message("number_word.summary.same", &[])
+            } else {
+                message("number_word.summary.near", &[])
+            },
+            detail: message(
+                if *distance == 0 { "number_word.detail.same" } else { "number_word.detail.near" },
+                &[
+                    mark,
+                    query,
+                    ("distance", distance),
+                    ("transformations", &transformations.join(", ")),
+                ],
             ),
-            severity: 0.8,
-            evidence: vec![EvidenceItem {
-                kind: format!("phonetic_{}", algorithm),
-                value: code.clone(),
-                context: Some(format!("Both encode to: {}", code)),
-            }],
+            severity,
+            evidence: transformations
+                .iter()
+                .map(|t| EvidenceItem::new(EvidenceKind::NumberWord, t))
+                .collect(),
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::HouseMarkMatch {
+            house_marks,
+            designators,
+        } => Explanation {
+            summary: message("house_mark.summary", &[]),
+            detail: message(
+                if house_marks.len() + designators.len() > 1 {
+                    "house_mark.detail.many"
+                } else {
+                    "house_mark.detail.one"
+                },
+                &[
+                    mark,
+                    query,
+                    (
+                        "words",
+                        &house_marks
+                            .iter()
+                            .chain(designators)
+                            .map(|w| format!("'{}'", w))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                ],
+            ),
+            severity,
+            evidence: house_marks
+                .iter()
+                .map(|w| (EvidenceKind::HouseMark, w))
+                .chain(designators.iter().map(|w| (EvidenceKind::Designator, w)))
+                .map(|(kind, w)| EvidenceItem::new(kind, w).with_context("stripped".to_string()))
+                .collect(),
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::CompoundMatch { splits } => Explanation {
+            summary: message("compound.summary", &[]),
+            detail: message("compound.detail", &[mark, query, ("splits", &splits.join(", "))]),
+            severity,
+            evidence: splits
+                .iter()
+                .map(|s| EvidenceItem::new(EvidenceKind::Segmentation, s))
+                .collect(),
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::AcronymMatch { acronym, expansion } => Explanation {
+            summary: message("acronym.summary", &[]),
+            detail: message("acronym.detail", &[("acronym", acronym), ("expansion", expansion)]),
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::Acronym, acronym)
+                .with_context(format!("initials of {}", expansion))],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::ForeignEquivalent {
+            language,
+            translation,
+        } => Explanation {
+            summary: message(
+                "foreign.summary",
+                &[("language", language), ("translation", translation)],
+            ),
+            detail: message(
+                "foreign.detail",
+                &[mark, query, ("language", language), ("translation", translation)],
+            ),
+            severity,
+            evidence: vec![
+                EvidenceItem::new(EvidenceKind::Language, language),
+                EvidenceItem::new(EvidenceKind::Translation, translation),
+            ],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::PhoneticMatch {
+            algorithm,
+            code,
+            variant,
+        } => Explanation {
+            summary: message("phonetic.summary", &[]),
+            detail: message("phonetic.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::phonetic(algorithm), code)
+                .with_context(match variant {
+                    Some(variant) => format!(
+                        "Both encode to: {} ({} pronunciation of '{}', {} of '{}')",
+                        code,
+                        variant_name(variant.query),
+                        query_text,
+                        variant_name(variant.mark),
+                        mark_text
+                    ),
+                    None => format!("Both encode to: {}", code),
+                })],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::NearPhoneticMatch {
+            distance,
+            similarity,
+            phonemes,
+        } => Explanation {
+            summary: message("near_phonetic.summary", &[("percent", &percent(*similarity))]),
+            detail: message("near_phonetic.detail", &[mark, query, ("distance", distance)]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::Phonemes,
+                format!("/{}/ vs /{}/", phonemes.0, phonemes.1),
+            )
+            .with_context(format!("distance {}", distance))],
+            references: references::for_flag(flag),
         },
 
         RiskFlag::FuzzyMatch { distance } => Explanation {
-            summary: "Spelled similarly".to_string(),
-            detail: format!(
-                "The mark '{}' differs from '{}' by only {} character(s). \
-                 This minor spelling difference may not prevent consumer confusion.",
-                mark_text, query_text, distance
+            summary: message("fuzzy.summary", &[]),
+            detail: message("fuzzy.detail", &[mark, query, ("distance", distance)]),
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::EditDistance, distance.to_string())],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::StringSimilarity { metric, score } if metric == "token_sort_ratio" => Explanation {
+            summary: message("reordered.summary", &[("percent", &percent(*score))]),
+            detail: message("reordered.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::from(metric.as_str()),
+                format!("{:.3}", score),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::StringSimilarity { metric, score } if metric == "stem_match" => Explanation {
+            summary: message("stem.summary", &[]),
+            detail: message("stem.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::from(metric.as_str()),
+                format!("{:.3}", score),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::StringSimilarity { metric, score } if metric == "prefix_weighted" => Explanation {
+            summary: message("prefix.summary", &[("percent", &percent(*score))]),
+            detail: message("prefix.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::from(metric.as_str()),
+                format!("{:.3}", score),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::StringSimilarity { metric, score } if metric.starts_with("ngram") => Explanation {
+            summary: message("ngram.summary", &[("percent", &percent(*score))]),
+            detail: message("ngram.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::from(metric.as_str()),
+                format!("{:.3}", score),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::StringSimilarity { metric, score } => Explanation {
+            summary: message("spelling.summary", &[("percent", &percent(*score))]),
+            detail: message("spelling.detail", &[mark, query, ("percent", &percent(*score))]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::from(metric.as_str()),
+                format!("{:.3}", score),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::SyllableMatch { score, aligned } => Explanation {
+            summary: message("syllable.summary", &[("percent", &percent(*score))]),
+            detail: message("syllable.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::AlignedSyllables,
+                aligned
+                    .iter()
+                    .map(|(a, b)| {
+                        let gap = |s: &str| if s.is_empty() { "-".to_string() } else { s.to_string() };
+                        if a == b {
+                            a.clone()
+                        } else {
+                            format!("{}/{}", gap(a), gap(b))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" · "),
+            )
+            .with_context(format!("{:.3}", score))],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::ClassOverlap { classes, inferred } => Explanation {
+            summary: message(
+                "class_overlap.summary",
+                &[(
+                    "classes",
+                    &classes.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "),
+                )],
             ),
-            severity: 0.5 - (*distance as f32 * 0.1),
-            evidence: vec![EvidenceItem {
-                kind: "edit_distance".to_string(),
-                value: distance.to_string(),
-                context: None,
-            }],
+            detail: message(
+                if *inferred { "class_overlap.detail.inferred" } else { "class_overlap.detail" },
+                &[(
+                    "classes",
+                    &classes
+                        .iter()
+                        .map(|c| message("class", &[("class", c)]))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )],
+            ),
+            severity,
+            evidence: classes
+                .iter()
+                .map(|c| {
+                    EvidenceItem::new(EvidenceKind::NiceClass, c.to_string())
+                        .with_context(inferred.then(|| "inferred".to_string()))
+                })
+                .collect(),
+            references: references::for_flag(flag),
         },
 
-        RiskFlag::ClassOverlap { classes } => Explanation {
-            summary: format!("Same class ({})", classes.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")),
-            detail: format!(
-                "Both marks are registered in the same Nice classification(s): {}. \
-                 This increases the likelihood of confusion in the marketplace.",
-                classes.iter().map(|c| format!("Class {}", c)).collect::<Vec<_>>().join(", ")
+        RiskFlag::RelatedClasses { pairs, relatedness } => Explanation {
+            summary: message(
+                "related_classes.summary",
+                &[(
+                    "pairs",
+                    &pairs
+                        .iter()
+                        .map(|(a, b)| format!("{}/{}", a, b))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )],
+            ),
+            detail: message(
+                "related_classes.detail",
+                &[("percent", &percent(*relatedness))],
             ),
-            severity: 0.6,
-            evidence: classes.iter().map(|c| EvidenceItem {
-                kind: "nice_class".to_string(),
-                value: c.to_string(),
-                context: None,
-            }).collect(),
+            severity,
+            evidence: pairs
+                .iter()
+                .map(|(a, b)| {
+                    EvidenceItem::new(EvidenceKind::RelatedClasses, format!("{}/{}", a, b))
+                        .with_context(format!("Class {} and Class {}", a, b))
+                })
+                .collect(),
+            references: references::for_flag(flag),
         },
 
         RiskFlag::GoodsServicesSimilar { similarity } => Explanation {
-            summary: "Similar goods/services".to_string(),
-            detail: format!(
-                "The goods and services descriptions are {:.0}% similar. \
-                 Even with different marks, similar goods increase confusion risk.",
-                similarity * 100.0
+            summary: message("goods.summary", &[]),
+            detail: message("goods.detail", &[("percent", &percent(*similarity))]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::GoodsSimilarity,
+                format!("{:.2}", similarity),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::DesignCodeOverlap { codes } => Explanation {
+            summary: message("design_code.summary", &[("codes", &codes.join(", "))]),
+            detail: message("design_code.detail", &[("codes", &codes.join(", "))]),
+            severity,
+            evidence: codes
+                .iter()
+                .map(|c| EvidenceItem::new(EvidenceKind::DesignCode, c))
+                .collect(),
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::DominantTermMatch {
+            term,
+            transformations,
+        } => Explanation {
+            summary: message("dominant.summary", &[("term", term)]),
+            detail: message(
+                if transformations.is_empty() {
+                    "dominant.detail"
+                } else {
+                    "dominant.detail.normalized"
+                },
+                &[("term", term)],
             ),
-            severity: similarity * 0.5,
-            evidence: vec![EvidenceItem {
-                kind: "goods_similarity".to_string(),
-                value: format!("{:.2}", similarity),
-                context: None,
-            }],
+            severity,
+            evidence: std::iter::once(EvidenceItem::new(EvidenceKind::DominantTerm, term))
+                .chain(
+                    transformations
+                        .iter()
+                        .map(|t| EvidenceItem::new(EvidenceKind::DominantTransformation, t)),
+                )
+                .collect(),
+            references: references::for_flag(flag),
         },
 
-        RiskFlag::DominantTermMatch { term } => Explanation {
-            summary: format!("Dominant term '{}' matches", term),
-            detail: format!(
-                "The dominant/distinctive element '{}' appears in both marks. \
-                 Courts often focus on dominant terms when assessing confusion.",
-                term
+        RiskFlag::FamousMark {
+            mark: famous,
+            owner,
+            cross_class,
+        } => Explanation {
+            summary: message("famous.summary", &[("famous", famous)]),
+            detail: message(
+                if *cross_class { "famous.detail.cross_class" } else { "famous.detail" },
+                &[mark, query, ("famous", famous), ("owner", owner)],
             ),
-            severity: 0.7,
-            evidence: vec![EvidenceItem {
-                kind: "dominant_term".to_string(),
-                value: term.clone(),
-                context: None,
-            }],
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::FamousMark, famous)
+                .with_context(if *cross_class {
+                    format!("owned by {}; no shared class", owner)
+                } else {
+                    format!("owned by {}", owner)
+                })],
+            references: references::for_flag(flag),
         },
 
-        RiskFlag::FamousMark => Explanation {
-            summary: "Famous mark".to_string(),
-            detail: format!(
-                "The mark '{}' may be considered famous/well-known. \
-                 Famous marks receive broader protection against dilution.",
-                mark_text
+        RiskFlag::DilutionRisk {
+            kind,
+            famous,
+            score,
+        } => {
+            let key = match kind {
+                DilutionKind::Blurring => "blurring",
+                DilutionKind::Tarnishment => "tarnishment",
+            };
+            Explanation {
+                summary: message(&format!("{}.summary", key), &[("famous", famous)]),
+                detail: message(&format!("{}.detail", key), &[query, ("famous", famous)]),
+                severity,
+                evidence: vec![EvidenceItem::new(EvidenceKind::Dilution, key.to_string())
+                    .with_context(format!("score {:.2}", score))],
+                references: references::for_flag(flag),
+            }
+        }
+
+        RiskFlag::OwnerConflict {
+            owner,
+            related_marks,
+        } => Explanation {
+            summary: message("owner_conflict.summary", &[]),
+            detail: message(
+                "owner_conflict.detail",
+                &[mark, query, ("owner", owner), ("marks", &related_marks.join(", "))],
             ),
-            severity: 0.95,
-            evidence: vec![EvidenceItem {
-                kind: "famous_mark".to_string(),
-                value: mark_text.to_string(),
-                context: None,
-            }],
+            severity,
+            evidence: related_marks
+                .iter()
+                .map(|m| {
+                    EvidenceItem::new(EvidenceKind::OwnerMark, m)
+                        .with_context(format!("owned by {}", owner))
+                })
+                .collect(),
+            references: references::for_flag(flag),
         },
 
-        RiskFlag::CommonLawRisk => Explanation {
-            summary: "Common law usage".to_string(),
-            detail: "There may be unregistered common law trademark rights. \
-                     Consider conducting a comprehensive common law search."
-                .to_string(),
-            severity: 0.4,
-            evidence: vec![],
+        RiskFlag::CommonLawRisk {
+            source,
+            evidence,
+            confidence,
+        } => Explanation {
+            summary: message("common_law.summary", &[]),
+            detail: message("common_law.detail", &[mark, ("source", source)]),
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::Use, evidence)
+                .with_context(format!("{} ({:.0}% confidence)", source, confidence * 100.0))],
+            references: references::for_flag(flag),
         },
+
+        RiskFlag::DesignElementRisk { shared, codes } => Explanation {
+            summary: if shared.is_empty() {
+                message("design_element.summary", &[])
+            } else {
+                message("design_element.summary.shared", &[("shared", &shared.join(", "))])
+            },
+            detail: message(
+                if shared.is_empty() {
+                    "design_element.detail"
+                } else {
+                    "design_element.detail.shared"
+                },
+                &[mark, ("codes", &codes.join(", ")), ("shared", &shared.join(", "))],
+            ),
+            severity,
+            evidence: codes
+                .iter()
+                .map(|c| {
+                    EvidenceItem::new(EvidenceKind::DesignCode, c)
+                        .with_context(shared.contains(c).then(|| "shared".to_string()))
+                })
+                .collect(),
+            references: references::for_flag(flag),
+        },
+
+        RiskFlag::RecentlyDead { months } => Explanation {
+            summary: message(
+                match months {
+                    0 => "recently_dead.summary.zero",
+                    1 => "recently_dead.summary.one",
+                    _ => "recently_dead.summary.many",
+                },
+                &[("months", months)],
+            ),
+            detail: message("recently_dead.detail", &[mark]),
+            severity,
+            evidence: vec![EvidenceItem::new(
+                EvidenceKind::StatusAge,
+                format!("{} months", months),
+            )],
+            references: references::for_flag(flag),
+        },
+
+        // A variant added to the model before this crate explains it
+        _ => Explanation {
+            summary: flag.label().to_string(),
+            detail: message("other.detail", &[mark, query]),
+            severity,
+            evidence: vec![EvidenceItem::new(EvidenceKind::RiskFlag, flag.kind())],
+            references: references::for_flag(flag),
+        },
+    };
+    if level == DetailLevel::Plain {
+        explanation.references.clear();
+    }
+    explanation
+}
+
+fn variant_name(variant: CodeVariant) -> &'static str {
+    match variant {
+        CodeVariant::Primary => "primary",
+        CodeVariant::Alternate => "alternate",
+    }
+}
+
+/// Overall risk level of a hit, from its most severe flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskTier {
+    /// No flag reaches severity 0.5
+    #[default]
+    Low,
+    /// A flag reaches severity 0.5
+    Moderate,
+    /// A flag reaches severity 0.8
+    High,
+}
+
+impl RiskTier {
+    /// The tier of a hit.
+    pub fn of(hit: &CandidateHit) -> Self {
+        Self::of_with(hit, &SeverityTable::DEFAULT)
+    }
+
+    /// The tier of a hit under a severity table.
+    pub fn of_with(hit: &CandidateHit, severities: &SeverityTable) -> Self {
+        let severity = hit.flags.iter().map(|f| severities.severity(f)).fold(0.0_f32, f32::max);
+        Self::from_severity(severity)
+    }
+
+    /// The tier a single severity falls in.
+    pub fn from_severity(severity: f32) -> Self {
+        if severity >= 0.8 {
+            Self::High
+        } else if severity >= 0.5 {
+            Self::Moderate
+        } else {
+            Self::Low
+        }
+    }
+
+    /// Display label ("HIGH RISK").
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "LOW RISK",
+            Self::Moderate => "MODERATE RISK",
+            Self::High => "HIGH RISK",
+        }
     }
 }
 
 /// Generate a combined risk summary for all flags.
 pub fn summarize_risk(hit: &CandidateHit) -> String {
+    summarize_risk_with(hit, &SeverityTable::DEFAULT)
+}
+
+/// Generate a combined risk summary for all flags, tiered under a
+/// severity table.
+pub fn summarize_risk_with(hit: &CandidateHit, severities: &SeverityTable) -> String {
     if hit.flags.is_empty() {
         return "Low risk - no significant matches found.".to_string();
     }
 
-    let max_severity = hit.flags.iter().map(|f| f.severity()).fold(0.0_f32, f32::max);
-
-    let level = if max_severity >= 0.8 {
-        "HIGH RISK"
-    } else if max_severity >= 0.5 {
-        "MODERATE RISK"
-    } else {
-        "LOW RISK"
-    };
-
     let flag_labels: Vec<_> = hit.flags.iter().map(|f| f.label()).collect();
-    format!("{}: {}", level, flag_labels.join(", "))
+    let tier = RiskTier::of_with(hit, severities);
+    let summary = format!("{}: {}", tier.label(), flag_labels.join(", "));
+    match hit.risk_probability {
+        Some(p) => format!("{} ({:.0}% likelihood of conflict)", summary, p * 100.0),
+        None => summary,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ilegalflow_model::NiceClass;
 
     #[test]
     fn test_explain_exact_match() {
@@ -196,8 +1009,565 @@ mod tests {
         let flag = RiskFlag::PhoneticMatch {
             algorithm: "soundex".to_string(),
             code: "N200".to_string(),
+            variant: None,
         };
         let explanation = explain_flag(&flag, "NIKE", "NYKE");
         assert!(explanation.detail.contains("sounds"));
+
+        let flag = RiskFlag::PhoneticMatch {
+            algorithm: "double_metaphone".to_string(),
+            code: "ARNF".to_string(),
+            variant: Some(ilegalflow_model::MatchedVariant {
+                query: CodeVariant::Primary,
+                mark: CodeVariant::Alternate,
+            }),
+        };
+        let explanation = explain_flag(&flag, "ARNOFF", "ARNOW");
+        assert_eq!(explanation.evidence[0].kind, EvidenceKind::PhoneticDoubleMetaphone);
+        assert_eq!(
+            explanation.evidence[0].context.as_deref(),
+            Some("Both encode to: ARNF (primary pronunciation of 'ARNOFF', alternate of 'ARNOW')")
+        );
+    }
+
+    #[test]
+    fn test_explain_review_note_first() {
+        let hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NIKE"),
+            retrieval_score: 1.0,
+            risk_score: 1.0,
+            risk_probability: None,
+            flags: vec![RiskFlag::ExactMatch],
+            review: Some(ReviewNote {
+                verdict: ReviewVerdict::Pin,
+                note: "Client's main competitor".to_string(),
+                reviewer: Some("jdoe".to_string()),
+            }),
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        let explanations = explain_hit(&hit, "NIKE");
+        assert_eq!(explanations.len(), 2);
+        assert!(explanations[0].detail.contains("Client's main competitor"));
+        assert_eq!(explanations[0].evidence[0].kind, EvidenceKind::ReviewerNote);
+    }
+
+    #[test]
+    fn test_explain_house_mark() {
+        let flag = RiskFlag::HouseMarkMatch {
+            house_marks: vec!["ACME".to_string()],
+            designators: vec!["SERIES".to_string()],
+        };
+        let exp = explain_flag(&flag, "NIKE", "ACME NIKE SERIES");
+        assert!(exp.detail.contains("once 'ACME', 'SERIES' are removed"));
+        assert_eq!(exp.evidence[0].kind, EvidenceKind::HouseMark);
+        assert_eq!(exp.evidence[1].kind, EvidenceKind::Designator);
+    }
+
+    #[test]
+    fn test_explain_near_phonetic() {
+        let flag = RiskFlag::NearPhoneticMatch {
+            distance: 1.0,
+            similarity: 0.75,
+            phonemes: ("naɪk".to_string(), "naɪki".to_string()),
+        };
+        let exp = explain_flag(&flag, "NIKE", "NIKEY");
+        assert_eq!(exp.severity, flag.severity());
+        assert_eq!(exp.evidence[0].value, "/naɪk/ vs /naɪki/");
+        assert!(exp.detail.contains("within 1 sound(s)"));
+    }
+
+    #[test]
+    fn test_explain_famous_mark() {
+        let flag = RiskFlag::FamousMark {
+            mark: "NIKE".to_string(),
+            owner: "Nike, Inc.".to_string(),
+            cross_class: true,
+        };
+        let explanation = explain_flag(&flag, "NIKE RUNNING", "NIKE DINER");
+        assert_eq!(explanation.summary, "Famous mark (NIKE)");
+        assert!(explanation.detail.contains("even in unrelated classes"));
+        assert_eq!(
+            explanation.evidence[0].context.as_deref(),
+            Some("owned by Nike, Inc.; no shared class")
+        );
+    }
+
+    #[test]
+    fn test_explain_dilution() {
+        let flag = RiskFlag::DilutionRisk {
+            kind: DilutionKind::Tarnishment,
+            famous: "NIKE".to_string(),
+            score: 0.5,
+        };
+        let explanation = explain_flag(&flag, "NIKE LOUNGE", "NIKE");
+        assert_eq!(explanation.summary, "Could tarnish famous mark NIKE");
+        assert!(explanation.detail.contains("by tarnishment"));
+        assert_eq!(explanation.severity, 0.45);
+        assert_eq!(explanation.evidence[0].value, "tarnishment");
+    }
+
+    #[test]
+    fn test_explain_related_classes() {
+        let flag = RiskFlag::RelatedClasses {
+            pairs: vec![
+                (NiceClass::of(25), NiceClass::of(35)),
+                (NiceClass::of(18), NiceClass::of(35)),
+            ],
+            relatedness: 0.5,
+        };
+        let explanation = explain_flag(&flag, "NIKE", "NYKE");
+        assert_eq!(explanation.summary, "Related classes (25/35, 18/35)");
+        assert!(explanation.detail.contains("50% related"));
+        assert_eq!(explanation.severity, 0.3);
+        assert_eq!(explanation.evidence.len(), 2);
+    }
+
+    #[test]
+    fn test_explain_common_law() {
+        let flag = RiskFlag::CommonLawRisk {
+            source: "domains".to_string(),
+            evidence: "nyke.shop".to_string(),
+            confidence: 0.75,
+        };
+        let explanation = explain_flag(&flag, "NIKE", "NYKE");
+        assert!(explanation.detail.contains("'NYKE' appears to be in use"));
+        assert_eq!(explanation.evidence[0].value, "nyke.shop");
+        assert_eq!(explanation.evidence[0].context.as_deref(), Some("domains (75% confidence)"));
+    }
+
+    #[test]
+    fn test_explain_design_element() {
+        let flag = RiskFlag::DesignElementRisk {
+            shared: vec!["05.07.13".to_string()],
+            codes: vec!["05.07.13".to_string(), "26.01.03".to_string()],
+        };
+        let explanation = explain_flag(&flag, "APPLE", "ORCHARD");
+        assert_eq!(explanation.summary, "Design mark with shared elements (05.07.13)");
+        assert_eq!(explanation.severity, 0.6);
+        assert_eq!(explanation.evidence[0].context.as_deref(), Some("shared"));
+        assert_eq!(explanation.evidence[1].context, None);
+    }
+
+    #[test]
+    fn test_explain_recently_dead() {
+        let explanation = explain_flag(&RiskFlag::RecentlyDead { months: 3 }, "NIKE", "NYKE");
+        assert_eq!(explanation.summary, "Died 3 months ago");
+        assert!(explanation.detail.contains("'NYKE' was abandoned"));
+        assert_eq!(explanation.evidence[0].value, "3 months");
+    }
+
+    #[test]
+    fn test_explain_status() {
+        let mut hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NIKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.2,
+            risk_probability: None,
+            flags: vec![RiskFlag::ExactMatch],
+            review: None,
+            status_adjustment: Some(StatusAdjustment {
+                status: TrademarkStatus::Dead,
+                multiplier: 0.2,
+            }),
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        let explanations = explain_hit(&hit, "NIKE");
+        assert_eq!(explanations.len(), 2);
+        assert_eq!(explanations[1].summary, "Dead mark: risk reduced");
+        assert!(explanations[1].detail.contains("reduced to 20%"));
+        assert_eq!(explanations[1].evidence[0].value, "DEAD");
+        assert_eq!(explanations[1].evidence[0].context.as_deref(), Some("risk x0.20"));
+
+        hit.status_adjustment = None;
+        assert_eq!(explain_hit(&hit, "NIKE").len(), 1);
+    }
+
+    #[test]
+    fn test_explain_prior_refusal() {
+        let mut record = TrademarkRecord::new("001", "NYKE");
+        assert!(explain_prosecution(&record).is_none());
+
+        let filed = ProsecutionEvent::new("NWAP", "NEW APPLICATION ENTERED");
+        let refused = ProsecutionEvent::new("CNRT", "NON-FINAL ACTION - SEC. 2(D) REFUSAL")
+            .with_date(ilegalflow_model::Date::from_ymd(2024, 1, 5).unwrap());
+        record.prosecution_events = vec![filed, refused];
+        let explanation = explain_prosecution(&record).unwrap();
+        assert_eq!(explanation.summary, "Previously refused for likelihood of confusion");
+        assert!(explanation.detail.contains("refused 'NYKE' under Section 2(d)"));
+        assert_eq!(explanation.severity, 0.0);
+        assert_eq!(explanation.evidence.len(), 1);
+        assert_eq!(explanation.evidence[0].context.as_deref(), Some("CNRT, 2024-01-05"));
+
+        let plain = explain_prosecution_at(&record, Locale::En, DetailLevel::Plain).unwrap();
+        assert!(plain.detail.starts_with("The trademark office once turned 'NYKE' down"));
+        assert!(plain.references.is_empty() && !explanation.references.is_empty());
+    }
+
+    #[test]
+    fn test_explain_dupont() {
+        use ilegalflow_model::{DuPontFactor, FactorFinding};
+
+        let assessment = DuPontAssessment {
+            findings: vec![
+                FactorFinding {
+                    factor: DuPontFactor::MarkSimilarity,
+                    weight: FactorWeight::FavorsConfusion,
+                    finding: "The marks are similar in sound.".to_string(),
+                    signals: vec!["Sounds Similar".to_string()],
+                },
+                FactorFinding {
+                    factor: DuPontFactor::ActualConfusion,
+                    weight: FactorWeight::NotAssessed,
+                    finding: "Evidence of actual confusion is outside the register.".to_string(),
+                    signals: Vec::new(),
+                },
+            ],
+        };
+        let explanations = explain_dupont(&assessment);
+        assert_eq!(
+            explanations[0].summary,
+            "du Pont factor 1 - Similarity of the marks: favors confusion"
+        );
+        assert_eq!(explanations[0].severity, 1.0);
+        assert_eq!(explanations[0].evidence[0].value, "Sounds Similar");
+        assert_eq!(explanations[0].references[0].citation, "TMEP § 1207.01");
+        assert_eq!(
+            explanations[1].summary,
+            "du Pont factor 7 - Actual confusion: not assessed"
+        );
+        assert!(explanations[1].evidence.is_empty());
+    }
+
+    #[test]
+    fn test_explain_syllables() {
+        let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+        let flag = RiskFlag::SyllableMatch {
+            score: 0.83,
+            aligned: vec![pair("VI", "VI"), pair("TA", "TA"), pair("LI", ""), pair("TY", "TEA")],
+        };
+        let exp = explain_flag(&flag, "VITALITY", "VITATEA");
+        assert_eq!(exp.evidence[0].kind, EvidenceKind::AlignedSyllables);
+        assert_eq!(exp.evidence[0].value, "VI · TA · LI/- · TY/TEA");
+    }
+
+    #[test]
+    fn test_verbosity_levels() {
+        let hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NYKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.5,
+            risk_probability: None,
+            flags: vec![RiskFlag::FuzzyMatch { distance: 1 }],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+
+        let terse = explain_hit_with(&hit, "NIKE", &ExplainOptions::new(Verbosity::Terse));
+        assert!(!terse[0].summary.is_empty());
+        assert!(terse[0].detail.is_empty() && terse[0].evidence.is_empty());
+        let json = serde_json::to_value(&terse[0]).unwrap();
+        assert!(json.get("detail").is_none() && json.get("evidence").is_none());
+
+        let standard = explain_hit_with(&hit, "NIKE", &ExplainOptions::new(Verbosity::Standard));
+        assert!(!standard[0].detail.is_empty() && standard[0].evidence.is_empty());
+        assert!(standard[0].references.is_empty());
+
+        let detailed = explain_hit(&hit, "NIKE");
+        assert_eq!(detailed[0].evidence.len(), 1);
+        assert_eq!(detailed[0].references[1].citation, "TMEP § 1207.01(b)(ii)");
+
+        assert_eq!("Terse".parse::<Verbosity>(), Ok(Verbosity::Terse));
+        assert!("verbose".parse::<Verbosity>().is_err());
+    }
+
+    /// One flag of each variant, and of each metric explained
+    /// differently. Checked against `RiskFlag::KINDS`, so a new variant
+    /// cannot be left out.
+    fn every_flag() -> Vec<RiskFlag> {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let similarity = |metric: &str| RiskFlag::StringSimilarity {
+            metric: metric.to_string(),
+            score: 0.9,
+        };
+        let flags = vec![
+            RiskFlag::ExactMatch,
+            RiskFlag::ObfuscatedMatch {
+                substitutions: strings(&["1 -> I"]),
+            },
+            RiskFlag::NumberWordMatch {
+                distance: 0,
+                transformations: strings(&["4 -> FOR"]),
+            },
+            RiskFlag::NumberWordMatch {
+                distance: 1,
+                transformations: strings(&["4 -> FOR"]),
+            },
+            RiskFlag::HouseMarkMatch {
+                house_marks: strings(&["ACME"]),
+                designators: Vec::new(),
+            },
+            RiskFlag::CompoundMatch {
+                splits: strings(&["SUNPOWER -> SUN POWER"]),
+            },
+            RiskFlag::AcronymMatch {
+                acronym: "IBM".to_string(),
+                expansion: "INTERNATIONAL BUSINESS MACHINES".to_string(),
+            },
+            RiskFlag::ForeignEquivalent {
+                language: "Italian".to_string(),
+                translation: "WOLF".to_string(),
+            },
+            RiskFlag::PhoneticMatch {
+                algorithm: "double_metaphone".to_string(),
+                code: "NK".to_string(),
+                variant: Some(ilegalflow_model::MatchedVariant {
+                    query: CodeVariant::Primary,
+                    mark: CodeVariant::Alternate,
+                }),
+            },
+            RiskFlag::NearPhoneticMatch {
+                distance: 0.5,
+                similarity: 0.9,
+                phonemes: ("N AY K".to_string(), "N AY K IY".to_string()),
+            },
+            RiskFlag::FuzzyMatch { distance: 2 },
+            similarity("jaro_winkler"),
+            similarity("token_sort_ratio"),
+            similarity("stem_match"),
+            similarity("prefix_weighted"),
+            similarity("ngram_3"),
+            RiskFlag::SyllableMatch {
+                score: 0.8,
+                aligned: vec![("MI".to_string(), "MIK".to_string())],
+            },
+            RiskFlag::ClassOverlap {
+                classes: NiceClass::of_all(&[25]),
+                inferred: true,
+            },
+            RiskFlag::RelatedClasses {
+                pairs: vec![(NiceClass::of(25), NiceClass::of(35))],
+                relatedness: 0.7,
+            },
+            RiskFlag::GoodsServicesSimilar { similarity: 0.6 },
+            RiskFlag::DesignCodeOverlap {
+                codes: strings(&["03.01.01"]),
+            },
+            RiskFlag::DominantTermMatch {
+                term: "NIKE".to_string(),
+                transformations: strings(&["join NI KE -> NIKE"]),
+            },
+            RiskFlag::FamousMark {
+                mark: "NIKE".to_string(),
+                owner: "Nike, Inc.".to_string(),
+                cross_class: true,
+            },
+            RiskFlag::DilutionRisk {
+                kind: DilutionKind::Tarnishment,
+                famous: "NIKE".to_string(),
+                score: 0.7,
+            },
+            RiskFlag::OwnerConflict {
+                owner: "Acme Corp".to_string(),
+                related_marks: strings(&["ACME ROCKET", "ACME ROCKETS"]),
+            },
+            RiskFlag::CommonLawRisk {
+                source: "web".to_string(),
+                evidence: "nike-shop.example".to_string(),
+                confidence: 0.8,
+            },
+            RiskFlag::DesignElementRisk {
+                shared: Vec::new(),
+                codes: strings(&["03.01.01"]),
+            },
+            RiskFlag::DesignElementRisk {
+                shared: strings(&["03.01.01"]),
+                codes: strings(&["03.01.01"]),
+            },
+            RiskFlag::RecentlyDead { months: 4 },
+        ];
+        for kind in RiskFlag::KINDS {
+            assert!(flags.iter().any(|f| f.kind() == kind), "{} not covered", kind);
+        }
+        flags
+    }
+
+    #[test]
+    fn test_severity_matches_flag() {
+        for flag in every_flag() {
+            for locale in Locale::ALL {
+                let explanation = explain_flag_in(&flag, "NIKE", "NYKE", locale);
+                assert_eq!(explanation.severity, flag.severity(), "{:?}", flag);
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_severities() {
+        let hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NYKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.5,
+            risk_probability: None,
+            flags: vec![RiskFlag::ClassOverlap {
+                classes: NiceClass::of_all(&[25]),
+                inferred: false,
+            }],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        let severities = SeverityTable {
+            class_overlap: 0.9,
+            ..SeverityTable::DEFAULT
+        };
+        let options = ExplainOptions::default().with_severities(severities);
+        assert_eq!(explain_hit_with(&hit, "NIKE", &options)[0].severity, 0.9);
+        assert_eq!(RiskTier::of(&hit), RiskTier::Moderate);
+        assert_eq!(RiskTier::of_with(&hit, &severities), RiskTier::High);
+        assert!(summarize_risk_with(&hit, &severities).starts_with("HIGH RISK"));
+    }
+
+    #[test]
+    fn test_detail_levels() {
+        let mut hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NIKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.5,
+            risk_probability: None,
+            flags: vec![RiskFlag::ClassOverlap {
+                classes: NiceClass::of_all(&[25]),
+                inferred: false,
+            }],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        hit.dupont = Some(DuPontAssessment {
+            findings: vec![ilegalflow_model::FactorFinding {
+                factor: ilegalflow_model::DuPontFactor::MarkSimilarity,
+                weight: FactorWeight::FavorsConfusion,
+                finding: "The marks are identical.".to_string(),
+                signals: Vec::new(),
+            }],
+        });
+
+        let professional = explain_hit(&hit, "NIKE");
+        assert_eq!(professional.len(), 2);
+        assert!(professional[0].detail.contains("Nice classification"));
+        assert!(!professional[0].references.is_empty());
+        assert!(professional[1].summary.starts_with("du Pont factor 1"));
+
+        let options = ExplainOptions::default().with_detail_level(DetailLevel::Plain);
+        let plain = explain_hit_with(&hit, "NIKE", &options);
+        assert_eq!(plain.len(), 1);
+        assert_eq!(plain[0].summary, "Same kind of products (25)");
+        assert!(!plain[0].detail.contains("Nice"));
+        assert!(plain[0].references.is_empty());
+        assert_eq!(plain[0].severity, professional[0].severity);
+
+        assert_eq!("Plain".parse::<DetailLevel>(), Ok(DetailLevel::Plain));
+        assert!("expert".parse::<DetailLevel>().is_err());
+    }
+
+    #[test]
+    fn test_explain_highlights() {
+        let mut hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NYKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.5,
+            risk_probability: None,
+            flags: vec![RiskFlag::FuzzyMatch { distance: 1 }],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        hit.highlights.push(ilegalflow_model::MatchSpans {
+            flag: "Spelled Similarly".to_string(),
+            ranges_in_query: vec![0..1, 2..4],
+            ranges_in_mark: vec![0..1, 2..4],
+        });
+
+        let explanations = explain_hit(&hit, "NIKE");
+        assert_eq!(explanations[0].evidence[0].ranges_in_mark, [0..1, 2..4]);
+        let json = serde_json::to_value(&explanations[0].evidence[0]).unwrap();
+        assert_eq!(json["ranges_in_query"][1], serde_json::json!({"start": 2, "end": 4}));
+    }
+
+    #[test]
+    fn test_explain_locale() {
+        let flag = RiskFlag::FuzzyMatch { distance: 1 };
+        let english = explain_flag(&flag, "NIKE", "NYKE");
+        assert_eq!(english.summary, "Spelled similarly");
+        assert_eq!(explain_flag_in(&flag, "NIKE", "NYKE", Locale::En).detail, english.detail);
+
+        let spanish = explain_flag_in(&flag, "NIKE", "NYKE", Locale::Es);
+        assert_eq!(spanish.summary, "Ortografía similar");
+        assert!(spanish.detail.starts_with("La marca 'NYKE' difiere de 'NIKE' en solo 1"));
+        assert_eq!(spanish.severity, english.severity);
+        assert_eq!(spanish.evidence[0].kind, english.evidence[0].kind);
+
+        let flag = RiskFlag::ClassOverlap {
+            classes: NiceClass::of_all(&[25]),
+            inferred: false,
+        };
+        let portuguese = explain_flag_in(&flag, "NIKE", "NYKE", Locale::Pt);
+        assert!(portuguese.detail.contains("Classe 25"));
+
+        let adjustment = StatusAdjustment {
+            status: TrademarkStatus::Dead,
+            multiplier: 0.1,
+        };
+        let status = explain_status_in(&adjustment, Locale::Es);
+        assert_eq!(status.summary, "Marca caducada: riesgo reducido");
+    }
+
+    #[test]
+    fn test_risk_tier() {
+        let mut hit = CandidateHit {
+            record: ilegalflow_model::TrademarkRecord::new("001", "NYKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.5,
+            risk_probability: None,
+            flags: vec![RiskFlag::FuzzyMatch { distance: 2 }],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        assert_eq!(RiskTier::of(&hit), RiskTier::Low);
+        hit.flags.push(RiskFlag::ClassOverlap {
+            classes: NiceClass::of_all(&[25]),
+            inferred: false,
+        });
+        assert_eq!(RiskTier::of(&hit), RiskTier::Moderate);
+        hit.flags.push(RiskFlag::ExactMatch);
+        assert_eq!(RiskTier::of(&hit), RiskTier::High);
+        assert!(summarize_risk(&hit).starts_with("HIGH RISK: "));
+        assert!(RiskTier::High > RiskTier::Moderate);
+
+        hit.risk_probability = Some(0.72);
+        assert!(summarize_risk(&hit).ends_with("(72% likelihood of conflict)"));
     }
 }
@@ -0,0 +1,107 @@
+//! Narrative prose for explanations.
+//!
+//! Explanations stay structured and deterministic. A narrator turns them,
+//! with the marks being compared, into client-ready paragraphs, typically
+//! by calling an LLM. Deployments supply their own; `NoNarrator` writes
+//! nothing, so callers fall back to the structured text. Narrators return
+//! a boxed future so they can be held as `Box<dyn ExplanationNarrator>`.
+
+use crate::Explanation;
+use ilegalflow_model::CandidateHit;
+use std::future::{self, Future};
+use std::pin::Pin;
+use thiserror::Error;
+
+/// Errors from generating a narrative.
+#[derive(Debug, Error)]
+pub enum NarrationError {
+    #[error("Narrator unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Narration failed: {0}")]
+    Failed(String),
+}
+
+/// What a narrative is written about.
+#[derive(Debug, Clone, Copy)]
+pub struct Comparison<'a> {
+    /// The mark searched for
+    pub query_text: &'a str,
+    /// The candidate it is compared with, including its record and flags
+    pub hit: &'a CandidateHit,
+    /// Structured explanations of the hit
+    pub explanations: &'a [Explanation],
+}
+
+/// A narrative in progress: the prose, or `None` to keep the structured
+/// text.
+pub type NarrationFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Option<String>, NarrationError>> + Send + 'a>>;
+
+/// Writes prose from structured explanations.
+pub trait ExplanationNarrator: Send + Sync {
+    /// A narrative for the comparison.
+    fn narrate<'a>(&'a self, comparison: &'a Comparison<'a>) -> NarrationFuture<'a>;
+}
+
+/// The default narrator, which writes nothing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoNarrator;
+
+impl ExplanationNarrator for NoNarrator {
+    fn narrate<'a>(&'a self, _comparison: &'a Comparison<'a>) -> NarrationFuture<'a> {
+        Box::pin(future::ready(Ok(None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain_hit;
+    use ilegalflow_model::{RiskFlag, TrademarkRecord};
+
+    /// Joins the summaries, standing in for an LLM.
+    struct Joiner;
+
+    impl ExplanationNarrator for Joiner {
+        fn narrate<'a>(&'a self, comparison: &'a Comparison<'a>) -> NarrationFuture<'a> {
+            let summaries: Vec<_> =
+                comparison.explanations.iter().map(|e| e.summary.as_str()).collect();
+            let prose = format!(
+                "{} and {}: {}.",
+                comparison.query_text,
+                comparison.hit.record.mark_text,
+                summaries.join("; ")
+            );
+            Box::pin(async move { Ok(Some(prose)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_narrators() {
+        let hit = CandidateHit {
+            record: TrademarkRecord::new("001", "NYKE"),
+            retrieval_score: 1.0,
+            risk_score: 0.5,
+            risk_probability: None,
+            flags: vec![RiskFlag::FuzzyMatch { distance: 1 }],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+        let explanations = explain_hit(&hit, "NIKE");
+        let comparison = Comparison {
+            query_text: "NIKE",
+            hit: &hit,
+            explanations: &explanations,
+        };
+
+        assert_eq!(NoNarrator.narrate(&comparison).await.unwrap(), None);
+        let narrator: Box<dyn ExplanationNarrator> = Box::new(Joiner);
+        let prose = narrator.narrate(&comparison).await.unwrap();
+        assert_eq!(prose.as_deref(), Some("NIKE and NYKE: Spelled similarly."));
+    }
+}
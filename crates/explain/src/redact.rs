@@ -0,0 +1,294 @@
+//! Redaction for results shared outside privilege.
+//!
+//! A `RedactionPolicy` says how much of a candidate's owner name, serial
+//! and registration numbers, and goods and services text to keep. It
+//! applies to records, and to explanations, where it masks those values
+//! wherever the text quotes them.
+
+use crate::Explanation;
+use ilegalflow_model::{CandidateHit, TrademarkRecord};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Text standing in for a fully redacted value.
+pub const REDACTED: &str = "[redacted]";
+
+/// Words of goods and services text kept by partial redaction.
+const GOODS_WORDS: usize = 3;
+
+/// Digits of a serial or registration number kept by partial redaction.
+const NUMBER_DIGITS: usize = 3;
+
+/// How much of a value to keep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Redaction {
+    /// Keep the value as is
+    #[default]
+    Keep,
+    /// Keep enough to tell values apart, mask the rest
+    Partial,
+    /// Replace the value with `[redacted]`
+    Full,
+}
+
+impl FromStr for Redaction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "partial" => Ok(Self::Partial),
+            "full" => Ok(Self::Full),
+            _ => Err(format!("Unknown redaction '{}' (keep, partial, full)", s)),
+        }
+    }
+}
+
+/// What to redact from shared results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionPolicy {
+    pub owner_names: Redaction,
+    /// Serial and registration numbers
+    pub serial_numbers: Redaction,
+    pub goods_services: Redaction,
+}
+
+impl RedactionPolicy {
+    /// Every field partially redacted.
+    pub fn shareable() -> Self {
+        Self::uniform(Redaction::Partial)
+    }
+
+    /// The same redaction for every field.
+    pub fn uniform(redaction: Redaction) -> Self {
+        Self {
+            owner_names: redaction,
+            serial_numbers: redaction,
+            goods_services: redaction,
+        }
+    }
+
+    /// Whether anything is redacted.
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// An owner name; partially, the first letter of each word
+    /// ("N***, I**.").
+    pub fn owner(&self, name: &str) -> String {
+        match self.owner_names {
+            Redaction::Keep => name.to_string(),
+            Redaction::Partial => {
+                let mut word_start = true;
+                name.chars()
+                    .map(|c| {
+                        let masked = if !c.is_alphanumeric() || word_start { c } else { '*' };
+                        word_start = c.is_whitespace();
+                        masked
+                    })
+                    .collect()
+            }
+            Redaction::Full => REDACTED.to_string(),
+        }
+    }
+
+    /// A serial or registration number; partially, the last three digits
+    /// ("*****002").
+    pub fn number(&self, number: &str) -> String {
+        match self.serial_numbers {
+            Redaction::Keep => number.to_string(),
+            Redaction::Partial => {
+                let masked = number.chars().count().saturating_sub(NUMBER_DIGITS);
+                number
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| if i < masked { '*' } else { c })
+                    .collect()
+            }
+            Redaction::Full => REDACTED.to_string(),
+        }
+    }
+
+    /// Goods and services text; partially, the first three words.
+    pub fn goods(&self, goods: &str) -> String {
+        match self.goods_services {
+            Redaction::Keep => goods.to_string(),
+            Redaction::Partial => {
+                let words: Vec<&str> = goods.split_whitespace().collect();
+                if words.len() <= GOODS_WORDS {
+                    goods.to_string()
+                } else {
+                    format!("{} ...", words[..GOODS_WORDS].join(" "))
+                }
+            }
+            Redaction::Full => REDACTED.to_string(),
+        }
+    }
+
+    /// A copy of the record with its fields redacted. The owner's
+    /// address is dropped unless owner names are kept.
+    pub fn record(&self, record: &TrademarkRecord) -> TrademarkRecord {
+        let mut record = record.clone();
+        if !record.owner.is_empty() {
+            record.owner.name = self.owner(&record.owner.name);
+        }
+        if self.owner_names != Redaction::Keep {
+            record.owner.address.clear();
+        }
+        record.serial_number = self.number(&record.serial_number);
+        record.registration_number = record.registration_number.map(|n| self.number(&n));
+        if !record.goods_services.is_empty() {
+            record.goods_services = self.goods(&record.goods_services);
+        }
+        record
+    }
+
+    /// A copy of the hit for sharing: its record and family records
+    /// redacted and, when anything is redacted, its links and review
+    /// note text dropped, since those quote the record's numbers.
+    pub fn hit(&self, hit: &CandidateHit) -> CandidateHit {
+        let mut hit = CandidateHit {
+            record: self.record(&hit.record),
+            family: hit.family.iter().map(|record| self.record(record)).collect(),
+            ..hit.clone()
+        };
+        if self.is_active() {
+            hit.links = None;
+            if let Some(review) = &mut hit.review {
+                review.note.clear();
+                review.reviewer = None;
+            }
+        }
+        hit
+    }
+
+    /// An explanation of a hit with the hit's owner name, numbers, and
+    /// goods and services masked wherever its text quotes them.
+    pub fn explanation(&self, mut explanation: Explanation, hit: &CandidateHit) -> Explanation {
+        if !self.is_active() {
+            return explanation;
+        }
+        let record = &hit.record;
+        // Goods text first, since it can quote the owner's name
+        let mut replacements = vec![
+            (record.goods_services.clone(), self.goods(&record.goods_services)),
+            (record.owner.name.clone(), self.owner(&record.owner.name)),
+            (record.serial_number.clone(), self.number(&record.serial_number)),
+        ];
+        if let Some(registration) = &record.registration_number {
+            replacements.push((registration.clone(), self.number(registration)));
+        }
+        replacements.retain(|(from, to)| !from.is_empty() && from != to);
+
+        let redact = |text: &mut String| {
+            for (from, to) in &replacements {
+                if text.contains(from.as_str()) {
+                    *text = text.replace(from.as_str(), to);
+                }
+            }
+        };
+        redact(&mut explanation.summary);
+        redact(&mut explanation.detail);
+        for item in &mut explanation.evidence {
+            redact(&mut item.value);
+            if let Some(context) = &mut item.context {
+                redact(context);
+            }
+        }
+        explanation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{explain_hit_with, ExplainOptions};
+    use ilegalflow_model::{Owner, ReviewNote, ReviewVerdict, RiskFlag, SourceLinks};
+
+    #[test]
+    fn test_partial_redaction() {
+        let policy = RedactionPolicy::shareable();
+        assert_eq!(policy.owner("Nike, Inc."), "N***, I**.");
+        assert_eq!(policy.number("72000002"), "*****002");
+        assert_eq!(policy.goods("Footwear, apparel, namely shirts"), "Footwear, apparel, namely ...");
+        assert_eq!(policy.goods("Footwear"), "Footwear");
+
+        let full = RedactionPolicy::uniform(Redaction::Full);
+        assert_eq!(full.owner("Nike, Inc."), REDACTED);
+        assert_eq!(RedactionPolicy::default().number("72000002"), "72000002");
+        assert!(!RedactionPolicy::default().is_active());
+    }
+
+    #[test]
+    fn test_redact_explanations() {
+        let mut record = TrademarkRecord::new("72000002", "NIKE");
+        record.owner = Owner::new("Nike, Inc.").with_address(vec!["Beaverton, OR".to_string()]);
+        record.registration_number = Some("0978952".to_string());
+        let hit = CandidateHit {
+            record,
+            retrieval_score: 1.0,
+            risk_score: 0.9,
+            risk_probability: None,
+            flags: vec![RiskFlag::FamousMark {
+                mark: "NIKE".to_string(),
+                owner: "Nike, Inc.".to_string(),
+                cross_class: false,
+            }],
+            review: Some(ReviewNote {
+                verdict: ReviewVerdict::Pin,
+                note: "Same owner as 72000002 (Reg. 0978952)".to_string(),
+                reviewer: None,
+            }),
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        };
+
+        let options = ExplainOptions::default().with_redaction(RedactionPolicy::shareable());
+        let explanations = explain_hit_with(&hit, "NIKE AIR", &options);
+        assert_eq!(explanations[0].evidence[0].value, "Same owner as *****002 (Reg. ****952)");
+        assert!(explanations[1].detail.contains("owned by N***, I**."));
+        assert_eq!(explanations[1].evidence[0].context.as_deref(), Some("owned by N***, I**."));
+        let json = serde_json::to_string(&explanations).unwrap();
+        assert!(!json.contains("Nike, Inc.") && !json.contains("72000002"));
+        assert!(RedactionPolicy::shareable().record(&hit.record).owner.address.is_empty());
+    }
+
+    #[test]
+    fn test_redact_hit() {
+        let record = TrademarkRecord::new("72000002", "NIKE");
+        let hit = CandidateHit {
+            links: SourceLinks::for_record(&record),
+            record,
+            retrieval_score: 1.0,
+            risk_score: 0.9,
+            risk_probability: None,
+            flags: Vec::new(),
+            review: Some(ReviewNote {
+                verdict: ReviewVerdict::Pin,
+                note: "Same owner as 72000002".to_string(),
+                reviewer: Some("jdoe".to_string()),
+            }),
+            status_adjustment: None,
+            family: vec![TrademarkRecord::new("72000003", "NIKE AIR")],
+            dupont: None,
+            highlights: Vec::new(),
+        };
+
+        let redacted = RedactionPolicy::shareable().hit(&hit);
+        assert_eq!(redacted.family[0].serial_number, "*****003");
+        assert!(redacted.links.is_none());
+        let review = redacted.review.unwrap();
+        assert_eq!(review.verdict, ReviewVerdict::Pin);
+        assert!(review.note.is_empty() && review.reviewer.is_none());
+        let json = serde_json::to_string(&RedactionPolicy::shareable().hit(&hit)).unwrap();
+        assert!(!json.contains("72000002") && !json.contains("72000003"));
+
+        let kept = RedactionPolicy::default().hit(&hit);
+        assert!(kept.links.is_some() && kept.review.unwrap().reviewer.is_some());
+    }
+}
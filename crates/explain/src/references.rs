@@ -0,0 +1,205 @@
+//! Legal references behind explanations.
+//!
+//! Each flag variant cites the TMEP sections, Lanham Act provisions and
+//! leading cases an examiner or court would apply to it, so attorneys can
+//! ground a write-up without looking them up. Citations are not
+//! localized.
+
+use ilegalflow_model::RiskFlag;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What kind of authority a reference is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// Trademark Manual of Examining Procedure section
+    Tmep,
+    /// Lanham Act section, cited to the U.S. Code
+    Statute,
+    /// Reported decision
+    Case,
+}
+
+/// A citation supporting an explanation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    /// Citation, e.g. "TMEP § 1207.01(b)"
+    pub citation: String,
+    /// What the cited authority covers
+    pub title: String,
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.citation, self.title)
+    }
+}
+
+/// The reference table: id, kind, citation and title.
+#[rustfmt::skip]
+const REFERENCES: &[(&str, ReferenceKind, &str, &str)] = &[
+    ("2(d)", ReferenceKind::Statute,
+     "15 U.S.C. § 1052(d)", "Lanham Act § 2(d), likelihood of confusion"),
+    ("43(a)", ReferenceKind::Statute,
+     "15 U.S.C. § 1125(a)", "Lanham Act § 43(a), unregistered marks"),
+    ("43(c)", ReferenceKind::Statute,
+     "15 U.S.C. § 1125(c)", "Lanham Act § 43(c), dilution"),
+    ("45", ReferenceKind::Statute,
+     "15 U.S.C. § 1127", "Lanham Act § 45, abandonment"),
+    ("1207.01", ReferenceKind::Tmep,
+     "TMEP § 1207.01", "Likelihood of confusion"),
+    ("1207.01(a)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(a)", "Relatedness of the goods or services"),
+    ("1207.01(b)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)", "Similarity of the marks"),
+    ("1207.01(b)(ii)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)(ii)", "Similarity in appearance"),
+    ("1207.01(b)(iii)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)(iii)", "Marks that contain additional matter"),
+    ("1207.01(b)(iv)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)(iv)", "Similarity in sound"),
+    ("1207.01(b)(vi)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)(vi)", "Doctrine of foreign equivalents"),
+    ("1207.01(b)(vii)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)(vii)", "Transposition of terms"),
+    ("1207.01(b)(viii)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(b)(viii)", "Marks consisting of multiple words"),
+    ("1207.01(c)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(c)", "Design marks"),
+    ("1207.01(d)(ix)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(d)(ix)", "Fame of the prior mark"),
+    ("1207.01(d)(xiv)", ReferenceKind::Tmep,
+     "TMEP § 1207.01(d)(xiv)", "Family of marks"),
+    ("1401", ReferenceKind::Tmep,
+     "TMEP § 1401", "Classification"),
+    ("du_pont", ReferenceKind::Case,
+     "In re E. I. du Pont de Nemours & Co., 476 F.2d 1357 (C.C.P.A. 1973)",
+     "Likelihood of confusion factors"),
+    ("national_data", ReferenceKind::Case,
+     "In re National Data Corp., 753 F.2d 1056 (Fed. Cir. 1985)",
+     "Weight given to the dominant portion of a mark"),
+    ("palm_bay", ReferenceKind::Case,
+     "Palm Bay Imports, Inc. v. Veuve Clicquot Ponsardin, 396 F.3d 1369 (Fed. Cir. 2005)",
+     "Foreign equivalents"),
+    ("kenner_parker", ReferenceKind::Case,
+     "Kenner Parker Toys Inc. v. Rose Art Industries, Inc., 963 F.2d 350 (Fed. Cir. 1992)",
+     "Fame of the prior mark"),
+    ("j_and_j", ReferenceKind::Case,
+     "J & J Snack Foods Corp. v. McDonald's Corp., 932 F.2d 1460 (Fed. Cir. 1991)",
+     "Family of marks"),
+];
+
+/// References for a risk flag.
+pub fn for_flag(flag: &RiskFlag) -> Vec<Reference> {
+    flag_ids(flag).iter().filter_map(|id| lookup(id)).collect()
+}
+
+/// References for a du Pont factor analysis.
+pub fn dupont() -> Vec<Reference> {
+    ["1207.01", "du_pont"].iter().filter_map(|id| lookup(id)).collect()
+}
+
+/// References for an earlier likelihood-of-confusion refusal.
+pub fn prior_refusal() -> Vec<Reference> {
+    ["2(d)", "1207.01"].iter().filter_map(|id| lookup(id)).collect()
+}
+
+/// Ids of the references cited for a flag.
+fn flag_ids(flag: &RiskFlag) -> &'static [&'static str] {
+    match flag {
+        RiskFlag::ExactMatch => &["2(d)", "1207.01(b)", "du_pont"],
+        RiskFlag::ObfuscatedMatch { .. }
+        | RiskFlag::CompoundMatch { .. }
+        | RiskFlag::FuzzyMatch { .. }
+        | RiskFlag::SyllableMatch { .. } => &["2(d)", "1207.01(b)(ii)"],
+        RiskFlag::NumberWordMatch { .. }
+        | RiskFlag::PhoneticMatch { .. }
+        | RiskFlag::NearPhoneticMatch { .. } => &["2(d)", "1207.01(b)(iv)"],
+        RiskFlag::HouseMarkMatch { .. } => &["2(d)", "1207.01(b)(iii)"],
+        RiskFlag::AcronymMatch { .. } => &["2(d)", "1207.01(b)"],
+        RiskFlag::ForeignEquivalent { .. } => &["2(d)", "1207.01(b)(vi)", "palm_bay"],
+        RiskFlag::StringSimilarity { metric, .. } if metric == "token_sort_ratio" => {
+            &["2(d)", "1207.01(b)(vii)"]
+        }
+        RiskFlag::StringSimilarity { .. } => &["2(d)", "1207.01(b)(ii)"],
+        RiskFlag::ClassOverlap { .. } => &["1207.01(a)", "1401"],
+        RiskFlag::RelatedClasses { .. } | RiskFlag::GoodsServicesSimilar { .. } => {
+            &["1207.01(a)", "du_pont"]
+        }
+        RiskFlag::DesignCodeOverlap { .. } | RiskFlag::DesignElementRisk { .. } => {
+            &["1207.01(c)"]
+        }
+        RiskFlag::DominantTermMatch { .. } => &["1207.01(b)(viii)", "national_data"],
+        RiskFlag::FamousMark { .. } => &["1207.01(d)(ix)", "kenner_parker"],
+        RiskFlag::DilutionRisk { .. } => &["43(c)"],
+        RiskFlag::CommonLawRisk { .. } => &["43(a)"],
+        RiskFlag::OwnerConflict { .. } => &["1207.01(d)(xiv)", "j_and_j"],
+        RiskFlag::RecentlyDead { .. } => &["45"],
+        _ => &["2(d)", "1207.01"],
+    }
+}
+
+fn lookup(id: &str) -> Option<Reference> {
+    REFERENCES
+        .iter()
+        .find(|(key, ..)| *key == id)
+        .map(|(_, kind, citation, title)| Reference {
+            kind: *kind,
+            citation: citation.to_string(),
+            title: title.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::NiceClass;
+
+    #[test]
+    fn test_for_flag() {
+        let refs = for_flag(&RiskFlag::ForeignEquivalent {
+            language: "Italian".to_string(),
+            translation: "WOLF".to_string(),
+        });
+        let citations: Vec<_> = refs.iter().map(|r| r.citation.as_str()).collect();
+        assert_eq!(citations[0], "15 U.S.C. § 1052(d)");
+        assert_eq!(citations[1], "TMEP § 1207.01(b)(vi)");
+        assert_eq!(refs[2].kind, ReferenceKind::Case);
+        assert_eq!(refs[1].to_string(), "TMEP § 1207.01(b)(vi) (Doctrine of foreign equivalents)");
+
+        let reordered = RiskFlag::StringSimilarity {
+            metric: "token_sort_ratio".to_string(),
+            score: 1.0,
+        };
+        assert_eq!(for_flag(&reordered)[1].title, "Transposition of terms");
+    }
+
+    #[test]
+    fn test_table_ids_resolve() {
+        // Cited ids resolve, and each appears in the table once
+        let flags = [
+            RiskFlag::ExactMatch,
+            RiskFlag::FuzzyMatch { distance: 1 },
+            RiskFlag::ClassOverlap {
+                classes: NiceClass::of_all(&[25]),
+                inferred: false,
+            },
+            RiskFlag::OwnerConflict {
+                owner: "Acme Corp".to_string(),
+                related_marks: vec!["ACME ROCKET".to_string()],
+            },
+            RiskFlag::RecentlyDead { months: 3 },
+        ];
+        for flag in &flags {
+            assert_eq!(for_flag(flag).len(), flag_ids(flag).len(), "{:?}", flag);
+        }
+        assert_eq!(dupont().len(), 2);
+        for (id, ..) in REFERENCES {
+            assert_eq!(REFERENCES.iter().filter(|(key, ..)| key == id).count(), 1, "{}", id);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Markdown and HTML rendering of explanations.
+//!
+//! Each explanation renders as a list item: a severity badge, the
+//! summary, the detail, a nested list of evidence, and the references
+//! cited for it. Explanations with no severity (reviewer notes, status
+//! adjustments) carry no badge. All text is escaped, so marks and
+//! reviewer notes cannot inject markup.
+
+use crate::i18n::message;
+use crate::{Explanation, Locale, RiskTier};
+use std::fmt::Write;
+
+/// Render explanations as a Markdown list.
+pub fn markdown(explanations: &[Explanation], locale: Locale) -> String {
+    let mut md = String::new();
+    for explanation in explanations {
+        let _ = write!(md, "-");
+        if explanation.severity > 0.0 {
+            let _ = write!(md, " `{}`", badge(explanation.severity, locale).1);
+        }
+        let _ = write!(md, " **{}**", escape_markdown(&explanation.summary));
+        if !explanation.detail.is_empty() {
+            let _ = write!(md, ": {}", escape_markdown(&explanation.detail));
+        }
+        let _ = writeln!(md);
+        for item in &explanation.evidence {
+            let _ = write!(
+                md,
+                "  - {}: {}",
+                escape_markdown(item.kind.as_str()),
+                escape_markdown(&item.value)
+            );
+            if let Some(context) = &item.context {
+                let _ = write!(md, " ({})", escape_markdown(context));
+            }
+            let _ = writeln!(md);
+        }
+        if !explanation.references.is_empty() {
+            let citations = citations(explanation);
+            let see = message(locale, "references", &[("citations", &citations)]);
+            let _ = writeln!(md, "  - {}", escape_markdown(&see));
+        }
+    }
+    md
+}
+
+/// Render explanations as an HTML fragment: a `<ul class="explanations">`
+/// styled through the `severity-*`, `badge-*` and `evidence` classes.
+pub fn html(explanations: &[Explanation], locale: Locale) -> String {
+    let mut out = String::from("<ul class=\"explanations\">\n");
+    for explanation in explanations {
+        if explanation.severity > 0.0 {
+            let (class, label) = badge(explanation.severity, locale);
+            let _ = write!(
+                out,
+                "<li class=\"explanation severity-{0}\">\
+                 <span class=\"badge badge-{0}\">{1}</span> ",
+                class,
+                escape_html(&label)
+            );
+        } else {
+            let _ = write!(out, "<li class=\"explanation\">");
+        }
+        let _ = write!(out, "<strong>{}</strong>", escape_html(&explanation.summary));
+        if !explanation.detail.is_empty() {
+            let _ = write!(out, "<p>{}</p>", escape_html(&explanation.detail));
+        }
+        if !explanation.evidence.is_empty() {
+            let _ = write!(out, "<ul class=\"evidence\">");
+            for item in &explanation.evidence {
+                let _ = write!(
+                    out,
+                    "<li><code>{}</code> {}",
+                    escape_html(item.kind.as_str()),
+                    escape_html(&item.value)
+                );
+                if let Some(context) = &item.context {
+                    let _ = write!(out, " <small>{}</small>", escape_html(context));
+                }
+                let _ = write!(out, "</li>");
+            }
+            let _ = write!(out, "</ul>");
+        }
+        if !explanation.references.is_empty() {
+            let citations = citations(explanation);
+            let see = message(locale, "references", &[("citations", &citations)]);
+            let _ = write!(out, "<p class=\"references\">{}</p>", escape_html(&see));
+        }
+        let _ = writeln!(out, "</li>");
+    }
+    out.push_str("</ul>\n");
+    out
+}
+
+/// Citations of an explanation's references, separated by semicolons.
+fn citations(explanation: &Explanation) -> String {
+    let citations: Vec<&str> =
+        explanation.references.iter().map(|r| r.citation.as_str()).collect();
+    citations.join("; ")
+}
+
+/// CSS class suffix and localized label for a severity.
+fn badge(severity: f32, locale: Locale) -> (&'static str, String) {
+    let class = match RiskTier::from_severity(severity) {
+        RiskTier::Low => "low",
+        RiskTier::Moderate => "moderate",
+        RiskTier::High => "high",
+    };
+    (class, message(locale, &format!("tier.{}", class), &[]))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Backslash-escape Markdown syntax and fold line breaks, so the text
+/// stays inside its list item.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' | '\r' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{explain_flag, explain_flag_in, explain_review};
+    use ilegalflow_model::{ReviewNote, ReviewVerdict, RiskFlag};
+
+    #[test]
+    fn test_markdown() {
+        let explanations = vec![
+            explain_flag(&RiskFlag::ExactMatch, "NIKE", "NIKE"),
+            explain_review(&ReviewNote {
+                verdict: ReviewVerdict::Pin,
+                note: "Watch *closely*".to_string(),
+                reviewer: None,
+            }),
+        ];
+        let md = markdown(&explanations, Locale::En);
+        let lines: Vec<_> = md.lines().collect();
+        assert!(lines[0].starts_with("- `High` **Exact match found**: The mark 'NIKE'"));
+        assert_eq!(lines[1], "  - exact\\_match: NIKE");
+        assert!(lines[2].starts_with("  - See 15 U.S.C. § 1052(d); TMEP § 1207.01(b); In re "));
+        assert!(lines[3].starts_with("- **Flagged by reviewer**: "));
+        assert!(lines[3].ends_with("Note: Watch \\*closely\\*"));
+
+        let spanish = vec![explain_flag_in(&RiskFlag::ExactMatch, "NIKE", "NIKE", Locale::Es)];
+        assert!(markdown(&spanish, Locale::Es).starts_with("- `Alto` **Coincidencia exacta**"));
+    }
+
+    #[test]
+    fn test_html_escapes() {
+        let flag = RiskFlag::FuzzyMatch { distance: 1 };
+        let explanations = vec![explain_flag(&flag, "NIKE", "<script>alert(1)</script>")];
+        let html = html(&explanations, Locale::En);
+        assert!(html.starts_with(
+            "<ul class=\"explanations\">\n<li class=\"explanation severity-low\">\
+             <span class=\"badge badge-low\">Low</span> <strong>Spelled similarly</strong>"
+        ));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<ul class=\"evidence\"><li><code>edit_distance</code> 1</li></ul>"));
+        assert!(html.contains(
+            "<p class=\"references\">See 15 U.S.C. § 1052(d); TMEP § 1207.01(b)(ii)</p>"
+        ));
+        assert!(html.ends_with("</li>\n</ul>\n"));
+    }
+}
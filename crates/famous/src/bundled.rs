@@ -0,0 +1,52 @@
+//! The bundled famous marks: name, owner and the classes each is best
+//! known in. Marks are widely recognized by the general consuming public
+//! (the standard for fame under the TDRA), not merely well known in a
+//! niche.
+
+#[rustfmt::skip]
+pub(crate) const FAMOUS_MARKS: &[(&str, &str, &[u16])] = &[
+    ("ADIDAS", "adidas AG", &[18, 25, 28]),
+    ("AMAZON", "Amazon Technologies, Inc.", &[9, 35, 38, 42]),
+    ("AMERICAN EXPRESS", "American Express Company", &[36]),
+    ("APPLE", "Apple Inc.", &[9, 35, 42]),
+    ("BARBIE", "Mattel, Inc.", &[28]),
+    ("BMW", "Bayerische Motoren Werke AG", &[12, 37]),
+    ("BUDWEISER", "Anheuser-Busch, LLC", &[32]),
+    ("BURGER KING", "Burger King Company LLC", &[43]),
+    ("CHANEL", "Chanel, Inc.", &[3, 14, 18, 25]),
+    ("COCA-COLA", "The Coca-Cola Company", &[32]),
+    ("DISNEY", "Disney Enterprises, Inc.", &[9, 16, 28, 41]),
+    ("FACEBOOK", "Meta Platforms, Inc.", &[9, 38, 42, 45]),
+    ("FORD", "Ford Motor Company", &[12, 37]),
+    ("GOOGLE", "Google LLC", &[9, 35, 38, 42]),
+    ("GUCCI", "Gucci America, Inc.", &[14, 18, 25]),
+    ("HARLEY-DAVIDSON", "H-D U.S.A., LLC", &[12, 25]),
+    ("HERSHEY'S", "Hershey Chocolate & Confectionery LLC", &[30]),
+    ("IBM", "International Business Machines Corporation", &[9, 42]),
+    ("INTEL", "Intel Corporation", &[9]),
+    ("KELLOGG'S", "Kellogg North America Company", &[30]),
+    ("KODAK", "Eastman Kodak Company", &[1, 9, 40]),
+    ("LEGO", "LEGO Juris A/S", &[28]),
+    ("LEVI'S", "Levi Strauss & Co.", &[25]),
+    ("LOUIS VUITTON", "Louis Vuitton Malletier", &[18, 25]),
+    ("MASTERCARD", "Mastercard International Incorporated", &[9, 36]),
+    ("MCDONALD'S", "McDonald's Corporation", &[29, 30, 43]),
+    ("MERCEDES-BENZ", "Mercedes-Benz Group AG", &[12, 37]),
+    ("MICROSOFT", "Microsoft Corporation", &[9, 42]),
+    ("NETFLIX", "Netflix, Inc.", &[9, 38, 41]),
+    ("NIKE", "Nike, Inc.", &[18, 25, 28]),
+    ("PEPSI", "PepsiCo, Inc.", &[32]),
+    ("PORSCHE", "Dr. Ing. h.c. F. Porsche AG", &[12]),
+    ("PRADA", "Prada S.A.", &[18, 25]),
+    ("ROLEX", "Rolex Watch U.S.A., Inc.", &[14]),
+    ("SAMSUNG", "Samsung Electronics Co., Ltd.", &[9, 11]),
+    ("SONY", "Sony Group Corporation", &[9]),
+    ("STARBUCKS", "Starbucks Corporation", &[30, 43]),
+    ("TESLA", "Tesla, Inc.", &[9, 12]),
+    ("TIFFANY", "Tiffany and Company", &[14]),
+    ("TOYOTA", "Toyota Jidosha Kabushiki Kaisha", &[12]),
+    ("VICTORIA'S SECRET", "Victoria's Secret Stores Brand Management, LLC", &[3, 25]),
+    ("VISA", "Visa International Service Association", &[9, 36]),
+    ("XEROX", "Xerox Corporation", &[9, 16, 40]),
+    ("YOUTUBE", "Google LLC", &[9, 38, 41]),
+];
@@ -0,0 +1,170 @@
+//! Famous marks for iLegalFlow trademark analysis.
+//!
+//! Famous marks are protected beyond the classes they are registered in:
+//! NIKE blocks a NIKE restaurant as well as NIKE shoes. This crate ships
+//! a curated list of widely recognized marks (name, owner and core
+//! classes) and finds them inside other marks. Users add their own marks
+//! in code or from a tab-separated file.
+
+mod bundled;
+
+use ilegalflow_features::{normalize_text, read_tsv};
+use ilegalflow_model::NiceClass;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// A famous mark.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FamousMark {
+    /// Mark as usually written
+    pub name: String,
+    /// Owner of the mark
+    pub owner: String,
+    /// Nice classes the mark is best known in
+    pub classes: Vec<NiceClass>,
+}
+
+/// A list of famous marks, found by their letters and digits alone so
+/// COCA-COLA, COCA COLA and COCACOLA are the same mark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FamousMarks {
+    marks: Vec<FamousMark>,
+    /// Compact name to position in `marks`
+    index: HashMap<String, usize>,
+    /// Most words a listed name spans in a mark
+    max_words: usize,
+}
+
+impl Default for FamousMarks {
+    /// The bundled list.
+    fn default() -> Self {
+        let mut marks = Self::empty();
+        for (name, owner, classes) in bundled::FAMOUS_MARKS {
+            marks.insert(FamousMark {
+                name: name.to_string(),
+                owner: owner.to_string(),
+                classes: NiceClass::of_all(classes),
+            });
+        }
+        marks
+    }
+}
+
+/// A name's letters and digits, uppercased.
+fn compact(text: &str) -> String {
+    normalize_text(text).split_whitespace().collect()
+}
+
+impl FamousMarks {
+    /// A list with no marks.
+    pub fn empty() -> Self {
+        Self {
+            marks: Vec::new(),
+            index: HashMap::new(),
+            max_words: 0,
+        }
+    }
+
+    /// Add a mark, replacing any listed under the same name.
+    pub fn insert(&mut self, mark: FamousMark) {
+        let key = compact(&mark.name);
+        if key.is_empty() {
+            return;
+        }
+        let words = normalize_text(&mark.name).split_whitespace().count();
+        // Run-together names may be written as several words (COCACOLA)
+        self.max_words = self.max_words.max(words).max(3);
+        match self.index.get(&key) {
+            Some(&i) => self.marks[i] = mark,
+            None => {
+                self.index.insert(key, self.marks.len());
+                self.marks.push(mark);
+            }
+        }
+    }
+
+    /// Add marks from a tab-separated file of `name`, `owner` and
+    /// comma-separated `classes` lines; blank lines and `#` comments are
+    /// skipped.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        read_tsv(path, |fields| {
+            let [name, owner, classes] = fields else {
+                return Err("expected name, owner, classes");
+            };
+            let classes = classes
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(|c| c.parse().map_err(|_| "classes must be Nice classes"))
+                .collect::<Result<_, _>>()?;
+            self.insert(FamousMark {
+                name: name.to_string(),
+                owner: owner.to_string(),
+                classes,
+            });
+            Ok(())
+        })
+    }
+
+    /// Number of listed marks.
+    pub fn len(&self) -> usize {
+        self.marks.len()
+    }
+
+    /// Whether no marks are listed.
+    pub fn is_empty(&self) -> bool {
+        self.marks.is_empty()
+    }
+
+    /// The famous mark a whole mark is, if any.
+    pub fn get(&self, text: &str) -> Option<&FamousMark> {
+        self.index.get(&compact(text)).map(|&i| &self.marks[i])
+    }
+
+    /// Famous marks appearing in a mark as whole words (NIKE in NIKE
+    /// GOLF, COCA COLA in COCA COLA ZERO), in order of appearance.
+    pub fn find_in(&self, text: &str) -> Vec<&FamousMark> {
+        let normalized = normalize_text(text);
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+        let mut found: Vec<usize> = Vec::new();
+        for start in 0..words.len() {
+            let mut key = String::new();
+            for word in words.iter().skip(start).take(self.max_words) {
+                key.push_str(word);
+                if let Some(&i) = self.index.get(&key) {
+                    if !found.contains(&i) {
+                        found.push(i);
+                    }
+                }
+            }
+        }
+        found.into_iter().map(|i| &self.marks[i]).collect()
+    }
+
+    /// Every listed mark.
+    pub fn iter(&self) -> impl Iterator<Item = &FamousMark> {
+        self.marks.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_in() {
+        let marks = FamousMarks::default();
+        assert!(marks.len() > 20);
+        assert_eq!(marks.get("Coca-Cola").unwrap().owner, "The Coca-Cola Company");
+        assert_eq!(marks.get("COCA COLA"), marks.get("COCACOLA"));
+        assert!(marks.get("NIKE GOLF").is_none());
+
+        let found: Vec<_> = marks.find_in("NIKE COCA COLA").iter().map(|m| &m.name).collect();
+        assert_eq!(found, vec!["NIKE", "COCA-COLA"]);
+        // Whole words only
+        assert!(marks.find_in("SNIKERS").is_empty());
+    }
+
+}
@@ -0,0 +1,62 @@
+//! Benchmarks for text normalization.
+//!
+//! Run with `cargo bench -p ilegalflow-features`. `baseline` is the
+//! previous three-allocation implementation, kept here for comparison.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ilegalflow_features::{normalize_text, normalize_text_into};
+use std::hint::black_box;
+
+const MARKS: &[&str] = &[
+    "NIKE",
+    "JUST DO IT",
+    "QUICK PRINT SOLUTIONS",
+    "Acme Widgets, Inc.",
+    "  The  Coca-Cola   Company ",
+    "Café Müller GmbH",
+];
+
+fn baseline(text: &str) -> String {
+    text.to_uppercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bench_normalize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("normalize_text");
+
+    group.bench_function("baseline", |b| {
+        b.iter(|| {
+            for mark in MARKS {
+                black_box(baseline(black_box(mark)));
+            }
+        })
+    });
+
+    group.bench_function("cow", |b| {
+        b.iter(|| {
+            for mark in MARKS {
+                black_box(normalize_text(black_box(mark)));
+            }
+        })
+    });
+
+    group.bench_function("reused_buffer", |b| {
+        let mut buf = String::new();
+        b.iter(|| {
+            for mark in MARKS {
+                normalize_text_into(black_box(mark), &mut buf);
+                black_box(&buf);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_normalize);
+criterion_main!(benches);
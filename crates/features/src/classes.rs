@@ -0,0 +1,128 @@
+//! Nice class suggestion from goods/services text.
+//!
+//! Some records arrive with no classes, which silently disables class
+//! overlap. Their goods/services descriptions usually name the goods in
+//! ID Manual terms, so classes can be suggested from the terms present.
+//! Suggestions are a backfill, not a classification: callers mark them
+//! as inferred so scoring can discount them.
+
+use crate::tokenize_goods;
+use ilegalflow_model::{NiceClass, SearchQuery, TrademarkRecord};
+
+/// ID Manual terms (singular, as `tokenize_goods` returns them) that
+/// point to one class. Words with other everyday senses (BAR, WATER,
+/// STORE) or several classes (SPORTING) are left out.
+#[rustfmt::skip]
+const CLASS_TERMS: &[(u16, &[&str])] = &[
+    (3, &["COSMETIC", "PERFUME", "SOAP", "SHAMPOO", "LOTION", "FRAGRANCE", "MAKEUP"]),
+    (5, &["PHARMACEUTICAL", "VITAMIN", "MEDICINE", "SUPPLEMENT", "DISINFECTANT"]),
+    (9, &["SOFTWARE", "DOWNLOADABLE", "COMPUTER", "ELECTRONIC", "SMARTPHONE", "BATTERY", "EYEGLASS"]),
+    (12, &["VEHICLE", "AUTOMOBILE", "BICYCLE", "TIRE", "MOTORCYCLE"]),
+    (14, &["JEWELRY", "WRISTWATCH", "CLOCK", "BRACELET", "NECKLACE"]),
+    (16, &["PAPER", "BOOK", "STATIONERY", "MAGAZINE", "PRINTED", "PUBLICATION"]),
+    (18, &["BAG", "HANDBAG", "LUGGAGE", "WALLET", "LEATHER", "BACKPACK", "UMBRELLA"]),
+    (20, &["FURNITURE", "MATTRESS", "PILLOW", "CHAIR"]),
+    (21, &["COOKWARE", "DINNERWARE", "MUG", "KITCHEN", "HOUSEHOLD"]),
+    (25, &["CLOTHING", "SHIRT", "PANT", "JACKET", "FOOTWEAR", "SHOE", "HAT", "CAP", "HEADWEAR", "APPAREL"]),
+    (28, &["TOY", "GAME", "PLAYTHING"]),
+    (29, &["MEAT", "CHEESE", "MILK", "DAIRY", "SNACK"]),
+    (30, &["COFFEE", "TEA", "BREAD", "PASTRY", "CHOCOLATE", "CANDY", "SAUCE"]),
+    (32, &["BEER", "JUICE", "SODA"]),
+    (33, &["WINE", "SPIRIT", "VODKA", "WHISKEY", "LIQUOR"]),
+    (35, &["RETAIL", "ADVERTISING", "MARKETING", "BUSINESS"]),
+    (36, &["INSURANCE", "BANKING", "FINANCIAL", "INVESTMENT"]),
+    (38, &["TELECOMMUNICATION", "BROADCASTING", "STREAMING"]),
+    (41, &["EDUCATION", "ENTERTAINMENT", "TRAINING", "CONCERT"]),
+    (42, &["CLOUD", "SAAS", "HOSTING", "DESIGN", "TECHNOLOGICAL", "SCIENTIFIC"]),
+    (43, &["RESTAURANT", "CAFE", "CATERING", "HOTEL"]),
+    (44, &["MEDICAL", "BEAUTY", "SALON", "VETERINARY", "HEALTH"]),
+    (45, &["LEGAL", "SECURITY", "DATING"]),
+];
+
+/// Suggest Nice classes for a goods/services description, most
+/// supported first, keeping at most `max_classes`.
+pub fn suggest_classes(goods_services: &str, max_classes: usize) -> Vec<NiceClass> {
+    let terms = tokenize_goods(goods_services);
+    let mut counts: Vec<(u16, usize)> = CLASS_TERMS
+        .iter()
+        .map(|(class, words)| {
+            let hits = terms.iter().filter(|t| words.contains(&t.as_str())).count();
+            (*class, hits)
+        })
+        .filter(|(_, hits)| *hits > 0)
+        .collect();
+    // Stable sort keeps ties in class order
+    counts.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+    counts
+        .into_iter()
+        .take(max_classes)
+        .map(|(class, _)| NiceClass::of(class))
+        .collect()
+}
+
+/// Fill a record's empty classes from its goods/services description,
+/// marking them inferred. Returns true if classes were added.
+pub fn backfill_classes(record: &mut TrademarkRecord, max_classes: usize) -> bool {
+    if !record.classes.is_empty() {
+        return false;
+    }
+    record.classes = suggest_classes(&record.goods_services, max_classes);
+    record.classes_inferred = !record.classes.is_empty();
+    record.classes_inferred
+}
+
+/// Fill a query's empty classes from its goods/services query, marking
+/// them inferred. Returns true if classes were added.
+pub fn backfill_query_classes(query: &mut SearchQuery, max_classes: usize) -> bool {
+    if !query.classes.is_empty() {
+        return false;
+    }
+    let Some(goods_services) = query.goods_services_query.as_deref() else {
+        return false;
+    };
+    query.classes = suggest_classes(goods_services, max_classes);
+    query.classes_inferred = !query.classes.is_empty();
+    query.classes_inferred
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_classes() {
+        assert_eq!(
+            suggest_classes("Clothing, namely, shirts and hats; leather bags", 3),
+            vec![25, 18]
+        );
+        assert_eq!(
+            suggest_classes("Restaurant and catering services; coffee", 1),
+            vec![43]
+        );
+        assert!(suggest_classes("Widgets", 3).is_empty());
+        // Ambiguous words point nowhere
+        assert_eq!(suggest_classes("Chocolate bar; water bottles; store fixtures", 3), vec![30]);
+        assert!(suggest_classes("Sporting goods, namely, rings and tables", 3).is_empty());
+    }
+
+    #[test]
+    fn test_backfill_classes() {
+        let mut record = TrademarkRecord::new("001", "NIKE");
+        record.goods_services = "Athletic footwear".to_string();
+        assert!(backfill_classes(&mut record, 3));
+        assert_eq!(record.classes, vec![25]);
+        assert!(record.classes_inferred);
+
+        // Filed classes are never replaced
+        let mut record = TrademarkRecord::new("002", "NIKE");
+        record.classes = NiceClass::of_all(&[9]);
+        record.goods_services = "Athletic footwear".to_string();
+        assert!(!backfill_classes(&mut record, 3));
+        assert_eq!(record.classes, vec![9]);
+        assert!(!record.classes_inferred);
+
+        let mut query = SearchQuery::new("NIKE").with_goods_services("computer software");
+        assert!(backfill_query_classes(&mut query, 3));
+        assert_eq!(query.classes, vec![9]);
+    }
+}
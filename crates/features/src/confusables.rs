@@ -0,0 +1,156 @@
+//! Leetspeak and character-substitution normalization.
+//!
+//! Marks like N1KE, GR8 or PHUN read as ordinary words once common
+//! substitutions are undone. Whole-word substitutions apply first, then
+//! single characters, then letter sequences. Digits are only replaced in
+//! words that also contain letters (so 7-ELEVEN keeps its 7), and symbols
+//! only inside a word (so YAHOO! keeps its meaning as punctuation).
+
+use crate::normalize_text;
+
+/// Substitution tables for `normalize_confusable_chars`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusableConfig {
+    /// Whole-word substitutions (GR8 → GREAT)
+    pub words: Vec<(String, String)>,
+    /// Single-character substitutions (1 → I, @ → A)
+    pub chars: Vec<(char, char)>,
+    /// Letter-sequence substitutions, applied after characters (PH → F)
+    pub sequences: Vec<(String, String)>,
+}
+
+impl Default for ConfusableConfig {
+    fn default() -> Self {
+        let pairs = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect()
+        };
+        Self {
+            words: pairs(&[
+                ("GR8", "GREAT"),
+                ("L8", "LATE"),
+                ("L8R", "LATER"),
+                ("M8", "MATE"),
+                ("H8", "HATE"),
+                ("W8", "WAIT"),
+                ("B4", "BEFORE"),
+                ("4EVER", "FOREVER"),
+                ("2NITE", "TONIGHT"),
+            ]),
+            chars: vec![
+                ('0', 'O'),
+                ('1', 'I'),
+                ('3', 'E'),
+                ('4', 'A'),
+                ('5', 'S'),
+                ('7', 'T'),
+                ('8', 'B'),
+                ('9', 'G'),
+                ('@', 'A'),
+                ('$', 'S'),
+                ('!', 'I'),
+                ('|', 'I'),
+                ('+', 'T'),
+                ('€', 'E'),
+            ],
+            sequences: pairs(&[("PH", "F")]),
+        }
+    }
+}
+
+/// Text with confusable characters replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusableText {
+    /// Normalized text after substitution
+    pub text: String,
+    /// Substitutions applied, e.g. "1 -> I" (each listed once)
+    pub substitutions: Vec<String>,
+}
+
+/// Undo leetspeak and look-alike substitutions (N1KE → NIKE,
+/// GR8 → GREAT, PHUN → FUN), returning normalized text.
+pub fn normalize_confusable_chars(text: &str, config: &ConfusableConfig) -> ConfusableText {
+    let is_symbol = |c: char| config.chars.iter().any(|(from, _)| *from == c);
+    let mut substitutions = Vec::new();
+    let mut record = |from: &str, to: &str| {
+        let substitution = format!("{} -> {}", from, to);
+        if !substitutions.contains(&substitution) {
+            substitutions.push(substitution);
+        }
+    };
+
+    let upper = text.to_uppercase();
+    let mut words = Vec::new();
+    for token in upper.split(|c: char| !c.is_alphanumeric() && !is_symbol(c)) {
+        if let Some((from, to)) = config.words.iter().find(|(from, _)| from == token) {
+            record(from, to);
+            words.push(to.clone());
+            continue;
+        }
+
+        let has_letter = token.chars().any(char::is_alphabetic);
+        let chars: Vec<char> = token.chars().collect();
+        let mut word = String::with_capacity(token.len());
+        for (i, &c) in chars.iter().enumerate() {
+            let interior = chars
+                .get(i + 1)
+                .is_some_and(|next| next.is_alphanumeric() || is_symbol(*next));
+            let replaceable = has_letter && !c.is_alphabetic() && (c.is_ascii_digit() || interior);
+            match config.chars.iter().find(|(from, _)| *from == c) {
+                Some((from, to)) if replaceable => {
+                    record(&from.to_string(), &to.to_string());
+                    word.push(*to);
+                }
+                _ => word.push(c),
+            }
+        }
+
+        for (from, to) in &config.sequences {
+            if word.contains(from.as_str()) {
+                record(from, to);
+                word = word.replace(from.as_str(), to);
+            }
+        }
+        words.push(word);
+    }
+
+    ConfusableText {
+        text: normalize_text(&words.join(" ")).into_owned(),
+        substitutions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> String {
+        normalize_confusable_chars(text, &ConfusableConfig::default()).text
+    }
+
+    #[test]
+    fn test_substitutions() {
+        assert_eq!(plain("N1KE"), "NIKE");
+        assert_eq!(plain("gr8 taste"), "GREAT TASTE");
+        assert_eq!(plain("Phun Zone"), "FUN ZONE");
+        assert_eq!(plain("$uper C@t"), "SUPER CAT");
+        assert_eq!(plain("L33T"), "LEET");
+
+        let result = normalize_confusable_chars("N1K3", &ConfusableConfig::default());
+        assert_eq!(result.substitutions, vec!["1 -> I", "3 -> E"]);
+    }
+
+    #[test]
+    fn test_leaves_numbers_and_punctuation() {
+        assert_eq!(plain("7-ELEVEN"), "7 ELEVEN");
+        assert_eq!(plain("YAHOO!"), "YAHOO");
+        assert_eq!(plain("21 CLUB"), "21 CLUB");
+        assert!(
+            normalize_confusable_chars("NIKE", &ConfusableConfig::default())
+                .substitutions
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Goods/services description similarity.
+//!
+//! Descriptions follow the USPTO ID Manual style ("Clothing, namely,
+//! shirts, pants and hats"), so they are tokenized on punctuation, with
+//! drafting boilerplate ("namely", "for use in") dropped and plurals
+//! folded. Descriptions are compared by cosine similarity of TF-IDF
+//! vectors, with document frequencies taken from the descriptions being
+//! compared: words every candidate shares count for little. With
+//! stemming on, terms are also stripped of -ING and -ER, so RUNNING and
+//! RUNNERS agree.
+
+use crate::stem::{singular, stem};
+use std::collections::HashMap;
+
+/// Shortest token kept.
+const MIN_TOKEN_LEN: usize = 3;
+
+/// Connectives and ID Manual boilerplate that say nothing about the goods.
+const STOPWORDS: &[&str] = &[
+    "AND",
+    "ANY",
+    "ARE",
+    "BEING",
+    "FEATURING",
+    "FOR",
+    "FROM",
+    "GOODS",
+    "INCLUDING",
+    "INTO",
+    "ITS",
+    "NAMELY",
+    "NOT",
+    "OTHER",
+    "PARTICULARLY",
+    "PROVIDING",
+    "RELATED",
+    "SAID",
+    "SAME",
+    "SERVICES",
+    "SOLD",
+    "SPECIALLY",
+    "SUCH",
+    "THE",
+    "THEIR",
+    "THEREOF",
+    "THESE",
+    "THOSE",
+    "USE",
+    "USED",
+    "VIA",
+    "WITH",
+];
+
+/// Split a goods/services description into normalized terms.
+///
+/// Uppercases, splits on anything that is not a letter or digit, drops
+/// stopwords and short tokens, and folds plurals (BATTERIES → BATTERY,
+/// WATCHES → WATCH, SHIRTS → SHIRT).
+pub fn tokenize_goods(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= MIN_TOKEN_LEN)
+        .map(|word| word.to_uppercase())
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .map(singular)
+        .collect()
+}
+
+/// Document frequencies over a set of goods/services descriptions.
+#[derive(Debug, Clone, Default)]
+pub struct TfIdfModel {
+    doc_freq: HashMap<String, usize>,
+    docs: usize,
+    stemming: bool,
+}
+
+/// A description's TF-IDF weights, L2-normalized.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TfIdfVector {
+    weights: HashMap<String, f32>,
+}
+
+impl TfIdfModel {
+    /// Build a model from the descriptions to be compared.
+    pub fn from_documents<'a>(docs: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut model = Self::default();
+        for doc in docs {
+            model.add_document(doc);
+        }
+        model
+    }
+
+    /// Stem terms beyond plural folding; set before adding descriptions.
+    pub fn with_stemming(mut self, stemming: bool) -> Self {
+        self.stemming = stemming;
+        self
+    }
+
+    /// A description's terms, stemmed if the model stems.
+    fn terms(&self, text: &str) -> Vec<String> {
+        let terms = tokenize_goods(text);
+        if self.stemming {
+            terms.iter().map(|term| stem(term)).collect()
+        } else {
+            terms
+        }
+    }
+
+    /// Count one more description.
+    pub fn add_document(&mut self, doc: &str) {
+        let mut terms = self.terms(doc);
+        terms.sort_unstable();
+        terms.dedup();
+        for term in terms {
+            *self.doc_freq.entry(term).or_default() += 1;
+        }
+        self.docs += 1;
+    }
+
+    /// Smoothed inverse document frequency; terms in every description
+    /// still weigh 1.0.
+    pub fn idf(&self, term: &str) -> f32 {
+        let df = self.doc_freq.get(term).copied().unwrap_or(0);
+        ((1 + self.docs) as f32 / (1 + df) as f32).ln() + 1.0
+    }
+
+    /// Weight a description's terms.
+    pub fn vector(&self, text: &str) -> TfIdfVector {
+        let mut weights: HashMap<String, f32> = HashMap::new();
+        for term in self.terms(text) {
+            *weights.entry(term).or_default() += 1.0;
+        }
+        for (term, weight) in weights.iter_mut() {
+            *weight *= self.idf(term);
+        }
+
+        let norm = weights.values().map(|w| w * w).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            weights.values_mut().for_each(|w| *w /= norm);
+        }
+        TfIdfVector { weights }
+    }
+
+    /// Cosine similarity of two descriptions, 0.0 - 1.0.
+    pub fn similarity(&self, a: &str, b: &str) -> f32 {
+        self.vector(a).cosine(&self.vector(b))
+    }
+}
+
+impl TfIdfVector {
+    /// True if the description had no usable terms.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Cosine similarity, 0.0 - 1.0.
+    pub fn cosine(&self, other: &Self) -> f32 {
+        let (small, large) = if self.weights.len() <= other.weights.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let dot: f32 = small
+            .weights
+            .iter()
+            .filter_map(|(term, w)| large.weights.get(term).map(|v| w * v))
+            .sum();
+        dot.clamp(0.0, 1.0)
+    }
+}
+
+/// Similarity of two descriptions compared on their own.
+pub fn goods_similarity(a: &str, b: &str) -> f32 {
+    TfIdfModel::from_documents([a, b]).similarity(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLOTHING: &str =
+        "Clothing, namely, shirts, pants, jackets, footwear, hats and caps, athletic uniforms";
+    const FOOTWEAR: &str = "Athletic footwear";
+    const SOFTWARE: &str = "Downloadable computer software for use in database management";
+    const SAAS: &str =
+        "Providing temporary use of on-line non-downloadable software for database management";
+    const RESTAURANT: &str = "Restaurant services; carry-out restaurant services";
+
+    #[test]
+    fn test_tokenize_goods() {
+        assert_eq!(
+            tokenize_goods("Clothing, namely, shirts and hats; wrist watches; batteries"),
+            vec!["CLOTHING", "SHIRT", "HAT", "WRIST", "WATCH", "BATTERY"]
+        );
+        assert_eq!(tokenize_goods("Glasses, boxes"), vec!["GLASS", "BOX"]);
+        assert!(tokenize_goods("Services, namely, the same").is_empty());
+    }
+
+    #[test]
+    fn test_idf() {
+        let model = TfIdfModel::from_documents([CLOTHING, FOOTWEAR, SOFTWARE]);
+        assert!(model.idf("ATHLETIC") < model.idf("SHIRT"));
+        assert!(model.idf("SHIRT") < model.idf("UNKNOWN"));
+    }
+
+    #[test]
+    fn test_stemming() {
+        let docs = ["Running shoes", "Shoes for runners", "Dress shoes"];
+        let plain = TfIdfModel::from_documents(docs);
+        let mut stemmed = TfIdfModel::default().with_stemming(true);
+        docs.iter().for_each(|doc| stemmed.add_document(doc));
+
+        assert!(stemmed.similarity(docs[0], docs[1]) > plain.similarity(docs[0], docs[1]));
+        assert!((stemmed.similarity(docs[0], docs[1]) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_goods_similarity() {
+        let model = TfIdfModel::from_documents([CLOTHING, FOOTWEAR, SOFTWARE, SAAS, RESTAURANT]);
+
+        assert!((model.similarity(FOOTWEAR, FOOTWEAR) - 1.0).abs() < 1e-5);
+        assert!(model.similarity(FOOTWEAR, CLOTHING) > 0.3);
+        assert!(model.similarity(SOFTWARE, SAAS) > 0.5);
+        assert_eq!(model.similarity(FOOTWEAR, SOFTWARE), 0.0);
+        assert_eq!(model.similarity(RESTAURANT, CLOTHING), 0.0);
+        assert_eq!(model.similarity("", FOOTWEAR), 0.0);
+
+        assert!((goods_similarity(FOOTWEAR, "footwear, athletic") - 1.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,98 @@
+//! House-mark and designator stripping.
+//!
+//! Product marks are often filed with the owner's house mark or an entity
+//! or product-line designator around them (ACME NIKE SERIES, NIKE INC).
+//! Stripping configured house marks and the designators leaves the part
+//! that identifies the product, so ACME NIKE SERIES compares with NIKE.
+//! A mark is never stripped to nothing.
+
+use crate::normalize_text;
+
+/// Entity and product-line designators.
+#[rustfmt::skip]
+const DESIGNATORS: &[&str] = &[
+    "INC", "LLC", "LLP", "CORP", "CORPORATION", "CO", "COMPANY", "LTD", "LIMITED", "PLC",
+    "GMBH", "AG", "SA", "SRL", "BV", "NV", "PTY", "SERIES", "COLLECTION", "EDITION", "BRAND",
+];
+
+/// A mark with its house marks and designators removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrippedMark {
+    /// Normalized text left after stripping
+    pub text: String,
+    /// House marks removed, as configured
+    pub house_marks: Vec<String>,
+    /// Designators removed
+    pub designators: Vec<String>,
+}
+
+impl StrippedMark {
+    /// Whether anything was removed.
+    pub fn is_stripped(&self) -> bool {
+        !self.house_marks.is_empty() || !self.designators.is_empty()
+    }
+}
+
+/// Remove designators and house marks (matched as whole words, in order)
+/// from a mark; the mark is left whole if nothing else would remain.
+pub fn strip_house_marks(text: &str, house_marks: &[String]) -> StrippedMark {
+    let normalized = normalize_text(text);
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let (designators, kept): (Vec<&str>, Vec<&str>) =
+        words.iter().partition(|w| DESIGNATORS.contains(w));
+    let (mut words, designators) = if kept.is_empty() {
+        (words, Vec::new())
+    } else {
+        (kept, designators)
+    };
+
+    let mut stripped_house = Vec::new();
+    for house_mark in house_marks {
+        let house = normalize_text(house_mark);
+        let house: Vec<&str> = house.split_whitespace().collect();
+        if house.is_empty() || house.len() >= words.len() {
+            continue;
+        }
+        if let Some(start) = words.windows(house.len()).position(|w| w == house.as_slice()) {
+            words.drain(start..start + house.len());
+            stripped_house.push(house.join(" "));
+        }
+    }
+
+    StrippedMark {
+        text: words.join(" "),
+        house_marks: stripped_house,
+        designators: designators.into_iter().map(str::to_string).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_house_marks() {
+        let house = vec!["Acme".to_string(), "PROCTER GAMBLE".to_string()];
+        let stripped = strip_house_marks("ACME NIKE SERIES", &house);
+        assert_eq!(stripped.text, "NIKE");
+        assert_eq!(stripped.house_marks, vec!["ACME"]);
+        assert_eq!(stripped.designators, vec!["SERIES"]);
+
+        let stripped = strip_house_marks("Procter Gamble Zylo Co.", &house);
+        assert_eq!(stripped.text, "ZYLO");
+        assert_eq!(stripped.house_marks, vec!["PROCTER GAMBLE"]);
+
+        assert!(!strip_house_marks("NIKE", &house).is_stripped());
+    }
+
+    #[test]
+    fn test_never_strips_to_nothing() {
+        let house = vec!["ACME".to_string()];
+        assert_eq!(strip_house_marks("ACME", &house).text, "ACME");
+        let stripped = strip_house_marks("ACME INC", &house);
+        assert_eq!(stripped.text, "ACME");
+        assert!(stripped.house_marks.is_empty());
+        assert_eq!(stripped.designators, vec!["INC"]);
+        assert_eq!(strip_house_marks("SERIES", &[]).text, "SERIES");
+    }
+}
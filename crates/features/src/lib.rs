@@ -1,78 +1,170 @@
 //! Feature extraction for trademark analysis.
 //!
 //! Provides pure functions for computing features used in scoring:
-//! - Phonetic encodings (Soundex, Metaphone)
+//! - Phonetic encodings (Soundex, Metaphone, Double Metaphone, NYSIIS,
+//!   Caverphone 2, Kölner Phonetik)
+//! - Phoneme-sequence edit distance for near homophones
 //! - Text normalization
-//! - N-gram generation
-//! - Dominant term extraction
+//! - N-gram generation and similarity (Jaccard, cosine)
+//! - String similarity (Levenshtein, Jaro-Winkler, token sort/set ratios,
+//!   prefix-weighted edit distance)
+//! - Syllable-level comparison (MICROSOFT vs MIKROSOFT)
+//! - Light stemming (WIDGETS → WIDGET, RUNNING → RUN)
+//! - Compound splitting (SUNPOWER → SUN POWER)
+//! - Goods/services similarity (TF-IDF cosine, and embeddings with the
+//!   `embeddings` feature)
+//! - Dominant term extraction (longest word, or rarest by corpus IDF)
+//! - Per-class term strength (BREW is weak for beer, not for software)
+//! - Mark strength from generic to fanciful
+//! - Stylized spelling normalization (KWIK → QUICK)
+//! - Leetspeak and look-alike substitution (N1KE → NIKE)
+//! - Number-word equivalence (4EVER → FOREVER)
+//! - House-mark and designator stripping (ACME NIKE SERIES → NIKE)
+//! - Nice class suggestion from goods/services text
+//! - Foreign-equivalent translation (LUPO → WOLF)
+//! - Transliteration of non-Latin marks (НИКА → NIKA)
 
-use rphonetic::{Encoder, Soundex, Metaphone};
+use ilegalflow_model::design::{design_code_covers, normalize_design_code};
+use ilegalflow_model::NiceClass;
+use std::borrow::Cow;
+use std::collections::HashMap;
 
-/// Phonetic encoding results for a mark.
-#[derive(Debug, Clone, Default)]
-pub struct PhoneticCodes {
-    pub soundex: Option<String>,
-    pub metaphone: Option<String>,
-}
-
-/// Compute phonetic encodings for a mark text.
-pub fn compute_phonetics(text: &str) -> PhoneticCodes {
-    let soundex = Soundex::default();
-    let metaphone = Metaphone::default();
+pub mod classes;
+pub mod confusables;
+pub mod goods;
+pub mod house_marks;
+pub mod numbers;
+pub mod phonemes;
+pub mod phonetic;
+pub mod relatedness;
+pub mod segment;
+pub mod stem;
+pub mod strength;
+pub mod syllables;
+pub mod terms;
+pub mod translations;
+pub mod transliterate;
+pub mod tsv;
+#[cfg(feature = "embeddings")]
+pub mod semantic;
 
-    // rphonetic encode() returns String directly
-    let soundex_code = soundex.encode(text);
-    let metaphone_code = metaphone.encode(text);
+pub use classes::{backfill_classes, backfill_query_classes, suggest_classes};
+pub use confusables::{normalize_confusable_chars, ConfusableConfig, ConfusableText};
+pub use goods::{goods_similarity, tokenize_goods, TfIdfModel, TfIdfVector};
+pub use house_marks::{strip_house_marks, StrippedMark};
+pub use numbers::{
+    match_number_variants, number_word_match, number_word_variants, NumberVariant,
+    NumberWordMatch,
+};
+#[cfg(feature = "embeddings")]
+pub use semantic::{cosine_similarity, semantic_similarity, PrecomputedEmbeddings, TextEmbedder};
+pub use relatedness::ClassRelatedness;
+pub use segment::{compound_match, segment, CompoundMatch};
+pub use stem::{stem, stem_text};
+pub use strength::{
+    mark_strength, mark_strength_with, term_strength, term_strength_in, MarkStrength, Strength,
+    StrengthEstimate,
+};
+pub use syllables::{syllabify, syllable_similarity, SyllableAlignment};
+pub use terms::{TermFrequencies, WeightedTerm};
+pub use translations::{ForeignEquivalentMatch, Translation, TranslationDictionary};
+pub use transliterate::{is_non_latin, latin_mark_text, transliterate};
+pub use tsv::read_tsv;
+pub use phonemes::{compare_phonemes, phoneme_distance, phoneme_match, phonemes, PhonemeMatch};
+pub use phonetic::{
+    compute_phonetics, phonetic_match, PhoneticAlgorithm, PhoneticCodes, PhoneticEncoder,
+    PhoneticEncoderSet, PhoneticEncoding, PhoneticMatchResult, PhoneticRegistry,
+};
 
-    PhoneticCodes {
-        soundex: if soundex_code.is_empty() { None } else { Some(soundex_code) },
-        metaphone: if metaphone_code.is_empty() { None } else { Some(metaphone_code) },
+/// Normalize text for comparison.
+///
+/// Uppercases, drops punctuation, and collapses whitespace into single
+/// spaces. Already-normalized text (the common case for USPTO marks) is
+/// borrowed rather than copied.
+pub fn normalize_text(text: &str) -> Cow<'_, str> {
+    if is_normalized(text) {
+        return Cow::Borrowed(text);
     }
+
+    let mut normalized = String::with_capacity(text.len());
+    normalize_text_into(text, &mut normalized);
+    Cow::Owned(normalized)
 }
 
-/// Check if two texts are phonetically similar.
-pub fn phonetic_match(text1: &str, text2: &str) -> Option<(String, String)> {
-    let codes1 = compute_phonetics(text1);
-    let codes2 = compute_phonetics(text2);
+/// Normalize text into a reusable buffer (cleared first).
+///
+/// Single pass, with a byte-level fast path for ASCII input.
+pub fn normalize_text_into(text: &str, out: &mut String) {
+    out.clear();
+    let mut pending_space = false;
 
-    // Check Soundex match
-    if let (Some(s1), Some(s2)) = (&codes1.soundex, &codes2.soundex) {
-        if s1 == s2 {
-            return Some(("soundex".to_string(), s1.clone()));
+    let mut push = |c: char, out: &mut String| {
+        if c.is_alphanumeric() {
+            if pending_space && !out.is_empty() {
+                out.push(' ');
+            }
+            pending_space = false;
+            out.push(c);
+        } else if c.is_whitespace() {
+            pending_space = true;
         }
-    }
+    };
 
-    // Check Metaphone match
-    if let (Some(m1), Some(m2)) = (&codes1.metaphone, &codes2.metaphone) {
-        if m1 == m2 {
-            return Some(("metaphone".to_string(), m1.clone()));
+    if text.is_ascii() {
+        for b in text.bytes() {
+            push(b.to_ascii_uppercase() as char, out);
+        }
+    } else {
+        for c in text.chars().flat_map(char::to_uppercase) {
+            push(c, out);
         }
     }
-
-    None
 }
 
-/// Normalize text for comparison.
-pub fn normalize_text(text: &str) -> String {
-    text.to_uppercase()
-        .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+/// Check whether text is already in normalized form.
+fn is_normalized(text: &str) -> bool {
+    if text.is_ascii() {
+        let bytes = text.as_bytes();
+        return bytes.first() != Some(&b' ')
+            && bytes.last() != Some(&b' ')
+            && !bytes.windows(2).any(|w| w == b"  ")
+            && bytes
+                .iter()
+                .all(|&b| b == b' ' || b.is_ascii_uppercase() || b.is_ascii_digit());
+    }
+
+    let mut previous_space = true;
+    for c in text.chars() {
+        if c == ' ' {
+            if previous_space {
+                return false;
+            }
+            previous_space = true;
+        } else if c.is_alphanumeric() && c.to_uppercase().eq(std::iter::once(c)) {
+            previous_space = false;
+        } else {
+            return false;
+        }
+    }
+    !previous_space || text.is_empty()
 }
 
+/// Entity suffixes and connectives never taken as a dominant term.
+pub(crate) const DOMINANT_STOPWORDS: &[&str] = &[
+    "INC", "LLC", "CORP", "CORPORATION", "CO", "COMPANY", "LTD", "THE", "A", "AN", "AND", "OF",
+    "FOR",
+];
+
 /// Extract dominant term(s) from a mark.
 ///
 /// Heuristic: longest word, excluding common suffixes like INC, LLC, CORP.
+/// With corpus frequencies, `TermFrequencies::dominant_terms` ranks words
+/// by how rare they are instead.
 pub fn extract_dominant_term(text: &str) -> Option<String> {
-    let stopwords = ["INC", "LLC", "CORP", "CO", "LTD", "THE", "A", "AN", "AND", "OF", "FOR"];
-
     let normalized = normalize_text(text);
     let words: Vec<&str> = normalized
         .split_whitespace()
-        .filter(|w| !stopwords.contains(&w.to_uppercase().as_str()))
+        .filter(|w| !DOMINANT_STOPWORDS.contains(w))
         .collect();
 
     words.into_iter()
@@ -80,6 +172,77 @@ pub fn extract_dominant_term(text: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Extract the dominant term of a mark for goods in `classes`: the
+/// longest of its strongest words, so a term generic or descriptive in
+/// those classes is passed over (ZYLO BREWING in class 32 → ZYLO).
+pub fn extract_dominant_term_in(text: &str, classes: &[NiceClass]) -> Option<String> {
+    let normalized = normalize_text(text);
+    normalized
+        .split_whitespace()
+        .filter(|w| !DOMINANT_STOPWORDS.contains(w))
+        .max_by_key(|w| (term_strength_in(w, classes), w.len()))
+        .map(|s| s.to_string())
+}
+
+/// Stylized spellings and their standard forms.
+const STYLIZED_SPELLINGS: &[(&str, &str)] = &[
+    ("KWIK", "QUICK"),
+    ("KWICK", "QUICK"),
+    ("QUIK", "QUICK"),
+    ("KOOL", "COOL"),
+    ("KUTE", "CUTE"),
+    ("KLEEN", "CLEAN"),
+    ("KRISPY", "CRISPY"),
+    ("KANDY", "CANDY"),
+    ("KAR", "CAR"),
+    ("LITE", "LIGHT"),
+    ("NITE", "NIGHT"),
+    ("BRITE", "BRIGHT"),
+    ("RITE", "RIGHT"),
+    ("XPRESS", "EXPRESS"),
+    ("EZ", "EASY"),
+    ("THRU", "THROUGH"),
+    ("TUFF", "TOUGH"),
+    ("FONE", "PHONE"),
+    ("LUV", "LOVE"),
+    ("SOX", "SOCKS"),
+    ("WERKS", "WORKS"),
+    ("U", "YOU"),
+    ("R", "ARE"),
+    ("4", "FOR"),
+    ("2", "TO"),
+];
+
+/// Shortest stylized spelling that may be split off a run-together word.
+const MIN_SPLIT_PREFIX: usize = 3;
+
+/// Shortest remainder left after splitting a run-together word.
+const MIN_SPLIT_REMAINDER: usize = 3;
+
+/// Get the standard spelling of a stylized word (KWIK → QUICK).
+///
+/// Expects normalized (uppercase) input.
+pub fn standard_spelling(word: &str) -> Option<&'static str> {
+    STYLIZED_SPELLINGS
+        .iter()
+        .find(|(stylized, _)| *stylized == word)
+        .map(|(_, standard)| *standard)
+}
+
+/// Split a leading stylized spelling off a run-together word.
+///
+/// "KWIKPRINT" → ("KWIK", "PRINT"). Very short prefixes and remainders
+/// are not split, so ordinary words aren't broken up by accident.
+pub fn split_stylized_prefix(word: &str) -> Option<(&'static str, &str)> {
+    STYLIZED_SPELLINGS
+        .iter()
+        .filter(|(stylized, _)| stylized.len() >= MIN_SPLIT_PREFIX)
+        .filter(|(stylized, _)| word.len() >= stylized.len() + MIN_SPLIT_REMAINDER)
+        .filter(|(stylized, _)| word.starts_with(stylized))
+        .max_by_key(|(stylized, _)| stylized.len())
+        .map(|(stylized, _)| (*stylized, &word[stylized.len()..]))
+}
+
 /// Generate character n-grams.
 pub fn generate_ngrams(text: &str, n: usize) -> Vec<String> {
     let normalized = normalize_text(text).replace(' ', "");
@@ -95,36 +258,272 @@ pub fn generate_ngrams(text: &str, n: usize) -> Vec<String> {
         .collect()
 }
 
+/// Character n-gram overlap between two texts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NgramSimilarity {
+    /// Shared distinct n-grams over all distinct n-grams
+    pub jaccard: f32,
+    /// Cosine of the n-gram count vectors
+    pub cosine: f32,
+}
+
+/// Compare two texts by character n-grams.
+///
+/// Spacing is ignored (see `generate_ngrams`), so "COCACOLA" and
+/// "COCA COLA" are identical. Texts shorter than `n` are compared whole.
+pub fn ngram_similarity(a: &str, b: &str, n: usize) -> NgramSimilarity {
+    NgramProfile::new(a, n).similarity(&NgramProfile::new(b, n))
+}
+
+/// A text's character n-gram counts, for repeated comparison.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NgramProfile {
+    counts: HashMap<String, u32>,
+    norm: f32,
+}
+
+impl NgramProfile {
+    /// Count a text's n-grams (see `ngram_similarity`).
+    pub fn new(text: &str, n: usize) -> Self {
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for gram in generate_ngrams(text, n.max(1)) {
+            if !gram.is_empty() {
+                *counts.entry(gram).or_default() += 1;
+            }
+        }
+        let norm = counts.values().map(|c| (*c * *c) as f32).sum::<f32>().sqrt();
+        Self { counts, norm }
+    }
+
+    /// Compare with another profile built with the same `n`.
+    pub fn similarity(&self, other: &Self) -> NgramSimilarity {
+        if self.counts.is_empty() || other.counts.is_empty() {
+            let same = self.counts.is_empty() && other.counts.is_empty();
+            let score = if same { 1.0 } else { 0.0 };
+            return NgramSimilarity {
+                jaccard: score,
+                cosine: score,
+            };
+        }
+
+        let mut shared = 0;
+        let mut dot = 0.0_f32;
+        for (gram, count_a) in &self.counts {
+            if let Some(count_b) = other.counts.get(gram) {
+                shared += 1;
+                dot += (*count_a * *count_b) as f32;
+            }
+        }
+
+        NgramSimilarity {
+            jaccard: shared as f32 / (self.counts.len() + other.counts.len() - shared) as f32,
+            cosine: dot / (self.norm * other.norm),
+        }
+    }
+}
+
 /// Compute Levenshtein edit distance between two strings.
 pub fn edit_distance(s1: &str, s2: &str) -> usize {
+    edit_distance_bounded(s1, s2, usize::MAX).unwrap_or(usize::MAX)
+}
+
+/// Levenshtein distance if it is at most `max`, else `None`.
+///
+/// Keeps two rows over the shorter string and stops as soon as a row
+/// exceeds `max`, so distant pairs cost little when screening many
+/// candidates.
+pub fn edit_distance_bounded(s1: &str, s2: &str, max: usize) -> Option<usize> {
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    let (long, short) = if s1.len() >= s2.len() { (s1, s2) } else { (s2, s1) };
+    if long.len() - short.len() > max {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=short.len()).collect();
+    let mut curr = vec![0; short.len() + 1];
+    for (i, a) in long.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, b) in short.iter().enumerate() {
+            let cost = usize::from(a != b);
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        // Distances never shrink from one row to the next
+        if curr.iter().min().is_some_and(|&m| m > max) {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    Some(prev[short.len()]).filter(|&d| d <= max)
+}
+
+/// Number of leading characters two strings share.
+pub fn common_prefix_len(s1: &str, s2: &str) -> usize {
+    s1.chars().zip(s2.chars()).take_while(|(a, b)| a == b).count()
+}
+
+/// Cost of an edit at a position: `1 + decay^position`, so an edit at
+/// the first character costs twice as much as one far into the mark.
+fn positional_cost(position: usize, decay: f32) -> f32 {
+    1.0 + decay.powi(position as i32)
+}
+
+/// Edit distance with edits near the start of the marks costing more,
+/// since consumers notice a mark's beginning first. `decay` (0.0 - 1.0)
+/// sets how quickly the extra cost fades along the mark.
+pub fn positional_edit_distance(s1: &str, s2: &str, decay: f32) -> f32 {
     let s1: Vec<char> = s1.chars().collect();
     let s2: Vec<char> = s2.chars().collect();
-    let len1 = s1.len();
-    let len2 = s2.len();
 
-    let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
+    let mut prev: Vec<f32> = Vec::with_capacity(s2.len() + 1);
+    prev.push(0.0);
+    for j in 1..=s2.len() {
+        prev.push(prev[j - 1] + positional_cost(j - 1, decay));
+    }
+    let mut curr = vec![0.0; s2.len() + 1];
+
+    for i in 1..=s1.len() {
+        curr[0] = prev[0] + positional_cost(i - 1, decay);
+        for j in 1..=s2.len() {
+            let substitution = if s1[i - 1] == s2[j - 1] {
+                0.0
+            } else {
+                positional_cost(i - 1, decay)
+            };
+            curr[j] = (prev[j] + positional_cost(i - 1, decay))
+                .min(curr[j - 1] + positional_cost(j - 1, decay))
+                .min(prev[j - 1] + substitution);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
 
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    prev[s2.len()]
+}
+
+/// Prefix-weighted similarity between two strings (0.0 - 1.0): one minus
+/// the positional edit distance over its maximum for the longer length.
+pub fn prefix_weighted_similarity(s1: &str, s2: &str, decay: f32) -> f32 {
+    let max_len = s1.chars().count().max(s2.chars().count());
+    if max_len == 0 {
+        return 1.0;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    let max_cost: f32 = (0..max_len).map(|i| positional_cost(i, decay)).sum();
+    1.0 - positional_edit_distance(s1, s2, decay) / max_cost
+}
+
+/// Edit-distance similarity between two strings (0.0 - 1.0):
+/// one minus the distance over the longer length.
+pub fn similarity_ratio(s1: &str, s2: &str) -> f32 {
+    let max_len = s1.chars().count().max(s2.chars().count());
+    if max_len == 0 {
+        return 1.0;
     }
+    1.0 - edit_distance(s1, s2) as f32 / max_len as f32
+}
+
+/// Similarity of two texts with their words sorted, so word order does
+/// not matter ("BLUE SKY" vs "SKY BLUE" scores 1.0).
+pub fn token_sort_ratio(s1: &str, s2: &str) -> f32 {
+    let sorted = |s: &str| {
+        let mut words: Vec<&str> = s.split_whitespace().collect();
+        words.sort_unstable();
+        words.join(" ")
+    };
+    similarity_ratio(&sorted(s1), &sorted(s2))
+}
+
+/// Similarity of two texts as word sets: shared words are compared
+/// together with each side's remaining words, and the best pairing wins.
+/// A text whose words are all contained in the other scores 1.0.
+pub fn token_set_ratio(s1: &str, s2: &str) -> f32 {
+    use std::collections::BTreeSet;
 
-    for i in 1..=len1 {
-        for j in 1..=len2 {
-            let cost = if s1[i - 1] == s2[j - 1] { 0 } else { 1 };
-            matrix[i][j] = (matrix[i - 1][j] + 1)
-                .min(matrix[i][j - 1] + 1)
-                .min(matrix[i - 1][j - 1] + cost);
+    let words1: BTreeSet<&str> = s1.split_whitespace().collect();
+    let words2: BTreeSet<&str> = s2.split_whitespace().collect();
+    if words1.is_empty() || words2.is_empty() {
+        return similarity_ratio(s1.trim(), s2.trim());
+    }
+
+    let join = |words: Vec<&str>| words.join(" ");
+    let shared = join(words1.intersection(&words2).copied().collect());
+    let with_rest = |only: Vec<&str>| {
+        let rest = join(only);
+        match (shared.is_empty(), rest.is_empty()) {
+            (true, _) => rest,
+            (false, true) => shared.clone(),
+            (false, false) => format!("{} {}", shared, rest),
         }
+    };
+    let combined1 = with_rest(words1.difference(&words2).copied().collect());
+    let combined2 = with_rest(words2.difference(&words1).copied().collect());
+
+    let mut best = similarity_ratio(&combined1, &combined2);
+    if !shared.is_empty() {
+        best = best
+            .max(similarity_ratio(&shared, &combined1))
+            .max(similarity_ratio(&shared, &combined2));
     }
+    best
+}
 
-    matrix[len1][len2]
+/// Jaro-Winkler similarity between two strings (0.0 - 1.0).
+///
+/// Rewards shared prefixes, so it is gentler than edit distance on long
+/// marks that differ only near the end.
+pub fn jaro_winkler(s1: &str, s2: &str) -> f32 {
+    const PREFIX_SCALE: f32 = 0.1;
+    const MAX_PREFIX: usize = 4;
+
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    if s1.is_empty() && s2.is_empty() {
+        return 1.0;
+    }
+    if s1.is_empty() || s2.is_empty() {
+        return 0.0;
+    }
+
+    // Characters match if equal and no further apart than the window
+    let window = (s1.len().max(s2.len()) / 2).saturating_sub(1);
+    let mut matched1 = vec![false; s1.len()];
+    let mut matched2 = vec![false; s2.len()];
+    let mut matches = 0;
+    for (i, c) in s1.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(s2.len());
+        for j in start..end {
+            if !matched2[j] && s2[j] == *c {
+                matched1[i] = true;
+                matched2[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // Half the matched characters that appear in a different order
+    let in_order1 = s1.iter().zip(&matched1).filter(|(_, m)| **m).map(|(c, _)| c);
+    let in_order2 = s2.iter().zip(&matched2).filter(|(_, m)| **m).map(|(c, _)| c);
+    let transpositions = in_order1.zip(in_order2).filter(|(a, b)| a != b).count() / 2;
+
+    let m = matches as f32;
+    let jaro = (m / s1.len() as f32 + m / s2.len() as f32 + (m - transpositions as f32) / m) / 3.0;
+
+    let prefix = s1
+        .iter()
+        .zip(&s2)
+        .take(MAX_PREFIX)
+        .take_while(|(a, b)| a == b)
+        .count();
+    jaro + prefix as f32 * PREFIX_SCALE * (1.0 - jaro)
 }
 
 /// Check Nice class overlap.
-pub fn class_overlap(classes1: &[u16], classes2: &[u16]) -> Vec<u16> {
+pub fn class_overlap(classes1: &[NiceClass], classes2: &[NiceClass]) -> Vec<NiceClass> {
     classes1
         .iter()
         .filter(|c| classes2.contains(c))
@@ -132,27 +531,102 @@ pub fn class_overlap(classes1: &[u16], classes2: &[u16]) -> Vec<u16> {
         .collect()
 }
 
+/// Check design code overlap.
+///
+/// Returns the record's codes (normalized) covered by any query code,
+/// so a division code like `26.01` catches every section beneath it.
+pub fn design_code_overlap(query_codes: &[String], record_codes: &[String]) -> Vec<String> {
+    let query_codes: Vec<String> = query_codes
+        .iter()
+        .filter_map(|c| normalize_design_code(c))
+        .collect();
+
+    let mut overlap: Vec<String> = record_codes
+        .iter()
+        .filter_map(|c| normalize_design_code(c))
+        .filter(|code| query_codes.iter().any(|q| design_code_covers(q, code)))
+        .collect();
+    overlap.sort();
+    overlap.dedup();
+    overlap
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_phonetic_match() {
-        // These should match phonetically
-        assert!(phonetic_match("SMITH", "SMYTH").is_some());
-        assert!(phonetic_match("NIKE", "NYKE").is_some());
-    }
-
     #[test]
     fn test_normalize_text() {
         assert_eq!(normalize_text("  Hello,  World!  "), "HELLO WORLD");
         assert_eq!(normalize_text("ACME Inc."), "ACME INC");
+        assert_eq!(normalize_text("Café  Müller\t"), "CAFÉ MÜLLER");
+        assert_eq!(normalize_text("straße"), "STRASSE");
+        assert_eq!(normalize_text(" . "), "");
+
+        assert!(matches!(normalize_text("NIKE AIR"), Cow::Borrowed(_)));
+        assert!(matches!(normalize_text("CAFÉ"), Cow::Borrowed(_)));
+        assert!(matches!(normalize_text("NIKE  AIR"), Cow::Owned(_)));
+
+        let mut buf = String::new();
+        normalize_text_into("a-b c", &mut buf);
+        assert_eq!(buf, "AB C");
     }
 
     #[test]
     fn test_dominant_term() {
         assert_eq!(extract_dominant_term("ACME Corporation"), Some("ACME".to_string()));
         assert_eq!(extract_dominant_term("The Widget Company Inc"), Some("WIDGET".to_string()));
+
+        // A term weak for the goods is passed over
+        assert_eq!(extract_dominant_term("ZYLO BREWING"), Some("BREWING".to_string()));
+        assert_eq!(
+            extract_dominant_term_in("ZYLO BREWING", &NiceClass::of_all(&[32])),
+            Some("ZYLO".to_string())
+        );
+        assert_eq!(
+            extract_dominant_term_in("ZYLO BREWING", &NiceClass::of_all(&[9])),
+            Some("BREWING".to_string())
+        );
+    }
+
+    #[test]
+    fn test_stylized_spellings() {
+        assert_eq!(standard_spelling("KWIK"), Some("QUICK"));
+        assert_eq!(standard_spelling("QUICK"), None);
+        assert_eq!(split_stylized_prefix("KWIKPRINT"), Some(("KWIK", "PRINT")));
+        assert_eq!(split_stylized_prefix("KWIKE"), None);
+        assert_eq!(split_stylized_prefix("KARMA"), None);
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.001;
+        assert!(close(jaro_winkler("MARTHA", "MARHTA"), 0.961));
+        assert!(close(jaro_winkler("DIXON", "DICKSONX"), 0.813));
+        assert!(close(jaro_winkler("NIKE", "NIKE"), 1.0));
+        assert!(close(jaro_winkler("", ""), 1.0));
+        assert!(close(jaro_winkler("NIKE", ""), 0.0));
+        assert!(close(jaro_winkler("ABC", "XYZ"), 0.0));
+
+        // Harsh as an edit distance, close by Jaro-Winkler
+        assert_eq!(edit_distance("MICROSOFTWARE", "MICROSOFTWEAR"), 2);
+        assert!(jaro_winkler("MICROSOFTWARE", "MICROSOFTWEAR") > 0.95);
+    }
+
+    #[test]
+    fn test_token_ratios() {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.001;
+        assert!(close(similarity_ratio("NIKE", "NYKE"), 0.75));
+        assert!(close(similarity_ratio("", ""), 1.0));
+
+        assert!(close(token_sort_ratio("BLUE SKY", "SKY BLUE"), 1.0));
+        assert!(close(token_sort_ratio("BLUE SKY BREWING", "SKY BLUE BREWERY"), 0.8125));
+        assert!(similarity_ratio("BLUE SKY BREWING", "SKY BLUE BREWERY") < 0.5);
+
+        assert!(close(token_set_ratio("BLUE SKY", "SKY BLUE BREWING CO"), 1.0));
+        assert!(close(token_set_ratio("BLUE SKY", "SKY BLUE"), 1.0));
+        assert!(token_set_ratio("RED APPLE", "GREEN PEAR") < 0.5);
+        assert!(close(token_set_ratio("", ""), 1.0));
     }
 
     #[test]
@@ -161,16 +635,82 @@ mod tests {
         assert_eq!(ngrams, vec!["NI", "IK", "KE"]);
     }
 
+    #[test]
+    fn test_ngram_similarity() {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.001;
+
+        let same = ngram_similarity("COCACOLA", "COCA COLA", 3);
+        assert!(close(same.jaccard, 1.0) && close(same.cosine, 1.0));
+
+        // NI IK KE vs NY YK KE: one shared bigram of five
+        let found = ngram_similarity("NIKE", "NYKE", 2);
+        assert!(close(found.jaccard, 0.2));
+        assert!(close(found.cosine, 1.0 / 3.0));
+
+        // Repeated grams count toward cosine but not Jaccard
+        let found = ngram_similarity("AAAA", "AA", 2);
+        assert!(close(found.jaccard, 1.0) && close(found.cosine, 1.0));
+
+        assert!(close(ngram_similarity("NIKE", "ADIDAS", 3).cosine, 0.0));
+        assert!(close(ngram_similarity("", "NIKE", 3).jaccard, 0.0));
+        assert!(close(ngram_similarity("AB", "AB", 3).jaccard, 1.0));
+    }
+
     #[test]
     fn test_edit_distance() {
         assert_eq!(edit_distance("NIKE", "NIKE"), 0);
         assert_eq!(edit_distance("NIKE", "NYKE"), 1);
-        assert_eq!(edit_distance("NIKE", "ADIDAS"), 6);
+        assert_eq!(edit_distance("NIKE", "ADIDAS"), 5);
+        assert_eq!(edit_distance("", "NIKE"), 4);
+    }
+
+    #[test]
+    fn test_edit_distance_bounded() {
+        assert_eq!(edit_distance_bounded("NIKE", "NYKE", 1), Some(1));
+        assert_eq!(edit_distance_bounded("NIKE", "ADIDAS", 3), None);
+        assert_eq!(edit_distance_bounded("NIKE", "ADIDAS", 5), Some(5));
+        // Exits on the length difference alone
+        assert_eq!(edit_distance_bounded("NIKE", "NIKEBOOTS", 2), None);
+        assert_eq!(edit_distance_bounded("", "", 0), Some(0));
+        for (a, b) in [("KITTEN", "SITTING"), ("MICROSOFT", "MIKROSOFT"), ("NYKE", "")] {
+            assert_eq!(edit_distance_bounded(a, b, usize::MAX), Some(edit_distance(a, b)));
+        }
+    }
+
+    #[test]
+    fn test_prefix_weighted_similarity() {
+        assert_eq!(common_prefix_len("MICROSOFT", "MICROSOAP"), 7);
+        assert_eq!(common_prefix_len("NIKE", "BIKE"), 0);
+
+        // A differing first letter costs more than a differing last one
+        let front = positional_edit_distance("NIKE", "BIKE", 0.5);
+        let back = positional_edit_distance("NIKE", "NIKA", 0.5);
+        assert!((front - 2.0).abs() < 1e-6);
+        assert!((back - 1.125).abs() < 1e-6);
+        assert_eq!(positional_edit_distance("NIKE", "NIKE", 0.5), 0.0);
+
+        assert_eq!(prefix_weighted_similarity("NIKE", "NIKE", 0.5), 1.0);
+        assert!(
+            prefix_weighted_similarity("NIKE", "NIKA", 0.5)
+                > prefix_weighted_similarity("NIKE", "BIKE", 0.5)
+        );
+        assert_eq!(prefix_weighted_similarity("", "", 0.5), 1.0);
     }
 
     #[test]
     fn test_class_overlap() {
-        assert_eq!(class_overlap(&[9, 25, 42], &[25, 35, 42]), vec![25, 42]);
-        assert_eq!(class_overlap(&[1, 2], &[3, 4]), Vec::<u16>::new());
+        let overlap = |a: &[u16], b: &[u16]| {
+            class_overlap(&NiceClass::of_all(a), &NiceClass::of_all(b))
+        };
+        assert_eq!(overlap(&[9, 25, 42], &[25, 35, 42]), vec![25, 42]);
+        assert_eq!(overlap(&[1, 2], &[3, 4]), Vec::<u16>::new());
+    }
+
+    #[test]
+    fn test_design_code_overlap() {
+        let query = vec!["26.01".to_string(), "030101".to_string()];
+        let record = vec!["260103".to_string(), "26.11.01".to_string(), "03.01.01".to_string()];
+        assert_eq!(design_code_overlap(&query, &record), vec!["03.01.01", "26.01.03"]);
+        assert!(design_code_overlap(&query, &[]).is_empty());
     }
 }
@@ -0,0 +1,204 @@
+//! Number-word equivalence.
+//!
+//! Marks often swap a numeral for the word it sounds like (4EVER for
+//! FOREVER, 2GO for TO GO) or the other way round. Each mark is expanded
+//! into variants with one numeral/word substitution applied, in either
+//! direction, and marks are compared across their variants. Spacing is
+//! ignored, since a numeral usually stands in for a separate word.
+//!
+//! A number word is only replaced at the start or end of a word (FOREVER,
+//! not HONEY), and a match must be closer than the marks are without
+//! substitution, so look-alikes such as HONEY/MONEY are left to the
+//! spelling rules.
+
+use crate::{edit_distance_bounded, normalize_text};
+
+/// Digits and the words they stand in for; the first word is the number
+/// itself.
+const NUMBER_WORDS: &[(char, &[&str])] = &[
+    ('0', &["ZERO"]),
+    ('1', &["ONE", "WON"]),
+    ('2', &["TWO", "TOO", "TO"]),
+    ('3', &["THREE"]),
+    ('4', &["FOUR", "FORE", "FOR"]),
+    ('5', &["FIVE"]),
+    ('6', &["SIX"]),
+    ('7', &["SEVEN"]),
+    ('8', &["EIGHT", "ATE"]),
+    ('9', &["NINE"]),
+];
+
+/// A spelling of a mark with numerals and number words swapped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberVariant {
+    /// Normalized text with spaces removed
+    pub text: String,
+    /// Substitutions applied, e.g. "4 -> FOR" (empty for the mark itself)
+    pub transformations: Vec<String>,
+}
+
+/// Two marks that match once numerals and number words are swapped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberWordMatch {
+    /// Edit distance between the closest variants (0 = equivalent)
+    pub distance: usize,
+    /// Substitutions applied to either mark
+    pub transformations: Vec<String>,
+}
+
+/// Expand a mark into its numeral/word variants.
+///
+/// The first variant is the mark itself. Each further variant applies
+/// one substitution to every occurrence: a digit spelled as one of its
+/// words (4EVER → FOREVER), or a number word at the start or end of a
+/// word written as its digit (TO GO → 2GO).
+pub fn number_word_variants(text: &str) -> Vec<NumberVariant> {
+    let normalized = normalize_text(text);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let compact = tokens.concat();
+    let mut variants = vec![NumberVariant {
+        text: compact.clone(),
+        transformations: Vec::new(),
+    }];
+    let mut push = |text: String, from: &str, to: &str| {
+        if !variants.iter().any(|v| v.text == text) {
+            variants.push(NumberVariant {
+                text,
+                transformations: vec![format!("{} -> {}", from, to)],
+            });
+        }
+    };
+
+    for (digit, words) in NUMBER_WORDS {
+        let digit = digit.to_string();
+        for word in *words {
+            if compact.contains(digit.as_str()) {
+                push(compact.replace(digit.as_str(), word), &digit, word);
+            }
+            if let Some(text) = replace_at_boundaries(&tokens, word, &digit) {
+                push(text, word, &digit);
+            }
+        }
+    }
+    variants
+}
+
+/// Replace `from` where it starts or ends a word, returning the compact
+/// text if anything was replaced.
+fn replace_at_boundaries(words: &[&str], from: &str, to: &str) -> Option<String> {
+    let mut replaced = false;
+    let text = words
+        .iter()
+        .map(|word| {
+            if let Some(rest) = word.strip_prefix(from) {
+                replaced = true;
+                format!("{}{}", to, rest)
+            } else if let Some(head) = word.strip_suffix(from) {
+                replaced = true;
+                format!("{}{}", head, to)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect();
+    replaced.then_some(text)
+}
+
+/// Closest match between two marks' variants within `max_distance`, if
+/// it needs at least one substitution and is closer than the marks
+/// themselves.
+pub fn match_number_variants(
+    a: &[NumberVariant],
+    b: &[NumberVariant],
+    max_distance: usize,
+) -> Option<NumberWordMatch> {
+    let mut best: Option<(usize, &NumberVariant, &NumberVariant)> = None;
+    for va in a {
+        for vb in b {
+            if va.transformations.is_empty() && vb.transformations.is_empty() {
+                continue;
+            }
+            let Some(distance) = edit_distance_bounded(&va.text, &vb.text, max_distance) else {
+                continue;
+            };
+            if best.is_none_or(|(d, _, _)| distance < d) {
+                best = Some((distance, va, vb));
+            }
+        }
+    }
+
+    let (distance, va, vb) = best?;
+    if edit_distance_bounded(&a[0].text, &b[0].text, max_distance).is_some_and(|d| d <= distance) {
+        return None;
+    }
+    let mut transformations = va.transformations.clone();
+    for transformation in &vb.transformations {
+        if !transformations.contains(transformation) {
+            transformations.push(transformation.clone());
+        }
+    }
+    Some(NumberWordMatch {
+        distance,
+        transformations,
+    })
+}
+
+/// Match two marks across numeral/word substitutions; marks that already
+/// match without one are not reported.
+pub fn number_word_match(a: &str, b: &str, max_distance: usize) -> Option<NumberWordMatch> {
+    let a = number_word_variants(a);
+    let b = number_word_variants(b);
+    if a[0].text == b[0].text {
+        return None;
+    }
+    match_number_variants(&a, &b, max_distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_word_variants() {
+        let texts: Vec<String> = number_word_variants("4 Ever")
+            .into_iter()
+            .map(|v| v.text)
+            .collect();
+        assert_eq!(texts, vec!["4EVER", "FOUREVER", "FOREEVER", "FOREVER"]);
+
+        let variants = number_word_variants("TO GO");
+        assert!(variants
+            .iter()
+            .any(|v| v.text == "2GO" && v.transformations == vec!["TO -> 2"]));
+        assert_eq!(number_word_variants("NIKE").len(), 1);
+    }
+
+    #[test]
+    fn test_number_word_match() {
+        let found = number_word_match("4EVER", "FOREVER", 0).unwrap();
+        assert_eq!(found.distance, 0);
+        assert_eq!(found.transformations, vec!["FOR -> 4"]);
+
+        assert_eq!(number_word_match("2GO", "TO GO", 0).unwrap().distance, 0);
+        assert_eq!(number_word_match("GR8 TASTE", "GREAT TASTE", 0), None);
+        assert_eq!(
+            number_word_match("2 GO CAFE", "TO GO CAFES", 1)
+                .unwrap()
+                .distance,
+            1
+        );
+        // Spacing alone is not a number-word match
+        assert_eq!(number_word_match("TO GO", "TOGO", 1), None);
+
+        // Number words inside a word, or substitutions that bring the
+        // marks no closer, are not matches
+        for (a, b) in [
+            ("STONE", "SPINE"),
+            ("HONEY", "MONEY"),
+            ("TOYOTA", "TOYOTAS"),
+            ("ATELIER", "ATELIERS"),
+        ] {
+            assert_eq!(number_word_match(a, b, 2), None, "{} / {}", a, b);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Phoneme-sequence edit distance.
+//!
+//! Phonetic codes match all or nothing: NIKE and NIKEY either share a
+//! code or they do not. Here marks are transcribed into IPA-like phoneme
+//! sequences by English spelling rules (PH → f, magic E, soft C and G,
+//! silent letters) and compared by edit distance over phonemes. Swapping
+//! a phoneme for a near one (voiced for voiceless, one vowel for another)
+//! costs half an edit, so near-homophones score as near matches.
+
+/// Cost of substituting a near phoneme.
+const NEAR_COST: f32 = 0.5;
+
+/// Phoneme pairs that sound alike.
+#[rustfmt::skip]
+const NEAR_PAIRS: &[(&str, &str)] = &[
+    ("p", "b"), ("t", "d"), ("k", "g"), ("f", "v"), ("s", "z"), ("θ", "ð"), ("θ", "f"),
+    ("ʃ", "tʃ"), ("tʃ", "dʒ"), ("s", "ʃ"), ("m", "n"), ("n", "ŋ"), ("l", "r"), ("w", "v"),
+];
+
+/// Spellings read as one unit, longest first within each length.
+#[rustfmt::skip]
+const GRAPHEMES: &[(&str, &[&str])] = &[
+    ("TCH", &["tʃ"]), ("SCH", &["s", "k"]), ("IGH", &["aɪ"]),
+    ("PH", &["f"]), ("TH", &["θ"]), ("SH", &["ʃ"]), ("CH", &["tʃ"]), ("CK", &["k"]),
+    ("QU", &["k", "w"]), ("WH", &["w"]), ("NG", &["ŋ"]), ("DG", &["dʒ"]),
+    ("EE", &["i"]), ("EA", &["i"]), ("EY", &["eɪ"]), ("OO", &["u"]), ("OU", &["aʊ"]),
+    ("OW", &["aʊ"]), ("AI", &["eɪ"]), ("AY", &["eɪ"]), ("EI", &["eɪ"]), ("OA", &["oʊ"]),
+    ("OE", &["oʊ"]), ("AU", &["ɔ"]), ("AW", &["ɔ"]), ("OI", &["ɔɪ"]), ("OY", &["ɔɪ"]),
+    ("UE", &["u"]), ("EW", &["u"]), ("IE", &["aɪ"]),
+];
+
+fn is_vowel_letter(c: u8) -> bool {
+    matches!(c, b'A' | b'E' | b'I' | b'O' | b'U' | b'Y')
+}
+
+fn is_vowel_phoneme(p: &str) -> bool {
+    p.starts_with(['a', 'e', 'i', 'o', 'u', 'æ', 'ɛ', 'ɪ', 'ɒ', 'ʌ', 'ɔ', 'ə'])
+}
+
+/// Transcribe one uppercase ASCII word.
+fn transcribe_word(word: &[u8], out: &mut Vec<&'static str>) {
+    let n = word.len();
+    let mut i = 0;
+    // Silent initial letters (KNIGHT, WRITE, PSALM, GNOME)
+    if n > 2 && matches!(&word[..2], b"KN" | b"WR" | b"PS" | b"GN") {
+        i = 1;
+    }
+    // Final E after a consonant is silent and lengthens the vowel before
+    // it (NIKE); its position, if any
+    let magic_e = (n >= 3
+        && word[n - 1] == b'E'
+        && !is_vowel_letter(word[n - 2])
+        && is_vowel_letter(word[n - 3]))
+    .then(|| n - 3);
+
+    while i < n {
+        if magic_e.is_some_and(|v| i == v) {
+            out.push(match word[i] {
+                b'A' => "eɪ",
+                b'E' => "i",
+                b'I' | b'Y' => "aɪ",
+                b'O' => "oʊ",
+                _ => "u",
+            });
+            i += 1;
+            continue;
+        }
+        if i == n - 1 && word[i] == b'E' && n > 2 {
+            break;
+        }
+
+        if let Some((grapheme, phonemes)) = GRAPHEMES
+            .iter()
+            .find(|(g, _)| word[i..].starts_with(g.as_bytes()))
+        {
+            out.extend_from_slice(phonemes);
+            i += grapheme.len();
+            continue;
+        }
+
+        let c = word[i];
+        let next = word.get(i + 1).copied();
+        let soft = next.is_some_and(|n| matches!(n, b'E' | b'I' | b'Y'));
+        // Doubled consonants sound once (APPLE)
+        if i > 0 && word[i - 1] == c && !is_vowel_letter(c) {
+            i += 1;
+            continue;
+        }
+        let last = i == n - 1;
+        match c {
+            b'A' => out.push(if last { "ə" } else { "æ" }),
+            b'E' => out.push("ɛ"),
+            b'I' => out.push(if last { "i" } else { "ɪ" }),
+            b'O' => out.push(if last { "oʊ" } else { "ɒ" }),
+            b'U' => out.push("ʌ"),
+            b'Y' if i == 0 && next.is_some_and(is_vowel_letter) => out.push("j"),
+            b'Y' => out.push(if last { "i" } else { "ɪ" }),
+            b'C' => out.push(if soft { "s" } else { "k" }),
+            b'G' => out.push(if soft { "dʒ" } else { "g" }),
+            // H sounds only before a vowel
+            b'H' if next.is_some_and(is_vowel_letter) => out.push("h"),
+            b'H' => {}
+            b'W' if next.is_some_and(is_vowel_letter) => out.push("w"),
+            b'W' => {}
+            b'X' if i == 0 => out.push("z"),
+            b'X' => out.extend_from_slice(&["k", "s"]),
+            b'B' => out.push("b"),
+            b'D' => out.push("d"),
+            b'F' => out.push("f"),
+            b'J' => out.push("dʒ"),
+            b'K' | b'Q' => out.push("k"),
+            b'L' => out.push("l"),
+            b'M' => out.push("m"),
+            b'N' => out.push("n"),
+            b'P' => out.push("p"),
+            b'R' => out.push("r"),
+            b'S' => out.push("s"),
+            b'T' => out.push("t"),
+            b'V' => out.push("v"),
+            b'Z' => out.push("z"),
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// Transcribe a mark into phonemes; non-letters are skipped.
+pub fn phonemes(text: &str) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    for word in text.split(|c: char| !c.is_ascii_alphabetic()) {
+        if !word.is_empty() {
+            transcribe_word(word.to_ascii_uppercase().as_bytes(), &mut out);
+        }
+    }
+    out
+}
+
+/// Cost of substituting one phoneme for another.
+fn substitution_cost(a: &str, b: &str) -> f32 {
+    if a == b {
+        0.0
+    } else if (is_vowel_phoneme(a) && is_vowel_phoneme(b))
+        || NEAR_PAIRS
+            .iter()
+            .any(|&(x, y)| (x == a && y == b) || (x == b && y == a))
+    {
+        NEAR_COST
+    } else {
+        1.0
+    }
+}
+
+/// Edit distance between phoneme sequences; near phonemes substitute
+/// for half an edit.
+pub fn phoneme_distance(a: &[&str], b: &[&str]) -> f32 {
+    let mut prev: Vec<f32> = (0..=b.len()).map(|j| j as f32).collect();
+    let mut curr = vec![0.0; b.len() + 1];
+    for i in 1..=a.len() {
+        curr[0] = i as f32;
+        for j in 1..=b.len() {
+            curr[j] = (prev[j] + 1.0)
+                .min(curr[j - 1] + 1.0)
+                .min(prev[j - 1] + substitution_cost(a[i - 1], b[j - 1]));
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Phoneme distance and similarity between two marks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhonemeMatch {
+    /// Edit distance in phonemes
+    pub distance: f32,
+    /// One minus the distance over the longer sequence (0.0 - 1.0)
+    pub similarity: f32,
+    /// Transcription of the first mark
+    pub a: String,
+    /// Transcription of the second mark
+    pub b: String,
+}
+
+/// Compare two transcribed marks.
+pub fn compare_phonemes(a: &[&str], b: &[&str]) -> PhonemeMatch {
+    let distance = phoneme_distance(a, b);
+    let max_len = a.len().max(b.len());
+    PhonemeMatch {
+        distance,
+        similarity: if max_len == 0 {
+            1.0
+        } else {
+            1.0 - distance / max_len as f32
+        },
+        a: a.concat(),
+        b: b.concat(),
+    }
+}
+
+/// Compare two marks by their phoneme sequences.
+pub fn phoneme_match(a: &str, b: &str) -> PhonemeMatch {
+    compare_phonemes(&phonemes(a), &phonemes(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phonemes() {
+        assert_eq!(phonemes("NIKE"), vec!["n", "aɪ", "k"]);
+        assert_eq!(phonemes("NYKE"), phonemes("NIKE"));
+        assert_eq!(phonemes("PHONE"), vec!["f", "oʊ", "n"]);
+        assert_eq!(phonemes("FONE"), phonemes("PHONE"));
+        assert_eq!(phonemes("CITY"), vec!["s", "ɪ", "t", "i"]);
+        assert_eq!(phonemes("KNIGHT"), vec!["n", "aɪ", "t"]);
+        assert_eq!(phonemes("APPLE"), vec!["æ", "p", "l"]);
+        assert_eq!(phonemes("GO"), vec!["g", "oʊ"]);
+        assert!(phonemes("123").is_empty());
+    }
+
+    #[test]
+    fn test_phoneme_distance() {
+        let found = phoneme_match("ZAPPOS", "SAPPOS");
+        assert_eq!(found.distance, 0.5);
+        assert_eq!(found.similarity, 0.9);
+
+        let found = phoneme_match("NIKE", "NIKEY");
+        assert!(found.distance > 0.0 && found.distance <= 2.0);
+
+        assert_eq!(phoneme_match("PHONE", "FONE").distance, 0.0);
+        assert!(phoneme_match("NIKE", "ADIDAS").distance > 2.0);
+        assert_eq!(phoneme_match("", "").similarity, 1.0);
+    }
+}
@@ -0,0 +1,505 @@
+//! Phonetic encoders.
+//!
+//! Two marks sound alike under an encoder when they share a code. The
+//! built-in encoders cover Soundex, Metaphone, Double Metaphone, NYSIIS,
+//! Caverphone 2 and Kölner Phonetik (Cologne, for German-origin marks).
+//! A [`PhoneticRegistry`] selects which encoders take part in matching;
+//! custom encoders plug in through the [`PhoneticEncoder`] trait.
+//! The built-in encoders are constructed once, in a shared
+//! [`PhoneticEncoderSet`], rather than on every call.
+
+use ilegalflow_model::{CodeVariant, MatchedVariant};
+use rphonetic::{Caverphone2, Cologne, DoubleMetaphone, Encoder, Metaphone, Nysiis, Soundex};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+/// A phonetic encoding algorithm.
+pub trait PhoneticEncoder: Send + Sync {
+    /// Algorithm name reported in matches (e.g. "soundex")
+    fn name(&self) -> &str;
+
+    /// Encode a text. The primary code comes first, followed by any
+    /// alternate code; empty when the text has nothing to encode.
+    fn encode(&self, text: &str) -> Vec<String>;
+
+    /// Whether the encoder produces alternate codes, in which case
+    /// matches record which variants matched.
+    fn has_alternates(&self) -> bool {
+        false
+    }
+}
+
+/// Built-in phonetic algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhoneticAlgorithm {
+    Soundex,
+    Metaphone,
+    DoubleMetaphone,
+    Nysiis,
+    Caverphone2,
+    Cologne,
+}
+
+impl PhoneticAlgorithm {
+    /// Every built-in algorithm.
+    pub const ALL: [PhoneticAlgorithm; 6] = [
+        Self::Soundex,
+        Self::Metaphone,
+        Self::DoubleMetaphone,
+        Self::Nysiis,
+        Self::Caverphone2,
+        Self::Cologne,
+    ];
+
+    /// Algorithms used by default, in match order.
+    pub const DEFAULT: [PhoneticAlgorithm; 3] =
+        [Self::Soundex, Self::Metaphone, Self::DoubleMetaphone];
+
+    /// Algorithm name, as used in config and match results.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Soundex => "soundex",
+            Self::Metaphone => "metaphone",
+            Self::DoubleMetaphone => "double_metaphone",
+            Self::Nysiis => "nysiis",
+            Self::Caverphone2 => "caverphone2",
+            Self::Cologne => "cologne",
+        }
+    }
+}
+
+impl fmt::Display for PhoneticAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PhoneticAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|a| a.as_str()).collect();
+                format!("Unknown phonetic algorithm '{}' ({})", s, names.join(", "))
+            })
+    }
+}
+
+impl PhoneticEncoder for PhoneticAlgorithm {
+    fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    fn encode(&self, text: &str) -> Vec<String> {
+        PhoneticEncoderSet::shared().encode(*self, text)
+    }
+
+    fn has_alternates(&self) -> bool {
+        matches!(self, Self::DoubleMetaphone)
+    }
+}
+
+/// Phonetic encoding results for a mark.
+#[derive(Debug, Clone, Default)]
+pub struct PhoneticCodes {
+    pub soundex: Option<String>,
+    pub metaphone: Option<String>,
+    /// Double Metaphone primary code
+    pub double_metaphone: Option<String>,
+    /// Double Metaphone alternate code (alternate pronunciation)
+    pub double_metaphone_alt: Option<String>,
+    pub nysiis: Option<String>,
+    pub caverphone2: Option<String>,
+    /// Kölner Phonetik code
+    pub cologne: Option<String>,
+}
+
+/// A phonetic match between two texts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhoneticMatchResult {
+    /// Algorithm that matched (e.g. "soundex")
+    pub algorithm: String,
+    /// The shared code
+    pub code: String,
+    /// Which codes matched, for algorithms with code variants
+    pub variant: Option<MatchedVariant>,
+}
+
+/// The built-in encoders, constructed once and reused for every text.
+#[derive(Debug, Clone, Default)]
+pub struct PhoneticEncoderSet {
+    soundex: Soundex,
+    metaphone: Metaphone,
+    double_metaphone: DoubleMetaphone,
+    nysiis: Nysiis,
+}
+
+impl PhoneticEncoderSet {
+    /// The process-wide set, built on first use.
+    pub fn shared() -> &'static Self {
+        static SHARED: OnceLock<PhoneticEncoderSet> = OnceLock::new();
+        SHARED.get_or_init(Self::default)
+    }
+
+    /// Encode a text under one algorithm; see [`PhoneticEncoder::encode`].
+    pub fn encode(&self, algorithm: PhoneticAlgorithm, text: &str) -> Vec<String> {
+        let codes = match algorithm {
+            // rphonetic's Soundex panics on non-ASCII letters
+            PhoneticAlgorithm::Soundex => {
+                let ascii: String = text.chars().filter(char::is_ascii).collect();
+                vec![self.soundex.encode(&ascii)]
+            }
+            PhoneticAlgorithm::Metaphone => vec![self.metaphone.encode(text)],
+            PhoneticAlgorithm::DoubleMetaphone => {
+                let result = self.double_metaphone.double_metaphone(text);
+                vec![result.primary(), result.alternate()]
+            }
+            PhoneticAlgorithm::Nysiis => vec![self.nysiis.encode(text)],
+            // Caverphone pads codes with '1'; all padding means no code
+            PhoneticAlgorithm::Caverphone2 => {
+                let code = Caverphone2.encode(text);
+                vec![if code.bytes().all(|b| b == b'1') {
+                    String::new()
+                } else {
+                    code
+                }]
+            }
+            PhoneticAlgorithm::Cologne => vec![Cologne.encode(text)],
+        };
+
+        // An encoder without a primary code has nothing to offer
+        if codes.first().is_none_or(String::is_empty) {
+            return Vec::new();
+        }
+        codes.into_iter().filter(|code| !code.is_empty()).collect()
+    }
+
+    /// Compute every built-in encoding of a text.
+    pub fn codes(&self, text: &str) -> PhoneticCodes {
+        let primary = |algorithm| self.encode(algorithm, text).into_iter().next();
+        let double_metaphone = self.encode(PhoneticAlgorithm::DoubleMetaphone, text);
+
+        PhoneticCodes {
+            soundex: primary(PhoneticAlgorithm::Soundex),
+            metaphone: primary(PhoneticAlgorithm::Metaphone),
+            double_metaphone: double_metaphone.first().cloned(),
+            double_metaphone_alt: double_metaphone.get(1).cloned(),
+            nysiis: primary(PhoneticAlgorithm::Nysiis),
+            caverphone2: primary(PhoneticAlgorithm::Caverphone2),
+            cologne: primary(PhoneticAlgorithm::Cologne),
+        }
+    }
+
+    /// Compute every built-in encoding of each text, in order.
+    pub fn codes_batch<'a>(&self, texts: impl IntoIterator<Item = &'a str>) -> Vec<PhoneticCodes> {
+        texts.into_iter().map(|text| self.codes(text)).collect()
+    }
+}
+
+/// Compute every built-in phonetic encoding for a mark text.
+pub fn compute_phonetics(text: &str) -> PhoneticCodes {
+    PhoneticEncoderSet::shared().codes(text)
+}
+
+/// Check if two texts are phonetically similar using the default
+/// encoders (Soundex, Metaphone, Double Metaphone).
+pub fn phonetic_match(text1: &str, text2: &str) -> Option<PhoneticMatchResult> {
+    first_match(
+        PhoneticAlgorithm::DEFAULT
+            .iter()
+            .map(|algorithm| algorithm as &dyn PhoneticEncoder),
+        text1,
+        text2,
+    )
+}
+
+/// Try each encoder in order and return the first shared code.
+///
+/// For encoders with alternate codes every pairing is tried, primary
+/// pairs first.
+fn first_match<'a>(
+    encoders: impl IntoIterator<Item = &'a dyn PhoneticEncoder>,
+    text1: &str,
+    text2: &str,
+) -> Option<PhoneticMatchResult> {
+    encoders
+        .into_iter()
+        .find_map(|encoder| match_codes(encoder, &encoder.encode(text1), text2))
+}
+
+/// Match the first text's codes against the second text under one
+/// encoder.
+fn match_codes(
+    encoder: &dyn PhoneticEncoder,
+    codes1: &[String],
+    text2: &str,
+) -> Option<PhoneticMatchResult> {
+    const VARIANTS: [CodeVariant; 2] = [CodeVariant::Primary, CodeVariant::Alternate];
+
+    let codes2 = encoder.encode(text2);
+    for (query, c1) in VARIANTS.into_iter().zip(codes1) {
+        for (mark, c2) in VARIANTS.into_iter().zip(&codes2) {
+            if c1 == c2 {
+                return Some(PhoneticMatchResult {
+                    algorithm: encoder.name().to_string(),
+                    code: c1.clone(),
+                    variant: encoder
+                        .has_alternates()
+                        .then_some(MatchedVariant { query, mark }),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// A text's codes under each encoder of a registry, in registry order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhoneticEncoding {
+    codes: Vec<Vec<String>>,
+}
+
+/// The set of encoders that participate in phonetic matching.
+///
+/// Encoders are tried in registration order, so cheaper or more
+/// specific encoders should come first.
+#[derive(Clone)]
+pub struct PhoneticRegistry {
+    encoders: Vec<Arc<dyn PhoneticEncoder>>,
+}
+
+impl Default for PhoneticRegistry {
+    fn default() -> Self {
+        Self::from_algorithms(&PhoneticAlgorithm::DEFAULT)
+    }
+}
+
+impl fmt::Debug for PhoneticRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// Serialized as the encoder names, in match order.
+impl Serialize for PhoneticRegistry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.names().serialize(serializer)
+    }
+}
+
+/// Deserialized from built-in algorithm names; custom encoders are
+/// registered in code.
+impl<'de> Deserialize<'de> for PhoneticRegistry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let algorithms = Vec::<PhoneticAlgorithm>::deserialize(deserializer)?;
+        Ok(Self::from_algorithms(&algorithms))
+    }
+}
+
+impl PhoneticRegistry {
+    /// Create a registry with no encoders (matches nothing).
+    pub fn empty() -> Self {
+        Self {
+            encoders: Vec::new(),
+        }
+    }
+
+    /// Create a registry from built-in algorithms.
+    pub fn from_algorithms(algorithms: &[PhoneticAlgorithm]) -> Self {
+        algorithms
+            .iter()
+            .fold(Self::empty(), |registry, &algorithm| {
+                registry.with_encoder(algorithm)
+            })
+    }
+
+    /// Create a registry with every built-in algorithm.
+    pub fn all() -> Self {
+        Self::from_algorithms(&PhoneticAlgorithm::ALL)
+    }
+
+    /// Add an encoder, replacing any existing encoder with the same name.
+    pub fn with_encoder(mut self, encoder: impl PhoneticEncoder + 'static) -> Self {
+        let encoder: Arc<dyn PhoneticEncoder> = Arc::new(encoder);
+        match self
+            .encoders
+            .iter()
+            .position(|e| e.name() == encoder.name())
+        {
+            Some(i) => self.encoders[i] = encoder,
+            None => self.encoders.push(encoder),
+        }
+        self
+    }
+
+    /// Names of the registered encoders, in match order.
+    pub fn names(&self) -> Vec<&str> {
+        self.encoders.iter().map(|encoder| encoder.name()).collect()
+    }
+
+    /// Check if two texts are phonetically similar under any registered
+    /// encoder.
+    pub fn phonetic_match(&self, text1: &str, text2: &str) -> Option<PhoneticMatchResult> {
+        first_match(self.encoders.iter().map(|encoder| &**encoder), text1, text2)
+    }
+
+    /// Encode a text under every registered encoder, for repeated
+    /// matching with `match_encoded`.
+    pub fn encode(&self, text: &str) -> PhoneticEncoding {
+        PhoneticEncoding {
+            codes: self.encoders.iter().map(|encoder| encoder.encode(text)).collect(),
+        }
+    }
+
+    /// Encode each text under every registered encoder, in order.
+    pub fn encode_batch<'a>(
+        &self,
+        texts: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<PhoneticEncoding> {
+        texts.into_iter().map(|text| self.encode(text)).collect()
+    }
+
+    /// Like `phonetic_match`, with the first text already encoded by
+    /// this registry.
+    pub fn match_encoded(
+        &self,
+        encoded: &PhoneticEncoding,
+        text: &str,
+    ) -> Option<PhoneticMatchResult> {
+        self.encoders
+            .iter()
+            .zip(&encoded.codes)
+            .find_map(|(encoder, codes)| match_codes(&**encoder, codes, text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phonetic_match() {
+        // These should match phonetically
+        assert!(phonetic_match("SMITH", "SMYTH").is_some());
+        assert!(phonetic_match("NIKE", "NYKE").is_some());
+    }
+
+    #[test]
+    fn test_match_encoded() {
+        let registry = PhoneticRegistry::all();
+        let encoded = registry.encode("ARNOFF");
+        for other in ["ARNOW", "SMITH", "ARNOFF"] {
+            assert_eq!(
+                registry.match_encoded(&encoded, other),
+                registry.phonetic_match("ARNOFF", other)
+            );
+        }
+    }
+
+    #[test]
+    fn test_double_metaphone_variants() {
+        let codes = compute_phonetics("SMITH");
+        assert_eq!(codes.double_metaphone.as_deref(), Some("SM0"));
+        assert_eq!(codes.double_metaphone_alt.as_deref(), Some("XMT"));
+
+        // Only the alternate pronunciation of ARNOW matches
+        let found = phonetic_match("ARNOFF", "ARNOW").unwrap();
+        assert_eq!(found.algorithm, "double_metaphone");
+        assert_eq!(found.code, "ARNF");
+        assert_eq!(
+            found.variant,
+            Some(MatchedVariant {
+                query: CodeVariant::Primary,
+                mark: CodeVariant::Alternate,
+            })
+        );
+
+        assert!(phonetic_match("NIKE", "ADIDAS").is_none());
+    }
+
+    #[test]
+    fn test_additional_encoders() {
+        let codes = compute_phonetics("MUELLER");
+        assert_eq!(codes.nysiis.as_deref(), Some("MALAR"));
+        assert_eq!(codes.caverphone2.as_deref(), Some("MLA1111111"));
+        assert_eq!(codes.cologne.as_deref(), Some("657"));
+
+        // Caverphone padding alone is not a code
+        assert_eq!(compute_phonetics("123").caverphone2, None);
+
+        // Umlaut and transcribed spellings encode alike
+        let cologne = PhoneticRegistry::from_algorithms(&[PhoneticAlgorithm::Cologne]);
+        let found = cologne.phonetic_match("MÜLLER", "MUELLER").unwrap();
+        assert_eq!(found.algorithm, "cologne");
+        assert_eq!(found.code, "657");
+        assert_eq!(found.variant, None);
+    }
+
+    #[test]
+    fn test_encoder_set_batch() {
+        assert!(std::ptr::eq(PhoneticEncoderSet::shared(), PhoneticEncoderSet::shared()));
+
+        let texts = ["SMITH", "MUELLER", "123"];
+        let batch = PhoneticEncoderSet::default().codes_batch(texts);
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch[0].double_metaphone_alt, compute_phonetics("SMITH").double_metaphone_alt);
+        assert_eq!(batch[1].cologne, compute_phonetics("MUELLER").cologne);
+
+        let registry = PhoneticRegistry::all();
+        let encoded = registry.encode_batch(texts);
+        assert_eq!(encoded[1], registry.encode("MUELLER"));
+        assert!(registry.match_encoded(&encoded[0], "SMYTH").is_some());
+    }
+
+    #[test]
+    fn test_registry_selection() {
+        assert_eq!(
+            PhoneticRegistry::default().names(),
+            vec!["soundex", "metaphone", "double_metaphone"]
+        );
+        assert_eq!(PhoneticRegistry::all().names().len(), 6);
+        assert!(PhoneticRegistry::empty()
+            .phonetic_match("NIKE", "NYKE")
+            .is_none());
+
+        let registry = PhoneticRegistry::from_algorithms(&[PhoneticAlgorithm::Nysiis]);
+        assert_eq!(
+            registry
+                .phonetic_match("STEPHEN", "STEVEN")
+                .unwrap()
+                .algorithm,
+            "nysiis"
+        );
+
+        assert_eq!("Caverphone2".parse(), Ok(PhoneticAlgorithm::Caverphone2));
+        assert!("kolner".parse::<PhoneticAlgorithm>().is_err());
+    }
+
+    #[test]
+    fn test_custom_encoder() {
+        struct FirstLetter;
+
+        impl PhoneticEncoder for FirstLetter {
+            fn name(&self) -> &str {
+                "first_letter"
+            }
+
+            fn encode(&self, text: &str) -> Vec<String> {
+                text.chars().next().map(String::from).into_iter().collect()
+            }
+        }
+
+        let registry = PhoneticRegistry::empty().with_encoder(FirstLetter);
+        let found = registry.phonetic_match("NIKE", "NORTH").unwrap();
+        assert_eq!(found.algorithm, "first_letter");
+        assert_eq!(found.code, "N");
+    }
+}
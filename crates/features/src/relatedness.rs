@@ -0,0 +1,150 @@
+//! Graded relatedness of Nice classes.
+//!
+//! Class overlap only counts identical class numbers, but goods in one
+//! class and services in another often travel together: clothing (25)
+//! and clothing retail (35), software (9) and software services (42).
+//! Each related pair gets a grade from 0.0 (unrelated) to 1.0 (as close
+//! as the same class). The bundled grades follow the USPTO coordinated
+//! class guide; a tab-separated file can add pairs or override grades.
+
+use crate::read_tsv;
+use ilegalflow_model::NiceClass;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// Bundled related class pairs and their grades.
+#[rustfmt::skip]
+const RELATED_CLASSES: &[(u16, u16, f32)] = &[
+    // Goods and their retail services
+    (3, 35, 0.5), (5, 35, 0.4), (9, 35, 0.5), (14, 35, 0.5), (16, 35, 0.4), (18, 35, 0.6),
+    (20, 35, 0.4), (21, 35, 0.4), (25, 35, 0.6), (28, 35, 0.5), (29, 35, 0.4), (30, 35, 0.4),
+    // Apparel and accessories
+    (14, 25, 0.5), (18, 25, 0.7), (25, 28, 0.3),
+    // Software, telecom, and technology services
+    (9, 42, 0.8), (9, 38, 0.5), (9, 41, 0.4), (9, 28, 0.4), (38, 41, 0.5), (38, 42, 0.5),
+    // Food, beverages, and restaurant services
+    (29, 43, 0.5), (30, 43, 0.6), (31, 43, 0.3), (32, 43, 0.5), (33, 43, 0.6), (32, 33, 0.7),
+    (29, 30, 0.5),
+    // Health and beauty
+    (3, 5, 0.5), (3, 44, 0.5), (5, 44, 0.6), (10, 44, 0.6), (5, 10, 0.5),
+    // Publications, toys, and entertainment
+    (16, 41, 0.5), (28, 41, 0.4),
+    // Vehicles and their servicing/transport
+    (12, 37, 0.6), (12, 39, 0.3),
+    // Business and finance
+    (35, 36, 0.3),
+];
+
+/// Relatedness grades of class pairs; pairs are symmetric and a class
+/// is never related to itself (that is class overlap).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassRelatedness {
+    grades: HashMap<(NiceClass, NiceClass), f32>,
+}
+
+impl Default for ClassRelatedness {
+    /// The bundled grades.
+    fn default() -> Self {
+        let mut relatedness = Self::empty();
+        for &(a, b, grade) in RELATED_CLASSES {
+            relatedness.set(NiceClass::of(a), NiceClass::of(b), grade);
+        }
+        relatedness
+    }
+}
+
+/// A pair in canonical order.
+fn pair(a: NiceClass, b: NiceClass) -> (NiceClass, NiceClass) {
+    (a.min(b), a.max(b))
+}
+
+impl ClassRelatedness {
+    /// No related classes.
+    pub fn empty() -> Self {
+        Self {
+            grades: HashMap::new(),
+        }
+    }
+
+    /// Grade a pair of classes, clamped to 0.0 - 1.0; 0.0 removes it.
+    pub fn set(&mut self, a: NiceClass, b: NiceClass, grade: f32) {
+        if a == b {
+            return;
+        }
+        let grade = grade.clamp(0.0, 1.0);
+        if grade > 0.0 {
+            self.grades.insert(pair(a, b), grade);
+        } else {
+            self.grades.remove(&pair(a, b));
+        }
+    }
+
+    /// Add or override grades from a tab-separated file of `class`,
+    /// `class` and `grade` lines; blank lines and `#` comments are
+    /// skipped.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        read_tsv(path, |fields| {
+            let [a, b, grade] = fields else {
+                return Err("expected class, class, grade");
+            };
+            let (Ok(a), Ok(b)) = (a.parse(), b.parse()) else {
+                return Err("classes must be Nice classes");
+            };
+            let grade: f32 = grade
+                .parse()
+                .ok()
+                .filter(|g| (0.0..=1.0).contains(g))
+                .ok_or("grade must be between 0 and 1")?;
+            self.set(a, b, grade);
+            Ok(())
+        })
+    }
+
+    /// Grade of a pair of classes; 0.0 for unrelated or identical ones.
+    pub fn grade(&self, a: NiceClass, b: NiceClass) -> f32 {
+        self.grades.get(&pair(a, b)).copied().unwrap_or(0.0)
+    }
+
+    /// Related pairs across two class lists, `(first, second)` with
+    /// their grade, highest first.
+    pub fn related_pairs(
+        &self,
+        first: &[NiceClass],
+        second: &[NiceClass],
+    ) -> Vec<(NiceClass, NiceClass, f32)> {
+        let mut pairs: Vec<(NiceClass, NiceClass, f32)> = first
+            .iter()
+            .flat_map(|&a| second.iter().map(move |&b| (a, b)))
+            .map(|(a, b)| (a, b, self.grade(a, b)))
+            .filter(|&(_, _, grade)| grade > 0.0)
+            .collect();
+        pairs.sort_by(|x, y| y.2.total_cmp(&x.2).then((x.0, x.1).cmp(&(y.0, y.1))));
+        pairs.dedup();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grade(relatedness: &ClassRelatedness, a: u16, b: u16) -> f32 {
+        relatedness.grade(NiceClass::of(a), NiceClass::of(b))
+    }
+
+    #[test]
+    fn test_related_pairs() {
+        let relatedness = ClassRelatedness::default();
+        assert_eq!(grade(&relatedness, 42, 9), 0.8);
+        assert_eq!(grade(&relatedness, 25, 25), 0.0);
+        assert_eq!(grade(&relatedness, 25, 1), 0.0);
+
+        let pairs = relatedness
+            .related_pairs(&NiceClass::of_all(&[25, 9]), &NiceClass::of_all(&[35, 42]));
+        let expected = [(9, 42, 0.8), (25, 35, 0.6), (9, 35, 0.5)]
+            .map(|(a, b, grade)| (NiceClass::of(a), NiceClass::of(b), grade));
+        assert_eq!(pairs, expected);
+    }
+
+}
@@ -0,0 +1,156 @@
+//! Compound-mark decomposition.
+//!
+//! Marks often run words together (SUNPOWER for SUN POWER). A compound
+//! is split by dynamic programming into the fewest known words, drawing
+//! on a bundled dictionary of words common in marks plus the words of
+//! the mark it is compared with. Two marks match as compounds when their
+//! words agree once compounds are split, ignoring entity suffixes such as
+//! CO and INC.
+
+use crate::{normalize_text, DOMINANT_STOPWORDS};
+
+/// Shortest word a compound is split into.
+const MIN_PART_LEN: usize = 2;
+
+/// Words common in marks.
+#[rustfmt::skip]
+pub(crate) const DICTIONARY: &[&str] = &[
+    "AIR", "ALL", "AMERICA", "APPLE", "AQUA", "ARC", "ART", "AUTO", "BABY", "BACK", "BAY",
+    "BEAR", "BEE", "BELL", "BEST", "BIG", "BIRD", "BLACK", "BLUE", "BOOK", "BOX", "BRIGHT",
+    "BROOK", "BUILD", "CARE", "CASH", "CITY", "CLEAN", "CLEAR", "CLOUD", "COAST", "CODE",
+    "COOL", "CORE", "CRAFT", "CREEK", "CROWN", "DATA", "DAY", "DEEP", "DESIGN", "DIRECT",
+    "DOG", "DREAM", "DRIVE", "EAGLE", "EARTH", "EASY", "ECO", "EDGE", "EVER", "EYE", "FACE",
+    "FAST", "FIELD", "FIRE", "FIRST", "FISH", "FIT", "FLASH", "FLOW", "FLY", "FOOD", "FOOT",
+    "FOREST", "FOX", "FREE", "FRESH", "FUN", "GATE", "GEAR", "GLOBAL", "GLOW", "GO", "GOLD",
+    "GOOD", "GREEN", "GRID", "GROUP", "GUARD", "HAPPY", "HARBOR", "HEART", "HILL", "HOME",
+    "HOOK", "HOUSE", "HUB", "ICE", "INK", "IRON", "JET", "KEY", "KID", "KING", "LAB", "LAKE",
+    "LAND", "LIFE", "LIGHT", "LINE", "LINK", "LION", "LIVE", "LOCK", "LOGIC", "LOOP", "LOVE",
+    "MAGIC", "MAP", "MARK", "MASTER", "MAX", "MEDIA", "METRO", "MIND", "MOON", "MOTION",
+    "MOUNTAIN", "NATION", "NET", "NEW", "NEXT", "NIGHT", "NORTH", "NOVA", "OAK", "OCEAN",
+    "ONE", "OPEN", "PAD", "PATH", "PAY", "PEAK", "PET", "PINE", "PLAY", "PLUS", "POINT",
+    "POWER", "PRIME", "PRO", "PURE", "QUEST", "QUICK", "RAIN", "RED", "RIDGE", "RISE",
+    "RIVER", "ROCK", "ROOT", "ROSE", "RUN", "SAFE", "SALT", "SEA", "SHIELD", "SHIP", "SHOP",
+    "SIGN", "SILVER", "SKY", "SMART", "SNOW", "SOFT", "SOLAR", "SOUND", "SOURCE", "SPARK",
+    "SPEED", "SPORT", "SPRING", "STAR", "STONE", "STORM", "STREAM", "SUMMIT", "SUN", "SURE",
+    "SWIFT", "TECH", "TIME", "TOP", "TOWN", "TRACK", "TRAIL", "TREE", "TRUE", "TRUST",
+    "VALLEY", "VISION", "WALL", "WATER", "WAVE", "WAY", "WEB", "WELL", "WEST", "WHITE",
+    "WILD", "WIND", "WING", "WISE", "WOLF", "WOOD", "WORKS", "WORLD", "ZONE",
+];
+
+/// Two marks whose words agree once compounds are split.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompoundMatch {
+    /// Splits applied, e.g. "SUNPOWER -> SUN POWER"
+    pub splits: Vec<String>,
+}
+
+/// Split a normalized word into the fewest dictionary words, also
+/// accepting the words in `extra`; `None` unless it splits into two or
+/// more words.
+pub fn segment(word: &str, extra: &[&str]) -> Option<Vec<String>> {
+    if !word.is_ascii() {
+        return None;
+    }
+    let known = |part: &str| {
+        part.len() >= MIN_PART_LEN && (DICTIONARY.contains(&part) || extra.contains(&part))
+    };
+
+    // best[i]: fewest words covering word[..i], and where the last starts
+    let n = word.len();
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; n + 1];
+    best[0] = Some((0, 0));
+    for end in 1..=n {
+        best[end] = (0..end)
+            .filter_map(|start| {
+                let (count, _) = best[start]?;
+                known(&word[start..end]).then_some((count + 1, start))
+            })
+            .min_by_key(|(count, _)| *count);
+    }
+
+    let (count, _) = best[n]?;
+    if count < 2 {
+        return None;
+    }
+    let mut parts = Vec::with_capacity(count);
+    let mut end = n;
+    while end > 0 {
+        let (_, start) = best[end]?;
+        parts.push(word[start..end].to_string());
+        end = start;
+    }
+    parts.reverse();
+    Some(parts)
+}
+
+/// A mark's words without entity suffixes and connectives.
+fn content_words(text: &str) -> Vec<String> {
+    normalize_text(text)
+        .split_whitespace()
+        .filter(|w| !DOMINANT_STOPWORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Split the compounds of one mark using the other mark's words.
+fn expand(words: &[String], other: &[String], splits: &mut Vec<String>) -> Vec<String> {
+    let vocabulary: Vec<&str> = other.iter().map(String::as_str).collect();
+    let mut expanded = Vec::with_capacity(words.len());
+    for word in words {
+        if other.contains(word) {
+            expanded.push(word.clone());
+            continue;
+        }
+        match segment(word, &vocabulary) {
+            Some(parts) => {
+                splits.push(format!("{} -> {}", word, parts.join(" ")));
+                expanded.extend(parts);
+            }
+            None => expanded.push(word.clone()),
+        }
+    }
+    expanded
+}
+
+/// Whether two marks have the same words once compounds are split.
+pub fn compound_match(a: &str, b: &str) -> Option<CompoundMatch> {
+    let a = content_words(a);
+    let b = content_words(b);
+    if a.is_empty() || a == b {
+        return None;
+    }
+
+    let mut splits = Vec::new();
+    let a_expanded = expand(&a, &b, &mut splits);
+    let b_expanded = expand(&b, &a, &mut splits);
+    (!splits.is_empty() && a_expanded == b_expanded).then_some(CompoundMatch { splits })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment() {
+        assert_eq!(segment("SUNPOWER", &[]).unwrap(), vec!["SUN", "POWER"]);
+        assert_eq!(segment("BLUESKYWORKS", &[]).unwrap(), vec!["BLUE", "SKY", "WORKS"]);
+        // The other mark's words extend the dictionary
+        assert_eq!(segment("ZYLOTECH", &["ZYLO"]).unwrap(), vec!["ZYLO", "TECH"]);
+        assert_eq!(segment("ZYLOTECH", &[]), None);
+        // A single word is not a compound
+        assert_eq!(segment("POWER", &[]), None);
+    }
+
+    #[test]
+    fn test_compound_match() {
+        let found = compound_match("SUNPOWER", "Sun Power Co").unwrap();
+        assert_eq!(found.splits, vec!["SUNPOWER -> SUN POWER"]);
+
+        let found = compound_match("ZYLO TECHWORKS", "ZYLOTECH WORKS").unwrap();
+        assert_eq!(found.splits.len(), 2);
+
+        assert_eq!(compound_match("SUNPOWER", "SUN POWER SOLAR"), None);
+        assert_eq!(compound_match("SUN POWER", "SUN POWER INC"), None);
+        assert_eq!(compound_match("NIKE", "ADIDAS"), None);
+    }
+}
@@ -0,0 +1,145 @@
+//! Embedding-based semantic similarity (feature `embeddings`).
+//!
+//! Lexical measures miss related goods that share no words ("footwear"
+//! vs "sneakers"). Texts are compared by the cosine of their sentence
+//! embeddings instead. Embeddings come from a `TextEmbedder`; the
+//! built-in `PrecomputedEmbeddings` serves vectors computed offline, and
+//! a model-backed embedder can be plugged in through the same trait.
+
+use crate::normalize_text;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Maps text to an embedding vector.
+pub trait TextEmbedder: Send + Sync + fmt::Debug {
+    /// Embed a text, or `None` if it cannot be embedded.
+    fn embed(&self, text: &str) -> Option<Vec<f32>>;
+}
+
+/// Embeddings computed ahead of time, looked up by normalized text.
+#[derive(Debug, Clone, Default)]
+pub struct PrecomputedEmbeddings {
+    vectors: HashMap<String, Vec<f32>>,
+    dims: Option<usize>,
+}
+
+impl PrecomputedEmbeddings {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a text's embedding; every vector must have the same length.
+    pub fn insert(&mut self, text: &str, vector: Vec<f32>) -> Result<(), String> {
+        let dims = *self.dims.get_or_insert(vector.len());
+        if vector.len() != dims {
+            return Err(format!(
+                "Embedding for '{}' has {} dimensions, expected {}",
+                text,
+                vector.len(),
+                dims
+            ));
+        }
+        self.vectors
+            .insert(normalize_text(text).into_owned(), vector);
+        Ok(())
+    }
+
+    /// Load a JSON object mapping texts to vectors.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let raw: HashMap<String, Vec<f32>> = serde_json::from_reader(file)?;
+
+        let mut embeddings = Self::new();
+        for (text, vector) in raw {
+            embeddings
+                .insert(&text, vector)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        Ok(embeddings)
+    }
+
+    /// Number of embedded texts.
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    /// True if no texts are embedded.
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+}
+
+impl TextEmbedder for PrecomputedEmbeddings {
+    fn embed(&self, text: &str) -> Option<Vec<f32>> {
+        self.vectors.get(normalize_text(text).as_ref()).cloned()
+    }
+}
+
+/// Cosine similarity of two vectors, clamped to 0.0 - 1.0; 0.0 for
+/// mismatched lengths or zero vectors.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|y| y * y).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return 0.0;
+    }
+    (dot / norm).clamp(0.0, 1.0)
+}
+
+/// Semantic similarity of two texts, 0.0 - 1.0, or `None` if either
+/// cannot be embedded.
+pub fn semantic_similarity(embedder: &dyn TextEmbedder, a: &str, b: &str) -> Option<f32> {
+    Some(cosine_similarity(&embedder.embed(a)?, &embedder.embed(b)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn embeddings() -> PrecomputedEmbeddings {
+        let mut embeddings = PrecomputedEmbeddings::new();
+        embeddings.insert("Footwear", vec![0.9, 0.1, 0.0]).unwrap();
+        embeddings.insert("Sneakers", vec![0.8, 0.2, 0.1]).unwrap();
+        embeddings
+            .insert("Computer software", vec![0.0, 0.1, 0.9])
+            .unwrap();
+        embeddings
+    }
+
+    #[test]
+    fn test_semantic_similarity() {
+        let embeddings = embeddings();
+        let related = semantic_similarity(&embeddings, "footwear", "SNEAKERS").unwrap();
+        let unrelated = semantic_similarity(&embeddings, "footwear", "computer software").unwrap();
+        assert!(related > 0.95);
+        assert!(unrelated < 0.1);
+        assert_eq!(semantic_similarity(&embeddings, "footwear", "hats"), None);
+    }
+
+    #[test]
+    fn test_dimension_check() {
+        let mut embeddings = embeddings();
+        assert!(embeddings.insert("Hats", vec![1.0]).is_err());
+        assert_eq!(embeddings.len(), 3);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_load() {
+        let path =
+            std::env::temp_dir().join(format!("ilegalflow-embeddings-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"Footwear": [1, 0], "Sneakers": [1, 0.1]}"#).unwrap();
+        let embeddings = PrecomputedEmbeddings::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(embeddings.len(), 2);
+        assert!(embeddings.embed("FOOTWEAR").is_some());
+    }
+}
@@ -0,0 +1,99 @@
+//! Light stemming for marks and goods/services text.
+//!
+//! Folds plurals (WIDGETS → WIDGET, BATTERIES → BATTERY) and strips the
+//! -ING and -ER suffixes (BAKING, BAKERS → BAK), undoubling a final
+//! consonant (RUNNING → RUN). Stems are not always words; they only need
+//! to agree between the texts being compared.
+
+use crate::normalize_text;
+
+/// Shortest stem left after stripping a suffix.
+const MIN_STEM_LEN: usize = 3;
+
+/// Fold an uppercase English plural to its singular.
+pub(crate) fn singular(word: String) -> String {
+    let len = word.len();
+    if len <= 3 || !word.is_ascii() {
+        return word;
+    }
+    if let Some(stem) = word.strip_suffix("IES") {
+        return format!("{}Y", stem);
+    }
+    if ["SSES", "XES", "CHES", "SHES", "ZES"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        return word[..len - 2].to_string();
+    }
+    if word.ends_with('S')
+        && !["SS", "US", "IS"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix))
+    {
+        return word[..len - 1].to_string();
+    }
+    word
+}
+
+/// Strip a suffix if a stem with a vowel of at least `MIN_STEM_LEN`
+/// letters remains, undoubling its final consonant.
+fn strip_suffix<'a>(word: &'a str, suffix: &str) -> Option<&'a str> {
+    let stem = word.strip_suffix(suffix)?;
+    if stem.len() < MIN_STEM_LEN || !stem.contains(['A', 'E', 'I', 'O', 'U', 'Y']) {
+        return None;
+    }
+    let bytes = stem.as_bytes();
+    let last = bytes[bytes.len() - 1];
+    let doubled = last == bytes[bytes.len() - 2]
+        && !b"AEIOULSZ".contains(&last)
+        && stem.len() > MIN_STEM_LEN;
+    Some(if doubled { &stem[..stem.len() - 1] } else { stem })
+}
+
+/// Stem an uppercase word.
+pub fn stem(word: &str) -> String {
+    let word = singular(word.to_string());
+    if !word.is_ascii() {
+        return word;
+    }
+    match strip_suffix(&word, "ING").or_else(|| strip_suffix(&word, "ER")) {
+        Some(stem) => stem.to_string(),
+        None => word,
+    }
+}
+
+/// Normalize text and stem each word.
+pub fn stem_text(text: &str) -> String {
+    normalize_text(text)
+        .split_whitespace()
+        .map(stem)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(stem("WIDGETS"), "WIDGET");
+        assert_eq!(stem("BATTERIES"), "BATTERY");
+        assert_eq!(stem("BAKING"), "BAK");
+        assert_eq!(stem("BAKERS"), "BAK");
+        assert_eq!(stem("RUNNING"), "RUN");
+        assert_eq!(stem("RUNNER"), "RUN");
+        assert_eq!(stem("SELLING"), "SELL");
+        // Too short to strip
+        assert_eq!(stem("KING"), "KING");
+        assert_eq!(stem("RING"), "RING");
+        assert_eq!(stem("NIKE"), "NIKE");
+    }
+
+    #[test]
+    fn test_stem_text() {
+        assert_eq!(stem_text("Widgets"), stem_text("WIDGET"));
+        assert_eq!(stem_text("Running Shoes"), "RUN SHOE");
+        assert_eq!(stem_text(""), "");
+    }
+}
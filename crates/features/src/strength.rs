@@ -0,0 +1,309 @@
+//! Per-class strength of mark terms.
+//!
+//! A shared term matters only if it is distinctive for the goods: BREW is
+//! weak in class 32 (beer) but distinctive in class 9 (software). Terms
+//! are graded against a bundled dataset of generic and descriptive terms
+//! per Nice class, plus laudatory terms (BEST, PREMIUM) that are weak in
+//! every class. Unlisted terms are distinctive.
+//!
+//! Whole marks are placed on the distinctiveness spectrum, from generic
+//! to fanciful, by how weak their terms are, whether their distinctive
+//! words are known words or coinages, and whether a coinage embeds a
+//! term describing the goods (TECHNOVA for software is suggestive).
+
+use crate::segment::{segment, DICTIONARY};
+use crate::stem::stem;
+use crate::terms::TermFrequencies;
+use crate::{normalize_text, DOMINANT_STOPWORDS};
+use ilegalflow_model::NiceClass;
+
+/// Marks a word must appear in for corpus frequencies to count it as a
+/// known word rather than a coinage.
+const KNOWN_WORD_MARKS: u64 = 5;
+
+/// Shortest weak term recognized inside a coined word.
+const MIN_EMBEDDED_LEN: usize = 3;
+
+/// Generic and descriptive terms per class.
+#[rustfmt::skip]
+const CLASS_WEAK_TERMS: &[(u16, &[&str], &[&str])] = &[
+    // (class, generic, descriptive)
+    (3, &["COSMETICS", "SOAP", "PERFUME", "SHAMPOO"], &["GLOW", "SILK", "PURE", "FRESH", "BEAUTY", "SKIN", "SPA"]),
+    (5, &["PHARMA", "VITAMIN", "SUPPLEMENT", "MEDICINE"], &["HEALTH", "RELIEF", "CARE", "VITAL", "CURE"]),
+    (9, &["SOFTWARE", "APP", "COMPUTER", "DATA"], &["TECH", "DIGITAL", "SMART", "SOFT", "CYBER", "BYTE", "LOGIC", "NET"]),
+    (12, &["AUTO", "CAR", "VEHICLE", "BIKE"], &["MOTOR", "DRIVE", "SPEED", "WHEEL"]),
+    (14, &["JEWELRY", "WATCH", "RING"], &["GOLD", "DIAMOND", "SILVER", "GEM", "TIME"]),
+    (18, &["BAG", "LUGGAGE", "WALLET"], &["LEATHER", "CARRY", "TRAVEL", "PACK"]),
+    (25, &["APPAREL", "CLOTHING", "SHIRT", "SHOE", "WEAR"], &["THREAD", "STYLE", "FIT", "SPORT", "ACTIVE", "FASHION", "COMFORT"]),
+    (28, &["TOY", "GAME"], &["PLAY", "FUN", "KID", "SPORT"]),
+    (29, &["MEAT", "CHEESE", "DAIRY"], &["FARM", "FRESH", "NATURAL", "HARVEST"]),
+    (30, &["COFFEE", "TEA", "BREAD", "BAKERY", "CANDY"], &["ROAST", "BREW", "BEAN", "SWEET", "BAKE", "CRISP"]),
+    (32, &["BEER", "ALE", "LAGER", "SODA", "JUICE", "WATER"], &["BREW", "HOP", "CRAFT", "BREWING", "MALT", "FIZZ", "SPRING"]),
+    (33, &["WINE", "VODKA", "WHISKEY", "SPIRIT"], &["VINEYARD", "CELLAR", "RESERVE", "VINTAGE", "DISTILL", "BARREL"]),
+    (35, &["RETAIL", "STORE", "SHOP", "MARKET"], &["SOLUTION", "CONSULTING", "GROUP", "MEDIA", "BRAND"]),
+    (36, &["BANK", "INSURANCE", "FINANCE", "CAPITAL"], &["TRUST", "WEALTH", "INVEST", "FUND", "SECURE"]),
+    (41, &["EDUCATION", "ACADEMY", "SCHOOL"], &["LEARN", "TRAINING", "FITNESS", "ENTERTAINMENT"]),
+    (42, &["CLOUD", "SAAS", "SOFTWARE"], &["TECH", "DIGITAL", "LABS", "DATA", "CODE", "SYSTEMS"]),
+    (43, &["RESTAURANT", "CAFE", "GRILL", "BAR", "HOTEL", "KITCHEN"], &["DINER", "BISTRO", "EATS", "TASTE", "FLAVOR"]),
+    (44, &["CLINIC", "SALON", "SPA"], &["HEALTH", "CARE", "WELLNESS", "BEAUTY", "MEDICAL"]),
+];
+
+/// Laudatory terms, descriptive in every class.
+#[rustfmt::skip]
+const LAUDATORY: &[&str] = &[
+    "BEST", "PREMIUM", "QUALITY", "ULTIMATE", "SUPREME", "ORIGINAL", "DELUXE", "ELITE",
+    "CLASSIC", "PRIME", "FINEST", "SUPERIOR", "TOP",
+];
+
+/// How distinctive a term is for the goods in a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    /// Names the goods (BEER for beer)
+    Generic,
+    /// Describes or praises the goods (BREW for beer, PREMIUM)
+    Descriptive,
+    /// Neither; the term identifies the source
+    Distinctive,
+}
+
+impl Strength {
+    /// Share of a match's weight this strength carries.
+    pub fn weight(self) -> f32 {
+        match self {
+            Self::Generic => 0.25,
+            Self::Descriptive => 0.5,
+            Self::Distinctive => 1.0,
+        }
+    }
+}
+
+/// Strength of a normalized term in a class.
+pub fn term_strength(term: &str, class: NiceClass) -> Strength {
+    strength_in(term, Some(class))
+}
+
+/// Strength of a term in a class, or by the laudatory list alone.
+fn strength_in(term: &str, class: Option<NiceClass>) -> Strength {
+    let stemmed = stem(term);
+    let listed = |terms: &[&str]| terms.iter().any(|t| *t == term || stem(t) == stemmed);
+
+    let class_terms = class.and_then(|class| CLASS_WEAK_TERMS.iter().find(|(c, ..)| class == *c));
+    if let Some((_, generic, descriptive)) = class_terms {
+        if listed(generic) {
+            return Strength::Generic;
+        }
+        if listed(descriptive) {
+            return Strength::Descriptive;
+        }
+    }
+    if listed(LAUDATORY) {
+        Strength::Descriptive
+    } else {
+        Strength::Distinctive
+    }
+}
+
+/// Weakest strength of a term across classes; with no classes only the
+/// laudatory terms are weak.
+pub fn term_strength_in(term: &str, classes: &[NiceClass]) -> Strength {
+    if classes.is_empty() {
+        return strength_in(term, None);
+    }
+    classes
+        .iter()
+        .map(|class| term_strength(term, *class))
+        .min()
+        .unwrap_or(Strength::Distinctive)
+}
+
+/// Place on the distinctiveness spectrum, weakest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MarkStrength {
+    /// Names the goods (BEER for beer)
+    Generic,
+    /// Describes or praises the goods (PREMIUM BREW for beer)
+    Descriptive,
+    /// Hints at the goods (TECHNOVA for software)
+    Suggestive,
+    /// A known word unrelated to the goods (APPLE for computers)
+    Arbitrary,
+    /// A coined word (ZYLO)
+    Fanciful,
+}
+
+impl MarkStrength {
+    /// Share of a conflict's risk a senior mark of this strength carries.
+    pub fn weight(self) -> f32 {
+        match self {
+            Self::Generic => 0.5,
+            Self::Descriptive => 0.7,
+            Self::Suggestive => 0.9,
+            Self::Arbitrary | Self::Fanciful => 1.0,
+        }
+    }
+}
+
+/// Estimated strength of a mark and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthEstimate {
+    /// Place on the spectrum
+    pub strength: MarkStrength,
+    /// The word that decided it, if any
+    pub term: Option<String>,
+    /// Why the mark was placed there
+    pub reason: String,
+}
+
+/// Generic and descriptive terms of the given classes.
+fn weak_terms(classes: &[NiceClass]) -> impl Iterator<Item = &'static str> + '_ {
+    CLASS_WEAK_TERMS
+        .iter()
+        .filter(|(class, ..)| classes.iter().any(|c| c == class))
+        .flat_map(|(_, generic, descriptive)| generic.iter().chain(descriptive.iter()))
+        .copied()
+}
+
+/// Whether a normalized word is in one of the bundled word lists.
+fn is_listed_word(word: &str) -> bool {
+    let stemmed = stem(word);
+    let listed = |w: &&str| *w == word || stem(w) == stemmed;
+    DICTIONARY.iter().any(listed)
+        || LAUDATORY.iter().any(listed)
+        || CLASS_WEAK_TERMS
+            .iter()
+            .any(|(_, generic, descriptive)| generic.iter().chain(descriptive.iter()).any(listed))
+}
+
+/// Estimate a mark's strength for goods in `classes`.
+pub fn mark_strength(text: &str, classes: &[NiceClass]) -> StrengthEstimate {
+    mark_strength_with(text, classes, None)
+}
+
+/// Like `mark_strength`, also counting words common in the corpus as
+/// known words.
+pub fn mark_strength_with(
+    text: &str,
+    classes: &[NiceClass],
+    frequencies: Option<&TermFrequencies>,
+) -> StrengthEstimate {
+    let normalized = normalize_text(text);
+    let mut words: Vec<&str> = Vec::new();
+    for word in normalized.split_whitespace() {
+        if !DOMINANT_STOPWORDS.contains(&word) && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    let estimate = |strength, term: Option<&str>, reason: String| StrengthEstimate {
+        strength,
+        term: term.map(str::to_string),
+        reason,
+    };
+
+    let graded: Vec<(&str, Strength)> =
+        words.iter().map(|w| (*w, term_strength_in(w, classes))).collect();
+    let Some(&(weakest, _)) = graded.iter().min_by_key(|(_, s)| *s) else {
+        return estimate(MarkStrength::Arbitrary, None, "No words to grade".to_string());
+    };
+    match graded.iter().map(|(_, s)| *s).max() {
+        Some(Strength::Generic) => {
+            return estimate(
+                MarkStrength::Generic,
+                Some(weakest),
+                format!("'{}' names the goods", weakest),
+            )
+        }
+        Some(Strength::Descriptive) => {
+            return estimate(
+                MarkStrength::Descriptive,
+                Some(weakest),
+                format!("'{}' describes the goods", weakest),
+            )
+        }
+        _ => {}
+    }
+    let has_weak = graded.iter().any(|(_, s)| *s < Strength::Distinctive);
+
+    let place = |word: &str| {
+        // A listed word is taken as itself (APPLE does not hint at APP)
+        let listed = is_listed_word(word);
+        let embedded = weak_terms(classes)
+            .find(|t| !listed && t.len() >= MIN_EMBEDDED_LEN && word.contains(t));
+        let known = listed
+            || segment(word, &[]).is_some()
+            || frequencies.is_some_and(|f| f.doc_freq(word) >= KNOWN_WORD_MARKS);
+        if let Some(term) = embedded {
+            (MarkStrength::Suggestive, format!("'{}' hints at the goods through '{}'", word, term))
+        } else if !known {
+            (MarkStrength::Fanciful, format!("'{}' is a coined word", word))
+        } else if has_weak {
+            (
+                MarkStrength::Suggestive,
+                format!("'{}' is paired with words describing the goods", word),
+            )
+        } else {
+            (MarkStrength::Arbitrary, format!("'{}' is a known word unrelated to the goods", word))
+        }
+    };
+    graded
+        .iter()
+        .filter(|(_, s)| *s == Strength::Distinctive)
+        .map(|(word, _)| (*word, place(word)))
+        .max_by_key(|(_, (strength, _))| *strength)
+        .map(|(word, (strength, reason))| estimate(strength, Some(word), reason))
+        .unwrap_or_else(|| estimate(MarkStrength::Arbitrary, None, String::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_term_strength() {
+        assert_eq!(term_strength("BREW", NiceClass::of(32)), Strength::Descriptive);
+        assert_eq!(term_strength("BREW", NiceClass::of(9)), Strength::Distinctive);
+        assert_eq!(term_strength("BEERS", NiceClass::of(32)), Strength::Generic);
+        assert_eq!(term_strength("BREWING", NiceClass::of(32)), Strength::Descriptive);
+        assert_eq!(term_strength("PREMIUM", NiceClass::of(9)), Strength::Descriptive);
+        assert_eq!(term_strength("ZYLO", NiceClass::of(32)), Strength::Distinctive);
+    }
+
+    #[test]
+    fn test_term_strength_in() {
+        assert_eq!(term_strength_in("TECH", &NiceClass::of_all(&[25, 9])), Strength::Descriptive);
+        assert_eq!(term_strength_in("COFFEE", &NiceClass::of_all(&[9, 30])), Strength::Generic);
+        assert_eq!(term_strength_in("TECH", &[]), Strength::Distinctive);
+        assert_eq!(term_strength_in("BEST", &[]), Strength::Descriptive);
+        assert!(Strength::Generic.weight() < Strength::Distinctive.weight());
+    }
+
+    #[test]
+    fn test_mark_strength() {
+        let strength =
+            |text: &str, classes: &[u16]| mark_strength(text, &NiceClass::of_all(classes)).strength;
+        assert_eq!(strength("BEER CO", &[32]), MarkStrength::Generic);
+        assert_eq!(strength("PREMIUM BREW", &[32]), MarkStrength::Descriptive);
+        assert_eq!(strength("TECHNOVA", &[9]), MarkStrength::Suggestive);
+        assert_eq!(strength("BLUE BREW", &[32]), MarkStrength::Suggestive);
+        assert_eq!(strength("APPLE", &[9]), MarkStrength::Arbitrary);
+        assert_eq!(strength("SUNPOWER", &[9]), MarkStrength::Arbitrary);
+        assert_eq!(strength("SMARTHOME", &[9]), MarkStrength::Suggestive);
+        assert_eq!(strength("ZYLO BREW", &[32]), MarkStrength::Fanciful);
+        assert_eq!(strength("ZYLO", &[32]), MarkStrength::Fanciful);
+
+        let estimate = mark_strength("TECHNOVA", &NiceClass::of_all(&[9]));
+        assert_eq!(estimate.term.as_deref(), Some("TECHNOVA"));
+        assert!(estimate.reason.contains("'TECH'"));
+    }
+
+    #[test]
+    fn test_mark_strength_with_frequencies() {
+        let frequencies = TermFrequencies::from_marks(["ZYLO"; 5]);
+        let software = NiceClass::of_all(&[9]);
+        assert_eq!(mark_strength("ZYLO", &software).strength, MarkStrength::Fanciful);
+        assert_eq!(
+            mark_strength_with("ZYLO", &software, Some(&frequencies)).strength,
+            MarkStrength::Arbitrary
+        );
+        assert!(MarkStrength::Generic.weight() < MarkStrength::Fanciful.weight());
+    }
+}
@@ -0,0 +1,187 @@
+//! Syllable-level comparison.
+//!
+//! Marks that respell one syllable (MICROSOFT vs MIKROSOFT, VITALITY vs
+//! VITALITEA) keep the rhythm of the original. Words are split into
+//! syllables by a rule-based heuristic: a syllable is built around each
+//! vowel group, a single consonant between vowels starts the next
+//! syllable, and a longer cluster is split before its last consonant or
+//! before a common onset (CR, ST, TH). The syllable sequences are then
+//! aligned, scoring each pair by its spelling similarity.
+
+use crate::{normalize_text, similarity_ratio};
+
+/// Consonant pairs that can start a syllable.
+#[rustfmt::skip]
+const ONSETS: &[&str] = &[
+    "BL", "BR", "CH", "CL", "CR", "DR", "FL", "FR", "GL", "GR", "KL", "KR", "PH", "PL", "PR",
+    "SC", "SH", "SK", "SL", "SM", "SN", "SP", "ST", "SW", "TH", "TR", "TW", "WH", "WR",
+];
+
+/// Two syllable sequences aligned pair by pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyllableAlignment {
+    /// Similarity of the sequences (0.0 - 1.0)
+    pub score: f32,
+    /// Aligned syllables; an empty string marks a gap
+    pub pairs: Vec<(String, String)>,
+}
+
+fn is_vowel(word: &[char], i: usize) -> bool {
+    match word[i] {
+        'A' | 'E' | 'I' | 'O' | 'U' => true,
+        // Y is a vowel except before a vowel (YES, BEYOND)
+        'Y' => i > 0 && !(i + 1 < word.len() && is_vowel(word, i + 1)),
+        _ => false,
+    }
+}
+
+/// Split one word into syllables.
+fn syllabify_word(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    // Start of each vowel group
+    let mut nuclei: Vec<usize> = (0..chars.len())
+        .filter(|&i| is_vowel(&chars, i) && (i == 0 || !is_vowel(&chars, i - 1)))
+        .collect();
+
+    // A final E after a consonant is silent (MIKE), unless it is the only
+    // vowel
+    let n = chars.len();
+    if nuclei.len() > 1
+        && n >= 2
+        && chars[n - 1] == 'E'
+        && nuclei.last() == Some(&(n - 1))
+        && !is_vowel(&chars, n - 2)
+    {
+        nuclei.pop();
+    }
+
+    if nuclei.len() <= 1 {
+        return vec![word.to_string()];
+    }
+
+    let mut syllables = Vec::with_capacity(nuclei.len());
+    let mut start = 0;
+    for pair in nuclei.windows(2) {
+        // Consonant cluster between the end of this vowel group and the
+        // next one
+        let mut cluster_start = pair[0];
+        while cluster_start < pair[1] && is_vowel(&chars, cluster_start) {
+            cluster_start += 1;
+        }
+        let cluster: String = chars[cluster_start..pair[1]].iter().collect();
+        let onset = match cluster.len() {
+            0 | 1 => cluster.len(),
+            len if ONSETS.contains(&&cluster[len - 2..]) => 2,
+            _ => 1,
+        };
+        let split = pair[1] - onset;
+        syllables.push(chars[start..split].iter().collect());
+        start = split;
+    }
+    syllables.push(chars[start..].iter().collect());
+    syllables
+}
+
+/// Split a mark into syllables, word by word.
+pub fn syllabify(text: &str) -> Vec<String> {
+    normalize_text(text)
+        .split_whitespace()
+        .flat_map(|word| {
+            word.split(|c: char| !c.is_ascii_alphabetic())
+                .filter(|part| !part.is_empty())
+                .flat_map(syllabify_word)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Align two marks' syllables, scoring pairs by spelling similarity.
+pub fn syllable_similarity(a: &str, b: &str) -> SyllableAlignment {
+    let a = syllabify(a);
+    let b = syllabify(b);
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return SyllableAlignment {
+            score: 1.0,
+            pairs: Vec::new(),
+        };
+    }
+
+    // cost[i][j]: cost of aligning a[..i] with b[..j]; a gap costs 1
+    let mut cost = vec![vec![0.0_f32; b.len() + 1]; a.len() + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i as f32;
+    }
+    for (j, cell) in cost[0].iter_mut().enumerate() {
+        *cell = j as f32;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = 1.0 - similarity_ratio(&a[i - 1], &b[j - 1]);
+            cost[i][j] = (cost[i - 1][j] + 1.0)
+                .min(cost[i][j - 1] + 1.0)
+                .min(cost[i - 1][j - 1] + substitution);
+        }
+    }
+
+    // Trace the alignment back
+    let mut pairs = Vec::with_capacity(max_len);
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0
+            && j > 0
+            && cost[i][j] == cost[i - 1][j - 1] + 1.0 - similarity_ratio(&a[i - 1], &b[j - 1])
+        {
+            pairs.push((a[i - 1].clone(), b[j - 1].clone()));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && cost[i][j] == cost[i - 1][j] + 1.0 {
+            pairs.push((a[i - 1].clone(), String::new()));
+            i -= 1;
+        } else {
+            pairs.push((String::new(), b[j - 1].clone()));
+            j -= 1;
+        }
+    }
+    pairs.reverse();
+
+    SyllableAlignment {
+        score: 1.0 - cost[a.len()][b.len()] / max_len as f32,
+        pairs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syllabify() {
+        assert_eq!(syllabify("Microsoft"), vec!["MI", "CRO", "SOFT"]);
+        assert_eq!(syllabify("MIKROSOFT"), vec!["MI", "KRO", "SOFT"]);
+        assert_eq!(syllabify("VITALITY"), vec!["VI", "TA", "LI", "TY"]);
+        assert_eq!(syllabify("VITALITEA"), vec!["VI", "TA", "LI", "TEA"]);
+        assert_eq!(syllabify("MIKE"), vec!["MIKE"]);
+        assert_eq!(syllabify("WINTER GARDEN"), vec!["WIN", "TER", "GAR", "DEN"]);
+        assert!(syllabify("").is_empty());
+    }
+
+    #[test]
+    fn test_syllable_similarity() {
+        let found = syllable_similarity("MICROSOFT", "MIKROSOFT");
+        assert!(found.score > 0.85);
+        assert_eq!(found.pairs[1], ("CRO".to_string(), "KRO".to_string()));
+
+        let found = syllable_similarity("VITALITY", "VITALITEA");
+        assert!(found.score > 0.8);
+        assert_eq!(found.pairs.len(), 4);
+        assert_eq!(found.pairs[3], ("TY".to_string(), "TEA".to_string()));
+
+        // A missing syllable is a gap
+        let found = syllable_similarity("VITALITY", "VITALY");
+        assert!(found.pairs.iter().any(|(a, b)| b.is_empty() && !a.is_empty()));
+
+        assert_eq!(syllable_similarity("NIKE", "NIKE").score, 1.0);
+        assert!(syllable_similarity("MICROSOFT", "BANANA").score < 0.5);
+    }
+}
@@ -0,0 +1,162 @@
+//! Corpus term frequencies for dominant-term extraction.
+//!
+//! The longest-word heuristic picks CORPORATION over a short coined term
+//! as soon as the stopword list misses a word. With document frequencies
+//! counted over the indexed marks, words are ranked by inverse document
+//! frequency instead: a word in few marks is distinctive, one in many is
+//! not. Words absent from the corpus rank highest, since coined terms
+//! rarely appear in other marks.
+
+use crate::{normalize_text, read_tsv, DOMINANT_STOPWORDS};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// Key of a saved table's mark count row; normalized terms never hold
+/// punctuation.
+const DOCS_KEY: &str = "@docs";
+
+/// A dominant term and its weight relative to the mark's top term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedTerm {
+    /// Normalized word
+    pub term: String,
+    /// Weight (0.0 - 1.0); the top term weighs 1.0
+    pub weight: f32,
+}
+
+/// Number of marks containing each word.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TermFrequencies {
+    doc_freq: HashMap<String, u64>,
+    docs: u64,
+}
+
+impl TermFrequencies {
+    /// Count the words of a set of marks.
+    pub fn from_marks<'a>(marks: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut frequencies = Self::default();
+        for mark in marks {
+            frequencies.add_mark(mark);
+        }
+        frequencies
+    }
+
+    /// Count one more mark.
+    pub fn add_mark(&mut self, text: &str) {
+        let normalized = normalize_text(text);
+        let mut words: Vec<&str> = normalized.split_whitespace().collect();
+        words.sort_unstable();
+        words.dedup();
+        for word in words {
+            *self.doc_freq.entry(word.to_string()).or_default() += 1;
+        }
+        self.docs += 1;
+    }
+
+    /// Number of marks counted.
+    pub fn marks(&self) -> u64 {
+        self.docs
+    }
+
+    /// Number of marks containing a normalized word.
+    pub fn doc_freq(&self, term: &str) -> u64 {
+        self.doc_freq.get(term).copied().unwrap_or(0)
+    }
+
+    /// Smoothed inverse document frequency of a normalized word.
+    pub fn idf(&self, term: &str) -> f32 {
+        ((1 + self.docs) as f32 / (1 + self.doc_freq(term)) as f32).ln() + 1.0
+    }
+
+    /// Up to `max` dominant terms of a mark, most distinctive first.
+    pub fn dominant_terms(&self, text: &str, max: usize) -> Vec<WeightedTerm> {
+        let normalized = normalize_text(text);
+        let mut words: Vec<&str> = Vec::new();
+        for word in normalized.split_whitespace() {
+            if !DOMINANT_STOPWORDS.contains(&word) && !words.contains(&word) {
+                words.push(word);
+            }
+        }
+
+        let mut ranked: Vec<(&str, f32)> = words.iter().map(|w| (*w, self.idf(w))).collect();
+        // Stable sort keeps mark order between equally rare words
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let top = ranked.first().map(|(_, idf)| *idf).unwrap_or(1.0);
+        ranked
+            .into_iter()
+            .take(max)
+            .map(|(term, idf)| WeightedTerm {
+                term: term.to_string(),
+                weight: idf / top,
+            })
+            .collect()
+    }
+
+    /// Read a table saved by `save`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut frequencies = Self::default();
+        read_tsv(path, |fields| {
+            let [key, count] = fields else {
+                return Err("expected term and count");
+            };
+            let count: u64 = count.parse().map_err(|_| "invalid count")?;
+            if *key == DOCS_KEY {
+                frequencies.docs = count;
+            } else {
+                frequencies.doc_freq.insert(key.to_string(), count);
+            }
+            Ok(())
+        })?;
+        Ok(frequencies)
+    }
+
+    /// Write the table as tab-separated `term`, `count` lines, most
+    /// frequent first, after an `@docs` row with the mark count.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut terms: Vec<(&String, &u64)> = self.doc_freq.iter().collect();
+        terms.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut out = format!("{}\t{}\n", DOCS_KEY, self.docs);
+        for (term, count) in terms {
+            out.push_str(&format!("{}\t{}\n", term, count));
+        }
+        std::fs::write(path, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corpus() -> TermFrequencies {
+        TermFrequencies::from_marks([
+            "ACME HOLDINGS",
+            "APEX HOLDINGS",
+            "ZENITH HOLDINGS",
+            "BLUE HOLDINGS",
+            "BLUE SKY",
+            "BLUE OCEAN",
+        ])
+    }
+
+    #[test]
+    fn test_dominant_terms() {
+        let frequencies = corpus();
+        assert_eq!(frequencies.marks(), 6);
+        assert!(frequencies.idf("HOLDINGS") < frequencies.idf("ACME"));
+
+        // The coined term outranks the longer, common word
+        let terms = frequencies.dominant_terms("ZYLO HOLDINGS INC", 3);
+        assert_eq!(terms[0].term, "ZYLO");
+        assert_eq!(terms[0].weight, 1.0);
+        assert_eq!(terms[1].term, "HOLDINGS");
+        assert!(terms[1].weight < 0.6);
+        assert_eq!(terms.len(), 2);
+
+        assert_eq!(frequencies.dominant_terms("BLUE SKY", 1)[0].term, "SKY");
+        assert!(frequencies.dominant_terms("THE INC", 2).is_empty());
+    }
+
+}
@@ -0,0 +1,225 @@
+//! Foreign-equivalent matching (doctrine of foreign equivalents).
+//!
+//! A mark in a common foreign language is compared by its English
+//! meaning, so LUPO (Italian) conflicts with WOLF. Marks are translated
+//! word by word through a `TranslationDictionary`, which bundles common
+//! Spanish, French, Italian and German terms and accepts user-supplied
+//! mappings. Two marks match if every word pair agrees, directly or
+//! through a translation, and at least one pair needed one.
+
+use crate::{normalize_text, read_tsv};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// Bundled (language, foreign, English) entries. Foreign words that are
+/// also English words (BAR, DIE, OR, RE, ROT, SOLE) are left out: a mark
+/// using one almost always means the English word.
+#[rustfmt::skip]
+const BUNDLED: &[(&str, &[(&str, &str)])] = &[
+    ("Spanish", &[
+        ("EL", "THE"), ("LA", "THE"), ("LOBO", "WOLF"), ("LEON", "LION"), ("SOL", "SUN"),
+        ("LUNA", "MOON"), ("ESTRELLA", "STAR"), ("CASA", "HOUSE"), ("ROJO", "RED"),
+        ("NEGRO", "BLACK"), ("BLANCO", "WHITE"), ("ORO", "GOLD"), ("AGUA", "WATER"),
+        ("FUEGO", "FIRE"), ("REY", "KING"), ("REINA", "QUEEN"), ("GATO", "CAT"),
+        ("PERRO", "DOG"), ("OSO", "BEAR"), ("AGUILA", "EAGLE"), ("CABALLO", "HORSE"),
+        ("AZUL", "BLUE"), ("VERDE", "GREEN"), ("CORAZON", "HEART"), ("AMOR", "LOVE"),
+        ("VIDA", "LIFE"), ("NUEVO", "NEW"),
+    ]),
+    ("French", &[
+        ("LE", "THE"), ("LA", "THE"), ("LOUP", "WOLF"), ("LION", "LION"), ("SOLEIL", "SUN"),
+        ("LUNE", "MOON"), ("ETOILE", "STAR"), ("MAISON", "HOUSE"), ("ROUGE", "RED"),
+        ("NOIR", "BLACK"), ("BLANC", "WHITE"), ("EAU", "WATER"), ("FEU", "FIRE"),
+        ("ROI", "KING"), ("REINE", "QUEEN"), ("CHIEN", "DOG"), ("OURS", "BEAR"),
+        ("AIGLE", "EAGLE"), ("CHEVAL", "HORSE"), ("BLEU", "BLUE"), ("VERT", "GREEN"),
+        ("COEUR", "HEART"), ("AMOUR", "LOVE"), ("NOUVEAU", "NEW"),
+    ]),
+    ("Italian", &[
+        ("IL", "THE"), ("LA", "THE"), ("LUPO", "WOLF"), ("LEONE", "LION"), ("LUNA", "MOON"),
+        ("STELLA", "STAR"), ("CASA", "HOUSE"), ("ROSSO", "RED"), ("NERO", "BLACK"),
+        ("BIANCO", "WHITE"), ("ORO", "GOLD"), ("ACQUA", "WATER"), ("FUOCO", "FIRE"),
+        ("REGINA", "QUEEN"), ("GATTO", "CAT"), ("ORSO", "BEAR"), ("AQUILA", "EAGLE"),
+        ("CAVALLO", "HORSE"), ("BLU", "BLUE"), ("VERDE", "GREEN"), ("CUORE", "HEART"),
+        ("AMORE", "LOVE"), ("VITA", "LIFE"), ("NUOVO", "NEW"),
+    ]),
+    ("German", &[
+        ("DER", "THE"), ("DAS", "THE"), ("WOLF", "WOLF"), ("LOWE", "LION"), ("SONNE", "SUN"),
+        ("MOND", "MOON"), ("HAUS", "HOUSE"), ("SCHWARZ", "BLACK"), ("WEISS", "WHITE"),
+        ("GOLD", "GOLD"), ("WASSER", "WATER"), ("FEUER", "FIRE"), ("KONIG", "KING"),
+        ("KONIGIN", "QUEEN"), ("KATZE", "CAT"), ("HUND", "DOG"), ("ADLER", "EAGLE"),
+        ("PFERD", "HORSE"), ("BLAU", "BLUE"), ("GRUN", "GREEN"), ("HERZ", "HEART"),
+        ("LIEBE", "LOVE"), ("LEBEN", "LIFE"), ("NEU", "NEW"),
+    ]),
+];
+
+/// A foreign word's English meaning.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Translation {
+    /// Language of the foreign word (e.g. "Italian")
+    pub language: String,
+    /// English meaning, normalized
+    pub english: String,
+}
+
+/// Two marks that are translations of each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignEquivalentMatch {
+    /// Languages of the translated words, in mark order
+    pub language: String,
+    /// Shared English meaning of the marks
+    pub translation: String,
+}
+
+/// Foreign words and their English meanings, keyed by normalized word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationDictionary {
+    entries: HashMap<String, Vec<Translation>>,
+}
+
+impl Default for TranslationDictionary {
+    /// The bundled dictionary.
+    fn default() -> Self {
+        let mut dictionary = Self::empty();
+        for (language, words) in BUNDLED {
+            for (foreign, english) in *words {
+                dictionary.insert(language, foreign, english);
+            }
+        }
+        dictionary
+    }
+}
+
+impl TranslationDictionary {
+    /// A dictionary with no entries.
+    pub fn empty() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Add a mapping; a word may have meanings in several languages.
+    pub fn insert(&mut self, language: &str, foreign: &str, english: &str) {
+        let translation = Translation {
+            language: language.to_string(),
+            english: normalize_text(english).into_owned(),
+        };
+        let meanings = self
+            .entries
+            .entry(normalize_text(foreign).into_owned())
+            .or_default();
+        if !meanings.contains(&translation) {
+            meanings.push(translation);
+        }
+    }
+
+    /// Add mappings from a tab-separated file of `language`, `foreign`,
+    /// `english` lines; blank lines and `#` comments are skipped.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        read_tsv(path, |fields| match fields {
+            [language, foreign, english] => {
+                self.insert(language, foreign, english);
+                Ok(())
+            }
+            _ => Err("expected language, foreign, english"),
+        })
+    }
+
+    /// English meanings of a normalized word.
+    pub fn translate(&self, word: &str) -> &[Translation] {
+        self.entries.get(word).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether two marks are translations of each other.
+    pub fn foreign_equivalent(&self, a: &str, b: &str) -> Option<ForeignEquivalentMatch> {
+        let a = normalize_text(a);
+        let b = normalize_text(b);
+        let a_words: Vec<&str> = a.split_whitespace().collect();
+        let b_words: Vec<&str> = b.split_whitespace().collect();
+        if a_words.is_empty() || a_words.len() != b_words.len() || a == b {
+            return None;
+        }
+
+        let mut languages: Vec<&str> = Vec::new();
+        let mut english = Vec::with_capacity(a_words.len());
+        for (wa, wb) in a_words.iter().zip(&b_words) {
+            let (meaning, language) = self.align(wa, wb)?;
+            if let Some(language) = language {
+                if !languages.contains(&language) {
+                    languages.push(language);
+                }
+            }
+            english.push(meaning);
+        }
+
+        (!languages.is_empty()).then(|| ForeignEquivalentMatch {
+            language: languages.join(", "),
+            translation: english.join(" "),
+        })
+    }
+
+    /// The shared meaning of two words and the language translated, if
+    /// any, or `None` if the words differ in meaning.
+    fn align<'a>(&'a self, a: &'a str, b: &'a str) -> Option<(&'a str, Option<&'a str>)> {
+        if a == b {
+            return Some((a, None));
+        }
+        // One side is the English word
+        for (foreign, english) in [(a, b), (b, a)] {
+            if let Some(t) = self
+                .translate(foreign)
+                .iter()
+                .find(|t| t.english == english)
+            {
+                return Some((english, Some(&t.language)));
+            }
+        }
+        // Both sides are foreign words with a shared meaning
+        self.translate(a).iter().find_map(|ta| {
+            self.translate(b)
+                .iter()
+                .find(|tb| tb.english == ta.english)
+                .map(|_| (ta.english.as_str(), Some(ta.language.as_str())))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_foreign_equivalent() {
+        let dictionary = TranslationDictionary::default();
+        assert_eq!(
+            dictionary.foreign_equivalent("LUPO", "Wolf"),
+            Some(ForeignEquivalentMatch {
+                language: "Italian".to_string(),
+                translation: "WOLF".to_string(),
+            })
+        );
+        let found = dictionary
+            .foreign_equivalent("EL LOBO", "THE WOLF")
+            .unwrap();
+        assert_eq!(found.language, "Spanish");
+        assert_eq!(found.translation, "THE WOLF");
+
+        // Two foreign words with the same meaning
+        assert_eq!(
+            dictionary
+                .foreign_equivalent("LOUP", "LUPO")
+                .unwrap()
+                .translation,
+            "WOLF"
+        );
+        assert_eq!(dictionary.foreign_equivalent("LUPO", "BEAR"), None);
+        assert_eq!(dictionary.foreign_equivalent("WOLF", "WOLF"), None);
+        assert_eq!(dictionary.foreign_equivalent("GOLD", "GOLD"), None);
+
+        // English homographs of foreign words are read as English
+        assert_eq!(dictionary.foreign_equivalent("GOLD BAR", "GOLD BEAR"), None);
+        assert_eq!(dictionary.foreign_equivalent("DIE HARD", "THE HARD"), None);
+        assert_eq!(dictionary.foreign_equivalent("OR", "GOLD"), None);
+        assert_eq!(dictionary.foreign_equivalent("ROT", "RED"), None);
+    }
+
+}
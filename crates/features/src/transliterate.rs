@@ -0,0 +1,231 @@
+//! Any-to-Latin transliteration for non-Latin marks.
+//!
+//! USPTO records include Cyrillic, Greek, Japanese and Korean marks.
+//! Comparing them against Latin marks needs a Latin spelling first, so
+//! НИКА is compared as NIKA. Cyrillic and Greek are transliterated
+//! letter by letter, kana by Hepburn romanization, and Hangul by the
+//! Revised Romanization of Korean. Han characters need a dictionary
+//! reading and are left as is; a record's filed transliteration covers
+//! them.
+
+use ilegalflow_model::TrademarkRecord;
+use std::borrow::Cow;
+
+#[rustfmt::skip]
+const CYRILLIC: &[(char, &str)] = &[
+    ('А', "A"), ('Б', "B"), ('В', "V"), ('Г', "G"), ('Д', "D"), ('Е', "E"), ('Ё', "YO"),
+    ('Ж', "ZH"), ('З', "Z"), ('И', "I"), ('Й', "Y"), ('К', "K"), ('Л', "L"), ('М', "M"),
+    ('Н', "N"), ('О', "O"), ('П', "P"), ('Р', "R"), ('С', "S"), ('Т', "T"), ('У', "U"),
+    ('Ф', "F"), ('Х', "KH"), ('Ц', "TS"), ('Ч', "CH"), ('Ш', "SH"), ('Щ', "SHCH"), ('Ъ', ""),
+    ('Ы', "Y"), ('Ь', ""), ('Э', "E"), ('Ю', "YU"), ('Я', "YA"), ('І', "I"), ('Ї', "YI"),
+    ('Є', "YE"), ('Ґ', "G"),
+];
+
+#[rustfmt::skip]
+const GREEK: &[(char, &str)] = &[
+    ('Α', "A"), ('Ά', "A"), ('Β', "V"), ('Γ', "G"), ('Δ', "D"), ('Ε', "E"), ('Έ', "E"),
+    ('Ζ', "Z"), ('Η', "I"), ('Ή', "I"), ('Θ', "TH"), ('Ι', "I"), ('Ί', "I"), ('Ϊ', "I"),
+    ('Κ', "K"), ('Λ', "L"), ('Μ', "M"), ('Ν', "N"), ('Ξ', "X"), ('Ο', "O"), ('Ό', "O"),
+    ('Π', "P"), ('Ρ', "R"), ('Σ', "S"), ('Τ', "T"), ('Υ', "Y"), ('Ύ', "Y"), ('Ϋ', "Y"),
+    ('Φ', "F"), ('Χ', "CH"), ('Ψ', "PS"), ('Ω', "O"), ('Ώ', "O"),
+];
+
+/// Katakana (hiragana is shifted onto it) in Hepburn romanization.
+#[rustfmt::skip]
+const KANA: &[(char, &str)] = &[
+    ('ア', "A"), ('イ', "I"), ('ウ', "U"), ('エ', "E"), ('オ', "O"),
+    ('カ', "KA"), ('キ', "KI"), ('ク', "KU"), ('ケ', "KE"), ('コ', "KO"),
+    ('ガ', "GA"), ('ギ', "GI"), ('グ', "GU"), ('ゲ', "GE"), ('ゴ', "GO"),
+    ('サ', "SA"), ('シ', "SHI"), ('ス', "SU"), ('セ', "SE"), ('ソ', "SO"),
+    ('ザ', "ZA"), ('ジ', "JI"), ('ズ', "ZU"), ('ゼ', "ZE"), ('ゾ', "ZO"),
+    ('タ', "TA"), ('チ', "CHI"), ('ツ', "TSU"), ('テ', "TE"), ('ト', "TO"),
+    ('ダ', "DA"), ('ヂ', "JI"), ('ヅ', "ZU"), ('デ', "DE"), ('ド', "DO"),
+    ('ナ', "NA"), ('ニ', "NI"), ('ヌ', "NU"), ('ネ', "NE"), ('ノ', "NO"),
+    ('ハ', "HA"), ('ヒ', "HI"), ('フ', "FU"), ('ヘ', "HE"), ('ホ', "HO"),
+    ('バ', "BA"), ('ビ', "BI"), ('ブ', "BU"), ('ベ', "BE"), ('ボ', "BO"),
+    ('パ', "PA"), ('ピ', "PI"), ('プ', "PU"), ('ペ', "PE"), ('ポ', "PO"),
+    ('マ', "MA"), ('ミ', "MI"), ('ム', "MU"), ('メ', "ME"), ('モ', "MO"),
+    ('ヤ', "YA"), ('ユ', "YU"), ('ヨ', "YO"),
+    ('ラ', "RA"), ('リ', "RI"), ('ル', "RU"), ('レ', "RE"), ('ロ', "RO"),
+    ('ワ', "WA"), ('ヲ', "O"), ('ン', "N"), ('ヴ', "VU"),
+];
+
+#[rustfmt::skip]
+const HANGUL_INITIAL: [&str; 19] = [
+    "G", "KK", "N", "D", "TT", "R", "M", "B", "PP", "S", "SS", "", "J", "JJ", "CH", "K", "T",
+    "P", "H",
+];
+
+#[rustfmt::skip]
+const HANGUL_MEDIAL: [&str; 21] = [
+    "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WO",
+    "WE", "WI", "YU", "EU", "UI", "I",
+];
+
+#[rustfmt::skip]
+const HANGUL_FINAL: [&str; 28] = [
+    "", "K", "K", "K", "N", "N", "N", "T", "L", "K", "M", "L", "L", "L", "P", "L", "M", "P",
+    "P", "T", "T", "NG", "T", "T", "K", "T", "P", "T",
+];
+
+fn lookup(table: &[(char, &'static str)], c: char) -> Option<&'static str> {
+    table.iter().find(|(from, _)| *from == c).map(|(_, to)| *to)
+}
+
+/// Whether a character is in a script this module transliterates.
+fn is_transliterable(c: char) -> bool {
+    matches!(c,
+        '\u{0370}'..='\u{03FF}'     // Greek
+        | '\u{0400}'..='\u{04FF}'   // Cyrillic
+        | '\u{3040}'..='\u{30FF}'   // Hiragana and Katakana
+        | '\u{AC00}'..='\u{D7A3}')  // Hangul syllables
+}
+
+/// Whether text contains letters outside the Latin script.
+pub fn is_non_latin(text: &str) -> bool {
+    text.chars()
+        .any(|c| c.is_alphabetic() && !c.is_ascii() && (is_transliterable(c) || is_han(c)))
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}')
+}
+
+/// Transliterate text to uppercase Latin letters; Latin text is
+/// returned unchanged.
+pub fn transliterate(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_transliterable) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len() * 2);
+    // Small tsu doubles the next consonant
+    let mut double_next = false;
+    for c in text.chars().flat_map(char::to_uppercase) {
+        // Shift hiragana onto katakana
+        let c = match c {
+            '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            _ => c,
+        };
+
+        let romaji = match c {
+            'ッ' => {
+                double_next = true;
+                continue;
+            }
+            // Long vowel mark
+            'ー' => continue,
+            'ャ' | 'ュ' | 'ョ' => {
+                let vowel = match c {
+                    'ャ' => "A",
+                    'ュ' => "U",
+                    _ => "O",
+                };
+                // KI + small YA is KYA; SHI, CHI and JI drop the Y (SHA)
+                if out.ends_with('I') {
+                    out.pop();
+                    if !(out.ends_with("SH") || out.ends_with("CH") || out.ends_with('J')) {
+                        out.push('Y');
+                    }
+                }
+                out.push_str(vowel);
+                continue;
+            }
+            'ァ' | 'ィ' | 'ゥ' | 'ェ' | 'ォ' => {
+                // A small vowel replaces the preceding vowel (FU + A is FA)
+                if out.ends_with(['A', 'I', 'U', 'E', 'O']) {
+                    out.pop();
+                }
+                out.push_str(match c {
+                    'ァ' => "A",
+                    'ィ' => "I",
+                    'ゥ' => "U",
+                    'ェ' => "E",
+                    _ => "O",
+                });
+                continue;
+            }
+            '\u{AC00}'..='\u{D7A3}' => {
+                let index = c as usize - 0xAC00;
+                let syllable = format!(
+                    "{}{}{}",
+                    HANGUL_INITIAL[index / (21 * 28)],
+                    HANGUL_MEDIAL[(index % (21 * 28)) / 28],
+                    HANGUL_FINAL[index % 28]
+                );
+                out.push_str(&syllable);
+                continue;
+            }
+            _ => lookup(CYRILLIC, c)
+                .or_else(|| lookup(GREEK, c))
+                .or_else(|| lookup(KANA, c)),
+        };
+
+        match romaji {
+            Some(romaji) => {
+                if std::mem::take(&mut double_next) {
+                    // CH is doubled as TCH
+                    match romaji.chars().next() {
+                        Some('C') => out.push('T'),
+                        Some(first) if !"AIUEON".contains(first) => out.push(first),
+                        _ => {}
+                    }
+                }
+                out.push_str(romaji);
+            }
+            None => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Latin text a record's mark is compared as: the filed transliteration
+/// of a non-Latin mark if there is one, else the mark transliterated.
+pub fn latin_mark_text(record: &TrademarkRecord) -> Cow<'_, str> {
+    match &record.transliteration {
+        Some(filed) if is_non_latin(&record.mark_text) && !filed.trim().is_empty() => {
+            Cow::Borrowed(filed)
+        }
+        _ => transliterate(&record.mark_text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cyrillic_and_greek() {
+        assert_eq!(transliterate("Ника"), "NIKA");
+        assert_eq!(transliterate("ЩИТ Шоколад"), "SHCHIT SHOKOLAD");
+        assert_eq!(transliterate("Αθήνα"), "ATHINA");
+        assert!(matches!(transliterate("NIKE"), Cow::Borrowed("NIKE")));
+    }
+
+    #[test]
+    fn test_kana_and_hangul() {
+        assert_eq!(transliterate("ナイキ"), "NAIKI");
+        assert_eq!(transliterate("キャノン"), "KYANON");
+        assert_eq!(transliterate("しゃしん"), "SHASHIN");
+        assert_eq!(transliterate("ニッケル"), "NIKKERU");
+        assert_eq!(transliterate("マッチャ"), "MATCHA");
+        assert_eq!(transliterate("ソニー"), "SONI");
+        assert_eq!(transliterate("ファン"), "FAN");
+        assert_eq!(transliterate("삼성"), "SAMSEONG");
+        assert_eq!(transliterate("현대"), "HYEONDAE");
+    }
+
+    #[test]
+    fn test_latin_mark_text() {
+        let mut record = TrademarkRecord::new("001", "小米");
+        assert!(is_non_latin(&record.mark_text));
+        assert_eq!(latin_mark_text(&record), "小米");
+        record.transliteration = Some("XIAOMI".to_string());
+        assert_eq!(latin_mark_text(&record), "XIAOMI");
+
+        // A filed transliteration of a Latin mark is not used
+        let mut record = TrademarkRecord::new("002", "NIKE");
+        record.transliteration = Some("NAIKI".to_string());
+        assert_eq!(latin_mark_text(&record), "NIKE");
+    }
+}
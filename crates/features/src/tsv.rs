@@ -0,0 +1,64 @@
+//! Tab-separated data files.
+//!
+//! User data (class grades, famous marks, translations, common-law
+//! listings, calibration outcomes) is loaded from tab-separated files
+//! sharing one layout: one row per line, blank lines and `#` comments
+//! skipped.
+
+use std::fmt::Display;
+use std::io;
+use std::path::Path;
+
+/// Read a tab-separated file, passing each row's trimmed fields to
+/// `row`. An error `row` returns fails the read as invalid data,
+/// prefixed with its line number.
+pub fn read_tsv<E: Display>(
+    path: impl AsRef<Path>,
+    row: impl FnMut(&[&str]) -> Result<(), E>,
+) -> io::Result<()> {
+    parse_tsv(&std::fs::read_to_string(path)?, row)
+}
+
+fn parse_tsv<E: Display>(
+    text: &str,
+    mut row: impl FnMut(&[&str]) -> Result<(), E>,
+) -> io::Result<()> {
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+        row(&fields).map_err(|message| {
+            let message = format!("line {}: {}", number + 1, message);
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsv() {
+        let mut rows = Vec::new();
+        parse_tsv("# a\tb\n\n  x \t y\nz\n", |fields: &[&str]| {
+            rows.push(fields.join("|"));
+            Ok::<_, String>(())
+        })
+        .unwrap();
+        assert_eq!(rows, ["x|y", "z"]);
+
+        let error = parse_tsv("x\n\nbad\n", |fields: &[&str]| match fields {
+            ["bad"] => Err("expected x"),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "line 3: expected x");
+
+        assert!(read_tsv("/nonexistent/ilegalflow.tsv", |_| Ok::<_, String>(())).is_err());
+    }
+}
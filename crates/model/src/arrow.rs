@@ -0,0 +1,481 @@
+//! Records to and from Arrow record batches and Parquet files.
+//!
+//! One row per record, in a flat schema that analysis tools read without
+//! unnesting: the owner and provenance are spread over prefixed columns,
+//! dates are `Date32`, and enums are their names. Classes, design codes
+//! and address lines are string lists; prosecution events are a list of
+//! structs. Reading casts each column to the schema's type, so files
+//! written by other tools (large or view strings, say) load too. As with
+//! JSON, only `serial_number` is required; a missing column reads as
+//! empty.
+
+use crate::{
+    Date, EntityType, MarkDrawingType, NiceClass, Owner, ProsecutionEvent, Provenance,
+    TrademarkRecord, TrademarkStatus,
+};
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Date32Type, TimestampMillisecondType, UInt64Type};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Date32Array, ListArray, RecordBatch, StringArray, StructArray,
+    TimestampMillisecondArray, UInt64Array,
+};
+use arrow_buffer::OffsetBuffer;
+use arrow_schema::{ArrowError, DataType, Field, FieldRef, Fields, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Records per batch when writing Parquet.
+pub const BATCH_ROWS: usize = 8192;
+
+/// Errors converting records to and from columns.
+#[derive(Debug, Error)]
+pub enum ColumnarError {
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Missing column '{0}'")]
+    MissingColumn(&'static str),
+
+    #[error("Invalid {column} '{value}' in row {row}")]
+    Invalid { column: &'static str, row: usize, value: String },
+}
+
+/// The schema of record batches.
+pub fn record_schema() -> SchemaRef {
+    let text = |name| Field::new(name, DataType::Utf8, true);
+    let date = |name| Field::new(name, DataType::Date32, true);
+    let list = |name, item| Field::new(name, DataType::List(list_item(item)), false);
+    Arc::new(Schema::new(vec![
+        Field::new("serial_number", DataType::Utf8, false),
+        text("registration_number"),
+        Field::new("mark_text", DataType::Utf8, false),
+        text("mark_text_normalized"),
+        text("transliteration"),
+        Field::new("status", DataType::Utf8, false),
+        text("status_code"),
+        list("classes", DataType::Utf8),
+        Field::new("classes_inferred", DataType::Boolean, false),
+        Field::new("goods_services", DataType::Utf8, false),
+        Field::new("owner_name", DataType::Utf8, false),
+        text("owner_entity_type"),
+        text("owner_citizenship"),
+        list("owner_address", DataType::Utf8),
+        date("filing_date"),
+        date("registration_date"),
+        date("status_date"),
+        text("drawing_type"),
+        list("design_codes", DataType::Utf8),
+        list("prosecution_events", DataType::Struct(event_fields())),
+        text("provenance_dataset"),
+        Field::new(
+            "provenance_ingested_at",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            true,
+        ),
+        text("provenance_source_file"),
+        Field::new("provenance_source_line", DataType::UInt64, true),
+        text("provenance_pipeline_version"),
+    ]))
+}
+
+fn list_item(data_type: DataType) -> FieldRef {
+    Arc::new(Field::new_list_field(data_type, false))
+}
+
+fn event_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("code", DataType::Utf8, false),
+        Field::new("description", DataType::Utf8, false),
+        Field::new("date", DataType::Date32, true),
+    ])
+}
+
+/// Records as a batch, one row each.
+pub fn records_to_batch(records: &[TrademarkRecord]) -> Result<RecordBatch, ColumnarError> {
+    let rows = || records.iter().enumerate();
+    let ingested_at = rows()
+        .map(|(row, r)| {
+            let ms = r.provenance.as_ref().and_then(|p| p.ingested_at_ms);
+            ms.map(|ms| i64::try_from(ms).map_err(|_| invalid("provenance_ingested_at", row, ms)))
+                .transpose()
+        })
+        .collect::<Result<TimestampMillisecondArray, _>>()?
+        .with_timezone("UTC");
+    let columns: Vec<ArrayRef> = vec![
+        text(records, |r| Some(&r.serial_number)),
+        text(records, |r| r.registration_number.as_deref()),
+        text(records, |r| Some(&r.mark_text)),
+        text(records, |r| r.mark_text_normalized.as_deref()),
+        text(records, |r| r.transliteration.as_deref()),
+        text(records, |r| Some(status_name(r.status))),
+        text(records, |r| r.status_code.as_deref()),
+        text_list(records, |r| r.classes.iter().map(NiceClass::to_string)),
+        Arc::new(records.iter().map(|r| Some(r.classes_inferred)).collect::<BooleanArray>()),
+        text(records, |r| Some(&r.goods_services)),
+        text(records, |r| Some(&r.owner.name)),
+        text(records, |r| r.owner.entity_type.map(entity_type_name)),
+        text(records, |r| r.owner.citizenship.as_deref()),
+        text_list(records, |r| &r.owner.address),
+        dates("filing_date", rows().map(|(row, r)| (row, r.filing_date)))?,
+        dates("registration_date", rows().map(|(row, r)| (row, r.registration_date)))?,
+        dates("status_date", rows().map(|(row, r)| (row, r.status_date)))?,
+        text(records, |r| r.drawing_type.map(|t| t.name())),
+        text_list(records, |r| &r.design_codes),
+        events(records)?,
+        text(records, |r| r.provenance.as_ref().map(|p| p.dataset.as_str())),
+        Arc::new(ingested_at),
+        text(records, |r| provenance_text(r, |p| &p.source_file)),
+        Arc::new(
+            records
+                .iter()
+                .map(|r| r.provenance.as_ref().and_then(|p| p.source_line))
+                .collect::<UInt64Array>(),
+        ),
+        text(records, |r| provenance_text(r, |p| &p.pipeline_version)),
+    ];
+    Ok(RecordBatch::try_new(record_schema(), columns)?)
+}
+
+fn invalid(column: &'static str, row: usize, value: impl ToString) -> ColumnarError {
+    ColumnarError::Invalid { column, row, value: value.to_string() }
+}
+
+fn text<'a>(
+    records: &'a [TrademarkRecord],
+    value: impl Fn(&'a TrademarkRecord) -> Option<&'a str>,
+) -> ArrayRef {
+    Arc::new(records.iter().map(value).collect::<StringArray>())
+}
+
+fn text_list<'a, I>(
+    records: &'a [TrademarkRecord],
+    items: impl Fn(&'a TrademarkRecord) -> I,
+) -> ArrayRef
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut builder = ListBuilder::new(StringBuilder::new()).with_field(list_item(DataType::Utf8));
+    for record in records {
+        builder.values().extend(items(record).into_iter().map(Some));
+        builder.append(true);
+    }
+    Arc::new(builder.finish())
+}
+
+fn provenance_text(
+    record: &TrademarkRecord,
+    field: impl Fn(&Provenance) -> &Option<String>,
+) -> Option<&str> {
+    record.provenance.as_ref().and_then(|p| field(p).as_deref())
+}
+
+fn dates(
+    column: &'static str,
+    values: impl Iterator<Item = (usize, Option<Date>)>,
+) -> Result<ArrayRef, ColumnarError> {
+    let days = values
+        .map(|(row, date)| {
+            date.map(|d| i32::try_from(d.days()).map_err(|_| invalid(column, row, d))).transpose()
+        })
+        .collect::<Result<Date32Array, _>>()?;
+    Ok(Arc::new(days))
+}
+
+fn events(records: &[TrademarkRecord]) -> Result<ArrayRef, ColumnarError> {
+    let events: Vec<(usize, &ProsecutionEvent)> = records
+        .iter()
+        .enumerate()
+        .flat_map(|(row, r)| r.prosecution_events.iter().map(move |event| (row, event)))
+        .collect();
+    let codes = events.iter().map(|(_, event)| event.code.as_str());
+    let descriptions = events.iter().map(|(_, event)| event.description.as_str());
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(codes)),
+        Arc::new(StringArray::from_iter_values(descriptions)),
+        dates("prosecution_events", events.iter().map(|(row, event)| (*row, event.date)))?,
+    ];
+    let values = StructArray::try_new(event_fields(), columns, None)?;
+    let offsets = OffsetBuffer::from_lengths(records.iter().map(|r| r.prosecution_events.len()));
+    let item = list_item(DataType::Struct(event_fields()));
+    Ok(Arc::new(ListArray::try_new(item, offsets, Arc::new(values), None)?))
+}
+
+fn status_name(status: TrademarkStatus) -> &'static str {
+    match status {
+        TrademarkStatus::Live => "LIVE",
+        TrademarkStatus::Dead => "DEAD",
+        TrademarkStatus::Pending => "PENDING",
+        TrademarkStatus::Unknown => "UNKNOWN",
+    }
+}
+
+fn entity_type_name(entity_type: EntityType) -> &'static str {
+    match entity_type {
+        EntityType::Individual => "individual",
+        EntityType::Corporation => "corporation",
+        EntityType::LimitedLiabilityCompany => "limited_liability_company",
+        EntityType::Partnership => "partnership",
+        EntityType::Trust => "trust",
+        EntityType::Association => "association",
+        EntityType::Government => "government",
+        EntityType::Other => "other",
+    }
+}
+
+/// Records from a batch with the columns of `record_schema`.
+pub fn records_from_batch(batch: &RecordBatch) -> Result<Vec<TrademarkRecord>, ColumnarError> {
+    let schema = record_schema();
+    let column = |name: &'static str| -> Result<Option<ArrayRef>, ColumnarError> {
+        let field = schema.field_with_name(name)?;
+        let column = batch.column_by_name(name);
+        Ok(column.map(|c| arrow_cast::cast(c, field.data_type())).transpose()?)
+    };
+    let serial_number =
+        column("serial_number")?.ok_or(ColumnarError::MissingColumn("serial_number"))?;
+    let registration_number = column("registration_number")?;
+    let mark_text = column("mark_text")?;
+    let mark_text_normalized = column("mark_text_normalized")?;
+    let transliteration = column("transliteration")?;
+    let status = column("status")?;
+    let status_code = column("status_code")?;
+    let classes = column("classes")?;
+    let classes_inferred = column("classes_inferred")?;
+    let goods_services = column("goods_services")?;
+    let owner_name = column("owner_name")?;
+    let owner_entity_type = column("owner_entity_type")?;
+    let owner_citizenship = column("owner_citizenship")?;
+    let owner_address = column("owner_address")?;
+    let filing_date = column("filing_date")?;
+    let registration_date = column("registration_date")?;
+    let status_date = column("status_date")?;
+    let drawing_type = column("drawing_type")?;
+    let design_codes = column("design_codes")?;
+    let prosecution_events = column("prosecution_events")?;
+    let provenance_dataset = column("provenance_dataset")?;
+    let provenance_ingested_at = column("provenance_ingested_at")?;
+    let provenance_source_file = column("provenance_source_file")?;
+    let provenance_source_line = column("provenance_source_line")?;
+    let provenance_pipeline_version = column("provenance_pipeline_version")?;
+
+    (0..batch.num_rows())
+        .map(|row| {
+            let serial_number = text_at(Some(&serial_number), row)
+                .ok_or_else(|| invalid("serial_number", row, "null"))?;
+            let classes = texts_at(classes.as_ref(), row)
+                .into_iter()
+                .map(|class| class.parse().map_err(|_| invalid("classes", row, class)))
+                .collect::<Result<_, _>>()?;
+            let entity_type = text_at(owner_entity_type.as_ref(), row)
+                .map(|name| name.parse().map_err(|_| invalid("owner_entity_type", row, name)))
+                .transpose()?;
+            let drawing_type = text_at(drawing_type.as_ref(), row)
+                .map(|name| {
+                    name.parse::<MarkDrawingType>().map_err(|_| invalid("drawing_type", row, name))
+                })
+                .transpose()?;
+            let provenance = match text_at(provenance_dataset.as_ref(), row) {
+                Some(dataset) => Some(Provenance {
+                    dataset,
+                    ingested_at_ms: ingested_at(provenance_ingested_at.as_ref(), row)?,
+                    source_file: text_at(provenance_source_file.as_ref(), row),
+                    source_line: provenance_source_line
+                        .as_ref()
+                        .map(|c| c.as_primitive::<UInt64Type>())
+                        .and_then(|c| c.is_valid(row).then(|| c.value(row))),
+                    pipeline_version: text_at(provenance_pipeline_version.as_ref(), row),
+                }),
+                None => None,
+            };
+            Ok(TrademarkRecord {
+                serial_number,
+                registration_number: text_at(registration_number.as_ref(), row),
+                mark_text: text_at(mark_text.as_ref(), row).unwrap_or_default(),
+                mark_text_normalized: text_at(mark_text_normalized.as_ref(), row),
+                transliteration: text_at(transliteration.as_ref(), row),
+                status: text_at(status.as_ref(), row)
+                    .map_or(TrademarkStatus::Unknown, |s| TrademarkStatus::from(s.as_str())),
+                status_code: text_at(status_code.as_ref(), row),
+                classes,
+                classes_inferred: classes_inferred
+                    .as_ref()
+                    .map(|c| c.as_boolean())
+                    .is_some_and(|c| c.is_valid(row) && c.value(row)),
+                goods_services: text_at(goods_services.as_ref(), row).unwrap_or_default(),
+                owner: Owner {
+                    name: text_at(owner_name.as_ref(), row).unwrap_or_default(),
+                    entity_type,
+                    citizenship: text_at(owner_citizenship.as_ref(), row),
+                    address: texts_at(owner_address.as_ref(), row),
+                },
+                filing_date: date_at(filing_date.as_ref(), row),
+                registration_date: date_at(registration_date.as_ref(), row),
+                status_date: date_at(status_date.as_ref(), row),
+                drawing_type,
+                design_codes: texts_at(design_codes.as_ref(), row),
+                prosecution_events: events_at(prosecution_events.as_ref(), row),
+                provenance,
+            })
+        })
+        .collect()
+}
+
+fn text_at(column: Option<&ArrayRef>, row: usize) -> Option<String> {
+    let column = column?.as_string::<i32>();
+    column.is_valid(row).then(|| column.value(row).to_string())
+}
+
+fn texts_at(column: Option<&ArrayRef>, row: usize) -> Vec<String> {
+    let Some(column) = column.map(|c| c.as_list::<i32>()).filter(|c| c.is_valid(row)) else {
+        return Vec::new();
+    };
+    let items = column.value(row);
+    let items = items.as_string::<i32>();
+    items.iter().flatten().map(str::to_string).collect()
+}
+
+fn date_at(column: Option<&ArrayRef>, row: usize) -> Option<Date> {
+    let column = column?.as_primitive::<Date32Type>();
+    column.is_valid(row).then(|| Date::from_days(column.value(row).into()))
+}
+
+fn ingested_at(column: Option<&ArrayRef>, row: usize) -> Result<Option<u64>, ColumnarError> {
+    let Some(column) = column.map(|c| c.as_primitive::<TimestampMillisecondType>()) else {
+        return Ok(None);
+    };
+    if column.is_null(row) {
+        return Ok(None);
+    }
+    let ms = column.value(row);
+    u64::try_from(ms).map(Some).map_err(|_| invalid("provenance_ingested_at", row, ms))
+}
+
+fn events_at(column: Option<&ArrayRef>, row: usize) -> Vec<ProsecutionEvent> {
+    let Some(column) = column.map(|c| c.as_list::<i32>()).filter(|c| c.is_valid(row)) else {
+        return Vec::new();
+    };
+    let events = column.value(row);
+    let events = events.as_struct();
+    let code = events.column_by_name("code");
+    let description = events.column_by_name("description");
+    let date = events.column_by_name("date");
+    (0..events.len())
+        .map(|i| ProsecutionEvent {
+            code: text_at(code, i).unwrap_or_default(),
+            description: text_at(description, i).unwrap_or_default(),
+            date: date_at(date, i),
+        })
+        .collect()
+}
+
+/// Write records to a Parquet file, `BATCH_ROWS` to a batch.
+pub fn write_parquet(
+    records: &[TrademarkRecord],
+    path: impl AsRef<Path>,
+) -> Result<(), ColumnarError> {
+    let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut writer = ArrowWriter::try_new(File::create(path)?, record_schema(), Some(properties))?;
+    for chunk in records.chunks(BATCH_ROWS) {
+        writer.write(&records_to_batch(chunk)?)?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+/// Read records from a Parquet file.
+pub fn read_parquet(path: impl AsRef<Path>) -> Result<Vec<TrademarkRecord>, ColumnarError> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?;
+    let mut records = Vec::new();
+    for batch in reader {
+        records.extend(records_from_batch(&batch?)?);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<TrademarkRecord> {
+        let mut quorland = TrademarkRecord::new("97123456", "QUORLAND");
+        quorland.registration_number = Some("7012345".to_string());
+        quorland.status = TrademarkStatus::Live;
+        quorland.classes = vec![NiceClass::of(25), NiceClass::A];
+        quorland.owner = Owner::new("Quorland, Inc.")
+            .with_entity_type(EntityType::LimitedLiabilityCompany)
+            .with_address(vec!["1 Main St".to_string(), "Portland, OR".to_string()]);
+        quorland.filing_date = Date::from_ymd(2023, 3, 14);
+        quorland.drawing_type = Some(MarkDrawingType::DesignWithText);
+        quorland.design_codes = vec!["26.01.03".to_string()];
+        quorland.prosecution_events = vec![
+            ProsecutionEvent::new("NWAP", "NEW APPLICATION ENTERED"),
+            ProsecutionEvent::new("CNRT", "NON-FINAL ACTION").with_date(Date::from_days(19500)),
+        ];
+        quorland.provenance = Some(
+            Provenance::new("uspto-daily-2026-10-14")
+                .with_ingested_at_ms(1_791_979_200_000)
+                .with_source("apc261014.xml", 1042),
+        );
+        vec![quorland, TrademarkRecord::new("88000001", "ZEPHYRA")]
+    }
+
+    fn json(records: &[TrademarkRecord]) -> serde_json::Value {
+        serde_json::to_value(records).unwrap()
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let records = records();
+        let batch = records_to_batch(&records).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema(), record_schema());
+        assert_eq!(json(&records_from_batch(&batch).unwrap()), json(&records));
+
+        // Columns may be missing or of a compatible type
+        let serials = StringArray::from(vec!["97123456"]);
+        let marks = arrow_cast::cast(&StringArray::from(vec!["QUORLAND"]), &DataType::LargeUtf8);
+        let batch = RecordBatch::try_from_iter([
+            ("serial_number", Arc::new(serials) as ArrayRef),
+            ("status", Arc::new(StringArray::from(vec!["live"]))),
+            ("mark_text", marks.unwrap()),
+        ])
+        .unwrap();
+        let read = records_from_batch(&batch).unwrap();
+        assert_eq!(read[0].mark_text, "QUORLAND");
+        assert_eq!(read[0].status, TrademarkStatus::Live);
+
+        let batch = RecordBatch::try_from_iter([(
+            "mark_text",
+            Arc::new(StringArray::from(vec!["NIKE"])) as ArrayRef,
+        )])
+        .unwrap();
+        let err = records_from_batch(&batch).unwrap_err();
+        assert_eq!(err.to_string(), "Missing column 'serial_number'");
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("ilegalflow-records-{}.parquet", std::process::id()));
+        let records = records();
+        write_parquet(&records, &path).unwrap();
+        let read = read_parquet(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(json(&read), json(&records));
+    }
+}
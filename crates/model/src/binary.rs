@@ -0,0 +1,468 @@
+//! Compact binary encoding of records and hits.
+//!
+//! For the local cache and for IPC between the daemon and the CLI, where
+//! JSON is too slow and too large for millions of records. The JSON forms
+//! skip empty fields and accept several shapes of a field, which a
+//! positional format cannot express, so records and hits are encoded
+//! with postcard through the wire structs below instead: every field is
+//! always written, in the order declared. That order is the format. Unit
+//! enums are written as their variant index, so their variants are only
+//! ever appended.
+//!
+//! Any change to a wire struct bumps `FORMAT_VERSION`. Each encoding
+//! starts with the version, and decoding rejects any other, so a cache
+//! written by another build is rebuilt rather than misread. Risk flags
+//! travel as their JSON, so a flag added by a newer build still arrives
+//! as `RiskFlag::Unrecognized`.
+
+use crate::{
+    CandidateHit, Date, DuPontAssessment, DuPontFactor, EntityType, FactorFinding, FactorWeight,
+    MarkDrawingType, MatchSpans, NiceClass, Owner, ProsecutionEvent, Provenance, ReviewNote,
+    ReviewVerdict, RiskFlag, SourceLinks, StatusAdjustment, TrademarkRecord, TrademarkStatus,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::ops::Range;
+use thiserror::Error;
+
+/// Version of the wire structs, written as the first byte.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Errors from encoding or decoding.
+#[derive(Debug, Error)]
+pub enum BinaryError {
+    #[error("Binary format version {0} is not supported (expected {FORMAT_VERSION})")]
+    Version(u8),
+
+    #[error("Invalid binary data: {0}")]
+    Postcard(#[from] postcard::Error),
+
+    #[error("Invalid risk flag: {0}")]
+    Flag(#[from] serde_json::Error),
+
+    #[error("Invalid {0} in binary data")]
+    Invalid(&'static str),
+}
+
+impl TrademarkRecord {
+    /// Encode in the compact binary format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BinaryError> {
+        encode(&RecordWire::from(self))
+    }
+
+    /// Decode from the compact binary format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryError> {
+        decode::<RecordWire>(bytes)?.into_record()
+    }
+}
+
+impl CandidateHit {
+    /// Encode in the compact binary format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BinaryError> {
+        encode(&HitWire::new(self)?)
+    }
+
+    /// Decode from the compact binary format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryError> {
+        decode::<HitWire>(bytes)?.into_hit()
+    }
+}
+
+fn encode<T: Serialize>(wire: &T) -> Result<Vec<u8>, BinaryError> {
+    Ok(postcard::to_extend(wire, vec![FORMAT_VERSION])?)
+}
+
+fn decode<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, BinaryError> {
+    match bytes.split_first() {
+        Some((&FORMAT_VERSION, wire)) => Ok(postcard::from_bytes(wire)?),
+        Some((&version, _)) => Err(BinaryError::Version(version)),
+        None => Err(postcard::Error::DeserializeUnexpectedEnd.into()),
+    }
+}
+
+fn owned(text: Option<Cow<'_, str>>) -> Option<String> {
+    text.map(Cow::into_owned)
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordWire<'a> {
+    serial_number: Cow<'a, str>,
+    registration_number: Option<Cow<'a, str>>,
+    mark_text: Cow<'a, str>,
+    mark_text_normalized: Option<Cow<'a, str>>,
+    transliteration: Option<Cow<'a, str>>,
+    status: TrademarkStatus,
+    status_code: Option<Cow<'a, str>>,
+    classes: Vec<u16>,
+    classes_inferred: bool,
+    goods_services: Cow<'a, str>,
+    owner: OwnerWire<'a>,
+    /// Days since 1970-01-01
+    filing_date: Option<i64>,
+    registration_date: Option<i64>,
+    status_date: Option<i64>,
+    /// USPTO drawing code digit
+    drawing_type: Option<u8>,
+    design_codes: Cow<'a, [String]>,
+    prosecution_events: Vec<EventWire<'a>>,
+    provenance: Option<ProvenanceWire<'a>>,
+}
+
+impl<'a> From<&'a TrademarkRecord> for RecordWire<'a> {
+    fn from(record: &'a TrademarkRecord) -> Self {
+        let text = |text: &'a Option<String>| text.as_deref().map(Cow::Borrowed);
+        Self {
+            serial_number: Cow::Borrowed(&record.serial_number),
+            registration_number: text(&record.registration_number),
+            mark_text: Cow::Borrowed(&record.mark_text),
+            mark_text_normalized: text(&record.mark_text_normalized),
+            transliteration: text(&record.transliteration),
+            status: record.status,
+            status_code: text(&record.status_code),
+            classes: record.classes.iter().map(NiceClass::code).collect(),
+            classes_inferred: record.classes_inferred,
+            goods_services: Cow::Borrowed(&record.goods_services),
+            owner: OwnerWire {
+                name: Cow::Borrowed(&record.owner.name),
+                entity_type: record.owner.entity_type,
+                citizenship: text(&record.owner.citizenship),
+                address: Cow::Borrowed(&record.owner.address),
+            },
+            filing_date: record.filing_date.map(|d| d.days()),
+            registration_date: record.registration_date.map(|d| d.days()),
+            status_date: record.status_date.map(|d| d.days()),
+            drawing_type: record.drawing_type.map(|t| t.code()),
+            design_codes: Cow::Borrowed(&record.design_codes),
+            prosecution_events: record
+                .prosecution_events
+                .iter()
+                .map(|event| EventWire {
+                    code: Cow::Borrowed(&event.code),
+                    description: Cow::Borrowed(&event.description),
+                    date: event.date.map(|d| d.days()),
+                })
+                .collect(),
+            provenance: record.provenance.as_ref().map(|p| ProvenanceWire {
+                dataset: Cow::Borrowed(&p.dataset),
+                ingested_at_ms: p.ingested_at_ms,
+                source_file: text(&p.source_file),
+                source_line: p.source_line,
+                pipeline_version: text(&p.pipeline_version),
+            }),
+        }
+    }
+}
+
+impl RecordWire<'_> {
+    fn into_record(self) -> Result<TrademarkRecord, BinaryError> {
+        let classes = self
+            .classes
+            .into_iter()
+            .map(|code| NiceClass::from_code(code).ok_or(BinaryError::Invalid("class")))
+            .collect::<Result<_, _>>()?;
+        let drawing_type = match self.drawing_type {
+            Some(code) => Some(
+                MarkDrawingType::ALL
+                    .into_iter()
+                    .find(|t| t.code() == code)
+                    .ok_or(BinaryError::Invalid("drawing type"))?,
+            ),
+            None => None,
+        };
+        Ok(TrademarkRecord {
+            serial_number: self.serial_number.into_owned(),
+            registration_number: owned(self.registration_number),
+            mark_text: self.mark_text.into_owned(),
+            mark_text_normalized: owned(self.mark_text_normalized),
+            transliteration: owned(self.transliteration),
+            status: self.status,
+            status_code: owned(self.status_code),
+            classes,
+            classes_inferred: self.classes_inferred,
+            goods_services: self.goods_services.into_owned(),
+            owner: Owner {
+                name: self.owner.name.into_owned(),
+                entity_type: self.owner.entity_type,
+                citizenship: owned(self.owner.citizenship),
+                address: self.owner.address.into_owned(),
+            },
+            filing_date: self.filing_date.map(Date::from_days),
+            registration_date: self.registration_date.map(Date::from_days),
+            status_date: self.status_date.map(Date::from_days),
+            drawing_type,
+            design_codes: self.design_codes.into_owned(),
+            prosecution_events: self
+                .prosecution_events
+                .into_iter()
+                .map(|event| ProsecutionEvent {
+                    code: event.code.into_owned(),
+                    description: event.description.into_owned(),
+                    date: event.date.map(Date::from_days),
+                })
+                .collect(),
+            provenance: self.provenance.map(|p| Provenance {
+                dataset: p.dataset.into_owned(),
+                ingested_at_ms: p.ingested_at_ms,
+                source_file: owned(p.source_file),
+                source_line: p.source_line,
+                pipeline_version: owned(p.pipeline_version),
+            }),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct OwnerWire<'a> {
+    name: Cow<'a, str>,
+    entity_type: Option<EntityType>,
+    citizenship: Option<Cow<'a, str>>,
+    address: Cow<'a, [String]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EventWire<'a> {
+    code: Cow<'a, str>,
+    description: Cow<'a, str>,
+    date: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProvenanceWire<'a> {
+    dataset: Cow<'a, str>,
+    ingested_at_ms: Option<u64>,
+    source_file: Option<Cow<'a, str>>,
+    source_line: Option<u64>,
+    pipeline_version: Option<Cow<'a, str>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HitWire<'a> {
+    record: RecordWire<'a>,
+    retrieval_score: f32,
+    risk_score: f32,
+    risk_probability: Option<f32>,
+    /// Each flag's JSON
+    flags: Vec<String>,
+    review: Option<ReviewWire<'a>>,
+    status_adjustment: Option<StatusAdjustment>,
+    links: Option<LinksWire<'a>>,
+    family: Vec<RecordWire<'a>>,
+    /// du Pont findings, in factor order
+    dupont: Option<Vec<FindingWire<'a>>>,
+    highlights: Vec<SpansWire<'a>>,
+}
+
+impl<'a> HitWire<'a> {
+    fn new(hit: &'a CandidateHit) -> Result<Self, BinaryError> {
+        let text = |text: &'a Option<String>| text.as_deref().map(Cow::Borrowed);
+        Ok(Self {
+            record: RecordWire::from(&hit.record),
+            retrieval_score: hit.retrieval_score,
+            risk_score: hit.risk_score,
+            risk_probability: hit.risk_probability,
+            flags: hit.flags.iter().map(serde_json::to_string).collect::<Result<_, _>>()?,
+            review: hit.review.as_ref().map(|review| ReviewWire {
+                verdict: review.verdict,
+                note: Cow::Borrowed(&review.note),
+                reviewer: text(&review.reviewer),
+            }),
+            status_adjustment: hit.status_adjustment,
+            links: hit.links.as_ref().map(|links| LinksWire {
+                tsdr: text(&links.tsdr),
+                tess: text(&links.tess),
+            }),
+            family: hit.family.iter().map(RecordWire::from).collect(),
+            dupont: hit.dupont.as_ref().map(|dupont| {
+                dupont
+                    .findings
+                    .iter()
+                    .map(|f| FindingWire {
+                        factor: f.factor,
+                        weight: f.weight,
+                        finding: Cow::Borrowed(&f.finding),
+                        signals: Cow::Borrowed(&f.signals),
+                    })
+                    .collect()
+            }),
+            highlights: hit
+                .highlights
+                .iter()
+                .map(|spans| SpansWire {
+                    flag: Cow::Borrowed(&spans.flag),
+                    ranges_in_query: Cow::Borrowed(&spans.ranges_in_query),
+                    ranges_in_mark: Cow::Borrowed(&spans.ranges_in_mark),
+                })
+                .collect(),
+        })
+    }
+
+    fn into_hit(self) -> Result<CandidateHit, BinaryError> {
+        Ok(CandidateHit {
+            record: self.record.into_record()?,
+            retrieval_score: self.retrieval_score,
+            risk_score: self.risk_score,
+            risk_probability: self.risk_probability,
+            flags: self
+                .flags
+                .iter()
+                .map(|flag| serde_json::from_str::<RiskFlag>(flag))
+                .collect::<Result<_, _>>()?,
+            review: self.review.map(|review| ReviewNote {
+                verdict: review.verdict,
+                note: review.note.into_owned(),
+                reviewer: owned(review.reviewer),
+            }),
+            status_adjustment: self.status_adjustment,
+            links: self.links.map(|links| SourceLinks {
+                tsdr: owned(links.tsdr),
+                tess: owned(links.tess),
+            }),
+            family: self
+                .family
+                .into_iter()
+                .map(RecordWire::into_record)
+                .collect::<Result<_, _>>()?,
+            dupont: self.dupont.map(|findings| DuPontAssessment {
+                findings: findings
+                    .into_iter()
+                    .map(|f| FactorFinding {
+                        factor: f.factor,
+                        weight: f.weight,
+                        finding: f.finding.into_owned(),
+                        signals: f.signals.into_owned(),
+                    })
+                    .collect(),
+            }),
+            highlights: self
+                .highlights
+                .into_iter()
+                .map(|spans| MatchSpans {
+                    flag: spans.flag.into_owned(),
+                    ranges_in_query: spans.ranges_in_query.into_owned(),
+                    ranges_in_mark: spans.ranges_in_mark.into_owned(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReviewWire<'a> {
+    verdict: ReviewVerdict,
+    note: Cow<'a, str>,
+    reviewer: Option<Cow<'a, str>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinksWire<'a> {
+    tsdr: Option<Cow<'a, str>>,
+    tess: Option<Cow<'a, str>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FindingWire<'a> {
+    factor: DuPontFactor,
+    weight: FactorWeight,
+    finding: Cow<'a, str>,
+    signals: Cow<'a, [String]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpansWire<'a> {
+    flag: Cow<'a, str>,
+    ranges_in_query: Cow<'a, [Range<usize>]>,
+    ranges_in_mark: Cow<'a, [Range<usize>]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> TrademarkRecord {
+        let mut record = TrademarkRecord::new("97123456", "QUORLAND");
+        record.registration_number = Some("7012345".to_string());
+        record.status = TrademarkStatus::Live;
+        record.classes = vec![NiceClass::of(25), NiceClass::A];
+        record.owner = Owner::new("Quorland, Inc.")
+            .with_entity_type(EntityType::Corporation)
+            .with_address(vec!["1 Main St".to_string()]);
+        record.filing_date = Date::from_ymd(2023, 3, 14);
+        record.drawing_type = Some(MarkDrawingType::DesignWithText);
+        record.design_codes = vec!["26.01.03".to_string()];
+        record.prosecution_events = vec![ProsecutionEvent::new("CNRT", "NON-FINAL ACTION")];
+        record.provenance = Some(Provenance::new("uspto-daily-2026-10-14"));
+        record
+    }
+
+    fn json<T: Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let record = record();
+        let bytes = record.to_bytes().unwrap();
+        assert_eq!(json(&TrademarkRecord::from_bytes(&bytes).unwrap()), json(&record));
+        assert!(bytes.len() * 2 < serde_json::to_vec(&record).unwrap().len());
+
+        // Field order is the format: a minimal record, byte for byte
+        let bytes = TrademarkRecord::new("1", "A").to_bytes().unwrap();
+        let mut expected = vec![FORMAT_VERSION, 1, b'1', 0, 1, b'A', 0, 0, 3, 0, 0, 0, 0];
+        expected.extend([0; 11]);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_hit_round_trip() {
+        let hit = CandidateHit {
+            record: record(),
+            retrieval_score: 12.5,
+            risk_score: 0.8,
+            risk_probability: Some(0.6),
+            flags: vec![
+                RiskFlag::FuzzyMatch { distance: 1 },
+                serde_json::from_str(r#"{"type": "TrademarkSquatting", "detail": [1]}"#).unwrap(),
+            ],
+            review: Some(ReviewNote {
+                verdict: ReviewVerdict::Pin,
+                note: "Client's own mark".to_string(),
+                reviewer: None,
+            }),
+            status_adjustment: Some(StatusAdjustment {
+                status: TrademarkStatus::Live,
+                multiplier: 1.0,
+            }),
+            links: SourceLinks::for_record(&record()),
+            family: vec![TrademarkRecord::new("97123457", "QUORLAND")],
+            dupont: Some(DuPontAssessment {
+                findings: vec![FactorFinding {
+                    factor: DuPontFactor::MarkSimilarity,
+                    weight: FactorWeight::FavorsConfusion,
+                    finding: "Spelled similarly.".to_string(),
+                    signals: vec!["Spelled Similarly".to_string()],
+                }],
+            }),
+            highlights: vec![MatchSpans {
+                flag: "Spelled Similarly".to_string(),
+                ranges_in_query: vec![0..3, 5..6],
+                ranges_in_mark: Vec::new(),
+            }],
+        };
+        let decoded = CandidateHit::from_bytes(&hit.to_bytes().unwrap()).unwrap();
+        assert_eq!(json(&decoded), json(&hit));
+        assert_eq!(decoded.flags[1].kind(), "TrademarkSquatting");
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let mut bytes = record().to_bytes().unwrap();
+        assert!(matches!(
+            TrademarkRecord::from_bytes(&bytes[..bytes.len() / 2]),
+            Err(BinaryError::Postcard(_))
+        ));
+        bytes[0] = FORMAT_VERSION + 1;
+        let err = TrademarkRecord::from_bytes(&bytes).unwrap_err();
+        assert_eq!(err.to_string(), "Binary format version 2 is not supported (expected 1)");
+        assert!(TrademarkRecord::from_bytes(&[]).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Building records.
+//!
+//! `RecordBuilder` sets fields fluently and checks the result on
+//! `build()` with `TrademarkRecord::validate`. Serial and registration
+//! numbers are canonicalized first. Status follows from the other fields
+//! unless set explicitly: a status code gives its coarse status, and
+//! otherwise a registration number implies a live registration.
+
+use crate::links::{canonical_number, REGISTRATION_DIGITS, SERIAL_DIGITS};
+use crate::{
+    Date, MarkDrawingType, NiceClass, Owner, ProsecutionEvent, Provenance, TrademarkRecord,
+    TrademarkStatus, ValidationIssue,
+};
+use thiserror::Error;
+
+/// Errors from building a record.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RecordError {
+    #[error("Serial number is required")]
+    MissingSerial,
+
+    #[error("Invalid serial number '{0}'")]
+    InvalidSerial(String),
+
+    #[error("Invalid registration number '{0}'")]
+    InvalidRegistration(String),
+
+    #[error("Invalid record: {}", describe(.0))]
+    Invalid(Vec<ValidationIssue>),
+}
+
+fn describe(issues: &[ValidationIssue]) -> String {
+    let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    messages.join("; ")
+}
+
+/// Builds a `TrademarkRecord`; see `TrademarkRecord::builder`.
+#[derive(Debug, Clone)]
+pub struct RecordBuilder {
+    record: TrademarkRecord,
+    status: Option<TrademarkStatus>,
+}
+
+impl Default for RecordBuilder {
+    fn default() -> Self {
+        Self {
+            record: TrademarkRecord::new("", ""),
+            status: None,
+        }
+    }
+}
+
+impl RecordBuilder {
+    /// Set the serial number.
+    pub fn with_serial_number(mut self, serial_number: impl Into<String>) -> Self {
+        self.record.serial_number = serial_number.into();
+        self
+    }
+
+    /// Set the registration number.
+    pub fn with_registration_number(mut self, registration_number: impl Into<String>) -> Self {
+        self.record.registration_number = Some(registration_number.into());
+        self
+    }
+
+    /// Set the mark text.
+    pub fn with_mark_text(mut self, mark_text: impl Into<String>) -> Self {
+        self.record.mark_text = mark_text.into();
+        self
+    }
+
+    /// Set the normalized mark text.
+    pub fn with_mark_text_normalized(mut self, normalized: impl Into<String>) -> Self {
+        self.record.mark_text_normalized = Some(normalized.into());
+        self
+    }
+
+    /// Set the transliteration.
+    pub fn with_transliteration(mut self, transliteration: impl Into<String>) -> Self {
+        self.record.transliteration = Some(transliteration.into());
+        self
+    }
+
+    /// Set the status, overriding the one implied by other fields.
+    pub fn with_status(mut self, status: TrademarkStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Set the USPTO status code.
+    pub fn with_status_code(mut self, status_code: impl Into<String>) -> Self {
+        self.record.status_code = Some(status_code.into());
+        self
+    }
+
+    /// Set the Nice classes.
+    pub fn with_classes(mut self, classes: Vec<NiceClass>) -> Self {
+        self.record.classes = classes;
+        self
+    }
+
+    /// Mark the classes as inferred from the goods and services.
+    pub fn with_classes_inferred(mut self, inferred: bool) -> Self {
+        self.record.classes_inferred = inferred;
+        self
+    }
+
+    /// Set the goods and services text.
+    pub fn with_goods_services(mut self, goods_services: impl Into<String>) -> Self {
+        self.record.goods_services = goods_services.into();
+        self
+    }
+
+    /// Set the owner.
+    pub fn with_owner(mut self, owner: impl Into<Owner>) -> Self {
+        self.record.owner = owner.into();
+        self
+    }
+
+    /// Set the filing date.
+    pub fn with_filing_date(mut self, date: Date) -> Self {
+        self.record.filing_date = Some(date);
+        self
+    }
+
+    /// Set the registration date.
+    pub fn with_registration_date(mut self, date: Date) -> Self {
+        self.record.registration_date = Some(date);
+        self
+    }
+
+    /// Set the status change date.
+    pub fn with_status_date(mut self, date: Date) -> Self {
+        self.record.status_date = Some(date);
+        self
+    }
+
+    /// Set the drawing type.
+    pub fn with_drawing_type(mut self, drawing_type: MarkDrawingType) -> Self {
+        self.record.drawing_type = Some(drawing_type);
+        self
+    }
+
+    /// Set the design search codes.
+    pub fn with_design_codes(mut self, design_codes: Vec<String>) -> Self {
+        self.record.design_codes = design_codes;
+        self
+    }
+
+    /// Add a prosecution event; events are kept in the order added.
+    pub fn with_prosecution_event(mut self, event: ProsecutionEvent) -> Self {
+        self.record.prosecution_events.push(event);
+        self
+    }
+
+    /// Set where the record was ingested from.
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.record.provenance = Some(provenance);
+        self
+    }
+
+    /// Canonicalize, check with `TrademarkRecord::validate`, and build
+    /// the record.
+    pub fn build(self) -> Result<TrademarkRecord, RecordError> {
+        let mut record = self.record;
+        if record.serial_number.trim().is_empty() {
+            return Err(RecordError::MissingSerial);
+        }
+        record.serial_number = canonical_number(&record.serial_number, SERIAL_DIGITS)
+            .ok_or(RecordError::InvalidSerial(record.serial_number))?;
+        if let Some(number) = record.registration_number.take() {
+            let canonical = canonical_number(&number, REGISTRATION_DIGITS)
+                .ok_or(RecordError::InvalidRegistration(number))?;
+            record.registration_number = Some(canonical);
+        }
+        record.status = self.status.unwrap_or_else(|| {
+            match (record.status_detail(), &record.registration_number) {
+                (Some(detail), _) => detail.status(),
+                (None, Some(_)) => TrademarkStatus::Live,
+                (None, None) => TrademarkStatus::Unknown,
+            }
+        });
+        record.validate().map_err(RecordError::Invalid)?;
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StatusDetail;
+
+    #[test]
+    fn test_build_record() {
+        let record = TrademarkRecord::builder()
+            .with_serial_number("72-016,902")
+            .with_registration_number("978,952")
+            .with_mark_text("NIKE")
+            .with_classes(NiceClass::of_all(&[25]))
+            .with_owner("Nike, Inc.")
+            .build()
+            .unwrap();
+        assert_eq!(record.serial_number, "72016902");
+        assert_eq!(record.registration_number.as_deref(), Some("0978952"));
+        assert_eq!(record.status, TrademarkStatus::Live);
+        assert_eq!(record.owner.name, "Nike, Inc.");
+
+        let cancelled = TrademarkRecord::builder()
+            .with_serial_number("72016902")
+            .with_registration_number("0978952")
+            .with_status_code("710")
+            .build()
+            .unwrap();
+        assert_eq!(cancelled.status, TrademarkStatus::Dead);
+        assert_eq!(cancelled.status_detail(), Some(StatusDetail::Cancelled));
+
+        let overridden = TrademarkRecord::builder()
+            .with_serial_number("72016902")
+            .with_registration_number("0978952")
+            .with_status(TrademarkStatus::Unknown)
+            .build()
+            .unwrap();
+        assert_eq!(overridden.status, TrademarkStatus::Unknown);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert_eq!(TrademarkRecord::builder().build().unwrap_err(), RecordError::MissingSerial);
+        let err = TrademarkRecord::builder().with_serial_number("123456789").build();
+        assert_eq!(err.unwrap_err(), RecordError::InvalidSerial("123456789".into()));
+
+        let filing = Date::from_ymd(2020, 6, 1).unwrap();
+        let err = TrademarkRecord::builder()
+            .with_serial_number("90000001")
+            .with_filing_date(filing)
+            .with_registration_date(filing.add_days(-1))
+            .build()
+            .unwrap_err();
+        assert!(matches!(&err, RecordError::Invalid(issues) if issues.len() == 1));
+        let message =
+            "Invalid record: Registration date 2020-05-31 is before filing date 2020-06-01";
+        assert_eq!(err.to_string(), message);
+    }
+}
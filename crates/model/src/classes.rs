@@ -0,0 +1,636 @@
+//! Nice classes and their groupings.
+//!
+//! `NiceClass` is a validated class number: 1-45, or one of the US-only
+//! classes A, B and 200 that certification and collective membership
+//! marks are filed in. Higher-level groupings are used for coarse
+//! filtering and result faceting:
+//! - `ClassKind`: goods (1-34) vs services (35-45)
+//! - `ClassCluster`: related-industry clusters (e.g. apparel & accessories)
+//! - Coordinated classes: pairs the USPTO treats as related when searching
+//!   (e.g. class 25 clothing and class 35 retail sale of clothing)
+
+use crate::{CandidateHit, SearchQuery};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Internal codes of the US letter classes, which sort after 200.
+const CODE_A: u16 = 1001;
+const CODE_B: u16 = 1002;
+
+/// Short titles of classes 1-45.
+const TITLES: [&str; 45] = [
+    "Chemicals",
+    "Paints",
+    "Cosmetics and cleaning preparations",
+    "Lubricants and fuels",
+    "Pharmaceuticals",
+    "Metal goods",
+    "Machinery",
+    "Hand tools",
+    "Electrical and scientific apparatus",
+    "Medical apparatus",
+    "Environmental control apparatus",
+    "Vehicles",
+    "Firearms",
+    "Jewelry",
+    "Musical instruments",
+    "Paper goods and printed matter",
+    "Rubber goods",
+    "Leather goods",
+    "Non-metallic building materials",
+    "Furniture and articles not otherwise classified",
+    "Housewares and glass",
+    "Cordage and fibers",
+    "Yarns and threads",
+    "Fabrics",
+    "Clothing",
+    "Fancy goods",
+    "Floor coverings",
+    "Toys and sporting goods",
+    "Meats and processed foods",
+    "Staple foods",
+    "Natural agricultural products",
+    "Light beverages",
+    "Wines and spirits",
+    "Smokers' articles",
+    "Advertising and business",
+    "Insurance and financial",
+    "Building construction and repair",
+    "Telecommunications",
+    "Transportation and storage",
+    "Treatment of materials",
+    "Education and entertainment",
+    "Computer and scientific",
+    "Hotels and restaurants",
+    "Medical, beauty and agricultural",
+    "Personal and legal",
+];
+
+/// A Nice class, or a US-only class (A, B, 200).
+///
+/// Serialized as the class number, or as "A" or "B".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NiceClass(u16);
+
+impl NiceClass {
+    /// US class A, certification marks for goods
+    pub const A: Self = Self(CODE_A);
+    /// US class B, certification marks for services
+    pub const B: Self = Self(CODE_B);
+    /// US class 200, collective membership marks
+    pub const COLLECTIVE_MEMBERSHIP: Self = Self(200);
+
+    /// The class with a number (1-45 or 200).
+    pub const fn new(number: u16) -> Option<Self> {
+        match number {
+            1..=45 | 200 => Some(Self(number)),
+            _ => None,
+        }
+    }
+
+    /// The class with a number known to be valid, as in tables and tests.
+    ///
+    /// # Panics
+    ///
+    /// If `number` is not a class; use `new` or `try_from` for data.
+    pub const fn of(number: u16) -> Self {
+        match Self::new(number) {
+            Some(class) => class,
+            None => panic!("not a Nice class"),
+        }
+    }
+
+    /// `of` for each number.
+    ///
+    /// # Panics
+    ///
+    /// If any number is not a class.
+    pub fn of_all(numbers: &[u16]) -> Vec<Self> {
+        numbers.iter().map(|&n| Self::of(n)).collect()
+    }
+
+    /// Every class from 1 to 45.
+    pub fn international() -> impl Iterator<Item = Self> {
+        (1..=45).map(Self)
+    }
+
+    /// The class number; `None` for the letter classes.
+    pub fn number(&self) -> Option<u16> {
+        (self.0 <= 200).then_some(self.0)
+    }
+
+    /// Whether the class covers goods (1-34, A).
+    pub fn is_goods(&self) -> bool {
+        matches!(self.0, 1..=34 | CODE_A)
+    }
+
+    /// Whether the class covers services (35-45, B).
+    pub fn is_services(&self) -> bool {
+        matches!(self.0, 35..=45 | CODE_B)
+    }
+
+    /// The class as stored: its number, or an internal code for A and B.
+    #[cfg(feature = "postcard")]
+    pub(crate) fn code(&self) -> u16 {
+        self.0
+    }
+
+    /// The class for a stored `code`.
+    #[cfg(feature = "postcard")]
+    pub(crate) fn from_code(code: u16) -> Option<Self> {
+        match code {
+            CODE_A | CODE_B => Some(Self(code)),
+            number => Self::new(number),
+        }
+    }
+
+    /// Short title of the class ("Clothing").
+    pub fn description(&self) -> &'static str {
+        match self.0 {
+            1..=45 => TITLES[self.0 as usize - 1],
+            200 => "Collective membership",
+            CODE_A => "Certification marks for goods",
+            _ => "Certification marks for services",
+        }
+    }
+}
+
+impl TryFrom<u16> for NiceClass {
+    type Error = String;
+
+    fn try_from(number: u16) -> Result<Self, Self::Error> {
+        Self::new(number).ok_or_else(|| format!("Invalid class {} (1-45, 200, A or B)", number))
+    }
+}
+
+impl PartialEq<u16> for NiceClass {
+    fn eq(&self, number: &u16) -> bool {
+        self.number() == Some(*number)
+    }
+}
+
+impl fmt::Display for NiceClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            CODE_A => f.write_str("A"),
+            CODE_B => f.write_str("B"),
+            number => write!(f, "{}", number),
+        }
+    }
+}
+
+impl FromStr for NiceClass {
+    type Err = String;
+
+    /// Parse "25", "025", "200", "A" or "B".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "A" | "a" => Ok(Self::A),
+            "B" | "b" => Ok(Self::B),
+            number => match number.parse::<u16>() {
+                Ok(number) => Self::try_from(number),
+                Err(_) => Err(format!("Invalid class '{}' (1-45, 200, A or B)", s)),
+            },
+        }
+    }
+}
+
+impl Serialize for NiceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.number() {
+            Some(number) => serializer.serialize_u16(number),
+            None => serializer.collect_str(self),
+        }
+    }
+}
+
+/// Deserialized from a number, or a string `FromStr` accepts; invalid
+/// classes are errors.
+impl<'de> Deserialize<'de> for NiceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(number) => u16::try_from(number)
+                .map_err(|_| format!("Invalid class {} (1-45, 200, A or B)", number))
+                .and_then(Self::try_from),
+            Raw::Text(text) => text.parse(),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A class number, or the letter of a US certification class.
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for NiceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "NiceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "oneOf": [
+                { "type": "integer", "minimum": 1, "maximum": 45 },
+                { "const": 200 },
+                { "enum": ["A", "B"] }
+            ]
+        })
+    }
+}
+
+/// Whether a Nice class covers goods or services.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClassKind {
+    /// Classes 1-34
+    Goods,
+    /// Classes 35-45
+    Services,
+}
+
+impl ClassKind {
+    /// Get the kind of a class; `None` for collective membership.
+    pub fn of(class: NiceClass) -> Option<Self> {
+        if class.is_goods() {
+            Some(Self::Goods)
+        } else if class.is_services() {
+            Some(Self::Services)
+        } else {
+            None
+        }
+    }
+}
+
+/// A named group of related Nice classes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassCluster {
+    /// Cluster identifier (e.g. "apparel-accessories")
+    pub name: String,
+
+    /// Human-readable label
+    pub label: String,
+
+    /// Member classes
+    pub classes: Vec<NiceClass>,
+}
+
+impl ClassCluster {
+    pub fn new(name: impl Into<String>, label: impl Into<String>, classes: Vec<NiceClass>) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            classes,
+        }
+    }
+
+    /// Check whether any of the given classes belongs to this cluster.
+    pub fn contains_any(&self, classes: &[NiceClass]) -> bool {
+        classes.iter().any(|c| self.classes.contains(c))
+    }
+}
+
+/// A configurable taxonomy of class clusters and coordinated classes.
+///
+/// A class may belong to several clusters (e.g. class 35 retail services
+/// sits with apparel and with business services). Coordinated pairs are
+/// symmetric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassTaxonomy {
+    pub clusters: Vec<ClassCluster>,
+
+    /// Coordinated class pairs
+    #[serde(default = "default_coordinated")]
+    pub coordinated: Vec<(NiceClass, NiceClass)>,
+}
+
+/// Bundled coordinated class pairs, after the USPTO coordinated class guide.
+fn default_coordinated() -> Vec<(NiceClass, NiceClass)> {
+    #[rustfmt::skip]
+    const PAIRS: &[(u16, u16)] = &[
+        // Goods and their retail services
+        (3, 35), (5, 35), (9, 35), (11, 35), (12, 35), (14, 35), (16, 35), (18, 35),
+        (20, 35), (21, 35), (24, 35), (25, 35), (28, 35), (29, 35), (30, 35),
+        // Apparel and accessories
+        (14, 25), (18, 25),
+        // Software, telecom, and technology services
+        (9, 38), (9, 41), (9, 42), (38, 41), (38, 42),
+        // Food, beverages, and restaurant services
+        (29, 43), (30, 43), (31, 43), (32, 43), (33, 43), (32, 33),
+        // Health and beauty
+        (3, 5), (3, 44), (5, 44), (10, 44),
+        // Publications, toys, and entertainment
+        (16, 41), (28, 41),
+        // Vehicles and their servicing/transport
+        (12, 37), (12, 39),
+    ];
+    PAIRS.iter().map(|&(a, b)| (NiceClass::of(a), NiceClass::of(b))).collect()
+}
+
+/// Bundled clusters: name, label and member classes.
+#[rustfmt::skip]
+const CLUSTERS: &[(&str, &str, &[u16])] = &[
+    ("apparel-accessories", "Apparel & Accessories", &[14, 18, 25, 35]),
+    ("food-beverage", "Food & Beverage", &[29, 30, 31, 32, 33, 43]),
+    ("technology", "Technology & Software", &[9, 38, 42]),
+    ("health-beauty", "Health & Beauty", &[3, 5, 10, 44]),
+    ("entertainment-media", "Entertainment & Media", &[9, 16, 28, 41]),
+    ("home-furnishings", "Home & Furnishings", &[8, 11, 20, 21, 24, 27]),
+    ("vehicles-transport", "Vehicles & Transport", &[12, 37, 39]),
+    ("business-finance", "Business & Finance", &[35, 36]),
+    ("industrial-construction", "Industrial & Construction", &[1, 2, 6, 7, 17, 19, 37, 40]),
+];
+
+impl Default for ClassTaxonomy {
+    /// The bundled taxonomy.
+    fn default() -> Self {
+        Self {
+            coordinated: default_coordinated(),
+            clusters: CLUSTERS
+                .iter()
+                .map(|&(name, label, classes)| {
+                    ClassCluster::new(name, label, NiceClass::of_all(classes))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ClassTaxonomy {
+    pub fn new(clusters: Vec<ClassCluster>) -> Self {
+        Self {
+            clusters,
+            coordinated: default_coordinated(),
+        }
+    }
+
+    /// Get the classes coordinated with a class, sorted.
+    pub fn coordinated_with(&self, class: NiceClass) -> Vec<NiceClass> {
+        let mut related: Vec<NiceClass> = self
+            .coordinated
+            .iter()
+            .filter_map(|&(a, b)| match class {
+                c if c == a => Some(b),
+                c if c == b => Some(a),
+                _ => None,
+            })
+            .collect();
+        related.sort_unstable();
+        related.dedup();
+        related
+    }
+
+    /// Get a cluster by name.
+    pub fn cluster(&self, name: &str) -> Option<&ClassCluster> {
+        self.clusters.iter().find(|c| c.name == name)
+    }
+
+    /// Get all clusters containing a class.
+    pub fn clusters_for(&self, class: NiceClass) -> impl Iterator<Item = &ClassCluster> {
+        self.clusters.iter().filter(move |c| c.classes.contains(&class))
+    }
+
+    /// Expand classes to every class sharing a cluster with them.
+    ///
+    /// Returns only the added classes, sorted; the input classes are not
+    /// repeated.
+    pub fn expand_clusters(&self, classes: &[NiceClass]) -> Vec<NiceClass> {
+        let mut expanded: Vec<NiceClass> = classes
+            .iter()
+            .flat_map(|&c| self.clusters_for(c))
+            .flat_map(|cluster| cluster.classes.iter().copied())
+            .filter(|c| !classes.contains(c))
+            .collect();
+        expanded.sort_unstable();
+        expanded.dedup();
+        expanded
+    }
+
+    /// Count hits per cluster, for result faceting.
+    ///
+    /// Clusters with no hits are omitted; facets are ordered by count
+    /// descending, then taxonomy order.
+    pub fn facets(&self, hits: &[CandidateHit]) -> Vec<ClusterFacet> {
+        let mut facets: Vec<ClusterFacet> = self
+            .clusters
+            .iter()
+            .map(|cluster| ClusterFacet {
+                cluster: cluster.name.clone(),
+                label: cluster.label.clone(),
+                count: hits
+                    .iter()
+                    .filter(|h| cluster.contains_any(&h.record.classes))
+                    .count(),
+            })
+            .filter(|f| f.count > 0)
+            .collect();
+        facets.sort_by_key(|f| std::cmp::Reverse(f.count));
+        facets
+    }
+}
+
+/// Number of hits falling into a class cluster.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterFacet {
+    pub cluster: String,
+    pub label: String,
+    pub count: usize,
+}
+
+/// Why a class was added to a filter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExpansionReason {
+    /// Coordinated with a requested class
+    Coordinated { with: NiceClass },
+    /// Shares a cluster with a requested class
+    Cluster { cluster: String },
+}
+
+/// A class added to a filter by expansion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassExpansion {
+    pub class: NiceClass,
+    pub reason: ExpansionReason,
+}
+
+/// The class filter actually applied for a query.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassFilter {
+    /// Classes requested by the user
+    pub requested: Vec<NiceClass>,
+
+    /// Classes added by expansion, with the reason for each
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expansions: Vec<ClassExpansion>,
+}
+
+impl ClassFilter {
+    /// Resolve the class filter for a query, applying coordinated-class
+    /// and cluster expansion if requested.
+    ///
+    /// Each added class is reported once, preferring the coordinated
+    /// reason as the more specific one.
+    pub fn resolve(query: &SearchQuery, taxonomy: &ClassTaxonomy) -> Self {
+        let mut expansions: Vec<ClassExpansion> = Vec::new();
+        let mut add = |class: NiceClass, reason: ExpansionReason| {
+            if !query.classes.contains(&class) && !expansions.iter().any(|e| e.class == class) {
+                expansions.push(ClassExpansion { class, reason });
+            }
+        };
+
+        if query.expand_related_classes {
+            for &requested in &query.classes {
+                for class in taxonomy.coordinated_with(requested) {
+                    add(class, ExpansionReason::Coordinated { with: requested });
+                }
+            }
+        }
+
+        if query.expand_class_clusters {
+            for &requested in &query.classes {
+                for cluster in taxonomy.clusters_for(requested) {
+                    for &class in &cluster.classes {
+                        add(
+                            class,
+                            ExpansionReason::Cluster {
+                                cluster: cluster.name.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        expansions.sort_by_key(|e| e.class);
+
+        Self {
+            requested: query.classes.clone(),
+            expansions,
+        }
+    }
+
+    /// Classes added by expansion, sorted.
+    pub fn expanded_classes(&self) -> Vec<NiceClass> {
+        self.expansions.iter().map(|e| e.class).collect()
+    }
+
+    /// All classes in the filter, sorted.
+    pub fn classes(&self) -> Vec<NiceClass> {
+        let mut all: Vec<NiceClass> = self
+            .requested
+            .iter()
+            .copied()
+            .chain(self.expansions.iter().map(|e| e.class))
+            .collect();
+        all.sort_unstable();
+        all.dedup();
+        all
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requested.is_empty() && self.expansions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrademarkRecord;
+
+    #[test]
+    fn test_nice_class() {
+        assert_eq!(NiceClass::new(25), Some(NiceClass::of(25)));
+        assert_eq!(NiceClass::new(0), None);
+        assert_eq!(NiceClass::new(99), None);
+        assert_eq!(NiceClass::of(25).description(), "Clothing");
+        assert!(NiceClass::of(25).is_goods() && NiceClass::of(35).is_services());
+        assert!(NiceClass::B.is_services() && !NiceClass::COLLECTIVE_MEMBERSHIP.is_goods());
+        assert_eq!("025".parse(), Ok(NiceClass::of(25)));
+        assert_eq!("a".parse(), Ok(NiceClass::A));
+        assert!("99".parse::<NiceClass>().is_err());
+        assert_eq!(NiceClass::international().count(), 45);
+
+        let classes = vec![NiceClass::of(25), NiceClass::COLLECTIVE_MEMBERSHIP, NiceClass::A];
+        let json = serde_json::to_string(&classes).unwrap();
+        assert_eq!(json, r#"[25,200,"A"]"#);
+        assert_eq!(serde_json::from_str::<Vec<NiceClass>>(&json).unwrap(), classes);
+        assert!(serde_json::from_str::<NiceClass>("99").is_err());
+        assert!(serde_json::from_str::<NiceClass>("\"99\"").is_err());
+    }
+
+    #[test]
+    fn test_class_kind() {
+        assert_eq!(ClassKind::of(NiceClass::of(25)), Some(ClassKind::Goods));
+        assert_eq!(ClassKind::of(NiceClass::of(35)), Some(ClassKind::Services));
+        assert_eq!(ClassKind::of(NiceClass::COLLECTIVE_MEMBERSHIP), None);
+    }
+
+    #[test]
+    fn test_expand_cluster() {
+        let taxonomy = ClassTaxonomy::default();
+        assert_eq!(taxonomy.expand_clusters(&[NiceClass::of(25)]), vec![14, 18, 35]);
+        assert_eq!(taxonomy.expand_clusters(&[NiceClass::of(13)]), Vec::<u16>::new());
+    }
+
+    #[test]
+    fn test_class_filter_resolve() {
+        let taxonomy = ClassTaxonomy::default();
+        let mut query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        assert!(ClassFilter::resolve(&query, &taxonomy).expansions.is_empty());
+
+        query.expand_class_clusters = true;
+        let filter = ClassFilter::resolve(&query, &taxonomy);
+        assert_eq!(filter.requested, vec![25]);
+        assert_eq!(filter.classes(), vec![14, 18, 25, 35]);
+    }
+
+    #[test]
+    fn test_coordinated_expansion() {
+        let taxonomy = ClassTaxonomy::default();
+        let clothing = NiceClass::of(25);
+        assert_eq!(taxonomy.coordinated_with(clothing), vec![14, 18, 35]);
+        assert!(taxonomy.coordinated_with(NiceClass::of(35)).contains(&clothing));
+
+        let mut query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25, 35]));
+        query.expand_related_classes = true;
+        let filter = ClassFilter::resolve(&query, &taxonomy);
+
+        assert!(!filter.expanded_classes().contains(&clothing));
+        let class_14 = filter.expansions.iter().find(|e| e.class == 14).unwrap();
+        assert_eq!(class_14.reason, ExpansionReason::Coordinated { with: clothing });
+        assert!(filter.classes().contains(&NiceClass::of(9)));
+    }
+
+    #[test]
+    fn test_facets() {
+        let taxonomy = ClassTaxonomy::default();
+        let hit = |classes: &[u16]| {
+            let mut record = TrademarkRecord::new("001", "NIKE");
+            record.classes = NiceClass::of_all(classes);
+            CandidateHit {
+                record,
+                retrieval_score: 1.0,
+                risk_score: 0.0,
+                risk_probability: None,
+                flags: vec![],
+                review: None,
+                status_adjustment: None,
+                links: None,
+                family: Vec::new(),
+                dupont: None,
+                highlights: Vec::new(),
+            }
+        };
+        let hits = vec![hit(&[25]), hit(&[18, 25]), hit(&[9])];
+        let facets = taxonomy.facets(&hits);
+
+        assert_eq!(facets[0].cluster, "apparel-accessories");
+        assert_eq!(facets[0].count, 2);
+        assert!(facets.iter().any(|f| f.cluster == "technology" && f.count == 1));
+    }
+}
@@ -0,0 +1,236 @@
+//! Calendar dates on records.
+//!
+//! `Date` is a day count from the Unix epoch, so dates compare and
+//! subtract without a calendar library. It serializes as an ISO
+//! `YYYY-MM-DD` string and parses both that and the USPTO's `YYYYMMDD`
+//! form.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A calendar date (UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date(i64);
+
+impl Date {
+    /// The date a number of days after 1970-01-01.
+    pub fn from_days(days: i64) -> Self {
+        Self(days)
+    }
+
+    /// The date for a year, month and day; `None` if there is no such
+    /// day.
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> Option<Self> {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month)
+            .contains(&day)
+            .then(|| Self(days_from_civil(year, month, day)))
+    }
+
+    /// Today's date (UTC).
+    pub fn today() -> Self {
+        let days = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64 / 86_400)
+            .unwrap_or(0);
+        Self(days)
+    }
+
+    /// Parse a strict ISO `YYYY-MM-DD` date.
+    pub fn parse_iso(date: &str) -> Option<Self> {
+        let parts: Vec<&str> = date.split('-').collect();
+        let [y, m, d] = parts.as_slice() else {
+            return None;
+        };
+        if y.len() != 4 || m.len() != 2 || d.len() != 2 {
+            return None;
+        }
+        Self::from_ymd(y.parse().ok()?, m.parse().ok()?, d.parse().ok()?)
+    }
+
+    /// Parse a USPTO `YYYYMMDD` date.
+    pub fn parse_compact(date: &str) -> Option<Self> {
+        if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Self::from_ymd(date[..4].parse().ok()?, date[4..6].parse().ok()?, date[6..].parse().ok()?)
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        self.0
+    }
+
+    /// Year, month and day.
+    pub fn ymd(&self) -> (i64, u32, u32) {
+        civil_from_days(self.0)
+    }
+
+    /// The date `days` later (earlier when negative).
+    pub fn add_days(&self, days: i64) -> Self {
+        Self(self.0 + days)
+    }
+
+    /// Whole years from this date to `later`; zero if `later` is earlier.
+    pub fn years_until(&self, later: Date) -> u32 {
+        let (y1, m1, d1) = self.ymd();
+        let (y2, m2, d2) = later.ymd();
+        let years = y2 - y1 - i64::from((m2, d2) < (m1, d1));
+        years.max(0) as u32
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parse `YYYY-MM-DD`, an ISO timestamp starting with one, or
+    /// `YYYYMMDD`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let iso = match s.get(..10) {
+            Some(date) if s.as_bytes().get(10) == Some(&b'T') => date,
+            _ => s,
+        };
+        Self::parse_iso(iso)
+            .or_else(|| Self::parse_compact(s))
+            .ok_or_else(|| format!("Invalid date '{}' (YYYY-MM-DD or YYYYMMDD)", s))
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserialized from a string in any form `FromStr` accepts, or from a
+/// `YYYYMMDD` number.
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Number(u64),
+        }
+        let text = match Raw::deserialize(deserializer)? {
+            Raw::Text(text) => text,
+            Raw::Number(number) => number.to_string(),
+        };
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserialize an optional date, reading a blank string as no date.
+pub fn deserialize_optional<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Date>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Text(String),
+        Number(u64),
+    }
+    let text = match Option::<Raw>::deserialize(deserializer)? {
+        Some(Raw::Text(text)) if !text.trim().is_empty() => text,
+        Some(Raw::Number(number)) => number.to_string(),
+        _ => return Ok(None),
+    };
+    text.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Date {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Date".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string", "format": "date" })
+    }
+}
+
+// Howard Hinnant's civil calendar algorithms.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forms() {
+        let leap_day = Date::from_days(19_782);
+        assert_eq!(leap_day.to_string(), "2024-02-29");
+        assert_eq!("2024-02-29".parse(), Ok(leap_day));
+        assert_eq!("20240229".parse(), Ok(leap_day));
+        assert_eq!("2024-02-29T12:00:00Z".parse(), Ok(leap_day));
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("last year".parse::<Date>().is_err());
+        assert_eq!(Date::parse_iso("20240229"), None);
+
+        let json = r#"["2024-02-29", "20240229", 20240229]"#;
+        assert_eq!(serde_json::from_str::<Vec<Date>>(json).unwrap(), [leap_day; 3]);
+        assert_eq!(serde_json::to_string(&leap_day).unwrap(), "\"2024-02-29\"");
+    }
+
+    #[test]
+    fn test_deserialize_optional() {
+        #[derive(Deserialize)]
+        struct Dated {
+            #[serde(default, deserialize_with = "deserialize_optional")]
+            date: Option<Date>,
+        }
+        let date = |json: &str| serde_json::from_str::<Dated>(json).map(|d| d.date);
+        assert_eq!(date(r#"{"date": ""}"#).unwrap(), None);
+        assert_eq!(date(r#"{"date": "  "}"#).unwrap(), None);
+        assert_eq!(date(r#"{"date": null}"#).unwrap(), None);
+        assert_eq!(date("{}").unwrap(), None);
+        assert_eq!(date(r#"{"date": 20240229}"#).unwrap(), Some(Date::from_days(19_782)));
+        assert!(date(r#"{"date": "last year"}"#).is_err());
+    }
+
+    #[test]
+    fn test_years_until() {
+        let filed = Date::from_ymd(2016, 10, 15).unwrap();
+        assert_eq!(filed.years_until(Date::from_ymd(2026, 10, 14).unwrap()), 9);
+        assert_eq!(filed.years_until(Date::from_ymd(2026, 10, 15).unwrap()), 10);
+        assert_eq!(filed.years_until(Date::from_ymd(2000, 1, 1).unwrap()), 0);
+        assert_eq!(filed.add_days(1).ymd(), (2016, 10, 16));
+    }
+}
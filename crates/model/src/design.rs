@@ -0,0 +1,124 @@
+//! USPTO design search codes.
+//!
+//! Design codes classify the figurative elements of a mark on three
+//! levels: category, division, and section (e.g. `26.01.03`). Codes are
+//! accepted with or without dots (`260103`) and kept in dotted form. A
+//! shorter code such as `26.01` stands for every section beneath it.
+//! Two codes can be compared at any level: `26.01.03` and `26.01.21`
+//! share a division (circles) but not a section.
+
+use serde::{Deserialize, Serialize};
+
+/// A level of the design code hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DesignLevel {
+    /// First two digits (`26`, geometric figures)
+    Category,
+    /// First four digits (`26.01`, circles)
+    Division,
+    /// All six digits (`26.01.03`)
+    Section,
+}
+
+impl DesignLevel {
+    /// Level of a normalized code.
+    pub fn of(code: &str) -> Option<Self> {
+        match code.len() {
+            2 => Some(Self::Category),
+            5 => Some(Self::Division),
+            8 => Some(Self::Section),
+            _ => None,
+        }
+    }
+
+    /// Length of a normalized code at this level.
+    fn len(self) -> usize {
+        match self {
+            Self::Category => 2,
+            Self::Division => 5,
+            Self::Section => 8,
+        }
+    }
+}
+
+/// Normalize a design code to dotted form (`260103` → `26.01.03`).
+///
+/// Returns `None` unless the code has one to three two-digit levels.
+pub fn normalize_design_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    let digits: String = if code.contains('.') {
+        let levels: Vec<&str> = code.split('.').collect();
+        if levels.iter().any(|l| l.len() != 2) {
+            return None;
+        }
+        levels.concat()
+    } else {
+        code.to_string()
+    };
+
+    if digits.is_empty() || digits.len() > 6 || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let levels: Vec<&str> = (0..digits.len()).step_by(2).map(|i| &digits[i..i + 2]).collect();
+    Some(levels.join("."))
+}
+
+/// Check whether a (normalized) design code covers another: equal, or
+/// a higher level of it (`26.01` covers `26.01.03`).
+pub fn design_code_covers(code: &str, other: &str) -> bool {
+    other == code || (other.starts_with(code) && other[code.len()..].starts_with('.'))
+}
+
+/// A normalized design code cut to a higher level (`26.01.03` at
+/// `Division` is `26.01`); `None` if the code does not reach that level.
+pub fn design_code_at(code: &str, level: DesignLevel) -> Option<&str> {
+    DesignLevel::of(code).filter(|own| *own >= level).map(|_| &code[..level.len()])
+}
+
+/// The deepest level at which two normalized design codes agree.
+pub fn shared_design_level(code: &str, other: &str) -> Option<DesignLevel> {
+    [DesignLevel::Section, DesignLevel::Division, DesignLevel::Category]
+        .into_iter()
+        .find(|&level| {
+            design_code_at(code, level).is_some_and(|at| design_code_at(other, level) == Some(at))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_design_code() {
+        assert_eq!(normalize_design_code("26.01.03").as_deref(), Some("26.01.03"));
+        assert_eq!(normalize_design_code("260103").as_deref(), Some("26.01.03"));
+        assert_eq!(normalize_design_code(" 2601 ").as_deref(), Some("26.01"));
+        assert_eq!(normalize_design_code("26.1.3"), None);
+        assert_eq!(normalize_design_code("2601031"), None);
+        assert_eq!(normalize_design_code("AB.01"), None);
+    }
+
+    #[test]
+    fn test_design_code_covers() {
+        assert!(design_code_covers("26.01", "26.01.03"));
+        assert!(design_code_covers("26.01.03", "26.01.03"));
+        assert!(!design_code_covers("26.01.03", "26.01"));
+        assert!(!design_code_covers("26.0", "26.01.03"));
+    }
+
+    #[test]
+    fn test_design_levels() {
+        assert_eq!(DesignLevel::of("26.01"), Some(DesignLevel::Division));
+        assert_eq!(design_code_at("26.01.03", DesignLevel::Category), Some("26"));
+        assert_eq!(design_code_at("26.01", DesignLevel::Section), None);
+        assert_eq!(shared_design_level("26.01.03", "26.01.21"), Some(DesignLevel::Division));
+        assert_eq!(shared_design_level("26.01.03", "26.01.03"), Some(DesignLevel::Section));
+        assert_eq!(shared_design_level("26.01.03", "26.11"), Some(DesignLevel::Category));
+        assert_eq!(shared_design_level("26.01.03", "03.01.01"), None);
+    }
+}
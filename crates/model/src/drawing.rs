@@ -0,0 +1,152 @@
+//! How a mark is drawn.
+//!
+//! USPTO records give a mark drawing code whose first digit is the
+//! drawing type (`4000` for a standard character mark, `3000` for a
+//! design with words). Parsing accepts the full code or its first digit,
+//! as well as the type's name.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The drawing type of a mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MarkDrawingType {
+    /// Typed drawing, the standard character form before November 2003
+    /// (code 1)
+    Typed,
+    /// A design with no words, letters or numbers (code 2)
+    DesignOnly,
+    /// A design with words, letters or numbers (code 3)
+    DesignWithText,
+    /// Words, letters or numbers in standard characters (code 4)
+    StandardCharacter,
+    /// Words, letters or numbers in stylized form (code 5)
+    Stylized,
+    /// No drawing: sound, scent and other non-visual marks (code 6)
+    NonVisual,
+}
+
+impl MarkDrawingType {
+    /// All drawing types, in code order.
+    pub const ALL: [MarkDrawingType; 6] = [
+        Self::Typed,
+        Self::DesignOnly,
+        Self::DesignWithText,
+        Self::StandardCharacter,
+        Self::Stylized,
+        Self::NonVisual,
+    ];
+
+    /// The drawing type of a USPTO mark drawing code (`4000` or `4`).
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim();
+        if code.is_empty() || code.len() > 4 || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        match code.as_bytes()[0] {
+            b'1' => Some(Self::Typed),
+            b'2' => Some(Self::DesignOnly),
+            b'3' => Some(Self::DesignWithText),
+            b'4' => Some(Self::StandardCharacter),
+            b'5' => Some(Self::Stylized),
+            b'6' => Some(Self::NonVisual),
+            _ => None,
+        }
+    }
+
+    /// The USPTO drawing code digit.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Typed => 1,
+            Self::DesignOnly => 2,
+            Self::DesignWithText => 3,
+            Self::StandardCharacter => 4,
+            Self::Stylized => 5,
+            Self::NonVisual => 6,
+        }
+    }
+
+    /// Snake-case name, as serialized.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Typed => "typed",
+            Self::DesignOnly => "design_only",
+            Self::DesignWithText => "design_with_text",
+            Self::StandardCharacter => "standard_character",
+            Self::Stylized => "stylized",
+            Self::NonVisual => "non_visual",
+        }
+    }
+
+    /// Whether the drawing has a design element.
+    pub fn has_design(&self) -> bool {
+        matches!(self, Self::DesignOnly | Self::DesignWithText)
+    }
+
+    /// Whether the mark has words, letters or numbers.
+    pub fn has_text(&self) -> bool {
+        !matches!(self, Self::DesignOnly | Self::NonVisual)
+    }
+}
+
+impl fmt::Display for MarkDrawingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for MarkDrawingType {
+    type Err = String;
+
+    /// Parse a drawing code (`4000`) or a name (`standard_character`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['-', ' '], "_");
+        Self::from_code(&name)
+            .or_else(|| Self::ALL.into_iter().find(|t| t.name() == name))
+            .ok_or_else(|| format!("Unknown mark drawing type '{}'", s))
+    }
+}
+
+/// Deserialized from a name or drawing code, as a string or a number.
+impl<'de> Deserialize<'de> for MarkDrawingType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Number(u64),
+        }
+        let text = match Raw::deserialize(deserializer)? {
+            Raw::Text(text) => text,
+            Raw::Number(number) => number.to_string(),
+        };
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_drawing_types() {
+        assert_eq!(MarkDrawingType::from_code("4000"), Some(MarkDrawingType::StandardCharacter));
+        assert_eq!(MarkDrawingType::from_code("2"), Some(MarkDrawingType::DesignOnly));
+        assert_eq!(MarkDrawingType::from_code("0000"), None);
+        assert_eq!(MarkDrawingType::from_code("30001"), None);
+        assert_eq!("Design with text".parse(), Ok(MarkDrawingType::DesignWithText));
+        assert!("hologram".parse::<MarkDrawingType>().is_err());
+
+        let json = r#"["stylized", "5000", 5000]"#;
+        let types: Vec<MarkDrawingType> = serde_json::from_str(json).unwrap();
+        assert_eq!(types, [MarkDrawingType::Stylized; 3]);
+        assert_eq!(serde_json::to_string(&types[0]).unwrap(), "\"stylized\"");
+        for drawing_type in MarkDrawingType::ALL {
+            let code = drawing_type.code().to_string();
+            assert_eq!(MarkDrawingType::from_code(&code), Some(drawing_type));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! The du Pont likelihood-of-confusion factors.
+//!
+//! Examining attorneys and the TTAB weigh likelihood of confusion under
+//! the thirteen factors of *In re E. I. du Pont de Nemours & Co.*,
+//! 476 F.2d 1357 (C.C.P.A. 1973). A `DuPontAssessment` records which way
+//! each factor points for one hit, as far as the computed signals go;
+//! factors that need evidence outside the register are left for the
+//! attorney.
+
+use serde::{Deserialize, Serialize};
+
+/// One of the thirteen du Pont factors, in the decision's order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DuPontFactor {
+    /// Similarity of the marks in appearance, sound, connotation and
+    /// commercial impression
+    MarkSimilarity,
+    /// Similarity and nature of the goods or services
+    GoodsRelatedness,
+    /// Similarity of established trade channels
+    TradeChannels,
+    /// Conditions of sale: impulse or careful, sophisticated purchasing
+    PurchaserCare,
+    /// Fame of the prior mark
+    Fame,
+    /// Number and nature of similar marks in use on similar goods
+    SimilarMarksInUse,
+    /// Nature and extent of any actual confusion
+    ActualConfusion,
+    /// Concurrent use without evidence of actual confusion
+    ConcurrentUse,
+    /// Variety of goods on which the prior mark is used
+    VarietyOfGoods,
+    /// Market interface between the applicant and the prior owner
+    MarketInterface,
+    /// The applicant's right to exclude others from its mark
+    RightToExclude,
+    /// Extent of potential confusion: de minimis or substantial
+    ExtentOfConfusion,
+    /// Any other fact probative of the effect of use
+    OtherFacts,
+}
+
+impl DuPontFactor {
+    /// Every factor, in order.
+    pub const ALL: [Self; 13] = [
+        Self::MarkSimilarity,
+        Self::GoodsRelatedness,
+        Self::TradeChannels,
+        Self::PurchaserCare,
+        Self::Fame,
+        Self::SimilarMarksInUse,
+        Self::ActualConfusion,
+        Self::ConcurrentUse,
+        Self::VarietyOfGoods,
+        Self::MarketInterface,
+        Self::RightToExclude,
+        Self::ExtentOfConfusion,
+        Self::OtherFacts,
+    ];
+
+    /// The factor's number in the decision (1 - 13).
+    pub fn number(&self) -> u8 {
+        *self as u8 + 1
+    }
+
+    /// Short title ("Similarity of the marks").
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::MarkSimilarity => "Similarity of the marks",
+            Self::GoodsRelatedness => "Relatedness of the goods/services",
+            Self::TradeChannels => "Channels of trade",
+            Self::PurchaserCare => "Conditions of sale",
+            Self::Fame => "Fame of the prior mark",
+            Self::SimilarMarksInUse => "Similar marks in use",
+            Self::ActualConfusion => "Actual confusion",
+            Self::ConcurrentUse => "Concurrent use without confusion",
+            Self::VarietyOfGoods => "Variety of goods",
+            Self::MarketInterface => "Market interface",
+            Self::RightToExclude => "Right to exclude",
+            Self::ExtentOfConfusion => "Extent of potential confusion",
+            Self::OtherFacts => "Other probative facts",
+        }
+    }
+}
+
+/// Which way a factor points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FactorWeight {
+    /// Supports a likelihood of confusion
+    FavorsConfusion,
+    /// Considered, but points neither way
+    Neutral,
+    /// Weighs against a likelihood of confusion
+    AgainstConfusion,
+    /// Needs evidence the search does not have
+    NotAssessed,
+}
+
+impl FactorWeight {
+    /// Display label ("favors confusion").
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::FavorsConfusion => "favors confusion",
+            Self::Neutral => "neutral",
+            Self::AgainstConfusion => "weighs against confusion",
+            Self::NotAssessed => "not assessed",
+        }
+    }
+}
+
+/// The finding on one factor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FactorFinding {
+    /// The factor
+    pub factor: DuPontFactor,
+    /// Which way it points
+    pub weight: FactorWeight,
+    /// One or two sentences on why
+    pub finding: String,
+    /// Labels of the risk flags the finding rests on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signals: Vec<String>,
+}
+
+/// Findings on the du Pont factors for one hit, in factor order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DuPontAssessment {
+    pub findings: Vec<FactorFinding>,
+}
+
+impl DuPontAssessment {
+    /// The finding on a factor, if assessed.
+    pub fn finding(&self, factor: DuPontFactor) -> Option<&FactorFinding> {
+        self.findings.iter().find(|f| f.factor == factor)
+    }
+
+    /// How many factors point a given way.
+    pub fn count(&self, weight: FactorWeight) -> usize {
+        self.findings.iter().filter(|f| f.weight == weight).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factor_numbers() {
+        assert_eq!(DuPontFactor::MarkSimilarity.number(), 1);
+        assert_eq!(DuPontFactor::OtherFacts.number(), 13);
+        assert_eq!(
+            serde_json::to_string(&FactorWeight::FavorsConfusion).unwrap(),
+            "\"favors_confusion\""
+        );
+    }
+}
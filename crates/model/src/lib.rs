@@ -3,14 +3,70 @@
 //! This crate defines the fundamental types used throughout the system:
 //! - `TrademarkRecord`: The normalized trademark data from USPTO
 //! - `TrademarkStatus`: Live, Dead, Pending status
+//! - `StatusDetail`: granular status from USPTO status codes
+//! - `RecordBuilder`: building checked records
+//! - `ValidationIssue`: problems found by `TrademarkRecord::validate`
+//! - `Date`: filing, registration and status dates
 //! - `CandidateHit`: A search result with score
+//! - `SearchResult`: ranked hits for a query, with search metadata
 //! - `RiskFlag`: Types of trademark risks identified
+//! - `NiceClass`: a validated Nice class, with its title
+//! - `ClassTaxonomy`: Nice class clusters for filtering and faceting
+//! - Design search codes for figurative elements
+//! - `MarkDrawingType`: how a mark is drawn (standard character, design)
+//! - `SourceLinks`: TSDR/TESS links for a record
+//! - `Owner`: a record's owner, with entity type and address
+//! - `ProsecutionEvent`: a record's prosecution history
+//! - `Provenance`: the dataset, file and pipeline a record came from
+//! - `ScoreNormalization`: mapping backend relevance scores to 0.0 - 1.0
+//! - `DuPontAssessment`: a hit weighed on the du Pont confusion factors
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "postcard")]
+pub mod binary;
+pub mod builder;
+pub mod classes;
+pub mod date;
+pub mod design;
+pub mod drawing;
+pub mod dupont;
+pub mod links;
+pub mod owner;
+pub mod prosecution;
+pub mod provenance;
+pub mod ranking;
+pub mod retrieval;
+pub mod severity;
+pub mod status;
+pub mod validate;
+
+pub use builder::{RecordBuilder, RecordError};
+pub use classes::{
+    ClassCluster, ClassExpansion, ClassFilter, ClassKind, ClassTaxonomy, ClusterFacet,
+    ExpansionReason, NiceClass,
+};
+pub use date::Date;
+pub use drawing::MarkDrawingType;
+pub use dupont::{DuPontAssessment, DuPontFactor, FactorFinding, FactorWeight};
+pub use links::SourceLinks;
+pub use owner::{EntityType, Owner};
+pub use prosecution::ProsecutionEvent;
+pub use provenance::Provenance;
+pub use ranking::{compare_hits, ByRisk};
+pub use retrieval::ScoreNormalization;
+pub use severity::{SeverityError, SeverityTable};
+pub use status::StatusDetail;
+pub use validate::ValidationIssue;
 
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
 /// Status of a trademark registration.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "UPPERCASE")]
+#[non_exhaustive]
 pub enum TrademarkStatus {
     /// Active registration
     Live,
@@ -18,16 +74,12 @@ pub enum TrademarkStatus {
     Dead,
     /// Application in progress
     Pending,
-    /// Unknown status
+    /// Unknown status, including statuses added after this build
+    #[default]
+    #[serde(other)]
     Unknown,
 }
 
-impl Default for TrademarkStatus {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl From<&str> for TrademarkStatus {
     fn from(s: &str) -> Self {
         match s.to_uppercase().as_str() {
@@ -39,11 +91,28 @@ impl From<&str> for TrademarkStatus {
     }
 }
 
+impl std::str::FromStr for TrademarkStatus {
+    type Err = String;
+
+    /// Parse a status by name, rejecting statuses this build does not know
+    /// (unlike `From<&str>`, which maps them to `Unknown`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "LIVE" => Ok(Self::Live),
+            "DEAD" => Ok(Self::Dead),
+            "PENDING" => Ok(Self::Pending),
+            "UNKNOWN" => Ok(Self::Unknown),
+            _ => Err(format!("Unknown status '{}' (LIVE, DEAD, PENDING or UNKNOWN)", s)),
+        }
+    }
+}
+
 /// A normalized trademark record from USPTO data.
 ///
 /// This is the canonical representation consumed by all downstream systems.
 /// Produced by `ilegalflow-data` pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TrademarkRecord {
     /// USPTO serial number (8 digits, zero-padded)
     pub serial_number: String,
@@ -60,41 +129,80 @@ pub struct TrademarkRecord {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mark_text_normalized: Option<String>,
 
+    /// Latin transliteration of a non-Latin mark, as filed (e.g. "NIKA"
+    /// for НИКА)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<String>,
+
     /// Current status
     #[serde(default)]
     pub status: TrademarkStatus,
 
-    /// USPTO status code (e.g., "800" for dead)
+    /// USPTO status code (e.g., "602" for abandoned); see `StatusDetail`
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub status_code: Option<String>,
 
     /// Nice classification codes
     #[serde(default)]
-    pub classes: Vec<u16>,
+    pub classes: Vec<NiceClass>,
+
+    /// Whether `classes` was inferred from the goods/services description
+    /// rather than taken from the filing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub classes_inferred: bool,
 
     /// Goods and services description
     #[serde(default)]
     pub goods_services: String,
 
-    /// Owner/registrant name
-    #[serde(default)]
-    pub owner_name: String,
+    /// Owner/registrant; read from a bare `owner_name` string too
+    #[serde(default, alias = "owner_name", skip_serializing_if = "Owner::is_empty")]
+    pub owner: Owner,
 
-    /// Filing date (ISO format)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub filing_date: Option<String>,
+    /// Filing date
+    #[serde(
+        default,
+        deserialize_with = "date::deserialize_optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub filing_date: Option<Date>,
 
-    /// Registration date (ISO format)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub registration_date: Option<String>,
+    /// Registration date
+    #[serde(
+        default,
+        deserialize_with = "date::deserialize_optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub registration_date: Option<Date>,
 
-    /// Status change date (ISO format)
+    /// Status change date
+    #[serde(
+        default,
+        deserialize_with = "date::deserialize_optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_date: Option<Date>,
+
+    /// How the mark is drawn, when known
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub status_date: Option<String>,
+    pub drawing_type: Option<MarkDrawingType>,
 
-    /// Whether this is a design mark (has visual elements)
-    #[serde(default)]
-    pub is_design_mark: bool,
+    /// Design search codes of the mark's figurative elements (`26.01.03`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub design_codes: Vec<String>,
+
+    /// Prosecution history, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prosecution_events: Vec<ProsecutionEvent>,
+
+    /// Where the record was ingested from
+    #[serde(
+        default,
+        rename = "ilegalflow:provenance",
+        alias = "provenance",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub provenance: Option<Provenance>,
 }
 
 impl TrademarkRecord {
@@ -105,28 +213,152 @@ impl TrademarkRecord {
             registration_number: None,
             mark_text: mark_text.into(),
             mark_text_normalized: None,
+            transliteration: None,
             status: TrademarkStatus::Unknown,
             status_code: None,
             classes: Vec::new(),
+            classes_inferred: false,
             goods_services: String::new(),
-            owner_name: String::new(),
+            owner: Owner::default(),
             filing_date: None,
             registration_date: None,
             status_date: None,
-            is_design_mark: false,
+            drawing_type: None,
+            design_codes: Vec::new(),
+            prosecution_events: Vec::new(),
+            provenance: None,
         }
     }
 
+    /// A builder for a record, checked on `build()`.
+    pub fn builder() -> RecordBuilder {
+        RecordBuilder::default()
+    }
+
     /// Get the effective mark text for matching (normalized if available).
     pub fn effective_mark_text(&self) -> &str {
         self.mark_text_normalized
             .as_deref()
             .unwrap_or(&self.mark_text)
     }
+
+    /// Whole years from filing to `date`; `None` without a filing date.
+    pub fn age_at(&self, date: Date) -> Option<u32> {
+        self.filing_date.map(|filed| filed.years_until(date))
+    }
+
+    /// Whole years from registration to `date`; `None` if unregistered.
+    pub fn registered_years_at(&self, date: Date) -> Option<u32> {
+        self.registration_date.map(|registered| registered.years_until(date))
+    }
+
+    /// Whether the mark has been registered for at least `years` as of
+    /// today.
+    pub fn is_registered_over(&self, years: u32) -> bool {
+        self.registered_years_at(Date::today()).is_some_and(|age| age >= years)
+    }
+
+    /// The record's design codes, normalized and cut to `level`, without
+    /// duplicates; codes that do not reach the level are dropped.
+    pub fn design_codes_at(&self, level: design::DesignLevel) -> Vec<String> {
+        let mut codes: Vec<String> = self
+            .design_codes
+            .iter()
+            .filter_map(|code| design::normalize_design_code(code))
+            .filter_map(|code| design::design_code_at(&code, level).map(str::to_string))
+            .collect();
+        codes.sort();
+        codes.dedup();
+        codes
+    }
+
+    /// Detailed status from the status code, when it has a known one.
+    pub fn status_detail(&self) -> Option<StatusDetail> {
+        self.status_code.as_deref().and_then(StatusDetail::from_code)
+    }
+
+    /// Whether this is a design mark: its drawing has a design element.
+    /// False when the drawing type is unknown.
+    pub fn is_design_mark(&self) -> bool {
+        self.drawing_type.is_some_and(|t| t.has_design())
+    }
+
+    /// Whether the mark was ever refused for likelihood of confusion
+    /// (Section 2(d)).
+    pub fn has_2d_refusal(&self) -> bool {
+        self.prosecution_events.iter().any(ProsecutionEvent::is_2d_refusal)
+    }
+}
+
+/// A record identified by USPTO number, in canonical form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "number", rename_all = "snake_case")]
+pub enum RecordId {
+    /// 8-digit serial number
+    Serial(String),
+    /// 7-digit registration number
+    Registration(String),
+}
+
+impl RecordId {
+    /// Serial number, zero-padded and stripped of separators.
+    pub fn serial(number: &str) -> Option<Self> {
+        links::canonical_number(number, links::SERIAL_DIGITS).map(Self::Serial)
+    }
+
+    /// Registration number, zero-padded and stripped of separators.
+    pub fn registration(number: &str) -> Option<Self> {
+        links::canonical_number(number, links::REGISTRATION_DIGITS).map(Self::Registration)
+    }
+
+    /// Guess the kind of number: 8 digits is a serial number, fewer a
+    /// registration number (`5,123,456`).
+    pub fn parse(number: &str) -> Option<Self> {
+        let digits = number.chars().filter(char::is_ascii_digit).count();
+        if digits == links::SERIAL_DIGITS {
+            Self::serial(number)
+        } else {
+            Self::registration(number)
+        }
+    }
+
+    /// The canonical number.
+    pub fn number(&self) -> &str {
+        match self {
+            Self::Serial(number) | Self::Registration(number) => number,
+        }
+    }
+}
+
+impl std::fmt::Display for RecordId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serial(number) => write!(f, "serial {}", number),
+            Self::Registration(number) => write!(f, "registration {}", number),
+        }
+    }
+}
+
+/// Which code of a multi-code phonetic algorithm (Double Metaphone).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CodeVariant {
+    Primary,
+    Alternate,
+}
+
+/// The code variants that matched on each side of a phonetic match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MatchedVariant {
+    pub query: CodeVariant,
+    pub mark: CodeVariant,
 }
 
 /// A candidate hit from search retrieval.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CandidateHit {
     /// The trademark record
     pub record: TrademarkRecord,
@@ -138,24 +370,175 @@ pub struct CandidateHit {
     #[serde(default)]
     pub risk_score: f32,
 
+    /// Calibrated probability of conflict (0.0 - 1.0), when the
+    /// re-ranker has a calibration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_probability: Option<f32>,
+
     /// Risk flags identified
     #[serde(default)]
     pub flags: Vec<RiskFlag>,
+
+    /// Reviewer annotation applied by an override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<ReviewNote>,
+
+    /// Scaling of the risk score by the record's status, when applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_adjustment: Option<StatusAdjustment>,
+
+    /// Links to the record on USPTO sites, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<SourceLinks>,
+
+    /// Other filings of the same mark by the same owner, grouped under
+    /// this hit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub family: Vec<TrademarkRecord>,
+
+    /// The hit weighed on the du Pont factors, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dupont: Option<DuPontAssessment>,
+
+    /// Characters of the query and mark behind each flag, for
+    /// highlighting
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<MatchSpans>,
+}
+
+/// Risk scaled by the status of the record (a dead mark with the same
+/// name is far less of a concern than a live one).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatusAdjustment {
+    /// Status of the record
+    pub status: TrademarkStatus,
+    /// Multiplier applied to the risk score
+    pub multiplier: f32,
+}
+
+/// The parts of the query and a mark that raised a flag. Ranges are char
+/// offsets (not bytes) into the marks as entered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MatchSpans {
+    /// Label of the flag
+    pub flag: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranges_in_query: Vec<Range<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranges_in_mark: Vec<Range<usize>>,
+}
+
+/// A reviewer's verdict on a specific hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewVerdict {
+    /// Not a concern for this client; hide from results
+    Suppress,
+    /// Keep at the top of results
+    Pin,
+}
+
+/// Reviewer annotation attached to a hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReviewNote {
+    /// The reviewer's verdict
+    pub verdict: ReviewVerdict,
+
+    /// Free-text note for the client file
+    #[serde(default)]
+    pub note: String,
+
+    /// Reviewer name or id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewer: Option<String>,
 }
 
 /// Types of trademark risk flags.
+///
+/// A `type` this build does not know parses as `Unrecognized`; a known
+/// `type` with a malformed `detail` is an error.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "type", content = "detail")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(remote = "Self", tag = "type", content = "detail")]
+#[non_exhaustive]
 pub enum RiskFlag {
     /// Exact text match
     ExactMatch,
 
+    /// Same text once leetspeak or look-alike substitutions are undone
+    /// (N1KE vs NIKE)
+    ObfuscatedMatch {
+        /// Substitutions applied, e.g. "1 -> I"
+        substitutions: Vec<String>,
+    },
+
+    /// Same or nearly the same text once numerals and number words are
+    /// swapped (4EVER vs FOREVER)
+    NumberWordMatch {
+        /// Edit distance after substitution (0 = equivalent)
+        distance: u8,
+        /// Substitutions applied, e.g. "4 -> FOR"
+        transformations: Vec<String>,
+    },
+
+    /// Same mark once house marks and designators are stripped (ACME
+    /// NIKE SERIES vs NIKE)
+    HouseMarkMatch {
+        /// House marks stripped from either mark
+        house_marks: Vec<String>,
+        /// Entity and product-line designators stripped from either mark
+        designators: Vec<String>,
+    },
+
+    /// Same words once run-together compounds are split (SUNPOWER vs
+    /// SUN POWER)
+    CompoundMatch {
+        /// Splits applied, e.g. "SUNPOWER -> SUN POWER"
+        splits: Vec<String>,
+    },
+
+    /// One mark is the initials of the other (IBM vs INTERNATIONAL
+    /// BUSINESS MACHINES)
+    AcronymMatch {
+        /// The acronym, as written in its mark
+        acronym: String,
+        /// The words it abbreviates
+        expansion: String,
+    },
+
+    /// Direct translation of the query (LUPO vs WOLF), under the
+    /// doctrine of foreign equivalents
+    ForeignEquivalent {
+        /// Language of the translated words (e.g. "Italian")
+        language: String,
+        /// Shared English meaning
+        translation: String,
+    },
+
     /// Phonetically similar (sounds like)
     PhoneticMatch {
         /// Algorithm used (soundex, metaphone, etc.)
         algorithm: String,
         /// The phonetic code that matched
         code: String,
+        /// Which code variants matched (Double Metaphone only)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        variant: Option<MatchedVariant>,
+    },
+
+    /// Sounds nearly alike: phoneme sequences within a phoneme or two
+    /// (NIKE vs NIKEY) without sharing a phonetic code
+    NearPhoneticMatch {
+        /// Phoneme edit distance; near phonemes count half
+        distance: f32,
+        /// Phoneme similarity (0.0 - 1.0)
+        similarity: f32,
+        /// Phonemes of the query and the mark
+        phonemes: (String, String),
     },
 
     /// Similar spelling (edit distance)
@@ -164,10 +547,38 @@ pub enum RiskFlag {
         distance: u8,
     },
 
+    /// High string similarity score
+    StringSimilarity {
+        /// Similarity metric (e.g. "jaro_winkler")
+        metric: String,
+        /// Similarity score (0.0 - 1.0)
+        score: f32,
+    },
+
+    /// Similar syllable by syllable (MICROSOFT vs MIKROSOFT)
+    SyllableMatch {
+        /// Alignment score (0.0 - 1.0)
+        score: f32,
+        /// Aligned syllables (query, mark); an empty string marks a gap
+        aligned: Vec<(String, String)>,
+    },
+
     /// Same Nice classification
     ClassOverlap {
         /// Overlapping class numbers
-        classes: Vec<u16>,
+        classes: Vec<NiceClass>,
+        /// Either side's classes were inferred from its goods/services
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        inferred: bool,
+    },
+
+    /// Classes that differ but are related (clothing and clothing
+    /// retail)
+    RelatedClasses {
+        /// Related class pairs (query class, mark class)
+        pairs: Vec<(NiceClass, NiceClass)>,
+        /// Grade of the closest pair (0.0 - 1.0)
+        relatedness: f32,
     },
 
     /// Similar goods/services description
@@ -176,62 +587,322 @@ pub enum RiskFlag {
         similarity: f32,
     },
 
+    /// Shared design search codes
+    DesignCodeOverlap {
+        /// Overlapping design codes
+        codes: Vec<String>,
+    },
+
     /// Dominant term match
     DominantTermMatch {
-        /// The dominant term that matched
+        /// The dominant term that matched (normalized element)
         term: String,
+        /// Spelling transformations applied to reach the match
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        transformations: Vec<String>,
+    },
+
+    /// Both marks contain a famous mark, which is protected beyond its
+    /// classes
+    FamousMark {
+        /// The famous mark, as usually written
+        mark: String,
+        /// Owner of the famous mark
+        owner: String,
+        /// The marks share no class, so only fame makes them conflict
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        cross_class: bool,
     },
 
-    /// Well-known/famous mark
-    FamousMark,
+    /// The query could dilute a famous mark it contains, whether or not
+    /// confusion is likely
+    DilutionRisk {
+        /// How the famous mark would be diluted
+        kind: DilutionKind,
+        /// The famous mark
+        famous: String,
+        /// Dilution score from fame, similarity and class distance
+        /// (0.0 - 1.0)
+        score: f32,
+    },
+
+    /// The mark's owner holds other marks similar to the query, which
+    /// may make up a family of marks
+    OwnerConflict {
+        /// The owner
+        owner: String,
+        /// The owner's other similar marks
+        related_marks: Vec<String>,
+    },
 
-    /// Common law usage concern
-    CommonLawRisk,
+    /// Evidence of unregistered use of a similar mark, which may carry
+    /// common law rights beyond its registration
+    CommonLawRisk {
+        /// Where the use was found
+        source: String,
+        /// What was found (e.g. a business or domain name)
+        evidence: String,
+        /// Confidence that the sighting is use of the mark (0.0 - 1.0)
+        confidence: f32,
+    },
+
+    /// A design mark, whose figurative elements the text comparison does
+    /// not cover
+    DesignElementRisk {
+        /// Design codes the query shares with the mark
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        shared: Vec<String>,
+        /// The mark's design codes
+        codes: Vec<String>,
+    },
+
+    /// The mark died recently, so it may be revived or still carry
+    /// goodwill
+    RecentlyDead {
+        /// Whole months since the status date
+        months: u32,
+    },
+
+    /// A flag this build does not know, kept as sent so it round-trips
+    #[serde(untagged)]
+    Unrecognized {
+        /// The flag's `type`
+        #[serde(rename = "type")]
+        kind: String,
+        /// The flag's `detail`, unparsed
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        detail: serde_json::Value,
+    },
+}
+
+impl Serialize for RiskFlag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RiskFlag::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RiskFlag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let kind = value
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+        if !Self::KINDS.contains(&kind) {
+            return Ok(Self::Unrecognized {
+                kind: kind.to_string(),
+                detail: value.get("detail").cloned().unwrap_or_default(),
+            });
+        }
+
+        // The derived impl would fall back to `Unrecognized` on a bad detail
+        match RiskFlag::deserialize(&value).map_err(D::Error::custom)? {
+            Self::Unrecognized { .. } => {
+                Err(D::Error::custom(format!("invalid detail for risk flag {}", kind)))
+            }
+            flag => Ok(flag),
+        }
+    }
 }
 
 impl RiskFlag {
+    /// Serialized `type` of every variant this build knows.
+    pub const KINDS: [&'static str; 23] = [
+        "ExactMatch",
+        "ObfuscatedMatch",
+        "NumberWordMatch",
+        "HouseMarkMatch",
+        "CompoundMatch",
+        "AcronymMatch",
+        "ForeignEquivalent",
+        "PhoneticMatch",
+        "NearPhoneticMatch",
+        "FuzzyMatch",
+        "StringSimilarity",
+        "SyllableMatch",
+        "ClassOverlap",
+        "RelatedClasses",
+        "GoodsServicesSimilar",
+        "DesignCodeOverlap",
+        "DominantTermMatch",
+        "FamousMark",
+        "DilutionRisk",
+        "OwnerConflict",
+        "CommonLawRisk",
+        "DesignElementRisk",
+        "RecentlyDead",
+    ];
+
+    /// The variant name, as serialized in `type`.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::ExactMatch => "ExactMatch",
+            Self::ObfuscatedMatch { .. } => "ObfuscatedMatch",
+            Self::NumberWordMatch { .. } => "NumberWordMatch",
+            Self::HouseMarkMatch { .. } => "HouseMarkMatch",
+            Self::CompoundMatch { .. } => "CompoundMatch",
+            Self::AcronymMatch { .. } => "AcronymMatch",
+            Self::ForeignEquivalent { .. } => "ForeignEquivalent",
+            Self::PhoneticMatch { .. } => "PhoneticMatch",
+            Self::NearPhoneticMatch { .. } => "NearPhoneticMatch",
+            Self::FuzzyMatch { .. } => "FuzzyMatch",
+            Self::StringSimilarity { .. } => "StringSimilarity",
+            Self::SyllableMatch { .. } => "SyllableMatch",
+            Self::ClassOverlap { .. } => "ClassOverlap",
+            Self::RelatedClasses { .. } => "RelatedClasses",
+            Self::GoodsServicesSimilar { .. } => "GoodsServicesSimilar",
+            Self::DesignCodeOverlap { .. } => "DesignCodeOverlap",
+            Self::DominantTermMatch { .. } => "DominantTermMatch",
+            Self::FamousMark { .. } => "FamousMark",
+            Self::DilutionRisk { .. } => "DilutionRisk",
+            Self::OwnerConflict { .. } => "OwnerConflict",
+            Self::CommonLawRisk { .. } => "CommonLawRisk",
+            Self::DesignElementRisk { .. } => "DesignElementRisk",
+            Self::RecentlyDead { .. } => "RecentlyDead",
+            Self::Unrecognized { kind, .. } => kind,
+        }
+    }
+
     /// Get a human-readable label for this flag.
     pub fn label(&self) -> &'static str {
         match self {
             Self::ExactMatch => "Exact Match",
+            Self::ObfuscatedMatch { .. } => "Disguised Spelling",
+            Self::NumberWordMatch { .. } => "Number Spelled Out",
+            Self::HouseMarkMatch { .. } => "Same Apart from House Mark",
+            Self::CompoundMatch { .. } => "Words Run Together",
+            Self::AcronymMatch { .. } => "Acronym Match",
+            Self::ForeignEquivalent { .. } => "Foreign Equivalent",
             Self::PhoneticMatch { .. } => "Sounds Similar",
+            Self::NearPhoneticMatch { .. } => "Sounds Nearly Alike",
             Self::FuzzyMatch { .. } => "Spelled Similarly",
+            Self::StringSimilarity { .. } => "Similar Spelling",
+            Self::SyllableMatch { .. } => "Similar Syllables",
             Self::ClassOverlap { .. } => "Same Class",
+            Self::RelatedClasses { .. } => "Related Classes",
+            Self::DesignCodeOverlap { .. } => "Same Design Elements",
             Self::GoodsServicesSimilar { .. } => "Similar Goods/Services",
             Self::DominantTermMatch { .. } => "Dominant Term Match",
-            Self::FamousMark => "Famous Mark",
-            Self::CommonLawRisk => "Common Law Risk",
+            Self::FamousMark { .. } => "Famous Mark",
+            Self::DilutionRisk { .. } => "Dilution Risk",
+            Self::OwnerConflict { .. } => "Owner's Similar Marks",
+            Self::CommonLawRisk { .. } => "Common Law Risk",
+            Self::DesignElementRisk { .. } => "Design Elements",
+            Self::RecentlyDead { .. } => "Recently Dead",
+            Self::Unrecognized { .. } => "Unrecognized Flag",
         }
     }
 
-    /// Get severity weight (higher = more concerning).
+    /// Get severity weight (higher = more concerning), from the default
+    /// `SeverityTable`.
     pub fn severity(&self) -> f32 {
-        match self {
-            Self::ExactMatch => 1.0,
-            Self::FamousMark => 0.95,
-            Self::PhoneticMatch { .. } => 0.8,
-            Self::DominantTermMatch { .. } => 0.7,
-            Self::ClassOverlap { .. } => 0.6,
-            Self::FuzzyMatch { distance } => 0.5 - (*distance as f32 * 0.1),
-            Self::GoodsServicesSimilar { similarity } => *similarity * 0.5,
-            Self::CommonLawRisk => 0.4,
+        SeverityTable::DEFAULT.severity(self)
+    }
+}
+
+/// How a mark dilutes a famous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DilutionKind {
+    /// Weakens the famous mark's link to a single source
+    Blurring,
+    /// Harms its reputation through unsavory goods or services
+    Tarnishment,
+}
+
+/// What a search is for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SearchIntent {
+    /// Full clearance analysis with every rule
+    #[default]
+    Clearance,
+    /// Quick screen for exact and near-exact conflicts only
+    Knockout,
+}
+
+impl std::str::FromStr for SearchIntent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "clearance" => Ok(Self::Clearance),
+            "knockout" => Ok(Self::Knockout),
+            other => Err(format!("Unknown search intent '{}' (clearance, knockout)", other)),
         }
     }
 }
 
 /// Query parameters for trademark search.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SearchQuery {
-    /// The mark text to search for
+    /// The mark text to search for (`*` at term start/end truncates)
     pub mark_text: String,
 
+    /// Append suffix truncation to short single-word marks
+    #[serde(default)]
+    pub auto_truncate: bool,
+
+    /// Free text matched against the owner name field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_query: Option<String>,
+
+    /// Free text matched against the goods/services field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goods_services_query: Option<String>,
+
     /// Optional Nice classes to filter by
     #[serde(default)]
-    pub classes: Vec<u16>,
+    pub classes: Vec<NiceClass>,
+
+    /// Whether `classes` was inferred from the goods/services query
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub classes_inferred: bool,
 
-    /// Filter by status
+    /// Widen the class filter to every class sharing a cluster
     #[serde(default)]
-    pub status_filter: Option<TrademarkStatus>,
+    pub expand_class_clusters: bool,
+
+    /// Widen the class filter to coordinated (related) classes
+    #[serde(default)]
+    pub expand_related_classes: bool,
+
+    /// Design search codes; matches marks sharing any of them (a
+    /// category or division code covers the codes beneath it)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub design_codes: Vec<String>,
+
+    /// Owners whose marks are excluded (e.g. the client's own portfolio)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_owners: Vec<String>,
+
+    /// Serial numbers excluded from results
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_serials: Vec<String>,
+
+    /// Filter by status (any of; empty = no filter)
+    ///
+    /// Also accepts a single status or null for compatibility with the
+    /// former `Option<TrademarkStatus>` field.
+    #[serde(default, deserialize_with = "deserialize_status_filter")]
+    pub status_filter: Vec<TrademarkStatus>,
+
+    /// Only marks filed on or after this date (ISO YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filed_after: Option<String>,
+
+    /// Only marks filed on or before this date (ISO YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filed_before: Option<String>,
+
+    /// Only marks whose status changed on or after this date (ISO YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_changed_after: Option<String>,
 
     /// Maximum results to retrieve
     #[serde(default = "default_limit")]
@@ -244,6 +915,21 @@ pub struct SearchQuery {
     /// Enable fuzzy matching
     #[serde(default = "default_true")]
     pub fuzzy: bool,
+
+    /// Full clearance or knockout screen
+    #[serde(default)]
+    pub intent: SearchIntent,
+}
+
+/// Replace full-text operator characters with spaces so free text
+/// (e.g. "Apple, Inc.") cannot alter the query structure.
+pub fn clean_free_text(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphanumeric() || c == '\'' { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 fn default_limit() -> usize {
@@ -254,6 +940,55 @@ fn default_true() -> bool {
     true
 }
 
+fn deserialize_status_filter<'de, D>(deserializer: D) -> Result<Vec<TrademarkStatus>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    // Strict, so a newer status cannot silently become a filter on `UNKNOWN`
+    let names = match Option::<OneOrMany>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(OneOrMany::One(name)) => vec![name],
+        Some(OneOrMany::Many(names)) => names,
+    };
+    names
+        .iter()
+        .map(|name| name.parse().map_err(serde::de::Error::custom))
+        .collect()
+}
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        Self {
+            mark_text: String::new(),
+            auto_truncate: false,
+            owner_query: None,
+            goods_services_query: None,
+            classes: Vec::new(),
+            classes_inferred: false,
+            expand_class_clusters: false,
+            expand_related_classes: false,
+            design_codes: Vec::new(),
+            exclude_owners: Vec::new(),
+            exclude_serials: Vec::new(),
+            status_filter: Vec::new(),
+            filed_after: None,
+            filed_before: None,
+            status_changed_after: None,
+            limit: default_limit(),
+            phonetic: true,
+            fuzzy: true,
+            intent: SearchIntent::Clearance,
+        }
+    }
+}
+
 impl SearchQuery {
     pub fn new(mark_text: impl Into<String>) -> Self {
         Self {
@@ -262,7 +997,7 @@ impl SearchQuery {
         }
     }
 
-    pub fn with_classes(mut self, classes: Vec<u16>) -> Self {
+    pub fn with_classes(mut self, classes: Vec<NiceClass>) -> Self {
         self.classes = classes;
         self
     }
@@ -271,6 +1006,114 @@ impl SearchQuery {
         self.limit = limit;
         self
     }
+
+    pub fn with_statuses(mut self, statuses: Vec<TrademarkStatus>) -> Self {
+        self.status_filter = statuses;
+        self
+    }
+
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner_query = Some(owner.into());
+        self
+    }
+
+    pub fn with_goods_services(mut self, goods_services: impl Into<String>) -> Self {
+        self.goods_services_query = Some(goods_services.into());
+        self
+    }
+
+    pub fn with_intent(mut self, intent: SearchIntent) -> Self {
+        self.intent = intent;
+        self
+    }
+
+    /// Query for marks that may conflict with an existing record.
+    ///
+    /// Searches the record's mark text in its classes, excluding the
+    /// record itself and marks of the same owner. A design-only mark is
+    /// searched by its design code divisions instead. The goods
+    /// description is not used as a filter: a full description matches
+    /// almost nothing, and class coverage already reflects it.
+    pub fn for_record(record: &TrademarkRecord) -> Self {
+        // Wildcards and operators in a stored mark are literal characters
+        let mut query = Self::new(clean_free_text(&record.mark_text))
+            .with_classes(record.classes.clone());
+        if query.mark_text.is_empty() {
+            query.design_codes = record.design_codes_at(design::DesignLevel::Division);
+        }
+        query.classes_inferred = record.classes_inferred;
+        query.exclude_serials = vec![record.serial_number.clone()];
+        if !record.owner.is_empty() {
+            query.exclude_owners = vec![record.owner.name.clone()];
+        }
+        query
+    }
+}
+
+/// Ranked hits for a query, with how they were found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SearchResult {
+    /// The query that was executed
+    pub query: SearchQuery,
+
+    /// Name of the backend that retrieved the candidates
+    #[serde(default)]
+    pub backend: String,
+
+    /// Time spent in retrieval, in milliseconds
+    #[serde(default)]
+    pub retrieval_latency_ms: u64,
+
+    /// Records matching the query in all, beyond the retrieval limit,
+    /// when the backend reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_available: Option<usize>,
+
+    /// Re-ranker profile or preset applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    /// Risk-ranked hits
+    pub hits: Vec<CandidateHit>,
+}
+
+impl SearchResult {
+    /// A result with no search metadata.
+    pub fn new(query: SearchQuery, hits: Vec<CandidateHit>) -> Self {
+        Self {
+            query,
+            backend: String::new(),
+            retrieval_latency_ms: 0,
+            total_available: None,
+            profile: None,
+            hits,
+        }
+    }
+
+    /// Set the backend name.
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.backend = backend.into();
+        self
+    }
+
+    /// Set the retrieval latency.
+    pub fn with_retrieval_latency_ms(mut self, latency_ms: u64) -> Self {
+        self.retrieval_latency_ms = latency_ms;
+        self
+    }
+
+    /// Set the total count of matching records.
+    pub fn with_total_available(mut self, total: usize) -> Self {
+        self.total_available = Some(total);
+        self
+    }
+
+    /// Set the profile applied.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -294,11 +1137,178 @@ mod tests {
         assert_eq!(parsed.mark_text, "ACME");
     }
 
+    #[test]
+    fn test_record_owner() {
+        let json = r#"{"serial_number": "001", "mark_text": "NIKE", "owner_name": "Nike, Inc."}"#;
+        let record: TrademarkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.owner, Owner::new("Nike, Inc."));
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["owner"]["name"], "Nike, Inc.");
+        let parsed: TrademarkRecord = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.owner, record.owner);
+    }
+
+    #[test]
+    fn test_record_classes() {
+        let json = r#"{"serial_number": "001", "mark_text": "ACME", "classes": [25, "035", "A"]}"#;
+        let record: TrademarkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.classes, [NiceClass::of(25), NiceClass::of(35), NiceClass::A]);
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["classes"], serde_json::json!([25, 35, "A"]));
+
+        let json = r#"{"serial_number": "001", "mark_text": "ACME", "classes": [25, 99]}"#;
+        assert!(serde_json::from_str::<TrademarkRecord>(json).is_err());
+    }
+
+    #[test]
+    fn test_record_dates() {
+        let json = r#"{"serial_number": "72000001", "mark_text": "NIKE",
+                       "filing_date": "19710618", "registration_date": "1974-01-22"}"#;
+        let record: TrademarkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.filing_date, Date::from_ymd(1971, 6, 18));
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["filing_date"], "1971-06-18");
+
+        // Blank dates are missing, not invalid
+        let json = r#"{"serial_number": "72000001", "mark_text": "NIKE",
+                       "filing_date": "", "status_date": " "}"#;
+        let blank: TrademarkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!((blank.filing_date, blank.status_date), (None, None));
+
+        let date = Date::from_ymd(2024, 1, 21).unwrap();
+        assert_eq!(record.age_at(date), Some(52));
+        assert_eq!(record.registered_years_at(date), Some(49));
+        assert!(record.is_registered_over(50));
+        assert!(!TrademarkRecord::new("001", "ACME").is_registered_over(0));
+    }
+
+    #[test]
+    fn test_status_filter_compat() {
+        let parse = |json: &str| serde_json::from_str::<SearchQuery>(json).unwrap().status_filter;
+
+        assert!(parse(r#"{"mark_text": "NIKE"}"#).is_empty());
+        assert!(parse(r#"{"mark_text": "NIKE", "status_filter": null}"#).is_empty());
+        assert_eq!(
+            parse(r#"{"mark_text": "NIKE", "status_filter": "LIVE"}"#),
+            vec![TrademarkStatus::Live]
+        );
+        assert_eq!(
+            parse(r#"{"mark_text": "NIKE", "status_filter": ["LIVE", "PENDING"]}"#),
+            vec![TrademarkStatus::Live, TrademarkStatus::Pending]
+        );
+
+        // Unknown statuses are rejected rather than read as UNKNOWN
+        let query = r#"{"mark_text": "NIKE", "status_filter": ["LIVE", "SUSPENDED"]}"#;
+        assert!(serde_json::from_str::<SearchQuery>(query).is_err());
+        assert_eq!("unknown".parse(), Ok(TrademarkStatus::Unknown));
+    }
+
+    #[test]
+    fn test_record_id() {
+        assert_eq!(RecordId::parse("97123456"), Some(RecordId::Serial("97123456".into())));
+        assert_eq!(RecordId::parse("5,123,456"), Some(RecordId::Registration("5123456".into())));
+        assert_eq!(RecordId::parse("978952"), Some(RecordId::Registration("0978952".into())));
+        assert_eq!(RecordId::serial("7123456"), Some(RecordId::Serial("07123456".into())));
+        assert_eq!(RecordId::parse("123456789"), None);
+        assert_eq!(RecordId::parse("reg 5123456"), None);
+        assert_eq!(RecordId::parse("5123456").unwrap().to_string(), "registration 5123456");
+    }
+
+    #[test]
+    fn test_search_result_serde() {
+        let result = SearchResult::new(SearchQuery::new("NIKE"), Vec::new())
+            .with_backend("manticore")
+            .with_retrieval_latency_ms(12)
+            .with_total_available(240);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["backend"], "manticore");
+        assert_eq!(json["total_available"], 240);
+        assert!(json.get("profile").is_none());
+
+        let parsed: SearchResult =
+            serde_json::from_value(serde_json::json!({"query": json["query"], "hits": []}))
+                .unwrap();
+        assert_eq!(parsed.backend, "");
+        assert_eq!(parsed.total_available, None);
+    }
+
+    #[test]
+    fn test_query_for_record() {
+        let mut record = TrademarkRecord::new("97123456", "ACME*WIDGETS");
+        record.classes = NiceClass::of_all(&[9, 42]);
+        record.owner = Owner::new("Acme Corp");
+
+        let query = SearchQuery::for_record(&record);
+        assert_eq!(query.mark_text, "ACME WIDGETS");
+        assert_eq!(query.classes, vec![9, 42]);
+        assert_eq!(query.exclude_serials, vec!["97123456"]);
+        assert_eq!(query.exclude_owners, vec!["Acme Corp"]);
+        assert_eq!(query.goods_services_query, None);
+
+        let mut design_only = TrademarkRecord::new("97123457", "");
+        design_only.design_codes = vec!["260101".into(), "26.01.03".into(), "03".into()];
+        let query = SearchQuery::for_record(&design_only);
+        assert_eq!(query.design_codes, vec!["26.01"]);
+        assert!(SearchQuery::for_record(&record).design_codes.is_empty());
+
+        let operators = TrademarkRecord::new("97123458", r#"AT&T - "X" (@owner|!^~/\)"#);
+        assert_eq!(SearchQuery::for_record(&operators).mark_text, "AT T X owner");
+    }
+
     #[test]
     fn test_risk_flag_severity() {
         assert!(RiskFlag::ExactMatch.severity() > RiskFlag::PhoneticMatch {
             algorithm: "soundex".into(),
-            code: "A250".into()
+            code: "A250".into(),
+            variant: None,
         }.severity());
     }
+
+    #[test]
+    fn test_risk_flag_kind() {
+        let flags = [
+            RiskFlag::AcronymMatch {
+                acronym: "IBM".into(),
+                expansion: "INTERNATIONAL BUSINESS MACHINES".into(),
+            },
+            RiskFlag::OwnerConflict {
+                owner: "Acme Corp".into(),
+                related_marks: vec!["ACME ROCKET".into(), "ACME ROCKETS".into()],
+            },
+            RiskFlag::RecentlyDead { months: 2 },
+        ];
+        for flag in flags {
+            let json = serde_json::to_value(&flag).unwrap();
+            assert_eq!(json["type"], flag.kind());
+            assert!(RiskFlag::KINDS.contains(&flag.kind()));
+            assert_eq!(serde_json::from_value::<RiskFlag>(json).unwrap(), flag);
+        }
+    }
+
+    #[test]
+    fn test_forward_compatible_enums() {
+        // A flag added by a newer server parses, and round-trips as sent
+        let json = serde_json::json!({"type": "TrademarkSquatting", "detail": {"domains": 3}});
+        let flag: RiskFlag = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(flag.kind(), "TrademarkSquatting");
+        assert_eq!(flag.label(), "Unrecognized Flag");
+        assert_eq!(flag.severity(), 0.0);
+        assert_eq!(serde_json::to_value(&flag).unwrap(), json);
+        let unit: RiskFlag = serde_json::from_str(r#"{"type": "Withdrawn"}"#).unwrap();
+        assert_eq!(serde_json::to_string(&unit).unwrap(), r#"{"type":"Withdrawn"}"#);
+
+        // Known flags are unaffected
+        let fuzzy: RiskFlag =
+            serde_json::from_str(r#"{"type": "FuzzyMatch", "detail": {"distance": 1}}"#).unwrap();
+        assert_eq!(fuzzy, RiskFlag::FuzzyMatch { distance: 1 });
+        let malformed = r#"{"type": "FuzzyMatch", "detail": {"distance": "x"}}"#;
+        assert!(serde_json::from_str::<RiskFlag>(malformed).is_err());
+        let exact = r#"{"type": "ExactMatch", "detail": 1}"#;
+        assert!(serde_json::from_str::<RiskFlag>(exact).is_err());
+
+        let status: TrademarkStatus = serde_json::from_str("\"SUSPENDED\"").unwrap();
+        assert_eq!(status, TrademarkStatus::Unknown);
+        let live: TrademarkStatus = serde_json::from_str("\"LIVE\"").unwrap();
+        assert_eq!(live, TrademarkStatus::Live);
+    }
 }
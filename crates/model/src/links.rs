@@ -0,0 +1,135 @@
+//! Links to a record's pages on USPTO sites.
+//!
+//! Numbers are taken as stored, so separators (`1,234,567`, `97-123456`)
+//! are dropped and short numbers are zero-padded: serial numbers to 8
+//! digits, registration numbers to 7.
+
+use crate::TrademarkRecord;
+use serde::{Deserialize, Serialize};
+
+/// TSDR (Trademark Status & Document Retrieval) case page.
+const TSDR_BASE: &str = "https://tsdr.uspto.gov/";
+
+/// USPTO trademark search, the successor to TESS.
+const TESS_BASE: &str = "https://tmsearch.uspto.gov/search/search-results";
+
+pub(crate) const SERIAL_DIGITS: usize = 8;
+pub(crate) const REGISTRATION_DIGITS: usize = 7;
+
+/// Links to view a record on USPTO sites.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SourceLinks {
+    /// TSDR status page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tsdr: Option<String>,
+
+    /// Trademark search (TESS) result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tess: Option<String>,
+}
+
+impl SourceLinks {
+    /// Build the links for a record, if it has a usable number.
+    pub fn for_record(record: &TrademarkRecord) -> Option<Self> {
+        let links = Self {
+            tsdr: record.tsdr_url(),
+            tess: record.tess_url(),
+        };
+        (links.tsdr.is_some() || links.tess.is_some()).then_some(links)
+    }
+}
+
+/// Canonical form of a serial or registration number: digits only,
+/// zero-padded to `width`. `None` if empty, all zeros, too long, or
+/// containing anything other than digits and separators.
+pub(crate) fn canonical_number(raw: &str, width: usize) -> Option<String> {
+    let mut digits = String::with_capacity(width);
+    for c in raw.trim().chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ',' | '-' | ' ' | '/' => {}
+            _ => return None,
+        }
+    }
+
+    if digits.len() > width || digits.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    Some(format!("{:0>width$}", digits, width = width))
+}
+
+impl TrademarkRecord {
+    /// TSDR status page, by serial number or else registration number.
+    pub fn tsdr_url(&self) -> Option<String> {
+        if let Some(serial) = canonical_number(&self.serial_number, SERIAL_DIGITS) {
+            return Some(format!(
+                "{}#caseNumber={}&caseType=SERIAL_NO&searchType=statusSearch",
+                TSDR_BASE, serial
+            ));
+        }
+
+        let registration = canonical_number(
+            self.registration_number.as_deref()?,
+            REGISTRATION_DIGITS,
+        )?;
+        Some(format!(
+            "{}#caseNumber={}&caseType=US_REGISTRATION_NO&searchType=statusSearch",
+            TSDR_BASE, registration
+        ))
+    }
+
+    /// Trademark search (TESS) result for the serial number.
+    pub fn tess_url(&self) -> Option<String> {
+        let serial = canonical_number(&self.serial_number, SERIAL_DIGITS)?;
+        Some(format!("{}?query={}", TESS_BASE, serial))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_urls() {
+        let record = TrademarkRecord::new("97123456", "NIKE");
+        assert_eq!(
+            record.tsdr_url().as_deref(),
+            Some("https://tsdr.uspto.gov/#caseNumber=97123456&caseType=SERIAL_NO&searchType=statusSearch")
+        );
+        assert_eq!(
+            record.tess_url().as_deref(),
+            Some("https://tmsearch.uspto.gov/search/search-results?query=97123456")
+        );
+    }
+
+    #[test]
+    fn test_number_formatting() {
+        assert_eq!(canonical_number("97-123,456", 8).as_deref(), Some("97123456"));
+        assert_eq!(canonical_number(" 1234567 ", 8).as_deref(), Some("01234567"));
+        assert_eq!(canonical_number("1,234,567", 7).as_deref(), Some("1234567"));
+        assert_eq!(canonical_number("123456789", 8), None);
+        assert_eq!(canonical_number("0000", 8), None);
+        assert_eq!(canonical_number("", 8), None);
+        assert_eq!(canonical_number("SN97123456", 8), None);
+    }
+
+    #[test]
+    fn test_registration_fallback() {
+        let mut record = TrademarkRecord::new("", "NIKE");
+        assert_eq!(SourceLinks::for_record(&record), None);
+
+        record.registration_number = Some("978,952".to_string());
+        assert_eq!(
+            record.tsdr_url().as_deref(),
+            Some("https://tsdr.uspto.gov/#caseNumber=0978952&caseType=US_REGISTRATION_NO&searchType=statusSearch")
+        );
+        assert_eq!(record.tess_url(), None);
+
+        let links = SourceLinks::for_record(&record).unwrap();
+        assert_eq!(
+            serde_json::to_value(&links).unwrap(),
+            serde_json::json!({ "tsdr": record.tsdr_url().unwrap() })
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Owners of records.
+//!
+//! Sources that only report a name still deserialize: a bare string is
+//! an owner with just that name. `Owner::key` folds case, punctuation
+//! and entity designators so "Nike, Inc." and "NIKE INC" group as one
+//! owner.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Entity designators dropped from the end of a name by `Owner::key`.
+const DESIGNATORS: &[&str] = &[
+    "INC", "INCORPORATED", "CORP", "CORPORATION", "CO", "COMPANY", "LLC", "LLP", "LP", "LTD",
+    "LIMITED", "PLC", "GMBH", "AG", "SA", "BV", "NV",
+];
+
+/// Legal form of an owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Individual,
+    Corporation,
+    LimitedLiabilityCompany,
+    Partnership,
+    Trust,
+    Association,
+    Government,
+    /// Any other legal form
+    Other,
+}
+
+impl FromStr for EntityType {
+    type Err = String;
+
+    /// Parse a USPTO legal entity name ("CORPORATION", "LIMITED
+    /// LIABILITY COMPANY") or abbreviation ("LLC").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_uppercase().replace(['_', '-'], " ");
+        match name.as_str() {
+            "INDIVIDUAL" | "SOLE PROPRIETORSHIP" => Ok(Self::Individual),
+            "CORPORATION" | "CORP" => Ok(Self::Corporation),
+            "LIMITED LIABILITY COMPANY" | "LLC" => Ok(Self::LimitedLiabilityCompany),
+            "PARTNERSHIP" | "LIMITED PARTNERSHIP" | "LIMITED LIABILITY PARTNERSHIP" | "LP"
+            | "LLP" => Ok(Self::Partnership),
+            "TRUST" => Ok(Self::Trust),
+            "ASSOCIATION" => Ok(Self::Association),
+            "GOVERNMENT ENTITY" | "GOVERNMENT" => Ok(Self::Government),
+            "OTHER" => Ok(Self::Other),
+            _ => Err(format!("Unknown entity type '{}'", s)),
+        }
+    }
+}
+
+/// The owner (registrant or applicant) of a record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Owner {
+    /// Name as filed
+    pub name: String,
+
+    /// Legal form
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<EntityType>,
+
+    /// Country of citizenship, or US state of organization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citizenship: Option<String>,
+
+    /// Address lines
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address: Vec<String>,
+}
+
+impl Owner {
+    /// An owner known only by name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the legal form.
+    pub fn with_entity_type(mut self, entity_type: EntityType) -> Self {
+        self.entity_type = Some(entity_type);
+        self
+    }
+
+    /// Set the citizenship or state of organization.
+    pub fn with_citizenship(mut self, citizenship: impl Into<String>) -> Self {
+        self.citizenship = Some(citizenship.into());
+        self
+    }
+
+    /// Set the address lines.
+    pub fn with_address(mut self, address: Vec<String>) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Whether no name is known.
+    pub fn is_empty(&self) -> bool {
+        self.name.trim().is_empty()
+    }
+
+    /// Name for grouping and matching owners: uppercase words of letters
+    /// and digits, without trailing entity designators ("NIKE" for
+    /// "Nike, Inc.").
+    pub fn key(&self) -> String {
+        // Periods are dropped rather than spaced, keeping N.V. one word
+        let cleaned: String = self
+            .name
+            .chars()
+            .filter(|c| *c != '.')
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect::<String>()
+            .to_uppercase();
+        let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+        while words.len() > 1 && words.last().is_some_and(|w| DESIGNATORS.contains(w)) {
+            words.pop();
+        }
+        words.join(" ")
+    }
+
+    /// Whether two owners are likely the same entity.
+    pub fn same_entity(&self, other: &Owner) -> bool {
+        !self.is_empty() && self.key() == other.key()
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl From<&str> for Owner {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Owner {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// Deserialized from the full structure or a bare name.
+impl<'de> Deserialize<'de> for Owner {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Fields {
+            #[serde(default)]
+            name: String,
+            #[serde(default)]
+            entity_type: Option<EntityType>,
+            #[serde(default)]
+            citizenship: Option<String>,
+            #[serde(default)]
+            address: Vec<String>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Name(String),
+            Full(Fields),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Name(name) => Self::new(name),
+            Raw::Full(fields) => Self {
+                name: fields.name,
+                entity_type: fields.entity_type,
+                citizenship: fields.citizenship,
+                address: fields.address,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_key() {
+        let nike = Owner::new("Nike, Inc.");
+        assert_eq!(nike.key(), "NIKE");
+        assert!(nike.same_entity(&Owner::new("NIKE INC")));
+        assert!(!nike.same_entity(&Owner::new("Nike Golf Co.")));
+        assert_eq!(Owner::new("Co.").key(), "CO");
+        assert!(!Owner::default().same_entity(&Owner::default()));
+        assert_eq!("llc".parse(), Ok(EntityType::LimitedLiabilityCompany));
+    }
+
+    #[test]
+    fn test_owner_serde() {
+        let bare: Owner = serde_json::from_str(r#""Nike, Inc.""#).unwrap();
+        assert_eq!(bare, Owner::new("Nike, Inc."));
+
+        let owner = Owner::new("Nike, Inc.")
+            .with_entity_type(EntityType::Corporation)
+            .with_citizenship("Oregon")
+            .with_address(vec!["One Bowerman Drive".to_string()]);
+        let json = serde_json::to_value(&owner).unwrap();
+        assert_eq!(json["entity_type"], "corporation");
+        assert_eq!(serde_json::from_value::<Owner>(json).unwrap(), owner);
+    }
+}
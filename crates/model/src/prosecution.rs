@@ -0,0 +1,74 @@
+//! Prosecution history of a record.
+//!
+//! Events are taken as TSDR lists them: a short event code and its
+//! description. A likelihood-of-confusion refusal (Section 2(d)) on a
+//! mark's own prosecution is context for a search: the examiner already
+//! found it close to another mark.
+
+use crate::Date;
+use serde::{Deserialize, Serialize};
+
+/// Phrases in an event description that mark a Section 2(d) refusal.
+const REFUSAL_2D_PHRASES: &[&str] = &["2(D)", "LIKELIHOOD OF CONFUSION"];
+
+/// An event in a record's prosecution history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProsecutionEvent {
+    /// USPTO event code (e.g. "CNRT")
+    pub code: String,
+
+    /// Event description (e.g. "NON-FINAL ACTION E-MAILED")
+    #[serde(default)]
+    pub description: String,
+
+    /// Date of the event
+    #[serde(
+        default,
+        deserialize_with = "crate::date::deserialize_optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub date: Option<Date>,
+}
+
+impl ProsecutionEvent {
+    /// An undated event.
+    pub fn new(code: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            description: description.into(),
+            date: None,
+        }
+    }
+
+    /// Set the event date.
+    pub fn with_date(mut self, date: Date) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Whether the event is a likelihood-of-confusion refusal under
+    /// Section 2(d).
+    pub fn is_2d_refusal(&self) -> bool {
+        let description = self.description.to_uppercase();
+        REFUSAL_2D_PHRASES.iter().any(|phrase| description.contains(phrase))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_2d_refusal() {
+        let refusal = ProsecutionEvent::new("CNRT", "Non-final action: Sec. 2(d) refusal");
+        assert!(refusal.is_2d_refusal());
+        let confusion = ProsecutionEvent::new("CNFR", "FINAL REFUSAL - LIKELIHOOD OF CONFUSION");
+        assert!(confusion.is_2d_refusal());
+        assert!(!ProsecutionEvent::new("NWAP", "NEW APPLICATION ENTERED").is_2d_refusal());
+
+        let json = r#"{"code": "CNRT", "description": "2(D) REFUSAL", "date": "20240105"}"#;
+        let event: ProsecutionEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.date, Date::from_ymd(2024, 1, 5));
+    }
+}
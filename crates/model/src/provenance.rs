@@ -0,0 +1,101 @@
+//! Where a record came from.
+//!
+//! Provenance names the dataset a record was ingested from, when, from
+//! which file and line, and by which pipeline version, so a result can
+//! say how fresh its data is and an audit can reproduce it. It
+//! serializes under the namespaced key `ilegalflow:provenance`, apart
+//! from the USPTO fields.
+
+use crate::Date;
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds in a day.
+const DAY_MS: u64 = 86_400_000;
+
+/// The source of a record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Provenance {
+    /// Source dataset (e.g. "uspto-daily-2026-10-14")
+    pub dataset: String,
+
+    /// When the record was ingested (Unix milliseconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingested_at_ms: Option<u64>,
+
+    /// Source file within the dataset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+
+    /// Line of the source file, from 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_line: Option<u64>,
+
+    /// Version of the pipeline that ingested the record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline_version: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance naming only the dataset.
+    pub fn new(dataset: impl Into<String>) -> Self {
+        Self {
+            dataset: dataset.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the ingest time (Unix milliseconds).
+    pub fn with_ingested_at_ms(mut self, ingested_at_ms: u64) -> Self {
+        self.ingested_at_ms = Some(ingested_at_ms);
+        self
+    }
+
+    /// Set the source file and line.
+    pub fn with_source(mut self, file: impl Into<String>, line: u64) -> Self {
+        self.source_file = Some(file.into());
+        self.source_line = Some(line);
+        self
+    }
+
+    /// Set the pipeline version.
+    pub fn with_pipeline_version(mut self, version: impl Into<String>) -> Self {
+        self.pipeline_version = Some(version.into());
+        self
+    }
+
+    /// The date (UTC) the record was ingested.
+    pub fn ingested_on(&self) -> Option<Date> {
+        self.ingested_at_ms.map(|ms| Date::from_days((ms / DAY_MS) as i64))
+    }
+
+    /// Whole days from ingest to `today`; `None` without an ingest time.
+    pub fn age_days(&self, today: Date) -> Option<i64> {
+        self.ingested_on().map(|ingested| (today.days() - ingested.days()).max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrademarkRecord;
+
+    #[test]
+    fn test_provenance() {
+        // 2026-10-14T12:00:00Z
+        let provenance = Provenance::new("uspto-daily-2026-10-14")
+            .with_ingested_at_ms(1_791_979_200_000)
+            .with_source("apc261014.xml", 1042)
+            .with_pipeline_version("0.4.1");
+        assert_eq!(provenance.ingested_on(), Date::from_ymd(2026, 10, 14));
+        assert_eq!(provenance.age_days(Date::from_ymd(2026, 10, 15).unwrap()), Some(1));
+        assert_eq!(Provenance::new("manual").age_days(Date::today()), None);
+
+        let mut record = TrademarkRecord::new("97123456", "ACME");
+        record.provenance = Some(provenance.clone());
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["ilegalflow:provenance"]["source_line"], 1042);
+        let parsed: TrademarkRecord = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.provenance, Some(provenance));
+    }
+}
@@ -0,0 +1,135 @@
+//! Ordering hits by risk.
+//!
+//! Scores are `f32`, which has no total order, so sorting them with
+//! `partial_cmp` needs a fallback for NaN and can rank hits differently
+//! from run to run. `compare_hits` is a total order: risk score, then
+//! retrieval score, then status, then serial number, with NaN scores
+//! ranked last. `ByRisk` wraps a hit in that order for `max_by_key`,
+//! heaps and other `Ord` APIs.
+
+use crate::{CandidateHit, TrademarkStatus};
+use std::cmp::Ordering;
+
+/// Compare two scores, higher first; NaN ranks after every number.
+pub fn compare_scores(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => b.total_cmp(&a),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+/// Rank of a status among hits of equal risk: live marks first, dead
+/// marks last.
+fn status_priority(status: TrademarkStatus) -> u8 {
+    match status {
+        TrademarkStatus::Live => 0,
+        TrademarkStatus::Pending => 1,
+        TrademarkStatus::Unknown => 2,
+        TrademarkStatus::Dead => 3,
+    }
+}
+
+/// Hit order, riskiest first: risk score, descending. Ties go to the
+/// higher retrieval score, then the livelier status, then the lower
+/// serial number, so equal scores rank the same across runs and
+/// backends.
+pub fn compare_hits(a: &CandidateHit, b: &CandidateHit) -> Ordering {
+    compare_scores(a.risk_score, b.risk_score)
+        .then_with(|| compare_scores(a.retrieval_score, b.retrieval_score))
+        .then_with(|| status_priority(a.record.status).cmp(&status_priority(b.record.status)))
+        .then_with(|| a.record.serial_number.cmp(&b.record.serial_number))
+}
+
+/// A hit ordered by risk: the riskier of two hits is the greater.
+#[derive(Debug, Clone, Copy)]
+pub struct ByRisk<'a>(pub &'a CandidateHit);
+
+impl PartialEq for ByRisk<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ByRisk<'_> {}
+
+impl PartialOrd for ByRisk<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByRisk<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_hits(other.0, self.0)
+    }
+}
+
+impl CandidateHit {
+    /// Compare with another hit in rank order (`Less` ranks first).
+    pub fn rank_cmp(&self, other: &CandidateHit) -> Ordering {
+        compare_hits(self, other)
+    }
+}
+
+/// Sort hits into rank order, riskiest first.
+pub fn sort_hits(hits: &mut [CandidateHit]) {
+    hits.sort_by(compare_hits);
+}
+
+/// The riskiest hit, if any.
+pub fn riskiest(hits: &[CandidateHit]) -> Option<&CandidateHit> {
+    hits.iter().max_by_key(|hit| ByRisk(hit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrademarkRecord;
+
+    fn hit(serial: &str, risk: f32, retrieval: f32, status: TrademarkStatus) -> CandidateHit {
+        let mut record = TrademarkRecord::new(serial, "QUORLAND");
+        record.status = status;
+        CandidateHit {
+            record,
+            retrieval_score: retrieval,
+            risk_score: risk,
+            risk_probability: None,
+            flags: Vec::new(),
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compare_scores() {
+        assert_eq!(compare_scores(0.9, 0.5), Ordering::Less);
+        assert_eq!(compare_scores(0.5, 0.5), Ordering::Equal);
+        assert_eq!(compare_scores(f32::NAN, 0.0), Ordering::Greater);
+        assert_eq!(compare_scores(f32::NEG_INFINITY, f32::NAN), Ordering::Less);
+        assert_eq!(compare_scores(f32::NAN, f32::NAN), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_rank_order() {
+        let mut hits = vec![
+            hit("001", 0.5, 4.0, TrademarkStatus::Live),
+            hit("002", 0.5, 5.0, TrademarkStatus::Dead),
+            hit("005", f32::NAN, 9.0, TrademarkStatus::Live),
+            hit("004", 0.5, 5.0, TrademarkStatus::Live),
+            hit("003", 0.5, 5.0, TrademarkStatus::Live),
+            hit("006", 0.9, 1.0, TrademarkStatus::Pending),
+        ];
+        sort_hits(&mut hits);
+        let serials: Vec<&str> = hits.iter().map(|h| h.record.serial_number.as_str()).collect();
+        assert_eq!(serials, ["006", "003", "004", "002", "001", "005"]);
+
+        assert_eq!(riskiest(&hits).unwrap().record.serial_number, "006");
+        assert!(ByRisk(&hits[0]) > ByRisk(&hits[1]));
+        assert_eq!(hits[1].rank_cmp(&hits[1]), Ordering::Equal);
+        assert!(riskiest(&[]).is_none());
+    }
+}
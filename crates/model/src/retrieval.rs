@@ -0,0 +1,89 @@
+//! Normalizing retrieval scores.
+//!
+//! Backends score relevance on their own scales: Manticore's `WEIGHT()`
+//! is unbounded and grows with the query, while other engines return
+//! 0.0 - 1.0. Before a retrieval score can be blended into a risk score
+//! it is mapped to 0.0 - 1.0; each backend names the mapping that suits
+//! its scores.
+
+use serde::{Deserialize, Serialize};
+
+/// A mapping of raw retrieval scores to 0.0 - 1.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// Divide by the best score among the candidates
+    #[default]
+    MaxScaled,
+
+    /// Stretch the candidates' scores so the worst is 0.0 and the best
+    /// 1.0
+    MinMax,
+
+    /// `score / (score + half)`, independent of the other candidates;
+    /// a score of `half` maps to 0.5
+    Saturating { half: f32 },
+
+    /// Scores are already 0.0 - 1.0; only clamped
+    Identity,
+}
+
+impl ScoreNormalization {
+    /// Normalize one query's candidate scores, in order. Negative and
+    /// non-finite scores map to 0.0.
+    pub fn normalize(&self, scores: &[f32]) -> Vec<f32> {
+        let clean = |s: f32| if s.is_finite() { s.max(0.0) } else { 0.0 };
+        let max = scores.iter().copied().map(clean).fold(0.0, f32::max);
+        let min = scores.iter().copied().map(clean).fold(max, f32::min);
+        scores
+            .iter()
+            .map(|&s| {
+                let s = clean(s);
+                let normalized = match *self {
+                    Self::MaxScaled if max > 0.0 => s / max,
+                    Self::MaxScaled => 0.0,
+                    Self::MinMax if max > min => (s - min) / (max - min),
+                    Self::MinMax if max > 0.0 => 1.0,
+                    Self::MinMax => 0.0,
+                    Self::Saturating { half } if half > 0.0 => s / (s + half),
+                    Self::Saturating { .. } => 0.0,
+                    Self::Identity => s,
+                };
+                normalized.clamp(0.0, 1.0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let scores = [2000.0, 500.0, 1000.0, f32::NAN];
+        assert_eq!(
+            ScoreNormalization::MaxScaled.normalize(&scores),
+            vec![1.0, 0.25, 0.5, 0.0]
+        );
+        assert_eq!(
+            ScoreNormalization::MinMax.normalize(&scores[..3]),
+            vec![1.0, 0.0, 1.0 / 3.0]
+        );
+        assert_eq!(
+            ScoreNormalization::Saturating { half: 1000.0 }.normalize(&scores[1..3]),
+            vec![1.0 / 3.0, 0.5]
+        );
+        assert_eq!(
+            ScoreNormalization::Identity.normalize(&[0.4, 1.5, -1.0]),
+            vec![0.4, 1.0, 0.0]
+        );
+
+        // A lone candidate, or all equal, is the best there is
+        assert_eq!(
+            ScoreNormalization::MinMax.normalize(&[3.0, 3.0]),
+            vec![1.0, 1.0]
+        );
+        assert_eq!(ScoreNormalization::MaxScaled.normalize(&[0.0]), vec![0.0]);
+    }
+}
@@ -0,0 +1,212 @@
+//! Configurable risk flag severities.
+//!
+//! `SeverityTable` holds the weight behind each flag's severity, so a
+//! deployment can tune them without a rebuild. Flags found outright take
+//! their weight as is; flags measured by a score are scaled by it, and
+//! flags measured by an edit distance lose `EDIT_STEP` per edit. The
+//! defaults are the weights `RiskFlag::severity` has always used.
+
+use crate::RiskFlag;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Severity lost per edit by distance-measured flags.
+pub const EDIT_STEP: f32 = 0.1;
+
+/// Errors from an invalid severity table.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SeverityError {
+    #[error("Severity {name} must be between 0.0 and 1.0, got {value}")]
+    OutOfRange { name: &'static str, value: f32 },
+}
+
+/// Severity weights by flag, each in 0.0 - 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeverityTable {
+    pub exact_match: f32,
+    pub obfuscated_match: f32,
+    /// Number-word match with no edits left; nearer ones score like
+    /// `fuzzy_match`
+    pub number_word_match: f32,
+    pub house_mark_match: f32,
+    pub compound_match: f32,
+    pub acronym_match: f32,
+    pub foreign_equivalent: f32,
+    pub phonetic_match: f32,
+    /// Scaled by the phoneme similarity
+    pub near_phonetic_match: f32,
+    /// Less `EDIT_STEP` per edit
+    pub fuzzy_match: f32,
+    /// Scaled by the score
+    pub string_similarity: f32,
+    /// Scaled by the alignment score
+    pub syllable_match: f32,
+    pub class_overlap: f32,
+    /// Scaled by the closest pair's relatedness
+    pub related_classes: f32,
+    /// Scaled by the similarity
+    pub goods_services_similar: f32,
+    pub design_code_overlap: f32,
+    pub dominant_term_match: f32,
+    pub famous_mark: f32,
+    /// Scaled by the dilution score
+    pub dilution_risk: f32,
+    pub owner_conflict: f32,
+    pub common_law_risk: f32,
+    /// Design mark sharing design codes with the query
+    pub design_element_risk: f32,
+    /// Design mark sharing no design codes with the query
+    pub design_element_unshared: f32,
+    pub recently_dead: f32,
+}
+
+impl SeverityTable {
+    /// The built-in weights.
+    pub const DEFAULT: SeverityTable = SeverityTable {
+        exact_match: 1.0,
+        obfuscated_match: 0.9,
+        number_word_match: 0.9,
+        house_mark_match: 0.85,
+        compound_match: 0.85,
+        acronym_match: 0.6,
+        foreign_equivalent: 0.75,
+        phonetic_match: 0.8,
+        near_phonetic_match: 0.7,
+        fuzzy_match: 0.5,
+        string_similarity: 0.5,
+        syllable_match: 0.5,
+        class_overlap: 0.6,
+        related_classes: 0.6,
+        goods_services_similar: 0.5,
+        design_code_overlap: 0.6,
+        dominant_term_match: 0.7,
+        famous_mark: 0.95,
+        dilution_risk: 0.9,
+        owner_conflict: 0.5,
+        common_law_risk: 0.4,
+        design_element_risk: 0.6,
+        design_element_unshared: 0.2,
+        recently_dead: 0.3,
+    };
+
+    /// The severity of a flag (higher = more concerning).
+    pub fn severity(&self, flag: &RiskFlag) -> f32 {
+        let edits = |distance: u8| distance as f32 * EDIT_STEP;
+        match flag {
+            RiskFlag::ExactMatch => self.exact_match,
+            RiskFlag::ObfuscatedMatch { .. } => self.obfuscated_match,
+            RiskFlag::NumberWordMatch { distance: 0, .. } => self.number_word_match,
+            RiskFlag::NumberWordMatch { distance, .. } => self.fuzzy_match - edits(*distance),
+            RiskFlag::FamousMark { .. } => self.famous_mark,
+            RiskFlag::HouseMarkMatch { .. } => self.house_mark_match,
+            RiskFlag::CompoundMatch { .. } => self.compound_match,
+            RiskFlag::PhoneticMatch { .. } => self.phonetic_match,
+            RiskFlag::ForeignEquivalent { .. } => self.foreign_equivalent,
+            RiskFlag::DominantTermMatch { .. } => self.dominant_term_match,
+            RiskFlag::AcronymMatch { .. } => self.acronym_match,
+            RiskFlag::ClassOverlap { .. } => self.class_overlap,
+            RiskFlag::RelatedClasses { relatedness, .. } => *relatedness * self.related_classes,
+            RiskFlag::DesignCodeOverlap { .. } => self.design_code_overlap,
+            RiskFlag::FuzzyMatch { distance } => self.fuzzy_match - edits(*distance),
+            RiskFlag::NearPhoneticMatch { similarity, .. } => {
+                *similarity * self.near_phonetic_match
+            }
+            RiskFlag::StringSimilarity { score, .. } => *score * self.string_similarity,
+            RiskFlag::SyllableMatch { score, .. } => *score * self.syllable_match,
+            RiskFlag::GoodsServicesSimilar { similarity } => {
+                *similarity * self.goods_services_similar
+            }
+            RiskFlag::DilutionRisk { score, .. } => *score * self.dilution_risk,
+            RiskFlag::OwnerConflict { .. } => self.owner_conflict,
+            RiskFlag::CommonLawRisk { .. } => self.common_law_risk,
+            RiskFlag::DesignElementRisk { shared, .. } if shared.is_empty() => {
+                self.design_element_unshared
+            }
+            RiskFlag::DesignElementRisk { .. } => self.design_element_risk,
+            RiskFlag::RecentlyDead { .. } => self.recently_dead,
+            // Scored by the build that raised it, through the hit's risk
+            RiskFlag::Unrecognized { .. } => 0.0,
+        }
+    }
+
+    /// Named weights, for validation and reporting.
+    pub fn entries(&self) -> [(&'static str, f32); 24] {
+        [
+            ("exact_match", self.exact_match),
+            ("obfuscated_match", self.obfuscated_match),
+            ("number_word_match", self.number_word_match),
+            ("house_mark_match", self.house_mark_match),
+            ("compound_match", self.compound_match),
+            ("acronym_match", self.acronym_match),
+            ("foreign_equivalent", self.foreign_equivalent),
+            ("phonetic_match", self.phonetic_match),
+            ("near_phonetic_match", self.near_phonetic_match),
+            ("fuzzy_match", self.fuzzy_match),
+            ("string_similarity", self.string_similarity),
+            ("syllable_match", self.syllable_match),
+            ("class_overlap", self.class_overlap),
+            ("related_classes", self.related_classes),
+            ("goods_services_similar", self.goods_services_similar),
+            ("design_code_overlap", self.design_code_overlap),
+            ("dominant_term_match", self.dominant_term_match),
+            ("famous_mark", self.famous_mark),
+            ("dilution_risk", self.dilution_risk),
+            ("owner_conflict", self.owner_conflict),
+            ("common_law_risk", self.common_law_risk),
+            ("design_element_risk", self.design_element_risk),
+            ("design_element_unshared", self.design_element_unshared),
+            ("recently_dead", self.recently_dead),
+        ]
+    }
+
+    /// Check that every weight is within 0.0 - 1.0.
+    pub fn validate(&self) -> Result<(), SeverityError> {
+        match self.entries().into_iter().find(|(_, v)| !(0.0..=1.0).contains(v)) {
+            Some((name, value)) => Err(SeverityError::OutOfRange { name, value }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for SeverityTable {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_severities() {
+        let fuzzy = RiskFlag::FuzzyMatch { distance: 2 };
+        let exact = RiskFlag::ExactMatch;
+        assert_eq!(SeverityTable::default().severity(&exact), exact.severity());
+        assert!((fuzzy.severity() - 0.3).abs() < 1e-6);
+
+        let table: SeverityTable =
+            serde_json::from_str(r#"{"fuzzy_match": 0.7, "recently_dead": 0.0}"#).unwrap();
+        assert!((table.severity(&fuzzy) - 0.5).abs() < 1e-6);
+        assert_eq!(table.severity(&RiskFlag::RecentlyDead { months: 2 }), 0.0);
+        assert_eq!(table.exact_match, 1.0);
+        assert!(serde_json::from_str::<SeverityTable>(r#"{"fuzzy": 0.7}"#).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(SeverityTable::DEFAULT.validate(), Ok(()));
+        let table = SeverityTable {
+            famous_mark: 1.5,
+            ..SeverityTable::DEFAULT
+        };
+        let err = table.validate().unwrap_err();
+        assert_eq!(err.to_string(), "Severity famous_mark must be between 0.0 and 1.0, got 1.5");
+        let table = SeverityTable {
+            class_overlap: f32::NAN,
+            ..SeverityTable::DEFAULT
+        };
+        assert!(table.validate().is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Detailed status from USPTO status codes.
+//!
+//! `TrademarkStatus` is coarse enough to filter on; `StatusDetail` says
+//! where in its life a mark is. Status codes are three digits, grouped
+//! by stage: 600s cover abandonment and prosecution up to allowance,
+//! 700s and 800s registrations and their maintenance, 900s expiry. The
+//! mapping is by range, so codes added within a stage map with it.
+
+use crate::TrademarkStatus;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a mark is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusDetail {
+    /// New application, not yet examined
+    Filed,
+    /// Under examination, including responses to office actions
+    UnderExamination,
+    /// Examination suspended, typically behind an earlier application
+    Suspended,
+    /// Refusal on appeal to the Board
+    ExParteAppeal,
+    /// Approved by the examiner, awaiting publication
+    ApprovedForPublication,
+    /// Published in the Official Gazette for opposition
+    PublishedForOpposition,
+    /// Opposed at the Board
+    OppositionPending,
+    /// Notice of allowance issued, awaiting a statement of use
+    Allowed,
+    /// Registered, including renewed and maintained registrations
+    Registered,
+    /// Application abandoned
+    Abandoned,
+    /// Registration cancelled
+    Cancelled,
+    /// Registration expired
+    Expired,
+}
+
+/// Status codes by range, inclusive; the first matching range wins.
+pub const STATUS_CODES: &[(u16, u16, StatusDetail)] = &[
+    (600, 629, StatusDetail::Abandoned),
+    (630, 639, StatusDetail::Filed),
+    (640, 649, StatusDetail::UnderExamination),
+    (650, 659, StatusDetail::Suspended),
+    (660, 660, StatusDetail::ExParteAppeal),
+    (661, 679, StatusDetail::UnderExamination),
+    (680, 685, StatusDetail::ApprovedForPublication),
+    (686, 686, StatusDetail::PublishedForOpposition),
+    (687, 687, StatusDetail::OppositionPending),
+    (688, 699, StatusDetail::Allowed),
+    (700, 708, StatusDetail::Registered),
+    (709, 729, StatusDetail::Cancelled),
+    (730, 899, StatusDetail::Registered),
+    (900, 999, StatusDetail::Expired),
+];
+
+impl StatusDetail {
+    /// All details, from filing to expiry.
+    pub const ALL: [StatusDetail; 12] = [
+        Self::Filed,
+        Self::UnderExamination,
+        Self::Suspended,
+        Self::ExParteAppeal,
+        Self::ApprovedForPublication,
+        Self::PublishedForOpposition,
+        Self::OppositionPending,
+        Self::Allowed,
+        Self::Registered,
+        Self::Abandoned,
+        Self::Cancelled,
+        Self::Expired,
+    ];
+
+    /// The detail for a USPTO status code ("602"); `None` for codes
+    /// outside the table.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code: u16 = code.trim().parse().ok()?;
+        STATUS_CODES
+            .iter()
+            .find(|(first, last, _)| (*first..=*last).contains(&code))
+            .map(|(_, _, detail)| *detail)
+    }
+
+    /// The coarse status, for filters.
+    pub fn status(&self) -> TrademarkStatus {
+        match self {
+            Self::Registered => TrademarkStatus::Live,
+            Self::Abandoned | Self::Cancelled | Self::Expired => TrademarkStatus::Dead,
+            _ => TrademarkStatus::Pending,
+        }
+    }
+
+    /// Snake-case name, as serialized.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Filed => "filed",
+            Self::UnderExamination => "under_examination",
+            Self::Suspended => "suspended",
+            Self::ExParteAppeal => "ex_parte_appeal",
+            Self::ApprovedForPublication => "approved_for_publication",
+            Self::PublishedForOpposition => "published_for_opposition",
+            Self::OppositionPending => "opposition_pending",
+            Self::Allowed => "allowed",
+            Self::Registered => "registered",
+            Self::Abandoned => "abandoned",
+            Self::Cancelled => "cancelled",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+impl From<StatusDetail> for TrademarkStatus {
+    fn from(detail: StatusDetail) -> Self {
+        detail.status()
+    }
+}
+
+impl fmt::Display for StatusDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for StatusDetail {
+    type Err = String;
+
+    /// Parse a name (`published_for_opposition`, or with hyphens or
+    /// spaces) or a status code.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['-', ' '], "_");
+        Self::ALL
+            .into_iter()
+            .find(|d| d.name() == name)
+            .or_else(|| Self::from_code(&name))
+            .ok_or_else(|| format!("Unknown status detail '{}'", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_codes() {
+        assert_eq!(StatusDetail::from_code("602"), Some(StatusDetail::Abandoned));
+        assert_eq!(StatusDetail::from_code("686"), Some(StatusDetail::PublishedForOpposition));
+        assert_eq!(StatusDetail::from_code("710"), Some(StatusDetail::Cancelled));
+        assert_eq!(StatusDetail::from_code(" 800 "), Some(StatusDetail::Registered));
+        assert_eq!(StatusDetail::from_code("900"), Some(StatusDetail::Expired));
+        assert_eq!(StatusDetail::from_code("500"), None);
+        assert_eq!(StatusDetail::from_code("live"), None);
+
+        assert_eq!(StatusDetail::Registered.status(), TrademarkStatus::Live);
+        assert_eq!(StatusDetail::Suspended.status(), TrademarkStatus::Pending);
+        assert_eq!(TrademarkStatus::from(StatusDetail::Expired), TrademarkStatus::Dead);
+        assert_eq!("opposition-pending".parse(), Ok(StatusDetail::OppositionPending));
+        assert_eq!("650".parse(), Ok(StatusDetail::Suspended));
+    }
+
+    #[test]
+    fn test_table_ranges() {
+        // Ranges are ordered and do not overlap
+        for pair in STATUS_CODES.windows(2) {
+            assert!(pair[0].0 <= pair[0].1 && pair[0].1 < pair[1].0);
+        }
+        for detail in StatusDetail::ALL {
+            assert!(STATUS_CODES.iter().any(|(_, _, d)| *d == detail), "{} unmapped", detail);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Checking records before they are indexed.
+//!
+//! `TrademarkRecord::validate` reports every problem with a record at
+//! once, so ingest can quarantine a bad row with the reasons rather than
+//! stop at the first.
+
+use crate::links::{canonical_number, REGISTRATION_DIGITS, SERIAL_DIGITS};
+use crate::{Date, NiceClass, StatusDetail, TrademarkRecord, TrademarkStatus};
+use serde::Serialize;
+use thiserror::Error;
+
+/// A problem found by `TrademarkRecord::validate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Error)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum ValidationIssue {
+    #[error("Serial number '{number}' is not 8 digits")]
+    InvalidSerial { number: String },
+
+    #[error("Registration number '{number}' is not 7 digits")]
+    InvalidRegistration { number: String },
+
+    #[error("Registration date {registration} is before filing date {filing}")]
+    RegisteredBeforeFiling { filing: Date, registration: Date },
+
+    #[error("Status date {status_date} is before filing date {filing}")]
+    StatusBeforeFiling { filing: Date, status_date: Date },
+
+    #[error("Class {class} is listed more than once")]
+    DuplicateClass { class: NiceClass },
+
+    #[error("Status is {status:?} but the record has a registration number")]
+    RegisteredWhilePending { status: TrademarkStatus },
+
+    #[error("Status is Live but the record has no registration number")]
+    LiveWithoutRegistration,
+
+    #[error("Status is {status:?} but status code {code} means {detail}")]
+    StatusCodeMismatch {
+        status: TrademarkStatus,
+        code: String,
+        detail: StatusDetail,
+    },
+}
+
+impl ValidationIssue {
+    /// The record field the issue is about.
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::InvalidSerial { .. } => "serial_number",
+            Self::InvalidRegistration { .. } | Self::LiveWithoutRegistration => {
+                "registration_number"
+            }
+            Self::RegisteredBeforeFiling { .. } => "registration_date",
+            Self::StatusBeforeFiling { .. } => "status_date",
+            Self::DuplicateClass { .. } => "classes",
+            Self::RegisteredWhilePending { .. } | Self::StatusCodeMismatch { .. } => "status",
+        }
+    }
+}
+
+impl TrademarkRecord {
+    /// Check the record: numbers in canonical form, dates in order,
+    /// classes listed once, and a status consistent with the
+    /// registration number and status code.
+    pub fn validate(&self) -> Result<(), Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        let canonical = |number: &str, digits| canonical_number(number, digits);
+        if canonical(&self.serial_number, SERIAL_DIGITS).as_deref() != Some(&self.serial_number) {
+            issues.push(ValidationIssue::InvalidSerial {
+                number: self.serial_number.clone(),
+            });
+        }
+        if let Some(number) = &self.registration_number {
+            if canonical(number, REGISTRATION_DIGITS).as_ref() != Some(number) {
+                issues.push(ValidationIssue::InvalidRegistration {
+                    number: number.clone(),
+                });
+            }
+        }
+
+        if let Some(filing) = self.filing_date {
+            if let Some(registration) = self.registration_date.filter(|d| *d < filing) {
+                issues.push(ValidationIssue::RegisteredBeforeFiling { filing, registration });
+            }
+            if let Some(status_date) = self.status_date.filter(|d| *d < filing) {
+                issues.push(ValidationIssue::StatusBeforeFiling { filing, status_date });
+            }
+        }
+
+        for (i, class) in self.classes.iter().enumerate() {
+            if self.classes[..i].contains(class) && !self.classes[i + 1..].contains(class) {
+                issues.push(ValidationIssue::DuplicateClass { class: *class });
+            }
+        }
+
+        match (self.status, &self.registration_number) {
+            (TrademarkStatus::Pending, Some(_)) => {
+                issues.push(ValidationIssue::RegisteredWhilePending {
+                    status: self.status,
+                });
+            }
+            (TrademarkStatus::Live, None) => issues.push(ValidationIssue::LiveWithoutRegistration),
+            _ => {}
+        }
+        if let (Some(code), Some(detail)) = (&self.status_code, self.status_detail()) {
+            if self.status != TrademarkStatus::Unknown && self.status != detail.status() {
+                issues.push(ValidationIssue::StatusCodeMismatch {
+                    status: self.status,
+                    code: code.clone(),
+                    detail,
+                });
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_record() {
+        let mut record = TrademarkRecord::new("72016902", "NIKE");
+        record.registration_number = Some("0978952".into());
+        record.status = TrademarkStatus::Live;
+        record.status_code = Some("800".into());
+        record.classes = NiceClass::of_all(&[25, 28]);
+        record.filing_date = Date::from_ymd(1971, 6, 18);
+        record.registration_date = Date::from_ymd(1974, 1, 22);
+        assert_eq!(record.validate(), Ok(()));
+        assert_eq!(TrademarkRecord::new("90000001", "").validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validation_issues() {
+        let mut record = TrademarkRecord::new("72-016902", "NIKE");
+        record.registration_number = Some("978952".into());
+        record.status = TrademarkStatus::Pending;
+        record.status_code = Some("602".into());
+        record.classes = NiceClass::of_all(&[25, 25, 25]);
+        record.filing_date = Date::from_ymd(1974, 1, 22);
+        record.registration_date = Date::from_ymd(1971, 6, 18);
+
+        let issues = record.validate().unwrap_err();
+        let fields: Vec<_> = issues.iter().map(ValidationIssue::field).collect();
+        assert_eq!(
+            fields,
+            [
+                "serial_number",
+                "registration_number",
+                "registration_date",
+                "classes",
+                "status",
+                "status"
+            ]
+        );
+        assert_eq!(issues[3].to_string(), "Class 25 is listed more than once");
+        assert_eq!(
+            serde_json::to_value(&issues[0]).unwrap(),
+            serde_json::json!({"issue": "invalid_serial", "number": "72-016902"})
+        );
+    }
+}
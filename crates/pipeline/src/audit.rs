@@ -0,0 +1,233 @@
+//! QA audit sampling.
+//!
+//! A configurable fraction of searches is persisted in full (request,
+//! hits, explanations) so QA can review production behavior. Every
+//! sampling decision, sampled or not, is appended to a manifest so the
+//! reviewed set can be shown to be a fair random sample.
+
+use crate::PipelineOutput;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::fs::{self, File, OpenOptions};
+use std::hash::BuildHasher;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The sampling decision made for one search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamplingDecision {
+    /// Unique id for the search
+    pub request_id: String,
+
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+
+    /// Configured sample rate at decision time
+    pub rate: f64,
+
+    /// Uniform random roll in [0, 1)
+    pub roll: f64,
+
+    /// Whether the search was persisted
+    pub sampled: bool,
+}
+
+/// A persisted audit entry for a sampled search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// The decision that selected this search
+    pub decision: SamplingDecision,
+
+    /// Backend that served the search
+    pub backend: String,
+
+    /// Tenant the search ran for, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+
+    /// Full pipeline output (query, hits, explanations)
+    pub output: PipelineOutput,
+}
+
+/// Destination for audit data.
+pub trait AuditSink: Send + Sync {
+    /// Append a sampling decision to the manifest.
+    fn record_decision(&self, decision: &SamplingDecision) -> io::Result<()>;
+
+    /// Persist a sampled search.
+    fn persist(&self, record: &AuditRecord) -> io::Result<()>;
+}
+
+/// Random sampler for audit decisions.
+///
+/// Rolls are derived from a randomly keyed hasher over a per-sampler
+/// counter, which avoids pulling in an RNG dependency. Request ids carry
+/// a random instance key from the same hasher, so samplers in different
+/// processes do not collide on the same millisecond and counter.
+#[derive(Debug)]
+pub struct Sampler {
+    rate: f64,
+    state: RandomState,
+    instance: u64,
+    counter: AtomicU64,
+}
+
+impl Sampler {
+    /// Create a sampler; the rate is clamped to 0.0 - 1.0.
+    pub fn new(rate: f64) -> Self {
+        let state = RandomState::new();
+        Self {
+            rate: rate.clamp(0.0, 1.0),
+            instance: state.hash_one(std::process::id()),
+            state,
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the effective sample rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Make a sampling decision for the next search.
+    pub fn decide(&self) -> SamplingDecision {
+        let seq = self.counter.fetch_add(1, Ordering::Relaxed);
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let hash = self.state.hash_one((seq, timestamp_ms));
+        // Top 53 bits give a uniform f64 in [0, 1)
+        let roll = (hash >> 11) as f64 / (1u64 << 53) as f64;
+
+        SamplingDecision {
+            request_id: format!("{:x}-{:016x}-{:04x}", timestamp_ms, self.instance, seq),
+            timestamp_ms,
+            rate: self.rate,
+            roll,
+            sampled: roll < self.rate,
+        }
+    }
+}
+
+/// Audit sink writing to a directory.
+///
+/// Layout:
+/// - `manifest.jsonl`: one `SamplingDecision` per line
+/// - `<request_id>.json`: one `AuditRecord` per sampled search
+#[derive(Debug)]
+pub struct DirectoryAuditSink {
+    dir: PathBuf,
+    manifest: Mutex<File>,
+}
+
+impl DirectoryAuditSink {
+    /// Open (creating if needed) an audit directory.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let manifest = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("manifest.jsonl"))?;
+
+        Ok(Self {
+            dir,
+            manifest: Mutex::new(manifest),
+        })
+    }
+}
+
+impl AuditSink for DirectoryAuditSink {
+    fn record_decision(&self, decision: &SamplingDecision) -> io::Result<()> {
+        let line = serde_json::to_string(decision)?;
+        let mut manifest = self
+            .manifest
+            .lock()
+            .map_err(|_| io::Error::other("manifest lock poisoned"))?;
+        writeln!(manifest, "{}", line)
+    }
+
+    /// Fails rather than overwrite an existing record with the same id.
+    fn persist(&self, record: &AuditRecord) -> io::Result<()> {
+        let path = self
+            .dir
+            .join(format!("{}.json", record.decision.request_id));
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        serde_json::to_writer_pretty(file, record)?;
+        Ok(())
+    }
+}
+
+/// In-memory audit sink, useful for tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAuditSink {
+    decisions: Arc<Mutex<Vec<SamplingDecision>>>,
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl MemoryAuditSink {
+    /// Get all recorded decisions.
+    pub fn decisions(&self) -> Vec<SamplingDecision> {
+        self.decisions.lock().map(|d| d.clone()).unwrap_or_default()
+    }
+
+    /// Get all persisted records.
+    pub fn records(&self) -> Vec<AuditRecord> {
+        self.records.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record_decision(&self, decision: &SamplingDecision) -> io::Result<()> {
+        self.decisions
+            .lock()
+            .map_err(|_| io::Error::other("lock poisoned"))?
+            .push(decision.clone());
+        Ok(())
+    }
+
+    fn persist(&self, record: &AuditRecord) -> io::Result<()> {
+        self.records
+            .lock()
+            .map_err(|_| io::Error::other("lock poisoned"))?
+            .push(record.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_bounds() {
+        let never = Sampler::new(0.0);
+        let always = Sampler::new(2.0);
+        assert_eq!(always.rate(), 1.0);
+
+        for _ in 0..100 {
+            assert!(!never.decide().sampled);
+            assert!(always.decide().sampled);
+        }
+    }
+
+    #[test]
+    fn test_sampler_rate_is_approximate() {
+        let sampler = Sampler::new(0.1);
+        let sampled = (0..10_000).filter(|_| sampler.decide().sampled).count();
+        assert!((500..1500).contains(&sampled), "sampled {}", sampled);
+    }
+
+    #[test]
+    fn test_request_ids_differ_across_samplers() {
+        let (a, b) = (Sampler::new(1.0), Sampler::new(1.0));
+        let (first, second) = (a.decide().request_id, b.decide().request_id);
+        assert_ne!(first, second);
+        assert_ne!(a.decide().request_id, first);
+    }
+}
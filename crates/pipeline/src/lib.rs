@@ -0,0 +1,958 @@
+//! Search pipeline orchestration.
+//!
+//! Composes the individual crates into a single search entry point:
+//!
+//! Query ──► Backend ──► Rerank ──► Explain ──► Output
+//!
+//! Cross-cutting concerns (QA audit sampling, reviewer overrides, etc.)
+//! hook in here so that the eval CLI and the future API server behave
+//! identically.
+
+pub mod audit;
+pub mod overrides;
+pub mod stats;
+pub mod watch;
+
+use audit::{AuditRecord, AuditSink, Sampler};
+use overrides::{OverrideStore, SuppressedHit};
+use ilegalflow_backend_manticore::{BackendError, SearchBackend};
+use ilegalflow_explain::{
+    assess_search_with, explain_hit_with, summarize_risk_with, Comparison, ExplainOptions,
+    Explanation, ExplanationNarrator, NoNarrator, SearchAssessment,
+};
+use ilegalflow_features::{backfill_classes, backfill_query_classes};
+use ilegalflow_model::{
+    CandidateHit, ClassFilter, ClassTaxonomy, ClusterFacet, Date, RecordId, SearchIntent,
+    SearchQuery, SearchResult, SourceLinks, TrademarkRecord,
+};
+use ilegalflow_query::{iso_date_days_ago, validate, ValidationErrors};
+use ilegalflow_rerank::guards::RuleWarning;
+use ilegalflow_rerank::context::QueryContext;
+use ilegalflow_rerank::knockout::knockout_with_context;
+use ilegalflow_rerank::{rerank_with_context, RerankConfig};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Errors from running the pipeline.
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("Invalid query: {0}")]
+    InvalidQuery(#[from] ValidationErrors),
+
+    #[error("Backend error: {0}")]
+    Backend(#[from] BackendError),
+
+    #[error("Record not found: {0}")]
+    RecordNotFound(RecordId),
+
+    #[error("Knockout search exceeded its {0:?} budget")]
+    BudgetExceeded(Duration),
+}
+
+/// Which hits get explanations during a run.
+///
+/// Explanation prose dominates the cost of large batch sweeps; hits
+/// skipped here can still be explained later with `Pipeline::explain`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ExplainPolicy {
+    /// Explain every hit
+    #[default]
+    All,
+    /// Explain hits at or above a risk score
+    AboveRisk(f32),
+    /// Explain nothing up front
+    OnDemand,
+}
+
+impl ExplainPolicy {
+    /// Whether a hit is explained during the run.
+    pub fn should_explain(&self, hit: &CandidateHit) -> bool {
+        match self {
+            Self::All => true,
+            Self::AboveRisk(min_risk) => hit.risk_score >= *min_risk,
+            Self::OnDemand => false,
+        }
+    }
+}
+
+impl FromStr for ExplainPolicy {
+    type Err = String;
+
+    /// Parse `all`, `on-demand`, or a minimum risk score such as `0.5`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(Self::All),
+            "on-demand" | "none" => Ok(Self::OnDemand),
+            other => match other.parse::<f32>() {
+                Ok(min_risk) if (0.0..=1.0).contains(&min_risk) => Ok(Self::AboveRisk(min_risk)),
+                _ => Err(format!(
+                    "Unknown explain policy '{}' (all, on-demand, or a risk score 0.0-1.0)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+/// Pipeline configuration.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineConfig {
+    /// Re-ranking weights
+    pub rerank: RerankConfig,
+    /// Fraction of searches (0.0 - 1.0) persisted to the audit sink
+    pub audit_sample_rate: f64,
+    /// Class clusters for expansion and result facets
+    pub class_taxonomy: ClassTaxonomy,
+    /// Explanation verbosity; its severity table is replaced by the
+    /// re-ranker's, so explanations and scoring agree
+    pub explain: ExplainOptions,
+    /// Which hits are explained during a run
+    pub explain_policy: ExplainPolicy,
+    /// Attach TSDR/TESS links to each hit
+    pub include_links: bool,
+    /// Limits for `SearchIntent::Knockout` searches
+    pub knockout: KnockoutBudget,
+    /// Class inference for records and queries without classes
+    pub class_backfill: ClassBackfill,
+    /// Name of the re-ranker profile or preset in `rerank`, reported
+    /// with results
+    pub profile: Option<String>,
+}
+
+/// Read-through class inference from goods/services text.
+///
+/// Inferred classes are marked `classes_inferred`, and the re-ranker
+/// discounts overlaps that rely on them.
+#[derive(Debug, Clone)]
+pub struct ClassBackfill {
+    /// Infer classes for retrieved records that have none (off by
+    /// default: an inferred class can raise a record's risk)
+    pub records: bool,
+    /// Infer classes for a query that has none but has a goods/services
+    /// query (used for scoring only, never to narrow retrieval)
+    pub query: bool,
+    /// Most classes inferred for one record or query
+    pub max_classes: usize,
+}
+
+impl Default for ClassBackfill {
+    fn default() -> Self {
+        Self {
+            records: false,
+            query: false,
+            max_classes: 3,
+        }
+    }
+}
+
+impl ClassBackfill {
+    /// Fill empty record classes, returning how many records changed.
+    pub fn apply_records(&self, candidates: &mut [(TrademarkRecord, f32)]) -> usize {
+        if !self.records {
+            return 0;
+        }
+        candidates
+            .iter_mut()
+            .filter(|(record, _)| record.classes.is_empty())
+            .map(|(record, _)| backfill_classes(record, self.max_classes))
+            .filter(|changed| *changed)
+            .count()
+    }
+
+    /// The query to score with: the query itself, or a copy with
+    /// inferred classes.
+    pub fn scoring_query<'a>(&self, query: &'a SearchQuery) -> Cow<'a, SearchQuery> {
+        if !self.query || !query.classes.is_empty() {
+            return Cow::Borrowed(query);
+        }
+        let mut inferred = query.clone();
+        if backfill_query_classes(&mut inferred, self.max_classes) {
+            Cow::Owned(inferred)
+        } else {
+            Cow::Borrowed(query)
+        }
+    }
+}
+
+/// Limits that keep knockout searches fast.
+#[derive(Debug, Clone)]
+pub struct KnockoutBudget {
+    /// Maximum candidates retrieved (caps the query limit)
+    pub retrieval_limit: usize,
+    /// Maximum time for retrieval
+    pub timeout: Duration,
+}
+
+impl Default for KnockoutBudget {
+    fn default() -> Self {
+        Self {
+            retrieval_limit: 25,
+            timeout: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Explanations generated for a single hit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HitExplanation {
+    /// Serial number of the explained hit
+    pub serial_number: String,
+
+    /// One-line risk summary
+    pub summary: String,
+
+    /// Per-flag explanations
+    pub explanations: Vec<Explanation>,
+
+    /// Prose from the pipeline's narrator, when it wrote any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub narrative: Option<String>,
+}
+
+impl HitExplanation {
+    /// Explain a hit against the query text.
+    pub fn new(hit: &CandidateHit, query_text: &str, options: &ExplainOptions) -> Self {
+        Self {
+            serial_number: hit.record.serial_number.clone(),
+            summary: summarize_risk_with(hit, &options.severities),
+            explanations: explain_hit_with(hit, query_text, options),
+            narrative: None,
+        }
+    }
+}
+
+/// Result of a pipeline run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineOutput {
+    /// The query, the risk-ranked hits, and how they were retrieved
+    #[serde(flatten)]
+    pub result: SearchResult,
+
+    /// Class filter applied, including any cluster expansions
+    pub class_filter: ClassFilter,
+
+    /// Number of candidates retrieved before re-ranking
+    pub retrieved: usize,
+
+    /// Explanations of the hits selected by the explain policy, in hit
+    /// order
+    pub explanations: Vec<HitExplanation>,
+
+    /// Hit counts per class cluster
+    pub class_facets: Vec<ClusterFacet>,
+
+    /// Hits removed by reviewer overrides
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<SuppressedHit>,
+
+    /// Rules skipped by applicability guards
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuleWarning>,
+
+    /// A knockout search stopped at a HIGH tier hit without checking
+    /// every candidate
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stopped_early: bool,
+
+    /// Hits dropped by the re-ranker's `min_risk_score` or `max_results`
+    #[serde(default)]
+    pub pruned: usize,
+
+    /// Design marks set aside by the re-ranker, when it buckets them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub design_hits: Vec<CandidateHit>,
+
+    /// Overall risk across the hits
+    #[serde(default)]
+    pub assessment: SearchAssessment,
+}
+
+/// Current `SearchResponse` envelope version.
+pub const RESPONSE_VERSION: u32 = 1;
+
+/// Versioned wire envelope for search results.
+///
+/// This is the JSON shape returned by the API and printed by
+/// `eval search --format json`, so tooling needs a single parser.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResponse {
+    /// Envelope version, bumped on breaking changes
+    pub version: u32,
+
+    /// Number of hits returned
+    pub total: usize,
+
+    #[serde(flatten)]
+    pub result: PipelineOutput,
+}
+
+impl SearchResponse {
+    pub fn new(result: PipelineOutput) -> Self {
+        Self {
+            version: RESPONSE_VERSION,
+            total: result.result.hits.len(),
+            result,
+        }
+    }
+}
+
+/// The search pipeline over a backend.
+pub struct Pipeline<B> {
+    backend: B,
+    config: PipelineConfig,
+    sampler: Sampler,
+    audit_sink: Option<Box<dyn AuditSink>>,
+    overrides: Option<OverrideStore>,
+    narrator: Box<dyn ExplanationNarrator>,
+}
+
+impl<B: SearchBackend> Pipeline<B> {
+    /// Create a new pipeline.
+    pub fn new(backend: B, mut config: PipelineConfig) -> Self {
+        config
+            .rerank
+            .retrieval_normalization
+            .get_or_insert_with(|| backend.score_normalization());
+        config.explain.severities = config.rerank.severities;
+        for warning in config.rerank.behaviors.warnings(&iso_date_days_ago(0)) {
+            tracing::warn!("{}", warning);
+        }
+        let sampler = Sampler::new(config.audit_sample_rate);
+        Self {
+            backend,
+            config,
+            sampler,
+            audit_sink: None,
+            overrides: None,
+            narrator: Box::new(NoNarrator),
+        }
+    }
+
+    /// Attach reviewer overrides, applied by `run_for_tenant`.
+    pub fn with_overrides(mut self, overrides: OverrideStore) -> Self {
+        self.overrides = Some(overrides);
+        self
+    }
+
+    /// Attach an audit sink for QA sampling.
+    pub fn with_audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Narrate explained hits with this narrator instead of `NoNarrator`.
+    pub fn with_narrator(mut self, narrator: impl ExplanationNarrator + 'static) -> Self {
+        self.narrator = Box::new(narrator);
+        self
+    }
+
+    /// Get the underlying backend.
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Get the pipeline configuration.
+    pub fn config(&self) -> &PipelineConfig {
+        &self.config
+    }
+
+    /// Run a search through retrieval, re-ranking, and explanation.
+    pub async fn run(&self, query: &SearchQuery) -> Result<PipelineOutput, PipelineError> {
+        self.run_inner(None, query).await
+    }
+
+    /// Run a search applying a tenant's reviewer overrides.
+    pub async fn run_for_tenant(
+        &self,
+        tenant: &str,
+        query: &SearchQuery,
+    ) -> Result<PipelineOutput, PipelineError> {
+        self.run_inner(Some(tenant), query).await
+    }
+
+    /// Find marks that may conflict with an existing record.
+    ///
+    /// Loads the record, derives a query from it (see
+    /// `SearchQuery::for_record`) and runs the standard analysis, applying
+    /// the tenant's overrides if given. Returns the record with the output.
+    pub async fn run_for_record(
+        &self,
+        tenant: Option<&str>,
+        id: &RecordId,
+    ) -> Result<(TrademarkRecord, PipelineOutput), PipelineError> {
+        let record = self
+            .backend
+            .fetch_record(id)
+            .await?
+            .ok_or_else(|| PipelineError::RecordNotFound(id.clone()))?;
+
+        let query = SearchQuery::for_record(&record);
+        let output = self.run_inner(tenant, &query).await?;
+        Ok((record, output))
+    }
+
+    async fn run_inner(
+        &self,
+        tenant: Option<&str>,
+        query: &SearchQuery,
+    ) -> Result<PipelineOutput, PipelineError> {
+        validate(query)?;
+        // Inferred query classes feed scoring only; retrieval and the
+        // reported class filter use the query as given
+        let scoring_query = self.config.class_backfill.scoring_query(query);
+        let today = self.config.rerank.today.unwrap_or_else(Date::today);
+        let context =
+            QueryContext::for_tenant_at(&scoring_query, &self.config.rerank, tenant, Some(today));
+
+        let is_knockout = query.intent == SearchIntent::Knockout;
+        let started = Instant::now();
+        let retrieval = if is_knockout {
+            let budget = &self.config.knockout;
+            let mut limited = query.clone();
+            limited.limit = limited.limit.min(budget.retrieval_limit);
+            tokio::time::timeout(budget.timeout, self.backend.retrieve(&limited))
+                .await
+                .map_err(|_| PipelineError::BudgetExceeded(budget.timeout))??
+        } else {
+            self.backend.retrieve(query).await?
+        };
+        let retrieval_latency_ms = started.elapsed().as_millis() as u64;
+        let mut candidates = retrieval.candidates;
+        let retrieved = candidates.len();
+        let backfilled = self.config.class_backfill.apply_records(&mut candidates);
+        if backfilled > 0 {
+            tracing::debug!(backfilled, "Inferred classes for records without classes");
+        }
+
+        // Knockout runs the reduced rule set with an early exit
+        let (hits, warnings, stopped_early, pruned, design_hits) = if is_knockout {
+            let outcome = knockout_with_context(&context, &scoring_query, candidates, &self.config.rerank);
+            (outcome.hits, outcome.warnings, outcome.stopped_early, outcome.pruned, Vec::new())
+        } else {
+            let outcome = rerank_with_context(&context, &scoring_query, candidates, &self.config.rerank);
+            (outcome.hits, outcome.warnings, false, outcome.pruned, outcome.design_hits)
+        };
+        for warning in &warnings {
+            tracing::warn!(rule = %warning.rule, skipped = warning.skipped, "{}", warning.reason);
+        }
+        let (mut hits, mut design_hits, suppressed) = match (tenant, &self.overrides) {
+            (Some(tenant), Some(overrides)) => {
+                let (hits, mut suppressed) = overrides.apply(tenant, hits);
+                let (design_hits, design_suppressed) = overrides.apply(tenant, design_hits);
+                suppressed.extend(design_suppressed);
+                (hits, design_hits, suppressed)
+            }
+            _ => (hits, design_hits, Vec::new()),
+        };
+
+        if self.config.include_links {
+            for hit in hits.iter_mut().chain(design_hits.iter_mut()) {
+                hit.links = SourceLinks::for_record(&hit.record);
+            }
+        }
+
+        let mut explanations = Vec::new();
+        for hit in hits.iter().filter(|hit| self.config.explain_policy.should_explain(hit)) {
+            let query_text = &context.mark_text;
+            let mut explanation = HitExplanation::new(hit, query_text, &self.config.explain);
+            explanation.narrative = self.narrate(query_text, hit, &explanation.explanations).await;
+            explanations.push(explanation);
+        }
+
+        let class_facets = self.config.class_taxonomy.facets(&hits);
+        let assessment = assess_search_with(query, &hits, &self.config.explain.severities);
+
+        let result = SearchResult {
+            query: query.clone(),
+            backend: self.backend.name().to_string(),
+            retrieval_latency_ms,
+            total_available: retrieval.total,
+            profile: self.config.profile.clone(),
+            hits,
+        };
+        let output = PipelineOutput {
+            result,
+            class_filter: ClassFilter::resolve(query, &self.config.class_taxonomy),
+            retrieved,
+            explanations,
+            class_facets,
+            suppressed,
+            warnings,
+            stopped_early,
+            pruned,
+            design_hits,
+            assessment,
+        };
+
+        self.audit(tenant, &output);
+
+        Ok(output)
+    }
+
+    /// Explain a single hit of a previous run, e.g. one skipped by the
+    /// explain policy.
+    pub fn explain(&self, output: &PipelineOutput, serial_number: &str) -> Option<HitExplanation> {
+        let hit = output
+            .result
+            .hits
+            .iter()
+            .find(|hit| hit.record.serial_number == serial_number)?;
+        Some(HitExplanation::new(hit, &output.result.query.mark_text, &self.config.explain))
+    }
+
+    /// The narrator's prose for a hit. Failures are logged and leave the
+    /// structured text.
+    async fn narrate(
+        &self,
+        query_text: &str,
+        hit: &CandidateHit,
+        explanations: &[Explanation],
+    ) -> Option<String> {
+        let hit = &self.config.explain.redaction.hit(hit);
+        let comparison = Comparison { query_text, hit, explanations };
+        match self.narrator.narrate(&comparison).await {
+            Ok(narrative) => narrative,
+            Err(e) => {
+                tracing::warn!(serial = %hit.record.serial_number, "Narration failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Record the sampling decision and persist sampled searches.
+    ///
+    /// Audit failures are logged, never surfaced to the caller.
+    fn audit(&self, tenant: Option<&str>, output: &PipelineOutput) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+
+        let decision = self.sampler.decide();
+
+        if decision.sampled {
+            let record = AuditRecord {
+                decision: decision.clone(),
+                backend: self.backend.name().to_string(),
+                tenant: tenant.map(str::to_string),
+                output: output.clone(),
+            };
+            if let Err(e) = sink.persist(&record) {
+                tracing::warn!(error = %e, request_id = %decision.request_id, "Failed to persist audit record");
+            }
+        }
+
+        if let Err(e) = sink.record_decision(&decision) {
+            tracing::warn!(error = %e, request_id = %decision.request_id, "Failed to record sampling decision");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use audit::MemoryAuditSink;
+    use ilegalflow_explain::{NarrationError, NarrationFuture};
+    use ilegalflow_model::{
+        MarkDrawingType, NiceClass, ReviewNote, ReviewVerdict, RiskFlag, ScoreNormalization,
+    };
+    use ilegalflow_rerank::design::DesignHandling;
+
+    struct MockBackend {
+        records: Vec<(TrademarkRecord, f32)>,
+    }
+
+    impl SearchBackend for MockBackend {
+        async fn search(
+            &self,
+            _query: &SearchQuery,
+        ) -> Result<Vec<(TrademarkRecord, f32)>, BackendError> {
+            Ok(self.records.clone())
+        }
+
+        async fn fetch_record(
+            &self,
+            id: &RecordId,
+        ) -> Result<Option<TrademarkRecord>, BackendError> {
+            Ok(self
+                .records
+                .iter()
+                .find(|(record, _)| RecordId::serial(&record.serial_number).as_ref() == Some(id))
+                .map(|(record, _)| record.clone()))
+        }
+
+        async fn health_check(&self) -> Result<(), BackendError> {
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "mock"
+        }
+
+        fn score_normalization(&self) -> ScoreNormalization {
+            ScoreNormalization::Identity
+        }
+    }
+
+    fn mock_backend() -> MockBackend {
+        MockBackend {
+            records: vec![
+                (TrademarkRecord::new("001", "NYKE"), 0.9),
+                (TrademarkRecord::new("002", "NIKE"), 1.0),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_reranks_and_explains() {
+        let pipeline = Pipeline::new(mock_backend(), PipelineConfig::default());
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+
+        assert_eq!(output.retrieved, 2);
+        assert_eq!(output.result.hits[0].record.serial_number, "002");
+        assert_eq!(output.explanations.len(), output.result.hits.len());
+        assert_eq!(output.explanations[0].serial_number, "002");
+    }
+
+    #[tokio::test]
+    async fn test_class_backfill() {
+        let mut filed = TrademarkRecord::new("001", "NYKE");
+        filed.classes = NiceClass::of_all(&[25]);
+        let mut unfiled = TrademarkRecord::new("002", "NYKEE");
+        unfiled.goods_services = "Athletic footwear".to_string();
+        let backend = || MockBackend {
+            records: vec![(filed.clone(), 1.0), (unfiled.clone(), 1.0)],
+        };
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+
+        // Record inference is opt-in
+        let pipeline = Pipeline::new(backend(), PipelineConfig::default());
+        let output = pipeline.run(&query).await.unwrap();
+        let unfiled = output.result.hits.iter().find(|h| h.record.serial_number == "002").unwrap();
+        assert!(unfiled.record.classes.is_empty());
+
+        let mut config = PipelineConfig::default();
+        config.class_backfill.records = true;
+        let pipeline = Pipeline::new(backend(), config);
+        let output = pipeline.run(&query).await.unwrap();
+
+        let overlap = |serial: &str| {
+            let hit = output.result.hits.iter().find(|h| h.record.serial_number == serial).unwrap();
+            hit.flags.iter().find_map(|f| match f {
+                RiskFlag::ClassOverlap { inferred, .. } => Some(*inferred),
+                _ => None,
+            })
+        };
+        assert_eq!(overlap("001"), Some(false));
+        assert_eq!(overlap("002"), Some(true));
+        let unfiled = output.result.hits.iter().find(|h| h.record.serial_number == "002").unwrap();
+        assert!(unfiled.record.classes_inferred);
+
+        // Query-time inference is opt-in and scores without filtering
+        let mut config = PipelineConfig::default();
+        config.class_backfill.query = true;
+        let mut filed = TrademarkRecord::new("001", "NYKE");
+        filed.classes = NiceClass::of_all(&[25]);
+        let pipeline = Pipeline::new(MockBackend { records: vec![(filed, 1.0)] }, config);
+        let query = SearchQuery::new("NIKE").with_goods_services("shoes");
+        let output = pipeline.run(&query).await.unwrap();
+        let overlap = RiskFlag::ClassOverlap {
+            classes: NiceClass::of_all(&[25]),
+            inferred: true,
+        };
+        assert!(output.result.hits[0].flags.contains(&overlap));
+        assert!(output.result.query.classes.is_empty());
+        assert!(output.class_filter.requested.is_empty());
+
+        // Inferred classes also decide which query term is dominant
+        let mut config = PipelineConfig::default();
+        config.class_backfill.query = true;
+        let backend = MockBackend {
+            records: vec![(TrademarkRecord::new("001", "ZETA SHOES"), 1.0)],
+        };
+        let pipeline = Pipeline::new(backend, config);
+        let query = SearchQuery::new("ACME SHOES").with_goods_services("shoes");
+        let output = pipeline.run(&query).await.unwrap();
+        let dominant = |f: &RiskFlag| matches!(f, RiskFlag::DominantTermMatch { .. });
+        assert!(!output.result.hits[0].flags.iter().any(dominant));
+    }
+
+    #[tokio::test]
+    async fn test_backend_score_normalization() {
+        let nyke_score = |config: PipelineConfig| async {
+            let pipeline = Pipeline::new(mock_backend(), config);
+            let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+            output.result.hits.iter().find(|h| h.record.serial_number == "001").unwrap().risk_score
+        };
+        let unblended = nyke_score(PipelineConfig::default()).await;
+
+        // The backend's scores are taken as they are
+        let mut config = PipelineConfig::default();
+        config.rerank.retrieval_weight = 0.1;
+        let pipeline = Pipeline::new(mock_backend(), config.clone());
+        assert_eq!(
+            pipeline.config.rerank.retrieval_normalization,
+            Some(ScoreNormalization::Identity)
+        );
+        assert!((nyke_score(config.clone()).await - unblended - 0.09).abs() < 1e-6);
+
+        // The configuration's own mapping wins: NYKE is the worst candidate
+        config.rerank.retrieval_normalization = Some(ScoreNormalization::MinMax);
+        assert_eq!(nyke_score(config).await, unblended);
+    }
+
+    #[tokio::test]
+    async fn test_knockout_intent() {
+        let pipeline = Pipeline::new(mock_backend(), PipelineConfig::default());
+        let query = SearchQuery::new("NIKE").with_intent(SearchIntent::Knockout);
+        let output = pipeline.run(&query).await.unwrap();
+
+        // NYKE is retrieved first, but the exact match is checked first
+        // and ends the screen
+        assert_eq!(output.retrieved, 2);
+        assert!(output.stopped_early);
+        assert_eq!(output.result.hits.len(), 1);
+        assert_eq!(output.result.hits[0].record.serial_number, "002");
+
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        assert!(!output.stopped_early);
+        assert_eq!(output.result.hits.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_explain_policy() {
+        let config = PipelineConfig {
+            explain_policy: ExplainPolicy::AboveRisk(0.9),
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(mock_backend(), config);
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+
+        // Only the exact match clears the threshold
+        assert_eq!(output.result.hits.len(), 2);
+        assert_eq!(output.explanations.len(), 1);
+        assert_eq!(output.explanations[0].serial_number, "002");
+        assert_eq!(output.assessment.hits, 2);
+        assert_eq!(output.assessment.blocking[0].serial_number, "002");
+
+        let config = PipelineConfig {
+            explain_policy: ExplainPolicy::OnDemand,
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(mock_backend(), config);
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        assert!(output.explanations.is_empty());
+
+        let explanation = pipeline.explain(&output, "001").unwrap();
+        assert!(!explanation.explanations.is_empty());
+        assert!(pipeline.explain(&output, "999").is_none());
+
+        assert_eq!("0.5".parse(), Ok(ExplainPolicy::AboveRisk(0.5)));
+        assert_eq!("on-demand".parse(), Ok(ExplainPolicy::OnDemand));
+        assert!("1.5".parse::<ExplainPolicy>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rerank_severities_explain() {
+        let mut config = PipelineConfig::default();
+        config.rerank.severities.exact_match = 0.6;
+        // NIKE is also famous
+        config.rerank.severities.famous_mark = 0.6;
+        config.rerank.severities.dilution_risk = 0.6;
+        let pipeline = Pipeline::new(mock_backend(), config);
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+
+        let exact = output.explanations.iter().find(|e| e.serial_number == "002").unwrap();
+        assert_eq!(exact.explanations[0].severity, 0.6);
+        assert!(exact.summary.starts_with("MODERATE RISK"));
+        assert!(output.assessment.blocking.iter().all(|b| b.serial_number != "002"));
+    }
+
+    /// Names the compared marks, standing in for an LLM.
+    struct Namer;
+
+    impl ExplanationNarrator for Namer {
+        fn narrate<'a>(&'a self, comparison: &'a Comparison<'a>) -> NarrationFuture<'a> {
+            let prose = format!("{} vs {}", comparison.query_text, comparison.hit.record.mark_text);
+            let failed = comparison.hit.record.serial_number == "001";
+            Box::pin(async move {
+                if failed {
+                    Err(NarrationError::Unavailable("offline".to_string()))
+                } else {
+                    Ok(Some(prose))
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_narrator() {
+        let output = Pipeline::new(mock_backend(), PipelineConfig::default())
+            .run(&SearchQuery::new("NIKE"))
+            .await
+            .unwrap();
+        assert!(output.explanations.iter().all(|e| e.narrative.is_none()));
+
+        let pipeline =
+            Pipeline::new(mock_backend(), PipelineConfig::default()).with_narrator(Namer);
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        let narrative = |serial: &str| {
+            let explanation = output.explanations.iter().find(|e| e.serial_number == serial);
+            explanation.unwrap().narrative.clone()
+        };
+        assert_eq!(narrative("002").as_deref(), Some("NIKE vs NIKE"));
+        // A failed narration keeps the structured text
+        assert_eq!(narrative("001"), None);
+        let json = serde_json::to_value(&output.explanations[0]).unwrap();
+        assert_eq!(json["narrative"], "NIKE vs NIKE");
+    }
+
+    #[tokio::test]
+    async fn test_search_response_envelope() {
+        let pipeline = Pipeline::new(mock_backend(), PipelineConfig::default());
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        let json = serde_json::to_value(SearchResponse::new(output)).unwrap();
+
+        assert_eq!(json["version"], RESPONSE_VERSION);
+        assert_eq!(json["total"], 2);
+        assert_eq!(json["query"]["mark_text"], "NIKE");
+        assert!(json["hits"].is_array());
+        assert!(json["explanations"].is_array());
+
+        let parsed: SearchResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.result.result.hits.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_source_links_opt_in() {
+        let pipeline = Pipeline::new(mock_backend(), PipelineConfig::default());
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        let json = serde_json::to_value(&output).unwrap();
+        assert!(json["hits"][0].get("links").is_none());
+
+        let config = PipelineConfig {
+            include_links: true,
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(mock_backend(), config);
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            json["hits"][0]["links"]["tsdr"],
+            "https://tsdr.uspto.gov/#caseNumber=00000002&caseType=SERIAL_NO&searchType=statusSearch"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_for_tenant_applies_overrides() {
+        let mut store = OverrideStore::default();
+        store.insert(
+            "acme",
+            "002",
+            ReviewNote {
+                verdict: ReviewVerdict::Suppress,
+                note: "Client's own mark".to_string(),
+                reviewer: None,
+            },
+        );
+        let pipeline =
+            Pipeline::new(mock_backend(), PipelineConfig::default()).with_overrides(store);
+
+        let output = pipeline
+            .run_for_tenant("acme", &SearchQuery::new("NIKE"))
+            .await
+            .unwrap();
+        assert_eq!(output.result.hits.len(), 1);
+        assert_eq!(output.suppressed[0].serial_number, "002");
+
+        let output = pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+        assert_eq!(output.result.hits.len(), 2);
+
+        // Bucketed design-mark hits are overridden too
+        let mut design = TrademarkRecord::new("003", "NYKE");
+        design.drawing_type = Some(MarkDrawingType::DesignWithText);
+        let mut backend = mock_backend();
+        backend.records.push((design, 0.9));
+        let mut config = PipelineConfig::default();
+        config.rerank.design_marks.handling = DesignHandling::Bucket;
+        let mut store = OverrideStore::default();
+        for serial in ["002", "003"] {
+            store.insert(
+                "acme",
+                serial,
+                ReviewNote {
+                    verdict: ReviewVerdict::Suppress,
+                    note: "Cleared".to_string(),
+                    reviewer: None,
+                },
+            );
+        }
+        let pipeline = Pipeline::new(backend, config).with_overrides(store);
+
+        let output = pipeline
+            .run_for_tenant("acme", &SearchQuery::new("NIKE"))
+            .await
+            .unwrap();
+        assert_eq!(output.result.hits.len(), 1);
+        assert!(output.design_hits.is_empty());
+        let suppressed: Vec<_> = output.suppressed.iter().map(|s| &s.serial_number).collect();
+        assert_eq!(suppressed, vec!["002", "003"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_for_record() {
+        let pipeline = Pipeline::new(mock_backend(), PipelineConfig::default());
+
+        let id = RecordId::serial("002").unwrap();
+        let (record, output) = pipeline.run_for_record(None, &id).await.unwrap();
+        assert_eq!(record.mark_text, "NIKE");
+        assert_eq!(output.result.query.mark_text, "NIKE");
+        assert_eq!(output.result.query.exclude_serials, vec!["002"]);
+
+        let missing = RecordId::serial("999").unwrap();
+        assert!(matches!(
+            pipeline.run_for_record(None, &missing).await,
+            Err(PipelineError::RecordNotFound(id)) if id == missing
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_query_rejected() {
+        let pipeline = Pipeline::new(mock_backend(), PipelineConfig::default());
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25])).with_limit(0);
+
+        match pipeline.run(&query).await {
+            Err(PipelineError::InvalidQuery(errors)) => {
+                assert_eq!(errors.issues()[0].field, "limit");
+            }
+            other => panic!("expected InvalidQuery, got {:?}", other.map(|o| o.result.hits.len())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_sampling() {
+        let sink = MemoryAuditSink::default();
+        let config = PipelineConfig {
+            audit_sample_rate: 1.0,
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(mock_backend(), config).with_audit_sink(sink.clone());
+        pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+
+        assert_eq!(sink.decisions().len(), 1);
+        assert!(sink.decisions()[0].sampled);
+        assert_eq!(sink.records().len(), 1);
+        assert_eq!(sink.records()[0].output.result.hits.len(), 2);
+
+        let sink = MemoryAuditSink::default();
+        let pipeline =
+            Pipeline::new(mock_backend(), PipelineConfig::default()).with_audit_sink(sink.clone());
+        pipeline.run(&SearchQuery::new("NIKE")).await.unwrap();
+
+        assert_eq!(sink.decisions().len(), 1);
+        assert!(!sink.decisions()[0].sampled);
+        assert!(sink.records().is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Reviewer overrides for specific hits.
+//!
+//! Attorneys can mark a hit as not a concern (suppressed from results) or
+//! pin it to the top for a client. Overrides are stored per tenant, keyed
+//! by serial number, and applied after re-ranking.
+
+use ilegalflow_model::{CandidateHit, ReviewNote, ReviewVerdict};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// A hit removed from results by a reviewer override.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressedHit {
+    pub serial_number: String,
+    pub mark_text: String,
+    pub review: ReviewNote,
+}
+
+/// Per-tenant reviewer overrides: tenant → serial number → note.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OverrideStore {
+    tenants: HashMap<String, HashMap<String, ReviewNote>>,
+}
+
+impl OverrideStore {
+    /// Load overrides from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Save overrides to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    /// Add or replace an override.
+    pub fn insert(&mut self, tenant: &str, serial_number: &str, review: ReviewNote) {
+        self.tenants
+            .entry(tenant.to_string())
+            .or_default()
+            .insert(serial_number.to_string(), review);
+    }
+
+    /// Remove an override.
+    pub fn remove(&mut self, tenant: &str, serial_number: &str) -> Option<ReviewNote> {
+        self.tenants.get_mut(tenant)?.remove(serial_number)
+    }
+
+    /// Get the override for a hit, if any.
+    pub fn get(&self, tenant: &str, serial_number: &str) -> Option<&ReviewNote> {
+        self.tenants.get(tenant)?.get(serial_number)
+    }
+
+    /// Apply a tenant's overrides to ranked hits.
+    ///
+    /// Suppressed hits are removed and returned separately; pinned hits
+    /// move to the top, keeping their relative order. Both are annotated
+    /// with the reviewer note.
+    pub fn apply(
+        &self,
+        tenant: &str,
+        hits: Vec<CandidateHit>,
+    ) -> (Vec<CandidateHit>, Vec<SuppressedHit>) {
+        let mut pinned = Vec::new();
+        let mut rest = Vec::new();
+        let mut suppressed = Vec::new();
+
+        for mut hit in hits {
+            match self.get(tenant, &hit.record.serial_number) {
+                Some(review) if review.verdict == ReviewVerdict::Suppress => {
+                    suppressed.push(SuppressedHit {
+                        serial_number: hit.record.serial_number,
+                        mark_text: hit.record.mark_text,
+                        review: review.clone(),
+                    });
+                }
+                Some(review) => {
+                    hit.review = Some(review.clone());
+                    pinned.push(hit);
+                }
+                None => rest.push(hit),
+            }
+        }
+
+        pinned.extend(rest);
+        (pinned, suppressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::TrademarkRecord;
+
+    fn hit(serial: &str, risk: f32) -> CandidateHit {
+        CandidateHit {
+            record: TrademarkRecord::new(serial, "MARK"),
+            retrieval_score: 1.0,
+            risk_score: risk,
+            risk_probability: None,
+            flags: vec![],
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    fn note(verdict: ReviewVerdict) -> ReviewNote {
+        ReviewNote {
+            verdict,
+            note: "Reviewed".to_string(),
+            reviewer: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let mut store = OverrideStore::default();
+        store.insert("acme", "001", note(ReviewVerdict::Suppress));
+        store.insert("acme", "003", note(ReviewVerdict::Pin));
+        store.insert("other", "002", note(ReviewVerdict::Suppress));
+
+        let hits = vec![hit("001", 0.9), hit("002", 0.8), hit("003", 0.1)];
+        let (hits, suppressed) = store.apply("acme", hits);
+
+        let serials: Vec<_> = hits.iter().map(|h| h.record.serial_number.as_str()).collect();
+        assert_eq!(serials, vec!["003", "002"]);
+        assert!(hits[0].review.is_some());
+        assert!(hits[1].review.is_none());
+        assert_eq!(suppressed.len(), 1);
+        assert_eq!(suppressed[0].serial_number, "001");
+    }
+
+    #[test]
+    fn test_store_serialization() {
+        let mut store = OverrideStore::default();
+        store.insert("acme", "001", note(ReviewVerdict::Pin));
+        let json = serde_json::to_string(&store).unwrap();
+        assert!(json.contains(r#""acme":{"001":{"verdict":"pin""#));
+
+        let parsed: OverrideStore = serde_json::from_str(&json).unwrap();
+        assert!(parsed.get("acme", "001").is_some());
+    }
+}
@@ -0,0 +1,456 @@
+//! Privacy-safe aggregate statistics over the query log.
+//!
+//! Aggregates such as "most conflicted words this quarter" are built from
+//! audit records without exposing individual customer queries:
+//!
+//! - k-anonymity: a term is only exported once at least `k` distinct
+//!   tenants searched it. Searches without a tenant count as a single
+//!   anonymous contributor, so they can never satisfy `k` on their own.
+//! - Contribution cap: each contributor adds at most `max_per_contributor`
+//!   to any count, so one tenant repeating a search cannot inflate it.
+//! - Noise: exported counts can be perturbed with Laplace noise
+//!   (sensitivity `max_per_contributor`, scale `max_per_contributor /
+//!   epsilon`) and rounded to whole numbers.
+
+use crate::audit::AuditRecord;
+use ilegalflow_features::normalize_text;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::hash::BuildHasher;
+use std::io;
+use std::path::Path;
+
+/// Shortest word counted as a term.
+const MIN_TERM_LEN: usize = 3;
+
+/// Default cap on one contributor's share of a count.
+pub const DEFAULT_MAX_PER_CONTRIBUTOR: u64 = 10;
+
+fn default_max_per_contributor() -> u64 {
+    DEFAULT_MAX_PER_CONTRIBUTOR
+}
+
+/// Noise added to exported counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Noise {
+    /// Exact counts
+    #[default]
+    None,
+    /// Laplace noise with privacy budget `epsilon` per count
+    Laplace { epsilon: f64 },
+}
+
+impl Noise {
+    /// Laplace noise with a privacy budget, which must be finite and
+    /// positive.
+    pub fn laplace(epsilon: f64) -> Result<Self, String> {
+        let noise = Self::Laplace { epsilon };
+        noise.validate()?;
+        Ok(noise)
+    }
+
+    /// Check the privacy budget.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Laplace { epsilon } if !(epsilon.is_finite() && *epsilon > 0.0) => Err(format!(
+                "epsilon must be finite and positive, got {}",
+                epsilon
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Settings applied before any aggregate is exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationConfig {
+    /// Minimum distinct contributors for a term to be exported
+    pub min_contributors: usize,
+    /// Risk score at which a search counts as conflicted
+    pub conflict_risk: f32,
+    /// Most any one contributor adds to a count
+    #[serde(default = "default_max_per_contributor")]
+    pub max_per_contributor: u64,
+    /// Noise added to exported counts
+    pub noise: Noise,
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            min_contributors: 5,
+            conflict_risk: 0.7,
+            max_per_contributor: DEFAULT_MAX_PER_CONTRIBUTOR,
+            noise: Noise::None,
+        }
+    }
+}
+
+/// Exported statistics for one term.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermStat {
+    /// Normalized search word
+    pub term: String,
+    /// Searches containing the term
+    pub searches: u64,
+    /// Searches containing the term with a conflicting hit
+    pub conflicted: u64,
+}
+
+/// An exported aggregate report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateReport {
+    /// Exported terms, most conflicted first
+    pub terms: Vec<TermStat>,
+    /// Number of terms withheld for too few contributors
+    pub suppressed: usize,
+    /// Settings the report was produced with
+    pub config: AggregationConfig,
+}
+
+/// Per-contributor counts for one term.
+#[derive(Debug, Default)]
+struct TermCounts {
+    /// Contributor -> (searches, conflicted)
+    contributors: HashMap<String, (u64, u64)>,
+}
+
+impl TermCounts {
+    /// Totals with each contributor clamped to `cap`.
+    fn capped(&self, cap: u64) -> (u64, u64) {
+        self.contributors.values().fold((0, 0), |(searches, conflicted), &(s, c)| {
+            (searches + s.min(cap), conflicted + c.min(cap))
+        })
+    }
+}
+
+/// Accumulates term counts from audit records.
+#[derive(Debug, Default)]
+pub struct TermAggregator {
+    terms: BTreeMap<String, TermCounts>,
+    records: usize,
+}
+
+impl TermAggregator {
+    /// Create an empty aggregator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of records added.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Add one audit record.
+    pub fn add(&mut self, record: &AuditRecord, config: &AggregationConfig) {
+        let output = &record.output;
+        let conflicted = output
+            .result
+            .hits
+            .iter()
+            .any(|hit| hit.risk_score >= config.conflict_risk);
+        let contributor = record.tenant.clone().unwrap_or_default();
+
+        let normalized = normalize_text(&output.result.query.mark_text);
+        let words: HashSet<&str> = normalized
+            .split_whitespace()
+            .filter(|word| word.chars().count() >= MIN_TERM_LEN)
+            .collect();
+
+        for word in words {
+            let counts = self.terms.entry(word.to_string()).or_default();
+            let own = counts.contributors.entry(contributor.clone()).or_default();
+            own.0 += 1;
+            own.1 += u64::from(conflicted);
+        }
+        self.records += 1;
+    }
+
+    /// Add the audit records in a directory written by
+    /// `DirectoryAuditSink`, optionally limited to a time window
+    /// (milliseconds since the Unix epoch, inclusive start, exclusive end).
+    ///
+    /// Unreadable records are skipped with a warning.
+    pub fn add_dir(
+        &mut self,
+        dir: impl AsRef<Path>,
+        window: (Option<u64>, Option<u64>),
+        config: &AggregationConfig,
+    ) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let record: AuditRecord = match fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+            {
+                Ok(record) => record,
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable audit record");
+                    continue;
+                }
+            };
+
+            let timestamp = record.decision.timestamp_ms;
+            if window.0.is_some_and(|start| timestamp < start)
+                || window.1.is_some_and(|end| timestamp >= end)
+            {
+                continue;
+            }
+            self.add(&record, config);
+        }
+        Ok(())
+    }
+
+    /// Export the aggregate, applying k-anonymity and noise.
+    ///
+    /// # Panics
+    ///
+    /// If the noise has an invalid privacy budget (see `Noise::validate`).
+    pub fn export(&self, config: &AggregationConfig) -> AggregateReport {
+        let cap = config.max_per_contributor.max(1);
+        let noise = NoiseSource::new(config.noise, cap);
+        let mut suppressed = 0;
+        let mut terms = Vec::new();
+
+        for (term, counts) in &self.terms {
+            if counts.contributors.len() < config.min_contributors.max(1) {
+                suppressed += 1;
+                continue;
+            }
+            let (searches, conflicted) = counts.capped(cap);
+            let searches = noise.perturb(searches);
+            let conflicted = noise.perturb(conflicted).min(searches);
+            terms.push(TermStat {
+                term: term.clone(),
+                searches,
+                conflicted,
+            });
+        }
+
+        terms.sort_by(|a, b| {
+            b.conflicted
+                .cmp(&a.conflicted)
+                .then(b.searches.cmp(&a.searches))
+                .then(a.term.cmp(&b.term))
+        });
+
+        AggregateReport {
+            terms,
+            suppressed,
+            config: config.clone(),
+        }
+    }
+}
+
+/// Laplace noise from a randomly keyed hasher, in the same way as the
+/// audit sampler, to avoid an RNG dependency.
+struct NoiseSource {
+    noise: Noise,
+    sensitivity: f64,
+    state: RandomState,
+    counter: std::cell::Cell<u64>,
+}
+
+impl NoiseSource {
+    fn new(noise: Noise, sensitivity: u64) -> Self {
+        // An unbounded budget would export exact counts
+        if let Err(e) = noise.validate() {
+            panic!("Invalid noise: {}", e);
+        }
+        Self {
+            noise,
+            sensitivity: sensitivity as f64,
+            state: RandomState::new(),
+            counter: std::cell::Cell::new(0),
+        }
+    }
+
+    /// Uniform value in the open interval (-0.5, 0.5).
+    fn uniform(&self) -> f64 {
+        let seq = self.counter.get();
+        self.counter.set(seq + 1);
+        let hash = self.state.hash_one(seq);
+        ((hash >> 11) as f64 + 0.5) / (1u64 << 53) as f64 - 0.5
+    }
+
+    fn perturb(&self, count: u64) -> u64 {
+        match self.noise {
+            Noise::None => count,
+            Noise::Laplace { epsilon } => {
+                let scale = self.sensitivity / epsilon;
+                let u = self.uniform();
+                let sample = -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln();
+                (count as f64 + sample).round().max(0.0) as u64
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::SamplingDecision;
+    use crate::PipelineOutput;
+    use ilegalflow_model::{CandidateHit, ClassFilter, SearchQuery, SearchResult, TrademarkRecord};
+
+    fn record(tenant: Option<&str>, mark: &str, risk: f32, timestamp_ms: u64) -> AuditRecord {
+        let query = SearchQuery::new(mark);
+        AuditRecord {
+            decision: SamplingDecision {
+                request_id: format!("{}", timestamp_ms),
+                timestamp_ms,
+                rate: 1.0,
+                roll: 0.0,
+                sampled: true,
+            },
+            backend: "mock".to_string(),
+            tenant: tenant.map(str::to_string),
+            output: PipelineOutput {
+                result: SearchResult::new(
+                    query,
+                    vec![CandidateHit {
+                        record: TrademarkRecord::new("001", "OTHER"),
+                        retrieval_score: 1.0,
+                        risk_score: risk,
+                        risk_probability: None,
+                        flags: vec![],
+                        review: None,
+                        status_adjustment: None,
+                        links: None,
+                        family: Vec::new(),
+                        dupont: None,
+                        highlights: Vec::new(),
+                    }],
+                ),
+                class_filter: ClassFilter::default(),
+                retrieved: 1,
+                explanations: vec![],
+                class_facets: vec![],
+                suppressed: vec![],
+                warnings: vec![],
+                stopped_early: false,
+                pruned: 0,
+                design_hits: Vec::new(),
+                assessment: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_k_anonymity() {
+        let config = AggregationConfig {
+            min_contributors: 3,
+            ..Default::default()
+        };
+        let mut aggregator = TermAggregator::new();
+        for tenant in ["a", "b", "c"] {
+            aggregator.add(&record(Some(tenant), "BLUE SKY", 0.9, 0), &config);
+        }
+        aggregator.add(&record(Some("a"), "BLUE OCEAN", 0.1, 0), &config);
+        // Untenanted searches are one contributor however many there are
+        for _ in 0..5 {
+            aggregator.add(&record(None, "SECRET PROJECT", 0.9, 0), &config);
+        }
+
+        let report = aggregator.export(&config);
+        assert_eq!(
+            report.terms,
+            vec![
+                TermStat {
+                    term: "BLUE".to_string(),
+                    searches: 4,
+                    conflicted: 3
+                },
+                TermStat {
+                    term: "SKY".to_string(),
+                    searches: 3,
+                    conflicted: 3
+                },
+            ]
+        );
+        // OCEAN, SECRET and PROJECT are withheld
+        assert_eq!(report.suppressed, 3);
+    }
+
+    #[test]
+    fn test_contribution_cap() {
+        let config = AggregationConfig {
+            min_contributors: 2,
+            max_per_contributor: 3,
+            ..Default::default()
+        };
+        let mut aggregator = TermAggregator::new();
+        for _ in 0..1_000 {
+            aggregator.add(&record(Some("a"), "ACME", 0.9, 0), &config);
+        }
+        aggregator.add(&record(Some("b"), "ACME", 0.1, 0), &config);
+
+        let report = aggregator.export(&config);
+        assert_eq!(
+            report.terms,
+            vec![TermStat {
+                term: "ACME".to_string(),
+                searches: 4,
+                conflicted: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_laplace_noise() {
+        let config = AggregationConfig {
+            min_contributors: 1,
+            noise: Noise::Laplace { epsilon: 0.5 },
+            ..Default::default()
+        };
+        let mut aggregator = TermAggregator::new();
+        for i in 0..50 {
+            aggregator.add(&record(Some("a"), &format!("TERM{:02} MARK", i), 0.9, 0), &config);
+        }
+
+        let report = aggregator.export(&config);
+        assert_eq!(report.terms.len(), 51);
+        assert!(report.terms.iter().all(|t| t.conflicted <= t.searches));
+        // Noisy counts differ from the true count of 1 for most terms
+        let perturbed = report.terms.iter().filter(|t| t.term != "MARK" && t.searches != 1).count();
+        assert!(perturbed > 10, "only {} of 50 counts perturbed", perturbed);
+
+        assert_eq!(Noise::laplace(0.5), Ok(config.noise));
+        for epsilon in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            assert!(Noise::laplace(epsilon).is_err());
+        }
+    }
+
+    #[test]
+    fn test_add_dir_window() {
+        let dir = std::env::temp_dir().join(format!("ilegalflow-stats-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (i, ts) in [1_000u64, 2_000, 3_000].into_iter().enumerate() {
+            let record = record(Some(&format!("t{}", i)), "ACME", 0.9, ts);
+            fs::write(dir.join(format!("{}.json", ts)), serde_json::to_vec(&record).unwrap()).unwrap();
+        }
+        fs::write(dir.join("manifest.jsonl"), "").unwrap();
+        fs::write(dir.join("broken.json"), "{").unwrap();
+
+        let config = AggregationConfig {
+            min_contributors: 1,
+            ..Default::default()
+        };
+        let mut aggregator = TermAggregator::new();
+        aggregator
+            .add_dir(&dir, (Some(2_000), Some(3_000)), &config)
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(aggregator.records(), 1);
+        assert_eq!(aggregator.export(&config).terms[0].searches, 1);
+    }
+}
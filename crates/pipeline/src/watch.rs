@@ -0,0 +1,299 @@
+//! Watchlists and their scheduling.
+//!
+//! A watchlist is a JSON file of saved searches, each re-run on its own
+//! cadence to catch newly filed conflicting marks. `WatchScheduler` keeps
+//! the due times; it is clock-agnostic (times are passed in as Unix
+//! milliseconds) so the daemon loop stays thin and the schedule testable.
+//!
+//! Every run is delayed by a random jitter so watches sharing a cadence
+//! don't hit the backend at the same instant.
+
+use ilegalflow_model::SearchQuery;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default cadence: once a day.
+pub const DEFAULT_EVERY_SECS: u64 = 86_400;
+
+fn default_every_secs() -> u64 {
+    DEFAULT_EVERY_SECS
+}
+
+/// A saved search re-run on a cadence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watch {
+    /// Unique name, used for output files and reload matching
+    pub name: String,
+
+    /// Search to run
+    pub query: SearchQuery,
+
+    /// Tenant whose reviewer overrides apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+
+    /// Seconds between runs
+    #[serde(default = "default_every_secs")]
+    pub every_secs: u64,
+}
+
+/// A watchlist file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    /// Maximum random delay added to each run, in seconds
+    #[serde(default)]
+    pub jitter_secs: u64,
+
+    pub watches: Vec<Watch>,
+}
+
+impl Watchlist {
+    /// Load a watchlist from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let list: Self = serde_json::from_reader(file)?;
+        list.validate().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(list)
+    }
+
+    /// Check that every watch has a unique, file-safe name and a nonzero cadence.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for watch in &self.watches {
+            if !is_safe_name(&watch.name) {
+                return Err(format!("invalid watch name: {:?}", watch.name));
+            }
+            if !seen.insert(watch.name.as_str()) {
+                return Err(format!("duplicate watch name: {:?}", watch.name));
+            }
+            if watch.every_secs == 0 {
+                return Err(format!("watch {:?} has every_secs = 0", watch.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` is usable as a single path component.
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Schedule state of one watch, as reported by the health endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchStatus {
+    pub name: String,
+    pub every_secs: u64,
+    /// Next run, Unix milliseconds
+    pub next_due_ms: u64,
+    /// Last run, Unix milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_ms: Option<u64>,
+    /// Error from the last run, if it failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct Scheduled {
+    watch: Watch,
+    status: WatchStatus,
+}
+
+/// Tracks when each watch is next due.
+#[derive(Debug)]
+pub struct WatchScheduler {
+    entries: Vec<Scheduled>,
+    jitter_ms: u64,
+    state: RandomState,
+    counter: AtomicU64,
+}
+
+impl WatchScheduler {
+    /// Schedule every watch for its first (jittered) run from `now_ms`.
+    pub fn new(watchlist: Watchlist, now_ms: u64) -> Self {
+        let mut scheduler = Self {
+            entries: Vec::new(),
+            jitter_ms: 0,
+            state: RandomState::new(),
+            counter: AtomicU64::new(0),
+        };
+        scheduler.reload(watchlist, now_ms);
+        scheduler
+    }
+
+    /// Replace the watchlist.
+    ///
+    /// Watches that kept their name and cadence keep their schedule and
+    /// history; new or changed watches are scheduled from `now_ms`.
+    pub fn reload(&mut self, watchlist: Watchlist, now_ms: u64) {
+        self.jitter_ms = watchlist.jitter_secs.saturating_mul(1000);
+        let mut previous = std::mem::take(&mut self.entries);
+
+        for watch in watchlist.watches {
+            let kept = previous
+                .iter()
+                .position(|e| e.watch.name == watch.name && e.watch.every_secs == watch.every_secs)
+                .map(|i| previous.swap_remove(i));
+
+            let status = match kept {
+                Some(entry) => entry.status,
+                None => WatchStatus {
+                    name: watch.name.clone(),
+                    every_secs: watch.every_secs,
+                    next_due_ms: now_ms.saturating_add(self.jitter()),
+                    last_run_ms: None,
+                    last_error: None,
+                },
+            };
+            self.entries.push(Scheduled { watch, status });
+        }
+    }
+
+    /// Number of scheduled watches.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no watches.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Earliest due time, if any watch is scheduled.
+    pub fn next_due_ms(&self) -> Option<u64> {
+        self.entries.iter().map(|e| e.status.next_due_ms).min()
+    }
+
+    /// Take the watches due at `now_ms` and schedule their next runs.
+    pub fn take_due(&mut self, now_ms: u64) -> Vec<Watch> {
+        let mut due = Vec::new();
+        for i in 0..self.entries.len() {
+            if self.entries[i].status.next_due_ms <= now_ms {
+                let jitter = self.jitter();
+                let entry = &mut self.entries[i];
+                entry.status.last_run_ms = Some(now_ms);
+                entry.status.next_due_ms = now_ms
+                    .saturating_add(entry.watch.every_secs.saturating_mul(1000))
+                    .saturating_add(jitter);
+                due.push(entry.watch.clone());
+            }
+        }
+        due
+    }
+
+    /// Record the outcome of a watch run.
+    pub fn record_result(&mut self, name: &str, error: Option<String>) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.watch.name == name) {
+            entry.status.last_error = error;
+        }
+    }
+
+    /// Schedule state of every watch.
+    pub fn status(&self) -> Vec<WatchStatus> {
+        self.entries.iter().map(|e| e.status.clone()).collect()
+    }
+
+    /// Uniform random delay in [0, jitter_ms].
+    fn jitter(&self) -> u64 {
+        if self.jitter_ms == 0 {
+            return 0;
+        }
+        let seq = self.counter.fetch_add(1, Ordering::Relaxed);
+        self.state.hash_one(seq) % self.jitter_ms.saturating_add(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchlist(jitter_secs: u64, watches: &[(&str, u64)]) -> Watchlist {
+        Watchlist {
+            jitter_secs,
+            watches: watches
+                .iter()
+                .map(|&(name, every_secs)| Watch {
+                    name: name.to_string(),
+                    query: SearchQuery::new(name),
+                    tenant: None,
+                    every_secs,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_scheduling_and_jitter() {
+        let mut scheduler = WatchScheduler::new(watchlist(0, &[("NIKE", 60), ("APPLE", 3600)]), 0);
+        assert_eq!(scheduler.next_due_ms(), Some(0));
+        assert_eq!(scheduler.take_due(0).len(), 2);
+
+        assert!(scheduler.take_due(59_999).is_empty());
+        let due = scheduler.take_due(60_000);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].name, "NIKE");
+        assert_eq!(scheduler.next_due_ms(), Some(120_000));
+
+        let jittered = WatchScheduler::new(watchlist(10, &[("NIKE", 60)]), 0);
+        assert!(jittered.next_due_ms().unwrap() <= 10_000);
+    }
+
+    #[test]
+    fn test_reload_keeps_unchanged_schedules() {
+        let mut scheduler = WatchScheduler::new(watchlist(0, &[("NIKE", 60), ("APPLE", 60)]), 0);
+        scheduler.take_due(0);
+        scheduler.record_result("NIKE", Some("timeout".to_string()));
+
+        scheduler.reload(watchlist(0, &[("NIKE", 60), ("APPLE", 120), ("ACME", 60)]), 1_000);
+        let status = scheduler.status();
+
+        assert_eq!(scheduler.len(), 3);
+        assert_eq!(status[0].next_due_ms, 60_000);
+        assert_eq!(status[0].last_error.as_deref(), Some("timeout"));
+        assert_eq!(status[1].next_due_ms, 1_000);
+        assert_eq!(status[2].next_due_ms, 1_000);
+    }
+
+    #[test]
+    fn test_watchlist_defaults() {
+        let json = r#"{"watches": [{"name": "nike", "query": {"mark_text": "NIKE"}}]}"#;
+        let list: Watchlist = serde_json::from_str(json).unwrap();
+        assert_eq!(list.jitter_secs, 0);
+        assert_eq!(list.watches[0].every_secs, DEFAULT_EVERY_SECS);
+        assert_eq!(list.watches[0].query.limit, 100);
+    }
+
+    #[test]
+    fn test_watchlist_validation() {
+        assert!(watchlist(0, &[("nike-daily", 60), ("apple_v2", 60)]).validate().is_ok());
+
+        for name in ["", "..", "../etc/cron", "a/b", "a\\b", "nike daily"] {
+            assert!(watchlist(0, &[(name, 60)]).validate().is_err(), "{name:?}");
+        }
+        assert!(watchlist(0, &[("NIKE", 60), ("NIKE", 120)]).validate().is_err());
+        assert!(watchlist(0, &[("NIKE", 0)]).validate().is_err());
+
+        let path = std::env::temp_dir()
+            .join(format!("ilegalflow-watchlist-{}.json", std::process::id()));
+        let json = r#"{"watches": [{"name": "../x", "query": {"mark_text": "NIKE"}}]}"#;
+        std::fs::write(&path, json).unwrap();
+        let err = Watchlist::load(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_huge_cadence_saturates() {
+        let mut scheduler = WatchScheduler::new(watchlist(0, &[("NIKE", u64::MAX)]), 0);
+        scheduler.take_due(0);
+        assert_eq!(scheduler.next_due_ms(), Some(u64::MAX));
+    }
+}
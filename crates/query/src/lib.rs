@@ -2,18 +2,318 @@
 //!
 //! Converts abstract `SearchQuery` into backend-specific query syntax:
 //! - Manticore SQL
-//! - USPTO TESS syntax (future)
-//! - Tantivy query (future)
+//! - USPTO TESS syntax
+//! - Tantivy query tree (see `tantivy`)
+//!
+//! `validate` checks a whole query up front and reports every problem.
+
+pub mod tantivy;
+pub mod validate;
+
+pub use tantivy::{Occur, TantivyDialect, TantivyQuery};
+pub use validate::{validate, ValidationErrors, ValidationIssue};
 
-use ilegalflow_model::SearchQuery;
+use ilegalflow_model::design::normalize_design_code;
+use ilegalflow_model::{
+    clean_free_text, ClassFilter, ClassTaxonomy, Date, NiceClass, SearchQuery, TrademarkStatus,
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum QueryError {
     #[error("Empty query text")]
     EmptyQuery,
-    #[error("Invalid class number: {0}")]
-    InvalidClass(u16),
+    #[error("Invalid wildcard in term '{0}': '*' is only allowed at the start or end of a term")]
+    InvalidWildcard(String),
+    #[error("Truncated term '{0}' is too short (minimum {MIN_TRUNCATED_STEM} characters)")]
+    TruncationTooShort(String),
+    #[error("Invalid date '{0}': expected YYYY-MM-DD")]
+    InvalidDate(String),
+    #[error("Invalid date range: {0} is after {1}")]
+    InvalidDateRange(String, String),
+    #[error("Limit {0} is out of range (1 - {max})", max = validate::MAX_LIMIT)]
+    LimitOutOfRange(usize),
+    #[error("Mark text is {0} characters long (maximum {max})", max = validate::MAX_MARK_TEXT_LEN)]
+    MarkTextTooLong(usize),
+    #[error("Invalid design code '{0}': expected e.g. 26.01.03")]
+    InvalidDesignCode(String),
+    #[error("Invalid serial number '{0}': expected digits only")]
+    InvalidSerial(String),
+    #[error("Class filter {0} has no numbered classes to search")]
+    UnindexedClasses(String),
+}
+
+impl QueryError {
+    /// Stable machine-readable code for API responses.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::EmptyQuery => "empty_query",
+            Self::InvalidWildcard(_) => "invalid_wildcard",
+            Self::TruncationTooShort(_) => "truncation_too_short",
+            Self::InvalidDate(_) => "invalid_date",
+            Self::InvalidDateRange(_, _) => "invalid_date_range",
+            Self::LimitOutOfRange(_) => "limit_out_of_range",
+            Self::MarkTextTooLong(_) => "mark_text_too_long",
+            Self::InvalidDesignCode(_) => "invalid_design_code",
+            Self::InvalidSerial(_) => "invalid_serial",
+            Self::UnindexedClasses(_) => "unindexed_classes",
+        }
+    }
+}
+
+/// Minimum number of literal characters in a truncated term.
+pub const MIN_TRUNCATED_STEM: usize = 2;
+
+/// Single-word marks up to this length get suffix truncation when
+/// `SearchQuery::auto_truncate` is set.
+pub const AUTO_TRUNCATE_MAX_LEN: usize = 4;
+
+/// A query term with optional truncation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    /// Literal text without wildcards
+    pub text: String,
+    /// Leading `*` (matches any prefix)
+    pub leading: bool,
+    /// Trailing `*` (matches any suffix)
+    pub trailing: bool,
+}
+
+impl Term {
+    /// Check whether this term uses truncation.
+    pub fn is_truncated(&self) -> bool {
+        self.leading || self.trailing
+    }
+
+    /// Render with `*` truncation markers (shared by Manticore and TESS).
+    pub fn to_wildcard_string(&self) -> String {
+        format!(
+            "{}{}{}",
+            if self.leading { "*" } else { "" },
+            self.text,
+            if self.trailing { "*" } else { "" }
+        )
+    }
+}
+
+/// Parse mark text into terms, validating wildcard placement.
+///
+/// `*` may appear only at the start and/or end of a term, and a truncated
+/// term must keep at least `MIN_TRUNCATED_STEM` literal characters.
+pub fn parse_terms(text: &str) -> Result<Vec<Term>, QueryError> {
+    text.split_whitespace()
+        .map(|word| {
+            let leading = word.starts_with('*');
+            let trailing = word.len() > 1 && word.ends_with('*');
+            let inner = word.trim_start_matches('*').trim_end_matches('*');
+
+            if inner.contains('*') || word.starts_with("**") || word.ends_with("**") {
+                return Err(QueryError::InvalidWildcard(word.to_string()));
+            }
+
+            let term = Term {
+                text: inner.to_string(),
+                leading,
+                trailing,
+            };
+            if term.is_truncated() && term.text.chars().count() < MIN_TRUNCATED_STEM {
+                return Err(QueryError::TruncationTooShort(word.to_string()));
+            }
+
+            Ok(term)
+        })
+        .collect()
+}
+
+/// Parse the query's mark text, applying auto-truncation if requested.
+///
+/// May return no terms when the query is purely fielded (owner or
+/// goods/services only).
+pub fn query_terms(query: &SearchQuery) -> Result<Vec<Term>, QueryError> {
+    let mut terms = parse_terms(&query.mark_text)?;
+
+    if query.auto_truncate
+        && terms.len() == 1
+        && !terms[0].is_truncated()
+        && terms[0].text.chars().count() <= AUTO_TRUNCATE_MAX_LEN
+        && terms[0].text.chars().count() >= MIN_TRUNCATED_STEM
+    {
+        terms[0].trailing = true;
+    }
+
+    Ok(terms)
+}
+
+/// Parse an ISO `YYYY-MM-DD` date into days since the Unix epoch.
+pub fn parse_iso_date(date: &str) -> Result<i64, QueryError> {
+    Date::parse_iso(date)
+        .map(|date| date.days())
+        .ok_or_else(|| QueryError::InvalidDate(date.to_string()))
+}
+
+/// Format days since the Unix epoch as an ISO `YYYY-MM-DD` date.
+pub fn format_iso_date(days: i64) -> String {
+    Date::from_days(days).to_string()
+}
+
+/// ISO date `days` before today (UTC), for "last N days" filters.
+pub fn iso_date_days_ago(days: i64) -> String {
+    Date::today().add_days(-days).to_string()
+}
+
+/// A resolved date-range condition on a timestamp attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateBound {
+    /// Attribute name (e.g. `filing_date`)
+    pub attribute: &'static str,
+    /// `>=` for lower bounds, `<=` for upper bounds
+    pub operator: &'static str,
+    /// Unix timestamp (seconds) of the bound
+    pub timestamp: i64,
+}
+
+/// Resolve and validate the query's date filters.
+///
+/// Bounds are inclusive: an upper bound covers the whole day.
+pub fn date_bounds(query: &SearchQuery) -> Result<Vec<DateBound>, QueryError> {
+    let mut bounds = Vec::new();
+
+    let filed_after = query.filed_after.as_deref().map(parse_iso_date).transpose()?;
+    let filed_before = query.filed_before.as_deref().map(parse_iso_date).transpose()?;
+    let status_after = query
+        .status_changed_after
+        .as_deref()
+        .map(parse_iso_date)
+        .transpose()?;
+
+    if let (Some(after), Some(before)) = (filed_after, filed_before) {
+        if after > before {
+            return Err(QueryError::InvalidDateRange(
+                format_iso_date(after),
+                format_iso_date(before),
+            ));
+        }
+    }
+
+    if let Some(days) = filed_after {
+        bounds.push(DateBound {
+            attribute: "filing_date",
+            operator: ">=",
+            timestamp: days * 86_400,
+        });
+    }
+    if let Some(days) = filed_before {
+        bounds.push(DateBound {
+            attribute: "filing_date",
+            operator: "<=",
+            timestamp: (days + 1) * 86_400 - 1,
+        });
+    }
+    if let Some(days) = status_after {
+        bounds.push(DateBound {
+            attribute: "status_date",
+            operator: ">=",
+            timestamp: days * 86_400,
+        });
+    }
+
+    Ok(bounds)
+}
+
+/// Searchable text fields beyond the mark itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    Owner,
+    GoodsServices,
+}
+
+impl TextField {
+    /// Manticore full-text field name.
+    pub fn manticore_field(&self) -> &'static str {
+        match self {
+            Self::Owner => "owner_name",
+            Self::GoodsServices => "goods_services",
+        }
+    }
+
+    /// TESS field code.
+    pub fn tess_field(&self) -> &'static str {
+        match self {
+            Self::Owner => "ON",
+            Self::GoodsServices => "GS",
+        }
+    }
+}
+
+/// Get the non-empty fielded text criteria of a query.
+///
+/// Free text is cleaned of full-text operator characters.
+pub fn fielded_text(query: &SearchQuery) -> Vec<(TextField, String)> {
+    [
+        (TextField::Owner, &query.owner_query),
+        (TextField::GoodsServices, &query.goods_services_query),
+    ]
+    .into_iter()
+    .filter_map(|(field, text)| {
+        let cleaned = clean_free_text(text.as_deref()?);
+        (!cleaned.is_empty()).then_some((field, cleaned))
+    })
+    .collect()
+}
+
+/// Get the query's excluded owners, cleaned like fielded text.
+pub fn excluded_owners(query: &SearchQuery) -> Vec<String> {
+    query
+        .exclude_owners
+        .iter()
+        .map(|owner| clean_free_text(owner))
+        .filter(|owner| !owner.is_empty())
+        .collect()
+}
+
+/// Get the query's excluded serial numbers, checking they are numeric.
+pub fn excluded_serials(query: &SearchQuery) -> Result<Vec<String>, QueryError> {
+    query
+        .exclude_serials
+        .iter()
+        .map(|serial| {
+            let serial = serial.trim();
+            if serial.is_empty() || !serial.chars().all(|c| c.is_ascii_digit()) {
+                return Err(QueryError::InvalidSerial(serial.to_string()));
+            }
+            Ok(serial.to_string())
+        })
+        .collect()
+}
+
+/// A class as a three-digit index code (`025`); US letter classes as
+/// their letter.
+pub fn class_code(class: NiceClass) -> String {
+    match class.number() {
+        Some(number) => format!("{:03}", number),
+        None => class.to_string(),
+    }
+}
+
+/// Get the query's design codes as index terms.
+///
+/// Codes are indexed without dots (`260103`); category and division
+/// codes become prefix terms (`2601*`) covering the sections beneath.
+pub fn design_code_terms(query: &SearchQuery) -> Result<Vec<String>, QueryError> {
+    query
+        .design_codes
+        .iter()
+        .map(|code| {
+            let normalized = normalize_design_code(code)
+                .ok_or_else(|| QueryError::InvalidDesignCode(code.clone()))?;
+            let digits = normalized.replace('.', "");
+            Ok(if digits.len() < 6 {
+                format!("{}*", digits)
+            } else {
+                digits
+            })
+        })
+        .collect()
 }
 
 /// Trait for translating queries to backend-specific syntax.
@@ -27,18 +327,62 @@ pub trait QueryDialect {
 
 /// Manticore SQL dialect generator.
 #[derive(Debug, Default)]
-pub struct ManticoreDialect;
+pub struct ManticoreDialect {
+    /// Class clusters used when the query asks for cluster expansion
+    pub taxonomy: ClassTaxonomy,
+}
 
-impl QueryDialect for ManticoreDialect {
-    type Output = String;
+impl ManticoreDialect {
+    /// Build the full-text MATCH expression.
+    ///
+    /// Mark-only queries stay unscoped; once owner or goods/services text
+    /// is present each part is scoped to its field.
+    pub fn match_expression(&self, query: &SearchQuery) -> Result<String, QueryError> {
+        let terms = query_terms(query)?;
+        let mark_text = terms
+            .iter()
+            .map(Term::to_wildcard_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let fielded = fielded_text(query);
+        let design_codes = design_code_terms(query)?;
 
-    fn translate(&self, query: &SearchQuery) -> Result<String, QueryError> {
-        if query.mark_text.trim().is_empty() {
-            return Err(QueryError::EmptyQuery);
+        let exclusions: Vec<String> = excluded_owners(query)
+            .into_iter()
+            .map(|owner| format!(" -(@owner_name \"{}\")", owner))
+            .collect();
+
+        if fielded.is_empty() && design_codes.is_empty() {
+            if mark_text.is_empty() {
+                return Err(QueryError::EmptyQuery);
+            }
+            return Ok(format!("{}{}", mark_text, exclusions.concat()));
+        }
+
+        let mut parts = Vec::new();
+        if !mark_text.is_empty() {
+            parts.push(format!("@mark_text {}", mark_text));
         }
+        for (field, text) in fielded {
+            let text = match field {
+                TextField::Owner => format!("\"{}\"", text),
+                TextField::GoodsServices => text,
+            };
+            parts.push(format!("@{} {}", field.manticore_field(), text));
+        }
+        if !design_codes.is_empty() {
+            parts.push(format!("@design_codes ({})", design_codes.join(" | ")));
+        }
+
+        Ok(format!("{}{}", parts.join(" "), exclusions.concat()))
+    }
+
+    /// Build the WHERE clause (MATCH plus attribute filters) for a query.
+    pub fn where_clause(&self, query: &SearchQuery) -> Result<String, QueryError> {
+        let match_text = self.match_expression(query)?;
 
         // Escape single quotes for SQL
-        let escaped = query.mark_text.replace('\'', "''");
+        let escaped = match_text.replace('\'', "''");
 
         // Build MATCH clause
         let match_clause = format!("MATCH('{}')", escaped);
@@ -46,13 +390,56 @@ impl QueryDialect for ManticoreDialect {
         // Build WHERE conditions
         let mut conditions = vec![match_clause];
 
+        // Add class filter (with cluster expansion) if specified
+        let class_filter = ClassFilter::resolve(query, &self.taxonomy);
+        if !class_filter.is_empty() {
+            // The index holds class numbers only, so letter classes
+            // cannot match
+            let classes: Vec<String> = class_filter
+                .classes()
+                .iter()
+                .filter_map(|c| c.number())
+                .map(|n| n.to_string())
+                .collect();
+            if classes.is_empty() {
+                let filter: Vec<String> =
+                    class_filter.classes().iter().map(|c| c.to_string()).collect();
+                return Err(QueryError::UnindexedClasses(filter.join(", ")));
+            }
+            conditions.push(format!("ANY(classes) IN ({})", classes.join(", ")));
+        }
+
         // Add status filter if specified
-        if let Some(status) = &query.status_filter {
-            conditions.push(format!("status = '{:?}'", status));
+        match query.status_filter.as_slice() {
+            [] => {}
+            [status] => conditions.push(format!("status = '{:?}'", status)),
+            statuses => {
+                let statuses: Vec<String> = statuses.iter().map(|s| format!("'{:?}'", s)).collect();
+                conditions.push(format!("status IN ({})", statuses.join(", ")));
+            }
+        }
+
+        // Exclude specific registrations
+        let serials = excluded_serials(query)?;
+        if !serials.is_empty() {
+            let serials: Vec<String> = serials.iter().map(|s| format!("'{}'", s)).collect();
+            conditions.push(format!("serial_number NOT IN ({})", serials.join(", ")));
+        }
+
+        // Add date range filters on timestamp attributes
+        for bound in date_bounds(query)? {
+            conditions.push(format!("{} {} {}", bound.attribute, bound.operator, bound.timestamp));
         }
 
-        // Build final query
-        let where_clause = conditions.join(" AND ");
+        Ok(conditions.join(" AND "))
+    }
+}
+
+impl QueryDialect for ManticoreDialect {
+    type Output = String;
+
+    fn translate(&self, query: &SearchQuery) -> Result<String, QueryError> {
+        let where_clause = self.where_clause(query)?;
         let sql = format!(
             "SELECT * FROM trademarks WHERE {} LIMIT {}",
             where_clause, query.limit
@@ -62,9 +449,77 @@ impl QueryDialect for ManticoreDialect {
     }
 }
 
+/// USPTO TESS search syntax generator.
+///
+/// Produces a free-form search string such as
+/// `(NIK*)[BI] AND (025 OR 035)[IC] AND LIVE[LD]`. Date filters have no
+/// TESS equivalent and are validated but not emitted.
+#[derive(Debug, Default)]
+pub struct TessDialect {
+    /// Class clusters used when the query asks for cluster expansion
+    pub taxonomy: ClassTaxonomy,
+}
+
+impl QueryDialect for TessDialect {
+    type Output = String;
+
+    fn translate(&self, query: &SearchQuery) -> Result<String, QueryError> {
+        let terms = query_terms(query)?;
+        let mark = terms
+            .iter()
+            .map(Term::to_wildcard_string)
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let mut clauses = Vec::new();
+        if !mark.is_empty() {
+            clauses.push(format!("({})[BI]", mark));
+        }
+        for (field, text) in fielded_text(query) {
+            clauses.push(format!("({})[{}]", text, field.tess_field()));
+        }
+        let design_codes = design_code_terms(query)?;
+        if !design_codes.is_empty() {
+            clauses.push(format!("({})[DC]", design_codes.join(" OR ")));
+        }
+        if clauses.is_empty() {
+            return Err(QueryError::EmptyQuery);
+        }
+
+        let class_filter = ClassFilter::resolve(query, &self.taxonomy);
+        if !class_filter.is_empty() {
+            let classes: Vec<String> =
+                class_filter.classes().iter().map(|c| class_code(*c)).collect();
+            clauses.push(format!("({})[IC]", classes.join(" OR ")));
+        }
+
+        for owner in excluded_owners(query) {
+            clauses.push(format!("NOT ({})[ON]", owner));
+        }
+        let serials = excluded_serials(query)?;
+        if !serials.is_empty() {
+            clauses.push(format!("NOT ({})[SN]", serials.join(" OR ")));
+        }
+
+        date_bounds(query)?;
+
+        // TESS only distinguishes live (incl. pending) from dead, so a
+        // filter spanning both is no filter at all
+        let wants_dead = query.status_filter.contains(&TrademarkStatus::Dead);
+        let wants_live = query.status_filter.iter().any(|s| *s != TrademarkStatus::Dead);
+        match (wants_live, wants_dead) {
+            (true, false) => clauses.push("LIVE[LD]".to_string()),
+            (false, true) => clauses.push("DEAD[LD]".to_string()),
+            _ => {}
+        }
+
+        Ok(clauses.join(" AND "))
+    }
+}
+
 /// Generate phonetic variants of a query term.
 pub fn generate_variants(text: &str) -> Vec<String> {
-    let mut variants = vec![text.to_string()];
+    let variants = vec![text.to_string()];
 
     // TODO: Add phonetic variants
     // This would use ilegalflow-features to generate soundex/metaphone codes
@@ -79,7 +534,7 @@ mod tests {
 
     #[test]
     fn test_manticore_basic() {
-        let dialect = ManticoreDialect;
+        let dialect = ManticoreDialect::default();
         let query = SearchQuery::new("NIKE");
         let sql = dialect.translate(&query).unwrap();
         assert!(sql.contains("MATCH('NIKE')"));
@@ -88,15 +543,184 @@ mod tests {
 
     #[test]
     fn test_manticore_escaping() {
-        let dialect = ManticoreDialect;
+        let dialect = ManticoreDialect::default();
         let query = SearchQuery::new("O'REILLY");
         let sql = dialect.translate(&query).unwrap();
         assert!(sql.contains("O''REILLY"));
     }
 
+    #[test]
+    fn test_manticore_class_filter() {
+        let dialect = ManticoreDialect::default();
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let sql = dialect.translate(&query).unwrap();
+        assert!(sql.contains("ANY(classes) IN (25)"));
+
+        let mut query = query;
+        query.expand_class_clusters = true;
+        let sql = dialect.translate(&query).unwrap();
+        assert!(sql.contains("ANY(classes) IN (14, 18, 25, 35)"));
+
+        // Letter classes are not indexed, so a filter of only those is
+        // refused rather than emitted as an empty IN list
+        let query = SearchQuery::new("NIKE").with_classes(vec![NiceClass::A]);
+        let err = dialect.translate(&query).unwrap_err();
+        assert!(matches!(err, QueryError::UnindexedClasses(_)));
+        assert_eq!(err.code(), "unindexed_classes");
+        let query = SearchQuery::new("NIKE").with_classes(vec![NiceClass::A, NiceClass::of(25)]);
+        assert!(dialect.translate(&query).unwrap().contains("ANY(classes) IN (25)"));
+    }
+
+    #[test]
+    fn test_wildcard_translation() {
+        let dialect = ManticoreDialect::default();
+        let sql = dialect.translate(&SearchQuery::new("NIK*")).unwrap();
+        assert!(sql.contains("MATCH('NIK*')"));
+
+        let tess = TessDialect::default();
+        let query = SearchQuery::new("NIK*").with_classes(NiceClass::of_all(&[25]));
+        assert_eq!(tess.translate(&query).unwrap(), "(NIK*)[BI] AND (025)[IC]");
+    }
+
+    #[test]
+    fn test_wildcard_validation() {
+        assert!(matches!(parse_terms("N*KE"), Err(QueryError::InvalidWildcard(_))));
+        assert!(matches!(parse_terms("NIKE**"), Err(QueryError::InvalidWildcard(_))));
+        assert!(matches!(parse_terms("N*"), Err(QueryError::TruncationTooShort(_))));
+
+        let terms = parse_terms("*BOOK AIR*").unwrap();
+        assert!(terms[0].leading && !terms[0].trailing);
+        assert!(terms[1].trailing);
+        assert_eq!(terms[1].text, "AIR");
+    }
+
+    #[test]
+    fn test_auto_truncate_short_marks() {
+        let dialect = ManticoreDialect::default();
+        let mut query = SearchQuery::new("NIKE");
+        query.auto_truncate = true;
+        assert!(dialect.translate(&query).unwrap().contains("MATCH('NIKE*')"));
+
+        query.mark_text = "AMAZON".to_string();
+        assert!(dialect.translate(&query).unwrap().contains("MATCH('AMAZON')"));
+    }
+
+    #[test]
+    fn test_fielded_search() {
+        let dialect = ManticoreDialect::default();
+        let mut query = SearchQuery::new("")
+            .with_owner("Apple, Inc.")
+            .with_classes(NiceClass::of_all(&[9]));
+        query.status_filter = vec![TrademarkStatus::Live];
+        let sql = dialect.translate(&query).unwrap();
+        assert!(sql.contains("MATCH('@owner_name \"Apple Inc\"')"));
+        assert!(sql.contains("ANY(classes) IN (9)"));
+        assert!(sql.contains("status = 'Live'"));
+
+        let query = SearchQuery::new("IPHONE").with_goods_services("smart phones");
+        let sql = dialect.translate(&query).unwrap();
+        assert!(sql.contains("MATCH('@mark_text IPHONE @goods_services smart phones')"));
+
+        let tess = TessDialect::default();
+        assert_eq!(
+            tess.translate(&query).unwrap(),
+            "(IPHONE)[BI] AND (smart phones)[GS]"
+        );
+    }
+
+    #[test]
+    fn test_design_code_filter() {
+        let mut query = SearchQuery::new("");
+        query.design_codes = vec!["26.01.03".to_string(), "0301".to_string()];
+
+        let sql = ManticoreDialect::default().translate(&query).unwrap();
+        assert!(sql.contains("MATCH('@design_codes (260103 | 0301*)')"));
+
+        query.mark_text = "APPLE".to_string();
+        let tess = TessDialect::default().translate(&query).unwrap();
+        assert_eq!(tess, "(APPLE)[BI] AND (260103 OR 0301*)[DC]");
+
+        query.design_codes = vec!["26-01".to_string()];
+        assert!(matches!(
+            ManticoreDialect::default().translate(&query),
+            Err(QueryError::InvalidDesignCode(_))
+        ));
+    }
+
+    #[test]
+    fn test_exclude_own_portfolio() {
+        let mut query = SearchQuery::new("NIKE");
+        query.exclude_owners = vec!["Nike, Inc.".to_string()];
+        query.exclude_serials = vec!["72123456".to_string(), "73000001".to_string()];
+
+        let sql = ManticoreDialect::default().translate(&query).unwrap();
+        assert!(sql.contains("MATCH('NIKE -(@owner_name \"Nike Inc\")')"));
+        assert!(sql.contains("serial_number NOT IN ('72123456', '73000001')"));
+
+        let tess = TessDialect::default().translate(&query).unwrap();
+        assert_eq!(
+            tess,
+            "(NIKE)[BI] AND NOT (Nike Inc)[ON] AND NOT (72123456 OR 73000001)[SN]"
+        );
+
+        query.exclude_serials = vec!["1' OR '1".to_string()];
+        assert!(matches!(
+            ManticoreDialect::default().translate(&query),
+            Err(QueryError::InvalidSerial(_))
+        ));
+    }
+
+    #[test]
+    fn test_multiple_status_filter() {
+        let query = SearchQuery::new("NIKE")
+            .with_statuses(vec![TrademarkStatus::Live, TrademarkStatus::Pending]);
+
+        let sql = ManticoreDialect::default().translate(&query).unwrap();
+        assert!(sql.contains("status IN ('Live', 'Pending')"));
+
+        let tess = TessDialect::default().translate(&query).unwrap();
+        assert_eq!(tess, "(NIKE)[BI] AND LIVE[LD]");
+
+        let query = query.with_statuses(vec![TrademarkStatus::Live, TrademarkStatus::Dead]);
+        let tess = TessDialect::default().translate(&query).unwrap();
+        assert_eq!(tess, "(NIKE)[BI]");
+    }
+
+    #[test]
+    fn test_iso_dates() {
+        assert_eq!(parse_iso_date("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_iso_date("2024-02-29").unwrap(), 19_782);
+        assert_eq!(format_iso_date(19_782), "2024-02-29");
+        assert!(parse_iso_date("2023-02-29").is_err());
+        assert!(parse_iso_date("2024-1-05").is_err());
+        assert!(parse_iso_date("20240105").is_err());
+    }
+
+    #[test]
+    fn test_date_range_filters() {
+        let dialect = ManticoreDialect::default();
+        let mut query = SearchQuery::new("NIKE");
+        query.filed_after = Some("2024-01-01".to_string());
+        query.filed_before = Some("2024-01-31".to_string());
+        query.status_changed_after = Some("2024-06-01".to_string());
+        let sql = dialect.translate(&query).unwrap();
+        assert!(sql.contains("filing_date >= 1704067200"));
+        assert!(sql.contains("filing_date <= 1706745599"));
+        assert!(sql.contains("status_date >= 1717200000"));
+
+        query.filed_before = Some("2023-12-31".to_string());
+        assert!(matches!(
+            dialect.translate(&query),
+            Err(QueryError::InvalidDateRange(_, _))
+        ));
+
+        query.filed_before = Some("31/12/2024".to_string());
+        assert!(matches!(dialect.translate(&query), Err(QueryError::InvalidDate(_))));
+    }
+
     #[test]
     fn test_empty_query_error() {
-        let dialect = ManticoreDialect;
+        let dialect = ManticoreDialect::default();
         let query = SearchQuery::new("   ");
         assert!(matches!(
             dialect.translate(&query),
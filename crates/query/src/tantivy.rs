@@ -0,0 +1,415 @@
+//! Tantivy query dialect.
+//!
+//! Unlike the Manticore and TESS dialects this produces a structured
+//! query tree rather than a string, mirroring Tantivy's query types
+//! (`BooleanQuery`, `FuzzyTermQuery`, `PhrasePrefixQuery`, ...). The
+//! future Tantivy backend maps each node onto the matching Tantivy
+//! query against its schema, so no query-parser escaping is involved.
+//!
+//! Text terms are lowercased to match Tantivy's default tokenizer.
+
+use crate::{
+    class_code, date_bounds, design_code_terms, excluded_owners, excluded_serials, fielded_text,
+    query_terms, QueryDialect, QueryError, Term, TextField,
+};
+use ilegalflow_model::{ClassFilter, ClassTaxonomy, SearchQuery};
+
+/// How a clause participates in a boolean query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Occur {
+    Must,
+    Should,
+    MustNot,
+}
+
+/// A Tantivy query tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TantivyQuery {
+    /// Exact term
+    Term { field: &'static str, text: String },
+
+    /// Term within a Levenshtein distance
+    Fuzzy {
+        field: &'static str,
+        text: String,
+        distance: u8,
+        transpositions: bool,
+    },
+
+    /// Terms starting with a prefix (trailing truncation)
+    Prefix { field: &'static str, text: String },
+
+    /// Terms matching a regex (leading truncation)
+    Regex { field: &'static str, pattern: String },
+
+    /// Adjacent terms in order
+    Phrase {
+        field: &'static str,
+        terms: Vec<String>,
+    },
+
+    /// Documents under any of the facet paths (e.g. `/nice/025`)
+    Facets {
+        field: &'static str,
+        paths: Vec<String>,
+    },
+
+    /// Inclusive range on an i64 fast field
+    Range {
+        field: &'static str,
+        lower: Option<i64>,
+        upper: Option<i64>,
+    },
+
+    /// Boolean combination of clauses
+    Boolean(Vec<(Occur, TantivyQuery)>),
+}
+
+impl TantivyQuery {
+    /// Match any of the queries (a single query is returned as is).
+    fn any_of(mut queries: Vec<TantivyQuery>) -> Self {
+        if queries.len() == 1 {
+            return queries.remove(0);
+        }
+        Self::Boolean(queries.into_iter().map(|q| (Occur::Should, q)).collect())
+    }
+
+    /// Get the top-level clauses of a boolean query.
+    pub fn clauses(&self) -> &[(Occur, TantivyQuery)] {
+        match self {
+            Self::Boolean(clauses) => clauses,
+            _ => &[],
+        }
+    }
+}
+
+/// Fuzzy distance for a term of a given length: exact for very short
+/// terms, where one edit changes the word entirely, and at most 2 (the
+/// Tantivy maximum) for long ones.
+pub fn fuzzy_distance(len: usize) -> u8 {
+    match len {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// Tantivy query tree generator.
+#[derive(Debug, Default)]
+pub struct TantivyDialect {
+    /// Class clusters used when the query asks for cluster expansion
+    pub taxonomy: ClassTaxonomy,
+}
+
+impl TantivyDialect {
+    /// Build the query for a single mark term.
+    fn mark_term(&self, term: &Term, fuzzy: bool) -> TantivyQuery {
+        let field = "mark_text";
+        let text = term.text.to_lowercase();
+
+        match (term.leading, term.trailing) {
+            (false, true) => TantivyQuery::Prefix { field, text },
+            (true, trailing) => TantivyQuery::Regex {
+                field,
+                pattern: format!(".*{}{}", regex_escape(&text), if trailing { ".*" } else { "" }),
+            },
+            (false, false) => match fuzzy_distance(text.chars().count()) {
+                distance if fuzzy && distance > 0 => TantivyQuery::Fuzzy {
+                    field,
+                    text,
+                    distance,
+                    transpositions: true,
+                },
+                _ => TantivyQuery::Term { field, text },
+            },
+        }
+    }
+}
+
+impl QueryDialect for TantivyDialect {
+    type Output = TantivyQuery;
+
+    fn translate(&self, query: &SearchQuery) -> Result<TantivyQuery, QueryError> {
+        let mut clauses = Vec::new();
+
+        for term in query_terms(query)? {
+            clauses.push((Occur::Must, self.mark_term(&term, query.fuzzy)));
+        }
+
+        for (field, text) in fielded_text(query) {
+            let words = text.split_whitespace().map(str::to_lowercase);
+            match field {
+                TextField::Owner => clauses.push((
+                    Occur::Must,
+                    TantivyQuery::Phrase {
+                        field: field.manticore_field(),
+                        terms: words.collect(),
+                    },
+                )),
+                TextField::GoodsServices => {
+                    for text in words {
+                        clauses.push((
+                            Occur::Must,
+                            TantivyQuery::Term {
+                                field: field.manticore_field(),
+                                text,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        let design_codes = design_code_terms(query)?;
+        if !design_codes.is_empty() {
+            let codes = design_codes
+                .into_iter()
+                .map(|code| match code.strip_suffix('*') {
+                    Some(prefix) => TantivyQuery::Prefix {
+                        field: "design_codes",
+                        text: prefix.to_string(),
+                    },
+                    None => TantivyQuery::Term {
+                        field: "design_codes",
+                        text: code,
+                    },
+                })
+                .collect();
+            clauses.push((Occur::Must, TantivyQuery::any_of(codes)));
+        }
+
+        if clauses.is_empty() {
+            return Err(QueryError::EmptyQuery);
+        }
+
+        for owner in excluded_owners(query) {
+            clauses.push((
+                Occur::MustNot,
+                TantivyQuery::Phrase {
+                    field: "owner_name",
+                    terms: owner.split_whitespace().map(str::to_lowercase).collect(),
+                },
+            ));
+        }
+        for serial in excluded_serials(query)? {
+            clauses.push((
+                Occur::MustNot,
+                TantivyQuery::Term {
+                    field: "serial_number",
+                    text: serial,
+                },
+            ));
+        }
+
+        let class_filter = ClassFilter::resolve(query, &self.taxonomy);
+        if !class_filter.is_empty() {
+            clauses.push((
+                Occur::Must,
+                TantivyQuery::Facets {
+                    field: "classes",
+                    paths: class_filter
+                        .classes()
+                        .iter()
+                        .map(|c| format!("/nice/{}", class_code(*c)))
+                        .collect(),
+                },
+            ));
+        }
+
+        if !query.status_filter.is_empty() {
+            let statuses = query
+                .status_filter
+                .iter()
+                .map(|status| TantivyQuery::Term {
+                    field: "status",
+                    text: format!("{:?}", status).to_lowercase(),
+                })
+                .collect();
+            clauses.push((Occur::Must, TantivyQuery::any_of(statuses)));
+        }
+
+        // Merge lower/upper bounds on the same attribute into one range
+        let mut ranges: Vec<(&'static str, Option<i64>, Option<i64>)> = Vec::new();
+        for bound in date_bounds(query)? {
+            let i = match ranges.iter().position(|r| r.0 == bound.attribute) {
+                Some(i) => i,
+                None => {
+                    ranges.push((bound.attribute, None, None));
+                    ranges.len() - 1
+                }
+            };
+            if bound.operator == ">=" {
+                ranges[i].1 = Some(bound.timestamp);
+            } else {
+                ranges[i].2 = Some(bound.timestamp);
+            }
+        }
+        clauses.extend(
+            ranges
+                .into_iter()
+                .map(|(field, lower, upper)| (Occur::Must, TantivyQuery::Range { field, lower, upper })),
+        );
+
+        Ok(TantivyQuery::Boolean(clauses))
+    }
+}
+
+/// Escape regex metacharacters in a literal.
+fn regex_escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| {
+            let escape = !c.is_alphanumeric() && c != ' ';
+            escape.then_some('\\').into_iter().chain(std::iter::once(c))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::{NiceClass, TrademarkStatus};
+
+    fn translate(query: &SearchQuery) -> TantivyQuery {
+        TantivyDialect::default().translate(query).unwrap()
+    }
+
+    #[test]
+    fn test_tantivy_basic() {
+        let query = translate(&SearchQuery::new("NIKE AIR"));
+        assert_eq!(
+            query.clauses(),
+            &[
+                (
+                    Occur::Must,
+                    TantivyQuery::Fuzzy {
+                        field: "mark_text",
+                        text: "nike".to_string(),
+                        distance: 1,
+                        transpositions: true,
+                    }
+                ),
+                (
+                    Occur::Must,
+                    TantivyQuery::Term {
+                        field: "mark_text",
+                        text: "air".to_string(),
+                    }
+                ),
+            ]
+        );
+
+        let mut exact = SearchQuery::new("NIKE");
+        exact.fuzzy = false;
+        assert!(matches!(
+            &translate(&exact).clauses()[0].1,
+            TantivyQuery::Term { text, .. } if text == "nike"
+        ));
+    }
+
+    #[test]
+    fn test_tantivy_escaping() {
+        let query = translate(&SearchQuery::new("O'REILLY"));
+        assert!(matches!(
+            &query.clauses()[0].1,
+            TantivyQuery::Fuzzy { text, distance: 2, .. } if text == "o'reilly"
+        ));
+
+        let query = translate(&SearchQuery::new("*C++"));
+        assert!(matches!(
+            &query.clauses()[0].1,
+            TantivyQuery::Regex { pattern, .. } if pattern == ".*c\\+\\+"
+        ));
+    }
+
+    #[test]
+    fn test_tantivy_class_filter() {
+        let mut query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25, 9]));
+        let clauses = translate(&query).clauses().to_vec();
+        assert_eq!(
+            clauses[1].1,
+            TantivyQuery::Facets {
+                field: "classes",
+                paths: vec!["/nice/009".to_string(), "/nice/025".to_string()],
+            }
+        );
+
+        query.classes = NiceClass::of_all(&[25]);
+        query.expand_class_clusters = true;
+        let clauses = translate(&query).clauses().to_vec();
+        assert!(matches!(
+            &clauses[1].1,
+            TantivyQuery::Facets { paths, .. } if paths.contains(&"/nice/018".to_string())
+        ));
+    }
+
+    #[test]
+    fn test_tantivy_wildcards() {
+        let query = translate(&SearchQuery::new("NIK* *BOOK*"));
+        assert_eq!(
+            query.clauses()[0].1,
+            TantivyQuery::Prefix {
+                field: "mark_text",
+                text: "nik".to_string(),
+            }
+        );
+        assert_eq!(
+            query.clauses()[1].1,
+            TantivyQuery::Regex {
+                field: "mark_text",
+                pattern: ".*book.*".to_string(),
+            }
+        );
+        assert!(TantivyDialect::default()
+            .translate(&SearchQuery::new("N*KE"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_tantivy_fielded_and_exclusions() {
+        let mut query = SearchQuery::new("")
+            .with_owner("Apple, Inc.")
+            .with_goods_services("smart phones");
+        query.exclude_serials = vec!["72123456".to_string()];
+        let clauses = translate(&query).clauses().to_vec();
+
+        assert_eq!(
+            clauses[0],
+            (
+                Occur::Must,
+                TantivyQuery::Phrase {
+                    field: "owner_name",
+                    terms: vec!["apple".to_string(), "inc".to_string()],
+                }
+            )
+        );
+        assert_eq!(clauses.len(), 4);
+        assert_eq!(clauses[3].0, Occur::MustNot);
+    }
+
+    #[test]
+    fn test_tantivy_status_and_dates() {
+        let mut query = SearchQuery::new("NIKE")
+            .with_statuses(vec![TrademarkStatus::Live, TrademarkStatus::Pending]);
+        query.filed_after = Some("2024-01-01".to_string());
+        query.filed_before = Some("2024-12-31".to_string());
+        let clauses = translate(&query).clauses().to_vec();
+
+        assert!(matches!(&clauses[1].1, TantivyQuery::Boolean(statuses) if statuses.len() == 2));
+        assert_eq!(
+            clauses[2].1,
+            TantivyQuery::Range {
+                field: "filing_date",
+                lower: Some(1_704_067_200),
+                upper: Some(1_735_689_599),
+            }
+        );
+    }
+
+    #[test]
+    fn test_tantivy_empty_query_error() {
+        assert!(matches!(
+            TantivyDialect::default().translate(&SearchQuery::new("   ")),
+            Err(QueryError::EmptyQuery)
+        ));
+    }
+}
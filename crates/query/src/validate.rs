@@ -0,0 +1,189 @@
+//! Up-front validation of search queries.
+//!
+//! Translation stops at the first problem it meets; `validate` instead
+//! checks the whole query and reports every problem with the field it
+//! came from, so an API can answer a bad request with one 400 response
+//! listing everything the caller has to fix.
+
+use crate::{excluded_serials, fielded_text, format_iso_date, parse_iso_date, parse_terms, QueryError};
+use ilegalflow_model::design::normalize_design_code;
+use ilegalflow_model::SearchQuery;
+use serde::Serialize;
+use std::fmt;
+
+/// Largest accepted result limit.
+pub const MAX_LIMIT: usize = 1000;
+
+/// Longest accepted mark text, in characters.
+pub const MAX_MARK_TEXT_LEN: usize = 200;
+
+/// A single validation failure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Offending `SearchQuery` field (e.g. `classes`)
+    pub field: &'static str,
+
+    /// Stable machine-readable code (e.g. `invalid_date`)
+    pub code: &'static str,
+
+    /// Human-readable message
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(field: &'static str, error: QueryError) -> Self {
+        Self {
+            field,
+            code: error.code(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// All validation failures for a query (never empty).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors(Vec<ValidationIssue>);
+
+impl ValidationErrors {
+    /// Get the individual failures.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self
+            .0
+            .iter()
+            .map(|issue| format!("{}: {}", issue.field, issue.message))
+            .collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Validate a query, collecting every failure.
+pub fn validate(query: &SearchQuery) -> Result<(), ValidationErrors> {
+    let mut issues = Vec::new();
+
+    // Mark text: length, wildcards, and something to search for
+    let length = query.mark_text.chars().count();
+    if length > MAX_MARK_TEXT_LEN {
+        issues.push(ValidationIssue::new(
+            "mark_text",
+            QueryError::MarkTextTooLong(length),
+        ));
+    }
+    match parse_terms(&query.mark_text) {
+        Ok(terms)
+            if terms.is_empty() && fielded_text(query).is_empty() && query.design_codes.is_empty() =>
+        {
+            issues.push(ValidationIssue::new("mark_text", QueryError::EmptyQuery));
+        }
+        Ok(_) => {}
+        Err(e) => issues.push(ValidationIssue::new("mark_text", e)),
+    }
+
+    for code in &query.design_codes {
+        if normalize_design_code(code).is_none() {
+            issues.push(ValidationIssue::new(
+                "design_codes",
+                QueryError::InvalidDesignCode(code.clone()),
+            ));
+        }
+    }
+
+    if let Err(e) = excluded_serials(query) {
+        issues.push(ValidationIssue::new("exclude_serials", e));
+    }
+
+    if query.limit == 0 || query.limit > MAX_LIMIT {
+        issues.push(ValidationIssue::new(
+            "limit",
+            QueryError::LimitOutOfRange(query.limit),
+        ));
+    }
+
+    // Dates: each must parse, and the filing range must be ordered
+    let mut parse = |field: &'static str, date: &Option<String>| {
+        parse_iso_date(date.as_deref()?)
+            .map_err(|e| issues.push(ValidationIssue::new(field, e)))
+            .ok()
+    };
+    let filed_after = parse("filed_after", &query.filed_after);
+    let filed_before = parse("filed_before", &query.filed_before);
+    parse("status_changed_after", &query.status_changed_after);
+
+    if let (Some(after), Some(before)) = (filed_after, filed_before) {
+        if after > before {
+            issues.push(ValidationIssue::new(
+                "filed_before",
+                QueryError::InvalidDateRange(format_iso_date(after), format_iso_date(before)),
+            ));
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationErrors(issues))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::NiceClass;
+
+    #[test]
+    fn test_valid_query() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[1, 25, 45]));
+        assert!(validate(&query).is_ok());
+
+        let owner_only = SearchQuery::new("").with_owner("Nike, Inc.");
+        assert!(validate(&owner_only).is_ok());
+    }
+
+    #[test]
+    fn test_collects_every_issue() {
+        let mut query = SearchQuery::new("N*KE")
+            .with_classes(vec![NiceClass::of(25)])
+            .with_limit(0);
+        query.filed_after = Some("2024-13-01".to_string());
+        query.status_changed_after = Some("yesterday".to_string());
+
+        let errors = validate(&query).unwrap_err();
+        let fields: Vec<_> = errors.issues().iter().map(|i| (i.field, i.code)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("mark_text", "invalid_wildcard"),
+                ("limit", "limit_out_of_range"),
+                ("filed_after", "invalid_date"),
+                ("status_changed_after", "invalid_date"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_long_and_reversed() {
+        let errors = validate(&SearchQuery::new("  ")).unwrap_err();
+        assert_eq!(errors.issues()[0].code, "empty_query");
+
+        let errors = validate(&SearchQuery::new("A".repeat(MAX_MARK_TEXT_LEN + 1))).unwrap_err();
+        assert_eq!(errors.issues()[0].code, "mark_text_too_long");
+
+        let mut query = SearchQuery::new("NIKE");
+        query.filed_after = Some("2024-06-01".to_string());
+        query.filed_before = Some("2024-01-01".to_string());
+        let errors = validate(&query).unwrap_err();
+        assert_eq!(errors.issues()[0].field, "filed_before");
+        assert_eq!(
+            serde_json::to_value(&errors).unwrap()[0]["code"],
+            "invalid_date_range"
+        );
+    }
+}
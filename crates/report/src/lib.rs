@@ -0,0 +1,597 @@
+//! PDF clearance reports.
+//!
+//! `ClearanceReport` turns a search query, its ranked hits and their
+//! explanations into the report attorneys send to clients: a branded
+//! cover with the overall risk, the methodology, a section per
+//! candidate, and an appendix of the raw records. A `RedactionPolicy`
+//! masks owners, numbers and goods text for reports shared outside
+//! privilege. An `ExplanationNarrator` adds prose to each candidate's
+//! analysis; it sees the candidates as redacted.
+
+mod pdf;
+
+use ilegalflow_explain::{
+    explain_hit_with, Comparison, ExplainOptions, Explanation, ExplanationNarrator, NoNarrator,
+    RedactionPolicy, RiskTier,
+};
+use ilegalflow_model::{CandidateHit, NiceClass, SearchQuery, SeverityTable};
+use pdf::{Color, Document, Font, Style, GRAY, MARGIN, PAGE_HEIGHT, PAGE_WIDTH, WHITE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Firm branding applied to a report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Branding {
+    /// Firm name on the cover band and in the footer
+    pub firm_name: String,
+    /// Report title
+    pub title: String,
+    /// Color of the cover band and headings (RGB, 0.0 - 1.0)
+    pub accent: [f32; 3],
+    /// Contact line at the foot of the cover
+    pub contact: Option<String>,
+    /// Closing paragraph of the methodology
+    pub disclaimer: String,
+}
+
+impl Default for Branding {
+    fn default() -> Self {
+        Self {
+            firm_name: "iLegalFlow".to_string(),
+            title: "Trademark Clearance Report".to_string(),
+            accent: [0.11, 0.24, 0.42],
+            contact: None,
+            disclaimer: "This report summarizes a search of the federal trademark register \
+                         and is not a legal opinion. Likelihood of confusion turns on facts \
+                         outside the register, and the findings here should be reviewed by \
+                         an attorney before a mark is adopted or filed."
+                .to_string(),
+        }
+    }
+}
+
+/// A clearance report for one search.
+pub struct ClearanceReport<'a> {
+    query: &'a SearchQuery,
+    hits: &'a [CandidateHit],
+    explanations: HashMap<&'a str, &'a [Explanation]>,
+    narratives: HashMap<&'a str, &'a str>,
+    narrator: Box<dyn ExplanationNarrator + 'a>,
+    branding: Branding,
+    date: Option<String>,
+    redaction: RedactionPolicy,
+    severities: SeverityTable,
+}
+
+const METHODOLOGY: &[&str] = &[
+    "Candidates were retrieved from the register by full-text, phonetic and \
+     character n-gram matching on the mark, and by the Nice classes and goods and \
+     services of the intended use.",
+    "Each candidate was then compared with the proposed mark for identical and \
+     near-identical spelling, sound, shared letter sequences and syllables, \
+     dominant terms, foreign equivalents, famous marks, design codes, and overlap \
+     or relatedness of classes and goods. Every finding below names the \
+     comparison it rests on and the evidence for it.",
+    "The risk score (0.0 - 1.0) combines those findings and is adjusted for the \
+     status of each record: dead marks count for less, and pending applications \
+     count as if registered. A candidate is HIGH RISK when any finding is severe \
+     enough on its own to support refusal, MODERATE RISK when a finding warrants \
+     review, and LOW RISK otherwise.",
+];
+
+impl<'a> ClearanceReport<'a> {
+    /// A report on `hits`, ranked as given, for `query`.
+    pub fn new(query: &'a SearchQuery, hits: &'a [CandidateHit]) -> Self {
+        Self {
+            query,
+            hits,
+            explanations: HashMap::new(),
+            narratives: HashMap::new(),
+            narrator: Box::new(NoNarrator),
+            branding: Branding::default(),
+            date: None,
+            redaction: RedactionPolicy::default(),
+            severities: SeverityTable::default(),
+        }
+    }
+
+    /// Use these explanations for the hit with a serial number; hits
+    /// without any are explained in English at full detail.
+    pub fn with_explanations(
+        mut self,
+        serial_number: &'a str,
+        explanations: &'a [Explanation],
+    ) -> Self {
+        self.explanations.insert(serial_number, explanations);
+        self
+    }
+
+    /// Use this prose for the hit with a serial number, e.g. the
+    /// pipeline's narrative, instead of asking the narrator. It is used as
+    /// given, without redaction.
+    pub fn with_narrative(mut self, serial_number: &'a str, narrative: &'a str) -> Self {
+        self.narratives.insert(serial_number, narrative);
+        self
+    }
+
+    /// Narrate hits without a narrative in `to_pdf_narrated`, instead of
+    /// `NoNarrator`.
+    pub fn with_narrator(mut self, narrator: impl ExplanationNarrator + 'a) -> Self {
+        self.narrator = Box::new(narrator);
+        self
+    }
+
+    pub fn with_branding(mut self, branding: Branding) -> Self {
+        self.branding = branding;
+        self
+    }
+
+    /// Date printed on the cover ("2026-10-15").
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Mask the candidates' owners, numbers and goods text, in their
+    /// records and explanations alike.
+    pub fn with_redaction(mut self, redaction: RedactionPolicy) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Tier hits, and explain those without explanations, under these
+    /// flag severities.
+    pub fn with_severities(mut self, severities: SeverityTable) -> Self {
+        self.severities = severities;
+        self
+    }
+
+    /// Overall risk: the highest tier among the hits.
+    pub fn tier(&self) -> RiskTier {
+        self.hits.iter().map(|hit| self.tier_of(hit)).max().unwrap_or(RiskTier::Low)
+    }
+
+    fn tier_of(&self, hit: &CandidateHit) -> RiskTier {
+        RiskTier::of_with(hit, &self.severities)
+    }
+
+    /// Render the report as a PDF document, with the narratives given.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        self.render(&HashMap::new())
+    }
+
+    /// Render the report, asking the narrator for the narratives not
+    /// given. A failed narration is logged and leaves the analysis as is.
+    pub async fn to_pdf_narrated(&self) -> Vec<u8> {
+        let mut narrated = HashMap::new();
+        for hit in self.hits {
+            let serial_number = hit.record.serial_number.as_str();
+            if self.narratives.contains_key(serial_number) {
+                continue;
+            }
+            let redacted = self.redaction.hit(hit);
+            let explanations = self.explanations_of(hit);
+            let comparison = Comparison {
+                query_text: &self.query.mark_text,
+                hit: &redacted,
+                explanations: &explanations,
+            };
+            match self.narrator.narrate(&comparison).await {
+                Ok(Some(narrative)) => {
+                    narrated.insert(serial_number, narrative);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(serial = %serial_number, "Narration failed: {}", e),
+            }
+        }
+        self.render(&narrated)
+    }
+
+    fn render(&self, narrated: &HashMap<&str, String>) -> Vec<u8> {
+        let mut doc = Document::new(format!("{} - Confidential", self.branding.firm_name));
+        self.cover(&mut doc);
+        doc.page_break();
+        self.methodology(&mut doc);
+        doc.page_break();
+        self.candidates(&mut doc, narrated);
+        if !self.hits.is_empty() {
+            doc.page_break();
+            self.appendix(&mut doc);
+        }
+        doc.finish(&format!("{}: {}", self.branding.title, self.query.mark_text))
+    }
+
+    fn cover(&self, doc: &mut Document) {
+        let accent = self.branding.accent;
+        doc.fill_rect(0.0, PAGE_HEIGHT - 120.0, PAGE_WIDTH, 120.0, accent);
+        let firm = Style::new(Font::Bold, 20.0).with_color(WHITE);
+        doc.text_at(MARGIN, PAGE_HEIGHT - 72.0, &self.branding.firm_name, firm);
+
+        doc.move_to(PAGE_HEIGHT - 180.0);
+        doc.paragraph(&self.branding.title, Style::new(Font::Bold, 24.0).with_color(accent));
+        doc.space(12.0);
+        doc.paragraph(&self.query.mark_text, Style::new(Font::Bold, 18.0));
+        doc.space(12.0);
+
+        let field = Style::new(Font::Regular, 11.0);
+        if !self.query.classes.is_empty() {
+            doc.paragraph(&format!("Classes: {}", join(&self.query.classes)), field);
+        }
+        if let Some(goods) = &self.query.goods_services_query {
+            doc.paragraph(&format!("Goods/services: {}", goods), field);
+        }
+        if let Some(date) = &self.date {
+            doc.paragraph(&format!("Prepared: {}", date), field);
+        }
+        doc.paragraph(&format!("Candidates reported: {}", self.hits.len()), field);
+
+        doc.space(24.0);
+        doc.line("Overall risk", Style::new(Font::Bold, 11.0));
+        doc.space(4.0);
+        let tier = self.tier();
+        let top = doc.cursor();
+        doc.badge(MARGIN, top, tier.label(), tier_color(tier));
+        doc.space(20.0);
+        let counts: Vec<String> = [RiskTier::High, RiskTier::Moderate, RiskTier::Low]
+            .into_iter()
+            .map(|t| {
+                let n = self.hits.iter().filter(|hit| self.tier_of(hit) == t).count();
+                format!("{}: {}", t.label(), n)
+            })
+            .collect();
+        doc.paragraph(&counts.join("    "), field.with_color(GRAY));
+
+        if let Some(contact) = &self.branding.contact {
+            doc.move_to(MARGIN + 60.0);
+            doc.paragraph(contact, Style::new(Font::Regular, 10.0).with_color(GRAY));
+        }
+    }
+
+    fn methodology(&self, doc: &mut Document) {
+        self.heading(doc, "Methodology");
+        let body = Style::new(Font::Regular, 10.0);
+        for paragraph in METHODOLOGY {
+            doc.paragraph(paragraph, body);
+            doc.space(6.0);
+        }
+        doc.paragraph(&self.branding.disclaimer, body.with_color(GRAY));
+        doc.space(18.0);
+
+        self.heading(doc, "Summary of candidates");
+        if self.hits.is_empty() {
+            doc.paragraph("No conflicting marks found.", body);
+            return;
+        }
+        let header = Style::new(Font::Bold, 9.0);
+        row(doc, &["#", "Mark", "Serial", "Status", "Score", "Risk"], header);
+        doc.rule();
+        let cell = Style::new(Font::Regular, 9.0);
+        for (i, hit) in self.hits.iter().enumerate() {
+            let record = self.redaction.record(&hit.record);
+            let values = [
+                (i + 1).to_string(),
+                truncate(&record.mark_text, cell, 200.0),
+                record.serial_number.clone(),
+                format!("{:?}", record.status),
+                format!("{:.2}", hit.risk_score),
+                self.tier_of(hit).label().to_string(),
+            ];
+            let values: Vec<&str> = values.iter().map(String::as_str).collect();
+            row(doc, &values, cell);
+        }
+    }
+
+    /// The hit's explanations as supplied, or generated, redacted either
+    /// way.
+    fn explanations_of(&self, hit: &CandidateHit) -> Vec<Explanation> {
+        match self.explanations.get(hit.record.serial_number.as_str()) {
+            Some(explanations) => explanations
+                .iter()
+                .map(|e| self.redaction.explanation(e.clone(), hit))
+                .collect(),
+            None => {
+                let options = ExplainOptions::default()
+                    .with_redaction(self.redaction)
+                    .with_severities(self.severities);
+                explain_hit_with(hit, &self.query.mark_text, &options)
+            }
+        }
+    }
+
+    fn candidates(&self, doc: &mut Document, narrated: &HashMap<&str, String>) {
+        self.heading(doc, "Candidates");
+        if self.hits.is_empty() {
+            doc.paragraph("No conflicting marks found.", Style::new(Font::Regular, 10.0));
+        }
+        let field = Style::new(Font::Regular, 9.5);
+        for (i, hit) in self.hits.iter().enumerate() {
+            let record = self.redaction.record(&hit.record);
+            doc.keep(90.0);
+            let title = Style::new(Font::Bold, 13.0).with_color(self.branding.accent);
+            let top = doc.cursor();
+            let tier = self.tier_of(hit);
+            let label_width = Font::Bold.measure(tier.label(), 8.0) + 8.0;
+            doc.badge(PAGE_WIDTH - MARGIN - label_width, top, tier.label(), tier_color(tier));
+            doc.line(&truncate(&format!("{}. {}", i + 1, record.mark_text), title, 400.0), title);
+            doc.space(4.0);
+
+            let mut fields = vec![format!("Serial number: {}", record.serial_number)];
+            if let Some(registration) = &record.registration_number {
+                fields.push(format!("Registration number: {}", registration));
+            }
+            if !record.owner.is_empty() {
+                fields.push(format!("Owner: {}", record.owner));
+            }
+            fields.push(match &record.status_date {
+                Some(date) => format!("Status: {:?} (since {})", record.status, date),
+                None => format!("Status: {:?}", record.status),
+            });
+            if !record.classes.is_empty() {
+                fields.push(format!("Classes: {}", join(&record.classes)));
+            }
+            if let Some(date) = &record.filing_date {
+                fields.push(format!("Filed: {}", date));
+            }
+            if let Some(date) = &record.registration_date {
+                fields.push(format!("Registered: {}", date));
+            }
+            fields.push(match hit.risk_probability {
+                Some(p) => format!(
+                    "Risk score: {:.2} ({:.0}% likelihood of conflict)",
+                    hit.risk_score,
+                    p * 100.0
+                ),
+                None => format!("Risk score: {:.2}", hit.risk_score),
+            });
+            for text in &fields {
+                doc.paragraph(text, field);
+            }
+            if !record.goods_services.is_empty() {
+                doc.paragraph(
+                    &format!("Goods/services: {}", record.goods_services),
+                    field.with_color(GRAY),
+                );
+            }
+
+            doc.space(6.0);
+            doc.line("Analysis", Style::new(Font::Bold, 10.0));
+            let serial_number = hit.record.serial_number.as_str();
+            let narrative = self.narratives.get(serial_number).copied();
+            if let Some(narrative) = narrative.or(narrated.get(serial_number).map(String::as_str)) {
+                doc.paragraph(narrative, field);
+                doc.space(4.0);
+            }
+            let explanations = self.explanations_of(hit);
+            for explanation in &explanations {
+                doc.keep(30.0);
+                doc.paragraph(
+                    &format!("\u{2022} {}", explanation.summary),
+                    Style::new(Font::Bold, 9.5).with_indent(6.0),
+                );
+                if !explanation.detail.is_empty() {
+                    doc.paragraph(&explanation.detail, field.with_indent(16.0));
+                }
+                for item in &explanation.evidence {
+                    let text = match &item.context {
+                        Some(context) => format!("{}: {} ({})", item.kind, item.value, context),
+                        None => format!("{}: {}", item.kind, item.value),
+                    };
+                    let evidence = Style::new(Font::Regular, 8.5).with_color(GRAY);
+                    doc.paragraph(&text, evidence.with_indent(16.0));
+                }
+                if !explanation.references.is_empty() {
+                    let citations: Vec<_> =
+                        explanation.references.iter().map(|r| r.citation.as_str()).collect();
+                    let see = Style::new(Font::Regular, 8.5).with_color(GRAY);
+                    doc.paragraph(&format!("See {}", citations.join("; ")), see.with_indent(16.0));
+                }
+            }
+            doc.space(6.0);
+            doc.rule();
+            doc.space(6.0);
+        }
+    }
+
+    fn appendix(&self, doc: &mut Document) {
+        self.heading(doc, "Appendix: Records");
+        let raw = Style::new(Font::Mono, 7.5);
+        for (i, hit) in self.hits.iter().enumerate() {
+            let record = self.redaction.record(&hit.record);
+            doc.keep(40.0);
+            doc.line(
+                &format!("A{}. {} ({})", i + 1, record.mark_text, record.serial_number),
+                Style::new(Font::Bold, 10.0),
+            );
+            let json = serde_json::to_string_pretty(&record).unwrap_or_default();
+            doc.preformatted(&json, raw);
+            doc.space(10.0);
+        }
+    }
+
+    fn heading(&self, doc: &mut Document, text: &str) {
+        doc.keep(40.0);
+        doc.line(text, Style::new(Font::Bold, 16.0).with_color(self.branding.accent));
+        doc.space(8.0);
+    }
+}
+
+/// Left edges of the summary table's columns, from the margin.
+const COLUMNS: [f32; 6] = [0.0, 24.0, 230.0, 310.0, 370.0, 420.0];
+
+fn row(doc: &mut Document, values: &[&str], style: Style) {
+    doc.keep(style.size * 1.3);
+    let top = doc.cursor();
+    for (x, value) in COLUMNS.iter().zip(values) {
+        doc.text_at(MARGIN + x, top - style.size, value, style);
+    }
+    doc.space(style.size * 1.3);
+}
+
+fn tier_color(tier: RiskTier) -> Color {
+    match tier {
+        RiskTier::High => [0.75, 0.11, 0.11],
+        RiskTier::Moderate => [0.85, 0.5, 0.0],
+        RiskTier::Low => [0.2, 0.5, 0.25],
+    }
+}
+
+fn join(classes: &[NiceClass]) -> String {
+    classes.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Shorten text to `width` points, ending it with "...".
+fn truncate(text: &str, style: Style, width: f32) -> String {
+    if style.font.measure(text, style.size) <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        if style.font.measure(&format!("{}{}...", out, c), style.size) > width {
+            break;
+        }
+        out.push(c);
+    }
+    out + "..."
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_explain::{explain_flag_in, Locale, NarrationFuture};
+    use ilegalflow_model::{Owner, RiskFlag, TrademarkRecord};
+
+    fn hit(serial: &str, mark: &str, flags: Vec<RiskFlag>) -> CandidateHit {
+        let mut record = TrademarkRecord::new(serial, mark);
+        record.owner = Owner::new("Nike, Inc.");
+        record.classes = NiceClass::of_all(&[25]);
+        CandidateHit {
+            record,
+            retrieval_score: 1.0,
+            risk_score: 0.9,
+            risk_probability: None,
+            flags,
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    fn contains(pdf: &[u8], text: &str) -> bool {
+        pdf.windows(text.len()).any(|w| w == text.as_bytes())
+    }
+
+    #[test]
+    fn test_report_sections() {
+        let query = SearchQuery::new("NIKEE").with_classes(NiceClass::of_all(&[25]));
+        let hits = vec![
+            hit("72000001", "NIKE", vec![RiskFlag::ExactMatch]),
+            hit("72000002", "NYKEE", vec![RiskFlag::FuzzyMatch { distance: 1 }]),
+        ];
+        let spanish = vec![explain_flag_in(&RiskFlag::ExactMatch, "NIKEE", "NIKE", Locale::Es)];
+        let branding = Branding {
+            firm_name: "Smith & Jones LLP".to_string(),
+            contact: Some("clearance@example.com".to_string()),
+            ..Default::default()
+        };
+        let report = ClearanceReport::new(&query, &hits)
+            .with_explanations("72000001", &spanish)
+            .with_branding(branding)
+            .with_date("2026-10-15");
+        assert_eq!(report.tier(), RiskTier::High);
+
+        let pdf = report.to_pdf();
+        assert!(pdf.starts_with(b"%PDF-"));
+        // Cover, methodology, candidates, appendix
+        assert!(contains(&pdf, "/Count 4"));
+        for text in [
+            "(Smith & Jones LLP) Tj",
+            "(Prepared: 2026-10-15) Tj",
+            "(HIGH RISK) Tj",
+            "(Methodology) Tj",
+            "(1. NIKE) Tj",
+            "(Owner: Nike, Inc.) Tj",
+            "(Appendix: Records) Tj",
+            "\"serial_number\": \"72000002\"",
+            "(Smith & Jones LLP - Confidential) Tj",
+            "(clearance@example.com) Tj",
+        ] {
+            assert!(contains(&pdf, text), "missing {}", text);
+        }
+        // Supplied explanations are used as given; others are generated
+        assert!(contains(&pdf, "Coincidencia exacta"));
+        assert!(!contains(&pdf, "Exact match found"));
+        assert!(contains(&pdf, "Spelled similarly"));
+    }
+
+    #[test]
+    fn test_redacted_report() {
+        let query = SearchQuery::new("NIKEE");
+        let mut hits = vec![hit("72000001", "NIKE", vec![RiskFlag::ExactMatch])];
+        hits[0].record.goods_services = "Footwear, apparel, namely shirts".to_string();
+        let pdf = ClearanceReport::new(&query, &hits)
+            .with_redaction(RedactionPolicy::shareable())
+            .to_pdf();
+        for text in ["(Owner: N***, I**.) Tj", "(*****001) Tj", "Footwear, apparel, namely ..."] {
+            assert!(contains(&pdf, text), "missing {}", text);
+        }
+        for text in ["Nike, Inc.", "72000001", "shirts"] {
+            assert!(!contains(&pdf, text), "leaked {}", text);
+        }
+    }
+
+    /// Writes the candidate's owner, standing in for an LLM.
+    struct OwnerNarrator;
+
+    impl ExplanationNarrator for OwnerNarrator {
+        fn narrate<'a>(&'a self, comparison: &'a Comparison<'a>) -> NarrationFuture<'a> {
+            let prose = format!("Owned by {}", comparison.hit.record.owner);
+            Box::pin(async move { Ok(Some(prose)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_narrated_report() {
+        let query = SearchQuery::new("NIKEE");
+        let hits = vec![
+            hit("72000001", "NIKE", vec![RiskFlag::ExactMatch]),
+            hit("72000002", "NYKEE", vec![RiskFlag::FuzzyMatch { distance: 1 }]),
+        ];
+        let report = ClearanceReport::new(&query, &hits)
+            .with_narrative("72000002", "Given prose.")
+            .with_narrator(OwnerNarrator)
+            .with_redaction(RedactionPolicy::shareable());
+        let pdf = report.to_pdf_narrated().await;
+        // The narrator sees the redacted candidate
+        assert!(contains(&pdf, "(Owned by N***, I**.) Tj"));
+        assert!(contains(&pdf, "(Given prose.) Tj"));
+        assert!(!contains(&pdf, "Nike, Inc."));
+
+        let pdf = report.to_pdf();
+        assert!(!contains(&pdf, "Owned by"));
+        assert!(contains(&pdf, "(Given prose.) Tj"));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let query = SearchQuery::new("ZQXVORA");
+        let report = ClearanceReport::new(&query, &[]);
+        assert_eq!(report.tier(), RiskTier::Low);
+
+        // Tiers follow the report's severities
+        let hits = vec![hit("72000001", "NIKE", vec![RiskFlag::ExactMatch])];
+        let severities = SeverityTable {
+            exact_match: 0.6,
+            ..SeverityTable::DEFAULT
+        };
+        let lowered = ClearanceReport::new(&query, &hits).with_severities(severities);
+        assert_eq!(lowered.tier(), RiskTier::Moderate);
+        let pdf = report.to_pdf();
+        assert!(contains(&pdf, "/Count 3"));
+        assert!(contains(&pdf, "(No conflicting marks found.) Tj"));
+        assert!(!contains(&pdf, "Appendix"));
+    }
+}
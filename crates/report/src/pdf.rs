@@ -0,0 +1,462 @@
+//! A minimal PDF writer.
+//!
+//! Lays out wrapped text, rules and filled boxes top to bottom on US
+//! Letter pages using the standard Helvetica and Courier fonts, which
+//! every viewer has, so no fonts are embedded. Text is WinAnsi-encoded;
+//! characters outside it print as '?'.
+
+use std::fmt::Write as _;
+
+pub const PAGE_WIDTH: f32 = 612.0;
+pub const PAGE_HEIGHT: f32 = 792.0;
+pub const MARGIN: f32 = 54.0;
+
+/// Lowest baseline body text may use; the footer sits below it.
+const BOTTOM: f32 = MARGIN + 18.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+    Mono,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+            Self::Mono => "F3",
+        }
+    }
+
+    /// Advance width of a character in thousandths of the font size.
+    fn width(&self, c: char) -> f32 {
+        let table = match self {
+            Self::Mono => return 600.0,
+            Self::Regular => &HELVETICA,
+            Self::Bold => &HELVETICA_BOLD,
+        };
+        match c {
+            ' '..='~' => table[c as usize - 32] as f32,
+            _ => 556.0,
+        }
+    }
+
+    /// Width of `text` in points at `size`.
+    pub fn measure(&self, text: &str, size: f32) -> f32 {
+        text.chars().map(|c| self.width(c)).sum::<f32>() * size / 1000.0
+    }
+}
+
+/// An RGB color, components 0.0 - 1.0.
+pub type Color = [f32; 3];
+
+pub const BLACK: Color = [0.0, 0.0, 0.0];
+pub const WHITE: Color = [1.0, 1.0, 1.0];
+pub const GRAY: Color = [0.4, 0.4, 0.4];
+
+/// How a block of text is set.
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    pub font: Font,
+    pub size: f32,
+    pub color: Color,
+    /// Left indent from the margin, in points
+    pub indent: f32,
+}
+
+impl Style {
+    pub const fn new(font: Font, size: f32) -> Self {
+        Self {
+            font,
+            size,
+            color: BLACK,
+            indent: 0.0,
+        }
+    }
+
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub const fn with_indent(mut self, indent: f32) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    fn leading(&self) -> f32 {
+        self.size * 1.3
+    }
+}
+
+/// A document being laid out, one page at a time.
+pub struct Document {
+    pages: Vec<Vec<u8>>,
+    /// Baseline position of the next line on the current page
+    y: f32,
+    footer: String,
+}
+
+impl Document {
+    /// Start a document; `footer` is printed at the bottom left of every
+    /// page, beside the page number.
+    pub fn new(footer: impl Into<String>) -> Self {
+        Self {
+            pages: vec![Vec::new()],
+            y: PAGE_HEIGHT - MARGIN,
+            footer: footer.into(),
+        }
+    }
+
+    /// Start a new page.
+    pub fn page_break(&mut self) {
+        self.pages.push(Vec::new());
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Break the page unless `height` points fit above the footer.
+    pub fn keep(&mut self, height: f32) {
+        if self.y - height < BOTTOM {
+            self.page_break();
+        }
+    }
+
+    /// Vertical position of the cursor: the top of the next line.
+    pub fn cursor(&self) -> f32 {
+        self.y
+    }
+
+    /// Move down by `height` points.
+    pub fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Move the cursor to `y` points from the bottom of the page.
+    pub fn move_to(&mut self, y: f32) {
+        self.y = y;
+    }
+
+    /// Set text, wrapped to the margins, breaking pages as needed.
+    pub fn paragraph(&mut self, text: &str, style: Style) {
+        let width = PAGE_WIDTH - 2.0 * MARGIN - style.indent;
+        for line in wrap(text, style.font, style.size, width) {
+            self.line(&line, style);
+        }
+    }
+
+    /// Set preformatted text line by line, keeping its indentation and
+    /// breaking lines that overflow the margins.
+    pub fn preformatted(&mut self, text: &str, style: Style) {
+        let width = PAGE_WIDTH - 2.0 * MARGIN - style.indent;
+        for line in text.lines() {
+            let mut rest = line;
+            loop {
+                let split = fit(rest, style.font, style.size, width);
+                self.line(&rest[..split], style);
+                rest = &rest[split..];
+                if rest.is_empty() {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Set a single line at the cursor without wrapping.
+    pub fn line(&mut self, text: &str, style: Style) {
+        self.keep(style.leading());
+        self.y -= style.size;
+        self.text_at(MARGIN + style.indent, self.y, text, style);
+        self.y -= style.leading() - style.size;
+    }
+
+    /// Draw text with its baseline at (`x`, `y`), leaving the cursor alone.
+    pub fn text_at(&mut self, x: f32, y: f32, text: &str, style: Style) {
+        let page = self.pages.last_mut().expect("a document always has a page");
+        show_text(page, x, y, text, style);
+    }
+
+    /// Fill a rectangle whose lower-left corner is (`x`, `y`).
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let [r, g, b] = color;
+        let page = self.pages.last_mut().expect("a document always has a page");
+        let _ = writeln!(
+            Ops(page),
+            "{} {} {} rg {:.2} {:.2} {:.2} {:.2} re f",
+            r,
+            g,
+            b,
+            x,
+            y,
+            width,
+            height
+        );
+    }
+
+    /// Draw a thin horizontal rule across the text column at the cursor.
+    pub fn rule(&mut self) {
+        self.keep(8.0);
+        self.y -= 4.0;
+        let page = self.pages.last_mut().expect("a document always has a page");
+        let _ = writeln!(
+            Ops(page),
+            "0.75 G 0.5 w {:.2} {:.2} m {:.2} {:.2} l S",
+            MARGIN,
+            self.y,
+            PAGE_WIDTH - MARGIN,
+            self.y
+        );
+        self.y -= 4.0;
+    }
+
+    /// Draw a colored label box with white text; the box's top left is at
+    /// (`x`, `top`). Returns the box width.
+    pub fn badge(&mut self, x: f32, top: f32, text: &str, color: Color) -> f32 {
+        let style = Style::new(Font::Bold, 8.0).with_color(WHITE);
+        let width = style.font.measure(text, style.size) + 8.0;
+        self.fill_rect(x, top - 12.0, width, 12.0, color);
+        self.text_at(x + 4.0, top - 9.0, text, style);
+        width
+    }
+
+    /// Add page footers and serialize the document.
+    pub fn finish(mut self, title: &str) -> Vec<u8> {
+        let count = self.pages.len();
+        let style = Style::new(Font::Regular, 8.0).with_color(GRAY);
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            let number = format!("Page {} of {}", i + 1, count);
+            let right = PAGE_WIDTH - MARGIN - style.font.measure(&number, style.size);
+            show_text(page, MARGIN, MARGIN - 18.0, &self.footer, style);
+            show_text(page, right, MARGIN - 18.0, &number, style);
+        }
+
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let kids: Vec<String> =
+            (0..count).map(|i| format!("{} 0 R", FIRST_PAGE + 2 * i)).collect();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), count).into_bytes(),
+        );
+        for name in ["Helvetica", "Helvetica-Bold", "Courier"] {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    name
+                )
+                .into_bytes(),
+            );
+        }
+        let mut info = b"<< /Title (".to_vec();
+        info.extend(encode(title));
+        info.extend_from_slice(b") /Producer (ilegalflow-report) >>");
+        objects.push(info);
+        for (i, content) in self.pages.iter().enumerate() {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> \
+                     /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    FIRST_PAGE + 2 * i + 1
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = out.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(table, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            table,
+            "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            INFO,
+            xref
+        );
+        out.extend(table.into_bytes());
+        out
+    }
+}
+
+/// Object number of the document info dictionary.
+const INFO: usize = 6;
+/// Object number of the first page; each page is followed by its content.
+const FIRST_PAGE: usize = 7;
+
+fn show_text(page: &mut Vec<u8>, x: f32, y: f32, text: &str, style: Style) {
+    let [r, g, b] = style.color;
+    let _ = write!(
+        Ops(page),
+        "BT /{} {} Tf {} {} {} rg {:.2} {:.2} Td (",
+        style.font.resource(),
+        style.size,
+        r,
+        g,
+        b,
+        x,
+        y
+    );
+    page.extend(encode(text));
+    page.extend_from_slice(b") Tj ET\n");
+}
+
+/// `fmt::Write` over a page's content bytes.
+struct Ops<'a>(&'a mut Vec<u8>);
+
+impl std::fmt::Write for Ops<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Break text into lines no wider than `width` points, on whitespace
+/// where possible.
+pub fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if font.measure(&candidate, size) <= width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let mut rest = word;
+            while font.measure(rest, size) > width {
+                let split = fit(rest, font, size, width);
+                lines.push(rest[..split].to_string());
+                rest = &rest[split..];
+            }
+            line = rest.to_string();
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Byte length of the longest prefix of `text` that fits in `width`
+/// points; at least one character.
+fn fit(text: &str, font: Font, size: f32, width: f32) -> usize {
+    let mut used = 0.0;
+    for (i, c) in text.char_indices() {
+        used += font.width(c) * size / 1000.0;
+        if used > width && i > 0 {
+            return i;
+        }
+    }
+    text.len()
+}
+
+/// Encode text as an escaped WinAnsi string body.
+fn encode(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => out.extend_from_slice(&[b'\\', c as u8]),
+            ' '..='~' => out.push(c as u8),
+            '\u{a0}'..='\u{ff}' => out.push(c as u32 as u8),
+            '•' => out.push(0x95),
+            '–' => out.push(0x96),
+            '—' => out.push(0x97),
+            '‘' => out.push(0x91),
+            '’' => out.push(0x92),
+            '“' => out.push(0x93),
+            '”' => out.push(0x94),
+            '…' => out.push(0x85),
+            '€' => out.push(0x80),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+/// Helvetica advance widths for ' ' through '~' (Adobe AFM).
+#[rustfmt::skip]
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Helvetica-Bold advance widths for ' ' through '~' (Adobe AFM).
+#[rustfmt::skip]
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        let lines = wrap("the quick brown fox jumps", Font::Regular, 10.0, 60.0);
+        assert_eq!(lines, vec!["the quick", "brown fox", "jumps"]);
+        assert!(lines.iter().all(|l| Font::Regular.measure(l, 10.0) <= 60.0));
+
+        // Words wider than the line are broken
+        let lines = wrap("ABCDEFGHIJKLMNOP", Font::Mono, 10.0, 30.0);
+        assert_eq!(lines, vec!["ABCDE", "FGHIJ", "KLMNO", "P"]);
+        assert_eq!(wrap("", Font::Regular, 10.0, 60.0), vec![""]);
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let mut doc = Document::new("Footer (draft)");
+        doc.paragraph("Café (São Paulo) – ±", Style::new(Font::Regular, 10.0));
+        doc.page_break();
+        doc.line("Second", Style::new(Font::Bold, 12.0));
+        let pdf = doc.finish("Report");
+        let contains = |needle: &[u8]| pdf.windows(needle.len()).any(|w| w == needle);
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(contains(b"/Count 2"));
+        assert!(contains(b"(Footer \\(draft\\)) Tj"));
+        assert!(contains(b"(Page 2 of 2) Tj"));
+        assert!(contains(b"(Caf\xe9 \\(S\xe3o Paulo\\) \x96 \xb1) Tj"));
+
+        // Every xref entry points at its object
+        let tail = std::str::from_utf8(&pdf[pdf.len() - 32..]).unwrap();
+        let start: usize = tail.lines().rev().nth(1).unwrap().parse().unwrap();
+        let xref = std::str::from_utf8(&pdf[start..]).unwrap();
+        let entries: Vec<_> = xref.lines().skip(3).take_while(|l| l.ends_with(" n ")).collect();
+        assert_eq!(entries.len(), 10);
+        for (i, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Benchmarks for re-ranking large candidate sets.
+//!
+//! Run with `cargo bench -p ilegalflow-rerank --features parallel`.
+//! `one_thread` scores candidates in a single-thread pool, the same work
+//! as a build without the feature; `all_threads` uses the global pool.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ilegalflow_model::{NiceClass, SearchQuery, TrademarkRecord};
+use ilegalflow_rerank::{context::QueryContext, rerank_with_context, RerankConfig};
+use std::hint::black_box;
+
+const SYLLABLES: &[&str] = &[
+    "QUOR", "LAN", "DA", "ZEF", "IRA", "NOK", "TEL", "VIA", "MOR", "SEN",
+];
+
+const GOODS: &[&str] = &[
+    "Athletic footwear and apparel",
+    "Computer software for inventory management",
+    "Restaurant and cafe services",
+    "Sporting goods, namely balls and rackets",
+];
+
+/// Deterministic synthetic candidates: two- and three-syllable marks
+/// across a few classes.
+fn candidates(count: usize) -> Vec<(TrademarkRecord, f32)> {
+    (0..count)
+        .map(|i| {
+            let n = SYLLABLES.len();
+            let mut mark = format!("{}{}", SYLLABLES[i % n], SYLLABLES[i / n % n]);
+            if i % 3 == 0 {
+                mark.push_str(SYLLABLES[i / (n * n) % n]);
+            }
+            let mut record = TrademarkRecord::new(format!("{:08}", i), &mark);
+            record.classes = vec![NiceClass::of([9, 25, 28, 43][i % 4])];
+            record.goods_services = GOODS[i % GOODS.len()].to_string();
+            (record, 1.0 / (1 + i % 50) as f32)
+        })
+        .collect()
+}
+
+fn bench_rerank(c: &mut Criterion) {
+    let mut query = SearchQuery::new("QUORLANDA").with_classes(vec![NiceClass::of(25)]);
+    query.goods_services_query = Some("Running shoes and athletic apparel".to_string());
+    let config = RerankConfig::default();
+    let context = QueryContext::new(&query, &config);
+    let one_thread = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("rerank");
+    group.sample_size(10);
+    for count in [1_000, 10_000] {
+        let batch = candidates(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("one_thread", count), &batch, |b, batch| {
+            b.iter(|| {
+                one_thread.install(|| {
+                    black_box(rerank_with_context(
+                        &context,
+                        &query,
+                        batch.clone(),
+                        &config,
+                    ))
+                })
+            })
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("all_threads", count),
+            &batch,
+            |b, batch| {
+                b.iter(|| {
+                    black_box(rerank_with_context(
+                        &context,
+                        &query,
+                        batch.clone(),
+                        &config,
+                    ))
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rerank);
+criterion_main!(benches);
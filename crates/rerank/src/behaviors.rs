@@ -0,0 +1,354 @@
+//! Named scoring behavior toggles.
+//!
+//! New rules ship behind a named behavior so they can be rolled out
+//! gradually. A behavior is off until its default-on date (or always off
+//! by default if it has none), and the scoring profile can override it
+//! globally or per tenant. Once a behavior passes its sunset date the
+//! toggle is due for removal, and overriding it raises a warning when
+//! the profile is loaded.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// A scoring behavior that can be toggled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Behavior {
+    /// Toggle name used in profiles
+    pub name: &'static str,
+    /// What the behavior changes
+    pub description: &'static str,
+    /// Date (YYYY-MM-DD) from which the behavior is on by default; `None`
+    /// ships it dark
+    pub default_on: Option<&'static str>,
+    /// Date (YYYY-MM-DD) after which the toggle is removed and the
+    /// behavior is permanent
+    pub sunset: Option<&'static str>,
+}
+
+/// Name of the number-word behavior.
+pub const NUMBER_WORDS: &str = "number_words";
+/// Name of the foreign-equivalent behavior.
+pub const FOREIGN_EQUIVALENTS: &str = "foreign_equivalents";
+/// Name of the shared-prefix behavior.
+pub const PREFIX_MATCH: &str = "prefix_match";
+/// Name of the syllable-comparison behavior.
+pub const SYLLABLES: &str = "syllables";
+/// Name of the compound-splitting behavior.
+pub const COMPOUNDS: &str = "compounds";
+/// Name of the phoneme-distance behavior.
+pub const NEAR_PHONETIC: &str = "near_phonetic";
+/// Name of the mark-strength behavior.
+pub const MARK_STRENGTH: &str = "mark_strength";
+/// Name of the house-mark stripping behavior.
+pub const HOUSE_MARKS: &str = "house_marks";
+/// Name of the status-weighting behavior.
+pub const STATUS_WEIGHTS: &str = "status_weights";
+/// Name of the famous-mark behavior.
+pub const FAMOUS_MARKS: &str = "famous_marks";
+/// Name of the dilution behavior.
+pub const DILUTION: &str = "dilution";
+/// Name of the related-classes behavior.
+pub const RELATED_CLASSES: &str = "related_classes";
+/// Name of the duplicate-filing behavior.
+pub const DEDUP_FILINGS: &str = "dedup_filings";
+/// Name of the mark-family grouping behavior.
+pub const MARK_FAMILIES: &str = "mark_families";
+/// Name of the dead-mark age behavior.
+pub const DEAD_MARK_AGE: &str = "dead_mark_age";
+/// Name of the design-mark behavior.
+pub const DESIGN_MARKS: &str = "design_marks";
+/// Name of the stemming behavior.
+pub const STEMMING: &str = "stemming";
+
+/// Every toggleable behavior.
+pub const BEHAVIORS: &[Behavior] = &[
+    Behavior {
+        name: NUMBER_WORDS,
+        description: "Match marks across numeral and number-word swaps (4EVER vs FOREVER)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: FOREIGN_EQUIVALENTS,
+        description: "Flag direct translations of the query (LUPO vs WOLF)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: PREFIX_MATCH,
+        description: "Boost marks sharing the query's beginning (MICROSOFT vs MICROSOAP)",
+        default_on: None,
+        sunset: None,
+    },
+    Behavior {
+        name: SYLLABLES,
+        description: "Compare marks syllable by syllable (VITALITY vs VITALITEA)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: COMPOUNDS,
+        description: "Split run-together words before comparing (SUNPOWER vs SUN POWER)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: NEAR_PHONETIC,
+        description: "Grade marks a phoneme or two apart in sound (NIKE vs NIKEY)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: MARK_STRENGTH,
+        description: "Scale risk by the strength of the senior mark (weaker for descriptive marks)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: HOUSE_MARKS,
+        description: "Match marks once house marks and designators are stripped (ACME NIKE SERIES)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: STATUS_WEIGHTS,
+        description: "Scale risk by the record's status (dead marks far below live ones)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: FAMOUS_MARKS,
+        description: "Flag marks sharing a famous mark with the query, across classes (NIKE GOLF)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: DILUTION,
+        description: "Score blurring and tarnishment of famous marks the query contains",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: RELATED_CLASSES,
+        description: "Score different but related classes by grade (clothing 25 and retail 35)",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: DEDUP_FILINGS,
+        description: "Collapse candidates sharing a serial or registration number",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: MARK_FAMILIES,
+        description: "Group filings of the same mark by the same owner into one hit",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: DEAD_MARK_AGE,
+        description: "Scale dead-mark risk by how long ago the mark died",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: DESIGN_MARKS,
+        description: "Discount or set aside text matches on design marks",
+        default_on: Some("2026-10-15"),
+        sunset: None,
+    },
+    Behavior {
+        name: STEMMING,
+        description: "Stem marks and goods/services terms before comparing (WIDGETS vs WIDGET)",
+        default_on: None,
+        sunset: None,
+    },
+];
+
+/// Look up a behavior by name.
+pub fn behavior(name: &str) -> Option<&'static Behavior> {
+    BEHAVIORS.iter().find(|b| b.name == name)
+}
+
+/// Behavior overrides in a scoring profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorToggles {
+    /// Overrides for every tenant
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, bool>,
+    /// Overrides per tenant, taking precedence over `overrides`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tenants: BTreeMap<String, BTreeMap<String, bool>>,
+}
+
+impl BehaviorToggles {
+    /// Override a behavior for every tenant.
+    pub fn with_override(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.overrides.insert(name.into(), enabled);
+        self
+    }
+
+    /// Override a behavior for one tenant.
+    pub fn with_tenant_override(
+        mut self,
+        tenant: impl Into<String>,
+        name: impl Into<String>,
+        enabled: bool,
+    ) -> Self {
+        self.tenants
+            .entry(tenant.into())
+            .or_default()
+            .insert(name.into(), enabled);
+        self
+    }
+
+    /// The behaviors enabled for a tenant on a date (YYYY-MM-DD); with no
+    /// date, every dated default is in effect.
+    pub fn resolve(&self, tenant: Option<&str>, today: Option<&str>) -> EnabledBehaviors {
+        let tenant = tenant.and_then(|t| self.tenants.get(t));
+        let enabled = BEHAVIORS
+            .iter()
+            .filter(|b| {
+                tenant
+                    .and_then(|overrides| overrides.get(b.name))
+                    .or_else(|| self.overrides.get(b.name))
+                    .copied()
+                    .unwrap_or_else(|| {
+                        b.default_on
+                            .is_some_and(|date| today.is_none_or(|today| date <= today))
+                    })
+            })
+            .map(|b| b.name)
+            .collect();
+        EnabledBehaviors { enabled }
+    }
+
+    /// Problems to report when the profile is loaded: unknown names and
+    /// overrides of sunset behaviors.
+    pub fn warnings(&self, today: &str) -> Vec<BehaviorWarning> {
+        let global = self.overrides.keys().map(|name| (None, name));
+        let tenants = self
+            .tenants
+            .iter()
+            .flat_map(|(tenant, overrides)| overrides.keys().map(move |name| (Some(tenant), name)));
+
+        global
+            .chain(tenants)
+            .filter_map(|(tenant, name)| match behavior(name) {
+                None => Some(BehaviorWarning::Unknown {
+                    name: name.clone(),
+                    tenant: tenant.cloned(),
+                }),
+                Some(b) => b.sunset.filter(|sunset| *sunset <= today).map(|sunset| {
+                    BehaviorWarning::PastSunset {
+                        name: name.clone(),
+                        tenant: tenant.cloned(),
+                        sunset: sunset.to_string(),
+                    }
+                }),
+            })
+            .collect()
+    }
+}
+
+/// A problem with a behavior override.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BehaviorWarning {
+    /// The override names no known behavior
+    Unknown {
+        name: String,
+        tenant: Option<String>,
+    },
+    /// The behavior's toggle is past its sunset date
+    PastSunset {
+        name: String,
+        tenant: Option<String>,
+        sunset: String,
+    },
+}
+
+impl fmt::Display for BehaviorWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = |tenant: &Option<String>| match tenant {
+            Some(tenant) => format!(" for tenant '{}'", tenant),
+            None => String::new(),
+        };
+        match self {
+            Self::Unknown { name, tenant } => {
+                write!(f, "Unknown behavior '{}' overridden{}", name, scope(tenant))
+            }
+            Self::PastSunset {
+                name,
+                tenant,
+                sunset,
+            } => write!(
+                f,
+                "Behavior '{}' overridden{} was sunset on {}; the override will stop working",
+                name,
+                scope(tenant),
+                sunset
+            ),
+        }
+    }
+}
+
+/// The behaviors in effect for one search.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnabledBehaviors {
+    enabled: BTreeSet<&'static str>,
+}
+
+impl EnabledBehaviors {
+    /// Whether a behavior is on.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.enabled.contains(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let toggles = BehaviorToggles::default();
+        assert!(!toggles.resolve(None, Some("2026-01-01")).is_enabled(NUMBER_WORDS));
+        assert!(toggles.resolve(None, Some("2026-10-15")).is_enabled(NUMBER_WORDS));
+        assert!(toggles.resolve(None, None).is_enabled(NUMBER_WORDS));
+
+        let toggles = toggles
+            .with_override(NUMBER_WORDS, false)
+            .with_tenant_override("acme", NUMBER_WORDS, true);
+        assert!(!toggles.resolve(None, Some("2026-10-15")).is_enabled(NUMBER_WORDS));
+        assert!(!toggles
+            .resolve(Some("other"), Some("2026-10-15"))
+            .is_enabled(NUMBER_WORDS));
+        assert!(toggles
+            .resolve(Some("acme"), Some("2026-10-15"))
+            .is_enabled(NUMBER_WORDS));
+        assert!(toggles
+            .resolve(Some("acme"), Some("2026-10-15"))
+            .is_enabled(FOREIGN_EQUIVALENTS));
+    }
+
+    #[test]
+    fn test_warnings() {
+        let toggles = BehaviorToggles::default()
+            .with_override("crowded_field", true)
+            .with_tenant_override("acme", NUMBER_WORDS, false);
+        let warnings = toggles.warnings("2026-10-15");
+        assert_eq!(
+            warnings,
+            vec![BehaviorWarning::Unknown {
+                name: "crowded_field".to_string(),
+                tenant: None,
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Unknown behavior 'crowded_field' overridden"
+        );
+    }
+}
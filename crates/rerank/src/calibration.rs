@@ -0,0 +1,202 @@
+//! Calibrated probability of conflict.
+//!
+//! Risk scores add up capped flag weights, so 0.6 for one query need not
+//! mean what 0.6 means for another. A calibration maps the final score
+//! to the probability that a reviewer would call the hit a conflict,
+//! fitted on reviewed eval results: either a logistic mapping or an
+//! isotonic table (monotone, piecewise linear). The UI can then say
+//! "72% likelihood of conflict".
+
+use ilegalflow_features::read_tsv;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors from an invalid calibration.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CalibrationError {
+    #[error("calibration parameters must be finite")]
+    NotFinite,
+
+    #[error("isotonic table needs at least one point")]
+    Empty,
+
+    #[error("isotonic points must have strictly increasing scores")]
+    Unordered,
+
+    #[error("isotonic probabilities must be non-decreasing and within 0.0 - 1.0")]
+    NotMonotone,
+}
+
+/// A mapping from risk score to probability of conflict.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Calibration {
+    /// Platt scaling: `1 / (1 + e^-(slope * score + intercept))`
+    Logistic { slope: f32, intercept: f32 },
+
+    /// Straight lines through `(score, probability)` points, flat
+    /// outside them
+    Isotonic { points: Vec<(f32, f32)> },
+}
+
+impl Calibration {
+    /// Fit an isotonic table to reviewed outcomes, `(score, conflict)`
+    /// pairs, by pooling adjacent violators.
+    pub fn fit_isotonic(outcomes: &[(f32, bool)]) -> Result<Self, CalibrationError> {
+        let mut outcomes: Vec<(f32, bool)> = outcomes.to_vec();
+        if outcomes.iter().any(|(score, _)| !score.is_finite()) {
+            return Err(CalibrationError::NotFinite);
+        }
+        outcomes.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Blocks of (score sum, conflicts, count), one per distinct score,
+        // merged while a block's conflict rate is not above the one before
+        // it
+        let mut blocks: Vec<(f32, f32, f32)> = Vec::new();
+        for group in outcomes.chunk_by(|a, b| a.0 == b.0) {
+            let count = group.len() as f32;
+            let conflicts = group.iter().filter(|(_, conflict)| *conflict).count() as f32;
+            blocks.push((group[0].0 * count, conflicts, count));
+            while blocks.len() > 1 {
+                let (b, a) = (blocks[blocks.len() - 1], blocks[blocks.len() - 2]);
+                if a.1 / a.2 < b.1 / b.2 {
+                    break;
+                }
+                blocks.pop();
+                *blocks.last_mut().unwrap() = (a.0 + b.0, a.1 + b.1, a.2 + b.2);
+            }
+        }
+
+        let points: Vec<(f32, f32)> = blocks
+            .into_iter()
+            .map(|(scores, conflicts, count)| (scores / count, conflicts / count))
+            .collect();
+        let calibration = Self::Isotonic { points };
+        calibration.validate()?;
+        Ok(calibration)
+    }
+
+    /// Fit an isotonic table to a tab-separated file of `score` and
+    /// `conflict` (1/0 or true/false) lines; blank lines and `#`
+    /// comments are skipped.
+    pub fn fit_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut outcomes = Vec::new();
+        read_tsv(path, |fields| {
+            let [score, conflict] = fields else {
+                return Err("expected score, conflict".to_string());
+            };
+            let score: f32 = score.parse().map_err(|_| format!("bad score '{}'", score))?;
+            let conflict = match conflict.to_lowercase().as_str() {
+                "1" | "true" => true,
+                "0" | "false" => false,
+                other => return Err(format!("bad conflict '{}'", other)),
+            };
+            outcomes.push((score, conflict));
+            Ok(())
+        })?;
+        Self::fit_isotonic(&outcomes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    /// Probability of conflict for a risk score.
+    pub fn probability(&self, score: f32) -> f32 {
+        let p = match self {
+            Self::Logistic { slope, intercept } => {
+                1.0 / (1.0 + (-(slope * score + intercept)).exp())
+            }
+            Self::Isotonic { points } => {
+                match points.iter().position(|(x, _)| *x >= score) {
+                    Some(0) => points[0].1,
+                    Some(i) => {
+                        let ((x0, y0), (x1, y1)) = (points[i - 1], points[i]);
+                        y0 + (score - x0) / (x1 - x0) * (y1 - y0)
+                    }
+                    None => points.last().map_or(0.0, |(_, y)| *y),
+                }
+            }
+        };
+        p.clamp(0.0, 1.0)
+    }
+
+    /// Check the calibration's parameters.
+    pub fn validate(&self) -> Result<(), CalibrationError> {
+        match self {
+            Self::Logistic { slope, intercept } => {
+                if !slope.is_finite() || !intercept.is_finite() {
+                    return Err(CalibrationError::NotFinite);
+                }
+            }
+            Self::Isotonic { points } => {
+                if points.is_empty() {
+                    return Err(CalibrationError::Empty);
+                }
+                if !points.iter().all(|(x, y)| x.is_finite() && y.is_finite()) {
+                    return Err(CalibrationError::NotFinite);
+                }
+                if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+                    return Err(CalibrationError::Unordered);
+                }
+                if points.windows(2).any(|w| w[0].1 > w[1].1)
+                    || !points.iter().all(|(_, y)| (0.0..=1.0).contains(y))
+                {
+                    return Err(CalibrationError::NotMonotone);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probability() {
+        let logistic = Calibration::Logistic {
+            slope: 8.0,
+            intercept: -4.0,
+        };
+        assert_eq!(logistic.probability(0.5), 0.5);
+        assert!(logistic.probability(0.9) > 0.9);
+
+        let isotonic = Calibration::Isotonic {
+            points: vec![(0.2, 0.1), (0.6, 0.5), (1.0, 0.9)],
+        };
+        assert_eq!(isotonic.probability(0.0), 0.1);
+        assert!((isotonic.probability(0.4) - 0.3).abs() < 1e-6);
+        assert_eq!(isotonic.probability(1.0), 0.9);
+        assert_eq!(isotonic.validate(), Ok(()));
+
+        let decreasing = Calibration::Isotonic {
+            points: vec![(0.2, 0.5), (0.6, 0.1)],
+        };
+        assert_eq!(decreasing.validate(), Err(CalibrationError::NotMonotone));
+    }
+
+    #[test]
+    fn test_fit_isotonic() {
+        let outcomes = [
+            (0.1, false),
+            (0.2, true),
+            (0.3, false),
+            (0.5, false),
+            (0.8, true),
+            (0.9, true),
+        ];
+        let Calibration::Isotonic { points } = Calibration::fit_isotonic(&outcomes).unwrap() else {
+            unreachable!()
+        };
+        // 0.2 and 0.3 pool, then pool with 0.5, to a third; 0.8 and 0.9
+        // share a rate
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0], (0.1, 0.0));
+        assert!((points[1].0 - 1.0 / 3.0).abs() < 1e-6);
+        assert!((points[1].1 - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(points[2], (0.85, 1.0));
+
+        assert_eq!(Calibration::fit_isotonic(&[]), Err(CalibrationError::Empty));
+    }
+}
@@ -0,0 +1,129 @@
+//! Combining flag contributions into a risk score.
+//!
+//! Each detector contributes its weight, scaled by how strongly its flag
+//! fired. Summing them (capped at 1.0) lets many weak flags outscore a
+//! single strong one; the other strategies keep one strong signal
+//! decisive and make weak ones add up with diminishing returns.
+
+use serde::{Deserialize, Serialize};
+
+/// How flag contributions combine into a risk score (0.0 - 1.0).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Combination {
+    /// Sum, capped at 1.0 (the original scoring)
+    #[default]
+    Additive,
+
+    /// The strongest contribution alone
+    Max,
+
+    /// `1 - (1 - c1)(1 - c2)...`: each flag is independent evidence, so
+    /// weak flags add up without ever reaching a certain conflict
+    NoisyOr,
+
+    /// A logistic curve over the sum, rescaled so no flags score 0.0:
+    /// sums well below `midpoint` stay low, sums above it rise quickly
+    /// at a rate set by `steepness`
+    WeightedLogistic { steepness: f32, midpoint: f32 },
+}
+
+impl Combination {
+    /// Combine one candidate's contributions.
+    pub fn combine(&self, contributions: &[f32]) -> f32 {
+        let contributions = contributions.iter().map(|c| c.clamp(0.0, 1.0));
+        let score = match *self {
+            Self::Additive => contributions.sum(),
+            Self::Max => contributions.fold(0.0, f32::max),
+            Self::NoisyOr => 1.0 - contributions.map(|c| 1.0 - c).product::<f32>(),
+            Self::WeightedLogistic {
+                steepness,
+                midpoint,
+            } => {
+                let sigmoid = |x: f32| 1.0 / (1.0 + (-steepness * (x - midpoint)).exp());
+                let floor = sigmoid(0.0);
+                (sigmoid(contributions.sum()) - floor) / (1.0 - floor)
+            }
+        };
+        score.clamp(0.0, 1.0)
+    }
+
+    /// Whether the parameters are usable: a positive steepness and a
+    /// finite midpoint.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::WeightedLogistic {
+                steepness,
+                midpoint,
+            } => steepness.is_finite() && steepness > 0.0 && midpoint.is_finite(),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rerank, RerankConfig, RerankConfigError};
+    use ilegalflow_model::{NiceClass, SearchQuery, TrademarkRecord};
+
+    #[test]
+    fn test_combine() {
+        let weak = [0.2, 0.2, 0.2, 0.2];
+        let strong = [0.7];
+        assert_eq!(Combination::Additive.combine(&weak), 0.8);
+        assert_eq!(Combination::Additive.combine(&[0.7, 0.7]), 1.0);
+        assert_eq!(Combination::Max.combine(&weak), 0.2);
+        assert!((Combination::NoisyOr.combine(&weak) - 0.5904).abs() < 1e-5);
+        assert!((Combination::NoisyOr.combine(&strong) - 0.7).abs() < 1e-6);
+
+        // Four weak flags no longer outrank one strong flag
+        for combination in [Combination::Max, Combination::NoisyOr] {
+            assert!(combination.combine(&weak) < combination.combine(&strong));
+        }
+
+        let logistic = Combination::WeightedLogistic {
+            steepness: 8.0,
+            midpoint: 0.5,
+        };
+        assert_eq!(logistic.combine(&[]), 0.0);
+        assert!(logistic.combine(&[0.2]) < 0.1);
+        assert!(logistic.combine(&[0.9]) > 0.9);
+        assert!(logistic.is_valid());
+        let flat = Combination::WeightedLogistic {
+            steepness: 0.0,
+            midpoint: 0.5,
+        };
+        assert!(!flat.is_valid());
+    }
+
+    #[test]
+    fn test_rerank_combination() {
+        let query = SearchQuery::new("ZEPHYRA").with_classes(NiceClass::of_all(&[25]));
+        let candidates = || {
+            let mut record = TrademarkRecord::new("001", "ZEPHYRO");
+            record.classes = NiceClass::of_all(&[25]);
+            vec![(record, 1.0)]
+        };
+        let score = |combination| {
+            let config = RerankConfig {
+                combination,
+                ..Default::default()
+            };
+            rerank(&query, candidates(), &config)[0].risk_score
+        };
+        let additive = score(Combination::Additive);
+        assert!(score(Combination::Max) < score(Combination::NoisyOr));
+        assert!(score(Combination::NoisyOr) <= additive);
+        assert_eq!(additive, rerank(&query, candidates(), &RerankConfig::default())[0].risk_score);
+
+        let config = RerankConfig {
+            combination: Combination::WeightedLogistic {
+                steepness: 8.0,
+                midpoint: f32::NAN,
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.validate(), Err(RerankConfigError::Combination));
+    }
+}
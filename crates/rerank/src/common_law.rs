@@ -0,0 +1,89 @@
+//! Evidence of unregistered use behind a candidate mark.
+//!
+//! Trademark rights come from use, so a candidate's owner may have rights
+//! beyond its registration, and a dead registration may still be in use.
+//! Sources such as business-name registries, domain listings or a
+//! commercial common-law search report sightings of a mark in use; a
+//! candidate that already resembles the query and has such evidence is
+//! flagged as a common-law risk.
+
+use ilegalflow_features::{normalize_text, read_tsv};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// A sighting of a mark in use outside the register.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseEvidence {
+    /// Where the use was found (e.g. "Oregon business registry")
+    pub source: String,
+    /// What was found (e.g. a business or domain name)
+    pub evidence: String,
+    /// Confidence that the sighting is use of the mark (0.0 - 1.0)
+    pub confidence: f32,
+}
+
+/// Reports use of marks outside the register.
+pub trait CommonLawSource: Send + Sync + fmt::Debug {
+    /// Source name, for logs.
+    fn name(&self) -> &str;
+
+    /// Evidence of use of a normalized mark.
+    fn find_use(&self, mark: &str) -> Vec<UseEvidence>;
+}
+
+/// Use evidence listed ahead of time, keyed by normalized mark.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UseListing {
+    entries: HashMap<String, Vec<UseEvidence>>,
+}
+
+impl UseListing {
+    /// Record a sighting of a mark.
+    pub fn insert(&mut self, mark: &str, evidence: UseEvidence) {
+        self.entries
+            .entry(normalize_text(mark).into_owned())
+            .or_default()
+            .push(evidence);
+    }
+
+    /// Add sightings from a tab-separated file of `mark`, `source`,
+    /// `evidence` and optional `confidence` (default 1.0) lines; blank
+    /// lines and `#` comments are skipped.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        read_tsv(path, |fields| {
+            let (mark, source, evidence, confidence) = match *fields {
+                [mark, source, evidence] => (mark, source, evidence, 1.0),
+                [mark, source, evidence, confidence] => {
+                    let confidence: f32 = confidence
+                        .parse()
+                        .ok()
+                        .filter(|c| (0.0..=1.0).contains(c))
+                        .ok_or("confidence must be between 0 and 1")?;
+                    (mark, source, evidence, confidence)
+                }
+                _ => return Err("expected mark, source, evidence[, confidence]"),
+            };
+            self.insert(
+                mark,
+                UseEvidence {
+                    source: source.to_string(),
+                    evidence: evidence.to_string(),
+                    confidence,
+                },
+            );
+            Ok(())
+        })
+    }
+}
+
+impl CommonLawSource for UseListing {
+    fn name(&self) -> &str {
+        "listing"
+    }
+
+    fn find_use(&self, mark: &str) -> Vec<UseEvidence> {
+        self.entries.get(mark).cloned().unwrap_or_default()
+    }
+}
@@ -0,0 +1,224 @@
+//! Query-side values derived once per search.
+//!
+//! Normalization, phonetic codes and phonemes, n-grams, famous marks, the
+//! dominant element of the query mark and the embedding of its goods are
+//! the same for every candidate. The pipeline builds a `QueryContext`
+//! once and hands it to each stage, so no stage recomputes them and every
+//! stage sees the same values.
+
+use crate::behaviors::{EnabledBehaviors, STEMMING};
+use crate::dominant::DominantProfile;
+use crate::RerankConfig;
+use ilegalflow_features::{
+    normalize_confusable_chars, normalize_text, number_word_variants, phonemes, stem_text,
+    strip_house_marks, transliterate, ConfusableText, NgramProfile, NumberVariant,
+    PhoneticEncoding, StrippedMark,
+};
+use ilegalflow_famous::FamousMark;
+use ilegalflow_model::{Date, SearchQuery};
+
+/// Derived values of a query mark.
+#[derive(Debug, Clone)]
+pub struct QueryContext {
+    /// The query mark as entered
+    pub mark_text: String,
+    /// Normalized mark text
+    pub normalized: String,
+    /// Normalized mark text with each word stemmed, if the stemming
+    /// behavior is on
+    pub stemmed: Option<String>,
+    /// Mark text with confusable characters undone
+    pub confusable: ConfusableText,
+    /// Numeral/number-word variants, the mark itself first
+    pub number_variants: Vec<NumberVariant>,
+    /// Codes under the configured phonetic encoders
+    pub phonetic: PhoneticEncoding,
+    /// Phoneme sequence of the normalized mark
+    pub phonemes: Vec<&'static str>,
+    /// Character n-grams of the configured size
+    pub ngrams: NgramProfile,
+    /// Dominant element and canonical spelling
+    pub dominant: DominantProfile,
+    /// Scoring behaviors in effect for this search
+    pub behaviors: EnabledBehaviors,
+    /// House marks stripped for this search
+    pub house_marks: Vec<String>,
+    /// Mark text with house marks and designators stripped
+    pub house_stripped: StrippedMark,
+    /// Normalized mark text with designators stripped
+    pub without_designators: String,
+    /// Famous marks the query contains
+    pub famous: Vec<FamousMark>,
+    /// Date of the search, if known
+    pub today: Option<Date>,
+    /// Embedding of the query's goods/services, if an embedder is
+    /// configured and can embed them
+    #[cfg(feature = "embeddings")]
+    pub goods_embedding: Option<Vec<f32>>,
+}
+
+impl QueryContext {
+    /// Derive the query's values for a re-ranker configuration; use the
+    /// context only with that configuration.
+    pub fn new(query: &SearchQuery, config: &RerankConfig) -> Self {
+        Self::for_tenant(query, config, None)
+    }
+
+    /// Like `new`, with the behavior overrides and house marks of a
+    /// tenant.
+    pub fn for_tenant(query: &SearchQuery, config: &RerankConfig, tenant: Option<&str>) -> Self {
+        Self::for_tenant_at(query, config, tenant, config.today)
+    }
+
+    /// Like `for_tenant`, scoring as of `today` instead of the
+    /// configured date.
+    pub fn for_tenant_at(
+        query: &SearchQuery,
+        config: &RerankConfig,
+        tenant: Option<&str>,
+        today: Option<Date>,
+    ) -> Self {
+        // Non-Latin marks are compared by their Latin spelling
+        let normalized = normalize_text(&transliterate(&query.mark_text)).into_owned();
+        let house_marks = config.house_marks.resolve(tenant);
+        let behaviors = config
+            .behaviors
+            .resolve(tenant, today.map(|date| date.to_string()).as_deref());
+        Self {
+            mark_text: query.mark_text.clone(),
+            confusable: normalize_confusable_chars(&query.mark_text, &config.confusables),
+            number_variants: number_word_variants(&normalized),
+            phonetic: config.phonetic.encode(&normalized),
+            phonemes: phonemes(&normalized),
+            ngrams: NgramProfile::new(&normalized, config.ngram_size),
+            stemmed: behaviors.is_enabled(STEMMING).then(|| stem_text(&normalized)),
+            dominant: DominantProfile::for_classes(
+                &query.mark_text,
+                &query.classes,
+                config.term_frequencies.clone(),
+                config.dominant_terms,
+            ),
+            behaviors,
+            house_stripped: strip_house_marks(&normalized, &house_marks),
+            without_designators: strip_house_marks(&normalized, &[]).text,
+            famous: config.famous_marks.find_in(&normalized).into_iter().cloned().collect(),
+            #[cfg(feature = "embeddings")]
+            goods_embedding: config
+                .embedder
+                .as_ref()
+                .zip(query.goods_services_query.as_deref())
+                .and_then(|(embedder, text)| embedder.embed(text)),
+            house_marks,
+            normalized,
+            today,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rerank, rerank_with_context};
+    use ilegalflow_features::ngram_similarity;
+    use ilegalflow_model::{CandidateHit, NiceClass, RiskFlag, TrademarkRecord};
+
+    #[test]
+    fn test_context_matches_direct_computation() {
+        let config = RerankConfig::default();
+        let query = SearchQuery::new("Nike Air");
+        let context = QueryContext::new(&query, &config);
+
+        assert_eq!(context.normalized, "NIKE AIR");
+        assert_eq!(
+            config.phonetic.match_encoded(&context.phonetic, "NYKE AIR"),
+            config.phonetic.phonetic_match("NIKE AIR", "NYKE AIR")
+        );
+        let direct = ngram_similarity("NIKE AIR", "NIKE AIRS", config.ngram_size);
+        let profiled = context
+            .ngrams
+            .similarity(&NgramProfile::new("NIKE AIRS", config.ngram_size));
+        assert!((direct.cosine - profiled.cosine).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rerank_with_context_matches_rerank() {
+        let config = RerankConfig::default();
+        let query = SearchQuery::new("N1KE").with_classes(NiceClass::of_all(&[25]));
+        let candidates = vec![
+            (TrademarkRecord::new("001", "NIKE"), 1.0),
+            (TrademarkRecord::new("002", "NYKE"), 0.8),
+            (TrademarkRecord::new("003", "REEBOK"), 0.5),
+        ];
+
+        let context = QueryContext::new(&query, &config);
+        let shared = rerank_with_context(&context, &query, candidates.clone(), &config);
+        let direct = rerank(&query, candidates, &config);
+        let summary = |hits: &[CandidateHit]| -> Vec<(String, f32, Vec<RiskFlag>)> {
+            hits.iter()
+                .map(|hit| {
+                    (
+                        hit.record.serial_number.clone(),
+                        hit.risk_score,
+                        hit.flags.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(summary(&shared.hits), summary(&direct));
+    }
+
+    #[test]
+    fn test_tenant_house_marks() {
+        let mut config = RerankConfig::default();
+        config.house_marks = config.house_marks.with_tenant_mark("acme", "ACME");
+        let query = SearchQuery::new("NIKE");
+        let candidates = vec![(TrademarkRecord::new("001", "ACME NIKE SERIES"), 1.0)];
+        let house = |hits: &[CandidateHit]| {
+            hits[0].flags.iter().find_map(|f| match f {
+                RiskFlag::HouseMarkMatch { house_marks, .. } => Some(house_marks.clone()),
+                _ => None,
+            })
+        };
+
+        let default = QueryContext::new(&query, &config);
+        let outcome = rerank_with_context(&default, &query, candidates.clone(), &config);
+        assert_eq!(house(&outcome.hits), None);
+
+        let acme = QueryContext::for_tenant(&query, &config, Some("acme"));
+        let outcome = rerank_with_context(&acme, &query, candidates, &config);
+        assert_eq!(house(&outcome.hits), Some(vec!["ACME".to_string()]));
+
+        // A designator alone is normalized away: an exact match, not a
+        // house-mark match
+        let candidates = vec![(TrademarkRecord::new("002", "NIKE INC"), 1.0)];
+        let outcome = rerank_with_context(&acme, &query, candidates, &config);
+        assert_eq!(house(&outcome.hits), None);
+        assert_eq!(outcome.hits[0].flags[0], RiskFlag::ExactMatch);
+    }
+
+    #[test]
+    fn test_tenant_behaviors() {
+        let mut config = RerankConfig::default();
+        config.behaviors = config.behaviors.with_tenant_override(
+            "acme",
+            crate::behaviors::FOREIGN_EQUIVALENTS,
+            false,
+        );
+        let query = SearchQuery::new("WOLF");
+        let candidates = vec![(TrademarkRecord::new("001", "LUPO"), 1.0)];
+        let foreign = |hits: &[CandidateHit]| {
+            hits[0]
+                .flags
+                .iter()
+                .any(|f| matches!(f, RiskFlag::ForeignEquivalent { .. }))
+        };
+
+        let default = QueryContext::new(&query, &config);
+        let outcome = rerank_with_context(&default, &query, candidates.clone(), &config);
+        assert!(foreign(&outcome.hits));
+
+        let acme = QueryContext::for_tenant(&query, &config, Some("acme"));
+        let outcome = rerank_with_context(&acme, &query, candidates, &config);
+        assert!(!foreign(&outcome.hits));
+    }
+}
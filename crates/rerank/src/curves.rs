@@ -0,0 +1,261 @@
+//! Severity curves.
+//!
+//! A curve maps a flag's raw measurement (an edit distance, a similarity
+//! score) to a factor in 0.0 - 1.0 that scales the flag's weight. This
+//! lets a scoring profile say, for example, that edit distance 1 is
+//! nearly as bad as an exact match while distance 3 barely matters.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors from an invalid curve definition.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CurveError {
+    #[error("{0}: parameters must be finite")]
+    NotFinite(&'static str),
+
+    #[error("{0}: linear curve needs two distinct x values")]
+    DegenerateLinear(&'static str),
+
+    #[error("{0}: step curve needs at least one point")]
+    EmptySteps(&'static str),
+
+    #[error("{0}: step points must have strictly increasing x")]
+    UnorderedSteps(&'static str),
+
+    #[error("{0}: sigmoid steepness must be non-zero")]
+    FlatSigmoid(&'static str),
+
+    #[error("{0}: curve values must be within 0.0 - 1.0")]
+    ValueOutOfRange(&'static str),
+}
+
+/// A mapping from a measurement to a severity factor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SeverityCurve {
+    /// Straight line through two points, clamped outside them
+    Linear { x0: f32, y0: f32, x1: f32, y1: f32 },
+
+    /// Piecewise constant: the value of the last point at or below the
+    /// input (the first point's value below the first point)
+    Step { points: Vec<(f32, f32)> },
+
+    /// Logistic curve, 0.5 at the midpoint; negative steepness makes it
+    /// decreasing
+    Sigmoid { midpoint: f32, steepness: f32 },
+}
+
+impl SeverityCurve {
+    /// The identity on 0.0 - 1.0, for measurements that are already
+    /// scores.
+    pub fn identity() -> Self {
+        Self::Linear {
+            x0: 0.0,
+            y0: 0.0,
+            x1: 1.0,
+            y1: 1.0,
+        }
+    }
+
+    /// Evaluate the curve, clamped to 0.0 - 1.0.
+    pub fn eval(&self, x: f32) -> f32 {
+        let y = match self {
+            Self::Linear { x0, y0, x1, y1 } => {
+                let t = ((x - x0) / (x1 - x0)).clamp(0.0, 1.0);
+                y0 + t * (y1 - y0)
+            }
+            Self::Step { points } => points
+                .iter()
+                .take_while(|(px, _)| *px <= x)
+                .last()
+                .or(points.first())
+                .map_or(0.0, |(_, py)| *py),
+            Self::Sigmoid {
+                midpoint,
+                steepness,
+            } => 1.0 / (1.0 + (-steepness * (x - midpoint)).exp()),
+        };
+        y.clamp(0.0, 1.0)
+    }
+
+    /// Check the curve's parameters; `name` identifies it in errors.
+    pub fn validate(&self, name: &'static str) -> Result<(), CurveError> {
+        let in_range = |y: f32| (0.0..=1.0).contains(&y);
+        match self {
+            Self::Linear { x0, y0, x1, y1 } => {
+                if ![x0, y0, x1, y1].iter().all(|v| v.is_finite()) {
+                    return Err(CurveError::NotFinite(name));
+                }
+                if x0 == x1 {
+                    return Err(CurveError::DegenerateLinear(name));
+                }
+                if !in_range(*y0) || !in_range(*y1) {
+                    return Err(CurveError::ValueOutOfRange(name));
+                }
+            }
+            Self::Step { points } => {
+                if points.is_empty() {
+                    return Err(CurveError::EmptySteps(name));
+                }
+                if !points.iter().all(|(x, y)| x.is_finite() && y.is_finite()) {
+                    return Err(CurveError::NotFinite(name));
+                }
+                if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+                    return Err(CurveError::UnorderedSteps(name));
+                }
+                if !points.iter().all(|(_, y)| in_range(*y)) {
+                    return Err(CurveError::ValueOutOfRange(name));
+                }
+            }
+            Self::Sigmoid {
+                midpoint,
+                steepness,
+            } => {
+                if !midpoint.is_finite() || !steepness.is_finite() {
+                    return Err(CurveError::NotFinite(name));
+                }
+                if *steepness == 0.0 {
+                    return Err(CurveError::FlatSigmoid(name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Curves for the flags scored from a measurement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeverityCurves {
+    /// Fuzzy match, by edit distance
+    pub fuzzy: SeverityCurve,
+    /// String similarity, by score
+    pub similarity: SeverityCurve,
+}
+
+impl SeverityCurves {
+    /// The linear curves used before curves were configurable: fuzzy
+    /// risk falls from 1.0 at distance 0 to 0.0 just past the maximum
+    /// distance, and similarity scores are used as is.
+    pub fn linear(max_edit_distance: usize) -> Self {
+        Self {
+            fuzzy: SeverityCurve::Linear {
+                x0: 0.0,
+                y0: 1.0,
+                x1: max_edit_distance as f32 + 1.0,
+                y1: 0.0,
+            },
+            similarity: SeverityCurve::identity(),
+        }
+    }
+
+    /// Check every curve.
+    pub fn validate(&self) -> Result<(), CurveError> {
+        self.fuzzy.validate("fuzzy")?;
+        self.similarity.validate("similarity")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_golden(curve: &SeverityCurve, golden: &[(f32, f32)]) {
+        for &(x, expected) in golden {
+            let y = curve.eval(x);
+            assert!(
+                (y - expected).abs() < 1e-4,
+                "{:?} at {}: expected {}, got {}",
+                curve,
+                x,
+                expected,
+                y
+            );
+        }
+    }
+
+    #[test]
+    fn test_linear_golden() {
+        assert_golden(
+            &SeverityCurves::linear(3).fuzzy,
+            &[(-1.0, 1.0), (0.0, 1.0), (1.0, 0.75), (2.0, 0.5), (3.0, 0.25), (5.0, 0.0)],
+        );
+        assert_golden(
+            &SeverityCurve::identity(),
+            &[(0.0, 0.0), (0.9, 0.9), (1.0, 1.0), (1.5, 1.0)],
+        );
+    }
+
+    #[test]
+    fn test_step_golden() {
+        let curve = SeverityCurve::Step {
+            points: vec![(1.0, 0.95), (2.0, 0.5), (3.0, 0.05)],
+        };
+        assert_golden(
+            &curve,
+            &[(0.0, 0.95), (1.0, 0.95), (1.5, 0.95), (2.0, 0.5), (3.0, 0.05), (9.0, 0.05)],
+        );
+    }
+
+    #[test]
+    fn test_sigmoid_golden() {
+        let decreasing = SeverityCurve::Sigmoid {
+            midpoint: 2.0,
+            steepness: -3.0,
+        };
+        assert_golden(
+            &decreasing,
+            &[(0.0, 0.997527), (1.0, 0.952574), (2.0, 0.5), (3.0, 0.047426)],
+        );
+
+        let increasing = SeverityCurve::Sigmoid {
+            midpoint: 0.9,
+            steepness: 20.0,
+        };
+        assert_golden(&increasing, &[(0.9, 0.5), (1.0, 0.880797)]);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(SeverityCurves::linear(3).validate(), Ok(()));
+
+        let cases = [
+            (
+                SeverityCurve::Linear { x0: 1.0, y0: 0.0, x1: 1.0, y1: 1.0 },
+                CurveError::DegenerateLinear("fuzzy"),
+            ),
+            (
+                SeverityCurve::Linear { x0: 0.0, y0: 0.0, x1: 1.0, y1: 2.0 },
+                CurveError::ValueOutOfRange("fuzzy"),
+            ),
+            (SeverityCurve::Step { points: vec![] }, CurveError::EmptySteps("fuzzy")),
+            (
+                SeverityCurve::Step { points: vec![(2.0, 0.5), (1.0, 0.9)] },
+                CurveError::UnorderedSteps("fuzzy"),
+            ),
+            (
+                SeverityCurve::Sigmoid { midpoint: f32::NAN, steepness: 1.0 },
+                CurveError::NotFinite("fuzzy"),
+            ),
+            (
+                SeverityCurve::Sigmoid { midpoint: 1.0, steepness: 0.0 },
+                CurveError::FlatSigmoid("fuzzy"),
+            ),
+        ];
+        for (curve, error) in cases {
+            assert_eq!(curve.validate("fuzzy"), Err(error));
+        }
+    }
+
+    #[test]
+    fn test_curve_serde() {
+        let curve: SeverityCurve =
+            serde_json::from_str(r#"{"type": "step", "points": [[1, 0.95], [2, 0.5]]}"#).unwrap();
+        assert_eq!(
+            curve,
+            SeverityCurve::Step {
+                points: vec![(1.0, 0.95), (2.0, 0.5)]
+            }
+        );
+    }
+}
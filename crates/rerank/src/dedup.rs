@@ -0,0 +1,154 @@
+//! Duplicate filings among retrieved candidates.
+//!
+//! Backends can return an application and its registration as separate
+//! documents, or several filings of one mark by one owner. Candidates
+//! sharing a serial or registration number collapse into one before
+//! scoring; after scoring, filings of the same mark by the same owner are
+//! grouped under the riskiest as its family.
+
+use ilegalflow_features::normalize_text;
+use ilegalflow_model::{CandidateHit, TrademarkRecord};
+use std::collections::HashMap;
+
+/// Identifiers a record shares with its duplicates.
+fn filing_keys(record: &TrademarkRecord) -> Vec<String> {
+    let serial = record.serial_number.trim();
+    let registration = record.registration_number.as_deref().map(str::trim);
+    let mut keys = Vec::new();
+    if !serial.is_empty() {
+        keys.push(format!("serial:{}", serial));
+    }
+    if let Some(registration) = registration.filter(|r| !r.is_empty()) {
+        keys.push(format!("registration:{}", registration));
+    }
+    keys
+}
+
+/// Collapse candidates sharing a serial or registration number into the
+/// first seen, keeping the best retrieval score. A registration replaces
+/// the bare application it duplicates.
+pub fn dedup_candidates(candidates: Vec<(TrademarkRecord, f32)>) -> Vec<(TrademarkRecord, f32)> {
+    let mut kept: Vec<(TrademarkRecord, f32)> = Vec::with_capacity(candidates.len());
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (record, retrieval_score) in candidates {
+        let keys = filing_keys(&record);
+        let i = match keys.iter().find_map(|key| seen.get(key).copied()) {
+            Some(i) => {
+                let (kept_record, kept_score) = &mut kept[i];
+                *kept_score = kept_score.max(retrieval_score);
+                if kept_record.registration_number.is_none() && record.registration_number.is_some()
+                {
+                    *kept_record = record;
+                }
+                i
+            }
+            None => {
+                kept.push((record, retrieval_score));
+                kept.len() - 1
+            }
+        };
+        for key in keys {
+            seen.entry(key).or_insert(i);
+        }
+    }
+    kept
+}
+
+/// Owner and mark shared by a family of filings, if the record names an
+/// owner.
+fn family_key(record: &TrademarkRecord) -> Option<(String, String)> {
+    let owner = normalize_text(&record.owner.name);
+    let mark = normalize_text(&record.mark_text);
+    (!owner.is_empty() && !mark.is_empty()).then(|| (owner.into_owned(), mark.into_owned()))
+}
+
+/// Group ranked hits by owner and mark: the first hit of each family
+/// keeps its place and the others' records move into its `family`.
+pub fn group_families(hits: Vec<CandidateHit>) -> Vec<CandidateHit> {
+    let mut grouped: Vec<CandidateHit> = Vec::with_capacity(hits.len());
+    let mut heads: HashMap<(String, String), usize> = HashMap::new();
+    for mut hit in hits {
+        let Some(key) = family_key(&hit.record) else {
+            grouped.push(hit);
+            continue;
+        };
+        match heads.get(&key) {
+            Some(&i) => {
+                let head = &mut grouped[i];
+                head.family.push(hit.record);
+                head.family.append(&mut hit.family);
+            }
+            None => {
+                heads.insert(key, grouped.len());
+                grouped.push(hit);
+            }
+        }
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behaviors::{BehaviorToggles, MARK_FAMILIES};
+    use crate::{rerank, RerankConfig};
+    use ilegalflow_model::{NiceClass, Owner, SearchQuery};
+
+    fn record(
+        serial: &str,
+        registration: Option<&str>,
+        mark: &str,
+        owner: &str,
+    ) -> TrademarkRecord {
+        let mut record = TrademarkRecord::new(serial, mark);
+        record.registration_number = registration.map(str::to_string);
+        record.owner = Owner::new(owner);
+        record
+    }
+
+    #[test]
+    fn test_dedup_candidates() {
+        let candidates = vec![
+            (record("001", None, "NIKE", ""), 0.5),
+            (record("002", Some("R2"), "NYKE", ""), 0.9),
+            (record("001", Some("R1"), "NIKE", ""), 0.7),
+            (record("003", Some("R2"), "NYKE", ""), 0.4),
+        ];
+        let kept = dedup_candidates(candidates);
+        assert_eq!(kept.len(), 2);
+        // The registration replaces the application, at the better score
+        assert_eq!(kept[0].0.registration_number.as_deref(), Some("R1"));
+        assert_eq!(kept[0].1, 0.7);
+        assert_eq!(kept[1].0.serial_number, "002");
+        assert_eq!(kept[1].1, 0.9);
+    }
+
+    #[test]
+    fn test_group_families() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let candidates = || {
+            let mut shoes = record("001", None, "NIKE", "Nike, Inc.");
+            shoes.classes = NiceClass::of_all(&[25]);
+            vec![
+                (record("002", None, "Nike", "NIKE INC"), 1.0),
+                (shoes, 1.0),
+                (record("003", None, "NIKE", "Other Co"), 1.0),
+                (record("001", None, "NIKE", "Nike, Inc."), 1.0),
+            ]
+        };
+
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert_eq!(hits.len(), 2);
+        // The duplicate 001 collapses; 001 outranks 002 and heads the family
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert_eq!(hits[0].family.len(), 1);
+        assert_eq!(hits[0].family[0].serial_number, "002");
+        assert!(hits[1].family.is_empty());
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(MARK_FAMILIES, false),
+            ..Default::default()
+        };
+        assert_eq!(rerank(&query, candidates(), &config).len(), 3);
+    }
+}
@@ -0,0 +1,184 @@
+//! Scoring of design marks.
+//!
+//! The literal element of a design mark may be incidental to its
+//! figurative one, so comparing it as a word mark misjudges the risk
+//! both ways. Design-mark candidates can have their text contributions
+//! discounted or be set aside in a bucket of their own, and when the
+//! record has design codes the result is reported as a
+//! `DesignElementRisk` rather than as a plain word-mark comparison.
+
+use crate::behaviors::DESIGN_MARKS;
+use crate::context::QueryContext;
+use crate::RerankConfig;
+use ilegalflow_model::{RiskFlag, TrademarkRecord};
+use serde::{Deserialize, Serialize};
+
+/// How design-mark candidates are scored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DesignHandling {
+    /// Like word marks
+    Compare,
+    /// Text contributions scaled by `text_factor`
+    #[default]
+    Discount,
+    /// Scored in full, but returned apart from the word marks
+    Bucket,
+}
+
+/// Settings for design-mark candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesignMarks {
+    pub handling: DesignHandling,
+    /// Share of text-similarity contributions kept when discounting
+    /// (0.0 - 1.0)
+    pub text_factor: f32,
+}
+
+impl Default for DesignMarks {
+    fn default() -> Self {
+        Self {
+            handling: DesignHandling::Discount,
+            text_factor: 0.5,
+        }
+    }
+}
+
+impl DesignMarks {
+    /// The handling in effect for a record: `Compare` for word marks or
+    /// when the behavior is off.
+    pub fn handling_for(&self, context: &QueryContext, record: &TrademarkRecord) -> DesignHandling {
+        if record.is_design_mark() && context.behaviors.is_enabled(DESIGN_MARKS) {
+            self.handling
+        } else {
+            DesignHandling::Compare
+        }
+    }
+}
+
+/// Whether a flag compares the marks' text.
+pub(crate) fn is_text_flag(flag: &RiskFlag) -> bool {
+    matches!(
+        flag,
+        RiskFlag::ObfuscatedMatch { .. }
+            | RiskFlag::NumberWordMatch { .. }
+            | RiskFlag::HouseMarkMatch { .. }
+            | RiskFlag::CompoundMatch { .. }
+            | RiskFlag::AcronymMatch { .. }
+            | RiskFlag::ForeignEquivalent { .. }
+            | RiskFlag::PhoneticMatch { .. }
+            | RiskFlag::NearPhoneticMatch { .. }
+            | RiskFlag::FuzzyMatch { .. }
+            | RiskFlag::StringSimilarity { .. }
+            | RiskFlag::SyllableMatch { .. }
+            | RiskFlag::DominantTermMatch { .. }
+    )
+}
+
+/// Scale the text contributions of a discounted design mark; `flags`
+/// and `contributions` are parallel.
+pub(crate) fn discount_text(flags: &[RiskFlag], contributions: &mut [f32], config: &RerankConfig) {
+    for (flag, contribution) in flags.iter().zip(contributions.iter_mut()) {
+        if is_text_flag(flag) {
+            *contribution *= config.design_marks.text_factor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rerank, rerank_outcome};
+    use ilegalflow_model::{MarkDrawingType, SearchQuery};
+
+    fn design_mark(serial: &str, mark: &str, codes: &[&str]) -> TrademarkRecord {
+        let mut record = TrademarkRecord::new(serial, mark);
+        record.drawing_type = Some(MarkDrawingType::DesignWithText);
+        record.design_codes = codes.iter().map(|c| c.to_string()).collect();
+        record
+    }
+
+    #[test]
+    fn test_discount() {
+        let query = SearchQuery::new("NIKE");
+        let candidates = || {
+            vec![
+                (design_mark("001", "NYKE", &[]), 1.0),
+                (TrademarkRecord::new("002", "NYKE"), 1.0),
+            ]
+        };
+
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "002");
+        assert!((hits[1].risk_score - hits[0].risk_score * 0.5).abs() < 1e-6);
+
+        let mut config = RerankConfig::default();
+        config.design_marks.handling = DesignHandling::Compare;
+        let hits = rerank(&query, candidates(), &config);
+        assert_eq!(hits[0].risk_score, hits[1].risk_score);
+    }
+
+    #[test]
+    fn test_design_element_risk() {
+        let mut query = SearchQuery::new("NIKE");
+        query.design_codes = vec!["05.07".to_string()];
+        let candidates = || {
+            vec![
+                (design_mark("001", "NYKE", &["26.01.03"]), 1.0),
+                (design_mark("002", "ORCHARD", &["05.07.13"]), 1.0),
+                (design_mark("003", "QUORLAND", &["26.01.03"]), 1.0),
+            ]
+        };
+
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        let flag = |serial: &str| {
+            let hit = hits.iter().find(|h| h.record.serial_number == serial).unwrap();
+            hit.flags
+                .iter()
+                .find(|f| matches!(f, RiskFlag::DesignElementRisk { .. }))
+                .cloned()
+        };
+        // The words were compared, so the design elements are reported
+        assert_eq!(
+            flag("001"),
+            Some(RiskFlag::DesignElementRisk {
+                shared: Vec::new(),
+                codes: vec!["26.01.03".to_string()],
+            })
+        );
+        assert_eq!(
+            flag("002"),
+            Some(RiskFlag::DesignElementRisk {
+                shared: vec!["05.07.13".to_string()],
+                codes: vec!["05.07.13".to_string()],
+            })
+        );
+        assert_eq!(flag("003"), None);
+
+        // Bucketed design marks are returned apart, fully scored
+        let mut config = RerankConfig::default();
+        config.design_marks.handling = DesignHandling::Bucket;
+        let candidates = vec![
+            (design_mark("001", "NYKE", &[]), 1.0),
+            (TrademarkRecord::new("002", "NYKE"), 1.0),
+        ];
+        let outcome = rerank_outcome(&query, candidates, &config);
+        assert_eq!(outcome.hits.len(), 1);
+        assert_eq!(outcome.design_hits.len(), 1);
+        assert_eq!(outcome.design_hits[0].risk_score, outcome.hits[0].risk_score);
+
+        // max_results caps both buckets together
+        config.max_results = Some(2);
+        let candidates = vec![
+            (design_mark("001", "NIKE", &[]), 1.0),
+            (TrademarkRecord::new("002", "NYKE"), 1.0),
+            (design_mark("003", "ZEPHYRA", &[]), 1.0),
+        ];
+        let outcome = rerank_outcome(&query, candidates, &config);
+        assert_eq!(outcome.hits.len() + outcome.design_hits.len(), 2);
+        assert_eq!(outcome.design_hits[0].record.serial_number, "001");
+        assert_eq!(outcome.hits[0].record.serial_number, "002");
+        assert_eq!(outcome.pruned, 1);
+    }
+}
@@ -0,0 +1,958 @@
+//! Flag detectors run by the re-ranker.
+//!
+//! Each check that can raise a risk flag is a [`FlagDetector`]. The
+//! re-ranker runs the detectors of its [`DetectorRegistry`] in order on
+//! every candidate that is not an exact match, adding up the score each
+//! flag contributes; exact matches score the maximum and only run
+//! `famous` and `dilution`, for their flags. The built-in checks are registered by default;
+//! downstream users add their own (industry-specific rules, say) with
+//! `with_detector`, or drop a built-in with `without`.
+
+use crate::behaviors::{
+    COMPOUNDS, FAMOUS_MARKS, FOREIGN_EQUIVALENTS, HOUSE_MARKS, NEAR_PHONETIC, NUMBER_WORDS,
+    PREFIX_MATCH, RELATED_CLASSES, SYLLABLES,
+};
+use crate::context::QueryContext;
+use crate::design::{is_text_flag, DesignHandling};
+use crate::dilution::DilutionDetector;
+use crate::guards::Rule;
+use crate::{
+    class_factor, number_word_equivalent, obfuscated_match, reordering_similarity, GoodsContext,
+    RerankConfig,
+};
+use ilegalflow_features::{
+    class_overlap, common_prefix_len, compare_phonemes, compound_match, design_code_overlap,
+    edit_distance_bounded, jaro_winkler, phonemes, prefix_weighted_similarity, stem_text,
+    strip_house_marks, syllable_similarity, NgramProfile,
+};
+use ilegalflow_model::{RiskFlag, SearchQuery, TrademarkRecord};
+use std::fmt;
+use std::sync::Arc;
+
+/// A candidate as seen by the detectors.
+pub struct Candidate<'a> {
+    /// The query being cleared
+    pub query: &'a SearchQuery,
+    /// The candidate record
+    pub record: &'a TrademarkRecord,
+    /// Normalized mark text, transliterated to Latin script
+    pub mark: &'a str,
+    /// Re-ranker configuration
+    pub config: &'a RerankConfig,
+    /// Flags raised by earlier detectors
+    pub flags: &'a [RiskFlag],
+    pub(crate) goods: Option<&'a GoodsContext>,
+}
+
+impl Candidate<'_> {
+    /// Whether both marks pass a rule's guard, without reporting a skip.
+    pub fn passes(&self, context: &QueryContext, rule: Rule) -> bool {
+        self.config
+            .guards
+            .get(rule)
+            .check_pair(&context.normalized, self.mark)
+            .is_ok()
+    }
+}
+
+/// A check that raises risk flags on a candidate.
+pub trait FlagDetector: Send + Sync {
+    /// Detector name, used to replace or remove it
+    fn name(&self) -> &str;
+
+    /// Rule whose guard both marks must pass for the detector to run;
+    /// skips are reported in the re-rank warnings.
+    fn rule(&self) -> Option<Rule> {
+        None
+    }
+
+    /// Whether the detector compares the query's mark text with the
+    /// candidate's; such detectors are skipped when the query has none.
+    fn compares_text(&self) -> bool {
+        false
+    }
+
+    /// Flags raised on the candidate, each with the score it adds.
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)>;
+}
+
+/// Marks equal once house marks and designators are stripped (ACME NIKE
+/// SERIES vs NIKE). Designators alone do not count; marks differing only
+/// by them are exact matches.
+struct HouseMarkDetector;
+
+impl FlagDetector for HouseMarkDetector {
+    fn name(&self) -> &str {
+        "house_marks"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        if !context.behaviors.is_enabled(HOUSE_MARKS) {
+            return Vec::new();
+        }
+        let mark = strip_house_marks(candidate.mark, &context.house_marks);
+        let query = &context.house_stripped;
+        let house_mark_removed = !query.house_marks.is_empty() || !mark.house_marks.is_empty();
+        if !house_mark_removed || query.text != mark.text {
+            return Vec::new();
+        }
+        let flag = RiskFlag::HouseMarkMatch {
+            house_marks: [&query.house_marks[..], &mark.house_marks[..]].concat(),
+            designators: [&query.designators[..], &mark.designators[..]].concat(),
+        };
+        vec![(flag, candidate.config.house_mark_weight)]
+    }
+}
+
+/// Words equal once stemmed (WIDGETS vs WIDGET).
+struct StemDetector;
+
+impl FlagDetector for StemDetector {
+    fn name(&self) -> &str {
+        "stemming"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        match &context.stemmed {
+            Some(stemmed) if *stemmed == stem_text(candidate.mark) => {
+                let flag = RiskFlag::StringSimilarity {
+                    metric: "stem_match".to_string(),
+                    score: 1.0,
+                };
+                vec![(flag, candidate.config.stem_weight)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Marks equal once character substitutions are undone (N1KE vs NIKE).
+struct ObfuscationDetector;
+
+impl FlagDetector for ObfuscationDetector {
+    fn name(&self) -> &str {
+        "obfuscation"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        obfuscated_match(context, candidate.record, candidate.config)
+            .map(|flag| (flag, candidate.config.obfuscation_weight))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Numeral/number-word swaps (4EVER vs FOREVER); near matches fall under
+/// the fuzzy guard.
+struct NumberWordDetector;
+
+impl FlagDetector for NumberWordDetector {
+    fn name(&self) -> &str {
+        "number_words"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        let disguised = candidate
+            .flags
+            .iter()
+            .any(|f| matches!(f, RiskFlag::ObfuscatedMatch { .. }));
+        if disguised || !context.behaviors.is_enabled(NUMBER_WORDS) {
+            return Vec::new();
+        }
+        let Some(found) = number_word_equivalent(context, candidate.mark, config.max_edit_distance)
+        else {
+            return Vec::new();
+        };
+        if found.distance > 0 && !candidate.passes(context, Rule::Fuzzy) {
+            return Vec::new();
+        }
+        let score = config.number_word_weight * config.curves.fuzzy.eval(found.distance as f32);
+        let flag = RiskFlag::NumberWordMatch {
+            distance: found.distance as u8,
+            transformations: found.transformations,
+        };
+        vec![(flag, score)]
+    }
+}
+
+/// Run-together compounds (SUNPOWER vs SUN POWER CO).
+struct CompoundDetector;
+
+impl FlagDetector for CompoundDetector {
+    fn name(&self) -> &str {
+        "compounds"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        if !context.behaviors.is_enabled(COMPOUNDS) {
+            return Vec::new();
+        }
+        compound_match(&context.normalized, candidate.mark)
+            .map(|found| {
+                let flag = RiskFlag::CompoundMatch {
+                    splits: found.splits,
+                };
+                (flag, candidate.config.compound_weight)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Foreign equivalents (LUPO vs WOLF).
+struct ForeignEquivalentDetector;
+
+impl FlagDetector for ForeignEquivalentDetector {
+    fn name(&self) -> &str {
+        "foreign_equivalents"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        if !context.behaviors.is_enabled(FOREIGN_EQUIVALENTS) {
+            return Vec::new();
+        }
+        let config = candidate.config;
+        config
+            .translations
+            .foreign_equivalent(&context.normalized, candidate.mark)
+            .map(|found| {
+                let flag = RiskFlag::ForeignEquivalent {
+                    language: found.language,
+                    translation: found.translation,
+                };
+                (flag, config.foreign_weight)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Shared phonetic codes, or failing that phoneme sequences a phoneme or
+/// two apart.
+struct PhoneticDetector;
+
+impl FlagDetector for PhoneticDetector {
+    fn name(&self) -> &str {
+        "phonetic"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Phonetic)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        if let Some(found) = config.phonetic.match_encoded(&context.phonetic, candidate.mark) {
+            let flag = RiskFlag::PhoneticMatch {
+                algorithm: found.algorithm,
+                code: found.code,
+                variant: found.variant,
+            };
+            return vec![(flag, config.phonetic_weight)];
+        }
+        if !context.behaviors.is_enabled(NEAR_PHONETIC) {
+            return Vec::new();
+        }
+        let found = compare_phonemes(&context.phonemes, &phonemes(candidate.mark));
+        if found.distance > 0.0
+            && found.distance <= config.max_phoneme_distance
+            && found.similarity >= config.min_phoneme_similarity
+        {
+            let score = config.near_phonetic_weight * found.similarity;
+            let flag = RiskFlag::NearPhoneticMatch {
+                distance: found.distance,
+                similarity: found.similarity,
+                phonemes: (found.a, found.b),
+            };
+            return vec![(flag, score)];
+        }
+        Vec::new()
+    }
+}
+
+/// Edit distance within `max_edit_distance`.
+struct FuzzyDetector;
+
+impl FlagDetector for FuzzyDetector {
+    fn name(&self) -> &str {
+        "fuzzy"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Fuzzy)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        edit_distance_bounded(&context.normalized, candidate.mark, config.max_edit_distance)
+            .filter(|&distance| distance > 0)
+            .map(|distance| {
+                let score = config.fuzzy_weight * config.curves.fuzzy.eval(distance as f32);
+                let flag = RiskFlag::FuzzyMatch {
+                    distance: distance as u8,
+                };
+                (flag, score)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// String similarity, which unlike edit distance does not penalize long
+/// marks for their length; reordered words are compared token-sorted.
+struct SimilarityDetector;
+
+impl FlagDetector for SimilarityDetector {
+    fn name(&self) -> &str {
+        "similarity"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Similarity)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        let query = context.normalized.as_str();
+        let similarity = jaro_winkler(query, candidate.mark);
+        let (metric, score) = if similarity >= config.similarity_threshold {
+            ("jaro_winkler", similarity)
+        } else if let Some(reordered) =
+            reordering_similarity(query, candidate.mark, config.token_sort_threshold)
+        {
+            ("token_sort_ratio", reordered)
+        } else {
+            return Vec::new();
+        };
+        let flag = RiskFlag::StringSimilarity {
+            metric: metric.to_string(),
+            score,
+        };
+        vec![(flag, config.similarity_weight * config.curves.similarity.eval(score))]
+    }
+}
+
+/// A shared beginning, which consumers notice first.
+struct PrefixDetector;
+
+impl FlagDetector for PrefixDetector {
+    fn name(&self) -> &str {
+        "prefix"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Similarity)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        let query = context.normalized.as_str();
+        if !context.behaviors.is_enabled(PREFIX_MATCH)
+            || common_prefix_len(query, candidate.mark) < config.prefix_length
+        {
+            return Vec::new();
+        }
+        let similarity = prefix_weighted_similarity(query, candidate.mark, config.prefix_decay);
+        let flag = RiskFlag::StringSimilarity {
+            metric: "prefix_weighted".to_string(),
+            score: similarity,
+        };
+        vec![(flag, config.prefix_weight * config.curves.similarity.eval(similarity))]
+    }
+}
+
+/// Syllable-by-syllable similarity, for marks respelling a syllable
+/// (VITALITY vs VITALITEA).
+struct SyllableDetector;
+
+impl FlagDetector for SyllableDetector {
+    fn name(&self) -> &str {
+        "syllables"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Similarity)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        if !context.behaviors.is_enabled(SYLLABLES) {
+            return Vec::new();
+        }
+        let config = candidate.config;
+        let alignment = syllable_similarity(&context.normalized, candidate.mark);
+        if alignment.pairs.len() < 2 || alignment.score < config.syllable_threshold {
+            return Vec::new();
+        }
+        let score = config.syllable_weight * config.curves.similarity.eval(alignment.score);
+        let flag = RiskFlag::SyllableMatch {
+            score: alignment.score,
+            aligned: alignment.pairs,
+        };
+        vec![(flag, score)]
+    }
+}
+
+/// Character n-gram overlap, which ignores spacing differences (COCACOLA
+/// vs COCA COLA).
+struct NgramDetector;
+
+impl FlagDetector for NgramDetector {
+    fn name(&self) -> &str {
+        "ngram"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Ngram)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        let ngram = context
+            .ngrams
+            .similarity(&NgramProfile::new(candidate.mark, config.ngram_size))
+            .cosine;
+        if ngram < config.ngram_threshold {
+            return Vec::new();
+        }
+        let flag = RiskFlag::StringSimilarity {
+            metric: "ngram_cosine".to_string(),
+            score: ngram,
+        };
+        vec![(flag, config.ngram_weight * config.curves.similarity.eval(ngram))]
+    }
+}
+
+/// Shared Nice classes, discounted when either side's were inferred.
+struct ClassDetector;
+
+impl FlagDetector for ClassDetector {
+    fn name(&self) -> &str {
+        "class"
+    }
+
+    fn detect(&self, _context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let (query, record) = (candidate.query, candidate.record);
+        let overlapping = class_overlap(&query.classes, &record.classes);
+        if overlapping.is_empty() {
+            return Vec::new();
+        }
+        let inferred = query.classes_inferred || record.classes_inferred;
+        let score = candidate.config.class_weight * class_factor(inferred, candidate.config);
+        let flag = RiskFlag::ClassOverlap {
+            classes: overlapping,
+            inferred,
+        };
+        vec![(flag, score)]
+    }
+}
+
+/// Different but related classes, when no class is shared; scored by the
+/// closest pair and discounted like class overlap.
+struct RelatedClassesDetector;
+
+impl FlagDetector for RelatedClassesDetector {
+    fn name(&self) -> &str {
+        "related_classes"
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let (query, record, config) = (candidate.query, candidate.record, candidate.config);
+        if !context.behaviors.is_enabled(RELATED_CLASSES)
+            || !class_overlap(&query.classes, &record.classes).is_empty()
+        {
+            return Vec::new();
+        }
+        let pairs = config
+            .class_relatedness
+            .related_pairs(&query.classes, &record.classes);
+        let Some(&(_, _, relatedness)) = pairs.first() else {
+            return Vec::new();
+        };
+        let inferred = query.classes_inferred || record.classes_inferred;
+        let score = config.related_class_weight * relatedness * class_factor(inferred, config);
+        let flag = RiskFlag::RelatedClasses {
+            pairs: pairs.into_iter().map(|(a, b, _)| (a, b)).collect(),
+            relatedness,
+        };
+        vec![(flag, score)]
+    }
+}
+
+/// Goods/services similarity, lexical and (with an embedder) semantic,
+/// keeping the higher.
+struct GoodsDetector;
+
+impl FlagDetector for GoodsDetector {
+    fn name(&self) -> &str {
+        "goods"
+    }
+
+    #[cfg_attr(not(feature = "embeddings"), allow(unused_variables))]
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        let similarity = candidate
+            .goods
+            .and_then(|goods| goods.similarity(candidate.record, config));
+        #[cfg(feature = "embeddings")]
+        let similarity = match (
+            similarity,
+            crate::semantic_goods_similarity(context, candidate.record, config),
+        ) {
+            (Some(lexical), Some(semantic)) => Some(lexical.max(semantic)),
+            (lexical, semantic) => lexical.or(semantic),
+        };
+        similarity
+            .map(|similarity| {
+                let flag = RiskFlag::GoodsServicesSimilar { similarity };
+                (flag, config.goods_weight * similarity)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Shared design codes.
+struct DesignDetector;
+
+impl FlagDetector for DesignDetector {
+    fn name(&self) -> &str {
+        "design"
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let (query, record, config) = (candidate.query, candidate.record, candidate.config);
+        let codes = design_code_overlap(&query.design_codes, &record.design_codes);
+        let handling = config.design_marks.handling_for(context, record);
+        if handling != DesignHandling::Compare && !record.design_codes.is_empty() {
+            // A design mark's elements are reported whenever its codes
+            // overlap or its words were compared
+            if codes.is_empty() && !candidate.flags.iter().any(is_text_flag) {
+                return Vec::new();
+            }
+            let contribution = if codes.is_empty() { 0.0 } else { config.design_weight };
+            let flag = RiskFlag::DesignElementRisk {
+                shared: codes,
+                codes: record.design_codes.clone(),
+            };
+            return vec![(flag, contribution)];
+        }
+        if codes.is_empty() {
+            return Vec::new();
+        }
+        vec![(RiskFlag::DesignCodeOverlap { codes }, config.design_weight)]
+    }
+}
+
+/// The query's dominant element in the mark, across stylized spellings
+/// and word boundaries.
+struct DominantDetector;
+
+impl FlagDetector for DominantDetector {
+    fn name(&self) -> &str {
+        "dominant"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::Dominant)
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        context
+            .dominant
+            .compare(&candidate.record.mark_text)
+            .map(|found| {
+                let flag = RiskFlag::DominantTermMatch {
+                    term: found.element,
+                    transformations: found.transformations,
+                };
+                (flag, candidate.config.dominant_weight * found.weight)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// A famous mark contained in both marks (NIKE RUNNING vs NIKE GOLF),
+/// scored whatever their classes.
+struct FamousDetector;
+
+impl FlagDetector for FamousDetector {
+    fn name(&self) -> &str {
+        "famous"
+    }
+
+    fn compares_text(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        if context.famous.is_empty() || !context.behaviors.is_enabled(FAMOUS_MARKS) {
+            return Vec::new();
+        }
+        let (query, record) = (candidate.query, candidate.record);
+        let found = candidate.config.famous_marks.find_in(candidate.mark);
+        let Some(famous) = found.into_iter().find(|famous| context.famous.contains(famous)) else {
+            return Vec::new();
+        };
+
+        let cross_class = !query.classes.is_empty()
+            && !record.classes.is_empty()
+            && class_overlap(&query.classes, &record.classes).is_empty();
+        let flag = RiskFlag::FamousMark {
+            mark: famous.name.clone(),
+            owner: famous.owner.clone(),
+            cross_class,
+        };
+        vec![(flag, candidate.config.famous_weight)]
+    }
+}
+
+/// Use of a resembling mark outside the register, from the configured
+/// common-law sources; runs last so it sees every mark-similarity flag.
+struct CommonLawDetector;
+
+impl FlagDetector for CommonLawDetector {
+    fn name(&self) -> &str {
+        "common_law"
+    }
+
+    fn detect(&self, _context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        let config = candidate.config;
+        if config.common_law_sources.is_empty() || !resembles(candidate.flags) {
+            return Vec::new();
+        }
+
+        config
+            .common_law_sources
+            .iter()
+            .flat_map(|source| source.find_use(candidate.mark))
+            .filter(|found| found.confidence >= config.common_law_threshold)
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+            .map(|found| {
+                let contribution = config.common_law_weight * found.confidence;
+                let flag = RiskFlag::CommonLawRisk {
+                    source: found.source,
+                    evidence: found.evidence,
+                    confidence: found.confidence,
+                };
+                (flag, contribution)
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Whether any flag compares the marks themselves; class, goods, design
+/// or owner flags alone do not make the marks alike.
+fn resembles(flags: &[RiskFlag]) -> bool {
+    flags.iter().any(|flag| {
+        crate::design::is_text_flag(flag)
+            || matches!(
+                flag,
+                RiskFlag::ExactMatch | RiskFlag::FamousMark { .. } | RiskFlag::DilutionRisk { .. }
+            )
+    })
+}
+
+/// The detectors the re-ranker runs, in order.
+#[derive(Clone)]
+pub struct DetectorRegistry {
+    detectors: Vec<Arc<dyn FlagDetector>>,
+}
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        Self::empty()
+            .with_detector(HouseMarkDetector)
+            .with_detector(StemDetector)
+            .with_detector(ObfuscationDetector)
+            .with_detector(NumberWordDetector)
+            .with_detector(CompoundDetector)
+            .with_detector(ForeignEquivalentDetector)
+            .with_detector(PhoneticDetector)
+            .with_detector(FuzzyDetector)
+            .with_detector(SimilarityDetector)
+            .with_detector(PrefixDetector)
+            .with_detector(SyllableDetector)
+            .with_detector(NgramDetector)
+            .with_detector(ClassDetector)
+            .with_detector(RelatedClassesDetector)
+            .with_detector(GoodsDetector)
+            .with_detector(DesignDetector)
+            .with_detector(DominantDetector)
+            .with_detector(FamousDetector)
+            .with_detector(DilutionDetector)
+            .with_detector(CommonLawDetector)
+    }
+}
+
+impl fmt::Debug for DetectorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl DetectorRegistry {
+    /// Create a registry with no detectors (flags only exact matches).
+    pub fn empty() -> Self {
+        Self {
+            detectors: Vec::new(),
+        }
+    }
+
+    /// Add a detector after the others, or in place of an existing
+    /// detector with the same name.
+    pub fn with_detector(mut self, detector: impl FlagDetector + 'static) -> Self {
+        let detector: Arc<dyn FlagDetector> = Arc::new(detector);
+        match self
+            .detectors
+            .iter()
+            .position(|d| d.name() == detector.name())
+        {
+            Some(i) => self.detectors[i] = detector,
+            None => self.detectors.push(detector),
+        }
+        self
+    }
+
+    /// Remove the detector with a name.
+    pub fn without(mut self, name: &str) -> Self {
+        self.detectors.retain(|d| d.name() != name);
+        self
+    }
+
+    /// Names of the registered detectors, in run order.
+    pub fn names(&self) -> Vec<&str> {
+        self.detectors.iter().map(|d| d.name()).collect()
+    }
+
+    /// The registered detectors, in run order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn FlagDetector> {
+        self.detectors.iter().map(|d| &**d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::NiceClass;
+    use crate::behaviors::BehaviorToggles;
+    use crate::rerank;
+
+    /// Flags marks filed in class 5 as regulated (a stand-in for an
+    /// industry-specific rule).
+    struct RegulatedClass;
+
+    const REGULATED: RiskFlag = RiskFlag::ClassOverlap {
+        classes: Vec::new(),
+        inferred: false,
+    };
+
+    impl FlagDetector for RegulatedClass {
+        fn name(&self) -> &str {
+            "regulated"
+        }
+
+        fn detect(&self, _: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+            if candidate.record.classes.contains(&NiceClass::of(5)) {
+                vec![(REGULATED.clone(), 0.3)]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = DetectorRegistry::default();
+        assert_eq!(registry.names()[0], "house_marks");
+        assert_eq!(registry.names().len(), 20);
+
+        let registry = registry.without("fuzzy").with_detector(RegulatedClass);
+        assert!(!registry.names().contains(&"fuzzy"));
+        assert_eq!(registry.names().last(), Some(&"regulated"));
+        let replaced = registry.clone().with_detector(RegulatedClass);
+        assert_eq!(replaced.names(), registry.names());
+    }
+
+    #[test]
+    fn test_custom_detector() {
+        let query = SearchQuery::new("ZEPHYRA");
+        let mut record = TrademarkRecord::new("001", "QUORLAND");
+        record.classes = NiceClass::of_all(&[5]);
+        let config = RerankConfig {
+            detectors: DetectorRegistry::default().with_detector(RegulatedClass),
+            ..RerankConfig::default()
+        };
+
+        let hits = rerank(&query, vec![(record.clone(), 1.0)], &config);
+        assert_eq!(hits[0].flags, vec![REGULATED]);
+        assert!((hits[0].risk_score - 0.3).abs() < 1e-6);
+
+        let hits = rerank(&query, vec![(record, 1.0)], &RerankConfig::default());
+        assert!(hits[0].flags.is_empty());
+    }
+
+    #[test]
+    fn test_famous_marks() {
+        use ilegalflow_famous::{FamousMark, FamousMarks};
+
+        let famous = |hits: &[ilegalflow_model::CandidateHit], serial: &str| {
+            let hit = hits.iter().find(|h| h.record.serial_number == serial).unwrap();
+            hit.flags.iter().find(|f| matches!(f, RiskFlag::FamousMark { .. })).cloned()
+        };
+        let query = SearchQuery::new("NIKE RUNNING").with_classes(NiceClass::of_all(&[25]));
+        let mut golf = TrademarkRecord::new("001", "NIKE GOLF");
+        golf.classes = NiceClass::of_all(&[25]);
+        let mut diner = TrademarkRecord::new("002", "Nike Diner");
+        diner.classes = NiceClass::of_all(&[43]);
+        let mut zephyra = TrademarkRecord::new("003", "ZEPHYRA");
+        zephyra.classes = NiceClass::of_all(&[43]);
+        let candidates = vec![(golf, 1.0), (diner, 1.0), (zephyra.clone(), 1.0)];
+
+        let hits = rerank(&query, candidates.clone(), &RerankConfig::default());
+        assert_eq!(
+            famous(&hits, "001"),
+            Some(RiskFlag::FamousMark {
+                mark: "NIKE".to_string(),
+                owner: "Nike, Inc.".to_string(),
+                cross_class: false,
+            })
+        );
+        assert!(matches!(
+            famous(&hits, "002"),
+            Some(RiskFlag::FamousMark { cross_class: true, .. })
+        ));
+        assert_eq!(famous(&hits, "003"), None);
+
+        // A user-supplied list
+        let mut marks = FamousMarks::empty();
+        marks.insert(FamousMark {
+            name: "ZEPHYRA".to_string(),
+            owner: "Zephyra Corp".to_string(),
+            classes: NiceClass::of_all(&[25]),
+        });
+        let config = RerankConfig {
+            famous_marks: Arc::new(marks),
+            ..RerankConfig::default()
+        };
+        let query = SearchQuery::new("ZEPHYRA CAFE");
+        let hits = rerank(&query, vec![(zephyra, 1.0)], &config);
+        assert!(famous(&hits, "003").is_some());
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(FAMOUS_MARKS, false),
+            ..RerankConfig::default()
+        };
+        let hits = rerank(&SearchQuery::new("NIKE RUNNING"), candidates, &config);
+        assert_eq!(famous(&hits, "001"), None);
+    }
+
+    #[test]
+    fn test_common_law() {
+        use crate::common_law::{UseEvidence, UseListing};
+
+        let mut listing = UseListing::default();
+        for (mark, confidence) in [("NYKE", 0.8), ("NIKA", 0.3), ("ADIDAS", 1.0)] {
+            let evidence = UseEvidence {
+                source: "business registry".to_string(),
+                evidence: format!("{} Sports LLC", mark),
+                confidence,
+            };
+            listing.insert(mark, evidence);
+        }
+        let config = RerankConfig {
+            common_law_sources: vec![Arc::new(listing)],
+            ..RerankConfig::default()
+        };
+        let query = SearchQuery::new("NIKE");
+        let candidates = vec![
+            (TrademarkRecord::new("001", "NYKE"), 1.0),
+            (TrademarkRecord::new("002", "NIKA"), 1.0),
+            (TrademarkRecord::new("003", "ADIDAS"), 1.0),
+        ];
+        let hits = rerank(&query, candidates, &config);
+        let common_law = |serial: &str| {
+            let hit = hits.iter().find(|h| h.record.serial_number == serial).unwrap();
+            hit.flags.iter().find(|f| matches!(f, RiskFlag::CommonLawRisk { .. })).cloned()
+        };
+
+        assert_eq!(
+            common_law("001"),
+            Some(RiskFlag::CommonLawRisk {
+                source: "business registry".to_string(),
+                evidence: "NYKE Sports LLC".to_string(),
+                confidence: 0.8,
+            })
+        );
+        // Below the confidence threshold
+        assert_eq!(common_law("002"), None);
+        // In use, but nothing like the query
+        assert_eq!(common_law("003"), None);
+
+        let owner = RiskFlag::OwnerConflict {
+            owner: "Nike, Inc.".to_string(),
+            related_marks: vec!["AIR JORDAN".to_string()],
+        };
+        let overlap = RiskFlag::ClassOverlap { classes: Vec::new(), inferred: false };
+        assert!(!resembles(&[owner, overlap]));
+        let acronym = RiskFlag::AcronymMatch {
+            acronym: "IBM".to_string(),
+            expansion: "INTERNATIONAL BUSINESS MACHINES".to_string(),
+        };
+        assert!(resembles(&[acronym]));
+    }
+}
@@ -0,0 +1,180 @@
+//! Dilution of famous marks.
+//!
+//! Confusion asks whether buyers would mistake one source for another;
+//! dilution asks whether the query would weaken a famous mark even where
+//! nobody is confused. It is blurring when the famous mark stops pointing
+//! to a single source (NIKE plumbing), and tarnishment when the query
+//! ties it to unsavory goods or services (NIKE cannabis).
+//!
+//! Candidates flagged `FamousMark` are scored as fame × similarity ×
+//! class factor. Similarity is the share of the query's words that the
+//! famous mark takes up. The class factor runs from 0.5, when the query
+//! is in the famous mark's classes and confusion already covers it, to
+//! 1.0 when the classes are unrelated.
+
+use crate::behaviors::DILUTION;
+use crate::context::QueryContext;
+use crate::detectors::{Candidate, FlagDetector};
+use ilegalflow_features::{class_overlap, normalize_text};
+use ilegalflow_model::{DilutionKind, NiceClass, RiskFlag, SearchQuery};
+
+/// Fame of a listed famous mark; the bundled list holds household names
+/// only.
+const FAME: f32 = 1.0;
+
+/// Nice classes whose goods tarnish by association (tobacco).
+const TARNISHING_CLASSES: &[NiceClass] = &[NiceClass::of(34)];
+
+/// Beginnings of goods/services words that tarnish by association.
+#[rustfmt::skip]
+const TARNISHING_TERMS: &[&str] = &[
+    "ADULT", "EROTIC", "PORN", "SEXUAL", "STRIPTEASE", "ESCORT", "CANNABIS", "MARIJUANA",
+    "TOBACCO", "CIGAR", "VAPE", "VAPING", "NARCOTIC", "FIREARM", "GAMBL", "CASINO",
+];
+
+/// Dilution of one famous mark by the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dilution {
+    pub kind: DilutionKind,
+    /// Combined score (0.0 - 1.0)
+    pub score: f32,
+    /// Share of the query's words the famous mark takes up
+    pub similarity: f32,
+    /// 0.0 in the famous mark's classes, 1.0 outside them, 0.5 when the
+    /// query has no classes
+    pub class_distance: f32,
+}
+
+/// Whether the query's goods or classes tarnish by association.
+fn is_tarnishing(query: &SearchQuery) -> bool {
+    if query.classes.iter().any(|c| TARNISHING_CLASSES.contains(c)) {
+        return true;
+    }
+    let Some(goods) = query.goods_services_query.as_deref() else {
+        return false;
+    };
+    normalize_text(goods)
+        .split_whitespace()
+        .any(|word| TARNISHING_TERMS.iter().any(|term| word.starts_with(term)))
+}
+
+/// Score the query's dilution of a famous mark it contains.
+pub fn dilution(
+    query: &SearchQuery,
+    query_normalized: &str,
+    famous: &str,
+    classes: &[NiceClass],
+) -> Dilution {
+    let query_words = query_normalized.split_whitespace().count().max(1);
+    let famous_words = normalize_text(famous).split_whitespace().count();
+    let similarity = (famous_words as f32 / query_words as f32).min(1.0);
+
+    let class_distance = if query.classes.is_empty() || classes.is_empty() {
+        0.5
+    } else if class_overlap(&query.classes, classes).is_empty() {
+        1.0
+    } else {
+        0.0
+    };
+
+    let kind = if is_tarnishing(query) {
+        DilutionKind::Tarnishment
+    } else {
+        DilutionKind::Blurring
+    };
+    Dilution {
+        kind,
+        score: FAME * similarity * (0.5 + 0.5 * class_distance),
+        similarity,
+        class_distance,
+    }
+}
+
+/// Dilution of the famous marks flagged on a candidate; runs after the
+/// famous-mark detector.
+pub(crate) struct DilutionDetector;
+
+impl FlagDetector for DilutionDetector {
+    fn name(&self) -> &str {
+        "dilution"
+    }
+
+    fn detect(&self, context: &QueryContext, candidate: &Candidate<'_>) -> Vec<(RiskFlag, f32)> {
+        if !context.behaviors.is_enabled(DILUTION) {
+            return Vec::new();
+        }
+        let config = candidate.config;
+        candidate
+            .flags
+            .iter()
+            .filter_map(|flag| match flag {
+                RiskFlag::FamousMark { mark, .. } => Some(mark),
+                _ => None,
+            })
+            .filter_map(|mark| {
+                let classes = config
+                    .famous_marks
+                    .get(mark)
+                    .map(|famous| famous.classes.as_slice())
+                    .unwrap_or_default();
+                let found = dilution(candidate.query, &context.normalized, mark, classes);
+                (found.score >= config.dilution_threshold).then(|| {
+                    let flag = RiskFlag::DilutionRisk {
+                        kind: found.kind,
+                        famous: mark.clone(),
+                        score: found.score,
+                    };
+                    (flag, config.dilution_weight * found.score)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rerank, RerankConfig};
+    use ilegalflow_model::TrademarkRecord;
+
+    #[test]
+    fn test_dilution_score() {
+        // Unrelated class: the whole query is the famous mark
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[11]));
+        let found = dilution(&query, "NIKE", "NIKE", &NiceClass::of_all(&[25]));
+        assert_eq!(found.kind, DilutionKind::Blurring);
+        assert_eq!(found.score, 1.0);
+
+        // Same class, half the words: confusion mostly covers it
+        let query = SearchQuery::new("NIKE RUNNING").with_classes(NiceClass::of_all(&[25]));
+        let found = dilution(&query, "NIKE RUNNING", "NIKE", &NiceClass::of_all(&[25]));
+        assert_eq!(found.similarity, 0.5);
+        assert_eq!(found.score, 0.25);
+
+        let mut query = SearchQuery::new("NIKE LOUNGE");
+        query.goods_services_query = Some("Cannabis dispensary services".to_string());
+        let found = dilution(&query, "NIKE LOUNGE", "NIKE", &NiceClass::of_all(&[25]));
+        assert_eq!(found.kind, DilutionKind::Tarnishment);
+        assert_eq!(found.class_distance, 0.5);
+    }
+
+    #[test]
+    fn test_dilution_flag() {
+        let query = SearchQuery::new("NIKE PLUMBING").with_classes(NiceClass::of_all(&[11]));
+        let mut record = TrademarkRecord::new("001", "NIKE");
+        record.classes = NiceClass::of_all(&[25]);
+        let hits = rerank(&query, vec![(record, 1.0)], &RerankConfig::default());
+        let dilution = hits[0]
+            .flags
+            .iter()
+            .find(|f| matches!(f, RiskFlag::DilutionRisk { .. }));
+        assert_eq!(
+            dilution,
+            Some(&RiskFlag::DilutionRisk {
+                kind: DilutionKind::Blurring,
+                famous: "NIKE".to_string(),
+                score: 0.5,
+            })
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Composite dominant-element comparison.
+//!
+//! Plain dominant-term matching compares the longest word of each mark,
+//! which misses stylized and run-together spellings such as KWIKPRINT
+//! against QUICK PRINT SOLUTIONS. This comparator canonicalizes both
+//! marks (splitting stylized prefixes, substituting standard spellings)
+//! and then looks for one mark's dominant element as a run of adjacent
+//! words in the other. Every transformation applied is recorded so the
+//! match can be explained.
+//!
+//! With corpus term frequencies, each mark contributes its rarest words
+//! as dominant terms, weighted by inverse document frequency, rather than
+//! its longest word alone. Terms generic or descriptive for the query's
+//! classes are passed over where possible and weigh less when matched.
+
+use ilegalflow_features::{
+    extract_dominant_term_in, normalize_text, split_stylized_prefix, standard_spelling,
+    term_strength_in, TermFrequencies, WeightedTerm,
+};
+use ilegalflow_model::NiceClass;
+use std::sync::Arc;
+
+/// Shortest element accepted as a dominant-element match.
+const MIN_ELEMENT_LEN: usize = 4;
+
+/// A dominant element shared by two marks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DominantElementMatch {
+    /// The normalized element, e.g. "QUICKPRINT"
+    pub element: String,
+
+    /// Transformations applied to reach the match, in order
+    pub transformations: Vec<String>,
+
+    /// Weight of the matched term relative to the marks' top terms
+    /// (1.0 without corpus frequencies)
+    pub weight: f32,
+}
+
+/// A mark reduced to standard-spelling words.
+#[derive(Debug, Clone)]
+struct Canonical {
+    words: Vec<String>,
+    transformations: Vec<String>,
+}
+
+impl Canonical {
+    fn new(text: &str) -> Self {
+        let mut words = Vec::new();
+        let mut transformations = Vec::new();
+
+        for word in normalize_text(text).split_whitespace() {
+            if let Some(standard) = standard_spelling(word) {
+                transformations.push(format!("respell {} -> {}", word, standard));
+                words.push(standard.to_string());
+            } else if let Some((prefix, rest)) = split_stylized_prefix(word) {
+                transformations.push(format!("split {} -> {} {}", word, prefix, rest));
+                if let Some(standard) = standard_spelling(prefix) {
+                    transformations.push(format!("respell {} -> {}", prefix, standard));
+                    words.push(standard.to_string());
+                }
+                words.push(rest.to_string());
+            } else {
+                words.push(word.to_string());
+            }
+        }
+
+        Self {
+            words,
+            transformations,
+        }
+    }
+
+    /// The words joined without spaces.
+    fn joined(&self) -> String {
+        self.words.concat()
+    }
+
+    /// Find a run of adjacent words spelling `element`.
+    fn find_run(&self, element: &str) -> Option<&[String]> {
+        (0..self.words.len()).find_map(|start| {
+            let mut joined = String::new();
+            for end in start..self.words.len() {
+                joined.push_str(&self.words[end]);
+                if joined == element {
+                    return Some(&self.words[start..=end]);
+                }
+                if joined.len() >= element.len() {
+                    break;
+                }
+            }
+            None
+        })
+    }
+}
+
+/// Compare the dominant elements of two marks.
+///
+/// A direct match of the dominant terms carries no transformations.
+pub fn compare_dominant(query_text: &str, mark_text: &str) -> Option<DominantElementMatch> {
+    DominantProfile::new(query_text).compare(mark_text)
+}
+
+/// A query's dominant elements and canonical words, for comparison with
+/// many marks.
+#[derive(Debug, Clone)]
+pub struct DominantProfile {
+    dominant: Vec<WeightedTerm>,
+    canonical: Canonical,
+    dominant_canonical: Vec<Canonical>,
+    frequencies: Option<Arc<TermFrequencies>>,
+    max_terms: usize,
+    classes: Vec<NiceClass>,
+}
+
+impl DominantProfile {
+    /// Analyze the query mark, taking its longest word as dominant.
+    pub fn new(text: &str) -> Self {
+        Self::with_frequencies(text, None, 1)
+    }
+
+    /// Analyze the query mark, taking up to `max_terms` of its rarest
+    /// words as dominant if corpus frequencies are given.
+    pub fn with_frequencies(
+        text: &str,
+        frequencies: Option<Arc<TermFrequencies>>,
+        max_terms: usize,
+    ) -> Self {
+        Self::for_classes(text, &[], frequencies, max_terms)
+    }
+
+    /// Like `with_frequencies`, grading terms by their strength for the
+    /// goods in `classes`.
+    pub fn for_classes(
+        text: &str,
+        classes: &[NiceClass],
+        frequencies: Option<Arc<TermFrequencies>>,
+        max_terms: usize,
+    ) -> Self {
+        let dominant = dominant_terms(text, classes, frequencies.as_deref(), max_terms);
+        let dominant_canonical = dominant.iter().map(|t| Canonical::new(&t.term)).collect();
+        Self {
+            dominant,
+            canonical: Canonical::new(text),
+            dominant_canonical,
+            frequencies,
+            max_terms,
+            classes: classes.to_vec(),
+        }
+    }
+
+    /// Compare with a mark (see `compare_dominant`).
+    pub fn compare(&self, mark_text: &str) -> Option<DominantElementMatch> {
+        if self.dominant.is_empty() {
+            return None;
+        }
+        let mark_dominant = dominant_terms(
+            mark_text,
+            &self.classes,
+            self.frequencies.as_deref(),
+            self.max_terms,
+        );
+
+        for query_term in &self.dominant {
+            if let Some(mark_term) = mark_dominant.iter().find(|t| t.term == query_term.term) {
+                return Some(DominantElementMatch {
+                    element: mark_term.term.clone(),
+                    transformations: Vec::new(),
+                    weight: query_term.weight.min(mark_term.weight),
+                });
+            }
+        }
+        if mark_dominant.is_empty() {
+            return None;
+        }
+
+        let mark = Canonical::new(mark_text);
+        self.dominant
+            .iter()
+            .zip(&self.dominant_canonical)
+            .find_map(|(term, dominant)| find_element(dominant, &mark, term.weight))
+            .or_else(|| {
+                mark_dominant.iter().find_map(|term| {
+                    find_element(&Canonical::new(&term.term), &self.canonical, term.weight)
+                })
+            })
+    }
+}
+
+/// A mark's dominant terms: the rarest words with corpus frequencies,
+/// else the longest word, each weighted by its strength in `classes`.
+fn dominant_terms(
+    text: &str,
+    classes: &[NiceClass],
+    frequencies: Option<&TermFrequencies>,
+    max_terms: usize,
+) -> Vec<WeightedTerm> {
+    let strength = |term: &str| term_strength_in(term, classes).weight();
+    match frequencies {
+        Some(frequencies) => {
+            let mut terms = frequencies.dominant_terms(text, usize::MAX);
+            for term in &mut terms {
+                term.weight *= strength(&term.term);
+            }
+            terms.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+            terms.truncate(max_terms);
+            terms
+        }
+        None => extract_dominant_term_in(text, classes)
+            .map(|term| WeightedTerm {
+                weight: strength(&term),
+                term,
+            })
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Look for a dominant element in the other mark's words.
+fn find_element(
+    dominant: &Canonical,
+    other: &Canonical,
+    weight: f32,
+) -> Option<DominantElementMatch> {
+    let element = dominant.joined();
+    if element.len() < MIN_ELEMENT_LEN {
+        return None;
+    }
+
+    let run = other.find_run(&element)?;
+
+    let mut transformations = dominant.transformations.clone();
+    transformations.extend(
+        other
+            .transformations
+            .iter()
+            .filter(|t| run.iter().any(|word| t.ends_with(word.as_str())))
+            .cloned(),
+    );
+    if run.len() > 1 {
+        transformations.push(format!("join {} -> {}", run.join(" "), element));
+    }
+
+    Some(DominantElementMatch {
+        element,
+        transformations,
+        weight,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direct_match() {
+        let found = compare_dominant("ACME WIDGETS", "WIDGETS INC").unwrap();
+        assert_eq!(found.element, "WIDGETS");
+        assert!(found.transformations.is_empty());
+        assert!(compare_dominant("NIKE", "ADIDAS").is_none());
+    }
+
+    #[test]
+    fn test_stylized_run_together_match() {
+        let found = compare_dominant("KWIKPRINT", "QUICK PRINT SOLUTIONS").unwrap();
+        assert_eq!(found.element, "QUICKPRINT");
+        assert_eq!(
+            found.transformations,
+            vec![
+                "split KWIKPRINT -> KWIK PRINT",
+                "respell KWIK -> QUICK",
+                "join QUICK PRINT -> QUICKPRINT",
+            ]
+        );
+
+        // Either side may carry the run-together form
+        let found = compare_dominant("QUICK PRINT SOLUTIONS", "KWIKPRINT").unwrap();
+        assert_eq!(found.element, "QUICKPRINT");
+    }
+
+    #[test]
+    fn test_respelled_word_in_other_mark() {
+        let found = compare_dominant("KOOL BREEZE", "COOL BREEZE").unwrap();
+        assert_eq!(found.element, "BREEZE");
+
+        let found = compare_dominant("BRIGHTSMILE", "BRITE SMILE").unwrap();
+        assert_eq!(found.element, "BRIGHTSMILE");
+        assert!(found.transformations.contains(&"respell BRITE -> BRIGHT".to_string()));
+    }
+
+    #[test]
+    fn test_corpus_frequencies() {
+        let frequencies = Arc::new(TermFrequencies::from_marks([
+            "GLOBAL CORPORATION",
+            "ACME GLOBAL",
+            "GLOBAL SOLUTIONS",
+            "GLOBAL PARTNERS",
+        ]));
+
+        // The longest word is the common one, so plain extraction misses
+        // the shared coined term
+        assert!(compare_dominant("ZYX GLOBAL", "ZYX SOLUTIONS").is_none());
+
+        let profile = DominantProfile::with_frequencies("ZYX GLOBAL", Some(frequencies), 2);
+        let found = profile.compare("ZYX SOLUTIONS").unwrap();
+        assert_eq!(found.element, "ZYX");
+        assert_eq!(found.weight, 1.0);
+
+        // Sharing only the common word weighs less
+        let found = profile.compare("GLOBAL WIDGETS").unwrap();
+        assert_eq!(found.element, "GLOBAL");
+        assert!(found.weight < 0.7);
+    }
+
+    #[test]
+    fn test_weak_terms_for_classes() {
+        // BREWING is the longest word, but descriptive for beer
+        let profile = DominantProfile::for_classes("ZYLO BREWING", &[NiceClass::of(32)], None, 1);
+        assert!(profile.compare("ACME BREWING").is_none());
+        assert_eq!(profile.compare("ZYLO ALES").unwrap().element, "ZYLO");
+
+        // For software, BREWING is distinctive
+        let profile = DominantProfile::for_classes("ZYLO BREWING", &[NiceClass::of(9)], None, 1);
+        let found = profile.compare("ACME BREWING").unwrap();
+        assert_eq!(found.element, "BREWING");
+        assert_eq!(found.weight, 1.0);
+
+        // A mark made only of weak terms still matches, at reduced weight
+        let profile = DominantProfile::for_classes("CRAFT BREWING", &[NiceClass::of(32)], None, 1);
+        let found = profile.compare("BREWING").unwrap();
+        assert_eq!(found.element, "BREWING");
+        assert_eq!(found.weight, 0.5);
+    }
+}
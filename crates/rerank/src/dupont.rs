@@ -0,0 +1,414 @@
+//! Weighing hits on the du Pont factors.
+//!
+//! Maps the signals behind a hit onto the thirteen likelihood-of-confusion
+//! factors, for attorney work product: mark flags speak to the similarity
+//! of the marks, the two sides' classes and goods to the relatedness of
+//! the goods, the famous-mark list to fame, and so on. Goods and fame are
+//! read from the records rather than the flags, since an exact match
+//! carries little beyond `ExactMatch`.
+//! Factors that turn on evidence outside the register (conditions of
+//! sale, actual confusion) are listed as not assessed.
+
+use crate::RerankConfig;
+use ilegalflow_features::class_overlap;
+use ilegalflow_model::{
+    CandidateHit, DuPontAssessment, DuPontFactor, FactorFinding, FactorWeight, NiceClass,
+    RiskFlag, SearchQuery, TrademarkStatus,
+};
+
+/// Risk score from which potential confusion is called substantial.
+const SUBSTANTIAL_RISK: f32 = 0.7;
+
+/// Risk score below which potential confusion is called de minimis.
+const DE_MINIMIS_RISK: f32 = 0.3;
+
+/// Weigh a hit on every du Pont factor.
+pub fn assess(query: &SearchQuery, hit: &CandidateHit, config: &RerankConfig) -> DuPontAssessment {
+    let findings = DuPontFactor::ALL
+        .iter()
+        .map(|&factor| {
+            let (weight, finding) = match factor {
+                DuPontFactor::MarkSimilarity => marks(hit),
+                DuPontFactor::GoodsRelatedness => goods(query, hit, config),
+                DuPontFactor::TradeChannels => channels(query, hit),
+                DuPontFactor::Fame => fame(hit, config),
+                DuPontFactor::VarietyOfGoods => variety(hit),
+                DuPontFactor::ExtentOfConfusion => extent(hit),
+                DuPontFactor::OtherFacts => other(hit),
+                _ => (FactorWeight::NotAssessed, outside_register(factor).to_string()),
+            };
+            FactorFinding {
+                factor,
+                weight,
+                finding,
+                signals: signals(factor, hit),
+            }
+        })
+        .collect();
+    DuPontAssessment { findings }
+}
+
+/// The aspect of the marks a flag shows them alike in, for flags that
+/// compare the marks themselves.
+fn mark_aspect(flag: &RiskFlag) -> Option<&'static str> {
+    match flag {
+        RiskFlag::ExactMatch
+        | RiskFlag::ObfuscatedMatch { .. }
+        | RiskFlag::NumberWordMatch { .. }
+        | RiskFlag::CompoundMatch { .. }
+        | RiskFlag::FuzzyMatch { .. }
+        | RiskFlag::StringSimilarity { .. }
+        | RiskFlag::SyllableMatch { .. }
+        | RiskFlag::DesignCodeOverlap { .. }
+        | RiskFlag::DesignElementRisk { .. } => Some("appearance"),
+        RiskFlag::PhoneticMatch { .. } | RiskFlag::NearPhoneticMatch { .. } => Some("sound"),
+        RiskFlag::ForeignEquivalent { .. } => Some("connotation"),
+        RiskFlag::HouseMarkMatch { .. }
+        | RiskFlag::AcronymMatch { .. }
+        | RiskFlag::DominantTermMatch { .. } => Some("commercial impression"),
+        _ => None,
+    }
+}
+
+/// Whether a flag bears on a factor.
+fn bears_on(factor: DuPontFactor, flag: &RiskFlag) -> bool {
+    match factor {
+        DuPontFactor::MarkSimilarity => mark_aspect(flag).is_some(),
+        DuPontFactor::GoodsRelatedness => matches!(
+            flag,
+            RiskFlag::ClassOverlap { .. }
+                | RiskFlag::RelatedClasses { .. }
+                | RiskFlag::GoodsServicesSimilar { .. }
+        ),
+        DuPontFactor::TradeChannels => matches!(
+            flag,
+            RiskFlag::ClassOverlap { inferred: false, .. } | RiskFlag::GoodsServicesSimilar { .. }
+        ),
+        DuPontFactor::Fame => {
+            matches!(flag, RiskFlag::FamousMark { .. } | RiskFlag::DilutionRisk { .. })
+        }
+        DuPontFactor::OtherFacts => {
+            matches!(
+                flag,
+                RiskFlag::OwnerConflict { .. }
+                    | RiskFlag::CommonLawRisk { .. }
+                    | RiskFlag::RecentlyDead { .. }
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Labels of the flags a factor's finding rests on, without repeats.
+fn signals(factor: DuPontFactor, hit: &CandidateHit) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for flag in hit.flags.iter().filter(|f| bears_on(factor, f)) {
+        if !labels.iter().any(|l| l == flag.label()) {
+            labels.push(flag.label().to_string());
+        }
+    }
+    labels
+}
+
+/// Why a factor that needs outside evidence is not assessed.
+fn outside_register(factor: DuPontFactor) -> &'static str {
+    match factor {
+        DuPontFactor::PurchaserCare => {
+            "The conditions of sale and the care of buyers are not in the register."
+        }
+        DuPontFactor::SimilarMarksInUse => {
+            "Third-party marks in use on similar goods need a separate search."
+        }
+        DuPontFactor::ActualConfusion => "Evidence of actual confusion is outside the register.",
+        DuPontFactor::ConcurrentUse => "Any period of concurrent use is outside the register.",
+        DuPontFactor::MarketInterface => {
+            "Agreements or dealings between the parties are outside the register."
+        }
+        DuPontFactor::RightToExclude => {
+            "The applicant's right to exclude others is outside the register."
+        }
+        _ => "Not assessed.",
+    }
+}
+
+fn marks(hit: &CandidateHit) -> (FactorWeight, String) {
+    if hit.flags.contains(&RiskFlag::ExactMatch) {
+        return (FactorWeight::FavorsConfusion, "The marks are identical.".to_string());
+    }
+    let mut aspects: Vec<&str> = Vec::new();
+    for aspect in hit.flags.iter().filter_map(mark_aspect) {
+        if !aspects.contains(&aspect) {
+            aspects.push(aspect);
+        }
+    }
+    if aspects.is_empty() {
+        let finding = "No similarity in appearance, sound, connotation or commercial \
+                       impression was found.";
+        return (FactorWeight::AgainstConfusion, finding.to_string());
+    }
+    let finding = format!("The marks are similar in {}.", aspects.join(", "));
+    (FactorWeight::FavorsConfusion, finding)
+}
+
+fn join_classes(classes: &[NiceClass]) -> String {
+    let classes: Vec<String> = classes.iter().map(NiceClass::to_string).collect();
+    classes.join(", ")
+}
+
+fn goods(query: &SearchQuery, hit: &CandidateHit, config: &RerankConfig) -> (FactorWeight, String) {
+    let record = &hit.record;
+    let mut parts: Vec<String> = Vec::new();
+    let shared = class_overlap(&query.classes, &record.classes);
+    if !shared.is_empty() {
+        let inferred = query.classes_inferred || record.classes_inferred;
+        parts.push(format!(
+            "Both cover class{} {}{}.",
+            if shared.len() == 1 { "" } else { "es" },
+            join_classes(&shared),
+            if inferred { " (inferred from goods/services)" } else { "" }
+        ));
+    } else if let Some((a, b, _)) =
+        config.class_relatedness.related_pairs(&query.classes, &record.classes).first()
+    {
+        parts.push(format!("Classes {} and {} are related.", a, b));
+    }
+    for flag in &hit.flags {
+        if let RiskFlag::GoodsServicesSimilar { similarity } = flag {
+            parts.push(format!(
+                "The goods/services descriptions are {:.0}% similar.",
+                similarity * 100.0
+            ));
+        }
+    }
+    if !parts.is_empty() {
+        (FactorWeight::FavorsConfusion, parts.join(" "))
+    } else if !query.classes.is_empty() && !hit.record.classes.is_empty() {
+        let finding = format!(
+            "The classes share nothing and are not related (class {} against {}).",
+            join_classes(&query.classes),
+            join_classes(&hit.record.classes)
+        );
+        (FactorWeight::AgainstConfusion, finding)
+    } else {
+        let finding = "Classes are missing on one side, so the goods were not compared.";
+        (FactorWeight::NotAssessed, finding.to_string())
+    }
+}
+
+fn channels(query: &SearchQuery, hit: &CandidateHit) -> (FactorWeight, String) {
+    let record = &hit.record;
+    let overlap = !query.classes_inferred
+        && !record.classes_inferred
+        && !class_overlap(&query.classes, &record.classes).is_empty();
+    let similar = hit
+        .flags
+        .iter()
+        .any(|f| matches!(f, RiskFlag::GoodsServicesSimilar { .. }));
+    if overlap && similar {
+        let finding = "Absent restrictions in the identifications, overlapping goods are \
+                       presumed to move in the same channels of trade to the same buyers.";
+        (FactorWeight::FavorsConfusion, finding.to_string())
+    } else {
+        let finding = "Channels of trade are not in the register beyond the identifications.";
+        (FactorWeight::NotAssessed, finding.to_string())
+    }
+}
+
+fn fame(hit: &CandidateHit, config: &RerankConfig) -> (FactorWeight, String) {
+    let flagged = hit.flags.iter().find_map(|flag| match flag {
+        RiskFlag::FamousMark { mark, .. } => Some(mark),
+        RiskFlag::DilutionRisk { famous, .. } => Some(famous),
+        _ => None,
+    });
+    // The prior mark may be famous even when no flag says so
+    let famous = flagged.or_else(|| {
+        let listed = config.famous_marks.find_in(&hit.record.mark_text);
+        listed.into_iter().next().map(|famous| &famous.name)
+    });
+    match famous {
+        Some(mark) => (
+            FactorWeight::FavorsConfusion,
+            format!("{} is a famous mark, and fame widens its protection.", mark),
+        ),
+        None => (
+            FactorWeight::Neutral,
+            "No evidence of fame was found, which leaves the factor neutral.".to_string(),
+        ),
+    }
+}
+
+fn variety(hit: &CandidateHit) -> (FactorWeight, String) {
+    if hit.family.is_empty() {
+        let finding = "Only one filing of the prior mark was found.";
+        return (FactorWeight::NotAssessed, finding.to_string());
+    }
+    let mut classes: Vec<NiceClass> = hit
+        .family
+        .iter()
+        .chain(std::iter::once(&hit.record))
+        .flat_map(|record| record.classes.iter().copied())
+        .collect();
+    classes.sort_unstable();
+    classes.dedup();
+    let filings = match hit.family.len() {
+        1 => "1 other filing".to_string(),
+        n => format!("{} other filings", n),
+    };
+    if classes.len() > hit.record.classes.len() {
+        let finding = format!(
+            "The owner has {} of the mark, covering classes {}.",
+            filings,
+            join_classes(&classes)
+        );
+        (FactorWeight::FavorsConfusion, finding)
+    } else {
+        let finding = format!("The owner has {} of the mark, in the same classes.", filings);
+        (FactorWeight::Neutral, finding)
+    }
+}
+
+fn extent(hit: &CandidateHit) -> (FactorWeight, String) {
+    let (weight, extent) = if hit.risk_score >= SUBSTANTIAL_RISK {
+        (FactorWeight::FavorsConfusion, "substantial")
+    } else if hit.risk_score < DE_MINIMIS_RISK {
+        (FactorWeight::AgainstConfusion, "de minimis")
+    } else {
+        (FactorWeight::Neutral, "moderate")
+    };
+    let finding = format!(
+        "A risk score of {:.2} suggests {} potential confusion.",
+        hit.risk_score, extent
+    );
+    (weight, finding)
+}
+
+fn other(hit: &CandidateHit) -> (FactorWeight, String) {
+    let use_found = hit.flags.iter().find_map(|flag| match flag {
+        RiskFlag::CommonLawRisk {
+            source, evidence, ..
+        } => Some(format!("unregistered use was found ({}, {})", evidence, source)),
+        _ => None,
+    });
+    let recently_dead = hit.flags.iter().find_map(|flag| match flag {
+        RiskFlag::RecentlyDead { months } => Some(*months),
+        _ => None,
+    });
+    if let (None, Some(months)) = (&use_found, recently_dead) {
+        let finding = format!(
+            "The prior filing died {} months ago and may be revived or still carry goodwill.",
+            months
+        );
+        return (FactorWeight::Neutral, finding);
+    }
+    let dead = hit.record.status == TrademarkStatus::Dead;
+    match (use_found, dead) {
+        (Some(found), false) => (FactorWeight::FavorsConfusion, capitalize(&found) + "."),
+        (Some(found), true) => (
+            FactorWeight::Neutral,
+            format!("The prior filing is dead, but {}.", found),
+        ),
+        (None, true) => (
+            FactorWeight::AgainstConfusion,
+            "The prior registration or application is dead.".to_string(),
+        ),
+        (None, false) => (FactorWeight::NotAssessed, "No other facts were found.".to_string()),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rerank, RerankConfig};
+    use ilegalflow_model::TrademarkRecord;
+
+    fn record(serial: &str, mark: &str, classes: Vec<u16>) -> TrademarkRecord {
+        let mut record = TrademarkRecord::new(serial, mark);
+        record.classes = NiceClass::of_all(&classes);
+        record
+    }
+
+    #[test]
+    fn test_assess() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let candidates = || vec![(record("001", "NYKE", vec![25]), 1.0)];
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert!(hits[0].dupont.is_none());
+
+        let config = RerankConfig {
+            dupont_assessment: true,
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        let assessment = hits[0].dupont.as_ref().unwrap();
+        assert_eq!(assessment.findings.len(), 13);
+
+        let marks = assessment.finding(DuPontFactor::MarkSimilarity).unwrap();
+        assert_eq!(marks.weight, FactorWeight::FavorsConfusion);
+        assert!(marks.finding.contains("sound"), "{}", marks.finding);
+        assert!(marks.signals.contains(&"Sounds Similar".to_string()), "{:?}", marks.signals);
+        let goods = assessment.finding(DuPontFactor::GoodsRelatedness).unwrap();
+        assert_eq!(goods.weight, FactorWeight::FavorsConfusion);
+        assert_eq!(goods.finding, "Both cover class 25.");
+        let fame = assessment.finding(DuPontFactor::Fame).unwrap();
+        assert_eq!(fame.weight, FactorWeight::Neutral);
+        let confusion = assessment.finding(DuPontFactor::ActualConfusion).unwrap();
+        assert_eq!(confusion.weight, FactorWeight::NotAssessed);
+    }
+
+    #[test]
+    fn test_assess_against() {
+        let query = SearchQuery::new("ZEPHYRA").with_classes(NiceClass::of_all(&[9]));
+        let mut dead = record("002", "QUORLAND", vec![1]);
+        dead.status = TrademarkStatus::Dead;
+        let config = RerankConfig {
+            dupont_assessment: true,
+            ..Default::default()
+        };
+        let hits = rerank(&query, vec![(dead, 1.0)], &config);
+        let assessment = hits[0].dupont.as_ref().unwrap();
+
+        for factor in [
+            DuPontFactor::MarkSimilarity,
+            DuPontFactor::GoodsRelatedness,
+            DuPontFactor::ExtentOfConfusion,
+            DuPontFactor::OtherFacts,
+        ] {
+            let finding = assessment.finding(factor).unwrap();
+            assert_eq!(finding.weight, FactorWeight::AgainstConfusion, "{:?}", factor);
+        }
+        assert_eq!(assessment.count(FactorWeight::FavorsConfusion), 0);
+    }
+
+    #[test]
+    fn test_assess_exact_match() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let config = RerankConfig {
+            dupont_assessment: true,
+            ..Default::default()
+        };
+        let hits = rerank(&query, vec![(record("001", "NIKE", vec![25]), 1.0)], &config);
+        let assessment = hits[0].dupont.as_ref().unwrap();
+
+        let goods = assessment.finding(DuPontFactor::GoodsRelatedness).unwrap();
+        assert_eq!(goods.weight, FactorWeight::FavorsConfusion);
+        assert_eq!(goods.finding, "Both cover class 25.");
+        let fame = assessment.finding(DuPontFactor::Fame).unwrap();
+        assert_eq!(fame.weight, FactorWeight::FavorsConfusion);
+        assert!(fame.finding.starts_with("NIKE is a famous mark"), "{}", fame.finding);
+
+        // Fame of the prior mark does not depend on the query
+        let query = SearchQuery::new("NIKEE").with_classes(NiceClass::of_all(&[9]));
+        let hits = rerank(&query, vec![(record("002", "NIKE", vec![25]), 1.0)], &config);
+        let assessment = hits[0].dupont.as_ref().unwrap();
+        let fame = assessment.finding(DuPontFactor::Fame).unwrap();
+        assert_eq!(fame.weight, FactorWeight::FavorsConfusion);
+    }
+}
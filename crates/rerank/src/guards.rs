@@ -0,0 +1,216 @@
+//! Per-rule applicability guards.
+//!
+//! Very short or non-alphabetic marks make some rules meaningless: a
+//! 2-letter mark is within edit distance 2 of every other 2-letter mark,
+//! and phonetic codes of `7-11` say nothing about pronunciation. Each
+//! rule has a guard both marks must pass before the rule runs; skipped
+//! rules are reported as warnings rather than silently dropped. Guards
+//! are off by default; `RuleGuards::short_marks` turns them on.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A rule that can be guarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    Phonetic,
+    Fuzzy,
+    Similarity,
+    Ngram,
+    Dominant,
+}
+
+impl Rule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Phonetic => "phonetic",
+            Self::Fuzzy => "fuzzy",
+            Self::Similarity => "similarity",
+            Self::Ngram => "ngram",
+            Self::Dominant => "dominant",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Requirements a mark must meet for a rule to apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleGuard {
+    /// Minimum mark length, ignoring spaces
+    pub min_mark_len: usize,
+    /// Minimum length of the mark's longest word
+    pub min_token_len: usize,
+    /// Require letters (and spaces) only
+    pub alphabetic_only: bool,
+}
+
+impl RuleGuard {
+    /// Check a normalized mark; the error describes the failure.
+    pub fn check(&self, normalized: &str) -> Result<(), String> {
+        let len = normalized.chars().filter(|c| !c.is_whitespace()).count();
+        if len < self.min_mark_len {
+            return Err(format!("shorter than {} characters", self.min_mark_len));
+        }
+
+        let longest = normalized
+            .split_whitespace()
+            .map(|word| word.chars().count())
+            .max()
+            .unwrap_or(0);
+        if longest < self.min_token_len {
+            return Err(format!("has no word of {} characters", self.min_token_len));
+        }
+
+        if self.alphabetic_only && !normalized.chars().all(|c| c.is_alphabetic() || c == ' ') {
+            return Err("is not alphabetic".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Check the query and candidate marks, query first.
+    pub fn check_pair(&self, query: &str, mark: &str) -> Result<(), String> {
+        self.check(query)
+            .map_err(|reason| format!("query mark {}", reason))?;
+        self.check(mark)
+            .map_err(|reason| format!("candidate mark {}", reason))
+    }
+}
+
+/// Guards for every rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleGuards {
+    pub phonetic: RuleGuard,
+    pub fuzzy: RuleGuard,
+    pub similarity: RuleGuard,
+    pub ngram: RuleGuard,
+    pub dominant: RuleGuard,
+}
+
+impl Default for RuleGuards {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl RuleGuards {
+    /// Guards for marks too short or too symbolic for a rule to mean
+    /// anything: spelling rules need four characters, and phonetic rules
+    /// four letters.
+    pub fn short_marks() -> Self {
+        let min_len = |min_mark_len| RuleGuard {
+            min_mark_len,
+            ..Default::default()
+        };
+        Self {
+            phonetic: RuleGuard {
+                min_mark_len: 4,
+                alphabetic_only: true,
+                ..Default::default()
+            },
+            fuzzy: min_len(4),
+            similarity: min_len(4),
+            ngram: min_len(4),
+            dominant: RuleGuard::default(),
+        }
+    }
+
+    /// No guards: every rule always applies.
+    pub fn none() -> Self {
+        Self {
+            phonetic: RuleGuard::default(),
+            fuzzy: RuleGuard::default(),
+            similarity: RuleGuard::default(),
+            ngram: RuleGuard::default(),
+            dominant: RuleGuard::default(),
+        }
+    }
+
+    /// Get the guard for a rule.
+    pub fn get(&self, rule: Rule) -> &RuleGuard {
+        match rule {
+            Rule::Phonetic => &self.phonetic,
+            Rule::Fuzzy => &self.fuzzy,
+            Rule::Similarity => &self.similarity,
+            Rule::Ngram => &self.ngram,
+            Rule::Dominant => &self.dominant,
+        }
+    }
+}
+
+/// Rules skipped by a guard during a re-rank.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleWarning {
+    /// The skipped rule
+    pub rule: Rule,
+    /// Why the guard failed
+    pub reason: String,
+    /// Number of candidates the rule was skipped for
+    pub skipped: usize,
+}
+
+impl fmt::Display for RuleWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} rule skipped for {} candidate(s): {}",
+            self.rule, self.skipped, self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_checks() {
+        let guard = RuleGuard {
+            min_mark_len: 4,
+            min_token_len: 3,
+            alphabetic_only: true,
+        };
+        assert_eq!(guard.check("NIKE"), Ok(()));
+        assert_eq!(
+            guard.check("ABC"),
+            Err("shorter than 4 characters".to_string())
+        );
+        assert_eq!(
+            guard.check("A B C D"),
+            Err("has no word of 3 characters".to_string())
+        );
+        assert_eq!(
+            guard.check("7 ELEVEN"),
+            Err("is not alphabetic".to_string())
+        );
+        assert_eq!(guard.check("CAFÉ"), Ok(()));
+        assert_eq!(RuleGuard::default().check(""), Ok(()));
+    }
+
+    #[test]
+    fn test_check_pair() {
+        let guards = RuleGuards::short_marks();
+        assert_eq!(guards.get(Rule::Fuzzy).check_pair("NIKE", "NYKE"), Ok(()));
+        assert_eq!(
+            guards.get(Rule::Fuzzy).check_pair("AB", "NYKE"),
+            Err("query mark shorter than 4 characters".to_string())
+        );
+        assert_eq!(
+            guards.get(Rule::Phonetic).check_pair("NIKE", "N1KE"),
+            Err("candidate mark is not alphabetic".to_string())
+        );
+        assert_eq!(
+            RuleGuards::none().get(Rule::Phonetic).check_pair("A", "1"),
+            Ok(())
+        );
+        assert_eq!(RuleGuards::default(), RuleGuards::none());
+    }
+}
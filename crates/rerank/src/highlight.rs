@@ -0,0 +1,252 @@
+//! Character spans behind flags, for highlighting.
+//!
+//! Spans are found in the normalized text the detectors compared, then
+//! mapped back to char offsets in the marks as entered, so the UI can
+//! highlight them in place. A mark compared through its transliteration
+//! gets no spans, since its characters do not line up with the text.
+
+use crate::context::QueryContext;
+use ilegalflow_features::{latin_mark_text, normalize_text};
+use ilegalflow_model::{MatchSpans, RiskFlag, TrademarkRecord};
+use std::ops::Range;
+
+/// Spans for the exact, fuzzy, dominant-term and acronym flags of a candidate.
+pub fn highlights(
+    context: &QueryContext,
+    record: &TrademarkRecord,
+    flags: &[RiskFlag],
+) -> Vec<MatchSpans> {
+    let highlighted = |flag: &&RiskFlag| {
+        matches!(
+            flag,
+            RiskFlag::ExactMatch
+                | RiskFlag::FuzzyMatch { .. }
+                | RiskFlag::DominantTermMatch { .. }
+                | RiskFlag::AcronymMatch { .. }
+        )
+    };
+    if !flags.iter().any(|flag| highlighted(&flag)) {
+        return Vec::new();
+    }
+
+    let query = Mapped::new(&context.mark_text, &context.normalized);
+    let mark_normalized = normalize_text(&latin_mark_text(record)).into_owned();
+    let mark = Mapped::new(&record.mark_text, &mark_normalized);
+
+    flags
+        .iter()
+        .filter(highlighted)
+        .filter_map(|flag| {
+            let (in_query, in_mark) = match flag {
+                RiskFlag::ExactMatch => (all(&query), all(&mark)),
+                RiskFlag::FuzzyMatch { .. } => match (&query, &mark) {
+                    (Some(q), Some(m)) => {
+                        let (in_query, in_mark) = aligned(&q.chars, &m.chars);
+                        (q.ranges(&in_query), m.ranges(&in_mark))
+                    }
+                    _ => (Vec::new(), Vec::new()),
+                },
+                RiskFlag::DominantTermMatch { term, .. } => {
+                    (find(&query, term), find(&mark, term))
+                }
+                // Each side holds either the acronym or its expansion
+                RiskFlag::AcronymMatch { acronym, expansion } => {
+                    let acronym = normalize_text(acronym);
+                    let expansion = normalize_text(expansion);
+                    let side = |mapped: &Option<Mapped>| {
+                        let found = find(mapped, &acronym);
+                        if found.is_empty() {
+                            find(mapped, &expansion)
+                        } else {
+                            found
+                        }
+                    };
+                    (side(&query), side(&mark))
+                }
+                _ => return None,
+            };
+            (!in_query.is_empty() || !in_mark.is_empty()).then(|| MatchSpans {
+                flag: flag.label().to_string(),
+                ranges_in_query: in_query,
+                ranges_in_mark: in_mark,
+            })
+        })
+        .collect()
+}
+
+/// Normalized text with the source char each character came from.
+struct Mapped {
+    chars: Vec<char>,
+    source: Vec<usize>,
+    /// Whether each source char is a letter or digit
+    alphanumeric: Vec<bool>,
+}
+
+impl Mapped {
+    /// Normalize `text` as `normalize_text` does, keeping the mapping;
+    /// `None` unless the result is `normalized`.
+    fn new(text: &str, normalized: &str) -> Option<Self> {
+        let mut chars = Vec::new();
+        let mut source = Vec::new();
+        let mut alphanumeric = Vec::new();
+        let mut pending_space = None;
+        for (i, c) in text.chars().enumerate() {
+            alphanumeric.push(c.is_alphanumeric());
+            for upper in c.to_uppercase() {
+                if upper.is_alphanumeric() {
+                    if let Some(space) = pending_space.take().filter(|_| !chars.is_empty()) {
+                        chars.push(' ');
+                        source.push(space);
+                    }
+                    chars.push(upper);
+                    source.push(i);
+                } else if upper.is_whitespace() {
+                    pending_space = Some(i);
+                }
+            }
+        }
+        chars.iter().copied().eq(normalized.chars()).then_some(Self {
+            chars,
+            source,
+            alphanumeric,
+        })
+    }
+
+    /// Source ranges covering normalized positions (ascending). Ranges
+    /// separated only by punctuation or spaces are merged.
+    fn ranges(&self, positions: &[usize]) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for &position in positions {
+            let i = self.source[position];
+            match ranges.last_mut() {
+                Some(last) if !self.alphanumeric[last.end.min(i)..i].contains(&true) => {
+                    last.end = last.end.max(i + 1);
+                }
+                _ => ranges.push(i..i + 1),
+            }
+        }
+        ranges
+    }
+}
+
+fn all(mapped: &Option<Mapped>) -> Vec<Range<usize>> {
+    match mapped {
+        Some(mapped) => mapped.ranges(&(0..mapped.chars.len()).collect::<Vec<_>>()),
+        None => Vec::new(),
+    }
+}
+
+/// Where a normalized term appears, ignoring spaces (QUICKPRINT in
+/// QUICK PRINT).
+fn find(mapped: &Option<Mapped>, term: &str) -> Vec<Range<usize>> {
+    let Some(mapped) = mapped else {
+        return Vec::new();
+    };
+    let compact: Vec<usize> =
+        (0..mapped.chars.len()).filter(|&i| mapped.chars[i] != ' ').collect();
+    let term: Vec<char> = term.chars().filter(|c| *c != ' ').collect();
+    if term.is_empty() || term.len() > compact.len() {
+        return Vec::new();
+    }
+    (0..=compact.len() - term.len())
+        .find(|&start| {
+            term.iter().enumerate().all(|(k, c)| mapped.chars[compact[start + k]] == *c)
+        })
+        .map(|start| mapped.ranges(&compact[start..start + term.len()]))
+        .unwrap_or_default()
+}
+
+/// Positions of the characters a minimal edit script keeps unchanged in
+/// each text.
+fn aligned(a: &[char], b: &[char]) -> (Vec<usize>, Vec<usize>) {
+    let (n, m) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitute = dp[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            dp[i][j] = substitute.min(dp[i - 1][j] + 1).min(dp[i][j - 1] + 1);
+        }
+    }
+
+    let (mut in_a, mut in_b) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] && dp[i][j] == dp[i - 1][j - 1] {
+            in_a.push(i - 1);
+            in_b.push(j - 1);
+            i -= 1;
+            j -= 1;
+        } else if dp[i][j] == dp[i - 1][j - 1] + 1 {
+            i -= 1;
+            j -= 1;
+        } else if dp[i][j] == dp[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    in_a.reverse();
+    in_b.reverse();
+    (in_a, in_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RerankConfig;
+    use ilegalflow_model::SearchQuery;
+
+    fn spans(query: &str, mark: &str, flag: RiskFlag) -> MatchSpans {
+        let context = QueryContext::new(&SearchQuery::new(query), &RerankConfig::default());
+        let record = TrademarkRecord::new("001", mark);
+        highlights(&context, &record, &[flag]).remove(0)
+    }
+
+    #[test]
+    fn test_exact_and_fuzzy_spans() {
+        let exact = spans("Nike", "nike, inc.", RiskFlag::ExactMatch);
+        assert_eq!(exact.flag, "Exact Match");
+        assert_eq!(exact.ranges_in_query, vec![0..4]);
+        assert_eq!(exact.ranges_in_mark, vec![0..9]);
+
+        // NYKEE keeps N, K and one E of NIKE
+        let fuzzy = spans("NIKE", "nykee", RiskFlag::FuzzyMatch { distance: 2 });
+        assert_eq!(fuzzy.ranges_in_query, [0..1, 2..4]);
+        assert_eq!(fuzzy.ranges_in_mark, [0..1, 2..3, 4..5]);
+    }
+
+    #[test]
+    fn test_dominant_spans() {
+        let flag = RiskFlag::DominantTermMatch {
+            term: "QUICKPRINT".to_string(),
+            transformations: Vec::new(),
+        };
+        let dominant = spans("QuickPrint", "Acme Quick-Print Solutions", flag);
+        assert_eq!(dominant.ranges_in_query, vec![0..10]);
+        assert_eq!(dominant.ranges_in_mark, vec![5..16]);
+
+        // Transliterated marks do not line up with their text
+        let context = QueryContext::new(&SearchQuery::new("NIKE"), &RerankConfig::default());
+        let record = TrademarkRecord::new("001", "НАЙК");
+        let spans = highlights(&context, &record, &[RiskFlag::ExactMatch]);
+        assert!(spans.iter().all(|s| s.ranges_in_mark.is_empty()));
+    }
+
+    #[test]
+    fn test_acronym_spans() {
+        let flag = RiskFlag::AcronymMatch {
+            acronym: "I.B.M.".to_string(),
+            expansion: "International Business Machines".to_string(),
+        };
+        let acronym = spans("IBM", "International Business Machines Corp", flag);
+        assert_eq!(acronym.flag, "Acronym Match");
+        assert_eq!(acronym.ranges_in_query, vec![0..3]);
+        assert_eq!(acronym.ranges_in_mark, vec![0..31]);
+    }
+}
@@ -0,0 +1,60 @@
+//! House marks stripped before comparing, globally and per tenant.
+//!
+//! A tenant's own house marks (its company name, umbrella brands) wrap
+//! many of the marks it searches and watches. Listing them lets ACME NIKE
+//! SERIES compare with NIKE for that tenant without stripping ACME for
+//! everyone else.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// House marks in a scoring profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HouseMarks {
+    /// House marks stripped for every tenant
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marks: Vec<String>,
+    /// House marks stripped for one tenant, on top of `marks`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tenants: BTreeMap<String, Vec<String>>,
+}
+
+impl HouseMarks {
+    /// Strip a house mark for every tenant.
+    pub fn with_mark(mut self, mark: impl Into<String>) -> Self {
+        self.marks.push(mark.into());
+        self
+    }
+
+    /// Strip a house mark for one tenant.
+    pub fn with_tenant_mark(mut self, tenant: impl Into<String>, mark: impl Into<String>) -> Self {
+        self.tenants.entry(tenant.into()).or_default().push(mark.into());
+        self
+    }
+
+    /// The house marks stripped for a tenant, tenant marks first.
+    pub fn resolve(&self, tenant: Option<&str>) -> Vec<String> {
+        tenant
+            .and_then(|t| self.tenants.get(t))
+            .into_iter()
+            .flatten()
+            .chain(&self.marks)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let marks = HouseMarks::default()
+            .with_mark("GLOBEX")
+            .with_tenant_mark("acme", "ACME");
+        assert_eq!(marks.resolve(Some("acme")), vec!["ACME", "GLOBEX"]);
+        assert_eq!(marks.resolve(Some("initech")), vec!["GLOBEX"]);
+        assert_eq!(marks.resolve(None), vec!["GLOBEX"]);
+    }
+}
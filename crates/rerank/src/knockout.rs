@@ -0,0 +1,236 @@
+//! Knockout screening.
+//!
+//! A knockout search only asks whether an obvious conflict exists, so it
+//! runs a reduced set of the registered detectors (exact, disguised,
+//! number-word, phonetic, and near-exact spelling, plus class overlap for
+//! ranking) and stops at the first candidate in the HIGH tier. The set is
+//! named in `knockout.detectors`, so detectors removed from the registry
+//! are skipped here too. Exact matches are checked first, so an
+//! earlier sound-alike cannot end the screen before them; the rest follow
+//! in retrieval order, best lexical matches first.
+
+use crate::context::QueryContext;
+use crate::guards::RuleWarning;
+use crate::highlight;
+use crate::{compute_risk, prune, RerankConfig};
+use ilegalflow_features::{latin_mark_text, normalize_text};
+use ilegalflow_model::{CandidateHit, RiskFlag, SearchQuery, TrademarkRecord};
+use serde::{Deserialize, Serialize};
+
+/// Registered detectors a knockout screen runs, by name.
+pub const DEFAULT_DETECTORS: [&str; 5] =
+    ["obfuscation", "number_words", "phonetic", "fuzzy", "class"];
+
+/// Settings for knockout screening.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KnockoutConfig {
+    /// Names of the registered detectors to run; others, and detectors
+    /// removed from the registry, are skipped
+    pub detectors: Vec<String>,
+    /// Maximum edit distance counted as near-exact
+    pub max_edit_distance: usize,
+    /// Flag severity that puts a hit in the HIGH tier and ends the
+    /// screen (matches the HIGH RISK summary threshold)
+    pub high_severity: f32,
+}
+
+impl Default for KnockoutConfig {
+    fn default() -> Self {
+        Self {
+            detectors: DEFAULT_DETECTORS.iter().map(|name| name.to_string()).collect(),
+            max_edit_distance: 1,
+            high_severity: 0.8,
+        }
+    }
+}
+
+/// Result of a knockout screen.
+#[derive(Debug, Clone)]
+pub struct KnockoutOutcome {
+    /// Risk-ranked hits among the candidates checked
+    pub hits: Vec<CandidateHit>,
+    /// Rules skipped by guards, one entry per rule and reason
+    pub warnings: Vec<RuleWarning>,
+    /// True if a HIGH tier hit ended the screen before every candidate
+    /// was checked
+    pub stopped_early: bool,
+    /// Hits dropped by `min_risk_score` or `max_results`
+    pub pruned: usize,
+}
+
+/// Screen candidates for exact and near-exact conflicts.
+pub fn knockout(
+    query: &SearchQuery,
+    candidates: Vec<(TrademarkRecord, f32)>,
+    config: &RerankConfig,
+) -> KnockoutOutcome {
+    knockout_with_context(&QueryContext::new(query, config), query, candidates, config)
+}
+
+/// Screen candidates using query values derived beforehand (see
+/// `QueryContext`).
+pub fn knockout_with_context(
+    context: &QueryContext,
+    query: &SearchQuery,
+    candidates: Vec<(TrademarkRecord, f32)>,
+    config: &RerankConfig,
+) -> KnockoutOutcome {
+    let query_normalized = context.normalized.as_str();
+    let total = candidates.len();
+    let mut hits = Vec::new();
+    let mut warnings: Vec<RuleWarning> = Vec::new();
+
+    // Near spellings count within the knockout's own distance, and only
+    // identical sounds
+    let budget = &config.knockout;
+    let screen = RerankConfig {
+        max_edit_distance: budget.max_edit_distance,
+        max_phoneme_distance: 0.0,
+        ..config.clone()
+    };
+
+    let mut candidates = candidates;
+    candidates.sort_by_key(|(record, _)| {
+        query_normalized.is_empty() || normalize_text(&latin_mark_text(record)) != query_normalized
+    });
+
+    for (record, retrieval_score) in candidates {
+        let risk = compute_risk(context, query, None, &record, &screen, Some(&budget.detectors));
+        for (rule, reason) in risk.skipped {
+            match warnings.iter_mut().find(|w| w.rule == rule && w.reason == reason) {
+                Some(warning) => warning.skipped += 1,
+                None => warnings.push(RuleWarning {
+                    rule,
+                    reason,
+                    skipped: 1,
+                }),
+            }
+        }
+
+        // Class overlap only matters alongside a mark match
+        let class_only = risk.flags.iter().all(|f| matches!(f, RiskFlag::ClassOverlap { .. }));
+        let (flags, score) = if class_only {
+            (Vec::new(), 0.0)
+        } else {
+            (risk.flags, risk.score)
+        };
+        let high = flags
+            .iter()
+            .any(|flag| config.severities.severity(flag) >= config.knockout.high_severity);
+        let highlights = highlight::highlights(context, &record, &flags);
+        hits.push(CandidateHit {
+            record,
+            retrieval_score,
+            risk_score: score,
+            risk_probability: None,
+            flags,
+            review: None,
+            status_adjustment: None,
+            links: None,
+            family: Vec::new(),
+            dupont: None,
+            highlights,
+        });
+        if high {
+            break;
+        }
+    }
+
+    let stopped_early = hits.len() < total;
+    hits.sort_by(config.hit_ordering);
+    let pruned = prune(&mut hits, config);
+
+    KnockoutOutcome {
+        hits,
+        warnings,
+        stopped_early,
+        pruned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ilegalflow_model::NiceClass;
+
+    fn candidate(serial: &str, mark: &str) -> (TrademarkRecord, f32) {
+        let mut record = TrademarkRecord::new(serial, mark);
+        record.classes = NiceClass::of_all(&[25]);
+        (record, 1.0)
+    }
+
+    #[test]
+    fn test_stops_at_high_tier_hit() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let candidates = vec![
+            candidate("001", "NIKKOR"),
+            candidate("002", "NYKE"),
+            candidate("003", "NIKE"),
+        ];
+
+        let outcome = knockout(&query, candidates, &RerankConfig::default());
+        // The exact match is checked first and ends the screen, even
+        // though NYKE (phonetic, HIGH tier) was retrieved before it
+        assert!(outcome.stopped_early);
+        assert_eq!(outcome.hits.len(), 1);
+        assert_eq!(outcome.hits[0].record.serial_number, "003");
+
+        // NYKE sounds identical, so NIKKOR is never checked
+        let candidates = vec![
+            candidate("002", "NYKE"),
+            candidate("001", "NIKKOR"),
+            candidate("004", "NIKON"),
+        ];
+        let outcome = knockout(&query, candidates, &RerankConfig::default());
+        assert!(outcome.stopped_early);
+        assert_eq!(outcome.hits.len(), 1);
+        assert_eq!(outcome.hits[0].record.serial_number, "002");
+
+        // With sound-alikes below the HIGH tier, the screen goes on
+        let mut config = RerankConfig::default();
+        config.severities.phonetic_match = 0.6;
+        let candidates = vec![candidate("002", "NYKE"), candidate("001", "NIKKOR")];
+        let outcome = knockout(&query, candidates, &config);
+        assert_eq!(outcome.hits.len(), 2);
+        assert!(!outcome.stopped_early);
+    }
+
+    #[test]
+    fn test_reduced_rule_set() {
+        let query = SearchQuery::new("QUICKPRINT").with_classes(NiceClass::of_all(&[25]));
+        let candidates = vec![
+            candidate("001", "KWIK PRINT"),
+            candidate("002", "QUIKPRINTS"),
+        ];
+
+        // Neither is within one edit, and the similarity and
+        // dominant-term rules do not run
+        let outcome = knockout(&query, candidates, &RerankConfig::default());
+        assert!(outcome
+            .hits
+            .iter()
+            .all(|hit| !hit.flags.iter().any(|flag| matches!(
+                flag,
+                RiskFlag::FuzzyMatch { .. }
+                    | RiskFlag::DominantTermMatch { .. }
+                    | RiskFlag::StringSimilarity { .. }
+            ))));
+    }
+
+    #[test]
+    fn test_follows_detector_registry() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let config = RerankConfig {
+            detectors: crate::detectors::DetectorRegistry::default().without("phonetic"),
+            ..RerankConfig::default()
+        };
+
+        // Without the phonetic detector, NYKE is only a near spelling
+        let outcome = knockout(&query, vec![candidate("002", "NYKE")], &config);
+        assert!(!outcome.hits[0]
+            .flags
+            .iter()
+            .any(|flag| matches!(flag, RiskFlag::PhoneticMatch { .. })));
+    }
+}
@@ -0,0 +1,298 @@
+//! Learned second-stage scoring.
+//!
+//! The heuristic scorer sums flag weights. A model trained on reviewed
+//! outcomes can weigh the same evidence better, so when one is configured
+//! each non-exact candidate's features (edit distance, phonetic flags,
+//! class overlap, goods similarity, retrieval and heuristic scores, and
+//! the status weight) are fed to it and its probability replaces the
+//! heuristic score, status multiplier and calibration. Models are
+//! logistic (linear) or gradient-boosted trees with a logistic link, so
+//! their output is a calibrated probability of conflict.
+//!
+//! Models are JSON files:
+//!
+//! ```json
+//! {"type": "linear", "bias": -3.0, "weights": {"phonetic_match": 2.5}}
+//! {"type": "gbdt", "base_score": -1.0, "trees": [
+//!     {"feature": "edit_distance", "threshold": 2.0,
+//!      "left": {"leaf": 1.2}, "right": {"leaf": -0.8}}
+//! ]}
+//! ```
+
+use crate::context::QueryContext;
+use ilegalflow_features::{edit_distance_bounded, latin_mark_text, normalize_text};
+use ilegalflow_model::{RiskFlag, TrademarkRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Features a model can read, in vector order.
+pub const FEATURES: [&str; 8] = [
+    "edit_distance",
+    "phonetic_match",
+    "near_phonetic",
+    "class_overlap",
+    "goods_similarity",
+    "retrieval_score",
+    "heuristic_score",
+    "status_multiplier",
+];
+
+/// Edit distances beyond this are recorded as this.
+const MAX_EDIT_DISTANCE: usize = 10;
+
+/// Errors loading a model.
+#[derive(Debug, Error)]
+pub enum ModelError {
+    #[error("Failed to read model {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid model: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Unknown model feature '{0}'")]
+    UnknownFeature(String),
+}
+
+/// A candidate's features, in `FEATURES` order.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeatureVector(pub [f32; FEATURES.len()]);
+
+impl FeatureVector {
+    /// Features of a candidate from its heuristic assessment.
+    pub fn extract(
+        context: &QueryContext,
+        record: &TrademarkRecord,
+        flags: &[RiskFlag],
+        heuristic_score: f32,
+        retrieval_score: f32,
+        status_multiplier: f32,
+    ) -> Self {
+        let mark = normalize_text(&latin_mark_text(record)).into_owned();
+        let distance = edit_distance_bounded(&context.normalized, &mark, MAX_EDIT_DISTANCE)
+            .unwrap_or(MAX_EDIT_DISTANCE);
+
+        let mut features = Self::default();
+        features.set("edit_distance", distance as f32);
+        features.set("retrieval_score", retrieval_score);
+        features.set("heuristic_score", heuristic_score);
+        features.set("status_multiplier", status_multiplier);
+        for flag in flags {
+            match flag {
+                RiskFlag::PhoneticMatch { .. } => features.set("phonetic_match", 1.0),
+                RiskFlag::NearPhoneticMatch { similarity, .. } => {
+                    features.set("near_phonetic", *similarity)
+                }
+                RiskFlag::ClassOverlap { classes, .. } => {
+                    features.set("class_overlap", classes.len() as f32)
+                }
+                RiskFlag::GoodsServicesSimilar { similarity } => {
+                    features.set("goods_similarity", *similarity)
+                }
+                _ => {}
+            }
+        }
+        features
+    }
+
+    /// Value of a named feature.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        FEATURES.iter().position(|f| *f == name).map(|i| self.0[i])
+    }
+
+    fn set(&mut self, name: &str, value: f32) {
+        if let Some(i) = FEATURES.iter().position(|f| *f == name) {
+            self.0[i] = value;
+        }
+    }
+}
+
+/// A node of a regression tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TreeNode {
+    Leaf {
+        leaf: f32,
+    },
+    /// Goes left when the feature is below the threshold
+    Split {
+        feature: String,
+        threshold: f32,
+        left: Box<TreeNode>,
+        right: Box<TreeNode>,
+    },
+}
+
+impl TreeNode {
+    fn predict(&self, features: &FeatureVector) -> f32 {
+        match self {
+            Self::Leaf { leaf } => *leaf,
+            Self::Split {
+                feature,
+                threshold,
+                left,
+                right,
+            } => {
+                let value = features.get(feature).unwrap_or_default();
+                if value < *threshold {
+                    left.predict(features)
+                } else {
+                    right.predict(features)
+                }
+            }
+        }
+    }
+
+    fn features(&self, out: &mut Vec<String>) {
+        if let Self::Split {
+            feature, left, right, ..
+        } = self
+        {
+            out.push(feature.clone());
+            left.features(out);
+            right.features(out);
+        }
+    }
+}
+
+/// A serialized risk model; both kinds produce a log-odds score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RiskModel {
+    /// Logistic regression
+    Linear {
+        bias: f32,
+        /// Coefficient per feature; features left out weigh nothing
+        weights: BTreeMap<String, f32>,
+    },
+    /// Gradient-boosted trees, summed over `base_score`
+    Gbdt {
+        #[serde(default)]
+        base_score: f32,
+        trees: Vec<TreeNode>,
+    },
+}
+
+impl RiskModel {
+    /// Load a JSON model, checking its feature names.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| ModelError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_json(&text)
+    }
+
+    /// Parse a JSON model, checking its feature names.
+    pub fn from_json(text: &str) -> Result<Self, ModelError> {
+        let model: Self = serde_json::from_str(text)?;
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Check that the model reads only known features.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        let names = match self {
+            Self::Linear { weights, .. } => weights.keys().cloned().collect(),
+            Self::Gbdt { trees, .. } => {
+                let mut names = Vec::new();
+                for tree in trees {
+                    tree.features(&mut names);
+                }
+                names
+            }
+        };
+        match names.into_iter().find(|name| !FEATURES.contains(&name.as_str())) {
+            Some(name) => Err(ModelError::UnknownFeature(name)),
+            None => Ok(()),
+        }
+    }
+
+    /// Log-odds of a conflict.
+    pub fn logit(&self, features: &FeatureVector) -> f32 {
+        match self {
+            Self::Linear { bias, weights } => {
+                bias + weights
+                    .iter()
+                    .map(|(name, weight)| weight * features.get(name).unwrap_or_default())
+                    .sum::<f32>()
+            }
+            Self::Gbdt { base_score, trees } => {
+                base_score + trees.iter().map(|tree| tree.predict(features)).sum::<f32>()
+            }
+        }
+    }
+
+    /// Probability of a conflict, or `None` if the model cannot score
+    /// the candidate (the heuristic score is kept).
+    pub fn probability(&self, features: &FeatureVector) -> Option<f32> {
+        let logit = self.logit(features);
+        logit.is_finite().then(|| 1.0 / (1.0 + (-logit).exp()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(values: &[(&str, f32)]) -> FeatureVector {
+        let mut features = FeatureVector::default();
+        for (name, value) in values {
+            features.set(name, *value);
+        }
+        features
+    }
+
+    #[test]
+    fn test_linear_model() {
+        let model = RiskModel::from_json(
+            r#"{"type": "linear", "bias": -2.0, "weights": {"phonetic_match": 2.0}}"#,
+        )
+        .unwrap();
+        assert_eq!(model.probability(&FeatureVector::default()), Some(1.0 / (1.0 + 2f32.exp())));
+        assert_eq!(model.probability(&features(&[("phonetic_match", 1.0)])), Some(0.5));
+
+        let features = features(&[("phonetic_match", f32::NAN)]);
+        assert_eq!(model.probability(&features), None);
+    }
+
+    #[test]
+    fn test_gbdt_model() {
+        let model = RiskModel::from_json(
+            r#"{"type": "gbdt", "base_score": -1.0, "trees": [
+                {"feature": "edit_distance", "threshold": 2.0,
+                 "left": {"feature": "class_overlap", "threshold": 1.0,
+                          "left": {"leaf": 0.5}, "right": {"leaf": 2.0}},
+                 "right": {"leaf": -1.0}},
+                {"leaf": 0.1}
+            ]}"#,
+        )
+        .unwrap();
+        let close = features(&[("edit_distance", 1.0), ("class_overlap", 1.0)]);
+        assert!((model.logit(&close) - 1.1).abs() < 1e-6);
+        let close_other_class = features(&[("edit_distance", 1.0)]);
+        assert!((model.logit(&close_other_class) + 0.4).abs() < 1e-6);
+        let far = features(&[("edit_distance", 5.0), ("class_overlap", 1.0)]);
+        assert!((model.logit(&far) + 1.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unknown_feature() {
+        let linear = r#"{"type": "linear", "bias": 0.0, "weights": {"fame": 1.0}}"#;
+        assert!(matches!(
+            RiskModel::from_json(linear),
+            Err(ModelError::UnknownFeature(name)) if name == "fame"
+        ));
+        let tree = r#"{"type": "gbdt", "trees": [
+            {"feature": "fame", "threshold": 1.0, "left": {"leaf": 0.0}, "right": {"leaf": 1.0}}
+        ]}"#;
+        assert!(matches!(RiskModel::from_json(tree), Err(ModelError::UnknownFeature(_))));
+        assert!(matches!(RiskModel::from_json("{}"), Err(ModelError::Parse(_))));
+    }
+}
@@ -3,133 +3,918 @@
 //! Takes raw search results and applies proprietary scoring logic
 //! to produce risk-ranked results with explanations.
 
-use ilegalflow_model::{CandidateHit, RiskFlag, SearchQuery, TrademarkRecord};
+pub mod behaviors;
+pub mod calibration;
+pub mod combine;
+pub mod common_law;
+pub mod context;
+pub mod curves;
+pub mod dedup;
+pub mod design;
+pub mod detectors;
+pub mod dilution;
+pub mod dominant;
+pub mod dupont;
+pub mod guards;
+pub mod highlight;
+pub mod house_marks;
+pub mod knockout;
+pub mod learned;
+pub mod presets;
+pub mod profiles;
+pub mod status;
+
+/// The default hit order, defined with `CandidateHit`.
+pub use ilegalflow_model::compare_hits;
+
+use behaviors::{
+    BehaviorToggles, DEAD_MARK_AGE, DEDUP_FILINGS, HOUSE_MARKS, MARK_FAMILIES, MARK_STRENGTH,
+    STATUS_WEIGHTS, STEMMING,
+};
+use calibration::{Calibration, CalibrationError};
+use combine::Combination;
+use curves::{CurveError, SeverityCurves};
+use design::{DesignHandling, DesignMarks};
+use common_law::CommonLawSource;
+use context::QueryContext;
+use detectors::{Candidate, DetectorRegistry, FlagDetector};
+use guards::{Rule, RuleGuards, RuleWarning};
+use house_marks::HouseMarks;
+use knockout::KnockoutConfig;
+use learned::{FeatureVector, RiskModel};
+use status::{months_since, DeadMarkDecay, StatusWeights};
+use ilegalflow_famous::FamousMarks;
+use ilegalflow_model::{
+    CandidateHit, Date, RiskFlag, ScoreNormalization, SearchQuery, SeverityError, SeverityTable,
+    StatusAdjustment, TrademarkRecord, TrademarkStatus,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use ilegalflow_features::{
-    class_overlap, edit_distance, extract_dominant_term, normalize_text, phonetic_match,
+    latin_mark_text, mark_strength_with, match_number_variants, normalize_confusable_chars,
+    normalize_text, number_word_variants, similarity_ratio, strip_house_marks, token_sort_ratio,
+    ConfusableConfig,
+    ClassRelatedness, NumberWordMatch, PhoneticRegistry, TermFrequencies, TfIdfModel,
+    TfIdfVector, TranslationDictionary,
 };
+#[cfg(feature = "embeddings")]
+use ilegalflow_features::{cosine_similarity, TextEmbedder};
+use std::cmp::Ordering;
+use std::sync::Arc;
 
 /// Configuration for the re-ranker.
-#[derive(Debug, Clone)]
+///
+/// Serializable for configuration files (see `profiles`); fields left
+/// out take their defaults. Values built in code (term frequencies,
+/// embedder, substitution tables, translations, class relatedness,
+/// famous marks, common-law sources, custom detectors, risk model, hit
+/// ordering) are not serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct RerankConfig {
+    /// Weight for a match disguised by character substitutions
+    pub obfuscation_weight: f32,
+    /// Weight for a match once numerals and number words are swapped
+    /// (scaled by the fuzzy curve)
+    pub number_word_weight: f32,
+    /// Weight for the same words once compounds are split
+    pub compound_weight: f32,
+    /// Weight for a direct translation of the query
+    pub foreign_weight: f32,
+    /// Weight for marks equal once house marks and designators are
+    /// stripped (ACME NIKE SERIES vs NIKE)
+    pub house_mark_weight: f32,
+    /// Weight for marks equal once stemmed (WIDGETS vs WIDGET)
+    pub stem_weight: f32,
     /// Weight for phonetic similarity
     pub phonetic_weight: f32,
+    /// Weight for marks nearly alike in sound (scaled by the phoneme
+    /// similarity)
+    pub near_phonetic_weight: f32,
+    /// Largest phoneme distance that raises a near-phonetic flag
+    pub max_phoneme_distance: f32,
+    /// Smallest phoneme similarity that raises a near-phonetic flag, so
+    /// short marks a couple of sounds apart are not flagged
+    pub min_phoneme_similarity: f32,
     /// Weight for fuzzy/edit distance
     pub fuzzy_weight: f32,
+    /// Weight for string similarity (scaled by the score)
+    pub similarity_weight: f32,
+    /// Minimum Jaro-Winkler score that raises a similarity flag
+    pub similarity_threshold: f32,
+    /// Minimum token-sort ratio that flags a word-reordering match
+    pub token_sort_threshold: f32,
+    /// Weight for a shared beginning (scaled by the prefix-weighted
+    /// similarity)
+    pub prefix_weight: f32,
+    /// Weight for syllable-level similarity (scaled by the alignment
+    /// score)
+    pub syllable_weight: f32,
+    /// Minimum syllable alignment score that raises a flag
+    pub syllable_threshold: f32,
+    /// Leading characters a mark must share with the query to be boosted
+    pub prefix_length: usize,
+    /// How quickly the extra cost of an early edit fades (0.0 - 1.0)
+    pub prefix_decay: f32,
+    /// Weight for character n-gram overlap (scaled by the cosine score)
+    pub ngram_weight: f32,
+    /// Minimum n-gram cosine score that raises a similarity flag
+    pub ngram_threshold: f32,
+    /// N-gram length
+    pub ngram_size: usize,
     /// Weight for class overlap
     pub class_weight: f32,
-    /// Weight for dominant term match
+    /// Share of `class_weight` scored when either side's classes were
+    /// inferred from goods/services text
+    pub inferred_class_factor: f32,
+    /// Weight for different but related classes (scaled by the closest
+    /// pair's grade)
+    pub related_class_weight: f32,
+    /// Grades of related class pairs (the bundled grades by default)
+    #[serde(skip)]
+    pub class_relatedness: ClassRelatedness,
+    /// Weight for dominant term match (scaled by the matched term's
+    /// weight when ranking by corpus frequencies)
     pub dominant_weight: f32,
+    /// Corpus term frequencies; when set, dominant terms are the marks'
+    /// rarest words rather than their longest
+    #[serde(skip)]
+    pub term_frequencies: Option<Arc<TermFrequencies>>,
+    /// Dominant terms taken per mark with corpus frequencies
+    pub dominant_terms: usize,
+    /// Weight for shared design codes
+    pub design_weight: f32,
+    /// How design-mark candidates are scored
+    pub design_marks: DesignMarks,
+    /// Weight for similar goods/services (scaled by the similarity)
+    pub goods_weight: f32,
+    /// Minimum goods/services TF-IDF similarity that raises a flag
+    pub goods_threshold: f32,
+    /// Weight for a famous mark contained in both marks, whatever their
+    /// classes
+    pub famous_weight: f32,
+    /// Famous marks protected across classes (the bundled list by
+    /// default)
+    #[serde(skip)]
+    pub famous_marks: Arc<FamousMarks>,
+    /// Weight for dilution of a famous mark (scaled by the dilution
+    /// score)
+    pub dilution_weight: f32,
+    /// Minimum dilution score that raises a flag
+    pub dilution_threshold: f32,
+    /// Weight for evidence of unregistered use of a resembling mark
+    /// (scaled by the evidence's confidence)
+    pub common_law_weight: f32,
+    /// Minimum confidence of use evidence that raises a flag
+    pub common_law_threshold: f32,
+    /// Sources of use evidence; no common-law flags without one
+    #[serde(skip)]
+    pub common_law_sources: Vec<Arc<dyn CommonLawSource>>,
+    /// Embedder for semantic goods/services similarity, used alongside
+    /// the lexical measure
+    #[cfg(feature = "embeddings")]
+    #[serde(skip)]
+    pub embedder: Option<Arc<dyn TextEmbedder>>,
+    /// Minimum semantic similarity that raises a goods/services flag
+    #[cfg(feature = "embeddings")]
+    pub semantic_threshold: f32,
     /// Maximum edit distance to consider
     pub max_edit_distance: usize,
+    /// Encoders that participate in phonetic matching
+    pub phonetic: PhoneticRegistry,
+    /// Curves scaling measured flags (fuzzy, similarity) by their value;
+    /// rebuild with `SeverityCurves::linear` when changing
+    /// `max_edit_distance`
+    pub curves: SeverityCurves,
+    /// Severity of each flag, which decides knockout screening and risk
+    /// tiers; the pipeline explains hits with the same table
+    pub severities: SeverityTable,
+    /// Applicability guards per rule (short or non-alphabetic marks);
+    /// none by default
+    pub guards: RuleGuards,
+    /// Settings for knockout screening
+    pub knockout: KnockoutConfig,
+    /// Leetspeak and look-alike substitutions undone before comparing
+    #[serde(skip)]
+    pub confusables: ConfusableConfig,
+    /// Foreign words and their English meanings
+    #[serde(skip)]
+    pub translations: TranslationDictionary,
+    /// Overrides of named scoring behaviors, globally and per tenant
+    pub behaviors: BehaviorToggles,
+    /// House marks stripped before comparing, globally and per tenant
+    pub house_marks: HouseMarks,
+    /// Risk multipliers by record status (live, pending, dead)
+    pub status_weights: StatusWeights,
+    /// Decay of a dead mark's multiplier with its age, used instead of
+    /// the dead weight when the record has a status date
+    pub dead_mark_decay: DeadMarkDecay,
+    /// Date to score as of. Unset, record ages are not known and every
+    /// dated behavior default is in effect; the pipeline scores as of
+    /// the search date.
+    pub today: Option<Date>,
+    /// Checks run on each candidate that is not an exact match
+    #[serde(skip)]
+    pub detectors: DetectorRegistry,
+    /// Learned model whose probability replaces the heuristic score of
+    /// non-exact candidates, status weights and calibration; the
+    /// heuristic score is used when unset or when the model cannot score
+    /// a candidate
+    #[serde(skip)]
+    pub risk_model: Option<Arc<RiskModel>>,
+    /// Mapping from final risk score to probability of conflict, fitted
+    /// on reviewed results; hits carry no probability when unset
+    pub calibration: Option<Calibration>,
+    /// Hits scoring below this are dropped as obvious non-conflicts
+    pub min_risk_score: f32,
+    /// Most hits returned, riskiest first, counting bucketed design-mark
+    /// hits; all when unset
+    pub max_results: Option<usize>,
+    /// Weight for full-text relevance (scaled by the normalized retrieval
+    /// score), added to the heuristic score of every candidate
+    pub retrieval_weight: f32,
+    /// Mapping of the backend's retrieval scores to 0.0 - 1.0; the
+    /// pipeline fills in the backend's own when unset, and plain
+    /// re-ranking divides by the best score
+    pub retrieval_normalization: Option<ScoreNormalization>,
+    /// Weigh each returned hit on the du Pont factors
+    pub dupont_assessment: bool,
+    /// How flag contributions combine into the risk score (summed by
+    /// default)
+    pub combination: Combination,
+    /// Order of the re-ranked hits
+    #[serde(skip)]
+    pub hit_ordering: HitOrdering,
+}
+
+/// Orders re-ranked hits; the first hit is the riskiest. The default is
+/// `compare_hits`.
+pub type HitOrdering = fn(&CandidateHit, &CandidateHit) -> Ordering;
+
+/// Errors from an invalid re-ranker configuration.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RerankConfigError {
+    #[error("Invalid severity curve: {0}")]
+    Curve(#[from] CurveError),
+
+    #[error("Invalid severity table: {0}")]
+    Severity(#[from] SeverityError),
+
+    #[error("{name} must be a non-negative number, got {value}")]
+    NegativeWeight { name: &'static str, value: f32 },
+
+    #[error("{name} is {value}; a weight above 1.0 saturates the risk score on its own")]
+    WeightTooHigh { name: &'static str, value: f32 },
+
+    #[error("Every weight is zero; only exact matches would score")]
+    NoWeights,
+
+    #[error("Invalid calibration: {0}")]
+    Calibration(#[from] CalibrationError),
+
+    #[error("min_risk_score must be between 0.0 and 1.0, got {0}")]
+    MinRiskScore(f32),
+
+    #[error("min_phoneme_similarity must be between 0.0 and 1.0, got {0}")]
+    MinPhonemeSimilarity(f32),
+
+    #[error("max_results must be at least 1")]
+    NoResults,
+
+    #[error("Retrieval score saturation point must be positive, got {0}")]
+    RetrievalSaturation(f32),
+
+    #[error("Weighted-logistic combination needs a positive steepness and a finite midpoint")]
+    Combination,
+
+    #[error("Dead-mark decay needs non-negative multipliers and a positive half-life")]
+    DeadMarkDecay,
+
+    #[error("design_marks.text_factor must be between 0.0 and 1.0, got {0}")]
+    DesignTextFactor(f32),
+}
+
+impl RerankConfig {
+    /// Check the configuration before use.
+    pub fn validate(&self) -> Result<(), RerankConfigError> {
+        self.curves.validate()?;
+        self.severities.validate()?;
+        let weights = self.weights();
+        for (name, value) in weights {
+            if value.is_nan() || value < 0.0 {
+                return Err(RerankConfigError::NegativeWeight { name, value });
+            }
+            if value > 1.0 {
+                return Err(RerankConfigError::WeightTooHigh { name, value });
+            }
+        }
+        if weights.iter().all(|&(_, value)| value == 0.0) {
+            return Err(RerankConfigError::NoWeights);
+        }
+        for (name, value) in self.status_weights.entries() {
+            if value.is_nan() || value < 0.0 {
+                return Err(RerankConfigError::NegativeWeight { name, value });
+            }
+        }
+        if let Some(calibration) = &self.calibration {
+            calibration.validate()?;
+        }
+        if !(0.0..=1.0).contains(&self.min_risk_score) {
+            return Err(RerankConfigError::MinRiskScore(self.min_risk_score));
+        }
+        if !(0.0..=1.0).contains(&self.min_phoneme_similarity) {
+            return Err(RerankConfigError::MinPhonemeSimilarity(self.min_phoneme_similarity));
+        }
+        if self.max_results == Some(0) {
+            return Err(RerankConfigError::NoResults);
+        }
+        if !(0.0..=1.0).contains(&self.design_marks.text_factor) {
+            return Err(RerankConfigError::DesignTextFactor(self.design_marks.text_factor));
+        }
+        if !self.dead_mark_decay.is_valid() {
+            return Err(RerankConfigError::DeadMarkDecay);
+        }
+        if !self.combination.is_valid() {
+            return Err(RerankConfigError::Combination);
+        }
+        if let Some(ScoreNormalization::Saturating { half }) = self.retrieval_normalization {
+            if !(half.is_finite() && half > 0.0) {
+                return Err(RerankConfigError::RetrievalSaturation(half));
+            }
+        }
+        Ok(())
+    }
+
+    /// Every flag weight, by field name.
+    pub fn weights(&self) -> [(&'static str, f32); 22] {
+        [
+            ("obfuscation_weight", self.obfuscation_weight),
+            ("number_word_weight", self.number_word_weight),
+            ("compound_weight", self.compound_weight),
+            ("foreign_weight", self.foreign_weight),
+            ("house_mark_weight", self.house_mark_weight),
+            ("stem_weight", self.stem_weight),
+            ("phonetic_weight", self.phonetic_weight),
+            ("near_phonetic_weight", self.near_phonetic_weight),
+            ("fuzzy_weight", self.fuzzy_weight),
+            ("similarity_weight", self.similarity_weight),
+            ("prefix_weight", self.prefix_weight),
+            ("syllable_weight", self.syllable_weight),
+            ("ngram_weight", self.ngram_weight),
+            ("class_weight", self.class_weight),
+            ("related_class_weight", self.related_class_weight),
+            ("dominant_weight", self.dominant_weight),
+            ("design_weight", self.design_weight),
+            ("goods_weight", self.goods_weight),
+            ("famous_weight", self.famous_weight),
+            ("dilution_weight", self.dilution_weight),
+            ("common_law_weight", self.common_law_weight),
+            ("retrieval_weight", self.retrieval_weight),
+        ]
+    }
 }
 
 impl Default for RerankConfig {
     fn default() -> Self {
         Self {
+            obfuscation_weight: 0.7,
+            number_word_weight: 0.7,
+            compound_weight: 0.6,
+            foreign_weight: 0.5,
+            house_mark_weight: 0.7,
+            stem_weight: 0.6,
             phonetic_weight: 0.3,
+            near_phonetic_weight: 0.2,
+            max_phoneme_distance: 2.0,
+            min_phoneme_similarity: 0.65,
             fuzzy_weight: 0.2,
+            similarity_weight: 0.15,
+            similarity_threshold: 0.9,
+            token_sort_threshold: 0.8,
+            prefix_weight: 0.1,
+            prefix_length: 3,
+            syllable_weight: 0.1,
+            syllable_threshold: 0.8,
+            prefix_decay: 0.5,
+            ngram_weight: 0.1,
+            ngram_threshold: 0.8,
+            ngram_size: 3,
             class_weight: 0.25,
+            inferred_class_factor: 0.5,
+            related_class_weight: 0.15,
+            class_relatedness: ClassRelatedness::default(),
             dominant_weight: 0.25,
+            term_frequencies: None,
+            dominant_terms: 2,
+            design_weight: 0.2,
+            design_marks: DesignMarks::default(),
+            goods_weight: 0.2,
+            goods_threshold: 0.3,
+            famous_weight: 0.5,
+            famous_marks: Arc::new(FamousMarks::default()),
+            dilution_weight: 0.3,
+            dilution_threshold: 0.3,
+            common_law_weight: 0.2,
+            common_law_threshold: 0.5,
+            common_law_sources: Vec::new(),
+            #[cfg(feature = "embeddings")]
+            embedder: None,
+            #[cfg(feature = "embeddings")]
+            semantic_threshold: 0.75,
             max_edit_distance: 3,
+            phonetic: PhoneticRegistry::default(),
+            curves: SeverityCurves::linear(3),
+            severities: SeverityTable::default(),
+            guards: RuleGuards::default(),
+            knockout: KnockoutConfig::default(),
+            confusables: ConfusableConfig::default(),
+            translations: TranslationDictionary::default(),
+            behaviors: BehaviorToggles::default(),
+            house_marks: HouseMarks::default(),
+            status_weights: StatusWeights::default(),
+            dead_mark_decay: DeadMarkDecay::default(),
+            today: None,
+            detectors: DetectorRegistry::default(),
+            risk_model: None,
+            calibration: None,
+            min_risk_score: 0.0,
+            max_results: None,
+            retrieval_weight: 0.0,
+            retrieval_normalization: None,
+            dupont_assessment: false,
+            combination: Combination::Additive,
+            hit_ordering: compare_hits,
         }
     }
 }
 
+/// Result of a re-rank, with any rules skipped by guards.
+#[derive(Debug, Clone)]
+pub struct RerankOutcome {
+    /// Risk-ranked hits
+    pub hits: Vec<CandidateHit>,
+    /// Rules skipped by guards, one entry per rule and reason
+    pub warnings: Vec<RuleWarning>,
+    /// Hits dropped by `min_risk_score` or `max_results`
+    pub pruned: usize,
+    /// Design marks set aside when design marks are bucketed, ranked
+    /// like `hits`
+    pub design_hits: Vec<CandidateHit>,
+}
+
 /// Re-rank candidates based on trademark risk analysis.
 pub fn rerank(
     query: &SearchQuery,
     candidates: Vec<(TrademarkRecord, f32)>,
     config: &RerankConfig,
 ) -> Vec<CandidateHit> {
-    let query_normalized = normalize_text(&query.mark_text);
-    let query_dominant = extract_dominant_term(&query.mark_text);
+    rerank_outcome(query, candidates, config).hits
+}
 
-    let mut hits: Vec<CandidateHit> = candidates
-        .into_iter()
-        .map(|(record, retrieval_score)| {
-            let (risk_score, flags) =
-                compute_risk(&query_normalized, &query.classes, query_dominant.as_deref(), &record, config);
+/// Re-rank candidates, reporting rules skipped by guards.
+pub fn rerank_outcome(
+    query: &SearchQuery,
+    candidates: Vec<(TrademarkRecord, f32)>,
+    config: &RerankConfig,
+) -> RerankOutcome {
+    rerank_with_context(&QueryContext::new(query, config), query, candidates, config)
+}
 
-            CandidateHit {
-                record,
-                retrieval_score,
-                risk_score,
-                flags,
+/// Re-rank candidates using query values derived beforehand (see
+/// `QueryContext`), reporting rules skipped by guards.
+///
+/// With the `parallel` feature, candidates are scored on the rayon
+/// thread pool; the outcome is the same either way.
+pub fn rerank_with_context(
+    context: &QueryContext,
+    query: &SearchQuery,
+    candidates: Vec<(TrademarkRecord, f32)>,
+    config: &RerankConfig,
+) -> RerankOutcome {
+    let candidates = if context.behaviors.is_enabled(DEDUP_FILINGS) {
+        dedup::dedup_candidates(candidates)
+    } else {
+        candidates
+    };
+    let goods = GoodsContext::new(context, query, &candidates);
+    let retrieval_scores: Vec<f32> = candidates.iter().map(|(_, score)| *score).collect();
+    let relevance = config
+        .retrieval_normalization
+        .unwrap_or_default()
+        .normalize(&retrieval_scores);
+    let score = |((record, retrieval_score), relevance)| {
+        let goods = goods.as_ref();
+        score_candidate(context, query, goods, record, retrieval_score, relevance, config)
+    };
+
+    #[cfg(feature = "parallel")]
+    let scored: Vec<(CandidateHit, Vec<(Rule, String)>)> = {
+        use rayon::prelude::*;
+        candidates.into_par_iter().zip(relevance).map(score).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let scored: Vec<(CandidateHit, Vec<(Rule, String)>)> =
+        candidates.into_iter().zip(relevance).map(score).collect();
+
+    let mut warnings: Vec<RuleWarning> = Vec::new();
+    let mut hits = Vec::with_capacity(scored.len());
+    for (hit, skipped) in scored {
+        for (rule, reason) in skipped {
+            match warnings.iter_mut().find(|w| w.rule == rule && w.reason == reason) {
+                Some(warning) => warning.skipped += 1,
+                None => warnings.push(RuleWarning {
+                    rule,
+                    reason,
+                    skipped: 1,
+                }),
             }
-        })
-        .collect();
+        }
+        hits.push(hit);
+    }
 
-    // Sort by risk score descending
-    hits.sort_by(|a, b| b.risk_score.partial_cmp(&a.risk_score).unwrap_or(std::cmp::Ordering::Equal));
+    hits.sort_by(config.hit_ordering);
+    if context.behaviors.is_enabled(MARK_FAMILIES) {
+        hits = dedup::group_families(hits);
+    }
+    let (mut design_hits, mut hits): (Vec<_>, Vec<_>) = hits.into_iter().partition(|hit| {
+        config.design_marks.handling_for(context, &hit.record) == DesignHandling::Bucket
+    });
+    let pruned = prune_buckets(&mut hits, &mut design_hits, config);
+    if config.dupont_assessment {
+        for hit in hits.iter_mut().chain(design_hits.iter_mut()) {
+            hit.dupont = Some(dupont::assess(query, hit, config));
+        }
+    }
 
-    hits
+    RerankOutcome {
+        hits,
+        warnings,
+        pruned,
+        design_hits,
+    }
+}
+
+/// Drop ranked hits below `min_risk_score` and beyond `max_results`,
+/// returning how many were dropped.
+pub(crate) fn prune(hits: &mut Vec<CandidateHit>, config: &RerankConfig) -> usize {
+    let total = hits.len();
+    hits.retain(|hit| hit.risk_score >= config.min_risk_score);
+    if let Some(max_results) = config.max_results {
+        hits.truncate(max_results);
+    }
+    total - hits.len()
 }
 
-/// Compute risk score and flags for a single candidate.
-fn compute_risk(
-    query_normalized: &str,
-    query_classes: &[u16],
-    query_dominant: Option<&str>,
+/// `prune` over hits and bucketed design-mark hits together: the
+/// `max_results` riskiest across both buckets are kept.
+fn prune_buckets(
+    hits: &mut Vec<CandidateHit>,
+    design_hits: &mut Vec<CandidateHit>,
+    config: &RerankConfig,
+) -> usize {
+    let total = hits.len() + design_hits.len();
+    hits.retain(|hit| hit.risk_score >= config.min_risk_score);
+    design_hits.retain(|hit| hit.risk_score >= config.min_risk_score);
+    if let Some(max_results) = config.max_results {
+        // Both buckets are sorted; walk them as one merged list
+        let (mut kept, mut kept_design) = (0, 0);
+        while kept + kept_design < max_results {
+            match (hits.get(kept), design_hits.get(kept_design)) {
+                (Some(hit), Some(design)) if (config.hit_ordering)(hit, design).is_gt() => {
+                    kept_design += 1
+                }
+                (Some(_), _) => kept += 1,
+                (None, Some(_)) => kept_design += 1,
+                (None, None) => break,
+            }
+        }
+        hits.truncate(kept);
+        design_hits.truncate(kept_design);
+    }
+    total - hits.len() - design_hits.len()
+}
+
+/// Score one candidate, returning its hit and the rules guards skipped.
+/// `relevance` is its normalized retrieval score.
+fn score_candidate(
+    context: &QueryContext,
+    query: &SearchQuery,
+    goods: Option<&GoodsContext>,
+    record: TrademarkRecord,
+    retrieval_score: f32,
+    relevance: f32,
+    config: &RerankConfig,
+) -> (CandidateHit, Vec<(Rule, String)>) {
+    let mut risk = compute_risk(context, query, goods, &record, config, None);
+
+    // How long a dead mark has been dead, if its status date says
+    let dead_months = if record.status == TrademarkStatus::Dead
+        && context.behaviors.is_enabled(DEAD_MARK_AGE)
+    {
+        record.status_date.zip(context.today).map(|(date, today)| months_since(date, today))
+    } else {
+        None
+    };
+    let adjustment = if context.behaviors.is_enabled(STATUS_WEIGHTS) {
+        match dead_months {
+            Some(months) => Some(StatusAdjustment {
+                status: TrademarkStatus::Dead,
+                multiplier: config.dead_mark_decay.multiplier(months),
+            }),
+            None => config.status_weights.adjustment(record.status),
+        }
+    } else {
+        None
+    };
+
+    let mut learned = None;
+    if let Some(model) = &config.risk_model {
+        if !risk.flags.contains(&RiskFlag::ExactMatch) {
+            let features = FeatureVector::extract(
+                context,
+                &record,
+                &risk.flags,
+                risk.score,
+                retrieval_score,
+                adjustment.as_ref().map_or(1.0, |adjustment| adjustment.multiplier),
+            );
+            learned = model.probability(&features);
+        }
+    }
+    // A learned model already weighs the retrieval score and status
+    let status_adjustment = match learned {
+        Some(probability) => {
+            risk.score = probability;
+            None
+        }
+        None => {
+            risk.score = (risk.score + config.retrieval_weight * relevance).min(1.0);
+            adjustment.filter(|_| risk.score > 0.0)
+        }
+    };
+    if let Some(adjustment) = &status_adjustment {
+        risk.score = (risk.score * adjustment.multiplier).min(1.0);
+    }
+    if let Some(months) = dead_months {
+        if months <= config.dead_mark_decay.recent_months as f32 && risk.score > 0.0 {
+            risk.flags.push(RiskFlag::RecentlyDead {
+                months: months as u32,
+            });
+        }
+    }
+
+    let highlights = highlight::highlights(context, &record, &risk.flags);
+    let hit = CandidateHit {
+        record,
+        retrieval_score,
+        risk_score: risk.score,
+        // A model's probability is already calibrated
+        risk_probability: learned
+            .or_else(|| config.calibration.as_ref().map(|c| c.probability(risk.score))),
+        flags: risk.flags,
+        review: None,
+        status_adjustment,
+        links: None,
+        family: Vec::new(),
+        dupont: None,
+        highlights,
+    };
+    (hit, risk.skipped)
+}
+
+/// The query's goods/services, weighted against the candidates' descriptions.
+struct GoodsContext {
+    model: TfIdfModel,
+    query: TfIdfVector,
+}
+
+impl GoodsContext {
+    /// `None` if the query has no goods/services text.
+    fn new(
+        context: &QueryContext,
+        query: &SearchQuery,
+        candidates: &[(TrademarkRecord, f32)],
+    ) -> Option<Self> {
+        let text = query.goods_services_query.as_deref()?;
+        let stemming = context.behaviors.is_enabled(STEMMING);
+        let mut model = TfIdfModel::default().with_stemming(stemming);
+        for (record, _) in candidates {
+            model.add_document(&record.goods_services);
+        }
+        model.add_document(text);
+        let query = model.vector(text);
+        (!query.is_empty()).then_some(Self { model, query })
+    }
+
+    /// TF-IDF similarity of a candidate's goods/services, if it reaches
+    /// the threshold.
+    fn similarity(&self, record: &TrademarkRecord, config: &RerankConfig) -> Option<f32> {
+        let similarity = self.model.vector(&record.goods_services).cosine(&self.query);
+        (similarity >= config.goods_threshold).then_some(similarity)
+    }
+}
+
+/// Semantic similarity of the query's and a candidate's goods/services,
+/// if an embedder is configured and it reaches the threshold.
+#[cfg(feature = "embeddings")]
+fn semantic_goods_similarity(
+    context: &QueryContext,
     record: &TrademarkRecord,
     config: &RerankConfig,
-) -> (f32, Vec<RiskFlag>) {
+) -> Option<f32> {
+    let embedder = config.embedder.as_ref()?;
+    let query = context.goods_embedding.as_deref()?;
+    let similarity = cosine_similarity(query, &embedder.embed(&record.goods_services)?);
+    (similarity >= config.semantic_threshold).then_some(similarity)
+}
+
+/// Risk assessment of a single candidate.
+pub(crate) struct Risk {
+    pub(crate) score: f32,
+    pub(crate) flags: Vec<RiskFlag>,
+    /// Rules skipped by a guard, with the reason
+    pub(crate) skipped: Vec<(Rule, String)>,
+}
+
+/// Detectors also run on exact matches, for their flags only.
+const EXACT_MATCH_DETECTORS: [&str; 2] = ["famous", "dilution"];
+
+/// Compute risk score and flags for a single candidate, running the
+/// registered detectors named in `only` (all of them when `None`).
+pub(crate) fn compute_risk(
+    context: &QueryContext,
+    query: &SearchQuery,
+    goods: Option<&GoodsContext>,
+    record: &TrademarkRecord,
+    config: &RerankConfig,
+    only: Option<&[String]>,
+) -> Risk {
+    let selected = |detector: &dyn FlagDetector| {
+        only.is_none_or(|names| names.iter().any(|name| name == detector.name()))
+    };
     let mut flags = Vec::new();
-    let mut score = 0.0_f32;
+    let mut contributions: Vec<f32> = Vec::new();
+    let mut skipped = Vec::new();
+
+    let query_normalized = context.normalized.as_str();
+    let mark_latin = latin_mark_text(record);
+    let mark_normalized = normalize_text(&mark_latin);
 
-    let mark_normalized = normalize_text(&record.mark_text);
+    // A design- or owner-only query has no text to compare
+    let has_text = !query_normalized.is_empty();
 
-    // Check exact match
-    if query_normalized == mark_normalized {
+    // Check exact match; designators (INC, SERIES) are not part of it
+    let same_text = query_normalized == mark_normalized
+        || (context.behaviors.is_enabled(HOUSE_MARKS)
+            && strip_house_marks(&mark_normalized, &[]).text == context.without_designators);
+    if has_text && same_text {
         flags.push(RiskFlag::ExactMatch);
-        return (1.0, flags); // Maximum risk
+        // Fame and dilution still apply to an identical mark
+        for detector in config.detectors.iter() {
+            if !EXACT_MATCH_DETECTORS.contains(&detector.name()) || !selected(detector) {
+                continue;
+            }
+            let candidate = Candidate {
+                query,
+                record,
+                mark: &mark_normalized,
+                config,
+                flags: &flags,
+                goods,
+            };
+            let found = detector.detect(context, &candidate);
+            flags.extend(found.into_iter().map(|(flag, _)| flag));
+        }
+        // Maximum risk
+        return Risk {
+            score: 1.0,
+            flags,
+            skipped,
+        };
     }
 
-    // Check phonetic match
-    if let Some((algorithm, code)) = phonetic_match(query_normalized, &mark_normalized) {
-        flags.push(RiskFlag::PhoneticMatch { algorithm, code });
-        score += config.phonetic_weight;
+    // Each rule's guard is checked once per candidate
+    let mut guarded: Vec<(Rule, bool)> = Vec::new();
+    for detector in config.detectors.iter().filter(|&detector| selected(detector)) {
+        if !has_text && detector.compares_text() {
+            continue;
+        }
+        if let Some(rule) = detector.rule() {
+            let passes = match guarded.iter().find(|(r, _)| *r == rule) {
+                Some((_, passes)) => *passes,
+                None => {
+                    let guard = config.guards.get(rule);
+                    let passes = match guard.check_pair(query_normalized, &mark_normalized) {
+                        Ok(()) => true,
+                        Err(reason) => {
+                            skipped.push((rule, reason));
+                            false
+                        }
+                    };
+                    guarded.push((rule, passes));
+                    passes
+                }
+            };
+            if !passes {
+                continue;
+            }
+        }
+
+        let candidate = Candidate {
+            query,
+            record,
+            mark: &mark_normalized,
+            config,
+            flags: &flags,
+            goods,
+        };
+        for (flag, contribution) in detector.detect(context, &candidate) {
+            flags.push(flag);
+            contributions.push(contribution);
+        }
     }
+    if config.design_marks.handling_for(context, record) == DesignHandling::Discount {
+        design::discount_text(&flags, &mut contributions, config);
+    }
+    let mut score = config.combination.combine(&contributions);
 
-    // Check fuzzy/edit distance
-    let distance = edit_distance(query_normalized, &mark_normalized);
-    if distance > 0 && distance <= config.max_edit_distance {
-        flags.push(RiskFlag::FuzzyMatch {
-            distance: distance as u8,
-        });
-        // Closer = higher risk
-        let fuzzy_score = 1.0 - (distance as f32 / (config.max_edit_distance as f32 + 1.0));
-        score += config.fuzzy_weight * fuzzy_score;
+    // A weak senior mark is protected narrowly, so scale its conflicts
+    // by its strength in its own classes
+    if context.behaviors.is_enabled(MARK_STRENGTH) {
+        let frequencies = config.term_frequencies.as_deref();
+        score *= mark_strength_with(&mark_latin, &record.classes, frequencies).strength.weight();
     }
 
-    // Check class overlap
-    let overlapping = class_overlap(query_classes, &record.classes);
-    if !overlapping.is_empty() {
-        flags.push(RiskFlag::ClassOverlap {
-            classes: overlapping,
-        });
-        score += config.class_weight;
+    Risk {
+        score,
+        flags,
+        skipped,
     }
+}
 
-    // Check dominant term match
-    if let Some(query_dom) = query_dominant {
-        if let Some(record_dom) = extract_dominant_term(&record.mark_text) {
-            if query_dom.to_uppercase() == record_dom.to_uppercase() {
-                flags.push(RiskFlag::DominantTermMatch { term: record_dom });
-                score += config.dominant_weight;
-            }
+/// Flag marks that are equal only once character substitutions are
+/// undone (N1KE vs NIKE); call after ruling out an exact match.
+pub(crate) fn obfuscated_match(
+    context: &QueryContext,
+    record: &TrademarkRecord,
+    config: &RerankConfig,
+) -> Option<RiskFlag> {
+    let query_plain = &context.confusable;
+    let mark_plain = normalize_confusable_chars(&record.mark_text, &config.confusables);
+    if query_plain.text.is_empty() || query_plain.text != mark_plain.text {
+        return None;
+    }
+
+    let mut substitutions = query_plain.substitutions.clone();
+    for substitution in mark_plain.substitutions {
+        if !substitutions.contains(&substitution) {
+            substitutions.push(substitution);
         }
     }
+    Some(RiskFlag::ObfuscatedMatch { substitutions })
+}
 
-    // Normalize score to 0.0 - 1.0
-    score = score.min(1.0);
+/// Number-word match between the query and a normalized mark within
+/// `max_distance`, unless the texts match without substitution.
+pub(crate) fn number_word_equivalent(
+    context: &QueryContext,
+    mark_normalized: &str,
+    max_distance: usize,
+) -> Option<NumberWordMatch> {
+    let mark_variants = number_word_variants(mark_normalized);
+    if context.number_variants[0].text == mark_variants[0].text {
+        return None;
+    }
+    match_number_variants(&context.number_variants, &mark_variants, max_distance)
+}
 
-    (score, flags)
+/// Share of `class_weight` a class overlap scores: discounted when either
+/// side's classes were inferred rather than filed.
+pub(crate) fn class_factor(inferred: bool, config: &RerankConfig) -> f32 {
+    if inferred {
+        config.inferred_class_factor
+    } else {
+        1.0
+    }
+}
+
+/// Token-sort similarity of two multi-word marks, if it reaches the
+/// threshold and beats the in-order similarity (the words are reordered).
+fn reordering_similarity(query: &str, mark: &str, threshold: f32) -> Option<f32> {
+    let multi_word = |s: &str| s.split_whitespace().nth(1).is_some();
+    if !multi_word(query) || !multi_word(mark) {
+        return None;
+    }
+
+    let sorted = token_sort_ratio(query, mark);
+    (sorted >= threshold && sorted > similarity_ratio(query, mark)).then_some(sorted)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ilegalflow_model::TrademarkStatus;
+    use behaviors::{NEAR_PHONETIC, PREFIX_MATCH};
+    use ilegalflow_model::{Date, NiceClass, Owner};
 
     fn make_record(serial: &str, mark: &str, classes: Vec<u16>) -> TrademarkRecord {
         TrademarkRecord {
@@ -137,21 +922,26 @@ mod tests {
             registration_number: None,
             mark_text: mark.to_string(),
             mark_text_normalized: None,
+            transliteration: None,
             status: TrademarkStatus::Live,
             status_code: None,
-            classes,
+            classes: NiceClass::of_all(&classes),
+            classes_inferred: false,
             goods_services: String::new(),
-            owner_name: String::new(),
+            owner: Owner::default(),
             filing_date: None,
             registration_date: None,
             status_date: None,
-            is_design_mark: false,
+            drawing_type: None,
+            design_codes: Vec::new(),
+            prosecution_events: Vec::new(),
+            provenance: None,
         }
     }
 
     #[test]
     fn test_exact_match_highest_risk() {
-        let query = SearchQuery::new("NIKE").with_classes(vec![25]);
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
         let candidates = vec![(make_record("001", "NIKE", vec![25]), 1.0)];
         let config = RerankConfig::default();
 
@@ -161,9 +951,342 @@ mod tests {
         assert!(hits[0].flags.contains(&RiskFlag::ExactMatch));
     }
 
+    #[test]
+    fn test_exact_famous_mark_across_classes() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[11]));
+        let candidates = || vec![(make_record("001", "NIKE", vec![25]), 1.0)];
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        let flags = &hits[0].flags;
+        assert_eq!(hits[0].risk_score, 1.0);
+        assert_eq!(flags[0], RiskFlag::ExactMatch);
+        assert!(flags.iter().any(|f| matches!(f, RiskFlag::FamousMark { cross_class: true, .. })));
+        assert!(flags.iter().any(|f| matches!(f, RiskFlag::DilutionRisk { .. })));
+
+        // Dropping a detector drops its flag here too
+        let config = RerankConfig {
+            detectors: DetectorRegistry::default().without("famous"),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert_eq!(hits[0].flags, vec![RiskFlag::ExactMatch]);
+    }
+
+    #[test]
+    fn test_textless_query_is_not_exact() {
+        let mut query = SearchQuery::new("");
+        query.design_codes = vec!["05.07".to_string()];
+        let mut record = make_record("001", "", vec![9]);
+        record.design_codes = vec!["05.07.13".to_string()];
+        let outcome = rerank_outcome(&query, vec![(record, 1.0)], &RerankConfig::default());
+        let flags = &outcome.hits[0].flags;
+        assert_eq!(flags, &vec![RiskFlag::DesignCodeOverlap { codes: vec!["05.07.13".into()] }]);
+        assert!(outcome.warnings.is_empty());
+
+        let query = SearchQuery::new("").with_owner("Acme Corp");
+        let candidates = vec![(make_record("002", "-", vec![]), 1.0)];
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert!(hits[0].flags.is_empty());
+        assert_eq!(hits[0].risk_score, 0.0);
+    }
+
+    #[test]
+    fn test_obfuscated_match() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let candidates = vec![
+            (make_record("001", "N1KE", vec![25]), 1.0),
+            (make_record("002", "7 ELEVEN", vec![25]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert!(hits[0].flags.contains(&RiskFlag::ObfuscatedMatch {
+            substitutions: vec!["1 -> I".to_string()]
+        }));
+        assert!(hits[0].risk_score >= 0.95);
+        assert!(!hits[1]
+            .flags
+            .iter()
+            .any(|f| matches!(f, RiskFlag::ObfuscatedMatch { .. })));
+    }
+
+    #[test]
+    fn test_number_word_match() {
+        let query = SearchQuery::new("2GO").with_classes(NiceClass::of_all(&[43]));
+        let candidates = vec![
+            (make_record("001", "TO GO", vec![43]), 1.0),
+            (make_record("002", "TOO GO CAFES", vec![43]), 1.0),
+            (make_record("003", "TOGA", vec![43]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert!(hits[0].flags.iter().any(|f| matches!(
+            f,
+            RiskFlag::NumberWordMatch { distance: 0, transformations } if transformations == &["TO -> 2"]
+        )));
+        // TOGA is one edit from TOGO; TOO GO CAFES is no match
+        assert!(hits[1].flags.iter().any(|f| matches!(
+            f,
+            RiskFlag::NumberWordMatch { distance: 1, .. }
+        )));
+        assert!(!hits[2].flags.iter().any(|f| matches!(f, RiskFlag::NumberWordMatch { .. })));
+
+        let query = SearchQuery::new("GO 4 IT");
+        let candidates = vec![(make_record("001", "GO FOR ITS", vec![]), 1.0)];
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert!(hits[0]
+            .flags
+            .iter()
+            .any(|f| matches!(f, RiskFlag::NumberWordMatch { distance: 1, .. })));
+    }
+
+    #[test]
+    fn test_inferred_class_discount() {
+        let query = SearchQuery::new("ZEPHYRA").with_classes(NiceClass::of_all(&[25]));
+        let mut inferred = make_record("002", "QUORLAND", vec![25]);
+        inferred.classes_inferred = true;
+        let candidates = vec![(make_record("001", "BLORVEX", vec![25]), 1.0), (inferred, 1.0)];
+        let config = RerankConfig::default();
+
+        let hits = rerank(&query, candidates, &config);
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert!((hits[0].risk_score - config.class_weight).abs() < 1e-6);
+        assert!(
+            (hits[1].risk_score - config.class_weight * config.inferred_class_factor).abs() < 1e-6
+        );
+        assert!(hits[1].flags.contains(&RiskFlag::ClassOverlap {
+            classes: NiceClass::of_all(&[25]),
+            inferred: true
+        }));
+    }
+
+    #[test]
+    fn test_related_classes() {
+        let query = SearchQuery::new("ZEPHYRA").with_classes(NiceClass::of_all(&[25]));
+        let candidates = vec![
+            (make_record("001", "BLORVEX", vec![35]), 1.0),
+            (make_record("002", "QUORLAND", vec![25, 35]), 1.0),
+            (make_record("003", "VANTRELL", vec![1]), 1.0),
+        ];
+        let config = RerankConfig::default();
+
+        let hits = rerank(&query, candidates, &config);
+        // Same class outranks related, and shared classes skip the grade
+        assert_eq!(hits[0].record.serial_number, "002");
+        assert!(!hits[0].flags.iter().any(|f| matches!(f, RiskFlag::RelatedClasses { .. })));
+        assert_eq!(
+            hits[1].flags,
+            vec![RiskFlag::RelatedClasses {
+                pairs: vec![(NiceClass::of(25), NiceClass::of(35))],
+                relatedness: 0.6
+            }]
+        );
+        assert!((hits[1].risk_score - config.related_class_weight * 0.6).abs() < 1e-6);
+        assert!(hits[2].flags.is_empty());
+    }
+
+    #[test]
+    fn test_prefix_match() {
+        let query = SearchQuery::new("ZEPHYRA");
+        let candidates = || {
+            vec![
+                (make_record("001", "ZEPHYROS", vec![]), 1.0),
+                (make_record("002", "XEPHYRA", vec![]), 1.0),
+            ]
+        };
+        let is_prefix = |f: &RiskFlag| {
+            matches!(f, RiskFlag::StringSimilarity { metric, .. } if metric == "prefix_weighted")
+        };
+
+        // Off unless the profile turns it on
+        let off = rerank(&query, candidates(), &RerankConfig::default());
+        assert!(off.iter().all(|hit| !hit.flags.iter().any(is_prefix)));
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(PREFIX_MATCH, true),
+            ..RerankConfig::default()
+        };
+        let on = rerank(&query, candidates(), &config);
+        let shared = on.iter().find(|hit| hit.record.serial_number == "001").unwrap();
+        let differing = on.iter().find(|hit| hit.record.serial_number == "002").unwrap();
+        assert!(shared.flags.iter().any(is_prefix));
+        assert!(!differing.flags.iter().any(is_prefix));
+
+        let before = off.iter().find(|hit| hit.record.serial_number == "001").unwrap();
+        assert!(shared.risk_score > before.risk_score);
+    }
+
+    #[test]
+    fn test_near_phonetic_match() {
+        let query = SearchQuery::new("TESLA");
+        let candidates = || {
+            vec![
+                (make_record("001", "TEZLAR", vec![]), 1.0),
+                (make_record("002", "NIKE", vec![]), 1.0),
+            ]
+        };
+        let is_near = |f: &RiskFlag| matches!(f, RiskFlag::NearPhoneticMatch { .. });
+
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        let phonemes = hits[0].flags.iter().find_map(|f| match f {
+            RiskFlag::NearPhoneticMatch { phonemes, .. } => Some(phonemes),
+            _ => None,
+        });
+        assert_eq!(phonemes.unwrap().1, "tɛzlær");
+        assert!(!hits[1].flags.iter().any(is_near));
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(NEAR_PHONETIC, false),
+            ..RerankConfig::default()
+        };
+        let off = rerank(&query, candidates(), &config);
+        assert!(!off[0].flags.iter().any(is_near));
+        assert!(off[0].risk_score < hits[0].risk_score);
+
+        // Two sounds apart in four is too far
+        let candidates = vec![(make_record("003", "OPAL", vec![]), 1.0)];
+        let hits = rerank(&SearchQuery::new("UBER"), candidates, &RerankConfig::default());
+        assert!(!hits[0].flags.iter().any(is_near));
+        let config = RerankConfig {
+            min_phoneme_similarity: 1.5,
+            ..RerankConfig::default()
+        };
+        assert_eq!(config.validate(), Err(RerankConfigError::MinPhonemeSimilarity(1.5)));
+    }
+
+    #[test]
+    fn test_mark_strength() {
+        let query = SearchQuery::new("BREWS");
+        let candidates = || {
+            vec![
+                (make_record("001", "BREW", vec![32]), 1.0),
+                (make_record("002", "BREW", vec![9]), 1.0),
+            ]
+        };
+        let risk = |hits: &[CandidateHit], serial: &str| {
+            hits.iter().find(|hit| hit.record.serial_number == serial).unwrap().risk_score
+        };
+
+        // BREW describes beer but not software
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert!(risk(&hits, "001") < risk(&hits, "002"));
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(MARK_STRENGTH, false),
+            ..RerankConfig::default()
+        };
+        let off = rerank(&query, candidates(), &config);
+        assert_eq!(risk(&off, "001"), risk(&off, "002"));
+        assert_eq!(risk(&off, "002"), risk(&hits, "002"));
+    }
+
+    #[test]
+    fn test_syllable_match() {
+        let query = SearchQuery::new("VITALITY");
+        let candidates = vec![
+            (make_record("001", "VITALITEA", vec![]), 1.0),
+            (make_record("002", "VOLATILE", vec![]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        let aligned = hits[0].flags.iter().find_map(|f| match f {
+            RiskFlag::SyllableMatch { aligned, .. } => Some(aligned),
+            _ => None,
+        });
+        assert_eq!(aligned.unwrap()[3], ("TY".to_string(), "TEA".to_string()));
+        assert!(!hits[1]
+            .flags
+            .iter()
+            .any(|f| matches!(f, RiskFlag::SyllableMatch { .. })));
+    }
+
+    #[test]
+    fn test_stemming() {
+        let query = SearchQuery::new("WIDGETS");
+        let is_stem = |f: &RiskFlag| {
+            matches!(f, RiskFlag::StringSimilarity { metric, .. } if metric == "stem_match")
+        };
+        let candidates = || vec![(make_record("001", "WIDGET", vec![]), 1.0)];
+
+        let plain = rerank(&query, candidates(), &RerankConfig::default());
+        assert!(!plain[0].flags.iter().any(is_stem));
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(STEMMING, true),
+            ..RerankConfig::default()
+        };
+        let stemmed = rerank(&query, candidates(), &config);
+        assert!(stemmed[0].flags.iter().any(is_stem));
+        assert!(stemmed[0].risk_score > plain[0].risk_score);
+    }
+
+    #[test]
+    fn test_compound_match() {
+        let query = SearchQuery::new("SUNPOWER").with_classes(NiceClass::of_all(&[9]));
+        let candidates = vec![
+            (make_record("001", "SUN POWER CO", vec![9]), 1.0),
+            (make_record("002", "SUN POWER SOLAR", vec![9]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert!(hits[0].flags.contains(&RiskFlag::CompoundMatch {
+            splits: vec!["SUNPOWER -> SUN POWER".to_string()],
+        }));
+        assert!(!hits[1]
+            .flags
+            .iter()
+            .any(|f| matches!(f, RiskFlag::CompoundMatch { .. })));
+    }
+
+    #[test]
+    fn test_foreign_equivalent() {
+        let query = SearchQuery::new("WOLF").with_classes(NiceClass::of_all(&[33]));
+        let candidates = vec![
+            (make_record("001", "LUPO", vec![33]), 1.0),
+            (make_record("002", "LUNA", vec![33]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert!(hits[0].flags.contains(&RiskFlag::ForeignEquivalent {
+            language: "Italian".to_string(),
+            translation: "WOLF".to_string(),
+        }));
+        assert!(!hits[1]
+            .flags
+            .iter()
+            .any(|f| matches!(f, RiskFlag::ForeignEquivalent { .. })));
+    }
+
+    #[test]
+    fn test_transliterated_marks() {
+        let query = SearchQuery::new("NIKA").with_classes(NiceClass::of_all(&[25]));
+        let mut filed = make_record("002", "小米", vec![25]);
+        filed.transliteration = Some("NIKA".to_string());
+        let candidates = vec![
+            (make_record("001", "НИКА", vec![25]), 1.0),
+            (filed, 1.0),
+            (make_record("003", "ナイキ", vec![25]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert!(hits[0].flags.contains(&RiskFlag::ExactMatch));
+        assert!(hits[1].flags.contains(&RiskFlag::ExactMatch));
+        assert!(!hits[2].flags.contains(&RiskFlag::ExactMatch));
+
+        // A non-Latin query is transliterated too
+        let query = SearchQuery::new("НИКЕ").with_classes(NiceClass::of_all(&[25]));
+        let candidates = vec![(make_record("001", "NIKE", vec![25]), 1.0)];
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert!(hits[0].flags.contains(&RiskFlag::ExactMatch));
+    }
+
     #[test]
     fn test_phonetic_match() {
-        let query = SearchQuery::new("NIKE").with_classes(vec![25]);
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
         let candidates = vec![(make_record("001", "NYKE", vec![25]), 1.0)];
         let config = RerankConfig::default();
 
@@ -171,13 +1294,566 @@ mod tests {
         assert!(hits[0].flags.iter().any(|f| matches!(f, RiskFlag::PhoneticMatch { .. })));
     }
 
+    #[test]
+    fn test_fuzzy_curve() {
+        use curves::SeverityCurve;
+
+        let query = SearchQuery::new("NIKE");
+        let candidates = || vec![(make_record("001", "NYKE", vec![]), 1.0)];
+
+        // Default linear curve: phonetic 0.3 + fuzzy 0.2 * 0.75
+        let config = RerankConfig::default();
+        assert_eq!(config.validate(), Ok(()));
+        let hits = rerank(&query, candidates(), &config);
+        assert!((hits[0].risk_score - 0.45).abs() < 1e-6);
+
+        // Distance 1 nearly as bad as exact
+        let mut config = RerankConfig::default();
+        config.curves.fuzzy = SeverityCurve::Step {
+            points: vec![(1.0, 0.95), (2.0, 0.5), (3.0, 0.05)],
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert!((hits[0].risk_score - 0.49).abs() < 1e-6);
+
+        config.curves.fuzzy = SeverityCurve::Step { points: vec![] };
+        assert!(matches!(config.validate(), Err(RerankConfigError::Curve(_))));
+    }
+
+    #[test]
+    fn test_status_weights() {
+        let query = SearchQuery::new("NIKE");
+        let mut dead = make_record("001", "NIKE", vec![]);
+        dead.status = TrademarkStatus::Dead;
+        let mut pending = make_record("002", "NIKE", vec![]);
+        pending.status = TrademarkStatus::Pending;
+        let candidates = || {
+            vec![
+                (dead.clone(), 1.0),
+                (pending.clone(), 1.0),
+                (make_record("003", "NIKE", vec![]), 1.0),
+            ]
+        };
+
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        let serials: Vec<_> = hits.iter().map(|h| h.record.serial_number.as_str()).collect();
+        assert_eq!(serials, vec!["003", "002", "001"]);
+        assert_eq!(hits[0].status_adjustment, None);
+        assert_eq!(hits[1].risk_score, 0.9);
+        assert_eq!(hits[2].risk_score, 0.2);
+        assert_eq!(hits[2].status_adjustment.unwrap().status, TrademarkStatus::Dead);
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(STATUS_WEIGHTS, false),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert!(hits.iter().all(|h| h.risk_score == 1.0 && h.status_adjustment.is_none()));
+    }
+
+    #[test]
+    fn test_dead_mark_age() {
+        let query = SearchQuery::new("ZEPHYRA");
+        let today = Date::parse_iso("2026-10-15").unwrap();
+        let dead = |serial: &str, days_ago: Option<i64>| {
+            let mut record = make_record(serial, "ZEPHYRA", vec![]);
+            record.status = TrademarkStatus::Dead;
+            record.status_date = days_ago.map(|days| today.add_days(-days));
+            (record, 1.0)
+        };
+        let candidates = || {
+            vec![
+                dead("001", Some(3650)),
+                dead("002", Some(61)),
+                dead("003", None),
+            ]
+        };
+
+        let dated = RerankConfig {
+            today: Some(today),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &dated);
+        let serials: Vec<_> = hits.iter().map(|h| h.record.serial_number.as_str()).collect();
+        assert_eq!(serials, vec!["002", "003", "001"]);
+        assert!(hits[0].risk_score > 0.7);
+        assert_eq!(hits[0].flags.last(), Some(&RiskFlag::RecentlyDead { months: 2 }));
+        // Without a status date the flat dead weight applies
+        assert_eq!(hits[1].risk_score, 0.2);
+        assert!(hits[2].risk_score < 0.06);
+        assert!(hits[2].flags.iter().all(|f| !matches!(f, RiskFlag::RecentlyDead { .. })));
+
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(DEAD_MARK_AGE, false),
+            ..dated
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert!(hits.iter().all(|h| h.risk_score == 0.2 && h.flags.len() == 1));
+
+        // Without a date to score as of, ages are unknown
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert!(hits.iter().all(|h| h.risk_score == 0.2 && h.flags.len() == 1));
+    }
+
+    #[test]
+    fn test_risk_model() {
+        let query = SearchQuery::new("NIKE").with_classes(NiceClass::of_all(&[25]));
+        let candidates = || {
+            vec![
+                (make_record("001", "NIKEEEE", vec![25]), 1.0),
+                (make_record("002", "NYKE", vec![9]), 1.0),
+                (make_record("003", "NIKE", vec![9]), 1.0),
+            ]
+        };
+        let model = RiskModel::from_json(
+            r#"{"type": "linear", "bias": 0.0, "weights": {"edit_distance": -1.0}}"#,
+        )
+        .unwrap();
+        let config = RerankConfig {
+            risk_model: Some(Arc::new(model)),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        // Exact matches keep their heuristic score
+        assert_eq!(hits[0].record.serial_number, "003");
+        assert_eq!(hits[0].risk_score, 1.0);
+        // The model ignores class overlap, unlike the heuristic scorer
+        assert_eq!(hits[1].record.serial_number, "002");
+        assert!((hits[1].risk_score - 1.0 / (1.0 + 1f32.exp())).abs() < 1e-6);
+        assert!((hits[2].risk_score - 1.0 / (1.0 + 3f32.exp())).abs() < 1e-6);
+
+        let heuristic = rerank(&query, candidates(), &RerankConfig::default());
+        assert_eq!(heuristic[1].record.serial_number, "001");
+
+        // Status is a model feature, not a multiplier on its probability,
+        // and the probability is reported as is
+        let model = RiskModel::from_json(
+            r#"{"type": "linear", "bias": -1.0, "weights": {"status_multiplier": 1.0}}"#,
+        )
+        .unwrap();
+        let config = RerankConfig {
+            risk_model: Some(Arc::new(model)),
+            calibration: Some(Calibration::Isotonic {
+                points: vec![(0.0, 0.05), (1.0, 0.8)],
+            }),
+            ..Default::default()
+        };
+        let mut dead = make_record("004", "NYKE", vec![25]);
+        dead.status = TrademarkStatus::Dead;
+        let candidates = vec![(make_record("001", "NYKE", vec![25]), 1.0), (dead, 1.0)];
+        let hits = rerank(&query, candidates, &config);
+        assert_eq!(hits[0].risk_score, 0.5);
+        assert_eq!(hits[0].risk_probability, Some(0.5));
+        let dead_weight = StatusWeights::default().dead;
+        assert!((hits[1].risk_score - 1.0 / (1.0 + (1.0 - dead_weight).exp())).abs() < 1e-6);
+        assert_eq!(hits[1].risk_probability, Some(hits[1].risk_score));
+        assert_eq!(hits[1].status_adjustment, None);
+    }
+
+    #[test]
+    fn test_tie_breaking() {
+        let query = SearchQuery::new("QUORLAND");
+        let mut dead = make_record("002", "QUORLAND", vec![]);
+        dead.status = TrademarkStatus::Dead;
+        let candidates = || {
+            vec![
+                (make_record("001", "QUORLAND", vec![]), 4.0),
+                (dead.clone(), 5.0),
+                (make_record("004", "QUORLAND", vec![]), 5.0),
+                (make_record("003", "QUORLAND", vec![]), 5.0),
+            ]
+        };
+        let config = RerankConfig {
+            behaviors: BehaviorToggles::default().with_override(STATUS_WEIGHTS, false),
+            ..Default::default()
+        };
+        let serials = |hits: &[CandidateHit]| -> Vec<String> {
+            hits.iter().map(|h| h.record.serial_number.clone()).collect()
+        };
+
+        let hits = rerank(&query, candidates(), &config);
+        assert!(hits.iter().all(|h| h.risk_score == hits[0].risk_score));
+        // Retrieval score, then live before dead, then serial number
+        assert_eq!(serials(&hits), vec!["003", "004", "002", "001"]);
+        let mut reversed = candidates();
+        reversed.reverse();
+        assert_eq!(serials(&rerank(&query, reversed, &config)), serials(&hits));
+
+        let config = RerankConfig {
+            hit_ordering: |a, b| a.record.serial_number.cmp(&b.record.serial_number),
+            ..config
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert_eq!(serials(&hits), vec!["001", "002", "003", "004"]);
+    }
+
+    #[test]
+    fn test_pruning() {
+        let query = SearchQuery::new("ZEPHYRA").with_classes(NiceClass::of_all(&[25]));
+        let candidates = || {
+            vec![
+                (make_record("001", "ZEPHYRA", vec![25]), 1.0),
+                (make_record("002", "ZEPHYRO", vec![25]), 1.0),
+                (make_record("003", "BLORVEX", vec![25]), 1.0),
+                (make_record("004", "QUORLAND", vec![1]), 1.0),
+            ]
+        };
+
+        let outcome = rerank_outcome(&query, candidates(), &RerankConfig::default());
+        assert_eq!((outcome.hits.len(), outcome.pruned), (4, 0));
+
+        let config = RerankConfig {
+            min_risk_score: 0.3,
+            ..Default::default()
+        };
+        let outcome = rerank_outcome(&query, candidates(), &config);
+        let serials: Vec<_> =
+            outcome.hits.iter().map(|h| h.record.serial_number.as_str()).collect();
+        assert_eq!(serials, vec!["001", "002"]);
+        assert_eq!(outcome.pruned, 2);
+
+        let config = RerankConfig {
+            max_results: Some(1),
+            ..config
+        };
+        let outcome = rerank_outcome(&query, candidates(), &config);
+        assert_eq!(outcome.hits[0].record.serial_number, "001");
+        assert_eq!(outcome.pruned, 3);
+
+        let config = RerankConfig {
+            max_results: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(config.validate(), Err(RerankConfigError::NoResults));
+        let config = RerankConfig {
+            min_risk_score: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(config.validate(), Err(RerankConfigError::MinRiskScore(1.5)));
+    }
+
+    #[test]
+    fn test_retrieval_blending() {
+        let query = SearchQuery::new("ZEPHYRA");
+        let candidates = || {
+            vec![
+                (make_record("001", "BLORVEX", vec![]), 4000.0),
+                (make_record("002", "QUORLAND", vec![]), 1000.0),
+                (make_record("003", "ZEPHYRA", vec![]), 2000.0),
+            ]
+        };
+
+        // Off by default: relevance only breaks ties
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert_eq!(hits[1].record.serial_number, "001");
+        assert_eq!(hits[1].risk_score, 0.0);
+
+        let config = RerankConfig {
+            retrieval_weight: 0.2,
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert_eq!(hits[0].risk_score, 1.0);
+        assert_eq!(hits[1].risk_score, 0.2);
+        assert_eq!(hits[2].risk_score, 0.05);
+        assert_eq!(hits[1].retrieval_score, 4000.0);
+
+        let config = RerankConfig {
+            retrieval_normalization: Some(ScoreNormalization::Saturating { half: 4000.0 }),
+            ..config
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert_eq!(hits[1].risk_score, 0.1);
+        assert_eq!(config.validate(), Ok(()));
+
+        let config = RerankConfig {
+            retrieval_normalization: Some(ScoreNormalization::Saturating { half: 0.0 }),
+            ..config
+        };
+        assert_eq!(config.validate(), Err(RerankConfigError::RetrievalSaturation(0.0)));
+    }
+
+    #[test]
+    fn test_calibration() {
+        let query = SearchQuery::new("NIKE");
+        let candidates = || vec![(make_record("001", "NIKE", vec![]), 1.0)];
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert_eq!(hits[0].risk_probability, None);
+
+        let config = RerankConfig {
+            calibration: Some(Calibration::Isotonic {
+                points: vec![(0.0, 0.05), (1.0, 0.8)],
+            }),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert_eq!(hits[0].risk_score, 1.0);
+        assert_eq!(hits[0].risk_probability, Some(0.8));
+
+        let config = RerankConfig {
+            calibration: Some(Calibration::Isotonic { points: Vec::new() }),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(RerankConfigError::Calibration(CalibrationError::Empty))
+        );
+    }
+
+    #[test]
+    fn test_validate_weights() {
+        let config = RerankConfig {
+            fuzzy_weight: -0.1,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(RerankConfigError::NegativeWeight { name: "fuzzy_weight", value: -0.1 })
+        );
+
+        let config = RerankConfig {
+            goods_weight: f32::NAN,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(RerankConfigError::NegativeWeight { .. })));
+
+        let config = RerankConfig {
+            class_weight: 1.5,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(RerankConfigError::WeightTooHigh { .. })));
+
+        let zeroed: serde_json::Map<String, serde_json::Value> = RerankConfig::default()
+            .weights()
+            .iter()
+            .map(|(name, _)| (name.to_string(), 0.0.into()))
+            .collect();
+        let config: RerankConfig = serde_json::from_value(zeroed.into()).unwrap();
+        assert_eq!(config.validate(), Err(RerankConfigError::NoWeights));
+    }
+
+    #[test]
+    fn test_phonetic_encoder_selection() {
+        use ilegalflow_features::PhoneticAlgorithm;
+
+        let query = SearchQuery::new("SCHMIDT");
+        let candidates = || vec![(make_record("001", "SCHMITT", vec![]), 1.0)];
+        let config = RerankConfig {
+            phonetic: PhoneticRegistry::from_algorithms(&[PhoneticAlgorithm::Cologne]),
+            ..Default::default()
+        };
+
+        let hits = rerank(&query, candidates(), &config);
+        assert!(hits[0].flags.iter().any(
+            |f| matches!(f, RiskFlag::PhoneticMatch { algorithm, .. } if algorithm == "cologne")
+        ));
+
+        let config = RerankConfig {
+            phonetic: PhoneticRegistry::empty(),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert!(!hits[0].flags.iter().any(|f| matches!(f, RiskFlag::PhoneticMatch { .. })));
+    }
+
+    #[test]
+    fn test_string_similarity() {
+        let query = SearchQuery::new("MICROSOFTWARE");
+        let candidates = vec![
+            (make_record("001", "MICROSOFTWEAR", vec![]), 1.0),
+            (make_record("002", "NYKE", vec![]), 1.0),
+        ];
+        let config = RerankConfig::default();
+
+        let hits = rerank(&query, candidates, &config);
+        assert!(hits[0].flags.iter().any(|f| matches!(
+            f,
+            RiskFlag::StringSimilarity { metric, score } if metric == "jaro_winkler" && *score > 0.95
+        )));
+        assert!(!hits[1].flags.iter().any(|f| matches!(f, RiskFlag::StringSimilarity { .. })));
+
+        let config = RerankConfig {
+            similarity_threshold: 0.99,
+            ..Default::default()
+        };
+        let candidates = vec![(make_record("001", "MICROSOFTWEAR", vec![]), 1.0)];
+        let hits = rerank(&query, candidates, &config);
+        assert!(!hits[0].flags.iter().any(|f| matches!(f, RiskFlag::StringSimilarity { .. })));
+    }
+
+    #[test]
+    fn test_ngram_similarity() {
+        let query = SearchQuery::new("COCACOLA");
+        let candidates = vec![
+            (make_record("001", "COCA COLA", vec![]), 1.0),
+            (make_record("002", "PEPSI", vec![]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        assert!(hits[0].flags.iter().any(|f| matches!(
+            f,
+            RiskFlag::StringSimilarity { metric, score } if metric == "ngram_cosine" && *score > 0.99
+        )));
+        assert!(hits[1].flags.is_empty());
+    }
+
+    #[test]
+    fn test_word_reordering() {
+        let query = SearchQuery::new("BLUE SKY BREWING");
+        let candidates = vec![
+            (make_record("001", "SKY BLUE BREWERY", vec![]), 1.0),
+            (make_record("002", "BLUE SKY BREWING COMPANY", vec![]), 1.0),
+        ];
+
+        let hits = rerank(&query, candidates, &RerankConfig::default());
+        let reordered = hits
+            .iter()
+            .find(|hit| hit.record.serial_number == "001")
+            .unwrap();
+        assert!(reordered.flags.iter().any(|f| matches!(
+            f,
+            RiskFlag::StringSimilarity { metric, .. } if metric == "token_sort_ratio"
+        )));
+
+        // Same order: not a reordering match
+        let extended = hits
+            .iter()
+            .find(|hit| hit.record.serial_number == "002")
+            .unwrap();
+        assert!(!extended.flags.iter().any(|f| matches!(
+            f,
+            RiskFlag::StringSimilarity { metric, .. } if metric == "token_sort_ratio"
+        )));
+    }
+
     #[test]
     fn test_class_overlap() {
-        let query = SearchQuery::new("ACME").with_classes(vec![9, 42]);
+        let query = SearchQuery::new("ACME").with_classes(NiceClass::of_all(&[9, 42]));
         let candidates = vec![(make_record("001", "WIDGET", vec![42, 35]), 1.0)];
         let config = RerankConfig::default();
 
         let hits = rerank(&query, candidates, &config);
-        assert!(hits[0].flags.iter().any(|f| matches!(f, RiskFlag::ClassOverlap { classes } if classes.contains(&42))));
+        let computers = NiceClass::of(42);
+        assert!(hits[0].flags.iter().any(
+            |f| matches!(f, RiskFlag::ClassOverlap { classes, .. } if classes.contains(&computers))
+        ));
+    }
+
+    #[test]
+    fn test_goods_services_similarity() {
+        let query = SearchQuery::new("SWOOSHY").with_goods_services("Athletic footwear");
+        let mut shoes = make_record("001", "ZEPHYR", vec![25]);
+        shoes.goods_services =
+            "Clothing, namely, shirts, pants, jackets, footwear, hats and caps, athletic uniforms"
+                .to_string();
+        let mut software = make_record("002", "ZEPHYR", vec![9]);
+        software.goods_services =
+            "Downloadable computer software for use in database management".to_string();
+
+        let hits = rerank(&query, vec![(shoes, 1.0), (software, 1.0)], &RerankConfig::default());
+        assert_eq!(hits[0].record.serial_number, "001");
+        assert!(matches!(
+            hits[0].flags.as_slice(),
+            [RiskFlag::GoodsServicesSimilar { similarity }] if *similarity > 0.3
+        ));
+        assert!(hits[1].flags.is_empty());
+
+        // No goods in the query, no goods comparison
+        let hits = rerank(
+            &SearchQuery::new("SWOOSHY"),
+            vec![(make_record("001", "ZEPHYR", vec![25]), 1.0)],
+            &RerankConfig::default(),
+        );
+        assert!(hits[0].flags.is_empty());
+    }
+
+    #[cfg(feature = "embeddings")]
+    #[test]
+    fn test_semantic_goods_similarity() {
+        use ilegalflow_features::PrecomputedEmbeddings;
+
+        let mut embeddings = PrecomputedEmbeddings::new();
+        embeddings.insert("Footwear", vec![0.9, 0.1, 0.0]).unwrap();
+        embeddings.insert("Sneakers", vec![0.8, 0.2, 0.1]).unwrap();
+
+        let query = SearchQuery::new("SWOOSHY").with_goods_services("Footwear");
+        let mut record = make_record("001", "ZEPHYR", vec![25]);
+        record.goods_services = "Sneakers".to_string();
+        let candidates = || vec![(record.clone(), 1.0)];
+
+        // No shared words, so nothing lexically
+        let hits = rerank(&query, candidates(), &RerankConfig::default());
+        assert!(hits[0].flags.is_empty());
+
+        let config = RerankConfig {
+            embedder: Some(Arc::new(embeddings)),
+            ..Default::default()
+        };
+        let hits = rerank(&query, candidates(), &config);
+        assert!(matches!(
+            hits[0].flags.as_slice(),
+            [RiskFlag::GoodsServicesSimilar { similarity }] if *similarity > 0.95
+        ));
+    }
+
+    #[test]
+    fn test_design_code_overlap() {
+        let mut query = SearchQuery::new("APPLE");
+        query.design_codes = vec!["05.07".to_string()];
+        let mut record = make_record("001", "ORCHARD", vec![9]);
+        record.design_codes = vec!["05.07.13".to_string(), "26.01.03".to_string()];
+
+        let hits = rerank(&query, vec![(record, 1.0)], &RerankConfig::default());
+        assert!(hits[0].flags.contains(&RiskFlag::DesignCodeOverlap {
+            codes: vec!["05.07.13".to_string()]
+        }));
+    }
+
+    #[test]
+    fn test_dominant_element_across_word_boundaries() {
+        let query = SearchQuery::new("KWIKPRINT");
+        let candidates = vec![(make_record("001", "QUICK PRINT SOLUTIONS", vec![40]), 1.0)];
+        let config = RerankConfig::default();
+
+        let hits = rerank(&query, candidates, &config);
+        let flag = hits[0]
+            .flags
+            .iter()
+            .find(|f| matches!(f, RiskFlag::DominantTermMatch { .. }))
+            .unwrap();
+        assert!(matches!(
+            flag,
+            RiskFlag::DominantTermMatch { term, transformations }
+                if term == "QUICKPRINT" && transformations.len() == 3
+        ));
+    }
+
+    #[test]
+    fn test_short_mark_guards() {
+        let query = SearchQuery::new("AB");
+        let candidates = || {
+            vec![
+                (make_record("001", "AC", vec![]), 1.0),
+                (make_record("002", "XY", vec![]), 1.0),
+            ]
+        };
+
+        let config = RerankConfig {
+            guards: RuleGuards::short_marks(),
+            ..Default::default()
+        };
+        let outcome = rerank_outcome(&query, candidates(), &config);
+        assert!(outcome.hits.iter().all(|hit| hit.flags.is_empty()));
+        let fuzzy = outcome
+            .warnings
+            .iter()
+            .find(|w| w.rule == Rule::Fuzzy)
+            .unwrap();
+        assert_eq!(fuzzy.skipped, 2);
+        assert_eq!(fuzzy.reason, "query mark shorter than 4 characters");
+
+        // Guards are off by default, and AB and AC are one edit apart
+        let outcome = rerank_outcome(&query, candidates(), &RerankConfig::default());
+        assert!(outcome.warnings.is_empty());
+        assert!(outcome.hits[0].flags.contains(&RiskFlag::FuzzyMatch { distance: 1 }));
     }
 }
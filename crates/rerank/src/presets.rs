@@ -0,0 +1,108 @@
+//! Preset re-ranker configurations per use case.
+//!
+//! Integrators pick a preset by name instead of tuning weights:
+//!
+//! - `knockout`: a quick screen before filing. At most one edit, strict
+//!   similarity thresholds, and no n-gram, syllable, prefix, goods,
+//!   related-class or dilution scoring.
+//! - `balanced`: everyday clearance; the default weights, up to three
+//!   edits.
+//! - `comprehensive`: a full clearance opinion. Up to four edits, lower
+//!   thresholds, stemming, more dominant terms, and heavier
+//!   near-phonetic and related-class weights.
+//!
+//! A configuration file can start from a preset with `preset = "..."`
+//! (see `profiles`).
+
+use crate::behaviors::{BehaviorToggles, STEMMING};
+use crate::curves::SeverityCurves;
+use crate::RerankConfig;
+
+/// Names of the presets, from narrowest to broadest.
+pub const PRESETS: &[&str] = &["knockout", "balanced", "comprehensive"];
+
+impl RerankConfig {
+    /// Near-identical marks only: one edit at most, strict similarity
+    /// thresholds, and no rules that score loose resemblance or the
+    /// goods alone.
+    pub fn knockout() -> Self {
+        Self {
+            max_edit_distance: 1,
+            curves: SeverityCurves::linear(1),
+            max_phoneme_distance: 1.0,
+            similarity_threshold: 0.95,
+            token_sort_threshold: 0.9,
+            near_phonetic_weight: 0.1,
+            prefix_weight: 0.0,
+            syllable_weight: 0.0,
+            ngram_weight: 0.0,
+            goods_weight: 0.0,
+            related_class_weight: 0.0,
+            dilution_weight: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// The default weights, for everyday clearance searches.
+    pub fn balanced() -> Self {
+        Self::default()
+    }
+
+    /// Everything plausibly confusing: up to four edits, lower
+    /// thresholds, stemming, and heavier weights on sound and related
+    /// classes.
+    pub fn comprehensive() -> Self {
+        Self {
+            max_edit_distance: 4,
+            curves: SeverityCurves::linear(4),
+            behaviors: BehaviorToggles::default().with_override(STEMMING, true),
+            max_phoneme_distance: 3.0,
+            min_phoneme_similarity: 0.6,
+            near_phonetic_weight: 0.25,
+            similarity_threshold: 0.85,
+            token_sort_threshold: 0.7,
+            syllable_threshold: 0.7,
+            ngram_threshold: 0.7,
+            goods_threshold: 0.2,
+            related_class_weight: 0.2,
+            dominant_terms: 3,
+            dilution_threshold: 0.2,
+            common_law_threshold: 0.3,
+            ..Self::default()
+        }
+    }
+
+    /// A preset by name (see [`PRESETS`]).
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "knockout" => Some(Self::knockout()),
+            "balanced" => Some(Self::balanced()),
+            "comprehensive" => Some(Self::comprehensive()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rerank;
+    use ilegalflow_model::{SearchQuery, TrademarkRecord};
+
+    #[test]
+    fn test_presets() {
+        for name in PRESETS {
+            let config = RerankConfig::preset(name).unwrap();
+            assert_eq!(config.validate(), Ok(()), "{}", name);
+        }
+        assert!(RerankConfig::preset("Knockout").is_some());
+        assert!(RerankConfig::preset("aggressive").is_none());
+
+        // Two edits away: only broader presets flag the spelling
+        let query = SearchQuery::new("ZEPHYRA");
+        let candidates = || vec![(TrademarkRecord::new("001", "ZAPHYRO"), 1.0)];
+        let score = |config: &RerankConfig| rerank(&query, candidates(), config)[0].risk_score;
+        assert!(score(&RerankConfig::knockout()) < score(&RerankConfig::balanced()));
+        assert!(score(&RerankConfig::balanced()) <= score(&RerankConfig::comprehensive()));
+    }
+}
@@ -0,0 +1,348 @@
+//! Re-ranker configuration files with named profiles.
+//!
+//! A TOML or YAML file sets any `RerankConfig` field by name. Settings at
+//! the top level are shared; each table under `profiles` (`knockout`,
+//! `full-clearance`, `aggressive`, ...) overrides them for one profile,
+//! and `default_profile` names the profile used when none is asked for:
+//!
+//! ```toml
+//! default_profile = "full-clearance"
+//! phonetic = ["soundex", "metaphone"]
+//!
+//! [profiles.full-clearance]
+//! fuzzy_weight = 0.25
+//!
+//! [profiles.knockout]
+//! max_edit_distance = 1
+//! ```
+//!
+//! A `preset` key, at the top level or in a profile, starts from a preset
+//! configuration (see `presets`) instead of the defaults.
+//!
+//! Fields left out keep their defaults, and unless curves are given the
+//! fuzzy curve follows `max_edit_distance`. Custom detectors, the embedder,
+//! confusables, translations and corpus term frequencies are set in code
+//! or by their own loaders, not in the file.
+
+use crate::curves::SeverityCurves;
+use crate::presets::PRESETS;
+use crate::{RerankConfig, RerankConfigError};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Key naming the profile used by default.
+const DEFAULT_PROFILE_KEY: &str = "default_profile";
+/// Key of the table of named profiles.
+const PROFILES_KEY: &str = "profiles";
+/// Key naming the preset settings start from.
+const PRESET_KEY: &str = "preset";
+
+/// Syntax of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Format implied by a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Errors loading a re-ranker configuration file.
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Unsupported config file '{0}' (expected .toml, .yaml or .yml)")]
+    Format(String),
+
+    #[error("Invalid rerank config: {0}")]
+    Parse(String),
+
+    #[error("Unknown rerank profile '{name}' (available: {available})")]
+    UnknownProfile { name: String, available: String },
+
+    #[error("Unknown rerank preset '{0}' (available: {available})", available = PRESETS.join(", "))]
+    UnknownPreset(String),
+
+    #[error("Invalid rerank profile '{profile}': {source}")]
+    Invalid {
+        profile: String,
+        #[source]
+        source: RerankConfigError,
+    },
+}
+
+/// The shared settings and named profiles of a configuration file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RerankProfiles {
+    base: Map<String, Value>,
+    profiles: BTreeMap<String, Map<String, Value>>,
+    default_profile: Option<String>,
+}
+
+impl RerankProfiles {
+    /// Read a TOML or YAML file, by its extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)
+            .ok_or_else(|| ProfileError::Format(path.display().to_string()))?;
+        let text = std::fs::read_to_string(path).map_err(|source| ProfileError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::parse(&text, format)
+    }
+
+    /// Parse the text of a configuration file.
+    pub fn parse(text: &str, format: ConfigFormat) -> Result<Self, ProfileError> {
+        let value: Value = match format {
+            ConfigFormat::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+        }
+        .map_err(ProfileError::Parse)?;
+        let mut base = match value {
+            Value::Object(map) => map,
+            // An empty YAML document
+            Value::Null => Map::new(),
+            _ => return Err(ProfileError::Parse("expected a table of settings".to_string())),
+        };
+
+        let default_profile = match base.remove(DEFAULT_PROFILE_KEY) {
+            None => None,
+            Some(Value::String(name)) => Some(name),
+            Some(_) => {
+                return Err(ProfileError::Parse(format!("{} must be a string", DEFAULT_PROFILE_KEY)))
+            }
+        };
+        let profiles = match base.remove(PROFILES_KEY) {
+            None => BTreeMap::new(),
+            Some(Value::Object(profiles)) => profiles
+                .into_iter()
+                .map(|(name, settings)| match settings {
+                    Value::Object(settings) => Ok((name, settings)),
+                    _ => Err(ProfileError::Parse(format!("profile '{}' must be a table", name))),
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(ProfileError::Parse(format!("{} must be a table", PROFILES_KEY))),
+        };
+
+        let profiles = Self {
+            base,
+            profiles,
+            default_profile,
+        };
+        if let Some(name) = &profiles.default_profile {
+            profiles.settings(name)?;
+        }
+        Ok(profiles)
+    }
+
+    /// Names of the profiles, in order.
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
+    }
+
+    /// Profile used when none is named, if the file names one.
+    pub fn default_profile(&self) -> Option<&str> {
+        self.default_profile.as_deref()
+    }
+
+    /// The configuration of a profile: the named one, else the default
+    /// profile, else the shared settings alone. The result is validated.
+    pub fn config(&self, profile: Option<&str>) -> Result<RerankConfig, ProfileError> {
+        let profile = profile.or(self.default_profile.as_deref());
+        let mut settings = self.base.clone();
+        if let Some(name) = profile {
+            merge(&mut settings, self.settings(name)?);
+        }
+
+        let own_curves = settings.contains_key("curves");
+        if let Some(preset) = settings.remove(PRESET_KEY) {
+            let Some(name) = preset.as_str() else {
+                return Err(ProfileError::Parse(format!("{} must be a string", PRESET_KEY)));
+            };
+            let preset = RerankConfig::preset(name)
+                .ok_or_else(|| ProfileError::UnknownPreset(name.to_string()))?;
+            let Value::Object(mut preset) =
+                serde_json::to_value(preset).map_err(|e| ProfileError::Parse(e.to_string()))?
+            else {
+                unreachable!("configs serialize to a table");
+            };
+            merge(&mut preset, &settings);
+            settings = preset;
+        }
+        let mut config: RerankConfig = serde_json::from_value(Value::Object(settings))
+            .map_err(|e| ProfileError::Parse(e.to_string()))?;
+        // Default curves follow the maximum edit distance
+        if !own_curves {
+            config.curves = SeverityCurves::linear(config.max_edit_distance);
+        }
+        config.validate().map_err(|source| ProfileError::Invalid {
+            profile: profile.unwrap_or("(shared)").to_string(),
+            source,
+        })?;
+        Ok(config)
+    }
+
+    fn settings(&self, name: &str) -> Result<&Map<String, Value>, ProfileError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ProfileError::UnknownProfile {
+                name: name.to_string(),
+                available: self.names().join(", "),
+            })
+    }
+}
+
+/// Overlay `overrides` on `settings`, merging nested tables.
+fn merge(settings: &mut Map<String, Value>, overrides: &Map<String, Value>) {
+    for (key, value) in overrides {
+        match (settings.get_mut(key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => merge(existing, nested),
+            _ => {
+                settings.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl RerankConfig {
+    /// Load a configuration from a TOML or YAML file, using `profile`
+    /// or else the file's default profile (see [`RerankProfiles`]).
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        profile: Option<&str>,
+    ) -> Result<Self, ProfileError> {
+        RerankProfiles::from_file(path)?.config(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behaviors::STEMMING;
+    use ilegalflow_features::PhoneticAlgorithm;
+
+    const TOML: &str = r#"
+default_profile = "full-clearance"
+phonetic = ["soundex", "cologne"]
+fuzzy_weight = 0.3
+
+[guards.fuzzy]
+min_mark_len = 4
+
+[profiles.full-clearance]
+similarity_weight = 0.2
+
+[profiles.knockout]
+max_edit_distance = 1
+fuzzy_weight = 0.1
+
+[profiles.knockout.guards.fuzzy]
+alphabetic_only = true
+"#;
+
+    #[test]
+    fn test_profiles() {
+        let profiles = RerankProfiles::parse(TOML, ConfigFormat::Toml).unwrap();
+        assert_eq!(profiles.names(), vec!["full-clearance", "knockout"]);
+
+        let full = profiles.config(None).unwrap();
+        assert_eq!(full.similarity_weight, 0.2);
+        assert_eq!(full.fuzzy_weight, 0.3);
+        assert_eq!(full.phonetic.names(), vec!["soundex", "cologne"]);
+        assert_eq!(full.max_edit_distance, RerankConfig::default().max_edit_distance);
+
+        // Nested tables merge with the shared settings
+        let knockout = profiles.config(Some("knockout")).unwrap();
+        assert_eq!(knockout.fuzzy_weight, 0.1);
+        assert_eq!(knockout.max_edit_distance, 1);
+        assert_eq!(knockout.curves, SeverityCurves::linear(1));
+        assert_eq!(knockout.guards.fuzzy.min_mark_len, 4);
+        assert!(knockout.guards.fuzzy.alphabetic_only);
+
+        assert!(matches!(
+            profiles.config(Some("aggressive")),
+            Err(ProfileError::UnknownProfile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_yaml() {
+        let yaml = "phonetic: [nysiis]\nprofiles:\n  aggressive:\n    fuzzy_weight: 0.4\n";
+        let profiles = RerankProfiles::parse(yaml, ConfigFormat::Yaml).unwrap();
+        let config = profiles.config(Some("aggressive")).unwrap();
+        assert_eq!(config.fuzzy_weight, 0.4);
+        assert_eq!(
+            config.phonetic.names(),
+            vec![PhoneticAlgorithm::Nysiis.as_str()]
+        );
+        // No profile named and no default: the shared settings
+        assert_eq!(profiles.config(None).unwrap().fuzzy_weight, 0.2);
+
+        let empty = RerankProfiles::parse("", ConfigFormat::Yaml).unwrap();
+        assert_eq!(empty.config(None).unwrap().fuzzy_weight, 0.2);
+    }
+
+    #[test]
+    fn test_preset() {
+        let text = "preset = \"comprehensive\"\nfuzzy_weight = 0.3\n\n\
+                    [profiles.screen]\npreset = \"knockout\"\n";
+        let profiles = RerankProfiles::parse(text, ConfigFormat::Toml).unwrap();
+
+        let config = profiles.config(None).unwrap();
+        assert_eq!(config.max_edit_distance, 4);
+        assert_eq!(config.behaviors.overrides.get(STEMMING), Some(&true));
+        assert_eq!(config.fuzzy_weight, 0.3);
+
+        // The profile's preset replaces the shared one; shared fields stay
+        let config = profiles.config(Some("screen")).unwrap();
+        assert_eq!(config.max_edit_distance, 1);
+        assert_eq!(config.ngram_weight, 0.0);
+        assert_eq!(config.fuzzy_weight, 0.3);
+
+        let profiles = RerankProfiles::parse("preset = \"wide\"", ConfigFormat::Toml).unwrap();
+        assert!(matches!(profiles.config(None), Err(ProfileError::UnknownPreset(_))));
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let parse = |text| RerankProfiles::parse(text, ConfigFormat::Toml);
+        assert!(matches!(parse("fuzzy_wieght = 0.3"), Ok(p) if p.config(None).is_err()));
+        assert!(matches!(
+            parse("default_profile = \"x\""),
+            Err(ProfileError::UnknownProfile { .. })
+        ));
+        assert!(matches!(parse("profiles = 3"), Err(ProfileError::Parse(_))));
+        assert!(matches!(
+            parse("fuzzy_weight = -0.5").unwrap().config(None),
+            Err(ProfileError::Invalid { .. })
+        ));
+        assert!(matches!(
+            parse("[severities]\nfamous_mark = 1.5").unwrap().config(None),
+            Err(ProfileError::Invalid { .. })
+        ));
+        let config = parse("[severities]\nrecently_dead = 0.5").unwrap().config(None).unwrap();
+        assert_eq!(config.severities.recently_dead, 0.5);
+        assert_eq!(config.severities.exact_match, 1.0);
+        assert!(matches!(
+            RerankConfig::from_file("profile.ini", None),
+            Err(ProfileError::Format(_))
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+//! Risk multipliers by record status.
+//!
+//! A live registration blocks a new mark; a cancelled or abandoned one
+//! rarely does, though it may be revived or still be in use. A pending
+//! application takes priority from its filing date if it registers, so
+//! it is weighed nearly as a live mark.
+//!
+//! How long ago a mark died matters too: one abandoned last month may be
+//! revived or still carry goodwill, one dead for ten years rarely
+//! matters. With a status date, a dead mark's multiplier decays from
+//! near live to a floor instead of the flat dead weight.
+
+use ilegalflow_model::{Date, StatusAdjustment, TrademarkStatus};
+use serde::{Deserialize, Serialize};
+
+/// Average days in a month.
+const DAYS_PER_MONTH: f32 = 30.44;
+
+/// Multiplier applied to the risk score for each record status.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusWeights {
+    pub live: f32,
+    pub pending: f32,
+    pub dead: f32,
+    /// Status not reported; kept at full weight to stay conservative
+    pub unknown: f32,
+}
+
+impl Default for StatusWeights {
+    fn default() -> Self {
+        Self {
+            live: 1.0,
+            pending: 0.9,
+            dead: 0.2,
+            unknown: 1.0,
+        }
+    }
+}
+
+impl StatusWeights {
+    /// Multiplier for a status.
+    pub fn multiplier(&self, status: TrademarkStatus) -> f32 {
+        match status {
+            TrademarkStatus::Live => self.live,
+            TrademarkStatus::Pending => self.pending,
+            TrademarkStatus::Dead => self.dead,
+            // Unknown, and statuses added after this build
+            _ => self.unknown,
+        }
+    }
+
+    /// The adjustment for a status, if it changes the score.
+    pub fn adjustment(&self, status: TrademarkStatus) -> Option<StatusAdjustment> {
+        let multiplier = self.multiplier(status);
+        (multiplier != 1.0).then_some(StatusAdjustment { status, multiplier })
+    }
+
+    /// Every multiplier, by field name.
+    pub(crate) fn entries(&self) -> [(&'static str, f32); 4] {
+        [
+            ("status_weights.live", self.live),
+            ("status_weights.pending", self.pending),
+            ("status_weights.dead", self.dead),
+            ("status_weights.unknown", self.unknown),
+        ]
+    }
+}
+
+/// Decay of a dead mark's multiplier with the months since it died.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeadMarkDecay {
+    /// Multiplier for a mark that died today
+    pub initial: f32,
+    /// Multiplier long after death
+    pub floor: f32,
+    /// Months for the multiplier to fall halfway to the floor
+    pub half_life_months: f32,
+    /// Marks dead at most this many months are flagged as recently dead
+    pub recent_months: u32,
+}
+
+impl Default for DeadMarkDecay {
+    fn default() -> Self {
+        Self {
+            initial: 0.8,
+            floor: 0.05,
+            half_life_months: 12.0,
+            recent_months: 24,
+        }
+    }
+}
+
+impl DeadMarkDecay {
+    /// Multiplier for a mark dead for `months`.
+    pub fn multiplier(&self, months: f32) -> f32 {
+        let decayed = 0.5_f32.powf(months.max(0.0) / self.half_life_months);
+        self.floor + (self.initial - self.floor) * decayed
+    }
+
+    /// Whether the multipliers are non-negative and the half-life
+    /// positive.
+    pub fn is_valid(&self) -> bool {
+        self.initial >= 0.0
+            && self.floor >= 0.0
+            && self.half_life_months.is_finite()
+            && self.half_life_months > 0.0
+    }
+}
+
+/// Months from a status date to `today`. Future dates count as zero.
+pub fn months_since(date: Date, today: Date) -> f32 {
+    (today.days() - date.days()).max(0) as f32 / DAYS_PER_MONTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjustment() {
+        let weights = StatusWeights::default();
+        assert_eq!(weights.adjustment(TrademarkStatus::Live), None);
+        assert_eq!(weights.adjustment(TrademarkStatus::Unknown), None);
+        assert_eq!(
+            weights.adjustment(TrademarkStatus::Dead),
+            Some(StatusAdjustment {
+                status: TrademarkStatus::Dead,
+                multiplier: 0.2,
+            })
+        );
+        assert_eq!(weights.multiplier(TrademarkStatus::Pending), 0.9);
+    }
+
+    #[test]
+    fn test_dead_mark_decay() {
+        let decay = DeadMarkDecay::default();
+        assert!((decay.multiplier(0.0) - 0.8).abs() < 1e-6);
+        assert!((decay.multiplier(12.0) - 0.425).abs() < 1e-6);
+        assert!(decay.multiplier(120.0) < 0.06);
+
+        let date = |s: &str| s.parse::<Date>().unwrap();
+        let today = date("2026-10-15");
+        assert_eq!(months_since(date("2026-10-15"), today), 0.0);
+        assert_eq!(months_since(date("2027-01-01"), today), 0.0);
+        let months = months_since(date("2016-10-15T00:00:00Z"), today);
+        assert!((months - 120.0).abs() < 0.1, "{}", months);
+    }
+}